@@ -11,6 +11,9 @@ pub fn borrow<'a>(
     pool_market: AccountInfo<'a>,
     pool_market_authority: AccountInfo<'a>,
     pool: AccountInfo<'a>,
+    registry: AccountInfo<'a>,
+    global_pause: AccountInfo<'a>,
+    pool_pause: AccountInfo<'a>,
     pool_borrow_authority: AccountInfo<'a>,
     destination: AccountInfo<'a>,
     token_account: AccountInfo<'a>,
@@ -22,6 +25,7 @@ pub fn borrow<'a>(
         &crate::id(),
         pool_market.key,
         pool.key,
+        registry.key,
         pool_borrow_authority.key,
         destination.key,
         token_account.key,
@@ -34,6 +38,9 @@ pub fn borrow<'a>(
         &[
             pool_market,
             pool,
+            registry,
+            global_pause,
+            pool_pause,
             pool_borrow_authority,
             pool_market_authority,
             destination,
@@ -98,6 +105,7 @@ pub fn deposit<'a>(
         &crate::id(),
         collateral_pool_accounts.pool_market.key,
         collateral_pool_accounts.pool.key,
+        collateral_pool_accounts.registry.key,
         source.key,
         collateral_pool_accounts.token_account.key,
         user_transfer_authority.key,
@@ -109,6 +117,9 @@ pub fn deposit<'a>(
         &[
             collateral_pool_accounts.pool_market,
             collateral_pool_accounts.pool,
+            collateral_pool_accounts.registry,
+            collateral_pool_accounts.global_pause,
+            collateral_pool_accounts.pool_pause,
             source,
             collateral_pool_accounts.token_account,
             collateral_pool_accounts.pool_market_authority,
@@ -132,6 +143,7 @@ pub fn withdraw<'a>(
         &crate::id(),
         collateral_pool_accounts.pool_market.key,
         collateral_pool_accounts.pool.key,
+        collateral_pool_accounts.registry.key,
         collateral_pool_withdraw_authority.key,
         destination.key,
         collateral_pool_accounts.token_account.key,
@@ -144,6 +156,9 @@ pub fn withdraw<'a>(
         &[
             collateral_pool_accounts.pool_market,
             collateral_pool_accounts.pool,
+            collateral_pool_accounts.registry,
+            collateral_pool_accounts.global_pause,
+            collateral_pool_accounts.pool_pause,
             collateral_pool_withdraw_authority,
             destination,
             collateral_pool_accounts.token_account,
@@ -7,11 +7,12 @@ use solana_program::{
     system_program, sysvar,
 };
 
+use everlend_registry::find_pause_addresses;
 use everlend_utils::find_program_address;
 
 use crate::{
-    find_pool_borrow_authority_program_address, find_pool_program_address,
-    find_pool_withdraw_authority_program_address,
+    find_pool_borrow_authority_program_address, find_pool_borrow_shares_program_address,
+    find_pool_program_address, find_pool_withdraw_authority_program_address,
 };
 
 /// Instructions supported by the program
@@ -22,6 +23,7 @@ pub enum CollateralPoolsInstruction {
     /// Accounts:
     /// [W] Pool market - uninitialized
     /// [RS] Market manager
+    /// [R] Registry
     /// [R] Rent sysvar
     InitPoolMarket,
 
@@ -37,6 +39,9 @@ pub enum CollateralPoolsInstruction {
     /// [R] Rent sysvar
     /// [R] System program
     /// [R] Token program id
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
     CreatePool,
 
     /// Creates and initializes a pool borrow authority
@@ -45,10 +50,14 @@ pub enum CollateralPoolsInstruction {
     /// [R] Pool market
     /// [R] Pool
     /// [W] Pool borrow authority
+    /// [W] Pool borrow shares
     /// [R] Borrow authority
     /// [WS] Market manager
     /// [R] Rent sysvar
     /// [R] System program
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
     CreatePoolBorrowAuthority {
         /// Share allowed
         share_allowed: u16,
@@ -60,7 +69,11 @@ pub enum CollateralPoolsInstruction {
     /// [R] Pool market
     /// [R] Pool
     /// [W] Pool borrow authority
+    /// [W] Pool borrow shares
     /// [RS] Market manager
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
     UpdatePoolBorrowAuthority {
         /// Share allowed
         share_allowed: u16,
@@ -69,10 +82,15 @@ pub enum CollateralPoolsInstruction {
     /// Delete a pool borrow authority
     ///
     /// Accounts:
-    /// [W] Pool borrow authority
+    /// [R] Pool market
     /// [R] Pool
+    /// [W] Pool borrow authority
+    /// [W] Pool borrow shares
     /// [W] Receiver lamports
     /// [RS] Market manager
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
     DeletePoolBorrowAuthority,
 
     /// Creates and initializes a pool withdraw authority
@@ -100,6 +118,9 @@ pub enum CollateralPoolsInstruction {
     /// Accounts:
     /// [R] Pool market
     /// [R] Pool
+    /// [R] Registry
+    /// [R] Global pause
+    /// [R] Pool pause
     /// [W] Source account (for token mint)
     /// [W] Token account
     /// [R] Pool market authority
@@ -114,6 +135,9 @@ pub enum CollateralPoolsInstruction {
     /// Accounts:
     /// [R] Pool market
     /// [R] Pool
+    /// [R] Registry
+    /// [R] Global pause
+    /// [R] Pool pause
     /// [R] Pool withdraw authority
     /// [W] Destination account (for token mint)
     /// [W] Token account
@@ -130,6 +154,9 @@ pub enum CollateralPoolsInstruction {
     /// Accounts:
     /// [R] Pool market
     /// [W] Pool
+    /// [R] Registry
+    /// [R] Global pause
+    /// [R] Pool pause
     /// [W] Pool borrow authority
     /// [W] Destination account (for token mint)
     /// [W] Token account
@@ -158,14 +185,14 @@ pub enum CollateralPoolsInstruction {
         interest_amount: u64,
     },
 
-    /// Update pool market manager
+    /// Propose a new pool market manager, takes effect after the new manager accepts it
     ///
     /// Accounts:
     /// [W] Pool market
-    /// [WS] Old manager
-    /// [RS] New manager
+    /// [RS] Manager
+    /// [R] New manager
     ///
-    UpdateManager,
+    ProposeManager,
 
     /// Migrate pool withdraw authority
     ///
@@ -190,6 +217,44 @@ pub enum CollateralPoolsInstruction {
     /// [R] Rent sysvar
     /// [R] System program
     MigratePoolBorrowAuthority,
+
+    /// Accept the pool market manager proposal
+    ///
+    /// Accounts:
+    /// [W] Pool market
+    /// [RS] New manager
+    AcceptManager,
+
+    /// Cancel the pool market manager proposal
+    ///
+    /// Accounts:
+    /// [W] Pool market
+    /// [RS] Manager
+    CancelManagerProposal,
+
+    /// Creates pool borrow shares tracking the total share allowed of the pool borrow authorities.
+    /// All existing borrow authorities of the pool must be passed
+    ///
+    /// Accounts:
+    /// [R] Pool market
+    /// [R] Pool
+    /// [W] Pool borrow shares
+    /// [WS] Market manager
+    /// [R] Rent sysvar
+    /// [R] System program
+    /// For each existing borrow authority of the pool:
+    /// [R] Pool borrow authority
+    InitPoolBorrowShares,
+
+    /// Migrate pool market to the layout with pending manager and registry
+    ///
+    /// Accounts:
+    /// [W] Pool market
+    /// [WS] Market manager
+    /// [R] Registry
+    /// [R] Rent sysvar
+    /// [R] System program
+    MigratePoolMarket,
 }
 
 /// Creates 'InitPoolMarket' instruction.
@@ -198,10 +263,12 @@ pub fn init_pool_market(
     program_id: &Pubkey,
     pool_market: &Pubkey,
     manager: &Pubkey,
+    registry: &Pubkey,
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new(*pool_market, false),
         AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(*registry, false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
     ];
 
@@ -255,11 +322,13 @@ pub fn create_pool_borrow_authority(
 ) -> Instruction {
     let (pool_borrow_authority, _) =
         find_pool_borrow_authority_program_address(program_id, pool, borrow_authority);
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(program_id, pool);
 
     let accounts = vec![
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new_readonly(*pool, false),
         AccountMeta::new(pool_borrow_authority, false),
+        AccountMeta::new(pool_borrow_shares, false),
         AccountMeta::new_readonly(*borrow_authority, false),
         AccountMeta::new(*manager, true),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
@@ -285,11 +354,13 @@ pub fn update_pool_borrow_authority(
 ) -> Instruction {
     let (pool_borrow_authority, _) =
         find_pool_borrow_authority_program_address(program_id, pool, borrow_authority);
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(program_id, pool);
 
     let accounts = vec![
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new_readonly(*pool, false),
         AccountMeta::new(pool_borrow_authority, false),
+        AccountMeta::new(pool_borrow_shares, false),
         AccountMeta::new_readonly(*manager, true),
     ];
 
@@ -312,11 +383,13 @@ pub fn delete_pool_borrow_authority(
 ) -> Instruction {
     let (pool_borrow_authority, _) =
         find_pool_borrow_authority_program_address(program_id, pool, borrow_authority);
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(program_id, pool);
 
     let accounts = vec![
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new_readonly(*pool, false),
         AccountMeta::new(pool_borrow_authority, false),
+        AccountMeta::new(pool_borrow_shares, false),
         AccountMeta::new(*receiver, false),
         AccountMeta::new_readonly(*manager, true),
     ];
@@ -391,14 +464,20 @@ pub fn deposit(
     program_id: &Pubkey,
     pool_market: &Pubkey,
     pool: &Pubkey,
+    registry: &Pubkey,
     source: &Pubkey,
     token_account: &Pubkey,
     user_transfer_authority: &Pubkey,
     amount: u64,
 ) -> Instruction {
+    let (global_pause, pool_pause) = find_pause_addresses(registry, pool);
+
     let accounts = vec![
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         AccountMeta::new(*source, false),
         AccountMeta::new(*token_account, false),
         AccountMeta::new_readonly(*user_transfer_authority, true),
@@ -418,17 +497,22 @@ pub fn withdraw(
     program_id: &Pubkey,
     pool_market: &Pubkey,
     pool: &Pubkey,
+    registry: &Pubkey,
     pool_withdraw_authority: &Pubkey,
     destination: &Pubkey,
     token_account: &Pubkey,
     user_transfer_authority: &Pubkey,
     amount: u64,
 ) -> Instruction {
+    let (global_pause, pool_pause) = find_pause_addresses(registry, pool);
     let (pool_market_authority, _) = find_program_address(program_id, pool_market);
 
     let accounts = vec![
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         AccountMeta::new_readonly(*pool_withdraw_authority, false),
         AccountMeta::new(*destination, false),
         AccountMeta::new(*token_account, false),
@@ -450,17 +534,22 @@ pub fn borrow(
     program_id: &Pubkey,
     pool_market: &Pubkey,
     pool: &Pubkey,
+    registry: &Pubkey,
     pool_borrow_authority: &Pubkey,
     destination: &Pubkey,
     token_account: &Pubkey,
     borrow_authority: &Pubkey,
     amount: u64,
 ) -> Instruction {
+    let (global_pause, pool_pause) = find_pause_addresses(registry, pool);
     let (pool_market_authority, _) = find_program_address(program_id, pool_market);
 
     let accounts = vec![
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new(*pool, false),
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         AccountMeta::new(*pool_borrow_authority, false),
         AccountMeta::new(*destination, false),
         AccountMeta::new(*token_account, false),
@@ -509,9 +598,8 @@ pub fn repay(
     )
 }
 
-/// Creates 'UpdateManager' instruction.
-#[allow(clippy::too_many_arguments)]
-pub fn update_manager(
+/// Creates 'ProposeManager' instruction.
+pub fn propose_manager(
     program_id: &Pubkey,
     pool_market: &Pubkey,
     manager: &Pubkey,
@@ -519,13 +607,49 @@ pub fn update_manager(
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new(*pool_market, false),
-        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(*new_manager, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &CollateralPoolsInstruction::ProposeManager,
+        accounts,
+    )
+}
+
+/// Creates 'AcceptManager' instruction.
+pub fn accept_manager(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    new_manager: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*pool_market, false),
         AccountMeta::new_readonly(*new_manager, true),
     ];
 
     Instruction::new_with_borsh(
         *program_id,
-        &CollateralPoolsInstruction::UpdateManager,
+        &CollateralPoolsInstruction::AcceptManager,
+        accounts,
+    )
+}
+
+/// Creates 'CancelManagerProposal' instruction.
+pub fn cancel_manager_proposal(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    manager: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*pool_market, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &CollateralPoolsInstruction::CancelManagerProposal,
         accounts,
     )
 }
@@ -583,3 +707,57 @@ pub fn migrate_pool_borrow_authority(
         accounts,
     )
 }
+
+/// Creates 'InitPoolBorrowShares' instruction.
+pub fn init_pool_borrow_shares(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    pool: &Pubkey,
+    manager: &Pubkey,
+    borrow_authorities: &[Pubkey],
+) -> Instruction {
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(program_id, pool);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*pool_market, false),
+        AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new(pool_borrow_shares, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    for borrow_authority in borrow_authorities {
+        let (pool_borrow_authority, _) =
+            find_pool_borrow_authority_program_address(program_id, pool, borrow_authority);
+        accounts.push(AccountMeta::new_readonly(pool_borrow_authority, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &CollateralPoolsInstruction::InitPoolBorrowShares,
+        accounts,
+    )
+}
+
+/// Creates 'MigratePoolMarket' instruction.
+pub fn migrate_pool_market(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    manager: &Pubkey,
+    registry: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*pool_market, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &CollateralPoolsInstruction::MigratePoolMarket,
+        accounts,
+    )
+}
@@ -60,6 +60,17 @@ pub fn find_pool_borrow_authority_program_address(
     )
 }
 
+/// Generates pool borrow shares address
+pub fn find_pool_borrow_shares_program_address(
+    program_id: &Pubkey,
+    pool_pubkey: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"borrow_shares".as_ref(), &pool_pubkey.to_bytes()],
+        program_id,
+    )
+}
+
 /// Generates pool withdraw authority address
 pub fn find_pool_withdraw_authority_program_address(
     program_id: &Pubkey,
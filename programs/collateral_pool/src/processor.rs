@@ -1,9 +1,10 @@
 //! Program state processor
 
 use borsh::BorshDeserialize;
+use everlend_registry::state::{Role, RoleAccounts};
 use everlend_utils::{
     assert_account_key, assert_non_zero_amount, assert_owned_by, assert_rent_exempt, assert_signer,
-    assert_uninitialized, cpi, find_program_address, EverlendError,
+    assert_uninitialized, cpi, cpi::system::realloc_with_rent, find_program_address, EverlendError,
 };
 use solana_program::program_error::ProgramError;
 use solana_program::{
@@ -18,12 +19,13 @@ use solana_program::{
 
 use crate::{
     find_legacy_pool_withdraw_authority_program_address,
-    find_pool_borrow_authority_program_address, find_pool_program_address,
-    find_pool_withdraw_authority_program_address,
+    find_pool_borrow_authority_program_address, find_pool_borrow_shares_program_address,
+    find_pool_program_address, find_pool_withdraw_authority_program_address,
     instruction::CollateralPoolsInstruction,
     state::{
-        InitPoolBorrowAuthorityParams, InitPoolMarketParams, InitPoolParams, Pool,
-        PoolBorrowAuthority, PoolMarket, PoolWithdrawAuthority,
+        assert_share_allowed, DeprecatedPoolMarket, InitPoolBorrowAuthorityParams,
+        InitPoolMarketParams, InitPoolParams, Pool, PoolBorrowAuthority, PoolBorrowShares,
+        PoolMarket, PoolWithdrawAuthority,
     },
     utils::*,
 };
@@ -37,6 +39,7 @@ impl Processor {
         let account_info_iter = &mut accounts.iter();
         let pool_market_info = next_account_info(account_info_iter)?;
         let manager_info = next_account_info(account_info_iter)?;
+        let registry_info = next_account_info(account_info_iter)?;
         let rent_info = next_account_info(account_info_iter)?;
         let rent = &Rent::from_account_info(rent_info)?;
 
@@ -45,6 +48,7 @@ impl Processor {
 
         // Check programs
         assert_owned_by(pool_market_info, program_id)?;
+        assert_owned_by(registry_info, &everlend_registry::id())?;
 
         // Get pool market state
         let mut pool_market = PoolMarket::unpack_unchecked(&pool_market_info.data.borrow())?;
@@ -52,6 +56,7 @@ impl Processor {
 
         pool_market.init(InitPoolMarketParams {
             manager: *manager_info.key,
+            registry: *registry_info.key,
         });
 
         PoolMarket::pack(pool_market, *pool_market_info.data.borrow_mut())?;
@@ -72,13 +77,19 @@ impl Processor {
         let rent = &Rent::from_account_info(rent_info)?;
         let _system_program_info = next_account_info(account_info_iter)?;
         let _token_program_info = next_account_info(account_info_iter)?;
+        let role_accounts = RoleAccounts::next_optional(&mut account_info_iter.enumerate())?;
 
         assert_signer(manager_info)?;
 
         assert_owned_by(pool_market_info, program_id)?;
 
         let pool_market = PoolMarket::unpack(&pool_market_info.data.borrow())?;
-        assert_account_key(manager_info, &pool_market.manager)?;
+        assert_manager_or_role(
+            &pool_market,
+            manager_info,
+            role_accounts.as_ref(),
+            Role::Operator,
+        )?;
 
         {
             let (pool_market_authority_pubkey, _) =
@@ -134,29 +145,48 @@ impl Processor {
         let pool_market_info = next_account_info(account_info_iter)?;
         let pool_info = next_account_info(account_info_iter)?;
         let pool_borrow_authority_info = next_account_info(account_info_iter)?;
+        let pool_borrow_shares_info = next_account_info(account_info_iter)?;
         let borrow_authority_info = next_account_info(account_info_iter)?;
         let manager_info = next_account_info(account_info_iter)?;
         let rent_info = next_account_info(account_info_iter)?;
         let rent = &Rent::from_account_info(rent_info)?;
         let _system_program_info = next_account_info(account_info_iter)?;
+        let role_accounts = RoleAccounts::next_optional(&mut account_info_iter.enumerate())?;
 
         assert_signer(manager_info)?;
+        assert_share_allowed(share_allowed)?;
 
         // Check programs
         assert_owned_by(pool_market_info, program_id)?;
         assert_owned_by(pool_info, program_id)?;
+        assert_owned_by(pool_borrow_shares_info, program_id)?;
 
         // Get pool market state
         let pool_market = PoolMarket::unpack(&pool_market_info.data.borrow())?;
 
-        // Check manager
-        assert_account_key(manager_info, &pool_market.manager)?;
+        // Check manager or role
+        assert_manager_or_role(
+            &pool_market,
+            manager_info,
+            role_accounts.as_ref(),
+            Role::Admin,
+        )?;
 
         let pool = Pool::unpack(&pool_info.data.borrow())?;
 
         // Check pool accounts
         assert_account_key(pool_market_info, &pool.pool_market)?;
 
+        // Check total share allowed of the pool
+        let mut pool_borrow_shares =
+            PoolBorrowShares::unpack(&pool_borrow_shares_info.data.borrow())?;
+        assert_account_key(pool_info, &pool_borrow_shares.pool)?;
+        pool_borrow_shares.update(0, share_allowed)?;
+        PoolBorrowShares::pack(
+            pool_borrow_shares,
+            *pool_borrow_shares_info.data.borrow_mut(),
+        )?;
+
         // Create pool borrow authority account
         let (pool_borrow_authority_pubkey, bump_seed) = find_pool_borrow_authority_program_address(
             program_id,
@@ -209,7 +239,9 @@ impl Processor {
         let pool_market_info = next_account_info(account_info_iter)?;
         let pool_info = next_account_info(account_info_iter)?;
         let pool_borrow_authority_info = next_account_info(account_info_iter)?;
+        let pool_borrow_shares_info = next_account_info(account_info_iter)?;
         let manager_info = next_account_info(account_info_iter)?;
+        let role_accounts = RoleAccounts::next_optional(&mut account_info_iter.enumerate())?;
 
         assert_signer(manager_info)?;
 
@@ -217,11 +249,17 @@ impl Processor {
         assert_owned_by(pool_market_info, program_id)?;
         assert_owned_by(pool_info, program_id)?;
         assert_owned_by(pool_borrow_authority_info, program_id)?;
+        assert_owned_by(pool_borrow_shares_info, program_id)?;
 
         let pool_market = PoolMarket::unpack(&pool_market_info.data.borrow())?;
 
-        // Check manager
-        assert_account_key(manager_info, &pool_market.manager)?;
+        // Check manager or role
+        assert_manager_or_role(
+            &pool_market,
+            manager_info,
+            role_accounts.as_ref(),
+            Role::Admin,
+        )?;
 
         let pool = Pool::unpack(&pool_info.data.borrow())?;
 
@@ -235,7 +273,17 @@ impl Processor {
         // Check pool borrow authority accounts
         assert_account_key(pool_info, &pool_borrow_authority.pool)?;
 
-        pool_borrow_authority.update_share_allowed(share_allowed);
+        // Check total share allowed of the pool
+        let mut pool_borrow_shares =
+            PoolBorrowShares::unpack(&pool_borrow_shares_info.data.borrow())?;
+        assert_account_key(pool_info, &pool_borrow_shares.pool)?;
+        pool_borrow_shares.update(pool_borrow_authority.share_allowed, share_allowed)?;
+        PoolBorrowShares::pack(
+            pool_borrow_shares,
+            *pool_borrow_shares_info.data.borrow_mut(),
+        )?;
+
+        pool_borrow_authority.update_share_allowed(share_allowed)?;
 
         PoolBorrowAuthority::pack(
             pool_borrow_authority,
@@ -254,8 +302,10 @@ impl Processor {
         let pool_market_info = next_account_info(account_info_iter)?;
         let pool_info = next_account_info(account_info_iter)?;
         let pool_borrow_authority_info = next_account_info(account_info_iter)?;
+        let pool_borrow_shares_info = next_account_info(account_info_iter)?;
         let receiver_info = next_account_info(account_info_iter)?;
         let manager_info = next_account_info(account_info_iter)?;
+        let role_accounts = RoleAccounts::next_optional(&mut account_info_iter.enumerate())?;
 
         assert_signer(manager_info)?;
 
@@ -263,11 +313,17 @@ impl Processor {
         assert_owned_by(pool_market_info, program_id)?;
         assert_owned_by(pool_info, program_id)?;
         assert_owned_by(pool_borrow_authority_info, program_id)?;
+        assert_owned_by(pool_borrow_shares_info, program_id)?;
 
         let pool_market = PoolMarket::unpack(&pool_market_info.data.borrow())?;
 
-        // Check manager
-        assert_account_key(manager_info, &pool_market.manager)?;
+        // Check manager or role
+        assert_manager_or_role(
+            &pool_market,
+            manager_info,
+            role_accounts.as_ref(),
+            Role::Admin,
+        )?;
 
         let pool = Pool::unpack(&pool_info.data.borrow())?;
 
@@ -280,6 +336,16 @@ impl Processor {
             PoolBorrowAuthority::unpack(&pool_borrow_authority_info.data.borrow())?;
         assert_account_key(pool_info, &pool_borrow_authority.pool)?;
 
+        // Release share allowed of the borrow authority
+        let mut pool_borrow_shares =
+            PoolBorrowShares::unpack(&pool_borrow_shares_info.data.borrow())?;
+        assert_account_key(pool_info, &pool_borrow_shares.pool)?;
+        pool_borrow_shares.update(pool_borrow_authority.share_allowed, 0)?;
+        PoolBorrowShares::pack(
+            pool_borrow_shares,
+            *pool_borrow_shares_info.data.borrow_mut(),
+        )?;
+
         let receiver_starting_lamports = receiver_info.lamports();
         let pool_borrow_authority_lamports = pool_borrow_authority_info.lamports();
 
@@ -406,6 +472,9 @@ impl Processor {
         let account_info_iter = &mut accounts.iter();
         let pool_market_info = next_account_info(account_info_iter)?;
         let pool_info = next_account_info(account_info_iter)?;
+        let registry_info = next_account_info(account_info_iter)?;
+        let global_pause_info = next_account_info(account_info_iter)?;
+        let pool_pause_info = next_account_info(account_info_iter)?;
         let source_info = next_account_info(account_info_iter)?;
         let token_account_info = next_account_info(account_info_iter)?;
         let user_transfer_authority_info = next_account_info(account_info_iter)?;
@@ -422,6 +491,17 @@ impl Processor {
         assert_account_key(pool_market_info, &pool.pool_market)?;
         assert_account_key(token_account_info, &pool.token_account)?;
 
+        let flags = pause_flags(
+            pool_market_info,
+            pool_info,
+            registry_info,
+            global_pause_info,
+            pool_pause_info,
+        )?;
+        if flags.deposit {
+            return Err(EverlendError::OperationPaused.into());
+        }
+
         cpi::spl_token::transfer(
             source_info.clone(),
             token_account_info.clone(),
@@ -438,6 +518,9 @@ impl Processor {
         let account_info_iter = &mut accounts.iter();
         let pool_market_info = next_account_info(account_info_iter)?;
         let pool_info = next_account_info(account_info_iter)?;
+        let registry_info = next_account_info(account_info_iter)?;
+        let global_pause_info = next_account_info(account_info_iter)?;
+        let pool_pause_info = next_account_info(account_info_iter)?;
         let pool_withdraw_authority_info = next_account_info(account_info_iter)?;
         let destination_info = next_account_info(account_info_iter)?;
         let token_account_info = next_account_info(account_info_iter)?;
@@ -457,6 +540,17 @@ impl Processor {
         assert_account_key(pool_market_info, &pool.pool_market)?;
         assert_account_key(token_account_info, &pool.token_account)?;
 
+        let flags = pause_flags(
+            pool_market_info,
+            pool_info,
+            registry_info,
+            global_pause_info,
+            pool_pause_info,
+        )?;
+        if flags.withdraw {
+            return Err(EverlendError::OperationPaused.into());
+        }
+
         let pool_withdraw_authority =
             PoolWithdrawAuthority::unpack(&pool_withdraw_authority_info.data.borrow())?;
 
@@ -487,6 +581,9 @@ impl Processor {
         let account_info_iter = &mut accounts.iter();
         let pool_market_info = next_account_info(account_info_iter)?;
         let pool_info = next_account_info(account_info_iter)?;
+        let registry_info = next_account_info(account_info_iter)?;
+        let global_pause_info = next_account_info(account_info_iter)?;
+        let pool_pause_info = next_account_info(account_info_iter)?;
         let pool_borrow_authority_info = next_account_info(account_info_iter)?;
         let destination_info = next_account_info(account_info_iter)?;
         let token_account_info = next_account_info(account_info_iter)?;
@@ -507,6 +604,17 @@ impl Processor {
         assert_account_key(pool_market_info, &pool.pool_market)?;
         assert_account_key(token_account_info, &pool.token_account)?;
 
+        let flags = pause_flags(
+            pool_market_info,
+            pool_info,
+            registry_info,
+            global_pause_info,
+            pool_pause_info,
+        )?;
+        if flags.borrow {
+            return Err(EverlendError::OperationPaused.into());
+        }
+
         // Check the impossibility of self borrow
         if token_account_info.key == destination_info.key {
             return Err(ProgramError::InvalidArgument);
@@ -616,22 +724,169 @@ impl Processor {
         Ok(())
     }
 
-    /// Process UpdateManager instruction
-    pub fn update_manager(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    /// Process ProposeManager instruction
+    pub fn propose_manager(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let pool_market_info = next_account_info(account_info_iter)?;
         let manager_info = next_account_info(account_info_iter)?;
         let new_manager_info = next_account_info(account_info_iter)?;
 
         assert_signer(manager_info)?;
+
+        assert_owned_by(pool_market_info, program_id)?;
+
+        let mut pool_market = PoolMarket::unpack(&pool_market_info.data.borrow())?;
+        assert_account_key(manager_info, &pool_market.manager)?;
+
+        pool_market.pending_manager = *new_manager_info.key;
+
+        PoolMarket::pack(pool_market, *pool_market_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process AcceptManager instruction
+    pub fn accept_manager(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let pool_market_info = next_account_info(account_info_iter)?;
+        let new_manager_info = next_account_info(account_info_iter)?;
+
         assert_signer(new_manager_info)?;
 
         assert_owned_by(pool_market_info, program_id)?;
 
+        let mut pool_market = PoolMarket::unpack(&pool_market_info.data.borrow())?;
+        assert_account_key(new_manager_info, &pool_market.pending_manager)?;
+
+        pool_market.manager = pool_market.pending_manager;
+        pool_market.pending_manager = Pubkey::default();
+
+        PoolMarket::pack(pool_market, *pool_market_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process CancelManagerProposal instruction
+    pub fn cancel_manager_proposal(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let pool_market_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+
+        assert_signer(manager_info)?;
+
+        assert_owned_by(pool_market_info, program_id)?;
+
         let mut pool_market = PoolMarket::unpack(&pool_market_info.data.borrow())?;
         assert_account_key(manager_info, &pool_market.manager)?;
 
-        pool_market.manager = *new_manager_info.key;
+        pool_market.pending_manager = Pubkey::default();
+
+        PoolMarket::pack(pool_market, *pool_market_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process InitPoolBorrowShares instruction
+    pub fn init_pool_borrow_shares(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let pool_market_info = next_account_info(account_info_iter)?;
+        let pool_info = next_account_info(account_info_iter)?;
+        let pool_borrow_shares_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+        let _system_program_info = next_account_info(account_info_iter)?;
+        let pool_borrow_authority_infos: Vec<&AccountInfo> = account_info_iter.collect();
+
+        assert_signer(manager_info)?;
+
+        // Check programs
+        assert_owned_by(pool_market_info, program_id)?;
+        assert_owned_by(pool_info, program_id)?;
+
+        let pool_market = PoolMarket::unpack(&pool_market_info.data.borrow())?;
+        assert_account_key(manager_info, &pool_market.manager)?;
+
+        let pool = Pool::unpack(&pool_info.data.borrow())?;
+
+        // Check pool accounts
+        assert_account_key(pool_market_info, &pool.pool_market)?;
+
+        let mut pool_borrow_shares = PoolBorrowShares::init(*pool_info.key);
+
+        // Existing borrow authorities of the pool are counted once
+        for (index, pool_borrow_authority_info) in pool_borrow_authority_infos.iter().enumerate() {
+            if pool_borrow_authority_infos[..index]
+                .iter()
+                .any(|info| info.key == pool_borrow_authority_info.key)
+            {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            assert_owned_by(pool_borrow_authority_info, program_id)?;
+
+            let pool_borrow_authority =
+                PoolBorrowAuthority::unpack(&pool_borrow_authority_info.data.borrow())?;
+            assert_account_key(pool_info, &pool_borrow_authority.pool)?;
+
+            let (pool_borrow_authority_pubkey, _) = find_pool_borrow_authority_program_address(
+                program_id,
+                pool_info.key,
+                &pool_borrow_authority.borrow_authority,
+            );
+            assert_account_key(pool_borrow_authority_info, &pool_borrow_authority_pubkey)?;
+
+            pool_borrow_shares.update(0, pool_borrow_authority.share_allowed)?;
+        }
+
+        // Create pool borrow shares account
+        let (pool_borrow_shares_pubkey, bump_seed) =
+            find_pool_borrow_shares_program_address(program_id, pool_info.key);
+        assert_account_key(pool_borrow_shares_info, &pool_borrow_shares_pubkey)?;
+
+        let signers_seeds = &[
+            b"borrow_shares".as_ref(),
+            &pool_info.key.to_bytes()[..32],
+            &[bump_seed],
+        ];
+
+        cpi::system::create_account::<PoolBorrowShares>(
+            program_id,
+            manager_info.clone(),
+            pool_borrow_shares_info.clone(),
+            &[signers_seeds],
+            rent,
+        )?;
+
+        PoolBorrowShares::pack(
+            pool_borrow_shares,
+            *pool_borrow_shares_info.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Process MigratePoolMarket instruction
+    pub fn migrate_pool_market(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let pool_market_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let registry_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+        let _system_program_info = next_account_info(account_info_iter)?;
+
+        assert_signer(manager_info)?;
+        assert_owned_by(pool_market_info, program_id)?;
+        assert_owned_by(registry_info, &everlend_registry::id())?;
+
+        let mut pool_market: PoolMarket =
+            DeprecatedPoolMarket::unpack(&pool_market_info.data.borrow())?.into();
+        assert_account_key(manager_info, &pool_market.manager)?;
+
+        pool_market.registry = *registry_info.key;
+
+        realloc_with_rent(pool_market_info, manager_info, rent, PoolMarket::LEN)?;
 
         PoolMarket::pack(pool_market, *pool_market_info.data.borrow_mut())?;
 
@@ -804,9 +1059,9 @@ impl Processor {
                 Self::repay(program_id, amount, interest_amount, accounts)
             }
 
-            CollateralPoolsInstruction::UpdateManager => {
-                msg!("CollateralPoolsInstruction: UpdateManager");
-                Self::update_manager(program_id, accounts)
+            CollateralPoolsInstruction::ProposeManager => {
+                msg!("CollateralPoolsInstruction: ProposeManager");
+                Self::propose_manager(program_id, accounts)
             }
 
             CollateralPoolsInstruction::MigratePoolWithdrawAuthority => {
@@ -818,6 +1073,26 @@ impl Processor {
                 msg!("CollateralPoolsInstruction: MigratePoolBorrowAuthority");
                 Self::migrate_pool_borrow_authority(program_id, accounts)
             }
+
+            CollateralPoolsInstruction::AcceptManager => {
+                msg!("CollateralPoolsInstruction: AcceptManager");
+                Self::accept_manager(program_id, accounts)
+            }
+
+            CollateralPoolsInstruction::CancelManagerProposal => {
+                msg!("CollateralPoolsInstruction: CancelManagerProposal");
+                Self::cancel_manager_proposal(program_id, accounts)
+            }
+
+            CollateralPoolsInstruction::InitPoolBorrowShares => {
+                msg!("CollateralPoolsInstruction: InitPoolBorrowShares");
+                Self::init_pool_borrow_shares(program_id, accounts)
+            }
+
+            CollateralPoolsInstruction::MigratePoolMarket => {
+                msg!("CollateralPoolsInstruction: MigratePoolMarket");
+                Self::migrate_pool_market(program_id, accounts)
+            }
         }
     }
 }
@@ -3,11 +3,13 @@ use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 
 mod pool;
 mod pool_borrow_authority;
+mod pool_borrow_shares;
 mod pool_market;
 mod pool_withdraw_authority;
 
 pub use pool::*;
 pub use pool_borrow_authority::*;
+pub use pool_borrow_shares::*;
 pub use pool_market::*;
 pub use pool_withdraw_authority::*;
 
@@ -24,6 +26,8 @@ pub enum AccountType {
     PoolBorrowAuthority,
     /// Pool withdraw authority
     PoolWithdrawAuthority,
+    /// Pool borrow shares
+    PoolBorrowShares,
 }
 
 impl Default for AccountType {
@@ -10,6 +10,18 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
+/// Max share allowed in basis points
+pub const MAX_SHARE_ALLOWED: u16 = 10_000;
+
+/// Check share allowed doesn't exceed the whole pool
+pub fn assert_share_allowed(share_allowed: u16) -> ProgramResult {
+    if share_allowed > MAX_SHARE_ALLOWED {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
 /// Pool borrow authority
 #[repr(C)]
 #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
@@ -59,8 +71,11 @@ impl PoolBorrowAuthority {
     }
 
     /// Update share allowed
-    pub fn update_share_allowed(&mut self, share: u16) {
-        self.share_allowed = share
+    pub fn update_share_allowed(&mut self, share: u16) -> ProgramResult {
+        assert_share_allowed(share)?;
+        self.share_allowed = share;
+
+        Ok(())
     }
 
     /// Get amount allowed
@@ -74,11 +89,17 @@ impl PoolBorrowAuthority {
 
     /// Check amount allowed
     pub fn check_amount_allowed(&self, total_pool_amount: u64) -> ProgramResult {
-        if self.amount_borrowed > self.get_amount_allowed(total_pool_amount)? {
-            Err(EverlendError::AmountAllowedCheckFailed.into())
-        } else {
-            Ok(())
+        let amount_allowed = self.get_amount_allowed(total_pool_amount)?;
+        if self.amount_borrowed > amount_allowed {
+            msg!(
+                "Amount borrowed {} exceeds amount allowed {}",
+                self.amount_borrowed,
+                amount_allowed
+            );
+            return Err(EverlendError::BorrowShareAllowedExceeded.into());
         }
+
+        Ok(())
     }
 }
 
@@ -0,0 +1,81 @@
+//! PoolBorrowShares state definitions
+use super::*;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_utils::EverlendError;
+use solana_program::{
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Sum of share allowed of the pool borrow authorities
+#[repr(C)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct PoolBorrowShares {
+    /// Account type - PoolBorrowShares
+    pub account_type: AccountType,
+    /// Pool
+    pub pool: Pubkey,
+    /// Total share allowed in basis points
+    pub total_share_allowed: u16,
+}
+
+impl PoolBorrowShares {
+    /// Initialize a pool borrow shares
+    pub fn init(pool: Pubkey) -> PoolBorrowShares {
+        PoolBorrowShares {
+            account_type: AccountType::PoolBorrowShares,
+            pool,
+            total_share_allowed: 0,
+        }
+    }
+
+    /// Replace share allowed of a borrow authority in the total
+    pub fn update(&mut self, old_share: u16, new_share: u16) -> ProgramResult {
+        let total_share_allowed = self
+            .total_share_allowed
+            .checked_sub(old_share)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_add(new_share)
+            .ok_or(EverlendError::MathOverflow)?;
+        if total_share_allowed > MAX_SHARE_ALLOWED {
+            msg!(
+                "Total share allowed {} exceeds {}",
+                total_share_allowed,
+                MAX_SHARE_ALLOWED
+            );
+            return Err(EverlendError::TotalShareAllowedExceeded.into());
+        }
+
+        self.total_share_allowed = total_share_allowed;
+
+        Ok(())
+    }
+}
+
+impl Sealed for PoolBorrowShares {}
+impl Pack for PoolBorrowShares {
+    // 1 + 32 + 2
+    const LEN: usize = 35;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Failed to deserialize");
+            msg!("Actual LEN: {}", std::mem::size_of::<PoolBorrowShares>());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for PoolBorrowShares {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::PoolBorrowShares
+    }
+}
@@ -9,6 +9,8 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
+pub use deprecated::DeprecatedPoolMarket;
+
 /// Pool market
 #[repr(C)]
 #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
@@ -17,6 +19,10 @@ pub struct PoolMarket {
     pub account_type: AccountType,
     /// Market manager
     pub manager: Pubkey,
+    /// Proposed manager, takes over after accepting
+    pub pending_manager: Pubkey,
+    /// Registry granting roles of the market
+    pub registry: Pubkey,
 }
 
 impl PoolMarket {
@@ -24,6 +30,7 @@ impl PoolMarket {
     pub fn init(&mut self, params: InitPoolMarketParams) {
         self.account_type = AccountType::PoolMarket;
         self.manager = params.manager;
+        self.registry = params.registry;
     }
 }
 
@@ -31,12 +38,16 @@ impl PoolMarket {
 pub struct InitPoolMarketParams {
     /// Market manager
     pub manager: Pubkey,
+    /// Registry granting roles of the market
+    pub registry: Pubkey,
+    /// Registry
+    pub registry: Pubkey,
 }
 
 impl Sealed for PoolMarket {}
 impl Pack for PoolMarket {
-    // 1 + 32
-    const LEN: usize = 33;
+    // 1 + 32 + 32 + 32
+    const LEN: usize = 97;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -63,3 +74,52 @@ impl Uninitialized for PoolMarket {
         self.account_type == AccountType::default()
     }
 }
+
+mod deprecated {
+    use super::*;
+
+    /// Pool market without pending manager and registry
+    #[repr(C)]
+    #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+    pub struct DeprecatedPoolMarket {
+        /// Account type - PoolMarket
+        pub account_type: AccountType,
+        /// Market manager
+        pub manager: Pubkey,
+    }
+
+    impl Sealed for DeprecatedPoolMarket {}
+    impl Pack for DeprecatedPoolMarket {
+        // 1 + 32
+        const LEN: usize = 33;
+
+        fn pack_into_slice(&self, dst: &mut [u8]) {
+            let mut slice = dst;
+            self.serialize(&mut slice).unwrap()
+        }
+
+        fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+            Self::try_from_slice(src).map_err(|_| {
+                msg!("Failed to deserialize");
+                ProgramError::InvalidAccountData
+            })
+        }
+    }
+
+    impl IsInitialized for DeprecatedPoolMarket {
+        fn is_initialized(&self) -> bool {
+            self.account_type == AccountType::PoolMarket
+        }
+    }
+
+    impl From<DeprecatedPoolMarket> for PoolMarket {
+        fn from(deprecated: DeprecatedPoolMarket) -> Self {
+            PoolMarket {
+                account_type: deprecated.account_type,
+                manager: deprecated.manager,
+                pending_manager: Pubkey::default(),
+                registry: Pubkey::default(),
+            }
+        }
+    }
+}
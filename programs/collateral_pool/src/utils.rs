@@ -1,7 +1,12 @@
 //! Utils
 
-use everlend_utils::EverlendError;
-use solana_program::{account_info::AccountInfo, program_error::ProgramError, program_pack::Pack};
+use crate::state::PoolMarket;
+use everlend_registry::state::{PauseFlags, Role, RoleAccounts};
+use everlend_utils::{assert_account_key, EverlendError};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack,
+};
 use spl_token::state::Account;
 
 /// Collateral pool accounts
@@ -12,6 +17,12 @@ pub struct CollateralPoolAccounts<'a> {
     pub pool_market_authority: AccountInfo<'a>,
     /// pool
     pub pool: AccountInfo<'a>,
+    /// registry
+    pub registry: AccountInfo<'a>,
+    /// global pause
+    pub global_pause: AccountInfo<'a>,
+    /// pool pause
+    pub pool_pause: AccountInfo<'a>,
     /// token account
     pub token_account: AccountInfo<'a>,
 }
@@ -26,3 +37,37 @@ pub fn total_pool_amount(
         .checked_add(total_amount_borrowed)
         .ok_or(EverlendError::MathOverflow)?)
 }
+
+/// Load pause flags of the collateral pool from the registry of the pool market
+pub fn pause_flags(
+    pool_market: &AccountInfo,
+    pool: &AccountInfo,
+    registry: &AccountInfo,
+    global_pause: &AccountInfo,
+    pool_pause: &AccountInfo,
+) -> Result<PauseFlags, ProgramError> {
+    let pool_market = PoolMarket::unpack(&pool_market.data.borrow())?;
+    assert_account_key(registry, &pool_market.registry)?;
+
+    PauseFlags::load(registry.key, pool.key, global_pause, pool_pause)
+}
+
+/// Check the signer is the pool market manager or is granted the role in the registry
+/// of the pool market
+pub fn assert_manager_or_role(
+    pool_market: &PoolMarket,
+    signer: &AccountInfo,
+    role_accounts: Option<&RoleAccounts>,
+    role: Role,
+) -> ProgramResult {
+    if let Some(role_accounts) = role_accounts {
+        assert_account_key(role_accounts.registry, &pool_market.registry)?;
+    }
+
+    everlend_registry::state::assert_manager_or_role(
+        &pool_market.manager,
+        signer,
+        role_accounts,
+        role,
+    )
+}
@@ -54,7 +54,7 @@ impl<'a, 'b> LarixClaimer<'a, 'b> {
             let registry = AccountLoader::next_with_owner(account_info_iter, &everlend_registry::id())?;
             let registry_markets
                 = everlend_registry::state::RegistryMarkets::unpack_from_slice(&registry.data.borrow())?;
-            if !registry_markets.money_markets.contains(staking_program_id) {
+            if !registry_markets.contains_money_market(staking_program_id) {
                 return Err(ProgramError::InvalidArgument);
             }
         }
@@ -3,16 +3,21 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use everlend_general_pool::find_withdrawal_requests_program_address;
 use everlend_liquidity_oracle::{find_token_oracle_program_address, state::DistributionArray};
+use everlend_registry::{find_guardian_program_address, find_pause_addresses};
 use everlend_utils::cpi::{francium, quarry};
 use everlend_utils::{find_program_address, PDA};
 use solana_program::{
+    clock::Slot,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     system_program, sysvar,
 };
 use spl_associated_token_account::get_associated_token_address;
 
-use crate::{state::MiningType, InternalMiningPDA, RebalancingPDA, TransitPDA};
+use crate::{
+    state::{MarketLimit, MiningType},
+    InternalMiningPDA, RebalancingLimitsPDA, RebalancingPDA, TransitPDA, YieldDistributionPDA,
+};
 
 /// Instructions supported by the program
 #[derive(Debug, BorshDeserialize, BorshSerialize, PartialEq)]
@@ -51,22 +56,34 @@ pub enum DepositorInstruction {
     /// [R] Depositor
     /// [R] Depositor authority
     /// [W] Rebalancing account
+    /// [R] Rebalancing limits account
     /// [R] Token mint
     /// [R] General pool market
     /// [R] General pool market authority
     /// [W] General pool
+    /// [R] Global pause
+    /// [R] Pool pause
     /// [W] General pool token account
     /// [W] General pool borrow authority
     /// [R] Withdrawals requests account
     /// [W] Liquidity transit account
     /// [R] Liquidity oracle
     /// [R] Token distribution
-    /// [WS] Rebalance executor account
+    /// [WS] Rebalance executor account, or any keeper with permissionless crank
     /// [R] Rent sysvar
     /// [R] Clock sysvar
     /// [R] System program
     /// [R] Token program id
     /// [R] Everlend general pool program id
+    /// [R] Yield distribution - only with yield optimization, before the safety fund accounts
+    /// Accounts to cover unsettled loss from the safety fund, only with unsettled loss:
+    /// [R] Income pool market
+    /// [R] Income pool market authority
+    /// [W] Safety fund ledger
+    /// [W] Safety fund token account
+    /// [R] Everlend income pools program id
+    /// [W] Keeper token account - optional, receives the keeper step reward for settled loss
+    /// [W] Keeper reward transit - optional, pays the keeper step reward
     StartRebalancing {
         /// Refresh income
         refresh_income: bool,
@@ -80,17 +97,22 @@ pub enum DepositorInstruction {
     /// [R] Depositor
     /// [R] Depositor authority
     /// [W] Rebalancing account
+    /// [R] Rebalancing limits account
+    /// [R] Global pause
+    /// [R] Pool pause
     /// [W] Liquidity transit account
     /// [R] Liquidity mint
     /// [W] Collateral transit account
     /// [W] Collateral mint
-    /// [S] Rebalance executor account
+    /// [S] Rebalance executor account, or any keeper with permissionless crank
     /// [R] Clock sysvar
     /// [R] Token program id
     /// [R] Money market program id
     /// [R] Internal mining account
     /// [] Money market deposit accounts
     /// [] Collateral storage accounts or money market mining accounts
+    /// [W] Keeper token account - optional, receives the keeper step reward
+    /// [W] Keeper reward transit - optional, pays the keeper step reward
     Deposit,
 
     /// Withdraw funds from MM pool to money market.
@@ -101,6 +123,8 @@ pub enum DepositorInstruction {
     /// [R] Depositor
     /// [R] Depositor authority
     /// [W] Rebalancing account
+    /// [R] Global pause
+    /// [R] Pool pause
     /// [R] Income pool market
     /// [R] Income pool
     /// [W] Income pool token account (for liquidity mint)
@@ -109,13 +133,15 @@ pub enum DepositorInstruction {
     /// [W] Liquidity transit account
     /// [W] Liquidity reserve transit account
     /// [R] Liquidity mint
-    /// [S] Rebalance executor account
+    /// [S] Rebalance executor account, or any keeper with permissionless crank
     /// [R] Clock sysvar
     /// [R] Token program id
     /// [R] Money market program id
     /// [R] Internal mining account
     /// [] Money market deposit accounts
     /// [] Collateral storage accounts or money market mining accounts
+    /// [W] Keeper token account - optional, receives the keeper step reward
+    /// [W] Keeper reward transit - optional, receives the keeper share of the income
     Withdraw,
 
     /// Initialize account for mining LM rewards
@@ -247,13 +273,15 @@ pub enum DepositorInstruction {
     /// [W] Liquidity transit account
     /// [W] Liquidity reserve transit account
     /// [R] Liquidity mint
-    /// [S] Rebalance executor account
+    /// [S] Rebalance executor account, or any keeper with permissionless crank
     /// [R] Clock sysvar
     /// [R] Token program id
     /// [R] Money market program id
     /// [R] Internal mining account
     /// [] Money market deposit accounts
     /// [] Collateral storage accounts or money market mining accounts
+    /// [W] Keeper token account - optional, receives the keeper step reward
+    /// [W] Keeper reward transit - optional, receives the keeper share of the income
     RefreshMMIncomes,
 
     /// Migrate Rebalancing
@@ -266,6 +294,133 @@ pub enum DepositorInstruction {
     /// [R] Rent sysvar
     /// [R] System program
     MigrateRebalancing,
+
+    /// Set per market limits of rebalancing
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [R] Depositor
+    /// [W] Rebalancing limits account
+    /// [R] Token mint
+    /// [WS] Manager
+    /// [R] Rent sysvar
+    /// [R] System program
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
+    SetRebalancingLimits {
+        /// Limits keyed by money market program id
+        limits: Vec<MarketLimit>,
+        /// Max age of oracle reserve rates in slots
+        reserve_rates_window: Slot,
+        /// Max deviation of oracle reserve rates from money market exchange rates
+        max_reserve_rate_deviation: u64,
+    },
+
+    /// Withdraw all collateral of the money market and disable it in rebalancing
+    ///
+    /// Accounts:
+    /// [R] Registry config
+    /// [R] Registry guardian
+    /// [R] Depositor
+    /// [R] Depositor authority
+    /// [W] Rebalancing account
+    /// [R] Income pool market
+    /// [R] Income pool
+    /// [W] Income pool token account (for liquidity mint)
+    /// [W] Collateral transit account
+    /// [W] Collateral mint
+    /// [W] Liquidity transit account
+    /// [W] Liquidity reserve transit account
+    /// [R] Liquidity mint
+    /// [S] Manager or guardian
+    /// [R] Clock sysvar
+    /// [R] Token program id
+    /// [R] Everlend income pools program id
+    /// [R] Money market program id
+    /// [R] Internal mining account
+    /// [] Money market withdraw accounts
+    /// [] Collateral storage accounts or money market mining accounts
+    EmergencyWithdraw {
+        /// Index of the money market in the registry market list
+        money_market_index: u8,
+    },
+
+    /// Enable the money market disabled by emergency withdraw
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [R] Depositor
+    /// [W] Rebalancing account
+    /// [R] Token mint
+    /// [S] Manager
+    EnableMarket {
+        /// Index of the money market in the registry market list
+        money_market_index: u8,
+    },
+
+    /// Set permissionless crank of rebalancing and keeper reward
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [R] Depositor
+    /// [W] Rebalancing limits account
+    /// [R] Token mint
+    /// [WS] Manager
+    /// [R] Rent sysvar
+    /// [R] System program
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
+    SetKeeperConfig {
+        /// Anyone can execute the computed rebalancing and compute it from a fresh oracle update
+        permissionless_crank: bool,
+        /// Share of the withdrawal income paid to the keeper reward transit (PRECISION_SCALER)
+        keeper_reward_share: u64,
+        /// Reward paid to the keeper for each executed step from the keeper reward transit
+        keeper_step_reward: u64,
+    },
+
+    /// Switch rebalancing to the yield distribution computed on-chain
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [R] Depositor
+    /// [W] Rebalancing limits account
+    /// [R] Token mint
+    /// [WS] Manager
+    /// [R] Rent sysvar
+    /// [R] System program
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
+    SetYieldOptimization {
+        /// Rebalancing follows the yield distribution
+        yield_optimization: bool,
+    },
+
+    /// Compute distribution maximizing blended supply rate from money market reserves
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [R] Depositor
+    /// [R] Rebalancing account
+    /// [R] Rebalancing limits account
+    /// [W] Yield distribution account
+    /// [R] Token mint
+    /// [R] General pool market
+    /// [R] General pool
+    /// [R] General pool token account
+    /// [R] Withdrawal requests account
+    /// [R] Liquidity transit account
+    /// [WS] Rebalance executor account
+    /// [R] Rent sysvar
+    /// [R] Clock sysvar
+    /// [R] System program
+    /// For each market following the registry market list order:
+    /// [R] Money market reserve
+    /// [R] Reserve collateral storage: collateral pool or internal mining
+    UpdateYieldDistribution,
 }
 
 /// Creates 'Init' instruction.
@@ -348,6 +503,11 @@ pub fn start_rebalancing(
         mint: mint.clone(),
     }
     .find_address(program_id);
+    let (rebalancing_limits, _) = RebalancingLimitsPDA {
+        depositor: depositor.clone(),
+        mint: mint.clone(),
+    }
+    .find_address(program_id);
     let (token_oracle, _) =
         find_token_oracle_program_address(&everlend_liquidity_oracle::id(), liquidity_oracle, mint);
     // General pool
@@ -358,6 +518,7 @@ pub fn start_rebalancing(
         general_pool_market,
         mint,
     );
+    let (global_pause, pool_pause) = find_pause_addresses(registry, &general_pool);
     let (general_pool_borrow_authority, _) =
         everlend_general_pool::find_pool_borrow_authority_program_address(
             &everlend_general_pool::id(),
@@ -381,10 +542,13 @@ pub fn start_rebalancing(
         AccountMeta::new_readonly(*depositor, false),
         AccountMeta::new_readonly(depositor_authority, false),
         AccountMeta::new(rebalancing, false),
+        AccountMeta::new_readonly(rebalancing_limits, false),
         AccountMeta::new_readonly(*mint, false),
         AccountMeta::new_readonly(*general_pool_market, false),
         AccountMeta::new_readonly(general_pool_market_authority, false),
         AccountMeta::new(general_pool, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         AccountMeta::new(*general_pool_token_account, false),
         AccountMeta::new(general_pool_borrow_authority, false),
         AccountMeta::new_readonly(withdrawal_requests, false),
@@ -406,6 +570,46 @@ pub fn start_rebalancing(
     )
 }
 
+/// Safety fund accounts appended to the 'StartRebalancing' instruction to cover unsettled loss
+pub fn safety_fund_accounts(income_pool_market: &Pubkey, mint: &Pubkey) -> Vec<AccountMeta> {
+    let (income_pool_market_authority, _) =
+        find_program_address(&everlend_income_pools::id(), income_pool_market);
+    let (safety_fund_ledger, _) = everlend_income_pools::find_safety_fund_ledger_address(
+        &everlend_income_pools::id(),
+        income_pool_market,
+        mint,
+    );
+    let (safety_fund_token_account, _) =
+        everlend_income_pools::find_safety_fund_token_account_address(
+            &everlend_income_pools::id(),
+            income_pool_market,
+            mint,
+        );
+
+    vec![
+        AccountMeta::new_readonly(*income_pool_market, false),
+        AccountMeta::new_readonly(income_pool_market_authority, false),
+        AccountMeta::new(safety_fund_ledger, false),
+        AccountMeta::new(safety_fund_token_account, false),
+        AccountMeta::new_readonly(everlend_income_pools::id(), false),
+    ]
+}
+
+/// Yield distribution account appended to the 'StartRebalancing' instruction with yield optimization
+pub fn yield_distribution_accounts(
+    program_id: &Pubkey,
+    depositor: &Pubkey,
+    mint: &Pubkey,
+) -> Vec<AccountMeta> {
+    let (yield_distribution, _) = YieldDistributionPDA {
+        depositor: *depositor,
+        mint: *mint,
+    }
+    .find_address(program_id);
+
+    vec![AccountMeta::new_readonly(yield_distribution, false)]
+}
+
 /// Creates 'ResetRebalancing' instruction.
 #[allow(clippy::too_many_arguments)]
 pub fn reset_rebalancing(
@@ -442,11 +646,313 @@ pub fn reset_rebalancing(
     )
 }
 
+/// Creates 'SetRebalancingLimits' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn set_rebalancing_limits(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    depositor: &Pubkey,
+    liquidity_mint: &Pubkey,
+    manager: &Pubkey,
+    limits: Vec<MarketLimit>,
+    reserve_rates_window: Slot,
+    max_reserve_rate_deviation: u64,
+) -> Instruction {
+    let (rebalancing_limits, _) = RebalancingLimitsPDA {
+        depositor: *depositor,
+        mint: *liquidity_mint,
+    }
+    .find_address(program_id);
+    let accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(*depositor, false),
+        AccountMeta::new(rebalancing_limits, false),
+        AccountMeta::new_readonly(*liquidity_mint, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &DepositorInstruction::SetRebalancingLimits {
+            limits,
+            reserve_rates_window,
+            max_reserve_rate_deviation,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'SetKeeperConfig' instruction.
+pub fn set_keeper_config(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    depositor: &Pubkey,
+    liquidity_mint: &Pubkey,
+    manager: &Pubkey,
+    permissionless_crank: bool,
+    keeper_reward_share: u64,
+    keeper_step_reward: u64,
+) -> Instruction {
+    let (rebalancing_limits, _) = RebalancingLimitsPDA {
+        depositor: *depositor,
+        mint: *liquidity_mint,
+    }
+    .find_address(program_id);
+    let accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(*depositor, false),
+        AccountMeta::new(rebalancing_limits, false),
+        AccountMeta::new_readonly(*liquidity_mint, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &DepositorInstruction::SetKeeperConfig {
+            permissionless_crank,
+            keeper_reward_share,
+            keeper_step_reward,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'SetYieldOptimization' instruction.
+pub fn set_yield_optimization(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    depositor: &Pubkey,
+    liquidity_mint: &Pubkey,
+    manager: &Pubkey,
+    yield_optimization: bool,
+) -> Instruction {
+    let (rebalancing_limits, _) = RebalancingLimitsPDA {
+        depositor: *depositor,
+        mint: *liquidity_mint,
+    }
+    .find_address(program_id);
+    let accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(*depositor, false),
+        AccountMeta::new(rebalancing_limits, false),
+        AccountMeta::new_readonly(*liquidity_mint, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &DepositorInstruction::SetYieldOptimization { yield_optimization },
+        accounts,
+    )
+}
+
+/// Creates 'UpdateYieldDistribution' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn update_yield_distribution(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    depositor: &Pubkey,
+    liquidity_mint: &Pubkey,
+    general_pool_market: &Pubkey,
+    general_pool_token_account: &Pubkey,
+    rebalance_executor: &Pubkey,
+    reserves: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let (rebalancing, _) = RebalancingPDA {
+        depositor: *depositor,
+        mint: *liquidity_mint,
+    }
+    .find_address(program_id);
+    let (rebalancing_limits, _) = RebalancingLimitsPDA {
+        depositor: *depositor,
+        mint: *liquidity_mint,
+    }
+    .find_address(program_id);
+    let (yield_distribution, _) = YieldDistributionPDA {
+        depositor: *depositor,
+        mint: *liquidity_mint,
+    }
+    .find_address(program_id);
+    let (general_pool, _) = everlend_general_pool::find_pool_program_address(
+        &everlend_general_pool::id(),
+        general_pool_market,
+        liquidity_mint,
+    );
+    let (withdrawal_requests, _) = find_withdrawal_requests_program_address(
+        &everlend_general_pool::id(),
+        general_pool_market,
+        liquidity_mint,
+    );
+    let (liquidity_transit, _) = TransitPDA {
+        seed: "",
+        depositor: *depositor,
+        mint: *liquidity_mint,
+    }
+    .find_address(program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(*depositor, false),
+        AccountMeta::new_readonly(rebalancing, false),
+        AccountMeta::new_readonly(rebalancing_limits, false),
+        AccountMeta::new(yield_distribution, false),
+        AccountMeta::new_readonly(*liquidity_mint, false),
+        AccountMeta::new_readonly(*general_pool_market, false),
+        AccountMeta::new_readonly(general_pool, false),
+        AccountMeta::new_readonly(*general_pool_token_account, false),
+        AccountMeta::new_readonly(withdrawal_requests, false),
+        AccountMeta::new_readonly(liquidity_transit, false),
+        AccountMeta::new(*rebalance_executor, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    for (reserve, collateral_storage) in reserves {
+        accounts.push(AccountMeta::new_readonly(*reserve, false));
+        accounts.push(AccountMeta::new_readonly(*collateral_storage, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &DepositorInstruction::UpdateYieldDistribution,
+        accounts,
+    )
+}
+
+/// Creates 'EmergencyWithdraw' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn emergency_withdraw(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    depositor: &Pubkey,
+    income_pool_market: &Pubkey,
+    income_pool_token_account: &Pubkey,
+    collateral_mint: &Pubkey,
+    liquidity_mint: &Pubkey,
+    authority: &Pubkey,
+    money_market_program_id: &Pubkey,
+    money_market_index: u8,
+    money_market_accounts: Vec<AccountMeta>,
+    collateral_storage_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let (registry_guardian, _) = find_guardian_program_address(&everlend_registry::id(), registry);
+    let (depositor_authority, _) = find_program_address(program_id, depositor);
+    let (rebalancing, _) = RebalancingPDA {
+        depositor: depositor.clone(),
+        mint: liquidity_mint.clone(),
+    }
+    .find_address(program_id);
+    // Income pool
+    let (income_pool, _) = everlend_income_pools::find_pool_program_address(
+        &everlend_income_pools::id(),
+        income_pool_market,
+        liquidity_mint,
+    );
+
+    let (collateral_transit, _) = TransitPDA {
+        seed: "",
+        depositor: depositor.clone(),
+        mint: collateral_mint.clone(),
+    }
+    .find_address(program_id);
+    let (liquidity_transit, _) = TransitPDA {
+        seed: "",
+        depositor: depositor.clone(),
+        mint: liquidity_mint.clone(),
+    }
+    .find_address(program_id);
+
+    let (liquidity_reserve_transit, _) = TransitPDA {
+        seed: "reserve",
+        depositor: depositor.clone(),
+        mint: liquidity_mint.clone(),
+    }
+    .find_address(program_id);
+
+    let (internal_mining, _) = InternalMiningPDA {
+        liquidity_mint: liquidity_mint.clone(),
+        collateral_mint: collateral_mint.clone(),
+        depositor: depositor.clone(),
+    }
+    .find_address(program_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(registry_guardian, false),
+        AccountMeta::new_readonly(*depositor, false),
+        AccountMeta::new_readonly(depositor_authority, false),
+        AccountMeta::new(rebalancing, false),
+        // Income pool
+        AccountMeta::new_readonly(*income_pool_market, false),
+        AccountMeta::new_readonly(income_pool, false),
+        AccountMeta::new(*income_pool_token_account, false),
+        // Common
+        AccountMeta::new(collateral_transit, false),
+        AccountMeta::new(*collateral_mint, false),
+        AccountMeta::new(liquidity_transit, false),
+        AccountMeta::new(liquidity_reserve_transit, false),
+        AccountMeta::new_readonly(*liquidity_mint, false),
+        AccountMeta::new_readonly(*authority, true),
+        // Programs
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(everlend_income_pools::id(), false),
+        // Money market
+        AccountMeta::new_readonly(*money_market_program_id, false),
+        AccountMeta::new_readonly(internal_mining, false),
+    ];
+
+    accounts.extend(money_market_accounts);
+    accounts.extend(collateral_storage_accounts);
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &DepositorInstruction::EmergencyWithdraw { money_market_index },
+        accounts,
+    )
+}
+
+/// Creates 'EnableMarket' instruction.
+pub fn enable_market(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    depositor: &Pubkey,
+    liquidity_mint: &Pubkey,
+    manager: &Pubkey,
+    money_market_index: u8,
+) -> Instruction {
+    let (rebalancing, _) = RebalancingPDA {
+        depositor: depositor.clone(),
+        mint: liquidity_mint.clone(),
+    }
+    .find_address(program_id);
+    let accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(*depositor, false),
+        AccountMeta::new(rebalancing, false),
+        AccountMeta::new_readonly(*liquidity_mint, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &DepositorInstruction::EnableMarket { money_market_index },
+        accounts,
+    )
+}
+
 /// Creates 'Deposit' instruction.
 #[allow(clippy::too_many_arguments)]
 pub fn deposit(
     program_id: &Pubkey,
     registry: &Pubkey,
+    general_pool_market: &Pubkey,
     depositor: &Pubkey,
     liquidity_mint: &Pubkey,
     collateral_mint: &Pubkey,
@@ -479,12 +985,26 @@ pub fn deposit(
         depositor: depositor.clone(),
     }
     .find_address(program_id);
+    let (rebalancing_limits, _) = RebalancingLimitsPDA {
+        depositor: *depositor,
+        mint: *liquidity_mint,
+    }
+    .find_address(program_id);
+    let (general_pool, _) = everlend_general_pool::find_pool_program_address(
+        &everlend_general_pool::id(),
+        general_pool_market,
+        liquidity_mint,
+    );
+    let (global_pause, pool_pause) = find_pause_addresses(registry, &general_pool);
 
     let mut accounts = vec![
         AccountMeta::new_readonly(*registry, false),
         AccountMeta::new_readonly(*depositor, false),
         AccountMeta::new_readonly(depositor_authority, false),
         AccountMeta::new(rebalancing, false),
+        AccountMeta::new_readonly(rebalancing_limits, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         // Common
         AccountMeta::new(liquidity_transit, false),
         AccountMeta::new_readonly(*liquidity_mint, false),
@@ -510,6 +1030,7 @@ pub fn deposit(
 pub fn withdraw(
     program_id: &Pubkey,
     registry: &Pubkey,
+    general_pool_market: &Pubkey,
     depositor: &Pubkey,
     income_pool_market: &Pubkey,
     income_pool_token_account: &Pubkey,
@@ -559,12 +1080,20 @@ pub fn withdraw(
         depositor: depositor.clone(),
     }
     .find_address(program_id);
+    let (general_pool, _) = everlend_general_pool::find_pool_program_address(
+        &everlend_general_pool::id(),
+        general_pool_market,
+        liquidity_mint,
+    );
+    let (global_pause, pool_pause) = find_pause_addresses(registry, &general_pool);
 
     let mut accounts = vec![
         AccountMeta::new_readonly(*registry, false),
         AccountMeta::new_readonly(*depositor, false),
         AccountMeta::new_readonly(depositor_authority, false),
         AccountMeta::new(rebalancing, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         // Income pool
         AccountMeta::new_readonly(*income_pool_market, false),
         AccountMeta::new_readonly(income_pool, false),
@@ -1,7 +1,7 @@
 use crate::{
-    state::{Depositor, Rebalancing, RebalancingOperation},
-    utils::{collateral_storage, deposit, money_market},
-    InternalMiningPDA, RebalancingPDA, TransitPDA,
+    state::{Depositor, Rebalancing, RebalancingLimits, RebalancingOperation},
+    utils::{collateral_storage, deposit, keeper_reward, money_market, pause_flags},
+    InternalMiningPDA, RebalancingLimitsPDA, RebalancingPDA, TransitPDA,
 };
 use everlend_registry::state::RegistryMarkets;
 use everlend_utils::{assert_account_key, find_program_address, AccountLoader, EverlendError, PDA};
@@ -17,6 +17,9 @@ pub struct DepositContext<'a, 'b> {
     depositor: &'a AccountInfo<'b>,
     depositor_authority: &'a AccountInfo<'b>,
     rebalancing: &'a AccountInfo<'b>,
+    rebalancing_limits: &'a AccountInfo<'b>,
+    global_pause: &'a AccountInfo<'b>,
+    pool_pause: &'a AccountInfo<'b>,
 
     collateral_transit: &'a AccountInfo<'b>,
     collateral_mint: &'a AccountInfo<'b>,
@@ -42,6 +45,10 @@ impl<'a, 'b> DepositContext<'a, 'b> {
         let depositor = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let depositor_authority = AccountLoader::next_unchecked(account_info_iter)?; //Signer PDA
         let rebalancing = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let rebalancing_limits = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let global_pause =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let pool_pause = AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
 
         let liquidity_transit =
             AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
@@ -64,6 +71,9 @@ impl<'a, 'b> DepositContext<'a, 'b> {
             depositor,
             depositor_authority,
             rebalancing,
+            rebalancing_limits,
+            global_pause,
+            pool_pause,
             collateral_transit,
             collateral_mint,
             liquidity_transit,
@@ -81,11 +91,17 @@ impl<'a, 'b> DepositContext<'a, 'b> {
         program_id: &Pubkey,
         account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
     ) -> ProgramResult {
-        {
-            let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
-
-            assert_account_key(self.executor, &depositor.rebalance_executor)?;
-            assert_account_key(self.registry, &depositor.registry)?;
+        let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
+        assert_account_key(self.registry, &depositor.registry)?;
+
+        let flags = pause_flags(
+            self.registry,
+            self.liquidity_mint.key,
+            self.global_pause,
+            self.pool_pause,
+        )?;
+        if flags.deposit || flags.rebalancing {
+            return Err(EverlendError::OperationPaused.into());
         }
 
         {
@@ -96,6 +112,14 @@ impl<'a, 'b> DepositContext<'a, 'b> {
             }
             .find_address(program_id);
             assert_account_key(self.rebalancing, &rebalancing_pubkey)?;
+
+            // Check rebalancing limits
+            let (rebalancing_limits_pubkey, _) = RebalancingLimitsPDA {
+                depositor: *self.depositor.key,
+                mint: *self.liquidity_mint.key,
+            }
+            .find_address(program_id);
+            assert_account_key(self.rebalancing_limits, &rebalancing_limits_pubkey)?;
         }
 
         let mut rebalancing = Rebalancing::unpack(&self.rebalancing.data.borrow())?;
@@ -106,6 +130,10 @@ impl<'a, 'b> DepositContext<'a, 'b> {
             return Err(EverlendError::RebalancingIsCompleted.into());
         }
 
+        if !rebalancing.permissionless_crank {
+            assert_account_key(self.executor, &depositor.rebalance_executor)?;
+        }
+
         {
             // Check transit: liquidity
             let (liquidity_transit_pubkey, _) = TransitPDA {
@@ -163,6 +191,7 @@ impl<'a, 'b> DepositContext<'a, 'b> {
 
         let collateral_stor = collateral_storage(
             &registry_markets,
+            self.registry,
             self.collateral_mint,
             self.depositor_authority,
             account_info_iter,
@@ -170,6 +199,13 @@ impl<'a, 'b> DepositContext<'a, 'b> {
             is_mining,
         )?;
 
+        let keeper_reward = keeper_reward(
+            program_id,
+            &rebalancing,
+            self.liquidity_mint,
+            account_info_iter,
+        )?;
+
         {
             let step = rebalancing.next_step();
 
@@ -177,12 +213,34 @@ impl<'a, 'b> DepositContext<'a, 'b> {
                 return Err(EverlendError::InvalidRebalancingOperation.into());
             }
 
-            if registry_markets.money_markets[usize::from(step.money_market_index)]
-                != *self.money_market_program.key
-            {
+            let market = registry_markets.get(usize::from(step.money_market_index))?;
+            if market.money_market != *self.money_market_program.key {
                 return Err(EverlendError::InvalidRebalancingMoneyMarket.into());
             }
+
+            if !market.is_active() {
+                return Err(EverlendError::MoneyMarketNotActive.into());
+            }
+
             money_market.refresh_reserve(self.clock.clone())?;
+
+            // Compare oracle reserve rate with money market exchange rate
+            if let Some(expected_collateral_amount) = step.collateral_amount {
+                if let Some(real_liquidity_amount) =
+                    money_market.real_liquidity_amount(expected_collateral_amount)?
+                {
+                    let rebalancing_limits = match self.rebalancing_limits.lamports() {
+                        0 => RebalancingLimits::default(),
+                        _ => RebalancingLimits::unpack(&self.rebalancing_limits.data.borrow())?,
+                    };
+
+                    rebalancing_limits.check_reserve_rate_deviation(
+                        step.liquidity_amount,
+                        real_liquidity_amount,
+                    )?;
+                }
+            }
+
             msg!("Deposit");
             let collateral_amount = deposit(
                 self.collateral_transit,
@@ -206,6 +264,10 @@ impl<'a, 'b> DepositContext<'a, 'b> {
             )?;
         }
 
+        if let Some(keeper_reward) = keeper_reward {
+            keeper_reward.pay_step_reward(self.depositor_authority, &[signers_seeds])?;
+        }
+
         Rebalancing::pack(rebalancing, *self.rebalancing.data.borrow_mut())?;
 
         Ok(())
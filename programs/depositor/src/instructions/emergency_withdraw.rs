@@ -0,0 +1,272 @@
+use crate::{
+    state::{Depositor, Rebalancing},
+    utils::{collateral_storage, money_market, withdraw},
+    InternalMiningPDA, RebalancingPDA, TransitPDA,
+};
+use everlend_income_pools::utils::IncomePoolAccounts;
+use everlend_registry::{
+    find_guardian_program_address,
+    state::{Registry, RegistryGuardian, RegistryMarkets},
+};
+use everlend_utils::{assert_account_key, find_program_address, AccountLoader, EverlendError, PDA};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, sysvar::clock, sysvar::Sysvar,
+};
+use std::{iter::Enumerate, slice::Iter};
+
+/// Instruction context
+pub struct EmergencyWithdrawContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    registry_guardian: &'a AccountInfo<'b>,
+    depositor: &'a AccountInfo<'b>,
+    depositor_authority: &'a AccountInfo<'b>,
+    rebalancing: &'a AccountInfo<'b>,
+
+    collateral_transit: &'a AccountInfo<'b>,
+    collateral_mint: &'a AccountInfo<'b>,
+
+    liquidity_transit: &'a AccountInfo<'b>,
+    liquidity_reserve_transit: &'a AccountInfo<'b>,
+    liquidity_mint: &'a AccountInfo<'b>,
+
+    clock: &'a AccountInfo<'b>,
+    authority: &'a AccountInfo<'b>,
+    internal_mining: &'a AccountInfo<'b>,
+
+    money_market_program: &'a AccountInfo<'b>,
+
+    income_pool_accounts: IncomePoolAccounts<'a, 'b>,
+}
+
+impl<'a, 'b> EmergencyWithdrawContext<'a, 'b> {
+    /// New EmergencyWithdraw instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+    ) -> Result<EmergencyWithdrawContext<'a, 'b>, ProgramError> {
+        let registry = AccountLoader::next_with_owner(account_info_iter, &everlend_registry::id())?;
+        let registry_guardian =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+
+        let depositor = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let depositor_authority = AccountLoader::next_unchecked(account_info_iter)?; //Signer PDA
+        let rebalancing = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+
+        let income_pool_market =
+            AccountLoader::next_with_owner(account_info_iter, &everlend_income_pools::id())?;
+        let income_pool =
+            AccountLoader::next_with_owner(account_info_iter, &everlend_income_pools::id())?;
+        let income_pool_token_account =
+            AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let income_pool_accounts = IncomePoolAccounts {
+            pool_market: income_pool_market,
+            pool: income_pool,
+            token_account: income_pool_token_account,
+        };
+
+        let collateral_transit =
+            AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let collateral_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let liquidity_transit =
+            AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let liquidity_reserve_transit =
+            AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let liquidity_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+
+        let authority = AccountLoader::next_signer(account_info_iter)?;
+
+        let clock = AccountLoader::next_with_key(account_info_iter, &clock::id())?;
+
+        let _token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
+        let _everlend_income_pools =
+            AccountLoader::next_with_key(account_info_iter, &everlend_income_pools::id())?;
+
+        let money_market_program = AccountLoader::next_unchecked(account_info_iter)?;
+
+        let internal_mining = AccountLoader::next_optional(account_info_iter, program_id)?;
+
+        Ok(EmergencyWithdrawContext {
+            registry,
+            registry_guardian,
+            depositor,
+            depositor_authority,
+            rebalancing,
+            collateral_transit,
+            collateral_mint,
+            liquidity_transit,
+            liquidity_reserve_transit,
+            liquidity_mint,
+            internal_mining,
+            authority,
+            money_market_program,
+            clock,
+            income_pool_accounts,
+        })
+    }
+
+    /// Process EmergencyWithdraw instruction
+    pub fn process(
+        &self,
+        program_id: &Pubkey,
+        account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+        money_market_index: u8,
+    ) -> ProgramResult {
+        {
+            let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
+            assert_account_key(self.registry, &depositor.registry)?;
+        }
+
+        {
+            // Check manager or guardian
+            let registry = Registry::unpack(&self.registry.data.borrow())?;
+            if !self.authority.key.eq(&registry.manager) {
+                let (registry_guardian_pubkey, _) =
+                    find_guardian_program_address(&everlend_registry::id(), self.registry.key);
+                assert_account_key(self.registry_guardian, &registry_guardian_pubkey)?;
+
+                let registry_guardian =
+                    RegistryGuardian::unpack(&self.registry_guardian.data.borrow())?;
+                assert_account_key(self.authority, &registry_guardian.guardian)?;
+            }
+        }
+
+        let registry_markets = RegistryMarkets::unpack_from_slice(&self.registry.data.borrow())?;
+
+        {
+            // Check rebalancing
+            let (rebalancing_pubkey, _) = RebalancingPDA {
+                depositor: *self.depositor.key,
+                mint: *self.liquidity_mint.key,
+            }
+            .find_address(program_id);
+            assert_account_key(self.rebalancing, &rebalancing_pubkey)?;
+        }
+
+        let mut rebalancing = Rebalancing::unpack(&self.rebalancing.data.borrow())?;
+        assert_account_key(self.depositor, &rebalancing.depositor)?;
+        assert_account_key(self.liquidity_mint, &rebalancing.mint)?;
+
+        {
+            // Check transit: liquidity
+            let (liquidity_transit_pubkey, _) = TransitPDA {
+                seed: "",
+                depositor: *self.depositor.key,
+                mint: *self.liquidity_mint.key,
+            }
+            .find_address(program_id);
+            assert_account_key(self.liquidity_transit, &liquidity_transit_pubkey)?;
+        }
+
+        {
+            // Check transit: liquidity reserve
+            let (liquidity_reserve_transit_pubkey, _) = TransitPDA {
+                seed: "reserve",
+                depositor: *self.depositor.key,
+                mint: *self.liquidity_mint.key,
+            }
+            .find_address(program_id);
+            assert_account_key(
+                self.liquidity_reserve_transit,
+                &liquidity_reserve_transit_pubkey,
+            )?;
+        }
+
+        {
+            // Check transit: collateral
+            let (collateral_transit_pubkey, _) = TransitPDA {
+                seed: "",
+                depositor: *self.depositor.key,
+                mint: *self.collateral_mint.key,
+            }
+            .find_address(program_id);
+            assert_account_key(self.collateral_transit, &collateral_transit_pubkey)?;
+        }
+
+        // Create depositor authority account
+        let signers_seeds = {
+            let (depositor_authority_pubkey, bump_seed) =
+                find_program_address(program_id, self.depositor.key);
+            assert_account_key(self.depositor_authority, &depositor_authority_pubkey)?;
+            &[&self.depositor.key.to_bytes()[..32], &[bump_seed]]
+        };
+
+        if !registry_markets
+            .get(usize::from(money_market_index))?
+            .money_market
+            .eq(self.money_market_program.key)
+        {
+            return Err(EverlendError::InvalidRebalancingMoneyMarket.into());
+        }
+
+        {
+            // Check internal mining account
+            let (internal_mining_pubkey, _) = InternalMiningPDA {
+                liquidity_mint: *self.liquidity_mint.key,
+                collateral_mint: *self.collateral_mint.key,
+                depositor: *self.depositor.key,
+            }
+            .find_address(program_id);
+            assert_account_key(self.internal_mining, &internal_mining_pubkey)?;
+        }
+
+        let clock = Clock::from_account_info(self.clock)?;
+
+        let step = rebalancing.emergency_withdraw(usize::from(money_market_index), clock.slot)?;
+        let collateral_amount = step.collateral_amount.unwrap();
+
+        // Nothing to withdraw, the market is only disabled
+        if collateral_amount > 0 {
+            let (money_market, is_mining) = money_market(
+                &registry_markets,
+                program_id,
+                self.money_market_program,
+                account_info_iter,
+                self.internal_mining,
+                self.collateral_mint.key,
+                self.depositor_authority.key,
+                self.depositor.key,
+                self.liquidity_mint,
+            )?;
+
+            let collateral_stor = collateral_storage(
+                &registry_markets,
+                self.registry,
+                self.collateral_mint,
+                self.depositor_authority,
+                account_info_iter,
+                true,
+                is_mining,
+            )?;
+
+            money_market.refresh_reserve(self.clock.clone())?;
+
+            msg!("Emergency withdraw");
+            let loss = withdraw(
+                self.income_pool_accounts,
+                self.collateral_transit,
+                self.collateral_mint,
+                self.liquidity_transit,
+                self.liquidity_reserve_transit,
+                self.depositor_authority,
+                self.clock,
+                &money_market,
+                is_mining,
+                &collateral_stor,
+                collateral_amount,
+                step.liquidity_amount,
+                None,
+                &[signers_seeds],
+            )?;
+
+            if loss > 0 {
+                rebalancing.add_loss(loss)?;
+                rebalancing.reduce_pending_deposits(loss)?;
+            }
+        }
+
+        Rebalancing::pack(rebalancing, *self.rebalancing.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    state::{Depositor, Rebalancing},
+    RebalancingPDA,
+};
+use everlend_registry::state::{Registry, RegistryMarkets};
+use everlend_utils::{assert_account_key, AccountLoader, PDA};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+use std::{iter::Enumerate, slice::Iter};
+
+/// Instruction context
+pub struct EnableMarketContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    depositor: &'a AccountInfo<'b>,
+    rebalancing: &'a AccountInfo<'b>,
+    liquidity_mint: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> EnableMarketContext<'a, 'b> {
+    /// New EnableMarket instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+    ) -> Result<EnableMarketContext<'a, 'b>, ProgramError> {
+        let registry = AccountLoader::next_with_owner(account_info_iter, &everlend_registry::id())?;
+        let depositor = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let rebalancing = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let liquidity_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+
+        Ok(EnableMarketContext {
+            registry,
+            depositor,
+            rebalancing,
+            liquidity_mint,
+            manager,
+        })
+    }
+
+    /// Process EnableMarket instruction
+    pub fn process(
+        &self,
+        program_id: &Pubkey,
+        _account_info_iter: &'a mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+        money_market_index: u8,
+    ) -> ProgramResult {
+        // Get depositor state
+        let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
+        // Check registry
+        assert_account_key(self.registry, &depositor.registry)?;
+
+        let registry = Registry::unpack(&self.registry.data.borrow())?;
+        // Check manager
+        assert_account_key(self.manager, &registry.manager)?;
+
+        let registry_markets = RegistryMarkets::unpack_from_slice(&self.registry.data.borrow())?;
+        registry_markets.get(usize::from(money_market_index))?;
+
+        // Check rebalancing
+        let (rebalancing_pubkey, _) = RebalancingPDA {
+            depositor: *self.depositor.key,
+            mint: *self.liquidity_mint.key,
+        }
+        .find_address(program_id);
+        assert_account_key(self.rebalancing, &rebalancing_pubkey)?;
+
+        let mut rebalancing = Rebalancing::unpack(&self.rebalancing.data.borrow())?;
+        // Check rebalancing accounts
+        assert_account_key(self.depositor, &rebalancing.depositor)?;
+        assert_account_key(self.liquidity_mint, &rebalancing.mint)?;
+
+        rebalancing.set_disabled(usize::from(money_market_index), false);
+
+        Rebalancing::pack(rebalancing, *self.rebalancing.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -129,10 +129,7 @@ impl<'a, 'b> InitMiningAccountContext<'a, 'b> {
                         everlend_registry::state::RegistryMarkets::unpack_from_slice(
                             &self.registry.data.borrow(),
                         )?;
-                    if !registry_markets
-                        .money_markets
-                        .contains(self.staking_program_id.key)
-                    {
+                    if !registry_markets.contains_money_market(self.staking_program_id.key) {
                         return Err(ProgramError::InvalidArgument);
                     }
                 }
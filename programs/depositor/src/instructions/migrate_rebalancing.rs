@@ -60,17 +60,7 @@ impl<'a, 'b> MigrateRebalancingContext<'a, 'b> {
             assert_account_key(self.manager, &registry.manager)?;
         }
 
-        let rebalancing = Rebalancing {
-            account_type: rebalancing.account_type,
-            depositor: rebalancing.depositor,
-            mint: rebalancing.mint,
-            amount_to_distribute: rebalancing.amount_to_distribute,
-            distributed_liquidity: rebalancing.distributed_liquidity,
-            received_collateral: rebalancing.received_collateral,
-            liquidity_distribution: rebalancing.liquidity_distribution,
-            steps: rebalancing.steps,
-            income_refreshed_at: rebalancing.income_refreshed_at,
-        };
+        let rebalancing: Rebalancing = rebalancing.into();
 
         realloc_with_rent(
             self.rebalancing,
@@ -2,23 +2,35 @@
 mod claim_mining_reward;
 mod create_transit;
 mod deposit;
+mod emergency_withdraw;
+mod enable_market;
 mod init;
 mod init_mining_account;
 mod migrate_depositor;
 mod migrate_rebalancing;
 mod refresh_mm_incomes;
+mod set_keeper_config;
 mod set_rebalancing;
+mod set_rebalancing_limits;
+mod set_yield_optimization;
 mod start_rebalancing;
+mod update_yield_distribution;
 mod withdraw;
 
 pub use claim_mining_reward::*;
 pub use create_transit::*;
 pub use deposit::*;
+pub use emergency_withdraw::*;
+pub use enable_market::*;
 pub use init::*;
 pub use init_mining_account::*;
 pub use migrate_depositor::*;
 pub use migrate_rebalancing::*;
 pub use refresh_mm_incomes::*;
+pub use set_keeper_config::*;
 pub use set_rebalancing::*;
+pub use set_rebalancing_limits::*;
+pub use set_yield_optimization::*;
 pub use start_rebalancing::*;
+pub use update_yield_distribution::*;
 pub use withdraw::*;
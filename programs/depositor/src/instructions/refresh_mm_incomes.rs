@@ -1,6 +1,6 @@
 use crate::{
     state::{Depositor, Rebalancing, RebalancingOperation},
-    utils::{collateral_storage, deposit, money_market, withdraw},
+    utils::{collateral_storage, deposit, keeper_reward, money_market, withdraw},
     InternalMiningPDA, RebalancingPDA, TransitPDA,
 };
 use everlend_income_pools::utils::IncomePoolAccounts;
@@ -108,12 +108,8 @@ impl<'a, 'b> RefreshMMIncomesContext<'a, 'b> {
         program_id: &Pubkey,
         account_info_iter: &'a mut Enumerate<Iter<'a, AccountInfo<'b>>>,
     ) -> ProgramResult {
-        {
-            let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
-            assert_account_key(self.executor, &depositor.rebalance_executor)?;
-
-            assert_account_key(self.registry, &depositor.registry)?;
-        }
+        let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
+        assert_account_key(self.registry, &depositor.registry)?;
 
         let registry_markets = RegistryMarkets::unpack_from_slice(&self.registry.data.borrow())?;
 
@@ -135,6 +131,10 @@ impl<'a, 'b> RefreshMMIncomesContext<'a, 'b> {
             return Err(EverlendError::RebalancingIsCompleted.into());
         }
 
+        if !rebalancing.permissionless_crank {
+            assert_account_key(self.executor, &depositor.rebalance_executor)?;
+        }
+
         // Check transit: liquidity
         {
             let (liquidity_transit_pubkey, _) = TransitPDA {
@@ -206,6 +206,7 @@ impl<'a, 'b> RefreshMMIncomesContext<'a, 'b> {
 
         let collateral_stor = collateral_storage(
             &registry_markets,
+            self.registry,
             self.collateral_mint,
             self.depositor_authority,
             account_info_iter,
@@ -213,6 +214,13 @@ impl<'a, 'b> RefreshMMIncomesContext<'a, 'b> {
             is_mining,
         )?;
 
+        let keeper_reward = keeper_reward(
+            program_id,
+            &rebalancing,
+            self.liquidity_mint,
+            account_info_iter,
+        )?;
+
         // Check two step operation
         let (withdraw_step, deposit_step) = rebalancing.next_refresh_steps()?;
 
@@ -221,12 +229,15 @@ impl<'a, 'b> RefreshMMIncomesContext<'a, 'b> {
         };
 
         // For both steps money_market is equal so check one of them
-        if !registry_markets.money_markets[usize::from(withdraw_step.money_market_index)]
-            .eq(self.money_market_program.key)
-        {
+        let market = registry_markets.get(usize::from(withdraw_step.money_market_index))?;
+        if !market.money_market.eq(self.money_market_program.key) {
             return Err(EverlendError::InvalidRebalancingMoneyMarket.into());
         }
 
+        if !market.is_active() {
+            return Err(EverlendError::MoneyMarketNotActive.into());
+        }
+
         if withdraw_step.operation != RebalancingOperation::RefreshWithdraw
             || deposit_step.operation != RebalancingOperation::RefreshDeposit
         {
@@ -248,7 +259,7 @@ impl<'a, 'b> RefreshMMIncomesContext<'a, 'b> {
             return Ok(());
         }
         msg!("Refresh Withdraw");
-        withdraw(
+        let loss = withdraw(
             self.income_pool_accounts,
             self.collateral_transit,
             self.collateral_mint,
@@ -261,10 +272,19 @@ impl<'a, 'b> RefreshMMIncomesContext<'a, 'b> {
             &collateral_stor,
             withdraw_step.collateral_amount.unwrap(),
             withdraw_step.liquidity_amount,
+            &keeper_reward,
             &[signers_seeds],
         )?;
 
         rebalancing.execute_step(RebalancingOperation::RefreshWithdraw, None, clock.slot)?;
+        rebalancing.add_loss(loss)?;
+
+        // Lost liquidity can't be deposited back
+        let deposit_amount = if loss > 0 {
+            rebalancing.reduce_refresh_deposit(loss)?
+        } else {
+            deposit_step.liquidity_amount
+        };
 
         money_market.refresh_reserve(self.clock.clone())?;
         msg!("Refresh Deposit");
@@ -277,7 +297,7 @@ impl<'a, 'b> RefreshMMIncomesContext<'a, 'b> {
             &money_market,
             is_mining,
             collateral_stor,
-            deposit_step.liquidity_amount,
+            deposit_amount,
             &[signers_seeds],
         )?;
 
@@ -287,6 +307,10 @@ impl<'a, 'b> RefreshMMIncomesContext<'a, 'b> {
             clock.slot,
         )?;
 
+        if let Some(keeper_reward) = keeper_reward {
+            keeper_reward.pay_step_reward(self.depositor_authority, &[signers_seeds])?;
+        }
+
         Rebalancing::pack(rebalancing, *self.rebalancing.data.borrow_mut())?;
 
         Ok(())
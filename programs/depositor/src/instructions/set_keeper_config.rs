@@ -0,0 +1,136 @@
+use crate::{
+    state::{Depositor, InitRebalancingLimitsParams, RebalancingLimits},
+    RebalancingLimitsPDA,
+};
+use everlend_registry::state::{assert_manager_or_role, Registry, Role, RoleAccounts};
+use everlend_utils::{assert_account_key, cpi, AccountLoader, PDA};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey, rent::Rent, system_program, sysvar::Sysvar,
+    sysvar::SysvarId,
+};
+use std::{iter::Enumerate, slice::Iter};
+
+/// Instruction context
+pub struct SetKeeperConfigContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    depositor: &'a AccountInfo<'b>,
+    rebalancing_limits: &'a AccountInfo<'b>,
+    liquidity_mint: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    role_accounts: Option<RoleAccounts<'a, 'b>>,
+}
+
+impl<'a, 'b> SetKeeperConfigContext<'a, 'b> {
+    /// New SetKeeperConfig instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+    ) -> Result<SetKeeperConfigContext<'a, 'b>, ProgramError> {
+        let registry = AccountLoader::next_with_owner(account_info_iter, &everlend_registry::id())?;
+        let depositor = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let rebalancing_limits = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let liquidity_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        let role_accounts = RoleAccounts::next_optional(account_info_iter)?;
+
+        Ok(SetKeeperConfigContext {
+            registry,
+            depositor,
+            rebalancing_limits,
+            liquidity_mint,
+            manager,
+            rent,
+            role_accounts,
+        })
+    }
+
+    /// Process SetKeeperConfig instruction
+    pub fn process(
+        &self,
+        program_id: &Pubkey,
+        _account_info_iter: &'a mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+        permissionless_crank: bool,
+        keeper_reward_share: u64,
+        keeper_step_reward: u64,
+    ) -> ProgramResult {
+        // Get depositor state
+        let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
+        // Check registry
+        assert_account_key(self.registry, &depositor.registry)?;
+
+        let registry = Registry::unpack(&self.registry.data.borrow())?;
+        // Check manager or role
+        if let Some(role_accounts) = &self.role_accounts {
+            assert_account_key(role_accounts.registry, &depositor.registry)?;
+        }
+        assert_manager_or_role(
+            &registry.manager,
+            self.manager,
+            self.role_accounts.as_ref(),
+            Role::Risk,
+        )?;
+
+        let seed = {
+            // Check rebalancing limits
+            let pda = RebalancingLimitsPDA {
+                depositor: *self.depositor.key,
+                mint: *self.liquidity_mint.key,
+            };
+            let (rebalancing_limits_pubkey, bump) = pda.find_address(program_id);
+            assert_account_key(self.rebalancing_limits, &rebalancing_limits_pubkey)?;
+            pda.get_signing_seeds(bump)
+        };
+
+        // Create or get rebalancing limits account
+        let mut rebalancing_limits = match self.rebalancing_limits.lamports() {
+            // Create rebalancing limits account
+            0 => {
+                let rent = &Rent::from_account_info(self.rent)?;
+
+                cpi::system::create_account::<RebalancingLimits>(
+                    program_id,
+                    self.manager.clone(),
+                    self.rebalancing_limits.clone(),
+                    &[&seed.as_seeds_slice()],
+                    rent,
+                )?;
+
+                let mut rebalancing_limits =
+                    RebalancingLimits::unpack_unchecked(&self.rebalancing_limits.data.borrow())?;
+                rebalancing_limits.init(InitRebalancingLimitsParams {
+                    depositor: *self.depositor.key,
+                    mint: *self.liquidity_mint.key,
+                });
+
+                rebalancing_limits
+            }
+            _ => {
+                let rebalancing_limits =
+                    RebalancingLimits::unpack(&self.rebalancing_limits.data.borrow())?;
+                assert_account_key(self.depositor, &rebalancing_limits.depositor)?;
+                assert_account_key(self.liquidity_mint, &rebalancing_limits.mint)?;
+
+                rebalancing_limits
+            }
+        };
+
+        rebalancing_limits.set_keeper_config(
+            permissionless_crank,
+            keeper_reward_share,
+            keeper_step_reward,
+        )?;
+
+        RebalancingLimits::pack(
+            rebalancing_limits,
+            *self.rebalancing_limits.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+}
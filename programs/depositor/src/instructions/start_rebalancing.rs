@@ -1,12 +1,15 @@
 use crate::{
-    state::{Depositor, InitRebalancingParams, Rebalancing},
-    utils::calculate_amount_to_distribute,
-    RebalancingPDA, TransitPDA,
+    state::{Depositor, InitRebalancingParams, Rebalancing, RebalancingLimits, YieldDistribution},
+    utils::{calculate_amount_to_distribute, keeper_reward},
+    RebalancingLimitsPDA, RebalancingPDA, TransitPDA, YieldDistributionPDA,
 };
 use everlend_general_pool::{find_withdrawal_requests_program_address, state::WithdrawalRequests};
+use everlend_income_pools::{
+    find_safety_fund_ledger_address, find_safety_fund_token_account_address,
+};
 
 use everlend_liquidity_oracle::{find_token_oracle_program_address, state::TokenOracle};
-use everlend_registry::state::{Registry, RegistryMarkets};
+use everlend_registry::state::{PauseFlags, Registry, RegistryMarkets};
 use everlend_utils::{
     assert_account_key, cpi, find_program_address, AccountLoader, EverlendError, PDA,
 };
@@ -26,10 +29,13 @@ pub struct StartRebalancingContext<'a, 'b> {
     depositor: &'a AccountInfo<'b>,
     depositor_authority: &'a AccountInfo<'b>,
     rebalancing: &'a AccountInfo<'b>,
+    rebalancing_limits: &'a AccountInfo<'b>,
     mint: &'a AccountInfo<'b>,
     general_pool_market: &'a AccountInfo<'b>,
     general_pool_market_authority: &'a AccountInfo<'b>,
     general_pool: &'a AccountInfo<'b>,
+    global_pause: &'a AccountInfo<'b>,
+    pool_pause: &'a AccountInfo<'b>,
     general_pool_token_account: &'a AccountInfo<'b>,
     general_pool_borrow_authority: &'a AccountInfo<'b>,
     withdrawal_requests: &'a AccountInfo<'b>,
@@ -53,6 +59,7 @@ impl<'a, 'b> StartRebalancingContext<'a, 'b> {
         let depositor_authority = AccountLoader::next_unchecked(account_info_iter)?; //Signer PDA
 
         let rebalancing = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let rebalancing_limits = AccountLoader::next_optional(account_info_iter, program_id)?;
         let mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
 
         let general_pool_market =
@@ -60,6 +67,9 @@ impl<'a, 'b> StartRebalancingContext<'a, 'b> {
         let general_pool_market_authority = AccountLoader::next_unchecked(account_info_iter)?; //PDA signer
         let general_pool =
             AccountLoader::next_with_owner(account_info_iter, &everlend_general_pool::id())?;
+        let global_pause =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let pool_pause = AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
         let general_pool_token_account =
             AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
         let general_pool_borrow_authority = AccountLoader::next_unchecked(account_info_iter)?;
@@ -90,10 +100,13 @@ impl<'a, 'b> StartRebalancingContext<'a, 'b> {
             depositor,
             depositor_authority,
             rebalancing,
+            rebalancing_limits,
             mint,
             general_pool_market,
             general_pool_market_authority,
             general_pool,
+            global_pause,
+            pool_pause,
             general_pool_token_account,
             general_pool_borrow_authority,
             withdrawal_requests,
@@ -110,16 +123,49 @@ impl<'a, 'b> StartRebalancingContext<'a, 'b> {
     pub fn process(
         &self,
         program_id: &Pubkey,
-        _account_info_iter: &'a mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+        account_info_iter: &'a mut Enumerate<Iter<'a, AccountInfo<'b>>>,
         refresh_income: bool,
     ) -> ProgramResult {
+        // Get depositor state
+        let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
+        assert_account_key(self.registry, &depositor.registry)?;
+
         {
-            // Get depositor state
-            let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
+            // Check rebalancing limits
+            let (rebalancing_limits_pubkey, _) = RebalancingLimitsPDA {
+                depositor: *self.depositor.key,
+                mint: *self.mint.key,
+            }
+            .find_address(program_id);
+            assert_account_key(self.rebalancing_limits, &rebalancing_limits_pubkey)?;
+        }
+
+        // Markets are unlimited until limits are set
+        let rebalancing_limits = match self.rebalancing_limits.lamports() {
+            0 => RebalancingLimits::default(),
+            _ => {
+                let rebalancing_limits =
+                    RebalancingLimits::unpack(&self.rebalancing_limits.data.borrow())?;
+                assert_account_key(self.depositor, &rebalancing_limits.depositor)?;
+                assert_account_key(self.mint, &rebalancing_limits.mint)?;
+
+                rebalancing_limits
+            }
+        };
+
+        // With permissionless crank any keeper can compute a rebalancing
+        // from a fresh oracle distribution, refresh income is left to the executor
+        if refresh_income || !rebalancing_limits.permissionless_crank {
             assert_account_key(self.executor, &depositor.rebalance_executor)?;
-            assert_account_key(self.registry, &depositor.registry)?;
         }
 
+        // Yield distribution computed on-chain replaces the oracle distribution
+        let yield_distribution = if !refresh_income && rebalancing_limits.yield_optimization {
+            Some(self.yield_distribution(program_id, account_info_iter)?)
+        } else {
+            None
+        };
+
         let registry = Registry::unpack(&self.registry.data.borrow())?;
         // Check root accounts
         assert_account_key(self.general_pool_market, &registry.general_pool_market)?;
@@ -175,7 +221,7 @@ impl<'a, 'b> StartRebalancingContext<'a, 'b> {
             return Err(EverlendError::IncompleteRebalancing.into());
         }
 
-        let general_pool_state =
+        let mut general_pool_state =
             everlend_general_pool::state::Pool::unpack(&self.general_pool.data.borrow())?;
 
         {
@@ -198,6 +244,16 @@ impl<'a, 'b> StartRebalancingContext<'a, 'b> {
             assert_account_key(self.general_pool, &general_pool_pubkey)?;
         }
 
+        let flags = PauseFlags::load(
+            self.registry.key,
+            self.general_pool.key,
+            self.global_pause,
+            self.pool_pause,
+        )?;
+        if flags.rebalancing {
+            return Err(EverlendError::OperationPaused.into());
+        }
+
         {
             // Check general pool accounts
             assert_account_key(self.general_pool_market, &general_pool_state.pool_market)?;
@@ -227,10 +283,36 @@ impl<'a, 'b> StartRebalancingContext<'a, 'b> {
             assert_account_key(self.liquidity_transit, &liquidity_transit_pubkey)?;
         }
 
+        let (depositor_authority_pubkey, bump_seed) =
+            find_program_address(program_id, self.depositor.key);
+        assert_account_key(self.depositor_authority, &depositor_authority_pubkey)?;
+        let signers_seeds = &[&self.depositor.key.to_bytes()[..32], &[bump_seed]];
+
+        rebalancing.set_keeper_config(&rebalancing_limits);
+
+        // Settle money market losses of the previous rebalancing
+        if rebalancing.unsettled_loss > 0 {
+            self.settle_loss(
+                &mut rebalancing,
+                &registry.income_pool_market,
+                account_info_iter,
+                &[signers_seeds],
+            )?;
+            general_pool_state =
+                everlend_general_pool::state::Pool::unpack(&self.general_pool.data.borrow())?;
+
+            // Settled loss is rewarded as a step, it happens once per loss
+            if let Some(keeper_reward) =
+                keeper_reward(program_id, &rebalancing, self.mint, account_info_iter)?
+            {
+                keeper_reward.pay_step_reward(self.depositor_authority, &[signers_seeds])?;
+            }
+        }
+
         let general_pool = Account::unpack(&self.general_pool_token_account.data.borrow())?;
         let liquidity_transit = Account::unpack(&self.liquidity_transit.data.borrow())?;
         let withdrawal_requests =
-            WithdrawalRequests::unpack(&self.withdrawal_requests.data.borrow())?;
+            WithdrawalRequests::unpack_any(&self.withdrawal_requests.data.borrow())?;
 
         let (available_liquidity, amount_to_distribute) = calculate_amount_to_distribute(
             rebalancing.total_distributed_liquidity()?,
@@ -250,11 +332,6 @@ impl<'a, 'b> StartRebalancingContext<'a, 'b> {
             return Err(EverlendError::RebalanceLiquidityCheckFailed.into());
         }
 
-        let (depositor_authority_pubkey, bump_seed) =
-            find_program_address(program_id, self.depositor.key);
-        assert_account_key(self.depositor_authority, &depositor_authority_pubkey)?;
-        let signers_seeds = &[&self.depositor.key.to_bytes()[..32], &[bump_seed]];
-
         if amount_to_distribute.gt(&available_liquidity) {
             let borrow_amount = amount_to_distribute
                 .checked_sub(available_liquidity)
@@ -265,6 +342,9 @@ impl<'a, 'b> StartRebalancingContext<'a, 'b> {
                 self.general_pool_market.clone(),
                 self.general_pool_market_authority.clone(),
                 self.general_pool.clone(),
+                self.registry.key,
+                self.global_pause.clone(),
+                self.pool_pause.clone(),
                 self.general_pool_borrow_authority.clone(),
                 self.liquidity_transit.clone(),
                 self.general_pool_token_account.clone(),
@@ -301,17 +381,22 @@ impl<'a, 'b> StartRebalancingContext<'a, 'b> {
         msg!("Computing");
         if refresh_income {
             rebalancing.compute_with_refresh_income(
-                &registry_markets.money_markets,
+                &registry_markets,
+                &rebalancing_limits,
                 registry.refresh_income_interval,
                 clock.slot,
                 amount_to_distribute,
             )?;
         } else {
             // Compute rebalancing steps
-            let token_oracle = TokenOracle::unpack(&self.token_oracle.data.borrow())?;
+            let mut token_oracle = TokenOracle::unpack(&self.token_oracle.data.borrow())?;
+            if let Some(yield_distribution) = yield_distribution {
+                token_oracle.liquidity_distribution = yield_distribution.liquidity_distribution;
+            }
 
             rebalancing.compute(
-                &registry_markets.money_markets,
+                &registry_markets,
+                &rebalancing_limits,
                 token_oracle,
                 amount_to_distribute,
                 clock.slot,
@@ -322,4 +407,106 @@ impl<'a, 'b> StartRebalancingContext<'a, 'b> {
 
         Ok(())
     }
+
+    /// Load yield distribution passed before the optional safety fund accounts
+    fn yield_distribution(
+        &self,
+        program_id: &Pubkey,
+        account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+    ) -> Result<YieldDistribution, ProgramError> {
+        let yield_distribution_info =
+            AccountLoader::next_with_owner(account_info_iter, program_id)?;
+
+        let (yield_distribution_pubkey, _) = YieldDistributionPDA {
+            depositor: *self.depositor.key,
+            mint: *self.mint.key,
+        }
+        .find_address(program_id);
+        assert_account_key(yield_distribution_info, &yield_distribution_pubkey)?;
+
+        YieldDistribution::unpack(&yield_distribution_info.data.borrow())
+    }
+
+    /// Cover unsettled loss from the safety fund as long as it has a ledger and a balance
+    /// and write off the rest in the general pool
+    fn settle_loss(
+        &self,
+        rebalancing: &mut Rebalancing,
+        income_pool_market: &Pubkey,
+        account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let loss = rebalancing.unsettled_loss;
+
+        let income_pool_market =
+            AccountLoader::next_with_key(account_info_iter, income_pool_market)?;
+        let income_pool_market_authority = AccountLoader::next_unchecked(account_info_iter)?;
+
+        // Safety fund accounts are checked by the key, they may not be created yet
+        let (safety_fund_ledger_pubkey, _) = find_safety_fund_ledger_address(
+            &everlend_income_pools::id(),
+            income_pool_market.key,
+            self.mint.key,
+        );
+        let safety_fund_ledger =
+            AccountLoader::next_with_key(account_info_iter, &safety_fund_ledger_pubkey)?;
+        let (safety_fund_token_account_pubkey, _) = find_safety_fund_token_account_address(
+            &everlend_income_pools::id(),
+            income_pool_market.key,
+            self.mint.key,
+        );
+        let safety_fund_token_account =
+            AccountLoader::next_with_key(account_info_iter, &safety_fund_token_account_pubkey)?;
+        let _income_pools_program =
+            AccountLoader::next_with_key(account_info_iter, &everlend_income_pools::id())?;
+
+        // Safety fund covers the loss once its ledger is created
+        let covered_amount = if safety_fund_ledger.owner.eq(&everlend_income_pools::id())
+            && safety_fund_token_account.owner.eq(&spl_token::id())
+        {
+            let safety_fund = Account::unpack(&safety_fund_token_account.data.borrow())?;
+            min(loss, safety_fund.amount)
+        } else {
+            0
+        };
+
+        if covered_amount > 0 {
+            everlend_income_pools::cpi::cover_loss(
+                income_pool_market.clone(),
+                income_pool_market_authority.clone(),
+                safety_fund_ledger.clone(),
+                safety_fund_token_account.clone(),
+                self.general_pool.clone(),
+                self.general_pool_borrow_authority.clone(),
+                self.depositor.clone(),
+                self.liquidity_transit.clone(),
+                self.depositor_authority.clone(),
+                self.clock.clone(),
+                self.mint.key,
+                covered_amount,
+                signers_seeds,
+            )?;
+        }
+
+        let write_off_amount = rebalancing.settle_loss(covered_amount)?;
+        if write_off_amount > 0 {
+            everlend_general_pool::cpi::write_off_loss(
+                self.general_pool_market.clone(),
+                self.general_pool.clone(),
+                self.general_pool_borrow_authority.clone(),
+                self.depositor_authority.clone(),
+                write_off_amount,
+                signers_seeds,
+            )?;
+        }
+
+        msg!(
+            "Loss: {} covered by safety fund: {} written off: {}",
+            loss,
+            covered_amount,
+            write_off_amount
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,352 @@
+use crate::{
+    state::{
+        Depositor, InitYieldDistributionParams, Rebalancing, RebalancingLimits, YieldDistribution,
+    },
+    utils::calculate_amount_to_distribute,
+    InternalMiningPDA, RebalancingLimitsPDA, RebalancingPDA, TransitPDA, YieldDistributionPDA,
+};
+use everlend_general_pool::{find_withdrawal_requests_program_address, state::WithdrawalRequests};
+use everlend_registry::state::{Registry, RegistryMarket, RegistryMarkets};
+use everlend_utils::{
+    assert_account_key, cpi,
+    integrations::{self, ReserveRates},
+    AccountLoader, EverlendError, PDA,
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, rent::Rent, system_program,
+    sysvar::clock, sysvar::Sysvar, sysvar::SysvarId,
+};
+use spl_token::state::Account;
+use std::{iter::Enumerate, slice::Iter};
+
+/// Instruction context
+pub struct UpdateYieldDistributionContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    depositor: &'a AccountInfo<'b>,
+    rebalancing: &'a AccountInfo<'b>,
+    rebalancing_limits: &'a AccountInfo<'b>,
+    yield_distribution: &'a AccountInfo<'b>,
+    mint: &'a AccountInfo<'b>,
+    general_pool_market: &'a AccountInfo<'b>,
+    general_pool: &'a AccountInfo<'b>,
+    general_pool_token_account: &'a AccountInfo<'b>,
+    withdrawal_requests: &'a AccountInfo<'b>,
+    liquidity_transit: &'a AccountInfo<'b>,
+    executor: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    clock: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> UpdateYieldDistributionContext<'a, 'b> {
+    /// New UpdateYieldDistribution instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+    ) -> Result<UpdateYieldDistributionContext<'a, 'b>, ProgramError> {
+        let registry = AccountLoader::next_with_owner(account_info_iter, &everlend_registry::id())?;
+        let depositor = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let rebalancing = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let rebalancing_limits = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let yield_distribution = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let general_pool_market =
+            AccountLoader::next_with_owner(account_info_iter, &everlend_general_pool::id())?;
+        let general_pool =
+            AccountLoader::next_with_owner(account_info_iter, &everlend_general_pool::id())?;
+        let general_pool_token_account =
+            AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let withdrawal_requests =
+            AccountLoader::next_with_owner(account_info_iter, &everlend_general_pool::id())?;
+        let liquidity_transit =
+            AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let executor = AccountLoader::next_signer(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let clock = AccountLoader::next_with_key(account_info_iter, &clock::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        Ok(UpdateYieldDistributionContext {
+            registry,
+            depositor,
+            rebalancing,
+            rebalancing_limits,
+            yield_distribution,
+            mint,
+            general_pool_market,
+            general_pool,
+            general_pool_token_account,
+            withdrawal_requests,
+            liquidity_transit,
+            executor,
+            rent,
+            clock,
+        })
+    }
+
+    /// Process UpdateYieldDistribution instruction
+    pub fn process(
+        &self,
+        program_id: &Pubkey,
+        account_info_iter: &'a mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+    ) -> ProgramResult {
+        {
+            let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
+            assert_account_key(self.executor, &depositor.rebalance_executor)?;
+            assert_account_key(self.registry, &depositor.registry)?;
+        }
+
+        let registry = Registry::unpack(&self.registry.data.borrow())?;
+        assert_account_key(self.general_pool_market, &registry.general_pool_market)?;
+
+        let registry_markets = RegistryMarkets::unpack_from_slice(&self.registry.data.borrow())?;
+
+        {
+            // Check rebalancing
+            let (rebalancing_pubkey, _) = RebalancingPDA {
+                depositor: *self.depositor.key,
+                mint: *self.mint.key,
+            }
+            .find_address(program_id);
+            assert_account_key(self.rebalancing, &rebalancing_pubkey)?;
+
+            // Check rebalancing limits
+            let (rebalancing_limits_pubkey, _) = RebalancingLimitsPDA {
+                depositor: *self.depositor.key,
+                mint: *self.mint.key,
+            }
+            .find_address(program_id);
+            assert_account_key(self.rebalancing_limits, &rebalancing_limits_pubkey)?;
+        }
+
+        let rebalancing = Rebalancing::unpack(&self.rebalancing.data.borrow())?;
+        assert_account_key(self.depositor, &rebalancing.depositor)?;
+        assert_account_key(self.mint, &rebalancing.mint)?;
+
+        // Markets are unlimited until limits are set
+        let rebalancing_limits = match self.rebalancing_limits.lamports() {
+            0 => RebalancingLimits::default(),
+            _ => {
+                let rebalancing_limits =
+                    RebalancingLimits::unpack(&self.rebalancing_limits.data.borrow())?;
+                assert_account_key(self.depositor, &rebalancing_limits.depositor)?;
+                assert_account_key(self.mint, &rebalancing_limits.mint)?;
+
+                rebalancing_limits
+            }
+        };
+
+        let seed = {
+            // Check yield distribution
+            let pda = YieldDistributionPDA {
+                depositor: *self.depositor.key,
+                mint: *self.mint.key,
+            };
+            let (yield_distribution_pubkey, bump) = pda.find_address(program_id);
+            assert_account_key(self.yield_distribution, &yield_distribution_pubkey)?;
+            pda.get_signing_seeds(bump)
+        };
+
+        // Create or get yield distribution account
+        let mut yield_distribution = match self.yield_distribution.lamports() {
+            0 => {
+                let rent = &Rent::from_account_info(self.rent)?;
+
+                cpi::system::create_account::<YieldDistribution>(
+                    program_id,
+                    self.executor.clone(),
+                    self.yield_distribution.clone(),
+                    &[&seed.as_seeds_slice()],
+                    rent,
+                )?;
+
+                let mut yield_distribution =
+                    YieldDistribution::unpack_unchecked(&self.yield_distribution.data.borrow())?;
+                yield_distribution.init(InitYieldDistributionParams {
+                    depositor: *self.depositor.key,
+                    mint: *self.mint.key,
+                });
+
+                yield_distribution
+            }
+            _ => {
+                let yield_distribution =
+                    YieldDistribution::unpack(&self.yield_distribution.data.borrow())?;
+                assert_account_key(self.depositor, &yield_distribution.depositor)?;
+                assert_account_key(self.mint, &yield_distribution.mint)?;
+
+                yield_distribution
+            }
+        };
+
+        // Reserves follow the registry market list order, every market is required
+        let mut reserves = Vec::new();
+        for (index, market) in registry_markets.markets.iter().enumerate() {
+            let reserve = self.reserve_rates(program_id, market, account_info_iter)?;
+            reserves.push(reserve.map(|mut reserve| {
+                // Our own liquidity doesn't count in the reserve supply
+                reserve.exclude_supplied(
+                    rebalancing
+                        .distributed_liquidity
+                        .get(index)
+                        .copied()
+                        .unwrap_or_default(),
+                );
+                reserve
+            }));
+        }
+
+        // Compute against the amount the next rebalancing distributes
+        let amount_to_distribute = self.amount_to_distribute(&rebalancing, program_id)?;
+        let clock = Clock::from_account_info(self.clock)?;
+
+        msg!("Computing");
+        yield_distribution.compute(
+            &registry_markets,
+            &rebalancing_limits,
+            &reserves,
+            amount_to_distribute,
+            clock.slot,
+        )?;
+
+        YieldDistribution::pack(
+            yield_distribution,
+            *self.yield_distribution.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Amount to distribute from the current general pool and transit state,
+    /// as computed by the 'StartRebalancing' instruction.
+    fn amount_to_distribute(
+        &self,
+        rebalancing: &Rebalancing,
+        program_id: &Pubkey,
+    ) -> Result<u64, ProgramError> {
+        {
+            // Check general pool
+            let (general_pool_pubkey, _) = everlend_general_pool::find_pool_program_address(
+                &everlend_general_pool::id(),
+                self.general_pool_market.key,
+                self.mint.key,
+            );
+            assert_account_key(self.general_pool, &general_pool_pubkey)?;
+        }
+
+        let general_pool_state =
+            everlend_general_pool::state::Pool::unpack(&self.general_pool.data.borrow())?;
+
+        {
+            // Check general pool accounts
+            assert_account_key(
+                self.general_pool_token_account,
+                &general_pool_state.token_account,
+            )?;
+
+            // Check withdrawal requests
+            let (withdrawal_requests_pubkey, _) = find_withdrawal_requests_program_address(
+                &everlend_general_pool::id(),
+                self.general_pool_market.key,
+                self.mint.key,
+            );
+            assert_account_key(self.withdrawal_requests, &withdrawal_requests_pubkey)?;
+
+            // Check transit: liquidity
+            let (liquidity_transit_pubkey, _) = TransitPDA {
+                seed: "",
+                depositor: *self.depositor.key,
+                mint: *self.mint.key,
+            }
+            .find_address(program_id);
+            assert_account_key(self.liquidity_transit, &liquidity_transit_pubkey)?;
+        }
+
+        let general_pool = Account::unpack(&self.general_pool_token_account.data.borrow())?;
+        let liquidity_transit = Account::unpack(&self.liquidity_transit.data.borrow())?;
+        let withdrawal_requests =
+            WithdrawalRequests::unpack_any(&self.withdrawal_requests.data.borrow())?;
+
+        let (_, amount_to_distribute) = calculate_amount_to_distribute(
+            rebalancing.total_distributed_liquidity()?,
+            liquidity_transit.amount,
+            general_pool.amount,
+            withdrawal_requests.liquidity_supply,
+        )?;
+
+        Ok(amount_to_distribute)
+    }
+
+    /// Read reserve liquidity and rate curve of the market.
+    /// Each market takes a reserve and its collateral storage account.
+    /// Inactive markets and markets without a rate curve are skipped.
+    fn reserve_rates(
+        &self,
+        program_id: &Pubkey,
+        market: &RegistryMarket,
+        account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+    ) -> Result<Option<ReserveRates>, ProgramError> {
+        let reserve = AccountLoader::next_unchecked(account_info_iter)?;
+        let collateral_storage = AccountLoader::next_unchecked(account_info_iter)?;
+
+        if !market.is_active() {
+            return Ok(None);
+        }
+
+        // Only for tests
+        let is_spl_lending =
+            market.money_market.to_string() == integrations::SPL_TOKEN_LENDING_PROGRAM_ID;
+        let is_supported = is_spl_lending
+            || matches!(
+                market.adapter,
+                integrations::MoneyMarket::PortFinance
+                    | integrations::MoneyMarket::Larix
+                    | integrations::MoneyMarket::Solend
+                    | integrations::MoneyMarket::Jet
+            );
+        if !is_supported {
+            return Ok(None);
+        }
+
+        if reserve.owner != &market.money_market {
+            return Err(EverlendError::InvalidMoneyMarketReserve.into());
+        }
+
+        let reserve_rates = match market.adapter {
+            _ if is_spl_lending => cpi::spl_token_lending::get_reserve_rates(reserve.clone())?,
+            integrations::MoneyMarket::PortFinance => {
+                cpi::port_finance::get_reserve_rates(reserve.clone())?
+            }
+            integrations::MoneyMarket::Larix => cpi::larix::get_reserve_rates(reserve.clone())?,
+            integrations::MoneyMarket::Solend => cpi::solend::get_reserve_rates(reserve.clone())?,
+            integrations::MoneyMarket::Jet => cpi::jet::get_reserve_rates(reserve.clone())?,
+            _ => return Ok(None),
+        };
+        if reserve_rates.liquidity_mint != *self.mint.key {
+            return Err(EverlendError::InvalidMoneyMarketReserve.into());
+        }
+
+        // The reserve collateral must be the one the depositor stores for the market
+        let (internal_mining_pubkey, _) = InternalMiningPDA {
+            liquidity_mint: *self.mint.key,
+            collateral_mint: reserve_rates.collateral_mint,
+            depositor: *self.depositor.key,
+        }
+        .find_address(program_id);
+        let (collateral_pool_pubkey, _) = everlend_collateral_pool::find_pool_program_address(
+            &everlend_collateral_pool::id(),
+            &market.collateral_pool_market,
+            &reserve_rates.collateral_mint,
+        );
+
+        let is_internal_mining = collateral_storage.key == &internal_mining_pubkey
+            && collateral_storage.owner == program_id;
+        let is_collateral_pool = collateral_storage.key == &collateral_pool_pubkey
+            && collateral_storage.owner == &everlend_collateral_pool::id();
+        if !is_internal_mining && !is_collateral_pool {
+            return Err(EverlendError::InvalidMoneyMarketReserve.into());
+        }
+
+        Ok(Some(reserve_rates))
+    }
+}
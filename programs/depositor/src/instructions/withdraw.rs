@@ -1,6 +1,6 @@
 use crate::{
     state::{Depositor, Rebalancing, RebalancingOperation},
-    utils::{collateral_storage, money_market, withdraw},
+    utils::{collateral_storage, keeper_reward, money_market, pause_flags, withdraw},
     InternalMiningPDA, RebalancingPDA, TransitPDA,
 };
 use everlend_income_pools::utils::IncomePoolAccounts;
@@ -18,6 +18,8 @@ pub struct WithdrawContext<'a, 'b> {
     depositor: &'a AccountInfo<'b>,
     depositor_authority: &'a AccountInfo<'b>,
     rebalancing: &'a AccountInfo<'b>,
+    global_pause: &'a AccountInfo<'b>,
+    pool_pause: &'a AccountInfo<'b>,
 
     collateral_transit: &'a AccountInfo<'b>,
     collateral_mint: &'a AccountInfo<'b>,
@@ -46,6 +48,9 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
         let depositor = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let depositor_authority = AccountLoader::next_unchecked(account_info_iter)?; //Signer PDA
         let rebalancing = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let global_pause =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let pool_pause = AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
 
         let income_pool_market =
             AccountLoader::next_with_owner(account_info_iter, &everlend_income_pools::id())?;
@@ -85,6 +90,8 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
             depositor,
             depositor_authority,
             rebalancing,
+            global_pause,
+            pool_pause,
             collateral_transit,
             collateral_mint,
             liquidity_transit,
@@ -104,11 +111,17 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
         program_id: &Pubkey,
         account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
     ) -> ProgramResult {
-        {
-            let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
-
-            assert_account_key(self.executor, &depositor.rebalance_executor)?;
-            assert_account_key(self.registry, &depositor.registry)?;
+        let depositor = Depositor::unpack(&self.depositor.data.borrow())?;
+        assert_account_key(self.registry, &depositor.registry)?;
+
+        let flags = pause_flags(
+            self.registry,
+            self.liquidity_mint.key,
+            self.global_pause,
+            self.pool_pause,
+        )?;
+        if flags.withdraw || flags.rebalancing {
+            return Err(EverlendError::OperationPaused.into());
         }
 
         let registry_markets = RegistryMarkets::unpack_from_slice(&self.registry.data.borrow())?;
@@ -131,6 +144,10 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
             return Err(EverlendError::RebalancingIsCompleted.into());
         }
 
+        if !rebalancing.permissionless_crank {
+            assert_account_key(self.executor, &depositor.rebalance_executor)?;
+        }
+
         {
             // Check transit: liquidity
             let (liquidity_transit_pubkey, _) = TransitPDA {
@@ -181,7 +198,9 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
             return Err(EverlendError::InvalidRebalancingOperation.into());
         }
 
-        if !registry_markets.money_markets[usize::from(step.money_market_index)]
+        if !registry_markets
+            .get(usize::from(step.money_market_index))?
+            .money_market
             .eq(self.money_market_program.key)
         {
             return Err(EverlendError::InvalidRebalancingMoneyMarket.into());
@@ -212,6 +231,7 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
 
         let collateral_stor = collateral_storage(
             &registry_markets,
+            self.registry,
             self.collateral_mint,
             self.depositor_authority,
             account_info_iter,
@@ -219,12 +239,19 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
             is_mining,
         )?;
 
+        let keeper_reward = keeper_reward(
+            program_id,
+            &rebalancing,
+            self.liquidity_mint,
+            account_info_iter,
+        )?;
+
         let clock = Clock::from_account_info(self.clock)?;
 
         money_market.refresh_reserve(self.clock.clone())?;
 
         msg!("Withdraw");
-        withdraw(
+        let loss = withdraw(
             self.income_pool_accounts,
             self.collateral_transit,
             self.collateral_mint,
@@ -237,10 +264,19 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
             &collateral_stor,
             step.collateral_amount.unwrap(),
             step.liquidity_amount,
+            &keeper_reward,
             &[signers_seeds],
         )?;
 
         rebalancing.execute_step(RebalancingOperation::Withdraw, None, clock.slot)?;
+        if loss > 0 {
+            rebalancing.add_loss(loss)?;
+            rebalancing.reduce_pending_deposits(loss)?;
+        }
+
+        if let Some(keeper_reward) = keeper_reward {
+            keeper_reward.pay_step_reward(self.depositor_authority, &[signers_seeds])?;
+        }
 
         Rebalancing::pack(rebalancing, *self.rebalancing.data.borrow_mut())?;
 
@@ -22,7 +22,8 @@ use solana_program::pubkey::Pubkey;
 solana_program::declare_id!("DepSR26sqzN67TNf1aZ3VCjTPduzKKqTEY8QQkk3KwEz");
 
 /// The list of allowed transit seeds
-const ALLOWED_TRANSIT_SEEDS: &[&str] = &["", "lm_reward", "reserve", FRANCIUM_REWARD_SEED];
+const ALLOWED_TRANSIT_SEEDS: &[&str] =
+    &["", "lm_reward", "reserve", "keeper", FRANCIUM_REWARD_SEED];
 
 /// Generates transit address
 pub struct TransitPDA<'a> {
@@ -62,6 +63,42 @@ impl PDA for RebalancingPDA {
     }
 }
 
+/// Generates rebalancing limits address
+pub struct RebalancingLimitsPDA {
+    ///
+    pub depositor: Pubkey,
+    ///
+    pub mint: Pubkey,
+}
+
+impl PDA for RebalancingLimitsPDA {
+    fn get_raw_seeds(&self) -> Seeds {
+        Seeds(vec![
+            "rebalancing_limits".as_bytes().to_vec(),
+            self.depositor.to_bytes().to_vec(),
+            self.mint.to_bytes().to_vec(),
+        ])
+    }
+}
+
+/// Generates yield distribution address
+pub struct YieldDistributionPDA {
+    ///
+    pub depositor: Pubkey,
+    ///
+    pub mint: Pubkey,
+}
+
+impl PDA for YieldDistributionPDA {
+    fn get_raw_seeds(&self) -> Seeds {
+        Seeds(vec![
+            "yield_distribution".as_bytes().to_vec(),
+            self.depositor.to_bytes().to_vec(),
+            self.mint.to_bytes().to_vec(),
+        ])
+    }
+}
+
 /// Generates internal mining program address
 pub struct InternalMiningPDA {
     ///
@@ -16,6 +16,9 @@ pub struct CollateralPool<'a, 'b> {
     collateral_pool_market_authority: &'a AccountInfo<'b>,
     collateral_pool: &'a AccountInfo<'b>,
     collateral_pool_token_account: &'a AccountInfo<'b>,
+    registry: &'a AccountInfo<'b>,
+    global_pause: &'a AccountInfo<'b>,
+    pool_pause: &'a AccountInfo<'b>,
     collateral_pool_withdraw_authority: Option<&'a AccountInfo<'b>>,
 }
 
@@ -23,6 +26,7 @@ impl<'a, 'b> CollateralPool<'a, 'b> {
     ///
     pub fn init(
         registry_markets: &RegistryMarkets,
+        registry: &'a AccountInfo<'b>,
         collateral_mint: &AccountInfo<'b>,
         authority: &AccountInfo<'b>,
         account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
@@ -37,12 +41,13 @@ impl<'a, 'b> CollateralPool<'a, 'b> {
             AccountLoader::next_with_owner(account_info_iter, &everlend_collateral_pool::id())?;
         let collateral_pool_token_account_info =
             AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let global_pause_info =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let pool_pause_info =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
 
         // Check collateral pool market
-        if !registry_markets
-            .collateral_pool_markets
-            .contains(collateral_pool_market_info.key)
-        {
+        if !registry_markets.contains_collateral_pool_market(collateral_pool_market_info.key) {
             return Err(ProgramError::InvalidArgument);
         }
 
@@ -96,6 +101,9 @@ impl<'a, 'b> CollateralPool<'a, 'b> {
             collateral_pool_market_authority: collateral_pool_market_authority_info,
             collateral_pool: collateral_pool_info,
             collateral_pool_token_account: collateral_pool_token_account_info,
+            registry,
+            global_pause: global_pause_info,
+            pool_pause: pool_pause_info,
             collateral_pool_withdraw_authority,
         })
     }
@@ -118,6 +126,9 @@ impl<'a, 'b> CollateralStorage<'b> for CollateralPool<'a, 'b> {
                 pool_market_authority: self.collateral_pool_market_authority.clone(),
                 pool: self.collateral_pool.clone(),
                 token_account: self.collateral_pool_token_account.clone(),
+                registry: self.registry.clone(),
+                global_pause: self.global_pause.clone(),
+                pool_pause: self.pool_pause.clone(),
             },
             collateral_transit,
             authority,
@@ -145,6 +156,9 @@ impl<'a, 'b> CollateralStorage<'b> for CollateralPool<'a, 'b> {
                 pool_market_authority: self.collateral_pool_market_authority.clone(),
                 pool: self.collateral_pool.clone(),
                 token_account: self.collateral_pool_token_account.clone(),
+                registry: self.registry.clone(),
+                global_pause: self.global_pause.clone(),
+                pool_pause: self.pool_pause.clone(),
             },
             self.collateral_pool_withdraw_authority.unwrap().clone(),
             collateral_transit,
@@ -237,6 +237,10 @@ impl<'a, 'b> MoneyMarket<'b> for Frakt<'a, 'b> {
         Ok(true)
     }
 
+    fn real_liquidity_amount(&self, _collateral_amount: u64) -> Result<Option<u64>, ProgramError> {
+        Ok(None)
+    }
+
     fn refresh_reserve(&self, _clock: AccountInfo<'b>) -> Result<(), ProgramError> {
         Ok(())
     }
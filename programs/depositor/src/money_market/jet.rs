@@ -129,6 +129,10 @@ impl<'a, 'b> MoneyMarket<'b> for Jet<'a, 'b> {
         Ok(real_liquidity_amount > expected_liquidity_amount)
     }
 
+    fn real_liquidity_amount(&self, collateral_amount: u64) -> Result<Option<u64>, ProgramError> {
+        jet::get_real_liquidity_amount(self.margin_pool.clone(), collateral_amount).map(Some)
+    }
+
     fn refresh_reserve(&self, _clock: AccountInfo<'b>) -> Result<(), ProgramError> {
         Ok(())
     }
@@ -214,6 +214,10 @@ impl<'a, 'b> MoneyMarket<'b> for Larix<'a, 'b> {
         Ok(real_liquidity_amount > expected_liquidity_amount)
     }
 
+    fn real_liquidity_amount(&self, collateral_amount: u64) -> Result<Option<u64>, ProgramError> {
+        larix::get_real_liquidity_amount(self.reserve.clone(), collateral_amount).map(Some)
+    }
+
     fn refresh_reserve(&self, _clock: AccountInfo<'b>) -> Result<(), ProgramError> {
         larix::refresh_reserve(
             &self.money_market_program_id,
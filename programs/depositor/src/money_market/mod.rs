@@ -102,6 +102,10 @@ pub trait MoneyMarket<'a> {
         expected_liquidity_amount: u64,
     ) -> Result<bool, ProgramError>;
 
+    /// Liquidity amount of the collateral by money market exchange rate.
+    /// None if the market has no exchange rate.
+    fn real_liquidity_amount(&self, collateral_amount: u64) -> Result<Option<u64>, ProgramError>;
+
     ///
     fn refresh_reserve(&self, clock: AccountInfo<'a>) -> Result<(), ProgramError>;
 }
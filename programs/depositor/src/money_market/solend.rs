@@ -147,6 +147,10 @@ impl<'a, 'b> MoneyMarket<'b> for Solend<'a, 'b> {
         Ok(real_liquidity_amount > expected_liquidity_amount)
     }
 
+    fn real_liquidity_amount(&self, collateral_amount: u64) -> Result<Option<u64>, ProgramError> {
+        solend::get_real_liquidity_amount(self.reserve.clone(), collateral_amount).map(Some)
+    }
+
     fn refresh_reserve(&self, clock: AccountInfo<'b>) -> Result<(), ProgramError> {
         solend::refresh_reserve(
             &self.money_market_program_id,
@@ -2,9 +2,11 @@
 
 use crate::instruction::DepositorInstruction;
 use crate::instructions::{
-    ClaimMiningRewardContext, CreateTransitContext, DepositContext, InitContext,
-    InitMiningAccountContext, MigrateDepositorContext, MigrateRebalancingContext,
-    RefreshMMIncomesContext, SetRebalancingContext, StartRebalancingContext, WithdrawContext,
+    ClaimMiningRewardContext, CreateTransitContext, DepositContext, EmergencyWithdrawContext,
+    EnableMarketContext, InitContext, InitMiningAccountContext, MigrateDepositorContext,
+    MigrateRebalancingContext, RefreshMMIncomesContext, SetKeeperConfigContext,
+    SetRebalancingContext, SetRebalancingLimitsContext, SetYieldOptimizationContext,
+    StartRebalancingContext, UpdateYieldDistributionContext, WithdrawContext,
 };
 use borsh::BorshDeserialize;
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey};
@@ -112,6 +114,69 @@ impl<'a, 'b> Processor {
                 MigrateRebalancingContext::new(program_id, account_info_iter)?
                     .process(program_id, account_info_iter)
             }
+
+            DepositorInstruction::SetRebalancingLimits {
+                limits,
+                reserve_rates_window,
+                max_reserve_rate_deviation,
+            } => {
+                msg!("DepositorInstruction: SetRebalancingLimits");
+                SetRebalancingLimitsContext::new(program_id, account_info_iter)?.process(
+                    program_id,
+                    account_info_iter,
+                    limits,
+                    reserve_rates_window,
+                    max_reserve_rate_deviation,
+                )
+            }
+
+            DepositorInstruction::EmergencyWithdraw { money_market_index } => {
+                msg!("DepositorInstruction: EmergencyWithdraw");
+                EmergencyWithdrawContext::new(program_id, account_info_iter)?.process(
+                    program_id,
+                    account_info_iter,
+                    money_market_index,
+                )
+            }
+
+            DepositorInstruction::EnableMarket { money_market_index } => {
+                msg!("DepositorInstruction: EnableMarket");
+                EnableMarketContext::new(program_id, account_info_iter)?.process(
+                    program_id,
+                    account_info_iter,
+                    money_market_index,
+                )
+            }
+
+            DepositorInstruction::SetKeeperConfig {
+                permissionless_crank,
+                keeper_reward_share,
+                keeper_step_reward,
+            } => {
+                msg!("DepositorInstruction: SetKeeperConfig");
+                SetKeeperConfigContext::new(program_id, account_info_iter)?.process(
+                    program_id,
+                    account_info_iter,
+                    permissionless_crank,
+                    keeper_reward_share,
+                    keeper_step_reward,
+                )
+            }
+
+            DepositorInstruction::SetYieldOptimization { yield_optimization } => {
+                msg!("DepositorInstruction: SetYieldOptimization");
+                SetYieldOptimizationContext::new(program_id, account_info_iter)?.process(
+                    program_id,
+                    account_info_iter,
+                    yield_optimization,
+                )
+            }
+
+            DepositorInstruction::UpdateYieldDistribution => {
+                msg!("DepositorInstruction: UpdateYieldDistribution");
+                UpdateYieldDistributionContext::new(program_id, account_info_iter)?
+                    .process(program_id, account_info_iter)
+            }
         }
     }
 }
@@ -1,16 +1,21 @@
 //! Depositor state definitions
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_registry::state::MAX_MONEY_MARKETS;
 
 mod depositor;
 mod internal_mining;
 mod rebalancing;
+mod rebalancing_limits;
 mod rebalancing_step;
+mod yield_distribution;
 
 pub use depositor::*;
 pub use internal_mining::*;
 pub use rebalancing::*;
+pub use rebalancing_limits::*;
 pub use rebalancing_step::*;
+pub use yield_distribution::*;
 
 /// Enum representing the account type managed by the program
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -23,6 +28,10 @@ pub enum AccountType {
     Rebalancing,
     /// Internal mining
     InternalMining,
+    /// Rebalancing limits
+    RebalancingLimits,
+    /// Yield distribution
+    YieldDistribution,
 }
 
 impl Default for AccountType {
@@ -32,4 +41,4 @@ impl Default for AccountType {
 }
 
 /// Total rebalancing steps for fixed state
-pub const TOTAL_REBALANCING_STEP: usize = 14;
+pub const TOTAL_REBALANCING_STEP: usize = 2 * MAX_MONEY_MARKETS;
@@ -1,11 +1,11 @@
 //! Program state definitions
 
-use super::{AccountType, RebalancingStep, TOTAL_REBALANCING_STEP};
+use super::{AccountType, RebalancingLimits, RebalancingStep, TOTAL_REBALANCING_STEP};
 use crate::state::RebalancingOperation;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 pub use deprecated::DeprecatedRebalancing;
 use everlend_liquidity_oracle::state::{Distribution, DistributionArray, TokenOracle};
-use everlend_registry::state::{DistributionPubkeys, TOTAL_DISTRIBUTIONS};
+use everlend_registry::state::{MarketStatus, RegistryMarkets, MAX_MONEY_MARKETS};
 use everlend_utils::{math, EverlendError};
 use solana_program::{
     clock::Slot,
@@ -46,6 +46,27 @@ pub struct Rebalancing {
 
     /// Income refreshed mark to avoid frequent refresh
     pub income_refreshed_at: Slot,
+
+    /// Markets disabled by emergency withdraw
+    pub disabled_markets: Vec<bool>,
+
+    /// Loss realized by withdrawals and not settled with the general pool yet
+    pub unsettled_loss: u64,
+
+    /// Total loss realized by withdrawals
+    pub total_loss: u64,
+
+    /// Part of the total loss covered by the safety fund
+    pub total_covered_loss: u64,
+
+    /// Anyone can execute the rebalancing steps
+    pub permissionless_crank: bool,
+
+    /// Share of the withdrawal income paid to the keeper reward transit (PRECISION_SCALER based)
+    pub keeper_reward_share: u64,
+
+    /// Reward paid to the keeper for each executed step from the keeper reward transit
+    pub keeper_step_reward: u64,
 }
 
 impl Rebalancing {
@@ -59,7 +80,8 @@ impl Rebalancing {
     /// Generate new steps from new and latest distribuition arrays
     pub fn compute(
         &mut self,
-        money_market_program_ids: &DistributionPubkeys,
+        registry_markets: &RegistryMarkets,
+        rebalancing_limits: &RebalancingLimits,
         token_oracle: TokenOracle,
         amount_to_distribute: u64,
         current_slot: Slot,
@@ -69,24 +91,46 @@ impl Rebalancing {
             return Err(EverlendError::LiquidityDistributionStale.into());
         }
 
-        if token_oracle.reserve_rates.updated_at != current_slot {
-            return Err(EverlendError::ReserveRatesStale.into());
-        }
+        rebalancing_limits
+            .check_reserve_rates_age(token_oracle.reserve_rates.updated_at, current_slot)?;
 
         // Reset steps
         self.steps = Vec::new();
+        self.resize(registry_markets.markets.len());
+
+        // Spread percents between active markets
+        let mut weights = vec![0; registry_markets.markets.len()];
+        let mut new_amounts = vec![0; registry_markets.markets.len()];
+        for (index, market) in registry_markets.markets.iter().enumerate() {
+            if market.is_active() && !self.is_disabled(index) {
+                weights[index] = token_oracle.liquidity_distribution.get(index);
+                new_amounts[index] = math::share_floor(amount_to_distribute, weights[index])?;
+            }
+        }
+
+        // Clip by market limits, undistributed excess stays on liquidity transit
+        let undistributed = rebalancing_limits.apply(
+            registry_markets,
+            amount_to_distribute,
+            &mut new_amounts,
+            &weights,
+        )?;
+        if undistributed > 0 {
+            msg!("Undistributed liquidity: {}", undistributed);
+        }
 
         // Compute steps
-        for (index, _) in money_market_program_ids
-            .iter()
-            .enumerate()
-            // Keeping index order
-            .filter(|&id| *id.1 != Default::default())
-        {
-            // Spread percents
-            let percent = token_oracle.liquidity_distribution.values[index];
+        for (index, market) in registry_markets.markets.iter().enumerate() {
             let prev_amount = self.distributed_liquidity[index];
-            let new_amount = math::share_floor(amount_to_distribute, percent)?;
+            let new_amount = match market.status {
+                // Disabled market has been withdrawn by emergency withdraw
+                MarketStatus::Active if self.is_disabled(index) => 0,
+                MarketStatus::Active => new_amounts[index],
+                // Keep distributed liquidity as is
+                MarketStatus::Paused => prev_amount,
+                // Withdraw all liquidity
+                MarketStatus::Retired => 0,
+            };
 
             let amount = math::abs_diff(new_amount, prev_amount)?;
 
@@ -94,18 +138,19 @@ impl Rebalancing {
                 // Deposit
                 Ordering::Greater => {
                     // Сheck collateral leak (only if it's set for market)
-                    let collateral_percent = token_oracle.reserve_rates.values[index];
+                    let collateral_percent = token_oracle.reserve_rates.get(index);
                     let expected_collateral = math::share_floor(amount, collateral_percent)?;
                     if collateral_percent > 0 && expected_collateral == 0 {
                         // Do nothing and preserve old amount
                         prev_amount
                     } else {
-                        // Deposit new liquidity
+                        // Deposit new liquidity, expected collateral is checked against
+                        // money market exchange rate and replaced at the deposit stage
                         self.add_step(RebalancingStep::new(
                             index as u8,
                             RebalancingOperation::Deposit,
                             amount,
-                            None, // Will be calculated at the deposit stage
+                            Some(expected_collateral).filter(|_| collateral_percent > 0),
                         ));
 
                         new_amount
@@ -145,7 +190,8 @@ impl Rebalancing {
     /// Generate new steps for withdraw all funds and deposit them back in MM pools
     pub fn compute_with_refresh_income(
         &mut self,
-        money_market_program_ids: &DistributionPubkeys,
+        registry_markets: &RegistryMarkets,
+        rebalancing_limits: &RebalancingLimits,
         refresh_income_interval: u64,
         income_refreshed_at: Slot,
         amount_to_distribute: u64,
@@ -156,18 +202,38 @@ impl Rebalancing {
 
         // Reset steps
         self.steps = Vec::new();
+        self.resize(registry_markets.markets.len());
+
+        // Spread percents between active markets
+        // Inactive markets are handled by regular rebalancing
+        let mut weights = vec![0; registry_markets.markets.len()];
+        let mut new_amounts = vec![0; registry_markets.markets.len()];
+        for (index, market) in registry_markets.markets.iter().enumerate() {
+            if market.is_active() && !self.is_disabled(index) {
+                weights[index] = self.liquidity_distribution.get(index);
+                new_amounts[index] = math::share_floor(amount_to_distribute, weights[index])?;
+            }
+        }
+
+        // Clip by market limits, undistributed excess stays on liquidity transit
+        let undistributed = rebalancing_limits.apply(
+            registry_markets,
+            amount_to_distribute,
+            &mut new_amounts,
+            &weights,
+        )?;
+        if undistributed > 0 {
+            msg!("Undistributed liquidity: {}", undistributed);
+        }
 
         // Compute steps
-        for (index, _) in money_market_program_ids
-            .iter()
-            .enumerate()
-            // Keeping index order
-            .filter(|&id| *id.1 != Default::default())
-        {
-            // Spread percents
-            let percent = self.liquidity_distribution.values[index];
+        for (index, market) in registry_markets.markets.iter().enumerate() {
+            if !market.is_active() || self.is_disabled(index) {
+                continue;
+            }
+
             let prev_amount = self.distributed_liquidity[index];
-            let new_amount = math::share_floor(amount_to_distribute, percent)?;
+            let new_amount = new_amounts[index];
 
             if prev_amount == 0 && new_amount == 0 {
                 continue;
@@ -210,6 +276,12 @@ impl Rebalancing {
         distributed_liquidity: DistributionArray,
         distribution_array: DistributionArray,
     ) -> Result<(), ProgramError> {
+        if distributed_liquidity.len() > MAX_MONEY_MARKETS
+            || distribution_array.len() > MAX_MONEY_MARKETS
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
         self.steps.retain(|&s| s.executed_at.is_some());
         self.amount_to_distribute = amount_to_distribute;
         self.distributed_liquidity = distributed_liquidity;
@@ -282,6 +354,192 @@ impl Rebalancing {
         Ok(())
     }
 
+    /// Generate and execute withdraw step of all collateral of the market, disabling it.
+    /// Pending steps of the market are dropped, the rest of the rebalancing goes on
+    pub fn emergency_withdraw(
+        &mut self,
+        money_market_index: usize,
+        slot: Slot,
+    ) -> Result<RebalancingStep, ProgramError> {
+        let distributed_liquidity = *self
+            .distributed_liquidity
+            .get(money_market_index)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        // Distributed liquidity is set on compute, revert pending steps of the market
+        let mut liquidity_amount = distributed_liquidity;
+        for step in self.steps.iter().filter(|step| {
+            step.executed_at.is_none() && usize::from(step.money_market_index) == money_market_index
+        }) {
+            liquidity_amount = match step.operation {
+                RebalancingOperation::Deposit | RebalancingOperation::RefreshDeposit => {
+                    liquidity_amount.saturating_sub(step.liquidity_amount)
+                }
+                RebalancingOperation::Withdraw | RebalancingOperation::RefreshWithdraw => {
+                    liquidity_amount
+                        .checked_add(step.liquidity_amount)
+                        .ok_or(EverlendError::MathOverflow)?
+                }
+            };
+        }
+        let collateral_amount = self.received_collateral[money_market_index];
+
+        let mut step = RebalancingStep::new(
+            money_market_index as u8,
+            RebalancingOperation::Withdraw,
+            liquidity_amount,
+            Some(collateral_amount),
+        );
+        step.set_executed_at(slot);
+
+        // Replace steps of the market, executed ones are already accounted in collateral
+        self.steps
+            .retain(|step| usize::from(step.money_market_index) != money_market_index);
+        let position = self
+            .steps
+            .iter()
+            .position(|step| step.executed_at.is_none())
+            .unwrap_or(self.steps.len());
+        self.steps.insert(position, step);
+
+        self.received_collateral[money_market_index] = 0;
+        self.distributed_liquidity[money_market_index] = 0;
+        self.set_disabled(money_market_index, true);
+
+        Ok(step)
+    }
+
+    /// Record the loss realized by the withdrawal
+    pub fn add_loss(&mut self, loss: u64) -> Result<(), ProgramError> {
+        self.unsettled_loss = self
+            .unsettled_loss
+            .checked_add(loss)
+            .ok_or(EverlendError::MathOverflow)?;
+        self.total_loss = self
+            .total_loss
+            .checked_add(loss)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Reduce the next refresh deposit by the loss of the refresh withdraw,
+    /// returns the liquidity amount left to deposit
+    pub fn reduce_refresh_deposit(&mut self, loss: u64) -> Result<u64, ProgramError> {
+        let step = self.next_step_mut();
+        if step.operation != RebalancingOperation::RefreshDeposit {
+            return Err(EverlendError::InvalidRebalancingOperation.into());
+        }
+
+        let reduction = loss.min(step.liquidity_amount);
+        step.liquidity_amount = step
+            .liquidity_amount
+            .checked_sub(reduction)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        let liquidity_amount = step.liquidity_amount;
+        let money_market_index = usize::from(step.money_market_index);
+        self.distributed_liquidity[money_market_index] = self.distributed_liquidity
+            [money_market_index]
+            .checked_sub(reduction)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        Ok(liquidity_amount)
+    }
+
+    /// Reduce pending deposits by the loss of the withdrawal, the liquidity transit is short
+    /// of the lost amount until the loss is settled. The last deposits are reduced first
+    pub fn reduce_pending_deposits(&mut self, loss: u64) -> Result<(), ProgramError> {
+        let mut remaining_loss = loss;
+        for step in self.steps.iter_mut().rev() {
+            if remaining_loss == 0 {
+                break;
+            }
+            if step.executed_at.is_some() || step.operation != RebalancingOperation::Deposit {
+                continue;
+            }
+
+            let reduction = remaining_loss.min(step.liquidity_amount);
+            let liquidity_amount = step
+                .liquidity_amount
+                .checked_sub(reduction)
+                .ok_or(EverlendError::MathOverflow)?;
+
+            // Keep expected collateral in line with the reduced liquidity
+            step.collateral_amount = step
+                .collateral_amount
+                .map(|collateral_amount| {
+                    math::percent_ratio(liquidity_amount, step.liquidity_amount, collateral_amount)
+                })
+                .transpose()?;
+            step.liquidity_amount = liquidity_amount;
+
+            let money_market_index = usize::from(step.money_market_index);
+            self.distributed_liquidity[money_market_index] = self.distributed_liquidity
+                [money_market_index]
+                .checked_sub(reduction)
+                .ok_or(EverlendError::MathOverflow)?;
+
+            remaining_loss = remaining_loss
+                .checked_sub(reduction)
+                .ok_or(EverlendError::MathOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    /// Settle the unsettled loss, the covered part is returned to the liquidity transit
+    /// and the rest is written off in the general pool. Returns the amount to write off
+    pub fn settle_loss(&mut self, covered_amount: u64) -> Result<u64, ProgramError> {
+        let write_off_amount = self
+            .unsettled_loss
+            .checked_sub(covered_amount)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        self.total_covered_loss = self
+            .total_covered_loss
+            .checked_add(covered_amount)
+            .ok_or(EverlendError::MathOverflow)?;
+        self.unsettled_loss = 0;
+
+        Ok(write_off_amount)
+    }
+
+    /// Keeper config of the computed rebalancing
+    pub fn set_keeper_config(&mut self, rebalancing_limits: &RebalancingLimits) {
+        self.permissionless_crank = rebalancing_limits.permissionless_crank;
+        self.keeper_reward_share = rebalancing_limits.keeper_reward_share;
+        self.keeper_step_reward = rebalancing_limits.keeper_step_reward;
+    }
+
+    /// Check market is disabled by emergency withdraw
+    pub fn is_disabled(&self, money_market_index: usize) -> bool {
+        self.disabled_markets
+            .get(money_market_index)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Disable or re-enable the market
+    pub fn set_disabled(&mut self, money_market_index: usize, disabled: bool) {
+        if self.disabled_markets.len() <= money_market_index {
+            self.disabled_markets.resize(money_market_index + 1, false);
+        }
+
+        self.disabled_markets[money_market_index] = disabled;
+    }
+
+    /// Extend distribution arrays to the registry market list size
+    fn resize(&mut self, markets_len: usize) {
+        if self.distributed_liquidity.len() < markets_len {
+            self.distributed_liquidity.resize(markets_len, 0);
+        }
+
+        if self.received_collateral.len() < markets_len {
+            self.received_collateral.resize(markets_len, 0);
+        }
+    }
+
     /// Add rebalancing step
     pub fn add_step(&mut self, rebalancing_step: RebalancingStep) {
         self.steps.push(rebalancing_step);
@@ -333,15 +591,22 @@ pub struct InitRebalancingParams {
 
 impl Sealed for Rebalancing {}
 impl Pack for Rebalancing {
-    // 725
+    // 1 + 32 + 32 + 8 + (4 + 8 * 16) + (4 + 8 * 16) + 140 + (4 + 32 * 28) + 8 + (4 + 16) + 8 + 8 + 8 + 1 + 8 + 8 = 1446
     const LEN: usize = 1
         + 32
         + 32
         + 8
-        + (8 * TOTAL_DISTRIBUTIONS)
-        + (8 * TOTAL_DISTRIBUTIONS)
+        + (4 + 8 * MAX_MONEY_MARKETS)
+        + (4 + 8 * MAX_MONEY_MARKETS)
         + Distribution::LEN
         + (4 + TOTAL_REBALANCING_STEP * RebalancingStep::LEN)
+        + 8
+        + (4 + MAX_MONEY_MARKETS)
+        + 8
+        + 8
+        + 8
+        + 1
+        + 8
         + 8;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
@@ -366,9 +631,11 @@ impl IsInitialized for Rebalancing {
 }
 
 mod deprecated {
-    pub const DEPRECATED_TOTAL_REBALANCING_STEP: usize = 8;
+    pub const DEPRECATED_TOTAL_REBALANCING_STEP: usize = 14;
 
     use super::*;
+    use everlend_liquidity_oracle::state::{DeprecatedDistribution, DeprecatedDistributionArray};
+    use everlend_registry::state::TOTAL_DISTRIBUTIONS;
 
     /// Rebalancing
     #[repr(C)]
@@ -387,13 +654,13 @@ mod deprecated {
         pub amount_to_distribute: u64,
 
         /// Distributed liquidity into MMs
-        pub distributed_liquidity: DistributionArray,
+        pub distributed_liquidity: DeprecatedDistributionArray,
 
         /// Received collateral in each market
-        pub received_collateral: DistributionArray,
+        pub received_collateral: DeprecatedDistributionArray,
 
         /// Current token distribution from liquidity oracle
-        pub liquidity_distribution: Distribution,
+        pub liquidity_distribution: DeprecatedDistribution,
 
         /// Rebalancing steps
         pub steps: Vec<RebalancingStep>,
@@ -404,14 +671,14 @@ mod deprecated {
 
     impl Sealed for DeprecatedRebalancing {}
     impl Pack for DeprecatedRebalancing {
-        // 1 + 32 + 32 + 8 + (8 * 10) + (8 * 10) + 88 + (4 + 8 * 28) + 8 = 557
+        // 1 + 32 + 32 + 8 + (8 * 10) + (8 * 10) + 88 + (4 + 14 * 28) + 8 = 725
         const LEN: usize = 1
             + 32
             + 32
             + 8
             + (8 * TOTAL_DISTRIBUTIONS)
             + (8 * TOTAL_DISTRIBUTIONS)
-            + Distribution::LEN
+            + DeprecatedDistribution::LEN
             + (4 + DEPRECATED_TOTAL_REBALANCING_STEP * RebalancingStep::LEN)
             + 8;
 
@@ -441,6 +708,29 @@ mod deprecated {
         }
     }
 
+    impl From<DeprecatedRebalancing> for Rebalancing {
+        fn from(deprecated: DeprecatedRebalancing) -> Self {
+            Rebalancing {
+                account_type: deprecated.account_type,
+                depositor: deprecated.depositor,
+                mint: deprecated.mint,
+                amount_to_distribute: deprecated.amount_to_distribute,
+                distributed_liquidity: deprecated.distributed_liquidity.to_vec(),
+                received_collateral: deprecated.received_collateral.to_vec(),
+                liquidity_distribution: deprecated.liquidity_distribution.into(),
+                steps: deprecated.steps,
+                income_refreshed_at: deprecated.income_refreshed_at,
+                disabled_markets: Vec::new(),
+                unsettled_loss: 0,
+                total_loss: 0,
+                total_covered_loss: 0,
+                permissionless_crank: false,
+                keeper_reward_share: 0,
+                keeper_step_reward: 0,
+            }
+        }
+    }
+
     impl IsInitialized for DeprecatedRebalancing {
         fn is_initialized(&self) -> bool {
             self.account_type == AccountType::Rebalancing
@@ -451,8 +741,25 @@ mod deprecated {
 #[cfg(test)]
 pub mod tests {
     use super::*;
-    use crate::state::RebalancingOperation;
-    use everlend_liquidity_oracle::state::DistributionArray;
+    use crate::state::{MarketLimit, RebalancingOperation, MAX_KEEPER_REWARD_SHARE};
+    use everlend_registry::state::RegistryMarket;
+    use everlend_utils::integrations::MoneyMarket;
+
+    fn registry_markets(count: usize) -> RegistryMarkets {
+        let mut registry_markets = RegistryMarkets::init();
+        for _ in 0..count {
+            registry_markets
+                .add(RegistryMarket {
+                    money_market: Pubkey::new_unique(),
+                    collateral_pool_market: Pubkey::default(),
+                    adapter: MoneyMarket::PortFinance,
+                    status: MarketStatus::Active,
+                })
+                .unwrap();
+        }
+
+        registry_markets
+    }
 
     #[test]
     fn packing() {
@@ -488,23 +795,20 @@ pub mod tests {
             mint: pk,
         });
 
-        let mut money_market_program_ids = DistributionPubkeys::default();
-        money_market_program_ids[0] = pk;
-        money_market_program_ids[1] = pk;
+        let registry_markets = registry_markets(2);
 
         let mut oracle: TokenOracle = Default::default();
-        let mut distribution = DistributionArray::default();
-        distribution[0] = 900_000_000u64;
-        distribution[1] = 100_000_000u64;
+        let distribution = vec![900_000_000u64, 100_000_000u64];
 
         oracle.reserve_rates.updated_at = current_slot;
         oracle
-            .update_liquidity_distribution(2, distribution)
+            .update_liquidity_distribution(2, distribution.clone())
             .unwrap();
 
         rebalancing
             .compute(
-                &money_market_program_ids,
+                &registry_markets,
+                &RebalancingLimits::default(),
                 oracle.clone(),
                 100_000_000,
                 current_slot,
@@ -528,23 +832,20 @@ pub mod tests {
             mint: pk,
         });
 
-        let mut money_market_program_ids = DistributionPubkeys::default();
-        money_market_program_ids[0] = pk;
-        money_market_program_ids[1] = pk;
+        let registry_markets = registry_markets(2);
 
         let mut token_oracle: TokenOracle = Default::default();
-        let mut distribution = DistributionArray::default();
-        distribution[0] = 1_000_000_000u64;
-        distribution[1] = 0;
+        let mut distribution = vec![1_000_000_000u64, 0];
 
         token_oracle.reserve_rates.updated_at = current_slot;
         token_oracle
-            .update_liquidity_distribution(2, distribution)
+            .update_liquidity_distribution(2, distribution.clone())
             .unwrap();
 
         rebalancing
             .compute(
-                &money_market_program_ids,
+                &registry_markets,
+                &RebalancingLimits::default(),
                 token_oracle.clone(),
                 1,
                 current_slot,
@@ -557,11 +858,12 @@ pub mod tests {
 
         distribution[0] = 0;
         token_oracle
-            .update_liquidity_distribution(4, distribution)
+            .update_liquidity_distribution(4, distribution.clone())
             .unwrap();
         rebalancing
             .compute(
-                &money_market_program_ids,
+                &registry_markets,
+                &RebalancingLimits::default(),
                 token_oracle.clone(),
                 1,
                 current_slot,
@@ -570,4 +872,532 @@ pub mod tests {
 
         println!("rebalancing = {:#?}", rebalancing);
     }
+
+    #[test]
+    fn computing_with_inactive_markets() {
+        let current_slot = 1;
+        let pk = Pubkey::new_unique();
+        let mut rebalancing: Rebalancing = Default::default();
+        rebalancing.init(InitRebalancingParams {
+            depositor: pk,
+            mint: pk,
+        });
+
+        let mut registry_markets = registry_markets(3);
+
+        let mut token_oracle: TokenOracle = Default::default();
+        token_oracle.reserve_rates.updated_at = current_slot;
+        token_oracle
+            .update_liquidity_distribution(2, vec![500_000_000u64, 300_000_000u64, 200_000_000u64])
+            .unwrap();
+
+        rebalancing
+            .compute(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                token_oracle.clone(),
+                100,
+                current_slot,
+            )
+            .unwrap();
+        assert_eq!(rebalancing.distributed_liquidity, vec![50, 30, 20]);
+        for collateral in [50, 30, 20] {
+            rebalancing
+                .execute_step(RebalancingOperation::Deposit, Some(collateral), 2)
+                .unwrap();
+        }
+
+        // Paused market keeps liquidity, retired market is withdrawn
+        registry_markets.markets[1].status = MarketStatus::Paused;
+        registry_markets.markets[2].status = MarketStatus::Retired;
+        token_oracle
+            .update_liquidity_distribution(3, vec![500_000_000u64, 0, 0])
+            .unwrap();
+
+        rebalancing
+            .compute(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                token_oracle,
+                100,
+                current_slot,
+            )
+            .unwrap();
+
+        assert_eq!(rebalancing.distributed_liquidity, vec![50, 30, 0]);
+        assert_eq!(rebalancing.steps.len(), 1);
+        assert_eq!(rebalancing.steps[0].money_market_index, 2);
+        assert_eq!(
+            rebalancing.steps[0].operation,
+            RebalancingOperation::Withdraw
+        );
+        assert_eq!(rebalancing.steps[0].collateral_amount, Some(20));
+    }
+
+    #[test]
+    fn emergency_withdraw() {
+        let current_slot = 1;
+        let pk = Pubkey::new_unique();
+        let mut rebalancing: Rebalancing = Default::default();
+        rebalancing.init(InitRebalancingParams {
+            depositor: pk,
+            mint: pk,
+        });
+
+        let registry_markets = registry_markets(3);
+
+        let mut token_oracle: TokenOracle = Default::default();
+        token_oracle.reserve_rates.updated_at = current_slot;
+        token_oracle
+            .update_liquidity_distribution(2, vec![500_000_000u64, 300_000_000u64, 200_000_000u64])
+            .unwrap();
+
+        rebalancing
+            .compute(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                token_oracle.clone(),
+                100,
+                current_slot,
+            )
+            .unwrap();
+
+        for collateral in [50, 30, 20] {
+            rebalancing
+                .execute_step(RebalancingOperation::Deposit, Some(collateral), 2)
+                .unwrap();
+        }
+
+        let step = rebalancing.emergency_withdraw(1, 3).unwrap();
+        assert_eq!(step.operation, RebalancingOperation::Withdraw);
+        assert_eq!(step.liquidity_amount, 30);
+        assert_eq!(step.collateral_amount, Some(30));
+        assert!(rebalancing.is_completed());
+        assert!(rebalancing.is_disabled(1));
+        assert_eq!(rebalancing.received_collateral[1], 0);
+        assert_eq!(rebalancing.distributed_liquidity, vec![50, 0, 20]);
+
+        // Disabled market gets no liquidity
+        token_oracle
+            .update_liquidity_distribution(4, vec![500_000_000u64, 300_000_000u64, 200_000_000u64])
+            .unwrap();
+        rebalancing
+            .compute(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                token_oracle,
+                100,
+                current_slot,
+            )
+            .unwrap();
+
+        assert_eq!(rebalancing.distributed_liquidity[1], 0);
+        assert!(rebalancing
+            .steps
+            .iter()
+            .all(|step| step.money_market_index != 1));
+
+        rebalancing.set_disabled(1, false);
+        assert!(!rebalancing.is_disabled(1));
+    }
+
+    #[test]
+    fn emergency_withdraw_during_rebalancing() {
+        let current_slot = 1;
+        let pk = Pubkey::new_unique();
+        let mut rebalancing: Rebalancing = Default::default();
+        rebalancing.init(InitRebalancingParams {
+            depositor: pk,
+            mint: pk,
+        });
+
+        let registry_markets = registry_markets(3);
+
+        let mut token_oracle: TokenOracle = Default::default();
+        token_oracle.reserve_rates.updated_at = current_slot;
+        token_oracle
+            .update_liquidity_distribution(2, vec![500_000_000u64, 300_000_000u64, 200_000_000u64])
+            .unwrap();
+
+        rebalancing
+            .compute(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                token_oracle.clone(),
+                100,
+                current_slot,
+            )
+            .unwrap();
+        for collateral in [50, 30, 20] {
+            rebalancing
+                .execute_step(RebalancingOperation::Deposit, Some(collateral), 2)
+                .unwrap();
+        }
+
+        // Move liquidity from the first market to the second one
+        token_oracle
+            .update_liquidity_distribution(3, vec![200_000_000u64, 600_000_000u64, 200_000_000u64])
+            .unwrap();
+        rebalancing
+            .compute(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                token_oracle,
+                100,
+                current_slot,
+            )
+            .unwrap();
+        assert_eq!(rebalancing.steps.len(), 2);
+        rebalancing
+            .execute_step(RebalancingOperation::Withdraw, None, 3)
+            .unwrap();
+
+        // Pending deposit of the market is dropped
+        let step = rebalancing.emergency_withdraw(1, 4).unwrap();
+        assert_eq!(step.liquidity_amount, 30);
+        assert_eq!(step.collateral_amount, Some(30));
+        assert_eq!(step.executed_at, Some(4));
+        assert!(rebalancing.is_completed());
+        assert!(rebalancing.is_disabled(1));
+        assert_eq!(rebalancing.received_collateral, vec![20, 0, 20]);
+        assert_eq!(rebalancing.distributed_liquidity, vec![20, 0, 20]);
+        assert_eq!(rebalancing.steps.len(), 2);
+        assert_eq!(rebalancing.steps[0].money_market_index, 0);
+        assert_eq!(rebalancing.steps[1], step);
+    }
+
+    #[test]
+    fn reduce_pending_deposits() {
+        let current_slot = 1;
+        let pk = Pubkey::new_unique();
+        let mut rebalancing: Rebalancing = Default::default();
+        rebalancing.init(InitRebalancingParams {
+            depositor: pk,
+            mint: pk,
+        });
+
+        let registry_markets = registry_markets(3);
+
+        let mut token_oracle: TokenOracle = Default::default();
+        token_oracle.reserve_rates.updated_at = current_slot;
+        token_oracle
+            .update_liquidity_distribution(2, vec![1_000_000_000u64, 0, 0])
+            .unwrap();
+
+        rebalancing
+            .compute(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                token_oracle.clone(),
+                100,
+                current_slot,
+            )
+            .unwrap();
+        rebalancing
+            .execute_step(RebalancingOperation::Deposit, Some(100), 2)
+            .unwrap();
+
+        // Move liquidity from the first market to the others
+        token_oracle.reserve_rates.values = vec![0, 500_000_000u64, 0];
+        token_oracle
+            .update_liquidity_distribution(3, vec![0, 500_000_000u64, 500_000_000u64])
+            .unwrap();
+        rebalancing
+            .compute(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                token_oracle,
+                100,
+                current_slot,
+            )
+            .unwrap();
+        assert_eq!(rebalancing.steps.len(), 3);
+
+        // Withdrawal returned 70 of 100
+        rebalancing
+            .execute_step(RebalancingOperation::Withdraw, None, 3)
+            .unwrap();
+        rebalancing.add_loss(30).unwrap();
+        rebalancing.reduce_pending_deposits(30).unwrap();
+
+        assert_eq!(rebalancing.steps[1].money_market_index, 1);
+        assert_eq!(rebalancing.steps[1].liquidity_amount, 50);
+        assert_eq!(rebalancing.steps[1].collateral_amount, Some(25));
+        assert_eq!(rebalancing.steps[2].money_market_index, 2);
+        assert_eq!(rebalancing.steps[2].liquidity_amount, 20);
+        assert_eq!(rebalancing.steps[2].collateral_amount, None);
+        assert_eq!(rebalancing.distributed_liquidity, vec![0, 50, 20]);
+        assert_eq!(rebalancing.unsettled_loss, 30);
+
+        // Loss over the pending deposits is left to the settlement
+        rebalancing.reduce_pending_deposits(80).unwrap();
+        assert_eq!(rebalancing.steps[1].liquidity_amount, 0);
+        assert_eq!(rebalancing.steps[1].collateral_amount, Some(0));
+        assert_eq!(rebalancing.steps[2].liquidity_amount, 0);
+        assert_eq!(rebalancing.distributed_liquidity, vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn computing_with_limits() {
+        let current_slot = 1;
+        let registry_markets = registry_markets(3);
+        let money_market = |index: usize| registry_markets.markets[index].money_market;
+
+        let mut token_oracle: TokenOracle = Default::default();
+        token_oracle.reserve_rates.updated_at = current_slot;
+        token_oracle
+            .update_liquidity_distribution(2, vec![600_000_000u64, 300_000_000u64, 100_000_000u64])
+            .unwrap();
+
+        // Excess of clipped markets is spread between markets with remaining capacity
+        let mut rebalancing_limits: RebalancingLimits = Default::default();
+        rebalancing_limits
+            .set(
+                vec![
+                    MarketLimit {
+                        money_market: money_market(1),
+                        max_liquidity: 320,
+                        max_share: 1_000_000_000,
+                    },
+                    MarketLimit {
+                        money_market: money_market(0),
+                        max_liquidity: u64::MAX,
+                        max_share: 400_000_000,
+                    },
+                ],
+                0,
+                0,
+            )
+            .unwrap();
+
+        let mut rebalancing: Rebalancing = Default::default();
+        rebalancing
+            .compute(
+                &registry_markets,
+                &rebalancing_limits,
+                token_oracle.clone(),
+                1000,
+                current_slot,
+            )
+            .unwrap();
+        assert_eq!(rebalancing.distributed_liquidity, vec![400, 320, 280]);
+
+        // Excess without remaining capacity stays undistributed
+        let limits = (0..3)
+            .map(|index| MarketLimit {
+                money_market: money_market(index),
+                max_liquidity: u64::MAX,
+                max_share: 200_000_000,
+            })
+            .collect();
+        rebalancing_limits.set(limits, 0, 0).unwrap();
+
+        let mut rebalancing: Rebalancing = Default::default();
+        rebalancing
+            .compute(
+                &registry_markets,
+                &rebalancing_limits,
+                token_oracle,
+                1000,
+                current_slot,
+            )
+            .unwrap();
+        assert_eq!(rebalancing.distributed_liquidity, vec![200, 200, 200]);
+        assert_eq!(rebalancing.total_distributed_liquidity().unwrap(), 600);
+
+        // Duplicated market limits
+        let limit = MarketLimit {
+            money_market: money_market(0),
+            max_liquidity: u64::MAX,
+            max_share: 200_000_000,
+        };
+        assert_eq!(
+            rebalancing_limits.set(vec![limit; 2], 0, 0).unwrap_err(),
+            ProgramError::InvalidArgument
+        );
+    }
+
+    #[test]
+    fn computing_with_limits_after_market_reorder() {
+        let current_slot = 1;
+        let mut registry_markets = registry_markets(2);
+
+        let mut rebalancing_limits: RebalancingLimits = Default::default();
+        rebalancing_limits
+            .set(
+                vec![MarketLimit {
+                    money_market: registry_markets.markets[0].money_market,
+                    max_liquidity: 100,
+                    max_share: 1_000_000_000,
+                }],
+                0,
+                0,
+            )
+            .unwrap();
+
+        // Limit follows the market to its new position
+        registry_markets.markets.swap(0, 1);
+
+        let mut token_oracle: TokenOracle = Default::default();
+        token_oracle.reserve_rates.updated_at = current_slot;
+        token_oracle
+            .update_liquidity_distribution(2, vec![500_000_000u64, 500_000_000u64])
+            .unwrap();
+
+        let mut rebalancing: Rebalancing = Default::default();
+        rebalancing
+            .compute(
+                &registry_markets,
+                &rebalancing_limits,
+                token_oracle,
+                1000,
+                current_slot,
+            )
+            .unwrap();
+        assert_eq!(rebalancing.distributed_liquidity, vec![900, 100]);
+    }
+
+    #[test]
+    fn computing_with_refresh_income_and_limits() {
+        let current_slot = 1;
+        let registry_markets = registry_markets(2);
+
+        let mut rebalancing_limits: RebalancingLimits = Default::default();
+        rebalancing_limits
+            .set(
+                vec![MarketLimit {
+                    money_market: registry_markets.markets[0].money_market,
+                    max_liquidity: 300,
+                    max_share: 1_000_000_000,
+                }],
+                0,
+                0,
+            )
+            .unwrap();
+
+        let mut rebalancing: Rebalancing = Default::default();
+        rebalancing
+            .set(1000, vec![300, 700], vec![500_000_000, 500_000_000])
+            .unwrap();
+        rebalancing
+            .compute_with_refresh_income(
+                &registry_markets,
+                &rebalancing_limits,
+                0,
+                current_slot,
+                1000,
+            )
+            .unwrap();
+
+        // Capped market doesn't get its 50% back, excess goes to the other market
+        assert_eq!(rebalancing.distributed_liquidity, vec![300, 700]);
+        assert_eq!(rebalancing.steps[1].liquidity_amount, 300);
+        assert_eq!(rebalancing.steps[3].liquidity_amount, 700);
+    }
+
+    #[test]
+    fn computing_with_reserve_rates_window() {
+        let current_slot = 10;
+        let registry_markets = registry_markets(2);
+
+        let mut token_oracle: TokenOracle = Default::default();
+        token_oracle.reserve_rates.updated_at = current_slot - 5;
+        token_oracle
+            .update_liquidity_distribution(2, vec![500_000_000u64, 500_000_000u64])
+            .unwrap();
+
+        // Reserve rates must be updated within the same slot by default
+        let mut rebalancing: Rebalancing = Default::default();
+        assert_eq!(
+            rebalancing.compute(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                token_oracle.clone(),
+                100,
+                current_slot,
+            ),
+            Err(EverlendError::ReserveRatesStale.into())
+        );
+
+        let mut rebalancing_limits: RebalancingLimits = Default::default();
+        rebalancing_limits.set(vec![], 5, 0).unwrap();
+
+        rebalancing
+            .compute(
+                &registry_markets,
+                &rebalancing_limits,
+                token_oracle,
+                100,
+                current_slot,
+            )
+            .unwrap();
+        assert_eq!(rebalancing.distributed_liquidity, vec![50, 50]);
+    }
+
+    #[test]
+    fn loss_accounting() {
+        let current_slot = 1;
+        let registry_markets = registry_markets(2);
+
+        let mut rebalancing: Rebalancing = Default::default();
+        rebalancing
+            .set(100, vec![50, 50], vec![500_000_000, 500_000_000])
+            .unwrap();
+        rebalancing
+            .compute_with_refresh_income(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                0,
+                current_slot,
+                100,
+            )
+            .unwrap();
+        assert_eq!(rebalancing.steps.len(), 4);
+
+        // Money market returned 40 of 50
+        rebalancing
+            .execute_step(RebalancingOperation::RefreshWithdraw, Some(0), current_slot)
+            .unwrap();
+        rebalancing.add_loss(10).unwrap();
+        assert_eq!(rebalancing.reduce_refresh_deposit(10).unwrap(), 40);
+        assert_eq!(rebalancing.steps[1].liquidity_amount, 40);
+        assert_eq!(rebalancing.distributed_liquidity, vec![40, 50]);
+
+        rebalancing
+            .execute_step(RebalancingOperation::RefreshDeposit, Some(0), current_slot)
+            .unwrap();
+        assert_eq!(
+            rebalancing.reduce_refresh_deposit(10),
+            Err(EverlendError::InvalidRebalancingOperation.into())
+        );
+
+        // Safety fund covers part of the loss, the rest is written off
+        assert_eq!(rebalancing.settle_loss(4).unwrap(), 6);
+        assert_eq!(rebalancing.unsettled_loss, 0);
+        assert_eq!(rebalancing.total_loss, 10);
+        assert_eq!(rebalancing.total_covered_loss, 4);
+
+        assert_eq!(
+            rebalancing.settle_loss(1),
+            Err(EverlendError::MathOverflow.into())
+        );
+    }
+
+    #[test]
+    fn keeper_config() {
+        let mut rebalancing_limits = RebalancingLimits::default();
+        assert_eq!(
+            rebalancing_limits.set_keeper_config(true, MAX_KEEPER_REWARD_SHARE + 1, 0),
+            Err(ProgramError::InvalidArgument)
+        );
+        rebalancing_limits
+            .set_keeper_config(true, MAX_KEEPER_REWARD_SHARE, 100)
+            .unwrap();
+
+        let mut rebalancing: Rebalancing = Default::default();
+        rebalancing.set_keeper_config(&rebalancing_limits);
+        assert!(rebalancing.permissionless_crank);
+        assert_eq!(rebalancing.keeper_reward_share, MAX_KEEPER_REWARD_SHARE);
+        assert_eq!(rebalancing.keeper_step_reward, 100);
+    }
 }
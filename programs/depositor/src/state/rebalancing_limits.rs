@@ -0,0 +1,326 @@
+//! Program state definitions
+
+use super::AccountType;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_registry::state::{RegistryMarkets, MAX_MONEY_MARKETS};
+use everlend_utils::{math, EverlendError, PRECISION_SCALER};
+use solana_program::{
+    clock::Slot,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+use std::cmp::min;
+
+/// Max share of the withdrawal income paid to the keeper reward transit, 10%
+pub const MAX_KEEPER_REWARD_SHARE: u64 = (PRECISION_SCALER / 10) as u64;
+
+/// Limits of a single money market for a token
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct MarketLimit {
+    /// Money market program id the limit belongs to
+    pub money_market: Pubkey,
+    /// Max liquidity amount deposited into the market
+    pub max_liquidity: u64,
+    /// Max share of the amount to distribute (PRECISION_SCALER based)
+    pub max_share: u64,
+}
+
+impl MarketLimit {
+    /// Market limit LEN
+    pub const LEN: usize = 32 + 8 + 8;
+
+    /// Limit without any restrictions
+    pub const UNLIMITED: MarketLimit = MarketLimit {
+        money_market: Pubkey::new_from_array([0; 32]),
+        max_liquidity: u64::MAX,
+        max_share: PRECISION_SCALER as u64,
+    };
+
+    /// Max liquidity allowed for the amount to distribute
+    pub fn cap(&self, amount_to_distribute: u64) -> Result<u64, ProgramError> {
+        Ok(min(
+            self.max_liquidity,
+            math::share_floor(amount_to_distribute, self.max_share)?,
+        ))
+    }
+}
+
+impl Default for MarketLimit {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// Rebalancing limits
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct RebalancingLimits {
+    /// Account type - RebalancingLimits
+    pub account_type: AccountType,
+
+    /// Depositor
+    pub depositor: Pubkey,
+
+    /// Mint
+    pub mint: Pubkey,
+
+    /// Max age of oracle reserve rates in slots
+    pub reserve_rates_window: Slot,
+
+    /// Max deviation of oracle reserve rates from money market exchange rates
+    /// (PRECISION_SCALER based). Zero disables the check.
+    pub max_reserve_rate_deviation: u64,
+
+    /// Limits keyed by money market program id,
+    /// so they stay with the market when the registry list changes
+    pub limits: Vec<MarketLimit>,
+
+    /// Anyone can execute the computed rebalancing and compute it from a fresh oracle update
+    pub permissionless_crank: bool,
+
+    /// Share of the withdrawal income paid to the keeper reward transit (PRECISION_SCALER based)
+    pub keeper_reward_share: u64,
+
+    /// Rebalancing follows the yield distribution computed on-chain
+    /// instead of the liquidity oracle distribution
+    pub yield_optimization: bool,
+
+    /// Reward paid to the keeper for each executed step from the keeper reward transit
+    pub keeper_step_reward: u64,
+}
+
+impl RebalancingLimits {
+    /// Initialize a rebalancing limits
+    pub fn init(&mut self, params: InitRebalancingLimitsParams) {
+        self.account_type = AccountType::RebalancingLimits;
+        self.depositor = params.depositor;
+        self.mint = params.mint;
+    }
+
+    /// Set market limits
+    pub fn set(
+        &mut self,
+        limits: Vec<MarketLimit>,
+        reserve_rates_window: Slot,
+        max_reserve_rate_deviation: u64,
+    ) -> Result<(), ProgramError> {
+        if limits.len() > MAX_MONEY_MARKETS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if limits
+            .iter()
+            .any(|limit| limit.max_share > PRECISION_SCALER as u64)
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // One limit per money market
+        for (index, limit) in limits.iter().enumerate() {
+            if limits[..index]
+                .iter()
+                .any(|prev| prev.money_market == limit.money_market)
+            {
+                return Err(ProgramError::InvalidArgument);
+            }
+        }
+
+        if max_reserve_rate_deviation > PRECISION_SCALER as u64 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        self.limits = limits;
+        self.reserve_rates_window = reserve_rates_window;
+        self.max_reserve_rate_deviation = max_reserve_rate_deviation;
+
+        Ok(())
+    }
+
+    /// Set keeper config
+    pub fn set_keeper_config(
+        &mut self,
+        permissionless_crank: bool,
+        keeper_reward_share: u64,
+        keeper_step_reward: u64,
+    ) -> Result<(), ProgramError> {
+        if keeper_reward_share > MAX_KEEPER_REWARD_SHARE {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        self.permissionless_crank = permissionless_crank;
+        self.keeper_reward_share = keeper_reward_share;
+        self.keeper_step_reward = keeper_step_reward;
+
+        Ok(())
+    }
+
+    /// Check oracle reserve rates are fresh enough
+    pub fn check_reserve_rates_age(
+        &self,
+        updated_at: Slot,
+        current_slot: Slot,
+    ) -> Result<(), ProgramError> {
+        if current_slot.saturating_sub(updated_at) > self.reserve_rates_window {
+            return Err(EverlendError::ReserveRatesStale.into());
+        }
+
+        Ok(())
+    }
+
+    /// Check liquidity amount expected by oracle reserve rate against the amount
+    /// computed from money market exchange rate
+    pub fn check_reserve_rate_deviation(
+        &self,
+        expected_liquidity_amount: u64,
+        real_liquidity_amount: u64,
+    ) -> Result<(), ProgramError> {
+        if self.max_reserve_rate_deviation == 0 {
+            return Ok(());
+        }
+
+        let deviation = math::percent_ratio(
+            math::abs_diff(expected_liquidity_amount, real_liquidity_amount)?,
+            expected_liquidity_amount,
+            PRECISION_SCALER as u64,
+        )?;
+        if deviation > self.max_reserve_rate_deviation {
+            msg!("Reserve rate deviation: {}", deviation);
+            return Err(EverlendError::ReserveRateDeviationExceeded.into());
+        }
+
+        Ok(())
+    }
+
+    /// Get market limit by money market program id. Markets without limit are unlimited.
+    pub fn get(&self, money_market: &Pubkey) -> MarketLimit {
+        self.limits
+            .iter()
+            .find(|limit| limit.money_market == *money_market)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Liquidity caps following the registry market list order
+    pub fn caps(
+        &self,
+        registry_markets: &RegistryMarkets,
+        amount_to_distribute: u64,
+    ) -> Result<Vec<u64>, ProgramError> {
+        registry_markets
+            .markets
+            .iter()
+            .map(|market| self.get(&market.money_market).cap(amount_to_distribute))
+            .collect()
+    }
+
+    /// Clip distributed amounts by market limits and redistribute excess
+    /// to the markets with remaining capacity following their weights.
+    /// Returns the amount that can't be distributed.
+    pub fn apply(
+        &self,
+        registry_markets: &RegistryMarkets,
+        amount_to_distribute: u64,
+        amounts: &mut [u64],
+        weights: &[u64],
+    ) -> Result<u64, ProgramError> {
+        let caps = self.caps(registry_markets, amount_to_distribute)?;
+        if caps.len() != amounts.len() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut excess = 0u64;
+        for (index, amount) in amounts.iter_mut().enumerate() {
+            if *amount > caps[index] {
+                msg!(
+                    "Market {}: {}",
+                    index,
+                    EverlendError::LiquidityDistributionClipped
+                );
+                excess = excess
+                    .checked_add(*amount - caps[index])
+                    .ok_or(EverlendError::MathOverflow)?;
+                *amount = caps[index];
+            }
+        }
+
+        // Each round fills at least one market or spends all excess
+        for _ in 0..amounts.len() {
+            if excess == 0 {
+                break;
+            }
+
+            let total_weight = (0..amounts.len())
+                .filter(|&index| amounts[index] < caps[index])
+                .try_fold(0u64, |acc, index| acc.checked_add(weights[index]))
+                .ok_or(EverlendError::MathOverflow)?;
+            if total_weight == 0 {
+                break;
+            }
+
+            let mut redistributed = 0u64;
+            for (index, amount) in amounts.iter_mut().enumerate() {
+                if *amount >= caps[index] || weights[index] == 0 {
+                    continue;
+                }
+
+                let share = math::percent_ratio(weights[index], total_weight, excess)?;
+                let share = min(share, caps[index] - *amount);
+
+                *amount = amount
+                    .checked_add(share)
+                    .ok_or(EverlendError::MathOverflow)?;
+                redistributed = redistributed
+                    .checked_add(share)
+                    .ok_or(EverlendError::MathOverflow)?;
+            }
+
+            if redistributed == 0 {
+                break;
+            }
+
+            excess = excess
+                .checked_sub(redistributed)
+                .ok_or(EverlendError::MathOverflow)?;
+        }
+
+        Ok(excess)
+    }
+}
+
+/// Initialize a RebalancingLimits params
+pub struct InitRebalancingLimitsParams {
+    /// Depositor
+    pub depositor: Pubkey,
+    /// Mint
+    pub mint: Pubkey,
+}
+
+impl Sealed for RebalancingLimits {}
+impl Pack for RebalancingLimits {
+    // 1 + 32 + 32 + 8 + 8 + (4 + 48 * 16) + 1 + 8 + 1 + 8 = 871
+    const LEN: usize =
+        1 + 32 + 32 + 8 + 8 + (4 + MarketLimit::LEN * MAX_MONEY_MARKETS) + 1 + 8 + 1 + 8;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for RebalancingLimits {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::RebalancingLimits
+    }
+}
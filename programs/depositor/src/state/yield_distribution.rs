@@ -0,0 +1,309 @@
+//! Program state definitions
+
+use super::{AccountType, RebalancingLimits};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_liquidity_oracle::state::{Distribution, DistributionArray};
+use everlend_registry::state::{RegistryMarkets, MAX_MONEY_MARKETS};
+use everlend_utils::{integrations::ReserveRates, EverlendError, PRECISION_SCALER};
+use solana_program::{
+    clock::Slot,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Amount to distribute is spread between markets by 5% steps
+pub const YIELD_DISTRIBUTION_STEPS: u64 = 20;
+
+/// Liquidity distribution computed on-chain from money market reserves
+#[repr(C)]
+#[derive(Debug, Clone, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct YieldDistribution {
+    /// Account type - YieldDistribution
+    pub account_type: AccountType,
+
+    /// Depositor
+    pub depositor: Pubkey,
+
+    /// Mint
+    pub mint: Pubkey,
+
+    /// Distribution maximizing the blended supply rate
+    pub liquidity_distribution: Distribution,
+
+    /// Expected supply rates of the markets after distribution (PRECISION_SCALER based)
+    pub supply_rates: DistributionArray,
+}
+
+impl YieldDistribution {
+    /// Initialize a yield distribution
+    pub fn init(&mut self, params: InitYieldDistributionParams) {
+        self.account_type = AccountType::YieldDistribution;
+        self.depositor = params.depositor;
+        self.mint = params.mint;
+    }
+
+    /// Greedily spread the amount to distribute by steps to the markets
+    /// with the highest marginal interest, our own deposit lowers the supply rate
+    /// of the market. Reserves must exclude the liquidity we have already supplied.
+    pub fn compute(
+        &mut self,
+        registry_markets: &RegistryMarkets,
+        rebalancing_limits: &RebalancingLimits,
+        reserves: &[Option<ReserveRates>],
+        amount_to_distribute: u64,
+        current_slot: Slot,
+    ) -> Result<(), ProgramError> {
+        if reserves.len() > registry_markets.markets.len() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let markets_count = registry_markets.markets.len();
+        let step_amount = amount_to_distribute / YIELD_DISTRIBUTION_STEPS;
+        let caps = rebalancing_limits.caps(registry_markets, amount_to_distribute)?;
+
+        let mut steps = vec![0u64; markets_count];
+        let mut amounts = vec![0u64; markets_count];
+
+        if step_amount > 0 {
+            for _ in 0..YIELD_DISTRIBUTION_STEPS {
+                let mut best: Option<(usize, u128)> = None;
+
+                for (index, reserve) in reserves.iter().enumerate() {
+                    let reserve = match reserve {
+                        Some(reserve) if registry_markets.markets[index].is_active() => reserve,
+                        _ => continue,
+                    };
+
+                    let new_amount = amounts[index]
+                        .checked_add(step_amount)
+                        .ok_or(EverlendError::MathOverflow)?;
+                    if new_amount > caps[index] {
+                        continue;
+                    }
+
+                    let interest = reserve
+                        .supply_interest(new_amount)?
+                        .saturating_sub(reserve.supply_interest(amounts[index])?);
+                    if best.map_or(true, |(_, best_interest)| interest > best_interest) {
+                        best = Some((index, interest));
+                    }
+                }
+
+                match best {
+                    Some((index, interest)) if interest > 0 => {
+                        steps[index] += 1;
+                        amounts[index] += step_amount;
+                    }
+                    // Markets are capped or have no income
+                    _ => break,
+                }
+            }
+        }
+
+        let mut supply_rates = vec![0; markets_count];
+        for (index, reserve) in reserves.iter().enumerate() {
+            if let Some(reserve) = reserve {
+                supply_rates[index] = reserve.supply_rate(amounts[index])?;
+            }
+        }
+
+        let values = steps
+            .iter()
+            .map(|steps| {
+                (*steps as u128 * PRECISION_SCALER / YIELD_DISTRIBUTION_STEPS as u128) as u64
+            })
+            .collect();
+        msg!("Yield distribution: {:?}", values);
+
+        self.liquidity_distribution = Distribution {
+            values,
+            updated_at: current_slot,
+        };
+        self.supply_rates = supply_rates;
+
+        Ok(())
+    }
+}
+
+/// Initialize a yield distribution params
+pub struct InitYieldDistributionParams {
+    /// Depositor
+    pub depositor: Pubkey,
+    /// Mint
+    pub mint: Pubkey,
+}
+
+impl Sealed for YieldDistribution {}
+impl Pack for YieldDistribution {
+    // 1 + 32 + 32 + 140 + (4 + 8 * 16) = 337
+    const LEN: usize = 1 + 32 + 32 + Distribution::LEN + (4 + 8 * MAX_MONEY_MARKETS);
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for YieldDistribution {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::YieldDistribution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MarketLimit;
+    use everlend_registry::state::{MarketStatus, RegistryMarket};
+    use everlend_utils::integrations::MoneyMarket;
+
+    fn registry_markets(count: usize) -> RegistryMarkets {
+        let mut registry_markets = RegistryMarkets::init();
+        for _ in 0..count {
+            registry_markets
+                .add(RegistryMarket {
+                    money_market: Pubkey::new_unique(),
+                    collateral_pool_market: Pubkey::new_unique(),
+                    adapter: MoneyMarket::PortFinance,
+                    status: MarketStatus::Active,
+                })
+                .unwrap();
+        }
+
+        registry_markets
+    }
+
+    fn reserve(available_amount: u64, borrowed_amount: u64) -> ReserveRates {
+        ReserveRates {
+            liquidity_mint: Pubkey::default(),
+            collateral_mint: Pubkey::default(),
+            available_amount,
+            borrowed_amount,
+            borrow_rate_curve: vec![
+                (0, 0),
+                (
+                    ReserveRates::from_percent(80),
+                    ReserveRates::from_percent(10),
+                ),
+                (
+                    ReserveRates::from_percent(100),
+                    ReserveRates::from_percent(100),
+                ),
+            ],
+            fee_rate: 0,
+        }
+    }
+
+    #[test]
+    fn packing() {
+        let mut yield_distribution = YieldDistribution::default();
+        yield_distribution.init(InitYieldDistributionParams {
+            depositor: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+        });
+        yield_distribution.liquidity_distribution = Distribution {
+            values: vec![PRECISION_SCALER as u64 / 2; MAX_MONEY_MARKETS],
+            updated_at: 1,
+        };
+        yield_distribution.supply_rates = vec![1; MAX_MONEY_MARKETS];
+
+        let mut expected = [0; YieldDistribution::LEN];
+        YieldDistribution::pack(yield_distribution.clone(), &mut expected).unwrap();
+
+        assert_eq!(
+            YieldDistribution::unpack(&expected).unwrap(),
+            yield_distribution
+        );
+    }
+
+    #[test]
+    fn computing() {
+        let registry_markets = registry_markets(3);
+        let reserves = vec![
+            // 50% utilization
+            Some(reserve(1_000, 1_000)),
+            // 80% utilization, best rate but shallow
+            Some(reserve(50, 200)),
+            None,
+        ];
+
+        let mut yield_distribution = YieldDistribution::default();
+        yield_distribution
+            .compute(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                &reserves,
+                1_000,
+                10,
+            )
+            .unwrap();
+
+        let values = &yield_distribution.liquidity_distribution.values;
+        assert_eq!(yield_distribution.liquidity_distribution.updated_at, 10);
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[2], 0);
+        // Shallow market gets a part only, our deposit lowers its rate
+        assert!(values[1] > 0);
+        assert!(values[0] > values[1]);
+        assert_eq!(values.iter().sum::<u64>(), PRECISION_SCALER as u64);
+        assert!(yield_distribution.supply_rates[0] > 0);
+    }
+
+    #[test]
+    fn computing_with_limits() {
+        let registry_markets = registry_markets(2);
+        let reserves = vec![Some(reserve(50, 200)), Some(reserve(1_000, 100))];
+
+        let mut rebalancing_limits = RebalancingLimits::default();
+        rebalancing_limits
+            .set(
+                vec![MarketLimit {
+                    money_market: registry_markets.markets[0].money_market,
+                    max_liquidity: u64::MAX,
+                    max_share: PRECISION_SCALER as u64 / 10,
+                }],
+                0,
+                0,
+            )
+            .unwrap();
+
+        let mut yield_distribution = YieldDistribution::default();
+        yield_distribution
+            .compute(&registry_markets, &rebalancing_limits, &reserves, 1_000, 1)
+            .unwrap();
+
+        let values = &yield_distribution.liquidity_distribution.values;
+        assert_eq!(values[0], PRECISION_SCALER as u64 / 10);
+        assert_eq!(values[1], PRECISION_SCALER as u64 * 9 / 10);
+    }
+
+    #[test]
+    fn computing_without_income() {
+        let registry_markets = registry_markets(1);
+        let reserves = vec![Some(reserve(1_000, 0))];
+
+        let mut yield_distribution = YieldDistribution::default();
+        yield_distribution
+            .compute(
+                &registry_markets,
+                &RebalancingLimits::default(),
+                &reserves,
+                1_000,
+                1,
+            )
+            .unwrap();
+
+        assert_eq!(yield_distribution.liquidity_distribution.values, vec![0]);
+    }
+}
@@ -3,15 +3,18 @@
 use crate::money_market::{CollateralPool, CollateralStorage, Francium, MoneyMarket, Tulip};
 use crate::money_market::{Frakt, Jet, Larix, PortFinance, SPLLending, Solend};
 use crate::{
-    state::{InternalMining, MiningType},
+    state::{InternalMining, MiningType, Rebalancing},
     TransitPDA,
 };
 use everlend_collateral_pool::find_pool_withdraw_authority_program_address;
 use everlend_income_pools::utils::IncomePoolAccounts;
-use everlend_registry::state::RegistryMarkets;
+use everlend_registry::{
+    find_pause_addresses,
+    state::{PauseFlags, Registry, RegistryMarkets},
+};
 use everlend_utils::{
-    abs_diff, assert_account_key, cpi, find_program_address, integrations, AccountLoader,
-    EverlendError, PDA,
+    abs_diff, assert_account_key, cpi, find_program_address, integrations, share_floor,
+    AccountLoader, EverlendError, PDA,
 };
 use num_traits::Zero;
 use solana_program::{
@@ -19,7 +22,11 @@ use solana_program::{
     program_error::ProgramError, program_pack::Pack, pubkey::Pubkey,
 };
 use spl_token::state::Account;
-use std::{cmp::Ordering, iter::Enumerate, slice::Iter};
+use std::{
+    cmp::{min, Ordering},
+    iter::Enumerate,
+    slice::Iter,
+};
 
 const RESERVE_THRESHOLD: u64 = 20;
 
@@ -93,7 +100,7 @@ pub fn deposit<'a, 'b>(
     Ok(collateral_amount)
 }
 
-/// Withdraw
+/// Withdraw, returns the loss if the money market returned less than expected
 #[allow(clippy::too_many_arguments)]
 pub fn withdraw<'a, 'b>(
     income_pool_accounts: IncomePoolAccounts<'a, 'b>,
@@ -108,8 +115,9 @@ pub fn withdraw<'a, 'b>(
     collateral_storage: &Option<Box<dyn CollateralStorage<'b> + 'a>>,
     collateral_amount: u64,
     expected_liquidity_amount: u64,
+    keeper_reward: &Option<KeeperReward<'a, 'b>>,
     signers_seeds: &[&[&[u8]]],
-) -> ProgramResult {
+) -> Result<u64, ProgramError> {
     let liquidity_transit_supply = Account::unpack(&liquidity_transit.data.borrow())?.amount;
 
     if is_mining {
@@ -170,19 +178,41 @@ pub fn withdraw<'a, 'b>(
     match received_amount.cmp(&expected_liquidity_amount) {
         Ordering::Greater => {
             msg!("income_amount: {}", diff);
-            everlend_income_pools::cpi::deposit(
-                income_pool_accounts,
-                liquidity_transit.clone(),
-                authority.clone(),
-                diff,
-                signers_seeds,
-            )?;
+
+            let mut income_amount = diff;
+            if let Some(keeper_reward) = keeper_reward {
+                let reward = share_floor(diff, keeper_reward.reward_share)?;
+                if reward > 0 {
+                    msg!("keeper_reward_income: {}", reward);
+                    cpi::spl_token::transfer(
+                        liquidity_transit.clone(),
+                        keeper_reward.reward_transit.clone(),
+                        authority.clone(),
+                        reward,
+                        signers_seeds,
+                    )?;
+                    income_amount = income_amount
+                        .checked_sub(reward)
+                        .ok_or(EverlendError::MathOverflow)?;
+                }
+            }
+
+            if income_amount > 0 {
+                everlend_income_pools::cpi::deposit(
+                    income_pool_accounts,
+                    liquidity_transit.clone(),
+                    authority.clone(),
+                    income_amount,
+                    signers_seeds,
+                )?;
+            }
         }
         Ordering::Less => {
             msg!("income_amount: -{}", diff);
             if diff.gt(&RESERVE_THRESHOLD) {
-                // throw error,  this amount is too big, probably something is wrong
-                return Err(EverlendError::ReserveThreshold.into());
+                // Money market took a haircut, the loss is settled with the general pool
+                msg!("Money market loss: {}", diff);
+                return Ok(diff);
             }
 
             cpi::spl_token::transfer(
@@ -196,7 +226,79 @@ pub fn withdraw<'a, 'b>(
         Ordering::Equal => {}
     }
 
-    Ok(())
+    Ok(0)
+}
+
+/// Keeper reward accounts and config of the rebalancing
+pub struct KeeperReward<'a, 'b> {
+    /// Keeper token account
+    pub token_account: &'a AccountInfo<'b>,
+    /// Keeper reward transit funded by the share of the withdrawal income
+    pub reward_transit: &'a AccountInfo<'b>,
+    /// Share of the withdrawal income paid to the keeper reward transit
+    pub reward_share: u64,
+    /// Reward paid to the keeper for each executed step
+    pub step_reward: u64,
+}
+
+impl<'a, 'b> KeeperReward<'a, 'b> {
+    /// Pay the step reward from the keeper reward transit as long as it has funds
+    pub fn pay_step_reward(
+        &self,
+        authority: &AccountInfo<'b>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let reward_transit = Account::unpack(&self.reward_transit.data.borrow())?;
+        let reward = min(self.step_reward, reward_transit.amount);
+        if reward == 0 {
+            return Ok(());
+        }
+
+        msg!("keeper_reward: {}", reward);
+        cpi::spl_token::transfer(
+            self.reward_transit.clone(),
+            self.token_account.clone(),
+            authority.clone(),
+            reward,
+            signers_seeds,
+        )
+    }
+}
+
+/// Keeper reward of the permissionless crank.
+/// The keeper token account and the keeper reward transit are optional
+/// and follow the money market and collateral storage accounts.
+pub fn keeper_reward<'a, 'b>(
+    program_id: &Pubkey,
+    rebalancing: &Rebalancing,
+    liquidity_mint: &AccountInfo<'b>,
+    account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
+) -> Result<Option<KeeperReward<'a, 'b>>, ProgramError> {
+    if !rebalancing.permissionless_crank
+        || (rebalancing.keeper_reward_share == 0 && rebalancing.keeper_step_reward == 0)
+        || !AccountLoader::has_more(account_info_iter)
+    {
+        return Ok(None);
+    }
+
+    let token_account = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+    let keeper_token = Account::unpack(&token_account.data.borrow())?;
+    assert_account_key(liquidity_mint, &keeper_token.mint)?;
+
+    let (reward_transit_pubkey, _) = TransitPDA {
+        seed: "keeper",
+        depositor: rebalancing.depositor,
+        mint: rebalancing.mint,
+    }
+    .find_address(program_id);
+    let reward_transit = AccountLoader::next_with_key(account_info_iter, &reward_transit_pubkey)?;
+
+    Ok(Some(KeeperReward {
+        token_account,
+        reward_transit,
+        reward_share: rebalancing.keeper_reward_share,
+        step_reward: rebalancing.keeper_step_reward,
+    }))
 }
 
 /// Money market
@@ -229,18 +331,13 @@ pub fn money_market<'a, 'b>(
         return Ok((Box::new(spl), is_mining));
     }
 
-    let index = registry_markets
-        .money_markets
-        .iter()
-        .position(|&r| r.eq(money_market_program.key));
-
-    if index.is_none() {
-        return Err(EverlendError::IncorrectInstructionProgramId.into());
-    }
+    let adapter = registry_markets
+        .find(money_market_program.key)
+        .ok_or(EverlendError::IncorrectInstructionProgramId)?
+        .adapter;
 
-    match index.unwrap() {
-        // Port Finance
-        0 => {
+    match adapter {
+        integrations::MoneyMarket::PortFinance => {
             let port = PortFinance::init(
                 money_market_program.key.clone(),
                 money_market_account_info_iter,
@@ -250,8 +347,7 @@ pub fn money_market<'a, 'b>(
             )?;
             return Ok((Box::new(port), is_mining));
         }
-        // Larix
-        1 => {
+        integrations::MoneyMarket::Larix => {
             let larix = Larix::init(
                 money_market_program.key.clone(),
                 money_market_account_info_iter,
@@ -259,24 +355,21 @@ pub fn money_market<'a, 'b>(
             )?;
             return Ok((Box::new(larix), is_mining));
         }
-        // Solend
-        2 => {
+        integrations::MoneyMarket::Solend => {
             let solend = Solend::init(
                 money_market_program.key.clone(),
                 money_market_account_info_iter,
             )?;
             return Ok((Box::new(solend), is_mining));
         }
-        // Tulip
-        3 => {
+        integrations::MoneyMarket::Tulip => {
             let tulip = Tulip::init(
                 money_market_program.key.clone(),
                 money_market_account_info_iter,
             )?;
             return Ok((Box::new(tulip), is_mining));
         }
-        // Francium
-        4 => {
+        integrations::MoneyMarket::Francium => {
             let francium = Francium::init(
                 program_id,
                 money_market_program.key.clone(),
@@ -287,16 +380,14 @@ pub fn money_market<'a, 'b>(
             )?;
             return Ok((Box::new(francium), is_mining));
         }
-        //Jet
-        5 => {
+        integrations::MoneyMarket::Jet => {
             let jet = Jet::init(
                 money_market_program.key.clone(),
                 money_market_account_info_iter,
             )?;
             return Ok((Box::new(jet), is_mining));
         }
-        // Frakt
-        6 => {
+        integrations::MoneyMarket::Frakt => {
             let frakt = Frakt::init(
                 money_market_program.key.clone(),
                 program_id.clone(),
@@ -306,13 +397,13 @@ pub fn money_market<'a, 'b>(
             )?;
             return Ok((Box::new(frakt), is_mining));
         }
-        _ => Err(EverlendError::IncorrectInstructionProgramId.into()),
     }
 }
 
 /// Money market
 pub fn collateral_storage<'a, 'b>(
     registry_markets: &RegistryMarkets,
+    registry: &'a AccountInfo<'b>,
     collateral_mint: &AccountInfo<'b>,
     depositor_authority: &AccountInfo<'b>,
     account_info_iter: &mut Enumerate<Iter<'a, AccountInfo<'b>>>,
@@ -325,6 +416,7 @@ pub fn collateral_storage<'a, 'b>(
 
     let coll_pool = CollateralPool::init(
         registry_markets,
+        registry,
         collateral_mint,
         depositor_authority,
         account_info_iter,
@@ -334,9 +426,27 @@ pub fn collateral_storage<'a, 'b>(
     Ok(Some(Box::new(coll_pool)))
 }
 
+/// Load pause flags of the general pool for the liquidity mint
+pub fn pause_flags(
+    registry: &AccountInfo,
+    liquidity_mint: &Pubkey,
+    global_pause: &AccountInfo,
+    pool_pause: &AccountInfo,
+) -> Result<PauseFlags, ProgramError> {
+    let general_pool_market = Registry::unpack(&registry.data.borrow())?.general_pool_market;
+    let (general_pool, _) = everlend_general_pool::find_pool_program_address(
+        &everlend_general_pool::id(),
+        &general_pool_market,
+        liquidity_mint,
+    );
+
+    PauseFlags::load(registry.key, &general_pool, global_pause, pool_pause)
+}
+
 /// Collateral pool deposit account
 #[allow(clippy::too_many_arguments)]
 pub fn collateral_pool_deposit_accounts(
+    registry: &Pubkey,
     pool_market: &Pubkey,
     collateral_mint: &Pubkey,
     collateral_pool_token_account: &Pubkey,
@@ -348,12 +458,15 @@ pub fn collateral_pool_deposit_accounts(
         pool_market,
         collateral_mint,
     );
+    let (global_pause, pool_pause) = find_pause_addresses(registry, &collateral_pool);
 
     vec![
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new_readonly(collateral_pool_market_authority, false),
         AccountMeta::new_readonly(collateral_pool, false),
         AccountMeta::new(*collateral_pool_token_account, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         AccountMeta::new_readonly(everlend_collateral_pool::id(), false),
     ]
 }
@@ -361,6 +474,7 @@ pub fn collateral_pool_deposit_accounts(
 /// Collateral pool deposit account
 #[allow(clippy::too_many_arguments)]
 pub fn collateral_pool_withdraw_accounts(
+    registry: &Pubkey,
     pool_market: &Pubkey,
     collateral_mint: &Pubkey,
     collateral_pool_token_account: &Pubkey,
@@ -374,6 +488,7 @@ pub fn collateral_pool_withdraw_accounts(
         pool_market,
         collateral_mint,
     );
+    let (global_pause, pool_pause) = find_pause_addresses(registry, &collateral_pool);
 
     let (depositor_authority, _) = find_program_address(depositor_program_id, depositor);
 
@@ -388,6 +503,8 @@ pub fn collateral_pool_withdraw_accounts(
         AccountMeta::new_readonly(collateral_pool_market_authority, false),
         AccountMeta::new_readonly(collateral_pool, false),
         AccountMeta::new(*collateral_pool_token_account, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         AccountMeta::new_readonly(everlend_collateral_pool::id(), false),
         AccountMeta::new_readonly(collateral_pool_withdraw_authority, false),
     ]
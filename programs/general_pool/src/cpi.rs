@@ -1,7 +1,7 @@
 //! CPI
 
 use solana_program::{
-    account_info::AccountInfo, program::invoke_signed, program_error::ProgramError,
+    account_info::AccountInfo, program::invoke_signed, program_error::ProgramError, pubkey::Pubkey,
 };
 
 /// General pool borrow tokens
@@ -10,6 +10,9 @@ pub fn borrow<'a>(
     pool_market: AccountInfo<'a>,
     pool_market_authority: AccountInfo<'a>,
     pool: AccountInfo<'a>,
+    registry: &Pubkey,
+    global_pause: AccountInfo<'a>,
+    pool_pause: AccountInfo<'a>,
     pool_borrow_authority: AccountInfo<'a>,
     destination: AccountInfo<'a>,
     token_account: AccountInfo<'a>,
@@ -21,6 +24,7 @@ pub fn borrow<'a>(
         &crate::id(),
         pool_market.key,
         pool.key,
+        registry,
         pool_borrow_authority.key,
         destination.key,
         token_account.key,
@@ -33,6 +37,8 @@ pub fn borrow<'a>(
         &[
             pool_market,
             pool,
+            global_pause,
+            pool_pause,
             pool_borrow_authority,
             pool_market_authority,
             destination,
@@ -83,3 +89,28 @@ pub fn repay<'a>(
         signers_seeds,
     )
 }
+
+/// General pool write off the loss of borrowed funds
+pub fn write_off_loss<'a>(
+    pool_market: AccountInfo<'a>,
+    pool: AccountInfo<'a>,
+    pool_borrow_authority: AccountInfo<'a>,
+    borrow_authority: AccountInfo<'a>,
+    amount: u64,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), ProgramError> {
+    let ix = crate::instruction::write_off_loss(
+        &crate::id(),
+        pool_market.key,
+        pool.key,
+        pool_borrow_authority.key,
+        borrow_authority.key,
+        amount,
+    );
+
+    invoke_signed(
+        &ix,
+        &[pool_market, pool, pool_borrow_authority, borrow_authority],
+        signers_seeds,
+    )
+}
@@ -7,11 +7,14 @@ use solana_program::{
     system_program, sysvar,
 };
 
+use everlend_registry::find_pause_addresses;
 use everlend_utils::find_program_address;
 
 use crate::{
-    find_pool_borrow_authority_program_address, find_pool_config_program_address,
-    find_pool_program_address, find_transit_program_address,
+    find_fee_schedule_program_address, find_pool_borrow_authority_program_address,
+    find_pool_borrow_shares_program_address, find_pool_config_program_address,
+    find_pool_fees_program_address, find_pool_program_address,
+    find_queued_withdrawal_request_program_address, find_transit_program_address,
     find_withdrawal_request_program_address, find_withdrawal_requests_program_address,
     state::SetPoolConfigParams,
 };
@@ -44,6 +47,10 @@ pub enum LiquidityPoolsInstruction {
     /// [R] Rent sysvar
     /// [R] System program
     /// [R] Token program id
+    /// [W] Pool borrow shares
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
     CreatePool,
 
     /// Creates and initializes a pool borrow authority
@@ -52,10 +59,14 @@ pub enum LiquidityPoolsInstruction {
     /// [R] Pool market
     /// [R] Pool
     /// [W] Pool borrow authority
+    /// [W] Pool borrow shares
     /// [R] Borrow authority
     /// [WS] Market manager
     /// [R] Rent sysvar
     /// [R] System program
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
     CreatePoolBorrowAuthority {
         /// Share allowed
         share_allowed: u16,
@@ -67,7 +78,11 @@ pub enum LiquidityPoolsInstruction {
     /// [R] Pool market
     /// [R] Pool
     /// [W] Pool borrow authority
+    /// [W] Pool borrow shares
     /// [RS] Market manager
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
     UpdatePoolBorrowAuthority {
         /// Share allowed
         share_allowed: u16,
@@ -79,8 +94,12 @@ pub enum LiquidityPoolsInstruction {
     /// [R] Pool market
     /// [R] Pool
     /// [W] Pool borrow authority
+    /// [W] Pool borrow shares
     /// [W] Receiver lamports
     /// [RS] Market manager
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
     DeletePoolBorrowAuthority,
 
     /// Deposit funds in the pool
@@ -99,6 +118,11 @@ pub enum LiquidityPoolsInstruction {
     /// [W] User mining reward account
     /// [R] Everlend rewards program
     /// [R] Token program id
+    /// [R] Fee schedule
+    /// [W] Pool fees
+    /// [R] Clock sysvar
+    /// [R] Global pause
+    /// [R] Pool pause
     Deposit {
         /// Amount to deposit
         amount: u64,
@@ -110,6 +134,8 @@ pub enum LiquidityPoolsInstruction {
     /// [R] Pool market
     /// [R] Pool market authority
     /// [R] Pool
+    /// [R] Global pause
+    /// [R] Pool pause
     /// [W] Pool mint account
     /// [W] Withdrawal requests account
     /// [W] Withdrawal request account
@@ -132,6 +158,8 @@ pub enum LiquidityPoolsInstruction {
     /// Accounts:
     /// [R] Pool market
     /// [W] Pool
+    /// [R] Global pause
+    /// [R] Pool pause
     /// [W] Pool borrow authority
     /// [W] Destination account (for token mint)
     /// [W] Token account
@@ -167,6 +195,8 @@ pub enum LiquidityPoolsInstruction {
     /// [R] Pool config
     /// [R] Pool market
     /// [R] Pool
+    /// [R] Global pause
+    /// [R] Pool pause
     /// [W] Pool mint account
     /// [W] Withdrawal requests account
     /// [W] Withdrawal request account
@@ -182,6 +212,8 @@ pub enum LiquidityPoolsInstruction {
     /// [R] Clock sysvar
     /// [R] System program
     /// [R] Token program id
+    /// [R] Fee schedule
+    /// [W] Pool fees
     WithdrawRequest {
         /// Collateral amount
         collateral_amount: u64,
@@ -210,22 +242,27 @@ pub enum LiquidityPoolsInstruction {
     /// [R] Manager
     ClosePoolMarket,
 
-    /// Migrate account data
+    /// Migrate pool market to the layout with pending manager
     ///
+    /// Accounts:
+    /// [W] Pool market
+    /// [WS] Manager
+    /// [R] Rent sysvar
+    /// [R] System program
     MigrationInstruction,
 
     /// Init user mining account
     ///
     InitUserMining,
 
-    /// Update pool market manager
+    /// Propose a new pool market manager, takes effect after the new manager accepts it
     ///
     /// Accounts:
     /// [W] Pool market
-    /// [WS] Old manager
-    /// [RS] New manager
+    /// [RS] Manager
+    /// [R] New manager
     ///
-    UpdateManager,
+    ProposeManager,
 
     /// SetTokenMetadata
     ///
@@ -247,6 +284,9 @@ pub enum LiquidityPoolsInstruction {
     /// [WS] Manager
     /// [R] Rent sysvar
     /// [R] System program
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
     SetPoolConfig {
         /// Pool config update params
         params: SetPoolConfigParams,
@@ -266,6 +306,224 @@ pub enum LiquidityPoolsInstruction {
     /// [R] Everlend rewards program account
     /// [R] Token program id
     TransferDeposit,
+
+    /// Create or update pool market fee schedule
+    ///
+    /// Accounts:
+    /// [R] Pool market
+    /// [W] Fee schedule
+    /// [WS] Manager
+    /// [R] Rent sysvar
+    /// [R] System program
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
+    SetFeeSchedule {
+        /// Owner of the pool token accounts receiving fees
+        treasury: Pubkey,
+        /// Performance fee in basis points
+        performance_fee: u16,
+        /// Annual management fee in basis points
+        management_fee: u16,
+    },
+
+    /// Accrue pool fees and mint pool tokens to treasury
+    ///
+    /// Accounts:
+    /// [R] Pool market
+    /// [R] Fee schedule
+    /// [R] Pool
+    /// [R] Pool config
+    /// [W] Pool fees
+    /// [W] Pool mint
+    /// [R] Token account
+    /// [W] Treasury pool token account
+    /// [R] Market authority
+    /// [WS] Payer
+    /// [R] Clock sysvar
+    /// [R] Rent sysvar
+    /// [R] System program
+    /// [R] Token program id
+    AccrueFees,
+
+    /// Claims rewards of all reward vaults and deposits the claimed reward
+    /// of the pool token mint back to the pool
+    ///
+    /// Accounts:
+    /// [R] Pool config
+    /// [R] Pool market
+    /// [R] Pool
+    /// [W] Source account (user reward token account of the pool token mint)
+    /// [W] Destination account (for pool mint)
+    /// [W] Token account
+    /// [W] Pool mint account
+    /// [R] Pool market authority
+    /// [RS] User transfer authority
+    /// [W] Mining reward pool
+    /// [W] Mining reward account
+    /// [R] Everlend rewards program
+    /// [R] Token program id
+    /// [R] Fee schedule
+    /// [W] Pool fees
+    /// [R] Clock sysvar
+    /// [R] Global pause
+    /// [R] Pool pause
+    /// For each reward vault in the mining reward pool order:
+    /// [R] Reward mint
+    /// [W] Reward vault
+    /// [W] User reward token account
+    CompoundRewards,
+
+    /// Move the amount of pool tokens to destination user account
+    ///
+    /// Accounts:
+    /// [R] Pool
+    /// [W] Source account
+    /// [W] Destination account
+    /// [RS] User transfer authority
+    /// [R] Destination user transfer authority
+    /// [W] Mining reward pool
+    /// [W] Mining reward user account
+    /// [W] Destination mining reward user account
+    /// [R] Everlend rewards program account
+    /// [R] Token program id
+    TransferDepositAmount {
+        /// Amount of pool tokens to transfer
+        amount: u64,
+    },
+
+    /// Decrease user mining share down to the pool token balance
+    ///
+    /// Accounts:
+    /// [R] Pool market
+    /// [R] Pool
+    /// [R] User pool token account
+    /// [R] User
+    /// [RS] Authority (user, or anyone for the canonical account)
+    /// [W] Mining reward pool
+    /// [W] Mining reward user account
+    /// [R] Everlend rewards program account
+    ResyncMining,
+
+    /// Move pool tokens to transit account and add withdraw request to the pool queue
+    ///
+    /// Accounts:
+    /// [R] Pool config
+    /// [R] Pool market
+    /// [R] Pool
+    /// [R] Global pause
+    /// [R] Pool pause
+    /// [W] Pool mint account
+    /// [W] Withdrawal requests account
+    /// [W] Withdrawal request account (for the next queue index)
+    /// [W] Source token account (for pool mint)
+    /// [R] Destination token account (for token mint)
+    /// [W] Pool token account
+    /// [W] Collateral transit account
+    /// [WS] User transfer authority
+    /// [W] Mining reward pool
+    /// [W] User mining reward account
+    /// [R] Everlend rewards program
+    /// [R] Rent sysvar
+    /// [R] Clock sysvar
+    /// [R] System program
+    /// [R] Token program id
+    /// [R] Fee schedule
+    /// [W] Pool fees
+    QueueWithdrawRequest {
+        /// Collateral amount
+        collateral_amount: u64,
+    },
+
+    /// Process the first withdraw request of the pool queue, partially if the pool is short of liquidity
+    ///
+    /// Liquidity reserved for legacy withdraw requests is left in the pool
+    ///
+    /// Accounts:
+    /// [R] Pool market
+    /// [R] Pool market authority
+    /// [R] Pool
+    /// [R] Global pause
+    /// [R] Pool pause
+    /// [W] Pool mint account
+    /// [W] Withdrawal requests account
+    /// [W] Withdrawal request account (for the queue head index)
+    /// [W] Destination token account (for token mint)
+    /// [W] Pool token account
+    /// [W] Collateral transit account
+    /// [W] From account
+    /// [R] Clock sysvar
+    /// [R] Token program id
+    ProcessWithdrawalRequest,
+
+    /// Burn pool tokens and withdraw idle liquidity of the pool without delay,
+    /// charging the pool config exit fee
+    ///
+    /// Accounts:
+    /// [R] Pool config
+    /// [R] Pool market
+    /// [R] Pool market authority
+    /// [R] Pool
+    /// [R] Global pause
+    /// [R] Pool pause
+    /// [R] Withdrawal requests account
+    /// [W] Source token account (for pool mint)
+    /// [W] Destination token account (for token mint)
+    /// [W] Pool token account
+    /// [W] Pool mint account
+    /// [RS] User transfer authority
+    /// [W] Mining reward pool
+    /// [W] User mining reward account
+    /// [R] Everlend rewards program
+    /// [R] Token program id
+    /// [R] Fee schedule
+    /// [W] Pool fees
+    /// [R] Clock sysvar
+    InstantWithdraw {
+        /// Collateral amount
+        collateral_amount: u64,
+    },
+
+    /// Accept the pool market manager proposal
+    ///
+    /// Accounts:
+    /// [W] Pool market
+    /// [RS] New manager
+    AcceptManager,
+
+    /// Cancel the pool market manager proposal
+    ///
+    /// Accounts:
+    /// [W] Pool market
+    /// [RS] Manager
+    CancelManagerProposal,
+
+    /// Write off the loss of borrowed funds, dropping the pool token exchange rate
+    ///
+    /// Accounts:
+    /// [R] Pool market
+    /// [W] Pool
+    /// [W] Pool borrow authority
+    /// [RS] Borrow authority
+    WriteOffLoss {
+        /// Amount of lost liquidity
+        amount: u64,
+    },
+
+    /// Creates pool borrow shares of a pool created without them and counts the passed
+    /// borrow authorities of the deprecated layout in it. Borrow authorities left out
+    /// can't borrow until recreated
+    ///
+    /// Accounts:
+    /// [R] Pool market
+    /// [R] Pool
+    /// [W] Pool borrow shares
+    /// [WS] Market manager
+    /// [R] Rent sysvar
+    /// [R] System program
+    /// For each existing borrow authority of the pool:
+    /// [W] Pool borrow authority
+    InitPoolBorrowShares,
 }
 
 /// Creates 'InitPoolMarket' instruction.
@@ -306,6 +564,7 @@ pub fn create_pool(
     let (transit_collateral, _) = find_transit_program_address(program_id, pool_market, pool_mint);
     let (withdrawal_requests, _) =
         find_withdrawal_requests_program_address(program_id, pool_market, token_mint);
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(program_id, &pool);
 
     let accounts = vec![
         AccountMeta::new_readonly(*pool_market, false),
@@ -321,6 +580,7 @@ pub fn create_pool(
         AccountMeta::new_readonly(sysvar::rent::id(), false),
         AccountMeta::new_readonly(system_program::id(), false),
         AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(pool_borrow_shares, false),
     ];
 
     Instruction::new_with_borsh(
@@ -342,11 +602,13 @@ pub fn create_pool_borrow_authority(
 ) -> Instruction {
     let (pool_borrow_authority, _) =
         find_pool_borrow_authority_program_address(program_id, pool, borrow_authority);
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(program_id, pool);
 
     let accounts = vec![
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new_readonly(*pool, false),
         AccountMeta::new(pool_borrow_authority, false),
+        AccountMeta::new(pool_borrow_shares, false),
         AccountMeta::new_readonly(*borrow_authority, false),
         AccountMeta::new(*manager, true),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
@@ -372,11 +634,13 @@ pub fn update_pool_borrow_authority(
 ) -> Instruction {
     let (pool_borrow_authority, _) =
         find_pool_borrow_authority_program_address(program_id, pool, borrow_authority);
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(program_id, pool);
 
     let accounts = vec![
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new_readonly(*pool, false),
         AccountMeta::new(pool_borrow_authority, false),
+        AccountMeta::new(pool_borrow_shares, false),
         AccountMeta::new_readonly(*manager, true),
     ];
 
@@ -399,11 +663,13 @@ pub fn delete_pool_borrow_authority(
 ) -> Instruction {
     let (pool_borrow_authority, _) =
         find_pool_borrow_authority_program_address(program_id, pool, borrow_authority);
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(program_id, pool);
 
     let accounts = vec![
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new_readonly(*pool, false),
         AccountMeta::new(pool_borrow_authority, false),
+        AccountMeta::new(pool_borrow_shares, false),
         AccountMeta::new(*receiver, false),
         AccountMeta::new_readonly(*manager, true),
     ];
@@ -421,6 +687,7 @@ pub fn deposit(
     program_id: &Pubkey,
     pool_market: &Pubkey,
     pool: &Pubkey,
+    registry: &Pubkey,
     source: &Pubkey,
     destination: &Pubkey,
     token_account: &Pubkey,
@@ -430,8 +697,11 @@ pub fn deposit(
     mining_reward_acc: &Pubkey,
     amount: u64,
 ) -> Instruction {
+    let (global_pause, pool_pause) = find_pause_addresses(registry, pool);
     let (pool_market_authority, _) = find_program_address(program_id, pool_market);
     let (pool_config, _) = find_pool_config_program_address(program_id, pool);
+    let (fee_schedule, _) = find_fee_schedule_program_address(program_id, pool_market);
+    let (pool_fees, _) = find_pool_fees_program_address(program_id, pool);
 
     let accounts = vec![
         AccountMeta::new_readonly(pool_config, false),
@@ -447,6 +717,11 @@ pub fn deposit(
         AccountMeta::new(*mining_reward_acc, false),
         AccountMeta::new_readonly(everlend_rewards::id(), false),
         AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(fee_schedule, false),
+        AccountMeta::new(pool_fees, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
     ];
 
     Instruction::new_with_borsh(
@@ -495,6 +770,7 @@ pub fn withdraw(
     program_id: &Pubkey,
     pool_market: &Pubkey,
     pool: &Pubkey,
+    registry: &Pubkey,
     destination: &Pubkey,
     token_account: &Pubkey,
     token_mint: &Pubkey,
@@ -502,6 +778,7 @@ pub fn withdraw(
     from: &Pubkey,
     addition_accounts: Vec<AccountMeta>,
 ) -> Instruction {
+    let (global_pause, pool_pause) = find_pause_addresses(registry, pool);
     let (pool_market_authority, _) = find_program_address(program_id, pool_market);
 
     let (withdrawal_requests, _) =
@@ -514,6 +791,8 @@ pub fn withdraw(
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new_readonly(pool_market_authority, false),
         AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         AccountMeta::new(*pool_mint, false),
         AccountMeta::new(withdrawal_requests, false),
         AccountMeta::new(withdrawal_request, false),
@@ -536,6 +815,7 @@ pub fn withdraw_request(
     program_id: &Pubkey,
     pool_market: &Pubkey,
     pool: &Pubkey,
+    registry: &Pubkey,
     source: &Pubkey,
     destination: &Pubkey,
     token_account: &Pubkey,
@@ -546,6 +826,7 @@ pub fn withdraw_request(
     mining_reward_acc: &Pubkey,
     collateral_amount: u64,
 ) -> Instruction {
+    let (global_pause, pool_pause) = find_pause_addresses(registry, pool);
     let (withdrawal_requests, _) =
         find_withdrawal_requests_program_address(program_id, pool_market, token_mint);
     let (collateral_transit, _) = find_transit_program_address(program_id, pool_market, pool_mint);
@@ -555,11 +836,15 @@ pub fn withdraw_request(
         user_transfer_authority,
     );
     let (pool_config, _) = find_pool_config_program_address(program_id, pool);
+    let (fee_schedule, _) = find_fee_schedule_program_address(program_id, pool_market);
+    let (pool_fees, _) = find_pool_fees_program_address(program_id, pool);
 
     let accounts = vec![
         AccountMeta::new_readonly(pool_config, false),
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         AccountMeta::new(*pool_mint, false),
         AccountMeta::new(withdrawal_requests, false),
         AccountMeta::new(withdrawal_request, false),
@@ -575,6 +860,8 @@ pub fn withdraw_request(
         AccountMeta::new_readonly(sysvar::clock::id(), false),
         AccountMeta::new_readonly(system_program::id(), false),
         AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(fee_schedule, false),
+        AccountMeta::new(pool_fees, false),
     ];
 
     Instruction::new_with_borsh(
@@ -631,17 +918,21 @@ pub fn borrow(
     program_id: &Pubkey,
     pool_market: &Pubkey,
     pool: &Pubkey,
+    registry: &Pubkey,
     pool_borrow_authority: &Pubkey,
     destination: &Pubkey,
     token_account: &Pubkey,
     borrow_authority: &Pubkey,
     amount: u64,
 ) -> Instruction {
+    let (global_pause, pool_pause) = find_pause_addresses(registry, pool);
     let (pool_market_authority, _) = find_program_address(program_id, pool_market);
 
     let accounts = vec![
         AccountMeta::new_readonly(*pool_market, false),
         AccountMeta::new(*pool, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         AccountMeta::new(*pool_borrow_authority, false),
         AccountMeta::new(*destination, false),
         AccountMeta::new(*token_account, false),
@@ -745,8 +1036,17 @@ pub fn init_user_mining(
 
 /// Creates 'Migration' instruction.
 #[allow(clippy::too_many_arguments)]
-pub fn migrate_instruction(program_id: &Pubkey) -> Instruction {
-    let accounts = vec![];
+pub fn migrate_instruction(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    manager: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*pool_market, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
 
     Instruction::new_with_borsh(
         *program_id,
@@ -755,9 +1055,8 @@ pub fn migrate_instruction(program_id: &Pubkey) -> Instruction {
     )
 }
 
-/// Creates 'UpdateManager' instruction.
-#[allow(clippy::too_many_arguments)]
-pub fn update_manager(
+/// Creates 'ProposeManager' instruction.
+pub fn propose_manager(
     program_id: &Pubkey,
     pool_market: &Pubkey,
     manager: &Pubkey,
@@ -765,13 +1064,49 @@ pub fn update_manager(
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new(*pool_market, false),
-        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(*new_manager, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::ProposeManager,
+        accounts,
+    )
+}
+
+/// Creates 'AcceptManager' instruction.
+pub fn accept_manager(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    new_manager: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*pool_market, false),
         AccountMeta::new_readonly(*new_manager, true),
     ];
 
     Instruction::new_with_borsh(
         *program_id,
-        &LiquidityPoolsInstruction::UpdateManager,
+        &LiquidityPoolsInstruction::AcceptManager,
+        accounts,
+    )
+}
+
+/// Creates 'CancelManagerProposal' instruction.
+pub fn cancel_manager_proposal(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    manager: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*pool_market, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::CancelManagerProposal,
         accounts,
     )
 }
@@ -801,3 +1136,450 @@ pub fn set_pool_config(
         accounts,
     )
 }
+
+/// Creates 'SetFeeSchedule' instruction.
+pub fn set_fee_schedule(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    manager: &Pubkey,
+    treasury: &Pubkey,
+    performance_fee: u16,
+    management_fee: u16,
+) -> Instruction {
+    let (fee_schedule, _) = find_fee_schedule_program_address(program_id, pool_market);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*pool_market, false),
+        AccountMeta::new(fee_schedule, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::SetFeeSchedule {
+            treasury: *treasury,
+            performance_fee,
+            management_fee,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'AccrueFees' instruction.
+pub fn accrue_fees(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    pool: &Pubkey,
+    pool_mint: &Pubkey,
+    token_account: &Pubkey,
+    treasury_account: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let (fee_schedule, _) = find_fee_schedule_program_address(program_id, pool_market);
+    let (pool_config, _) = find_pool_config_program_address(program_id, pool);
+    let (pool_fees, _) = find_pool_fees_program_address(program_id, pool);
+    let (pool_market_authority, _) = find_program_address(program_id, pool_market);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*pool_market, false),
+        AccountMeta::new_readonly(fee_schedule, false),
+        AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new_readonly(pool_config, false),
+        AccountMeta::new(pool_fees, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(*token_account, false),
+        AccountMeta::new(*treasury_account, false),
+        AccountMeta::new_readonly(pool_market_authority, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::AccrueFees,
+        accounts,
+    )
+}
+
+/// Creates 'CompoundRewards' instruction.
+/// Rewards are reward mint, vault and user reward token account for each vault of the mining reward pool.
+#[allow(clippy::too_many_arguments)]
+pub fn compound_rewards(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    pool: &Pubkey,
+    registry: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+    token_account: &Pubkey,
+    pool_mint: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    mining_reward_pool: &Pubkey,
+    mining_reward_acc: &Pubkey,
+    rewards: &[(Pubkey, Pubkey, Pubkey)],
+) -> Instruction {
+    let (global_pause, pool_pause) = find_pause_addresses(registry, pool);
+    let (pool_market_authority, _) = find_program_address(program_id, pool_market);
+    let (pool_config, _) = find_pool_config_program_address(program_id, pool);
+    let (fee_schedule, _) = find_fee_schedule_program_address(program_id, pool_market);
+    let (pool_fees, _) = find_pool_fees_program_address(program_id, pool);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(pool_config, false),
+        AccountMeta::new_readonly(*pool_market, false),
+        AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new(*source, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(pool_market_authority, false),
+        AccountMeta::new_readonly(*user_transfer_authority, true),
+        AccountMeta::new(*mining_reward_pool, false),
+        AccountMeta::new(*mining_reward_acc, false),
+        AccountMeta::new_readonly(everlend_rewards::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(fee_schedule, false),
+        AccountMeta::new(pool_fees, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
+    ];
+
+    for (reward_mint, vault, user_reward_token) in rewards {
+        accounts.push(AccountMeta::new_readonly(*reward_mint, false));
+        accounts.push(AccountMeta::new(*vault, false));
+        accounts.push(AccountMeta::new(*user_reward_token, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::CompoundRewards,
+        accounts,
+    )
+}
+
+/// Creates 'TransferDepositAmount' instruction
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_deposit_amount(
+    program_id: &Pubkey,
+    pool: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    destination_user_transfer_authority: &Pubkey,
+    mining_reward_pool: &Pubkey,
+    mining_reward_acc: &Pubkey,
+    destination_mining_reward_acc: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new(*source, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(*user_transfer_authority, true),
+        AccountMeta::new_readonly(*destination_user_transfer_authority, false),
+        AccountMeta::new(*mining_reward_pool, false),
+        AccountMeta::new(*mining_reward_acc, false),
+        AccountMeta::new(*destination_mining_reward_acc, false),
+        AccountMeta::new_readonly(everlend_rewards::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::TransferDepositAmount { amount },
+        accounts,
+    )
+}
+
+/// Creates 'ResyncMining' instruction
+#[allow(clippy::too_many_arguments)]
+pub fn resync_mining(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    pool: &Pubkey,
+    user_pool_account: &Pubkey,
+    user: &Pubkey,
+    authority: &Pubkey,
+    mining_reward_pool: &Pubkey,
+    mining_reward_acc: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*pool_market, false),
+        AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new_readonly(*user_pool_account, false),
+        AccountMeta::new_readonly(*user, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*mining_reward_pool, false),
+        AccountMeta::new(*mining_reward_acc, false),
+        AccountMeta::new_readonly(everlend_rewards::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::ResyncMining,
+        accounts,
+    )
+}
+
+/// Creates 'QueueWithdrawRequest' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn queue_withdraw_request(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    pool: &Pubkey,
+    registry: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+    token_account: &Pubkey,
+    token_mint: &Pubkey,
+    pool_mint: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    mining_reward_pool: &Pubkey,
+    mining_reward_acc: &Pubkey,
+    index: u64,
+    collateral_amount: u64,
+) -> Instruction {
+    let (global_pause, pool_pause) = find_pause_addresses(registry, pool);
+    let (withdrawal_requests, _) =
+        find_withdrawal_requests_program_address(program_id, pool_market, token_mint);
+    let (collateral_transit, _) = find_transit_program_address(program_id, pool_market, pool_mint);
+    let (withdrawal_request, _) =
+        find_queued_withdrawal_request_program_address(program_id, &withdrawal_requests, index);
+    let (pool_config, _) = find_pool_config_program_address(program_id, pool);
+    let (fee_schedule, _) = find_fee_schedule_program_address(program_id, pool_market);
+    let (pool_fees, _) = find_pool_fees_program_address(program_id, pool);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(pool_config, false),
+        AccountMeta::new_readonly(*pool_market, false),
+        AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new(withdrawal_requests, false),
+        AccountMeta::new(withdrawal_request, false),
+        AccountMeta::new(*source, false),
+        AccountMeta::new_readonly(*destination, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(collateral_transit, false),
+        AccountMeta::new(*user_transfer_authority, true),
+        AccountMeta::new(*mining_reward_pool, false),
+        AccountMeta::new(*mining_reward_acc, false),
+        AccountMeta::new_readonly(everlend_rewards::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(fee_schedule, false),
+        AccountMeta::new(pool_fees, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::QueueWithdrawRequest { collateral_amount },
+        accounts,
+    )
+}
+
+/// Creates 'ProcessWithdrawalRequest' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn process_withdrawal_request(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    pool: &Pubkey,
+    registry: &Pubkey,
+    destination: &Pubkey,
+    token_account: &Pubkey,
+    token_mint: &Pubkey,
+    pool_mint: &Pubkey,
+    from: &Pubkey,
+    index: u64,
+) -> Instruction {
+    let (global_pause, pool_pause) = find_pause_addresses(registry, pool);
+    let (pool_market_authority, _) = find_program_address(program_id, pool_market);
+    let (withdrawal_requests, _) =
+        find_withdrawal_requests_program_address(program_id, pool_market, token_mint);
+    let (withdrawal_request, _) =
+        find_queued_withdrawal_request_program_address(program_id, &withdrawal_requests, index);
+    let (collateral_transit, _) = find_transit_program_address(program_id, pool_market, pool_mint);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*pool_market, false),
+        AccountMeta::new_readonly(pool_market_authority, false),
+        AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new(withdrawal_requests, false),
+        AccountMeta::new(withdrawal_request, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(collateral_transit, false),
+        AccountMeta::new(*from, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::ProcessWithdrawalRequest,
+        accounts,
+    )
+}
+
+/// Creates 'CancelWithdrawRequest' instruction for the queued request.
+#[allow(clippy::too_many_arguments)]
+pub fn cancel_queued_withdraw_request(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    pool: &Pubkey,
+    source: &Pubkey,
+    token_mint: &Pubkey,
+    pool_mint: &Pubkey,
+    manager: &Pubkey,
+    from: &Pubkey,
+    index: u64,
+) -> Instruction {
+    let (pool_market_authority, _) = find_program_address(program_id, pool_market);
+
+    let (withdrawal_requests, _) =
+        find_withdrawal_requests_program_address(program_id, pool_market, token_mint);
+    let (collateral_transit, _) = find_transit_program_address(program_id, pool_market, pool_mint);
+    let (withdrawal_request, _) =
+        find_queued_withdrawal_request_program_address(program_id, &withdrawal_requests, index);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*pool_market, false),
+        AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new(withdrawal_requests, false),
+        AccountMeta::new(withdrawal_request, false),
+        AccountMeta::new(*source, false),
+        AccountMeta::new(collateral_transit, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(pool_market_authority, false),
+        AccountMeta::new(*from, false),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::CancelWithdrawRequest,
+        accounts,
+    )
+}
+
+/// Creates 'InstantWithdraw' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn instant_withdraw(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    pool: &Pubkey,
+    registry: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+    token_account: &Pubkey,
+    token_mint: &Pubkey,
+    pool_mint: &Pubkey,
+    user_transfer_authority: &Pubkey,
+    mining_reward_pool: &Pubkey,
+    mining_reward_acc: &Pubkey,
+    collateral_amount: u64,
+) -> Instruction {
+    let (global_pause, pool_pause) = find_pause_addresses(registry, pool);
+    let (pool_market_authority, _) = find_program_address(program_id, pool_market);
+    let (pool_config, _) = find_pool_config_program_address(program_id, pool);
+    let (fee_schedule, _) = find_fee_schedule_program_address(program_id, pool_market);
+    let (pool_fees, _) = find_pool_fees_program_address(program_id, pool);
+    let (withdrawal_requests, _) =
+        find_withdrawal_requests_program_address(program_id, pool_market, token_mint);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(pool_config, false),
+        AccountMeta::new_readonly(*pool_market, false),
+        AccountMeta::new_readonly(pool_market_authority, false),
+        AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
+        AccountMeta::new_readonly(withdrawal_requests, false),
+        AccountMeta::new(*source, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new(*pool_mint, false),
+        AccountMeta::new_readonly(*user_transfer_authority, true),
+        AccountMeta::new(*mining_reward_pool, false),
+        AccountMeta::new(*mining_reward_acc, false),
+        AccountMeta::new_readonly(everlend_rewards::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(fee_schedule, false),
+        AccountMeta::new(pool_fees, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::InstantWithdraw { collateral_amount },
+        accounts,
+    )
+}
+
+/// Creates 'WriteOffLoss' instruction.
+pub fn write_off_loss(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    pool: &Pubkey,
+    pool_borrow_authority: &Pubkey,
+    borrow_authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*pool_market, false),
+        AccountMeta::new(*pool, false),
+        AccountMeta::new(*pool_borrow_authority, false),
+        AccountMeta::new_readonly(*borrow_authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::WriteOffLoss { amount },
+        accounts,
+    )
+}
+
+/// Creates 'InitPoolBorrowShares' instruction.
+pub fn init_pool_borrow_shares(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    pool: &Pubkey,
+    manager: &Pubkey,
+    borrow_authorities: &[Pubkey],
+) -> Instruction {
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(program_id, pool);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*pool_market, false),
+        AccountMeta::new_readonly(*pool, false),
+        AccountMeta::new(pool_borrow_shares, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    for borrow_authority in borrow_authorities {
+        let (pool_borrow_authority, _) =
+            find_pool_borrow_authority_program_address(program_id, pool, borrow_authority);
+        accounts.push(AccountMeta::new(pool_borrow_authority, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityPoolsInstruction::InitPoolBorrowShares,
+        accounts,
+    )
+}
@@ -0,0 +1,43 @@
+use crate::state::PoolMarket;
+use everlend_utils::{assert_account_key, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+
+/// Instruction context
+pub struct AcceptManagerContext<'a, 'b> {
+    pool_market: &'a AccountInfo<'b>,
+    new_manager: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> AcceptManagerContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<AcceptManagerContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let new_manager = AccountLoader::next_signer(account_info_iter)?;
+
+        Ok(AcceptManagerContext {
+            pool_market,
+            new_manager,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, _program_id: &Pubkey) -> ProgramResult {
+        let mut pool_market = PoolMarket::unpack(&self.pool_market.data.borrow())?;
+        assert_account_key(self.new_manager, &pool_market.pending_manager)?;
+
+        pool_market.manager = pool_market.pending_manager;
+        pool_market.pending_manager = Pubkey::default();
+
+        PoolMarket::pack(pool_market, *self.pool_market.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
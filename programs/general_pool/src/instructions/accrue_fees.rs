@@ -0,0 +1,164 @@
+use crate::{
+    find_pool_fees_program_address,
+    state::{Pool, PoolFees},
+    utils::{pool_fee_rates, total_pool_amount},
+};
+use everlend_utils::{assert_account_key, cpi, find_program_address, AccountLoader, EverlendError};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+use spl_token::state::{Account, Mint};
+
+/// Instruction context
+pub struct AccrueFeesContext<'a, 'b> {
+    pool_market: &'a AccountInfo<'b>,
+    fee_schedule: &'a AccountInfo<'b>,
+    pool: &'a AccountInfo<'b>,
+    pool_config: &'a AccountInfo<'b>,
+    pool_fees: &'a AccountInfo<'b>,
+    pool_mint: &'a AccountInfo<'b>,
+    token_account: &'a AccountInfo<'b>,
+    treasury_account: &'a AccountInfo<'b>,
+    pool_market_authority: &'a AccountInfo<'b>,
+    payer: &'a AccountInfo<'b>,
+    clock: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> AccrueFeesContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<AccrueFeesContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let fee_schedule = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pool_config = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let pool_fees = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let pool_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let token_account = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let treasury_account = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let pool_market_authority = AccountLoader::next_unchecked(account_info_iter)?; // Is PDA account of this program
+        let payer = AccountLoader::next_signer(account_info_iter)?;
+        let clock = AccountLoader::next_with_key(account_info_iter, &Clock::id())?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+        let _token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
+
+        Ok(AccrueFeesContext {
+            pool_market,
+            fee_schedule,
+            pool,
+            pool_config,
+            pool_fees,
+            pool_mint,
+            token_account,
+            treasury_account,
+            pool_market_authority,
+            payer,
+            clock,
+            rent,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
+        // Get pool state
+        let pool = Pool::unpack(&self.pool.data.borrow())?;
+
+        // Check pool accounts
+        assert_account_key(self.pool_market, &pool.pool_market)?;
+        assert_account_key(self.token_account, &pool.token_account)?;
+        assert_account_key(self.pool_mint, &pool.pool_mint)?;
+
+        let (fee_schedule, performance_fee, management_fee) = pool_fee_rates(
+            program_id,
+            self.pool_market,
+            self.pool,
+            self.fee_schedule,
+            self.pool_config,
+        )?;
+
+        // Check treasury account
+        {
+            let treasury_account = Account::unpack(&self.treasury_account.data.borrow())?;
+            if treasury_account.mint != pool.pool_mint
+                || treasury_account.owner != fee_schedule.treasury
+            {
+                return Err(ProgramError::InvalidArgument);
+            }
+        }
+
+        let clock = Clock::from_account_info(self.clock)?;
+
+        let (pool_fees_pubkey, bump_seed) =
+            find_pool_fees_program_address(program_id, self.pool.key);
+        assert_account_key(self.pool_fees, &pool_fees_pubkey)?;
+
+        let mut pool_fees = if self.pool_fees.owner.eq(&Pubkey::default()) {
+            let signers_seeds: &[&[u8]] =
+                &[br"pool_fees", &self.pool.key.to_bytes()[..32], &[bump_seed]];
+
+            cpi::system::create_account::<PoolFees>(
+                program_id,
+                self.payer.clone(),
+                self.pool_fees.clone(),
+                &[signers_seeds],
+                &Rent::from_account_info(self.rent)?,
+            )?;
+
+            PoolFees::init(*self.pool.key, clock.unix_timestamp)
+        } else {
+            PoolFees::unpack(&self.pool_fees.data.borrow())?
+        };
+
+        let total_amount =
+            total_pool_amount(self.token_account.clone(), pool.total_amount_borrowed)?;
+        let total_minted = Mint::unpack_unchecked(&self.pool_mint.data.borrow())?
+            .supply
+            .checked_add(pool_fees.pending_fee_tokens)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        pool_fees.accrue(
+            total_amount,
+            total_minted,
+            performance_fee,
+            management_fee,
+            clock.unix_timestamp,
+        )?;
+        let mint_amount = pool_fees.take_pending_fee_tokens()?;
+
+        PoolFees::pack(pool_fees, *self.pool_fees.data.borrow_mut())?;
+
+        if mint_amount == 0 {
+            return Ok(());
+        }
+
+        msg!("Fee tokens minted: {}", mint_amount);
+
+        let (_, bump_seed) = find_program_address(program_id, self.pool_market.key);
+        let signers_seeds = &[&self.pool_market.key.to_bytes()[..32], &[bump_seed]];
+
+        // Mint fee pool tokens to treasury
+        cpi::spl_token::mint_to(
+            self.pool_mint.clone(),
+            self.treasury_account.clone(),
+            self.pool_market_authority.clone(),
+            mint_amount,
+            &[signers_seeds],
+        )
+    }
+}
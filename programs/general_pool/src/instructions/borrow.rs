@@ -1,8 +1,8 @@
 use crate::{
     state::{Pool, PoolBorrowAuthority},
-    utils::total_pool_amount,
+    utils::{pause_flags, total_pool_amount},
 };
-use everlend_utils::{assert_account_key, cpi, find_program_address, AccountLoader};
+use everlend_utils::{assert_account_key, cpi, find_program_address, AccountLoader, EverlendError};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
     program_pack::Pack, pubkey::Pubkey,
@@ -12,6 +12,8 @@ use solana_program::{
 pub struct BorrowContext<'a, 'b> {
     pool_market: &'a AccountInfo<'b>,
     pool: &'a AccountInfo<'b>,
+    global_pause: &'a AccountInfo<'b>,
+    pool_pause: &'a AccountInfo<'b>,
     pool_borrow_authority: &'a AccountInfo<'b>,
     destination: &'a AccountInfo<'b>,
     token_account: &'a AccountInfo<'b>,
@@ -28,6 +30,9 @@ impl<'a, 'b> BorrowContext<'a, 'b> {
         let account_info_iter = &mut accounts.iter().enumerate();
         let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let global_pause =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let pool_pause = AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
         let pool_borrow_authority = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let destination = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
         let token_account = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
@@ -38,6 +43,8 @@ impl<'a, 'b> BorrowContext<'a, 'b> {
         Ok(BorrowContext {
             pool_market,
             pool,
+            global_pause,
+            pool_pause,
             pool_borrow_authority,
             destination,
             token_account,
@@ -54,6 +61,17 @@ impl<'a, 'b> BorrowContext<'a, 'b> {
         assert_account_key(self.pool_market, &pool.pool_market)?;
         assert_account_key(self.token_account, &pool.token_account)?;
 
+        let flags = pause_flags(
+            self.pool_market,
+            self.pool,
+            self.global_pause,
+            self.pool_pause,
+        )?;
+        if flags.borrow {
+            return Err(EverlendError::OperationPaused.into());
+        }
+
+        // Borrow authorities of the deprecated layout aren't counted in the pool borrow shares
         let mut pool_borrow_authority =
             PoolBorrowAuthority::unpack(&self.pool_borrow_authority.data.borrow())?;
 
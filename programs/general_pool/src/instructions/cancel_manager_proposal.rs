@@ -0,0 +1,42 @@
+use crate::state::PoolMarket;
+use everlend_utils::{assert_account_key, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+
+/// Instruction context
+pub struct CancelManagerProposalContext<'a, 'b> {
+    pool_market: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> CancelManagerProposalContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<CancelManagerProposalContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+
+        Ok(CancelManagerProposalContext {
+            pool_market,
+            manager,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, _program_id: &Pubkey) -> ProgramResult {
+        let mut pool_market = PoolMarket::unpack(&self.pool_market.data.borrow())?;
+        assert_account_key(self.manager, &pool_market.manager)?;
+
+        pool_market.pending_manager = Pubkey::default();
+
+        PoolMarket::pack(pool_market, *self.pool_market.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
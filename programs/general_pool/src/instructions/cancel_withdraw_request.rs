@@ -1,5 +1,5 @@
 use crate::{
-    find_transit_program_address,
+    find_transit_program_address, find_withdrawal_request_program_address,
     state::{Pool, PoolMarket, WithdrawalRequest, WithdrawalRequests},
 };
 use everlend_utils::{assert_account_key, cpi, find_program_address, AccountLoader, EverlendError};
@@ -80,7 +80,7 @@ impl<'a, 'b> CancelWithdrawRequestContext<'a, 'b> {
         // and is linked to the pool
 
         let mut withdrawal_requests =
-            WithdrawalRequests::unpack(&self.withdrawal_requests.data.borrow())?;
+            WithdrawalRequests::unpack_any(&self.withdrawal_requests.data.borrow())?;
 
         // Check withdrawal requests accounts
         assert_account_key(self.pool, &withdrawal_requests.pool)?;
@@ -103,7 +103,17 @@ impl<'a, 'b> CancelWithdrawRequestContext<'a, 'b> {
             &[signers_seeds],
         )?;
 
-        withdrawal_requests.process(withdrawal_request.liquidity_amount)?;
+        // Legacy requests are keyed by the user, queued ones by the queue index
+        let (legacy_withdrawal_request_pubkey, _) = find_withdrawal_request_program_address(
+            program_id,
+            self.withdrawal_requests.key,
+            self.from.key,
+        );
+        if self.withdrawal_request.key == &legacy_withdrawal_request_pubkey {
+            withdrawal_requests.process(withdrawal_request.liquidity_amount)?;
+        } else {
+            withdrawal_requests.process_queued(withdrawal_request.liquidity_amount)?;
+        }
 
         // Close withdraw account and return rent
         let from_starting_lamports = self.from.lamports();
@@ -114,7 +124,7 @@ impl<'a, 'b> CancelWithdrawRequestContext<'a, 'b> {
             .checked_add(withdraw_request_lamports)
             .ok_or(EverlendError::MathOverflow)?;
 
-        WithdrawalRequests::pack(
+        WithdrawalRequests::pack_any(
             withdrawal_requests,
             *self.withdrawal_requests.data.borrow_mut(),
         )?;
@@ -0,0 +1,57 @@
+use everlend_rewards::cpi::claim_all;
+use everlend_utils::EverlendError;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+use spl_token::state::Account;
+
+use crate::instructions::DepositContext;
+
+/// Instruction context
+pub struct CompoundRewardsContext<'a, 'b> {
+    deposit: DepositContext<'a, 'b>,
+    rewards: Vec<AccountInfo<'b>>,
+}
+
+impl<'a, 'b> CompoundRewardsContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<CompoundRewardsContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let deposit = DepositContext::load(program_id, account_info_iter)?;
+
+        // Reward vault accounts are checked by the rewards program
+        let rewards = account_info_iter.map(|(_, acc)| acc.clone()).collect();
+
+        Ok(CompoundRewardsContext { deposit, rewards })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
+        let balance_before = Account::unpack(&self.deposit.source.data.borrow())?.amount;
+
+        claim_all(
+            self.deposit.everlend_rewards.key,
+            self.deposit.mining_reward_pool.clone(),
+            self.deposit.mining_reward_acc.clone(),
+            self.deposit.user_transfer_authority.clone(),
+            self.deposit.token_program.clone(),
+            &self.rewards,
+        )?;
+
+        // Only the reward of the pool token mint claimed to the source is compounded
+        let amount = Account::unpack(&self.deposit.source.data.borrow())?
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(EverlendError::MathOverflow)?;
+        if amount == 0 {
+            return Ok(());
+        }
+
+        self.deposit.process(program_id, amount)
+    }
+}
@@ -1,3 +1,4 @@
+use everlend_registry::state::{Role, RoleAccounts};
 use everlend_utils::{assert_account_key, cpi, AccountLoader};
 use solana_program::{
     account_info::AccountInfo,
@@ -12,12 +13,14 @@ use solana_program::{
 use spl_token::state::Mint;
 
 use crate::{
-    find_pool_config_program_address, find_pool_program_address, find_transit_program_address,
+    find_pool_borrow_shares_program_address, find_pool_config_program_address,
+    find_pool_program_address, find_transit_program_address,
     find_withdrawal_requests_program_address,
     state::{
-        InitPoolParams, InitWithdrawalRequestsParams, Pool, PoolConfig, PoolMarket,
-        WithdrawalRequests,
+        InitPoolParams, InitWithdrawalRequestsParams, Pool, PoolBorrowShares, PoolConfig,
+        PoolMarket, WithdrawalRequests,
     },
+    utils::assert_manager_or_role,
     withdrawal_requests_seed,
 };
 
@@ -34,6 +37,8 @@ pub struct CreatePoolContext<'a, 'b> {
     pool: &'a AccountInfo<'b>,
     transit: &'a AccountInfo<'b>,
     rent: &'a AccountInfo<'b>,
+    pool_borrow_shares: &'a AccountInfo<'b>,
+    role_accounts: Option<RoleAccounts<'a, 'b>>,
 }
 
 impl<'a, 'b> CreatePoolContext<'a, 'b> {
@@ -58,6 +63,9 @@ impl<'a, 'b> CreatePoolContext<'a, 'b> {
         let _system_program =
             AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
         let _token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
+        let pool_borrow_shares = AccountLoader::next_uninitialized(account_info_iter)?;
+
+        let role_accounts = RoleAccounts::next_optional(account_info_iter)?;
 
         Ok(CreatePoolContext {
             manager,
@@ -71,15 +79,22 @@ impl<'a, 'b> CreatePoolContext<'a, 'b> {
             rent,
             pool_config,
             withdrawal_requests,
+            pool_borrow_shares,
+            role_accounts,
         })
     }
 
     /// Process instruction
     pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
-        // Check manager
+        // Check manager or role
         {
             let pool_market = PoolMarket::unpack(&self.pool_market.data.borrow())?;
-            assert_account_key(self.manager, &pool_market.manager)?;
+            assert_manager_or_role(
+                &pool_market,
+                self.manager,
+                self.role_accounts.as_ref(),
+                Role::Operator,
+            )?;
         }
 
         let token_mint = Mint::unpack(&self.token_mint.data.borrow())?;
@@ -106,6 +121,7 @@ impl<'a, 'b> CreatePoolContext<'a, 'b> {
         self.create_transit(program_id, rent)?;
         self.create_withdrawal_requests(program_id, rent)?;
         self.create_pool_config(program_id, rent)?;
+        self.create_pool_borrow_shares(program_id, rent)?;
 
         Ok(())
     }
@@ -225,4 +241,29 @@ impl<'a, 'b> CreatePoolContext<'a, 'b> {
 
         PoolConfig::pack(PoolConfig::default(), *self.pool_config.data.borrow_mut())
     }
+
+    fn create_pool_borrow_shares(&self, program_id: &Pubkey, rent: &Rent) -> ProgramResult {
+        let (pool_borrow_shares_pubkey, bump_seed) =
+            find_pool_borrow_shares_program_address(program_id, self.pool.key);
+        assert_account_key(self.pool_borrow_shares, &pool_borrow_shares_pubkey)?;
+
+        let signers_seeds: &[&[u8]] = &[
+            br"borrow_shares",
+            &self.pool.key.to_bytes()[..32],
+            &[bump_seed],
+        ];
+
+        cpi::system::create_account::<PoolBorrowShares>(
+            program_id,
+            self.manager.clone(),
+            self.pool_borrow_shares.clone(),
+            &[signers_seeds],
+            rent,
+        )?;
+
+        PoolBorrowShares::pack(
+            PoolBorrowShares::init(*self.pool.key),
+            *self.pool_borrow_shares.data.borrow_mut(),
+        )
+    }
 }
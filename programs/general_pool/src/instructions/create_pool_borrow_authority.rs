@@ -1,3 +1,4 @@
+use everlend_registry::state::{Role, RoleAccounts};
 use everlend_utils::{assert_account_key, cpi, AccountLoader};
 use solana_program::{
     account_info::AccountInfo,
@@ -12,7 +13,11 @@ use solana_program::{
 
 use crate::{
     find_pool_borrow_authority_program_address,
-    state::{InitPoolBorrowAuthorityParams, Pool, PoolBorrowAuthority, PoolMarket},
+    state::{
+        assert_share_allowed, InitPoolBorrowAuthorityParams, Pool, PoolBorrowAuthority,
+        PoolBorrowShares, PoolMarket,
+    },
+    utils::assert_manager_or_role,
 };
 
 /// Instruction context
@@ -22,7 +27,9 @@ pub struct CreatePoolBorrowAuthorityContext<'a, 'b> {
     pool: &'a AccountInfo<'b>,
     pool_market: &'a AccountInfo<'b>,
     pool_borrow_authority: &'a AccountInfo<'b>,
+    pool_borrow_shares: &'a AccountInfo<'b>,
     rent: &'a AccountInfo<'b>,
+    role_accounts: Option<RoleAccounts<'a, 'b>>,
 }
 
 impl<'a, 'b> CreatePoolBorrowAuthorityContext<'a, 'b> {
@@ -36,72 +43,91 @@ impl<'a, 'b> CreatePoolBorrowAuthorityContext<'a, 'b> {
         let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let pool_borrow_authority = AccountLoader::next_uninitialized(account_info_iter)?;
+        let pool_borrow_shares = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let borrow_authority = AccountLoader::next_unchecked(account_info_iter)?; // Can be any account
         let manager = AccountLoader::next_signer(account_info_iter)?;
         let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
         let _system_program =
             AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
 
+        let role_accounts = RoleAccounts::next_optional(account_info_iter)?;
+
         Ok(CreatePoolBorrowAuthorityContext {
             borrow_authority,
             manager,
             pool,
             pool_borrow_authority,
+            pool_borrow_shares,
             rent,
             pool_market,
+            role_accounts,
         })
     }
 
     /// Process instruction
     pub fn process(&self, program_id: &Pubkey, share_allowed: u16) -> ProgramResult {
-        // Check manager
+        assert_share_allowed(share_allowed)?;
+
+        // Check manager or role
         {
             let pool_market = PoolMarket::unpack(&self.pool_market.data.borrow())?;
-            assert_account_key(self.manager, &pool_market.manager)?;
+            assert_manager_or_role(
+                &pool_market,
+                self.manager,
+                self.role_accounts.as_ref(),
+                Role::Admin,
+            )?;
 
             // Get pool state
             let pool = Pool::unpack(&self.pool.data.borrow())?;
             assert_account_key(self.pool_market, &pool.pool_market)?;
         }
 
+        let mut pool_borrow_shares =
+            PoolBorrowShares::unpack(&self.pool_borrow_shares.data.borrow())?;
+        assert_account_key(self.pool, &pool_borrow_shares.pool)?;
+        pool_borrow_shares.update(0, share_allowed)?;
+
         let rent = &Rent::from_account_info(self.rent)?;
 
-        {
-            // Create pool borrow authority account
-            let (pool_borrow_authority_pubkey, bump_seed) =
-                find_pool_borrow_authority_program_address(
-                    program_id,
-                    self.pool.key,
-                    self.borrow_authority.key,
-                );
-
-            assert_account_key(self.pool_borrow_authority, &pool_borrow_authority_pubkey)?;
-
-            let signers_seeds = &[
-                &self.pool.key.to_bytes()[..32],
-                &self.borrow_authority.key.to_bytes()[..32],
-                &[bump_seed],
-            ];
-
-            cpi::system::create_account::<PoolBorrowAuthority>(
-                program_id,
-                self.manager.clone(),
-                self.pool_borrow_authority.clone(),
-                &[signers_seeds],
-                rent,
-            )?;
-        }
+        // Create pool borrow authority account
+        let (pool_borrow_authority_pubkey, bump_seed) = find_pool_borrow_authority_program_address(
+            program_id,
+            self.pool.key,
+            self.borrow_authority.key,
+        );
+
+        assert_account_key(self.pool_borrow_authority, &pool_borrow_authority_pubkey)?;
+
+        let signers_seeds = &[
+            &self.pool.key.to_bytes()[..32],
+            &self.borrow_authority.key.to_bytes()[..32],
+            &[bump_seed],
+        ];
+
+        cpi::system::create_account::<PoolBorrowAuthority>(
+            program_id,
+            self.manager.clone(),
+            self.pool_borrow_authority.clone(),
+            &[signers_seeds],
+            rent,
+        )?;
 
         let pool_borrow_authority = PoolBorrowAuthority::init(InitPoolBorrowAuthorityParams {
             pool: *self.pool.key,
             borrow_authority: *self.borrow_authority.key,
             share_allowed,
+            bump: bump_seed,
         });
 
         PoolBorrowAuthority::pack(
             pool_borrow_authority,
             *self.pool_borrow_authority.data.borrow_mut(),
         )?;
+        PoolBorrowShares::pack(
+            pool_borrow_shares,
+            *self.pool_borrow_shares.data.borrow_mut(),
+        )?;
 
         Ok(())
     }
@@ -1,4 +1,8 @@
-use crate::state::{Pool, PoolBorrowAuthority, PoolMarket};
+use crate::state::{
+    DeprecatedPoolBorrowAuthority, Pool, PoolBorrowAuthority, PoolBorrowShares, PoolMarket,
+};
+use crate::utils::assert_manager_or_role;
+use everlend_registry::state::{Role, RoleAccounts};
 use everlend_utils::{assert_account_key, AccountLoader, EverlendError};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
@@ -10,8 +14,10 @@ pub struct DeletePoolBorrowAuthorityContext<'a, 'b> {
     pool_market: &'a AccountInfo<'b>,
     pool: &'a AccountInfo<'b>,
     pool_borrow_authority: &'a AccountInfo<'b>,
+    pool_borrow_shares: &'a AccountInfo<'b>,
     receiver: &'a AccountInfo<'b>,
     manager: &'a AccountInfo<'b>,
+    role_accounts: Option<RoleAccounts<'a, 'b>>,
 }
 
 impl<'a, 'b> DeletePoolBorrowAuthorityContext<'a, 'b> {
@@ -25,24 +31,34 @@ impl<'a, 'b> DeletePoolBorrowAuthorityContext<'a, 'b> {
         let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let pool_borrow_authority = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pool_borrow_shares = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let receiver = AccountLoader::next_unchecked(account_info_iter)?;
         let manager = AccountLoader::next_signer(account_info_iter)?;
 
+        let role_accounts = RoleAccounts::next_optional(account_info_iter)?;
+
         Ok(DeletePoolBorrowAuthorityContext {
             pool_market,
             pool,
             pool_borrow_authority,
+            pool_borrow_shares,
             receiver,
             manager,
+            role_accounts,
         })
     }
 
     /// Process instruction
     pub fn process(&self, _program_id: &Pubkey) -> ProgramResult {
-        // Check manager
+        // Check manager or role
         {
             let pool_market = PoolMarket::unpack(&self.pool_market.data.borrow())?;
-            assert_account_key(self.manager, &pool_market.manager)?;
+            assert_manager_or_role(
+                &pool_market,
+                self.manager,
+                self.role_accounts.as_ref(),
+                Role::Admin,
+            )?;
 
             // Get pool state
             let pool = Pool::unpack(&self.pool.data.borrow())?;
@@ -50,8 +66,22 @@ impl<'a, 'b> DeletePoolBorrowAuthorityContext<'a, 'b> {
 
             // Get pool borrow authority state to check initialized
             let pool_borrow_authority =
-                PoolBorrowAuthority::unpack(&self.pool_borrow_authority.data.borrow())?;
+                PoolBorrowAuthority::unpack_any(&self.pool_borrow_authority.data.borrow())?;
             assert_account_key(self.pool, &pool_borrow_authority.pool)?;
+
+            let mut pool_borrow_shares =
+                PoolBorrowShares::unpack(&self.pool_borrow_shares.data.borrow())?;
+            assert_account_key(self.pool, &pool_borrow_shares.pool)?;
+
+            // Share allowed of the deprecated layout isn't counted
+            if self.pool_borrow_authority.data_len() != DeprecatedPoolBorrowAuthority::LEN {
+                pool_borrow_shares.update(pool_borrow_authority.share_allowed, 0)?;
+            }
+
+            PoolBorrowShares::pack(
+                pool_borrow_shares,
+                *self.pool_borrow_shares.data.borrow_mut(),
+            )?;
         }
 
         let receiver_starting_lamports = self.receiver.lamports();
@@ -62,7 +92,7 @@ impl<'a, 'b> DeletePoolBorrowAuthorityContext<'a, 'b> {
             .checked_add(pool_borrow_authority_lamports)
             .ok_or(EverlendError::MathOverflow)?;
 
-        PoolBorrowAuthority::pack(
+        PoolBorrowAuthority::pack_any(
             Default::default(),
             *self.pool_borrow_authority.data.borrow_mut(),
         )?;
@@ -1,35 +1,46 @@
-use everlend_rewards::cpi::deposit_mining;
+use everlend_rewards::{cpi::deposit_mining, state::Mining};
 use everlend_utils::{
     assert_account_key, assert_non_zero_amount,
     cpi::{self},
     find_program_address, AccountLoader, EverlendError,
 };
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    program_pack::Pack, pubkey::Pubkey,
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{Sysvar, SysvarId},
 };
-use spl_token::state::Mint;
+use std::iter::Enumerate;
 
 use crate::{
-    find_pool_config_program_address, find_pool_program_address,
+    find_pool_config_program_address, find_pool_program_address, find_user_mining_address,
     state::{Pool, PoolConfig},
-    utils::total_pool_amount,
+    utils::{accrue_pool_fees, pause_flags, total_pool_amount},
 };
 
 /// Instruction context
 pub struct DepositContext<'a, 'b> {
     destination: &'a AccountInfo<'b>,
-    everlend_rewards: &'a AccountInfo<'b>,
+    pub(crate) everlend_rewards: &'a AccountInfo<'b>,
     pool: &'a AccountInfo<'b>,
+    global_pause: &'a AccountInfo<'b>,
+    pool_pause: &'a AccountInfo<'b>,
     pool_config: &'a AccountInfo<'b>,
     pool_market: &'a AccountInfo<'b>,
     pool_market_authority: &'a AccountInfo<'b>,
     pool_mint: &'a AccountInfo<'b>,
-    source: &'a AccountInfo<'b>,
+    pub(crate) source: &'a AccountInfo<'b>,
     token_account: &'a AccountInfo<'b>,
-    user_transfer_authority: &'a AccountInfo<'b>,
-    mining_reward_pool: &'a AccountInfo<'b>,
-    mining_reward_acc: &'a AccountInfo<'b>,
+    pub(crate) user_transfer_authority: &'a AccountInfo<'b>,
+    pub(crate) mining_reward_pool: &'a AccountInfo<'b>,
+    pub(crate) mining_reward_acc: &'a AccountInfo<'b>,
+    pub(crate) token_program: &'a AccountInfo<'b>,
+    fee_schedule: &'a AccountInfo<'b>,
+    pool_fees: &'a AccountInfo<'b>,
+    clock: &'a AccountInfo<'b>,
 }
 
 impl<'a, 'b> DepositContext<'a, 'b> {
@@ -40,6 +51,14 @@ impl<'a, 'b> DepositContext<'a, 'b> {
     ) -> Result<DepositContext<'a, 'b>, ProgramError> {
         let account_info_iter = &mut accounts.iter().enumerate();
 
+        Self::load(program_id, account_info_iter)
+    }
+
+    /// Load instruction context from the accounts iterator
+    pub(crate) fn load<I: Iterator<Item = &'a AccountInfo<'b>>>(
+        program_id: &Pubkey,
+        account_info_iter: &mut Enumerate<I>,
+    ) -> Result<DepositContext<'a, 'b>, ProgramError> {
         let pool_config = AccountLoader::next_optional(account_info_iter, program_id)?;
         let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
@@ -58,12 +77,24 @@ impl<'a, 'b> DepositContext<'a, 'b> {
             AccountLoader::next_with_owner(account_info_iter, &everlend_rewards::id())?;
         let everlend_rewards =
             AccountLoader::next_with_key(account_info_iter, &everlend_rewards::id())?;
-        let _token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
+        let token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
+
+        // fees accounts
+        let fee_schedule = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let pool_fees = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let clock = AccountLoader::next_with_key(account_info_iter, &Clock::id())?;
+
+        // pause accounts
+        let global_pause =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let pool_pause = AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
 
         Ok(DepositContext {
             destination,
             everlend_rewards,
             pool,
+            global_pause,
+            pool_pause,
             pool_config,
             pool_market,
             pool_market_authority,
@@ -73,6 +104,10 @@ impl<'a, 'b> DepositContext<'a, 'b> {
             user_transfer_authority,
             mining_reward_pool,
             mining_reward_acc,
+            token_program,
+            fee_schedule,
+            pool_fees,
+            clock,
         })
     }
 
@@ -88,23 +123,52 @@ impl<'a, 'b> DepositContext<'a, 'b> {
         assert_account_key(self.token_account, &pool.token_account)?;
         assert_account_key(self.pool_mint, &pool.pool_mint)?;
 
-        {
+        let flags = pause_flags(
+            self.pool_market,
+            self.pool,
+            self.global_pause,
+            self.pool_pause,
+        )?;
+        if flags.deposit {
+            return Err(EverlendError::OperationPaused.into());
+        }
+
+        let pool_config = {
             let (pool_config_pubkey, _) =
                 find_pool_config_program_address(program_id, self.pool.key);
             assert_account_key(self.pool_config, &pool_config_pubkey)?;
 
             // Check only if account exists
             if !self.pool_config.owner.eq(&Pubkey::default()) {
-                let pool_config = PoolConfig::unpack(&self.pool_config.data.borrow())?;
+                let pool_config = PoolConfig::unpack_any(&self.pool_config.data.borrow())?;
                 if amount < pool_config.deposit_minimum {
                     return Err(EverlendError::DepositAmountTooSmall.into());
                 }
+
+                Some(pool_config)
+            } else {
+                None
             }
-        }
+        };
 
         let total_incoming =
             total_pool_amount(self.token_account.clone(), pool.total_amount_borrowed)?;
-        let total_minted = Mint::unpack_unchecked(&self.pool_mint.data.borrow())?.supply;
+        let clock = Clock::from_account_info(self.clock)?;
+        let total_minted = accrue_pool_fees(
+            program_id,
+            self.pool_market,
+            self.pool,
+            self.fee_schedule,
+            self.pool_config,
+            self.pool_fees,
+            self.pool_mint,
+            total_incoming,
+            clock.unix_timestamp,
+        )?;
+
+        if let Some(pool_config) = pool_config {
+            self.check_deposit_caps(&pool_config, amount, total_incoming, total_minted)?;
+        }
 
         let mint_amount = if total_incoming == 0 || total_minted == 0 {
             amount
@@ -126,6 +190,53 @@ impl<'a, 'b> DepositContext<'a, 'b> {
         Ok(())
     }
 
+    fn check_deposit_caps(
+        &self,
+        pool_config: &PoolConfig,
+        amount: u64,
+        total_incoming: u64,
+        total_minted: u64,
+    ) -> ProgramResult {
+        if let Some(pool_deposit_cap) = pool_config.pool_deposit_cap {
+            let pool_amount = total_incoming
+                .checked_add(amount)
+                .ok_or(EverlendError::MathOverflow)?;
+            if pool_amount > pool_deposit_cap {
+                return Err(EverlendError::PoolDepositCapExceeded.into());
+            }
+        }
+
+        if let Some(user_deposit_cap) = pool_config.user_deposit_cap {
+            let (mining_reward_acc_pubkey, _) = find_user_mining_address(
+                self.user_transfer_authority.key,
+                self.mining_reward_pool.key,
+            );
+            assert_account_key(self.mining_reward_acc, &mining_reward_acc_pubkey)?;
+
+            // Mining share tracks pool tokens of the user
+            let share = Mining::unpack_any(&self.mining_reward_acc.data.borrow())?.share;
+            let user_amount = if total_minted == 0 {
+                share
+            } else {
+                (share as u128)
+                    .checked_mul(total_incoming as u128)
+                    .ok_or(EverlendError::MathOverflow)?
+                    .checked_div(total_minted as u128)
+                    .ok_or(EverlendError::MathOverflow)? as u64
+            };
+
+            if user_amount
+                .checked_add(amount)
+                .ok_or(EverlendError::MathOverflow)?
+                > user_deposit_cap
+            {
+                return Err(EverlendError::UserDepositCapExceeded.into());
+            }
+        }
+
+        Ok(())
+    }
+
     fn transfer_and_mint(
         &self,
         program_id: &Pubkey,
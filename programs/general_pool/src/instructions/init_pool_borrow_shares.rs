@@ -0,0 +1,140 @@
+use everlend_utils::{assert_account_key, cpi, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::{
+    find_pool_borrow_authority_program_address, find_pool_borrow_shares_program_address,
+    state::{
+        DeprecatedPoolBorrowAuthority, Pool, PoolBorrowAuthority, PoolBorrowShares, PoolMarket,
+    },
+};
+
+/// Instruction context
+pub struct InitPoolBorrowSharesContext<'a, 'b> {
+    pool_market: &'a AccountInfo<'b>,
+    pool: &'a AccountInfo<'b>,
+    pool_borrow_shares: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    pool_borrow_authorities: Vec<&'a AccountInfo<'b>>,
+}
+
+impl<'a, 'b> InitPoolBorrowSharesContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<InitPoolBorrowSharesContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pool_borrow_shares = AccountLoader::next_uninitialized(account_info_iter)?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        let mut pool_borrow_authorities = vec![];
+        while AccountLoader::has_more(account_info_iter) {
+            pool_borrow_authorities.push(AccountLoader::next_with_owner(
+                account_info_iter,
+                program_id,
+            )?);
+        }
+
+        Ok(InitPoolBorrowSharesContext {
+            pool_market,
+            pool,
+            pool_borrow_shares,
+            manager,
+            rent,
+            pool_borrow_authorities,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
+        // Check manager
+        {
+            let pool_market = PoolMarket::unpack(&self.pool_market.data.borrow())?;
+            assert_account_key(self.manager, &pool_market.manager)?;
+
+            // Get pool state
+            let pool = Pool::unpack(&self.pool.data.borrow())?;
+            assert_account_key(self.pool_market, &pool.pool_market)?;
+        }
+
+        let rent = &Rent::from_account_info(self.rent)?;
+        let mut pool_borrow_shares = PoolBorrowShares::init(*self.pool.key);
+
+        // Borrow authorities are counted once and moved to the actual layout,
+        // the ones left in the deprecated layout can't borrow
+        for pool_borrow_authority_info in self.pool_borrow_authorities.iter() {
+            if pool_borrow_authority_info.data_len() != DeprecatedPoolBorrowAuthority::LEN {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            let pool_borrow_authority: PoolBorrowAuthority =
+                DeprecatedPoolBorrowAuthority::unpack(&pool_borrow_authority_info.data.borrow())?
+                    .into();
+            assert_account_key(self.pool, &pool_borrow_authority.pool)?;
+
+            let (pool_borrow_authority_pubkey, bump) = find_pool_borrow_authority_program_address(
+                program_id,
+                self.pool.key,
+                &pool_borrow_authority.borrow_authority,
+            );
+            assert_account_key(pool_borrow_authority_info, &pool_borrow_authority_pubkey)?;
+
+            pool_borrow_shares.update(0, pool_borrow_authority.share_allowed)?;
+
+            cpi::system::realloc_with_rent(
+                pool_borrow_authority_info,
+                self.manager,
+                rent,
+                PoolBorrowAuthority::LEN,
+            )?;
+            PoolBorrowAuthority::pack(
+                PoolBorrowAuthority {
+                    bump,
+                    ..pool_borrow_authority
+                },
+                *pool_borrow_authority_info.data.borrow_mut(),
+            )?;
+        }
+
+        let (pool_borrow_shares_pubkey, bump_seed) =
+            find_pool_borrow_shares_program_address(program_id, self.pool.key);
+        assert_account_key(self.pool_borrow_shares, &pool_borrow_shares_pubkey)?;
+
+        let signers_seeds: &[&[u8]] = &[
+            br"borrow_shares",
+            &self.pool.key.to_bytes()[..32],
+            &[bump_seed],
+        ];
+
+        cpi::system::create_account::<PoolBorrowShares>(
+            program_id,
+            self.manager.clone(),
+            self.pool_borrow_shares.clone(),
+            &[signers_seeds],
+            rent,
+        )?;
+
+        PoolBorrowShares::pack(
+            pool_borrow_shares,
+            *self.pool_borrow_shares.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,255 @@
+use everlend_rewards::cpi::withdraw_mining;
+use everlend_utils::{
+    assert_account_key, assert_non_zero_amount,
+    cpi::{self},
+    find_program_address, AccountLoader, EverlendError,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{Sysvar, SysvarId},
+};
+use spl_token::state::Account;
+
+use crate::{
+    find_pool_config_program_address, find_pool_program_address,
+    state::{Pool, PoolConfig, WithdrawalRequests},
+    utils::{accrue_pool_fees, pause_flags, total_pool_amount},
+};
+
+/// Instruction context
+pub struct InstantWithdrawContext<'a, 'b> {
+    pool_config: &'a AccountInfo<'b>,
+    pool_market: &'a AccountInfo<'b>,
+    pool_market_authority: &'a AccountInfo<'b>,
+    pool: &'a AccountInfo<'b>,
+    global_pause: &'a AccountInfo<'b>,
+    pool_pause: &'a AccountInfo<'b>,
+    withdrawal_requests: &'a AccountInfo<'b>,
+    source: &'a AccountInfo<'b>,
+    destination: &'a AccountInfo<'b>,
+    token_account: &'a AccountInfo<'b>,
+    pool_mint: &'a AccountInfo<'b>,
+    user_transfer_authority: &'a AccountInfo<'b>,
+    mining_reward_pool: &'a AccountInfo<'b>,
+    mining_reward_acc: &'a AccountInfo<'b>,
+    everlend_rewards: &'a AccountInfo<'b>,
+    fee_schedule: &'a AccountInfo<'b>,
+    pool_fees: &'a AccountInfo<'b>,
+    clock: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> InstantWithdrawContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<InstantWithdrawContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let pool_config = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pool_market_authority = AccountLoader::next_unchecked(account_info_iter)?; // Is PDA account of this program
+        let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let global_pause =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let pool_pause = AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let withdrawal_requests = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+
+        let source = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let destination = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let token_account = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let pool_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let user_transfer_authority = AccountLoader::next_signer(account_info_iter)?;
+
+        // mining accounts
+        let mining_reward_pool =
+            AccountLoader::next_with_owner(account_info_iter, &everlend_rewards::id())?;
+        let mining_reward_acc =
+            AccountLoader::next_with_owner(account_info_iter, &everlend_rewards::id())?;
+        let everlend_rewards =
+            AccountLoader::next_with_key(account_info_iter, &everlend_rewards::id())?;
+        let _token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
+
+        // fees accounts
+        let fee_schedule = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let pool_fees = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let clock = AccountLoader::next_with_key(account_info_iter, &Clock::id())?;
+
+        Ok(InstantWithdrawContext {
+            pool_config,
+            pool_market,
+            pool_market_authority,
+            pool,
+            global_pause,
+            pool_pause,
+            withdrawal_requests,
+            source,
+            destination,
+            token_account,
+            pool_mint,
+            user_transfer_authority,
+            mining_reward_pool,
+            mining_reward_acc,
+            everlend_rewards,
+            fee_schedule,
+            pool_fees,
+            clock,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey, collateral_amount: u64) -> ProgramResult {
+        assert_non_zero_amount(collateral_amount)?;
+
+        // Get pool state
+        let pool = Pool::unpack(&self.pool.data.borrow())?;
+
+        // Check pool accounts
+        assert_account_key(self.pool_market, &pool.pool_market)?;
+        assert_account_key(self.token_account, &pool.token_account)?;
+        assert_account_key(self.pool_mint, &pool.pool_mint)?;
+
+        let flags = pause_flags(
+            self.pool_market,
+            self.pool,
+            self.global_pause,
+            self.pool_pause,
+        )?;
+        if flags.withdraw {
+            return Err(EverlendError::OperationPaused.into());
+        }
+
+        let destination_account = Account::unpack(&self.destination.data.borrow())?;
+        if pool.token_mint != destination_account.mint {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let withdrawal_requests =
+            WithdrawalRequests::unpack_any(&self.withdrawal_requests.data.borrow())?;
+        assert_account_key(self.pool, &withdrawal_requests.pool)?;
+
+        let total_incoming =
+            total_pool_amount(self.token_account.clone(), pool.total_amount_borrowed)?;
+        let clock = Clock::from_account_info(self.clock)?;
+        let total_minted = accrue_pool_fees(
+            program_id,
+            self.pool_market,
+            self.pool,
+            self.fee_schedule,
+            self.pool_config,
+            self.pool_fees,
+            self.pool_mint,
+            total_incoming,
+            clock.unix_timestamp,
+        )?;
+
+        let liquidity_amount = (collateral_amount as u128)
+            .checked_mul(total_incoming as u128)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_div(total_minted as u128)
+            .ok_or(EverlendError::MathOverflow)? as u64;
+        assert_non_zero_amount(liquidity_amount)?;
+
+        let mut exit_fee = 0;
+        {
+            let (pool_config_pubkey, _) =
+                find_pool_config_program_address(program_id, self.pool.key);
+            assert_account_key(self.pool_config, &pool_config_pubkey)?;
+
+            // Check only if account exists
+            if !self.pool_config.owner.eq(&Pubkey::default()) {
+                let pool_config = PoolConfig::unpack_any(&self.pool_config.data.borrow())?;
+                if liquidity_amount < pool_config.withdraw_minimum {
+                    return Err(EverlendError::WithdrawAmountTooSmall.into());
+                }
+
+                exit_fee = pool_config.exit_fee;
+            }
+        }
+
+        // Fee stays in the pool token account
+        let fee_amount = (liquidity_amount as u128)
+            .checked_mul(exit_fee as u128)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_div(10_000)
+            .ok_or(EverlendError::MathOverflow)? as u64;
+        let amount = liquidity_amount
+            .checked_sub(fee_amount)
+            .ok_or(EverlendError::MathOverflow)?;
+        assert_non_zero_amount(amount)?;
+
+        // Liquidity reserved for queued and legacy withdraw requests can't be withdrawn
+        let idle_liquidity = withdrawal_requests
+            .idle_liquidity(Account::unpack(&self.token_account.data.borrow())?.amount);
+        if amount > idle_liquidity {
+            msg!("Idle liquidity: {}, required: {}", idle_liquidity, amount);
+            return Err(EverlendError::InstantWithdrawInsufficientLiquidity.into());
+        }
+
+        self.burn_and_transfer(program_id, collateral_amount, amount)?;
+        self.withdraw_mining(program_id, &pool, collateral_amount)?;
+
+        Ok(())
+    }
+
+    fn burn_and_transfer(
+        &self,
+        program_id: &Pubkey,
+        collateral_amount: u64,
+        amount: u64,
+    ) -> ProgramResult {
+        // Burn pool token from source
+        cpi::spl_token::burn(
+            self.pool_mint.clone(),
+            self.source.clone(),
+            self.user_transfer_authority.clone(),
+            collateral_amount,
+            &[],
+        )?;
+
+        let (_, bump_seed) = find_program_address(program_id, self.pool_market.key);
+        let signers_seeds = &[&self.pool_market.key.to_bytes()[..32], &[bump_seed]];
+
+        // Transfer from token account to destination
+        cpi::spl_token::transfer(
+            self.token_account.clone(),
+            self.destination.clone(),
+            self.pool_market_authority.clone(),
+            amount,
+            &[signers_seeds],
+        )
+    }
+
+    fn withdraw_mining(
+        &self,
+        program_id: &Pubkey,
+        pool: &Pool,
+        collateral_amount: u64,
+    ) -> ProgramResult {
+        let (pool_pubkey, pool_bump_seed) =
+            find_pool_program_address(program_id, &pool.pool_market, &pool.token_mint);
+        assert_account_key(self.pool, &pool_pubkey)?;
+
+        let pool_seeds: &[&[u8]] = &[
+            &pool.pool_market.to_bytes()[..32],
+            &pool.token_mint.to_bytes()[..32],
+            &[pool_bump_seed],
+        ];
+
+        withdraw_mining(
+            self.everlend_rewards.key,
+            self.mining_reward_pool.clone(),
+            self.mining_reward_acc.clone(),
+            self.user_transfer_authority.clone(),
+            self.pool.to_owned(),
+            collateral_amount,
+            &[pool_seeds],
+        )
+    }
+}
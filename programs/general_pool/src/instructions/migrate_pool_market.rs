@@ -0,0 +1,60 @@
+use everlend_utils::{assert_account_key, cpi::system::realloc_with_rent, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::state::{DeprecatedPoolMarket, PoolMarket};
+
+/// Instruction context
+pub struct MigratePoolMarketContext<'a, 'b> {
+    pool_market: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> MigratePoolMarketContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<MigratePoolMarketContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        Ok(MigratePoolMarketContext {
+            pool_market,
+            manager,
+            rent,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, _program_id: &Pubkey) -> ProgramResult {
+        let pool_market: PoolMarket =
+            DeprecatedPoolMarket::unpack(&self.pool_market.data.borrow())?.into();
+        assert_account_key(self.manager, &pool_market.manager)?;
+
+        realloc_with_rent(
+            self.pool_market,
+            self.manager,
+            &Rent::from_account_info(self.rent)?,
+            PoolMarket::LEN,
+        )?;
+
+        PoolMarket::pack(pool_market, *self.pool_market.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
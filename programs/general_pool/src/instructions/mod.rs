@@ -1,34 +1,58 @@
 //! Program instructions
+mod accept_manager;
+mod accrue_fees;
 mod borrow;
+mod cancel_manager_proposal;
 mod cancel_withdraw_request;
+mod compound_rewards;
 mod create_pool;
 mod create_pool_borrow_authority;
 mod delete_pool_borrow_authority;
 mod deposit;
+mod init_pool_borrow_shares;
 mod init_pool_market;
 mod init_user_mining;
+mod instant_withdraw;
+mod migrate_pool_market;
+mod process_withdrawal_request;
+mod propose_manager;
+mod queue_withdraw_request;
 mod repay;
+mod resync_mining;
+mod set_fee_schedule;
 mod set_pool_config;
 mod set_token_metadata;
 mod transfer_deposit;
-mod update_manager;
 mod update_pool_borrow_authority;
 mod withdraw;
 mod withdraw_request;
+mod write_off_loss;
 
+pub use accept_manager::*;
+pub use accrue_fees::*;
 pub use borrow::*;
+pub use cancel_manager_proposal::*;
 pub use cancel_withdraw_request::*;
+pub use compound_rewards::*;
 pub use create_pool::*;
 pub use create_pool_borrow_authority::*;
 pub use delete_pool_borrow_authority::*;
 pub use deposit::*;
+pub use init_pool_borrow_shares::*;
 pub use init_pool_market::*;
 pub use init_user_mining::*;
+pub use instant_withdraw::*;
+pub use migrate_pool_market::*;
+pub use process_withdrawal_request::*;
+pub use propose_manager::*;
+pub use queue_withdraw_request::*;
 pub use repay::*;
+pub use resync_mining::*;
+pub use set_fee_schedule::*;
 pub use set_pool_config::*;
 pub use set_token_metadata::*;
 pub use transfer_deposit::*;
-pub use update_manager::*;
 pub use update_pool_borrow_authority::*;
 pub use withdraw::*;
 pub use withdraw_request::*;
+pub use write_off_loss::*;
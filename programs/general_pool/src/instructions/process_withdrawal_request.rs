@@ -0,0 +1,222 @@
+use crate::{
+    find_queued_withdrawal_request_program_address, find_transit_program_address,
+    state::{Pool, WithdrawalRequest, WithdrawalRequests},
+    utils::pause_flags,
+};
+use everlend_utils::{assert_account_key, cpi, find_program_address, AccountLoader, EverlendError};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{Sysvar, SysvarId},
+};
+use spl_token::state::Account;
+use std::cmp::min;
+
+/// Instruction context
+pub struct ProcessWithdrawalRequestContext<'a, 'b> {
+    pool_market: &'a AccountInfo<'b>,
+    pool_market_authority: &'a AccountInfo<'b>,
+    pool: &'a AccountInfo<'b>,
+    global_pause: &'a AccountInfo<'b>,
+    pool_pause: &'a AccountInfo<'b>,
+    pool_mint: &'a AccountInfo<'b>,
+    withdrawal_requests: &'a AccountInfo<'b>,
+    withdrawal_request: &'a AccountInfo<'b>,
+    destination: &'a AccountInfo<'b>,
+    token_account: &'a AccountInfo<'b>,
+    collateral_transit: &'a AccountInfo<'b>,
+    from: &'a AccountInfo<'b>,
+    clock: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> ProcessWithdrawalRequestContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<ProcessWithdrawalRequestContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pool_market_authority = AccountLoader::next_unchecked(account_info_iter)?; // Is PDA account of this program
+        let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let global_pause =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let pool_pause = AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let pool_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let withdrawal_requests = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let withdrawal_request = AccountLoader::next_unchecked(account_info_iter)?; // Can be closed by cancel
+        let destination = AccountLoader::next_unchecked(account_info_iter)?; // Checked with request if it exists
+        let token_account = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let collateral_transit =
+            AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let from = AccountLoader::next_unchecked(account_info_iter)?; // Request creator, can be any account
+        let clock = AccountLoader::next_with_key(account_info_iter, &Clock::id())?;
+        let _token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
+
+        Ok(ProcessWithdrawalRequestContext {
+            pool_market,
+            pool_market_authority,
+            pool,
+            global_pause,
+            pool_pause,
+            pool_mint,
+            withdrawal_requests,
+            withdrawal_request,
+            destination,
+            token_account,
+            collateral_transit,
+            from,
+            clock,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
+        {
+            // Check collateral token transit account
+            let (collateral_transit_pubkey, _) =
+                find_transit_program_address(program_id, self.pool_market.key, self.pool_mint.key);
+            assert_account_key(self.collateral_transit, &collateral_transit_pubkey)?;
+        }
+
+        // Get pool state
+        let pool = Pool::unpack(&self.pool.data.borrow())?;
+        assert_account_key(self.pool_market, &pool.pool_market)?;
+        assert_account_key(self.token_account, &pool.token_account)?;
+        assert_account_key(self.pool_mint, &pool.pool_mint)?;
+
+        let flags = pause_flags(
+            self.pool_market,
+            self.pool,
+            self.global_pause,
+            self.pool_pause,
+        )?;
+        if flags.withdraw {
+            return Err(EverlendError::OperationPaused.into());
+        }
+
+        let mut withdrawal_requests =
+            WithdrawalRequests::unpack_any(&self.withdrawal_requests.data.borrow())?;
+        assert_account_key(self.pool, &withdrawal_requests.pool)?;
+
+        // Only the queue head can be processed
+        let (withdrawal_request_pubkey, _) = find_queued_withdrawal_request_program_address(
+            program_id,
+            self.withdrawal_requests.key,
+            withdrawal_requests.head_index,
+        );
+        assert_account_key(self.withdrawal_request, &withdrawal_request_pubkey)?;
+
+        // Skip the request closed by cancel
+        if self.withdrawal_request.owner != program_id {
+            msg!("Skip closed request {}", withdrawal_requests.head_index);
+            withdrawal_requests.dequeue()?;
+
+            return WithdrawalRequests::pack_any(
+                withdrawal_requests,
+                *self.withdrawal_requests.data.borrow_mut(),
+            );
+        }
+
+        let mut withdrawal_request =
+            WithdrawalRequest::unpack(&self.withdrawal_request.data.borrow())?;
+
+        // Check withdraw request accounts
+        assert_account_key(self.pool, &withdrawal_request.pool)?;
+        assert_account_key(self.destination, &withdrawal_request.destination)?;
+        assert_account_key(self.from, &withdrawal_request.from)?;
+
+        // Check that enough time has passed to make a withdraw
+        {
+            let clock = Clock::from_account_info(self.clock)?;
+            if withdrawal_request.ticket > clock.slot {
+                return Err(EverlendError::WithdrawRequestsInvalidTicket.into());
+            }
+        }
+
+        // Partial fulfilment with liquidity left in the pool after legacy requests
+        let available_liquidity = withdrawal_requests
+            .queue_liquidity(Account::unpack(&self.token_account.data.borrow())?.amount);
+        let liquidity_amount = min(available_liquidity, withdrawal_request.liquidity_amount);
+        if liquidity_amount == 0 {
+            return Err(EverlendError::WithdrawRequestsInsufficientLiquidity.into());
+        }
+
+        let is_fulfilled = liquidity_amount == withdrawal_request.liquidity_amount;
+
+        // Collateral is rounded up in favor of the pool
+        let collateral_amount = if is_fulfilled {
+            withdrawal_request.collateral_amount
+        } else {
+            (withdrawal_request.collateral_amount as u128)
+                .checked_mul(liquidity_amount as u128)
+                .ok_or(EverlendError::MathOverflow)?
+                .checked_add(withdrawal_request.liquidity_amount as u128 - 1)
+                .ok_or(EverlendError::MathOverflow)?
+                .checked_div(withdrawal_request.liquidity_amount as u128)
+                .ok_or(EverlendError::MathOverflow)? as u64
+        };
+
+        let (_, bump_seed) = find_program_address(program_id, self.pool_market.key);
+        let signers_seeds = &[&self.pool_market.key.to_bytes()[..32], &[bump_seed]];
+
+        // Transfer from token account to destination
+        cpi::spl_token::transfer(
+            self.token_account.clone(),
+            self.destination.clone(),
+            self.pool_market_authority.clone(),
+            liquidity_amount,
+            &[signers_seeds],
+        )?;
+
+        // Burn from transit collateral pool token
+        cpi::spl_token::burn(
+            self.pool_mint.clone(),
+            self.collateral_transit.clone(),
+            self.pool_market_authority.clone(),
+            collateral_amount,
+            &[signers_seeds],
+        )?;
+
+        withdrawal_requests.process_queued(liquidity_amount)?;
+
+        if is_fulfilled {
+            withdrawal_requests.dequeue()?;
+
+            // Close withdraw account and return rent
+            let from_starting_lamports = self.from.lamports();
+            let withdraw_request_lamports = self.withdrawal_request.lamports();
+
+            **self.withdrawal_request.lamports.borrow_mut() = 0;
+            **self.from.lamports.borrow_mut() = from_starting_lamports
+                .checked_add(withdraw_request_lamports)
+                .ok_or(EverlendError::MathOverflow)?;
+
+            WithdrawalRequest::pack(
+                Default::default(),
+                *self.withdrawal_request.data.borrow_mut(),
+            )?;
+        } else {
+            withdrawal_request.liquidity_amount -= liquidity_amount;
+            withdrawal_request.collateral_amount -= collateral_amount;
+
+            WithdrawalRequest::pack(
+                withdrawal_request,
+                *self.withdrawal_request.data.borrow_mut(),
+            )?;
+        }
+
+        WithdrawalRequests::pack_any(
+            withdrawal_requests,
+            *self.withdrawal_requests.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+}
@@ -6,25 +6,25 @@ use solana_program::{
 };
 
 /// Instruction context
-pub struct UpdateManagerContext<'a, 'b> {
+pub struct ProposeManagerContext<'a, 'b> {
     pool_market: &'a AccountInfo<'b>,
     manager: &'a AccountInfo<'b>,
     new_manager: &'a AccountInfo<'b>,
 }
 
-impl<'a, 'b> UpdateManagerContext<'a, 'b> {
+impl<'a, 'b> ProposeManagerContext<'a, 'b> {
     /// New instruction context
     pub fn new(
         program_id: &Pubkey,
         accounts: &'a [AccountInfo<'b>],
-    ) -> Result<UpdateManagerContext<'a, 'b>, ProgramError> {
+    ) -> Result<ProposeManagerContext<'a, 'b>, ProgramError> {
         let account_info_iter = &mut accounts.iter().enumerate();
 
         let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let manager = AccountLoader::next_signer(account_info_iter)?;
-        let new_manager = AccountLoader::next_signer(account_info_iter)?;
+        let new_manager = AccountLoader::next_unchecked(account_info_iter)?;
 
-        Ok(UpdateManagerContext {
+        Ok(ProposeManagerContext {
             pool_market,
             manager,
             new_manager,
@@ -36,7 +36,7 @@ impl<'a, 'b> UpdateManagerContext<'a, 'b> {
         let mut pool_market = PoolMarket::unpack(&self.pool_market.data.borrow())?;
         assert_account_key(self.manager, &pool_market.manager)?;
 
-        pool_market.manager = *self.new_manager.key;
+        pool_market.pending_manager = *self.new_manager.key;
 
         PoolMarket::pack(pool_market, *self.pool_market.data.borrow_mut())?;
 
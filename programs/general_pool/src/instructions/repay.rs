@@ -57,7 +57,7 @@ impl<'a, 'b> RepayContext<'a, 'b> {
 
         // Get pool borrow authority state
         let mut pool_borrow_authority =
-            PoolBorrowAuthority::unpack(&self.pool_borrow_authority.data.borrow())?;
+            PoolBorrowAuthority::unpack_any(&self.pool_borrow_authority.data.borrow())?;
         assert_account_key(self.pool, &pool_borrow_authority.pool)?;
 
         pool_borrow_authority.repay(amount)?;
@@ -65,7 +65,7 @@ impl<'a, 'b> RepayContext<'a, 'b> {
 
         // Check interest ?
 
-        PoolBorrowAuthority::pack(
+        PoolBorrowAuthority::pack_any(
             pool_borrow_authority,
             *self.pool_borrow_authority.data.borrow_mut(),
         )?;
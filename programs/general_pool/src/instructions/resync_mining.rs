@@ -0,0 +1,130 @@
+use everlend_rewards::cpi::withdraw_mining;
+use everlend_rewards::state::Mining;
+use everlend_utils::{assert_account_key, AccountLoader, EverlendError};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, sysvar::Sysvar,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::state::Account;
+
+use crate::{find_pool_program_address, find_user_mining_address, state::Pool};
+
+/// Instruction context
+pub struct ResyncMiningContext<'a, 'b> {
+    pool_market: &'a AccountInfo<'b>,
+    pool: &'a AccountInfo<'b>,
+    user_pool_account: &'a AccountInfo<'b>,
+    user: &'a AccountInfo<'b>,
+    authority: &'a AccountInfo<'b>,
+    mining_reward_pool: &'a AccountInfo<'b>,
+    mining_reward_acc: &'a AccountInfo<'b>,
+    everlend_rewards: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> ResyncMiningContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<ResyncMiningContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let user_pool_account =
+            AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let user = AccountLoader::next_unchecked(account_info_iter)?;
+        let authority = AccountLoader::next_signer(account_info_iter)?;
+
+        // mining accounts
+        let mining_reward_pool =
+            AccountLoader::next_with_owner(account_info_iter, &everlend_rewards::id())?;
+        let mining_reward_acc =
+            AccountLoader::next_with_owner(account_info_iter, &everlend_rewards::id())?;
+        let everlend_rewards =
+            AccountLoader::next_with_key(account_info_iter, &everlend_rewards::id())?;
+
+        Ok(ResyncMiningContext {
+            pool_market,
+            pool,
+            user_pool_account,
+            user,
+            authority,
+            mining_reward_pool,
+            mining_reward_acc,
+            everlend_rewards,
+        })
+    }
+
+    /// Process instruction.
+    /// Decreases the user mining share down to the pool token balance.
+    /// The share follows a single token account, so pool tokens the user holds in other
+    /// accounts are not counted. The user can decrease it with any own account, anyone can
+    /// decrease it down to the balance of the canonical associated token account.
+    /// The share is only increased by deposits and transfers of the pool and never goes
+    /// below the locked share
+    pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
+        let pool = Pool::unpack(&self.pool.data.borrow())?;
+        let user_pool_account = Account::unpack(&self.user_pool_account.data.borrow())?;
+
+        // Check pool accounts
+        {
+            assert_account_key(self.pool_market, &pool.pool_market)?;
+
+            if user_pool_account.mint != pool.pool_mint {
+                return Err(ProgramError::InvalidArgument);
+            }
+            assert_account_key(self.user, &user_pool_account.owner)?;
+
+            let (mining_reward_acc_pubkey, _) =
+                find_user_mining_address(self.user.key, self.mining_reward_pool.key);
+            assert_account_key(self.mining_reward_acc, &mining_reward_acc_pubkey)?;
+        }
+
+        let balance = user_pool_account.amount;
+        let mining = Mining::unpack_any(&self.mining_reward_acc.data.borrow())?;
+
+        if balance > mining.share {
+            msg!("Mining share is increased only by deposits and transfers of the pool");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let is_user = self.authority.key == self.user.key;
+        let is_canonical_account = self.user_pool_account.key
+            == &get_associated_token_address(self.user.key, &pool.pool_mint);
+        if !(is_user || is_canonical_account) {
+            msg!("Only the user can decrease mining share with a non-canonical account");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Locked share stays until the lock ends
+        if balance < mining.active_locked_share(Clock::get()?.slot) {
+            return Err(EverlendError::RewardsShareLocked.into());
+        }
+
+        if balance == mining.share {
+            return Ok(());
+        }
+
+        let (pool_pubkey, pool_bump_seed) =
+            find_pool_program_address(program_id, &pool.pool_market, &pool.token_mint);
+        assert_account_key(self.pool, &pool_pubkey)?;
+
+        let pool_seeds: &[&[u8]] = &[
+            &pool.pool_market.to_bytes()[..32],
+            &pool.token_mint.to_bytes()[..32],
+            &[pool_bump_seed],
+        ];
+
+        withdraw_mining(
+            self.everlend_rewards.key,
+            self.mining_reward_pool.clone(),
+            self.mining_reward_acc.clone(),
+            self.user.clone(),
+            self.pool.clone(),
+            mining.share - balance,
+            &[pool_seeds],
+        )
+    }
+}
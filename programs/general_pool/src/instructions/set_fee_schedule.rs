@@ -0,0 +1,103 @@
+use crate::{
+    find_fee_schedule_program_address,
+    state::{FeeSchedule, PoolMarket},
+    utils::assert_manager_or_role,
+};
+use everlend_registry::state::{Role, RoleAccounts};
+use everlend_utils::{assert_account_key, cpi, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+/// Instruction context
+pub struct SetFeeScheduleContext<'a, 'b> {
+    pool_market: &'a AccountInfo<'b>,
+    fee_schedule: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    role_accounts: Option<RoleAccounts<'a, 'b>>,
+}
+
+impl<'a, 'b> SetFeeScheduleContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<SetFeeScheduleContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let fee_schedule = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        let role_accounts = RoleAccounts::next_optional(account_info_iter)?;
+
+        Ok(SetFeeScheduleContext {
+            pool_market,
+            fee_schedule,
+            manager,
+            rent,
+            role_accounts,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(
+        &self,
+        program_id: &Pubkey,
+        treasury: Pubkey,
+        performance_fee: u16,
+        management_fee: u16,
+    ) -> ProgramResult {
+        {
+            // Get pool market state
+            let pool_market = PoolMarket::unpack(&self.pool_market.data.borrow())?;
+            assert_manager_or_role(
+                &pool_market,
+                self.manager,
+                self.role_accounts.as_ref(),
+                Role::Risk,
+            )?;
+        }
+
+        let (fee_schedule_pubkey, bump_seed) =
+            find_fee_schedule_program_address(program_id, self.pool_market.key);
+        assert_account_key(self.fee_schedule, &fee_schedule_pubkey)?;
+
+        let mut fee_schedule = if self.fee_schedule.owner.eq(&Pubkey::default()) {
+            let signers_seeds: &[&[u8]] = &[
+                br"fees",
+                &self.pool_market.key.to_bytes()[..32],
+                &[bump_seed],
+            ];
+
+            cpi::system::create_account::<FeeSchedule>(
+                program_id,
+                self.manager.clone(),
+                self.fee_schedule.clone(),
+                &[signers_seeds],
+                &Rent::from_account_info(self.rent)?,
+            )?;
+
+            FeeSchedule::init(*self.pool_market.key)
+        } else {
+            FeeSchedule::unpack(&self.fee_schedule.data.borrow())?
+        };
+
+        fee_schedule.set(treasury, performance_fee, management_fee)?;
+
+        FeeSchedule::pack(fee_schedule, *self.fee_schedule.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 use crate::{
     find_pool_config_program_address,
     state::{Pool, PoolConfig, PoolMarket, SetPoolConfigParams},
+    utils::assert_manager_or_role,
 };
+use everlend_registry::state::{Role, RoleAccounts};
 use everlend_utils::{assert_account_key, cpi, AccountLoader};
 use solana_program::{
     account_info::AccountInfo,
@@ -21,6 +23,7 @@ pub struct SetPoolConfigContext<'a, 'b> {
     pool: &'a AccountInfo<'b>,
     pool_config: &'a AccountInfo<'b>,
     rent: &'a AccountInfo<'b>,
+    role_accounts: Option<RoleAccounts<'a, 'b>>,
 }
 
 impl<'a, 'b> SetPoolConfigContext<'a, 'b> {
@@ -39,12 +42,15 @@ impl<'a, 'b> SetPoolConfigContext<'a, 'b> {
         let _system_program =
             AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
 
+        let role_accounts = RoleAccounts::next_optional(account_info_iter)?;
+
         Ok(SetPoolConfigContext {
             pool_market,
             manager,
             pool,
             pool_config,
             rent,
+            role_accounts,
         })
     }
 
@@ -53,7 +59,12 @@ impl<'a, 'b> SetPoolConfigContext<'a, 'b> {
         {
             // Get pool market state
             let pool_market = PoolMarket::unpack(&self.pool_market.data.borrow())?;
-            assert_account_key(self.manager, &pool_market.manager)?;
+            assert_manager_or_role(
+                &pool_market,
+                self.manager,
+                self.role_accounts.as_ref(),
+                Role::Risk,
+            )?;
 
             // Get pool state
             let pool = Pool::unpack(&self.pool.data.borrow())?;
@@ -79,10 +90,22 @@ impl<'a, 'b> SetPoolConfigContext<'a, 'b> {
 
             PoolConfig::default()
         } else {
-            PoolConfig::unpack(&self.pool_config.data.borrow())?
+            let pool_config = PoolConfig::unpack_any(&self.pool_config.data.borrow())?;
+
+            // Migrate deprecated layout
+            if self.pool_config.data_len() != PoolConfig::LEN {
+                cpi::system::realloc_with_rent(
+                    self.pool_config,
+                    self.manager,
+                    rent,
+                    PoolConfig::LEN,
+                )?;
+            }
+
+            pool_config
         };
 
-        pool_config.set(params);
+        pool_config.set(params)?;
 
         PoolConfig::pack(pool_config, *self.pool_config.data.borrow_mut())?;
 
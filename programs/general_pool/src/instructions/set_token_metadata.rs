@@ -1,4 +1,6 @@
 use crate::state::{Pool, PoolMarket};
+use crate::utils::assert_manager_or_role;
+use everlend_registry::state::{Role, RoleAccounts};
 use everlend_utils::{assert_account_key, cpi::metaplex, find_program_address, AccountLoader};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
@@ -16,6 +18,7 @@ pub struct SetTokenMetadataContext<'a, 'b> {
     pool_mint: &'a AccountInfo<'b>,
     rent: &'a AccountInfo<'b>,
     system_program: &'a AccountInfo<'b>,
+    role_accounts: Option<RoleAccounts<'a, 'b>>,
 }
 
 impl<'a, 'b> SetTokenMetadataContext<'a, 'b> {
@@ -39,6 +42,8 @@ impl<'a, 'b> SetTokenMetadataContext<'a, 'b> {
             AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
         let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
 
+        let role_accounts = RoleAccounts::next_optional(account_info_iter)?;
+
         Ok(SetTokenMetadataContext {
             manager,
             metadata,
@@ -49,6 +54,7 @@ impl<'a, 'b> SetTokenMetadataContext<'a, 'b> {
             pool_mint,
             rent,
             system_program,
+            role_accounts,
         })
     }
 
@@ -63,7 +69,12 @@ impl<'a, 'b> SetTokenMetadataContext<'a, 'b> {
         {
             // Get pool market state
             let pool_market = PoolMarket::unpack(&self.pool_market.data.borrow())?;
-            assert_account_key(self.manager, &pool_market.manager)?;
+            assert_manager_or_role(
+                &pool_market,
+                self.manager,
+                self.role_accounts.as_ref(),
+                Role::Operator,
+            )?;
 
             // Get pool state
             let pool = Pool::unpack(&self.pool.data.borrow())?;
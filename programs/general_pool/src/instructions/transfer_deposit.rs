@@ -1,7 +1,7 @@
 use everlend_rewards::cpi::{deposit_mining, withdraw_mining};
 use everlend_rewards::state::Mining;
 use everlend_utils::{
-    assert_account_key,
+    assert_account_key, assert_non_zero_amount,
     cpi::{self},
     AccountLoader, EverlendError,
 };
@@ -64,8 +64,9 @@ impl<'a, 'b> TransferDepositContext<'a, 'b> {
         })
     }
 
-    /// Process instruction
-    pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
+    /// Process instruction.
+    /// Transfers the whole source balance if amount isn't specified
+    pub fn process(&self, program_id: &Pubkey, amount: Option<u64>) -> ProgramResult {
         // Get pool state
         let pool = Pool::unpack(&self.pool.data.borrow())?;
         let source_account = Account::unpack(&self.source.data.borrow())?;
@@ -83,10 +84,12 @@ impl<'a, 'b> TransferDepositContext<'a, 'b> {
             assert_account_key(self.mining_reward_acc, &mining_reward_acc_pubkey)?;
         }
 
-        let collateral_amount = source_account.amount;
-        let reward_share = Mining::unpack(&self.mining_reward_acc.data.borrow())?.share;
+        let collateral_amount = amount.unwrap_or(source_account.amount);
+        assert_non_zero_amount(collateral_amount)?;
 
-        if collateral_amount != reward_share {
+        // Transferred pool tokens must be backed by the mining share
+        let reward_share = Mining::unpack_any(&self.mining_reward_acc.data.borrow())?.share;
+        if collateral_amount > reward_share {
             return Err(EverlendError::RewardAndCollateralMismatch.into());
         }
 
@@ -99,7 +102,7 @@ impl<'a, 'b> TransferDepositContext<'a, 'b> {
             &[],
         )?;
 
-        self.transfer_mining(program_id, &pool, reward_share)?;
+        self.transfer_mining(program_id, &pool, collateral_amount)?;
 
         Ok(())
     }
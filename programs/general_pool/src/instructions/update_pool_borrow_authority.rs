@@ -1,4 +1,6 @@
-use crate::state::{Pool, PoolBorrowAuthority, PoolMarket};
+use crate::state::{Pool, PoolBorrowAuthority, PoolBorrowShares, PoolMarket};
+use crate::utils::assert_manager_or_role;
+use everlend_registry::state::{Role, RoleAccounts};
 use everlend_utils::{assert_account_key, AccountLoader};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
@@ -10,7 +12,9 @@ pub struct UpdatePoolBorrowAuthorityContext<'a, 'b> {
     pool_market: &'a AccountInfo<'b>,
     pool: &'a AccountInfo<'b>,
     pool_borrow_authority: &'a AccountInfo<'b>,
+    pool_borrow_shares: &'a AccountInfo<'b>,
     manager: &'a AccountInfo<'b>,
+    role_accounts: Option<RoleAccounts<'a, 'b>>,
 }
 
 impl<'a, 'b> UpdatePoolBorrowAuthorityContext<'a, 'b> {
@@ -24,22 +28,32 @@ impl<'a, 'b> UpdatePoolBorrowAuthorityContext<'a, 'b> {
         let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let pool_borrow_authority = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pool_borrow_shares = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let manager = AccountLoader::next_signer(account_info_iter)?;
 
+        let role_accounts = RoleAccounts::next_optional(account_info_iter)?;
+
         Ok(UpdatePoolBorrowAuthorityContext {
             pool_market,
             pool,
             pool_borrow_authority,
+            pool_borrow_shares,
             manager,
+            role_accounts,
         })
     }
 
     /// Process instruction
     pub fn process(&self, _program_id: &Pubkey, share_allowed: u16) -> ProgramResult {
-        // Check manager
+        // Check manager or role
         {
             let pool_market = PoolMarket::unpack(&self.pool_market.data.borrow())?;
-            assert_account_key(self.manager, &pool_market.manager)?;
+            assert_manager_or_role(
+                &pool_market,
+                self.manager,
+                self.role_accounts.as_ref(),
+                Role::Admin,
+            )?;
 
             // Get pool state
             let pool = Pool::unpack(&self.pool.data.borrow())?;
@@ -51,12 +65,21 @@ impl<'a, 'b> UpdatePoolBorrowAuthorityContext<'a, 'b> {
             PoolBorrowAuthority::unpack(&self.pool_borrow_authority.data.borrow())?;
         assert_account_key(self.pool, &pool_borrow_authority.pool)?;
 
-        pool_borrow_authority.share_allowed = share_allowed;
+        let mut pool_borrow_shares =
+            PoolBorrowShares::unpack(&self.pool_borrow_shares.data.borrow())?;
+        assert_account_key(self.pool, &pool_borrow_shares.pool)?;
+        pool_borrow_shares.update(pool_borrow_authority.share_allowed, share_allowed)?;
+
+        pool_borrow_authority.update_share_allowed(share_allowed)?;
 
         PoolBorrowAuthority::pack(
             pool_borrow_authority,
             *self.pool_borrow_authority.data.borrow_mut(),
         )?;
+        PoolBorrowShares::pack(
+            pool_borrow_shares,
+            *self.pool_borrow_shares.data.borrow_mut(),
+        )?;
 
         Ok(())
     }
@@ -1,6 +1,8 @@
 use crate::{
     find_transit_program_address, find_transit_sol_unwrap_address,
+    find_withdrawal_request_program_address,
     state::{Pool, WithdrawalRequest, WithdrawalRequests},
+    utils::pause_flags,
 };
 use everlend_utils::{assert_account_key, cpi, find_program_address, AccountLoader, EverlendError};
 use solana_program::{
@@ -26,6 +28,8 @@ pub struct WithdrawContext<'a, 'b> {
     pool_market: &'a AccountInfo<'b>,
     pool_market_authority: &'a AccountInfo<'b>,
     pool: &'a AccountInfo<'b>,
+    global_pause: &'a AccountInfo<'b>,
+    pool_pause: &'a AccountInfo<'b>,
     pool_mint: &'a AccountInfo<'b>,
     withdrawal_requests: &'a AccountInfo<'b>,
     withdrawal_request: &'a AccountInfo<'b>,
@@ -48,6 +52,9 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
         let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let pool_market_authority = AccountLoader::next_unchecked(account_info_iter)?; // Is PDA account of this program
         let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let global_pause =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let pool_pause = AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
         let pool_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
         let withdrawal_requests = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let withdrawal_request = AccountLoader::next_with_owner(account_info_iter, program_id)?;
@@ -82,6 +89,8 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
             pool_market,
             pool_market_authority,
             pool,
+            global_pause,
+            pool_pause,
             pool_mint,
             withdrawal_requests,
             withdrawal_request,
@@ -112,12 +121,30 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
         assert_account_key(self.token_account, &pool.token_account)?;
         assert_account_key(self.pool_mint, &pool.pool_mint)?;
 
+        let flags = pause_flags(
+            self.pool_market,
+            self.pool,
+            self.global_pause,
+            self.pool_pause,
+        )?;
+        if flags.withdraw {
+            return Err(EverlendError::OperationPaused.into());
+        }
+
         // We don't check the withdrawal requests pda, because it's created from the program
         // and is linked to the pool
         let mut withdrawal_requests =
-            WithdrawalRequests::unpack(&self.withdrawal_requests.data.borrow())?;
+            WithdrawalRequests::unpack_any(&self.withdrawal_requests.data.borrow())?;
         assert_account_key(self.pool, &withdrawal_requests.pool)?;
 
+        // Queued requests are processed in order by ProcessWithdrawalRequest
+        let (withdrawal_request_pubkey, _) = find_withdrawal_request_program_address(
+            program_id,
+            self.withdrawal_requests.key,
+            self.from.key,
+        );
+        assert_account_key(self.withdrawal_request, &withdrawal_request_pubkey)?;
+
         let withdrawal_request = WithdrawalRequest::unpack(&self.withdrawal_request.data.borrow())?;
 
         // Check withdraw request accounts
@@ -221,7 +248,7 @@ impl<'a, 'b> WithdrawContext<'a, 'b> {
             .checked_add(withdraw_request_lamports)
             .ok_or(EverlendError::MathOverflow)?;
 
-        WithdrawalRequests::pack(
+        WithdrawalRequests::pack_any(
             withdrawal_requests,
             *self.withdrawal_requests.data.borrow_mut(),
         )?;
@@ -5,7 +5,7 @@ use crate::{
         InitWithdrawalRequestParams, Pool, PoolConfig, WithdrawalRequest, WithdrawalRequests,
         WITHDRAW_DELAY,
     },
-    utils::total_pool_amount,
+    utils::{accrue_pool_fees, pause_flags, total_pool_amount},
 };
 use everlend_rewards::cpi::withdraw_mining;
 use everlend_utils::{
@@ -22,13 +22,15 @@ use solana_program::{
     system_program,
     sysvar::{Sysvar, SysvarId},
 };
-use spl_token::state::{Account, Mint};
+use spl_token::state::Account;
 
 /// Instruction context
 pub struct WithdrawRequestContext<'a, 'b> {
     pool_config: &'a AccountInfo<'b>,
     pool_market: &'a AccountInfo<'b>,
     pool: &'a AccountInfo<'b>,
+    global_pause: &'a AccountInfo<'b>,
+    pool_pause: &'a AccountInfo<'b>,
     pool_mint: &'a AccountInfo<'b>,
     withdrawal_requests: &'a AccountInfo<'b>,
     withdrawal_request: &'a AccountInfo<'b>,
@@ -42,6 +44,8 @@ pub struct WithdrawRequestContext<'a, 'b> {
     everlend_rewards_program: &'a AccountInfo<'b>,
     rent: &'a AccountInfo<'b>,
     clock: &'a AccountInfo<'b>,
+    fee_schedule: &'a AccountInfo<'b>,
+    pool_fees: &'a AccountInfo<'b>,
 }
 
 impl<'a, 'b> WithdrawRequestContext<'a, 'b> {
@@ -55,6 +59,9 @@ impl<'a, 'b> WithdrawRequestContext<'a, 'b> {
         let pool_config = AccountLoader::next_optional(account_info_iter, program_id)?;
         let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let global_pause =
+            AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
+        let pool_pause = AccountLoader::next_optional(account_info_iter, &everlend_registry::id())?;
         let pool_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
         let withdrawal_requests = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let withdrawal_request = AccountLoader::next_uninitialized(account_info_iter)?;
@@ -76,10 +83,16 @@ impl<'a, 'b> WithdrawRequestContext<'a, 'b> {
             AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
         let _token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
 
+        // fees accounts
+        let fee_schedule = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let pool_fees = AccountLoader::next_optional(account_info_iter, program_id)?;
+
         Ok(WithdrawRequestContext {
             pool_config,
             pool_market,
             pool,
+            global_pause,
+            pool_pause,
             pool_mint,
             withdrawal_requests,
             withdrawal_request,
@@ -93,6 +106,8 @@ impl<'a, 'b> WithdrawRequestContext<'a, 'b> {
             everlend_rewards_program,
             rent,
             clock,
+            fee_schedule,
+            pool_fees,
         })
     }
 
@@ -107,6 +122,16 @@ impl<'a, 'b> WithdrawRequestContext<'a, 'b> {
         assert_account_key(self.token_account, &pool.token_account)?;
         assert_account_key(self.pool_mint, &pool.pool_mint)?;
 
+        let flags = pause_flags(
+            self.pool_market,
+            self.pool,
+            self.global_pause,
+            self.pool_pause,
+        )?;
+        if flags.withdraw {
+            return Err(EverlendError::OperationPaused.into());
+        }
+
         // In all cases except SOL token, we must check destination account
         if pool.token_mint != spl_token::native_mint::id() {
             let destination_account = Account::unpack(&self.destination.data.borrow())?;
@@ -121,7 +146,7 @@ impl<'a, 'b> WithdrawRequestContext<'a, 'b> {
         assert_account_key(self.collateral_transit, &collateral_transit_pubkey)?;
 
         let mut withdrawal_requests =
-            WithdrawalRequests::unpack(&self.withdrawal_requests.data.borrow())?;
+            WithdrawalRequests::unpack_any(&self.withdrawal_requests.data.borrow())?;
 
         // Check withdrawal requests accounts
         assert_account_key(self.pool, &withdrawal_requests.pool)?;
@@ -136,7 +161,18 @@ impl<'a, 'b> WithdrawRequestContext<'a, 'b> {
 
         let total_incoming =
             total_pool_amount(self.token_account.clone(), pool.total_amount_borrowed)?;
-        let total_minted = Mint::unpack_unchecked(&self.pool_mint.data.borrow())?.supply;
+        let clock = Clock::from_account_info(self.clock)?;
+        let total_minted = accrue_pool_fees(
+            program_id,
+            self.pool_market,
+            self.pool,
+            self.fee_schedule,
+            self.pool_config,
+            self.pool_fees,
+            self.pool_mint,
+            total_incoming,
+            clock.unix_timestamp,
+        )?;
 
         let liquidity_amount = (collateral_amount as u128)
             .checked_mul(total_incoming as u128)
@@ -148,13 +184,16 @@ impl<'a, 'b> WithdrawRequestContext<'a, 'b> {
         let (pool_config_pubkey, _) = find_pool_config_program_address(program_id, self.pool.key);
         assert_account_key(self.pool_config, &pool_config_pubkey)?;
 
+        let mut withdraw_delay = WITHDRAW_DELAY;
         if !self.pool_config.owner.eq(&Pubkey::default()) {
             assert_owned_by(self.pool_config, program_id)?;
 
-            let pool_config = PoolConfig::unpack(&self.pool_config.data.borrow())?;
+            let pool_config = PoolConfig::unpack_any(&self.pool_config.data.borrow())?;
             if liquidity_amount < pool_config.withdraw_minimum {
                 return Err(EverlendError::WithdrawAmountTooSmall.into());
             }
+
+            withdraw_delay = pool_config.withdraw_delay();
         }
 
         // Transfer
@@ -168,7 +207,6 @@ impl<'a, 'b> WithdrawRequestContext<'a, 'b> {
 
         {
             let rent = &Rent::from_account_info(self.rent)?;
-            let clock = Clock::from_account_info(self.clock)?;
 
             let signers_seeds = &[
                 br"withdrawal",
@@ -192,7 +230,10 @@ impl<'a, 'b> WithdrawRequestContext<'a, 'b> {
                 destination: *self.destination.key,
                 liquidity_amount,
                 collateral_amount,
-                ticket: clock.slot + WITHDRAW_DELAY,
+                ticket: clock
+                    .slot
+                    .checked_add(withdraw_delay)
+                    .ok_or(EverlendError::MathOverflow)?,
             });
 
             WithdrawalRequest::pack(
@@ -203,7 +244,7 @@ impl<'a, 'b> WithdrawRequestContext<'a, 'b> {
 
         withdrawal_requests.add(liquidity_amount)?;
 
-        WithdrawalRequests::pack(
+        WithdrawalRequests::pack_any(
             withdrawal_requests,
             *self.withdrawal_requests.data.borrow_mut(),
         )?;
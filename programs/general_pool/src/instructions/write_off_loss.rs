@@ -0,0 +1,72 @@
+use crate::state::{Pool, PoolBorrowAuthority};
+use everlend_utils::{assert_account_key, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+
+/// Instruction context
+pub struct WriteOffLossContext<'a, 'b> {
+    pool_market: &'a AccountInfo<'b>,
+    pool: &'a AccountInfo<'b>,
+    pool_borrow_authority: &'a AccountInfo<'b>,
+    borrow_authority: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> WriteOffLossContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<WriteOffLossContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let pool_market = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pool_borrow_authority = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let borrow_authority = AccountLoader::next_signer(account_info_iter)?;
+
+        Ok(WriteOffLossContext {
+            pool_market,
+            pool,
+            pool_borrow_authority,
+            borrow_authority,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, _program_id: &Pubkey, amount: u64) -> ProgramResult {
+        let mut pool = Pool::unpack(&self.pool.data.borrow())?;
+
+        // Check pool accounts
+        assert_account_key(self.pool_market, &pool.pool_market)?;
+
+        let mut pool_borrow_authority =
+            PoolBorrowAuthority::unpack_any(&self.pool_borrow_authority.data.borrow())?;
+
+        // Check pool borrow authority accounts
+        assert_account_key(self.pool, &pool_borrow_authority.pool)?;
+        assert_account_key(
+            self.borrow_authority,
+            &pool_borrow_authority.borrow_authority,
+        )?;
+
+        // Borrowed liquidity is lost, so the pool token exchange rate drops
+        pool_borrow_authority.repay(amount)?;
+        pool.repay(amount)?;
+
+        msg!(
+            "Loss written off: {} total amount borrowed: {}",
+            amount,
+            pool.total_amount_borrowed
+        );
+
+        PoolBorrowAuthority::pack_any(
+            pool_borrow_authority,
+            *self.pool_borrow_authority.data.borrow_mut(),
+        )?;
+        Pool::pack(pool, *self.pool.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -86,6 +86,22 @@ pub fn find_withdrawal_request_program_address(
     )
 }
 
+/// Generates queued withdrawal request address
+pub fn find_queued_withdrawal_request_program_address(
+    program_id: &Pubkey,
+    withdrawal_requests_pubkey: &Pubkey,
+    index: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            br"withdrawal",
+            &withdrawal_requests_pubkey.to_bytes(),
+            &index.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
 /// Generates transit address
 pub fn find_transit_program_address(
     program_id: &Pubkey,
@@ -111,6 +127,24 @@ pub fn find_pool_config_program_address(program_id: &Pubkey, pool: &Pubkey) -> (
     Pubkey::find_program_address(&["config".as_bytes(), &pool.to_bytes()], program_id)
 }
 
+/// Calculates address of pool market fee schedule
+pub fn find_fee_schedule_program_address(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[br"fees", &pool_market.to_bytes()], program_id)
+}
+
+/// Calculates address of pool fees
+pub fn find_pool_fees_program_address(program_id: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[br"pool_fees", &pool.to_bytes()], program_id)
+}
+
+/// Calculates address of pool borrow shares
+pub fn find_pool_borrow_shares_program_address(program_id: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[br"borrow_shares", &pool.to_bytes()], program_id)
+}
+
 /// Generates user mining address
 pub fn find_user_mining_address(user: &Pubkey, pool_market: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
@@ -7,11 +7,14 @@ use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
 
 use crate::instruction::LiquidityPoolsInstruction;
 use crate::instructions::{
-    BorrowContext, CreatePoolBorrowAuthorityContext, CreatePoolContext,
-    DeletePoolBorrowAuthorityContext, DepositContext, InitPoolMarketContext, InitUserMiningContext,
-    RepayContext, SetPoolConfigContext, SetTokenMetadataContext, TransferDepositContext,
-    UpdateManagerContext, UpdatePoolBorrowAuthorityContext, WithdrawContext,
-    WithdrawRequestContext,
+    AcceptManagerContext, AccrueFeesContext, BorrowContext, CancelManagerProposalContext,
+    CompoundRewardsContext, CreatePoolBorrowAuthorityContext, CreatePoolContext,
+    DeletePoolBorrowAuthorityContext, DepositContext, InitPoolBorrowSharesContext,
+    InitPoolMarketContext, InitUserMiningContext, InstantWithdrawContext, MigratePoolMarketContext,
+    ProcessWithdrawalRequestContext, ProposeManagerContext, QueueWithdrawRequestContext,
+    RepayContext, ResyncMiningContext, SetFeeScheduleContext, SetPoolConfigContext,
+    SetTokenMetadataContext, TransferDepositContext, UpdatePoolBorrowAuthorityContext,
+    WithdrawContext, WithdrawRequestContext, WriteOffLossContext,
 };
 
 /// Instruction processing router
@@ -91,7 +94,7 @@ pub fn process_instruction(
 
         LiquidityPoolsInstruction::MigrationInstruction => {
             msg!("LiquidityPoolsInstruction: MigrationInstruction");
-            Err(EverlendError::TemporaryUnavailable.into())
+            MigratePoolMarketContext::new(program_id, accounts)?.process(program_id)
         }
 
         LiquidityPoolsInstruction::InitUserMining => {
@@ -99,9 +102,9 @@ pub fn process_instruction(
             InitUserMiningContext::new(program_id, accounts)?.process(program_id)
         }
 
-        LiquidityPoolsInstruction::UpdateManager => {
-            msg!("LiquidityPoolsInstruction: UpdateManager");
-            UpdateManagerContext::new(program_id, accounts)?.process(program_id)
+        LiquidityPoolsInstruction::ProposeManager => {
+            msg!("LiquidityPoolsInstruction: ProposeManager");
+            ProposeManagerContext::new(program_id, accounts)?.process(program_id)
         }
 
         LiquidityPoolsInstruction::SetTokenMetadata { name, symbol, uri } => {
@@ -117,7 +120,78 @@ pub fn process_instruction(
 
         LiquidityPoolsInstruction::TransferDeposit => {
             msg!("LiquidityPoolsInstruction: TransferDeposit");
-            TransferDepositContext::new(program_id, accounts)?.process(program_id)
+            TransferDepositContext::new(program_id, accounts)?.process(program_id, None)
+        }
+
+        LiquidityPoolsInstruction::SetFeeSchedule {
+            treasury,
+            performance_fee,
+            management_fee,
+        } => {
+            msg!("LiquidityPoolsInstruction: SetFeeSchedule");
+            SetFeeScheduleContext::new(program_id, accounts)?.process(
+                program_id,
+                treasury,
+                performance_fee,
+                management_fee,
+            )
+        }
+
+        LiquidityPoolsInstruction::AccrueFees => {
+            msg!("LiquidityPoolsInstruction: AccrueFees");
+            AccrueFeesContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        LiquidityPoolsInstruction::CompoundRewards => {
+            msg!("LiquidityPoolsInstruction: CompoundRewards");
+            CompoundRewardsContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        LiquidityPoolsInstruction::TransferDepositAmount { amount } => {
+            msg!("LiquidityPoolsInstruction: TransferDepositAmount");
+            TransferDepositContext::new(program_id, accounts)?.process(program_id, Some(amount))
+        }
+
+        LiquidityPoolsInstruction::ResyncMining => {
+            msg!("LiquidityPoolsInstruction: ResyncMining");
+            ResyncMiningContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        LiquidityPoolsInstruction::QueueWithdrawRequest { collateral_amount } => {
+            msg!("LiquidityPoolsInstruction: QueueWithdrawRequest");
+            QueueWithdrawRequestContext::new(program_id, accounts)?
+                .process(program_id, collateral_amount)
+        }
+
+        LiquidityPoolsInstruction::ProcessWithdrawalRequest => {
+            msg!("LiquidityPoolsInstruction: ProcessWithdrawalRequest");
+            ProcessWithdrawalRequestContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        LiquidityPoolsInstruction::InstantWithdraw { collateral_amount } => {
+            msg!("LiquidityPoolsInstruction: InstantWithdraw");
+            InstantWithdrawContext::new(program_id, accounts)?
+                .process(program_id, collateral_amount)
+        }
+
+        LiquidityPoolsInstruction::AcceptManager => {
+            msg!("LiquidityPoolsInstruction: AcceptManager");
+            AcceptManagerContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        LiquidityPoolsInstruction::CancelManagerProposal => {
+            msg!("LiquidityPoolsInstruction: CancelManagerProposal");
+            CancelManagerProposalContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        LiquidityPoolsInstruction::WriteOffLoss { amount } => {
+            msg!("LiquidityPoolsInstruction: WriteOffLoss");
+            WriteOffLossContext::new(program_id, accounts)?.process(program_id, amount)
+        }
+
+        LiquidityPoolsInstruction::InitPoolBorrowShares => {
+            msg!("LiquidityPoolsInstruction: InitPoolBorrowShares");
+            InitPoolBorrowSharesContext::new(program_id, accounts)?.process(program_id)
         }
     }
 }
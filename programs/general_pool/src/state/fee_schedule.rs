@@ -0,0 +1,92 @@
+//! Fee schedule state definitions
+
+use super::*;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Max fee in basis points
+pub const MAX_FEE_BPS: u16 = 10_000;
+
+/// Max performance fee in basis points
+pub const MAX_PERFORMANCE_FEE_BPS: u16 = 2_000;
+
+/// Max annual management fee in basis points
+pub const MAX_MANAGEMENT_FEE_BPS: u16 = 500;
+
+/// Max instant withdraw fee in basis points
+pub const MAX_EXIT_FEE_BPS: u16 = 300;
+
+/// Pool market fee schedule
+#[repr(C)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct FeeSchedule {
+    /// Account type - FeeSchedule
+    pub account_type: AccountType,
+    /// Pool market
+    pub pool_market: Pubkey,
+    /// Owner of the pool token accounts receiving fees
+    pub treasury: Pubkey,
+    /// Share of realized income in basis points
+    pub performance_fee: u16,
+    /// Annual share of pool assets in basis points
+    pub management_fee: u16,
+}
+
+impl FeeSchedule {
+    /// Initialize a fee schedule
+    pub fn init(pool_market: Pubkey) -> FeeSchedule {
+        FeeSchedule {
+            account_type: AccountType::FeeSchedule,
+            pool_market,
+            ..Default::default()
+        }
+    }
+
+    /// Set fee schedule
+    pub fn set(
+        &mut self,
+        treasury: Pubkey,
+        performance_fee: u16,
+        management_fee: u16,
+    ) -> Result<(), ProgramError> {
+        if performance_fee > MAX_PERFORMANCE_FEE_BPS || management_fee > MAX_MANAGEMENT_FEE_BPS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        self.treasury = treasury;
+        self.performance_fee = performance_fee;
+        self.management_fee = management_fee;
+
+        Ok(())
+    }
+}
+
+impl Sealed for FeeSchedule {}
+impl Pack for FeeSchedule {
+    // 1 + 32 + 32 + 2 + 2
+    const LEN: usize = 69;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Failed to deserialize");
+            msg!("Actual LEN: {}", std::mem::size_of::<FeeSchedule>());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for FeeSchedule {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::FeeSchedule
+    }
+}
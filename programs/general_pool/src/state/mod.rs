@@ -2,15 +2,21 @@
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use std::fmt;
 
+mod fee_schedule;
 mod pool;
 mod pool_borrow_authority;
+mod pool_borrow_shares;
 mod pool_config;
+mod pool_fees;
 mod pool_market;
 mod withdrawal_request;
 
+pub use fee_schedule::*;
 pub use pool::*;
 pub use pool_borrow_authority::*;
+pub use pool_borrow_shares::*;
 pub use pool_config::*;
+pub use pool_fees::*;
 pub use pool_market::*;
 pub use withdrawal_request::*;
 
@@ -31,6 +37,12 @@ pub enum AccountType {
     WithdrawRequest,
     /// Pool config
     PoolConfig,
+    /// Fee schedule
+    FeeSchedule,
+    /// Pool fees
+    PoolFees,
+    /// Pool borrow shares
+    PoolBorrowShares,
 }
 
 impl Default for AccountType {
@@ -10,6 +10,20 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
+pub use deprecated::DeprecatedPoolBorrowAuthority;
+
+/// Max share allowed in basis points
+pub const MAX_SHARE_ALLOWED: u16 = 10_000;
+
+/// Check share allowed doesn't exceed the whole pool
+pub fn assert_share_allowed(share_allowed: u16) -> ProgramResult {
+    if share_allowed > MAX_SHARE_ALLOWED {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
 /// Pool
 #[repr(C)]
 #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
@@ -24,6 +38,8 @@ pub struct PoolBorrowAuthority {
     pub amount_borrowed: u64,
     /// Share allowed
     pub share_allowed: u16,
+    /// Saved bump for pool borrow authority account
+    pub bump: u8,
 }
 
 impl PoolBorrowAuthority {
@@ -35,7 +51,34 @@ impl PoolBorrowAuthority {
             borrow_authority: params.borrow_authority,
             amount_borrowed: 0,
             share_allowed: params.share_allowed,
+            bump: params.bump,
+        }
+    }
+
+    /// Unpack pool borrow authority of the actual or deprecated layout
+    pub fn unpack_any(src: &[u8]) -> Result<PoolBorrowAuthority, ProgramError> {
+        if src.len() == DeprecatedPoolBorrowAuthority::LEN {
+            return Ok(DeprecatedPoolBorrowAuthority::unpack(src)?.into());
+        }
+
+        PoolBorrowAuthority::unpack(src)
+    }
+
+    /// Pack pool borrow authority keeping the deprecated layout of accounts not counted yet
+    pub fn pack_any(pool_borrow_authority: PoolBorrowAuthority, dst: &mut [u8]) -> ProgramResult {
+        if dst.len() == DeprecatedPoolBorrowAuthority::LEN {
+            return DeprecatedPoolBorrowAuthority::pack(pool_borrow_authority.into(), dst);
         }
+
+        PoolBorrowAuthority::pack(pool_borrow_authority, dst)
+    }
+
+    /// Update share allowed
+    pub fn update_share_allowed(&mut self, share: u16) -> ProgramResult {
+        assert_share_allowed(share)?;
+        self.share_allowed = share;
+
+        Ok(())
     }
 
     /// Borrow funds
@@ -71,11 +114,17 @@ impl PoolBorrowAuthority {
 
     /// Check amount allowed
     pub fn check_amount_allowed(&self, total_pool_amount: u64) -> ProgramResult {
-        if self.amount_borrowed > self.get_amount_allowed(total_pool_amount)? {
-            Err(EverlendError::AmountAllowedCheckFailed.into())
-        } else {
-            Ok(())
+        let amount_allowed = self.get_amount_allowed(total_pool_amount)?;
+        if self.amount_borrowed > amount_allowed {
+            msg!(
+                "Amount borrowed {} exceeds amount allowed {}",
+                self.amount_borrowed,
+                amount_allowed
+            );
+            return Err(EverlendError::BorrowShareAllowedExceeded.into());
         }
+
+        Ok(())
     }
 }
 
@@ -87,12 +136,14 @@ pub struct InitPoolBorrowAuthorityParams {
     pub borrow_authority: Pubkey,
     /// Share allowed
     pub share_allowed: u16,
+    /// Saved bump for pool borrow authority account
+    pub bump: u8,
 }
 
 impl Sealed for PoolBorrowAuthority {}
 impl Pack for PoolBorrowAuthority {
-    // 1 + 32 + 32 + 8 + 2
-    const LEN: usize = 75;
+    // 1 + 32 + 32 + 8 + 2 + 1
+    const LEN: usize = 76;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -119,3 +170,73 @@ impl Uninitialized for PoolBorrowAuthority {
         self.account_type == AccountType::default()
     }
 }
+
+mod deprecated {
+    use super::*;
+
+    /// Pool borrow authority created before the pool borrow shares.
+    /// Its share allowed isn't counted in the total, so it can't borrow.
+    #[repr(C)]
+    #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+    pub struct DeprecatedPoolBorrowAuthority {
+        /// Account type - PoolBorrowAuthority
+        pub account_type: AccountType,
+        /// Pool
+        pub pool: Pubkey,
+        /// Borrow authority
+        pub borrow_authority: Pubkey,
+        /// Amount borrowed
+        pub amount_borrowed: u64,
+        /// Share allowed
+        pub share_allowed: u16,
+    }
+
+    impl Sealed for DeprecatedPoolBorrowAuthority {}
+    impl Pack for DeprecatedPoolBorrowAuthority {
+        // 1 + 32 + 32 + 8 + 2
+        const LEN: usize = 75;
+
+        fn pack_into_slice(&self, dst: &mut [u8]) {
+            let mut slice = dst;
+            self.serialize(&mut slice).unwrap()
+        }
+
+        fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+            Self::try_from_slice(src).map_err(|_| {
+                msg!("Failed to deserialize");
+                ProgramError::InvalidAccountData
+            })
+        }
+    }
+
+    impl IsInitialized for DeprecatedPoolBorrowAuthority {
+        fn is_initialized(&self) -> bool {
+            self.account_type == AccountType::PoolBorrowAuthority
+        }
+    }
+
+    impl From<DeprecatedPoolBorrowAuthority> for PoolBorrowAuthority {
+        fn from(deprecated: DeprecatedPoolBorrowAuthority) -> Self {
+            PoolBorrowAuthority {
+                account_type: deprecated.account_type,
+                pool: deprecated.pool,
+                borrow_authority: deprecated.borrow_authority,
+                amount_borrowed: deprecated.amount_borrowed,
+                share_allowed: deprecated.share_allowed,
+                bump: 0,
+            }
+        }
+    }
+
+    impl From<PoolBorrowAuthority> for DeprecatedPoolBorrowAuthority {
+        fn from(pool_borrow_authority: PoolBorrowAuthority) -> Self {
+            DeprecatedPoolBorrowAuthority {
+                account_type: pool_borrow_authority.account_type,
+                pool: pool_borrow_authority.pool,
+                borrow_authority: pool_borrow_authority.borrow_authority,
+                amount_borrowed: pool_borrow_authority.amount_borrowed,
+                share_allowed: pool_borrow_authority.share_allowed,
+            }
+        }
+    }
+}
@@ -2,12 +2,15 @@
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
+    clock::Slot,
     msg,
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
 };
 
 use super::*;
+pub use deprecated::DeprecatedPoolConfig;
+
 /// Pool config
 #[repr(C)]
 #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -18,6 +21,18 @@ pub struct PoolConfig {
     pub deposit_minimum: u64,
     /// Minimum amount for withdraw request
     pub withdraw_minimum: u64,
+    /// Performance fee override in basis points
+    pub performance_fee: Option<u16>,
+    /// Management fee override in basis points
+    pub management_fee: Option<u16>,
+    /// Withdraw delay override in slots
+    pub withdraw_delay: Option<Slot>,
+    /// Instant withdraw fee in basis points, stays in the pool
+    pub exit_fee: u16,
+    /// Maximum total liquidity of the pool
+    pub pool_deposit_cap: Option<u64>,
+    /// Maximum liquidity deposited by a single user
+    pub user_deposit_cap: Option<u64>,
 }
 
 impl PoolConfig {
@@ -27,11 +42,22 @@ impl PoolConfig {
             account_type: AccountType::PoolConfig,
             deposit_minimum: 0,
             withdraw_minimum: 0,
+            performance_fee: None,
+            management_fee: None,
+            withdraw_delay: None,
+            exit_fee: 0,
+            pool_deposit_cap: None,
+            user_deposit_cap: None,
         }
     }
 
+    /// Withdraw delay of the pool
+    pub fn withdraw_delay(&self) -> Slot {
+        self.withdraw_delay.unwrap_or(WITHDRAW_DELAY)
+    }
+
     /// Set pool config
-    pub fn set(&mut self, params: SetPoolConfigParams) {
+    pub fn set(&mut self, params: SetPoolConfigParams) -> Result<(), ProgramError> {
         if params.deposit_minimum.is_some() {
             self.deposit_minimum = params.deposit_minimum.unwrap();
         }
@@ -39,12 +65,67 @@ impl PoolConfig {
         if params.withdraw_minimum.is_some() {
             self.withdraw_minimum = params.withdraw_minimum.unwrap();
         }
+
+        if let Some(performance_fee) = params.performance_fee {
+            if performance_fee.unwrap_or_default() > MAX_PERFORMANCE_FEE_BPS {
+                return Err(ProgramError::InvalidArgument);
+            }
+            self.performance_fee = performance_fee;
+        }
+
+        if let Some(management_fee) = params.management_fee {
+            if management_fee.unwrap_or_default() > MAX_MANAGEMENT_FEE_BPS {
+                return Err(ProgramError::InvalidArgument);
+            }
+            self.management_fee = management_fee;
+        }
+
+        if let Some(withdraw_delay) = params.withdraw_delay {
+            if withdraw_delay.unwrap_or_default() > MAX_WITHDRAW_DELAY {
+                return Err(ProgramError::InvalidArgument);
+            }
+            self.withdraw_delay = withdraw_delay;
+        }
+
+        if let Some(exit_fee) = params.exit_fee {
+            if exit_fee > MAX_EXIT_FEE_BPS {
+                return Err(ProgramError::InvalidArgument);
+            }
+            self.exit_fee = exit_fee;
+        }
+
+        if let Some(pool_deposit_cap) = params.pool_deposit_cap {
+            self.pool_deposit_cap = pool_deposit_cap;
+        }
+
+        if let Some(user_deposit_cap) = params.user_deposit_cap {
+            self.user_deposit_cap = user_deposit_cap;
+        }
+
+        Ok(())
+    }
+
+    /// Unpack pool config of the actual or deprecated layout
+    pub fn unpack_any(src: &[u8]) -> Result<PoolConfig, ProgramError> {
+        if src.len() == DeprecatedPoolConfig::LEN {
+            return Ok(DeprecatedPoolConfig::unpack(src)?.into());
+        }
+
+        // Layouts since fee overrides are prefixes of the actual one,
+        // missing fields are unpacked as zero values
+        if src.len() < PoolConfig::LEN {
+            let mut data = [0u8; PoolConfig::LEN];
+            data[..src.len()].copy_from_slice(src);
+            return PoolConfig::unpack(&data);
+        }
+
+        PoolConfig::unpack(src)
     }
 }
 
 impl Sealed for PoolConfig {}
 impl Pack for PoolConfig {
-    const LEN: usize = 1 + 8 + 8;
+    const LEN: usize = 1 + 8 + 8 + (1 + 2) + (1 + 2) + (1 + 8) + 2 + (1 + 8) + (1 + 8);
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -74,4 +155,73 @@ pub struct SetPoolConfigParams {
     pub deposit_minimum: Option<u64>,
     /// Minimum amount for withdraw request
     pub withdraw_minimum: Option<u64>,
+    /// Performance fee override, `Some(None)` resets to the market fee
+    pub performance_fee: Option<Option<u16>>,
+    /// Management fee override, `Some(None)` resets to the market fee
+    pub management_fee: Option<Option<u16>>,
+    /// Withdraw delay override, `Some(None)` resets to the default delay
+    pub withdraw_delay: Option<Option<Slot>>,
+    /// Instant withdraw fee in basis points
+    pub exit_fee: Option<u16>,
+    /// Pool deposit cap, `Some(None)` removes the cap
+    pub pool_deposit_cap: Option<Option<u64>>,
+    /// User deposit cap, `Some(None)` removes the cap
+    pub user_deposit_cap: Option<Option<u64>>,
+}
+
+mod deprecated {
+    use super::*;
+
+    /// Pool config without fee overrides
+    #[repr(C)]
+    #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema)]
+    pub struct DeprecatedPoolConfig {
+        /// Account type - PoolConfig
+        pub account_type: AccountType,
+        /// Minimum amount for deposit
+        pub deposit_minimum: u64,
+        /// Minimum amount for withdraw request
+        pub withdraw_minimum: u64,
+    }
+
+    impl Sealed for DeprecatedPoolConfig {}
+    impl Pack for DeprecatedPoolConfig {
+        const LEN: usize = 1 + 8 + 8;
+
+        fn pack_into_slice(&self, dst: &mut [u8]) {
+            let mut slice = dst;
+            self.serialize(&mut slice).unwrap()
+        }
+
+        fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+            let mut src_mut = src;
+            Self::deserialize(&mut src_mut).map_err(|err| {
+                msg!("Failed to deserialize");
+                msg!(&err.to_string());
+                ProgramError::InvalidAccountData
+            })
+        }
+    }
+
+    impl IsInitialized for DeprecatedPoolConfig {
+        fn is_initialized(&self) -> bool {
+            self.account_type == AccountType::PoolConfig
+        }
+    }
+
+    impl From<DeprecatedPoolConfig> for PoolConfig {
+        fn from(deprecated: DeprecatedPoolConfig) -> Self {
+            PoolConfig {
+                account_type: deprecated.account_type,
+                deposit_minimum: deprecated.deposit_minimum,
+                withdraw_minimum: deprecated.withdraw_minimum,
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: None,
+                exit_fee: 0,
+                pool_deposit_cap: None,
+                user_deposit_cap: None,
+            }
+        }
+    }
 }
@@ -0,0 +1,177 @@
+//! Pool fees state definitions
+
+use super::*;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_utils::{math::PRECISION_SCALER, EverlendError};
+use solana_program::{
+    clock::UnixTimestamp,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Seconds in a year for management fee accrual
+pub const SECONDS_PER_YEAR: u128 = 31_536_000;
+
+/// Pool fees accrual
+#[repr(C)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct PoolFees {
+    /// Account type - PoolFees
+    pub account_type: AccountType,
+    /// Pool
+    pub pool: Pubkey,
+    /// Highest pool token price charged with performance fee, scaled by precision
+    pub share_price_hwm: u64,
+    /// Last accrual timestamp
+    pub last_accrued_at: UnixTimestamp,
+    /// Total performance fee accrued in liquidity tokens
+    pub accrued_performance_fee: u64,
+    /// Total management fee accrued in liquidity tokens
+    pub accrued_management_fee: u64,
+    /// Total pool tokens minted to the treasury
+    pub minted_fee_tokens: u64,
+    /// Pool tokens accrued on deposits and withdrawals, minted by the next fees accrual
+    pub pending_fee_tokens: u64,
+}
+
+impl PoolFees {
+    /// Initialize a pool fees
+    pub fn init(pool: Pubkey, timestamp: UnixTimestamp) -> PoolFees {
+        PoolFees {
+            account_type: AccountType::PoolFees,
+            pool,
+            last_accrued_at: timestamp,
+            ..Default::default()
+        }
+    }
+
+    /// Accrue fees and return pool tokens amount added to the pending fee tokens
+    pub fn accrue(
+        &mut self,
+        total_amount: u64,
+        total_minted: u64,
+        performance_fee: u16,
+        management_fee: u16,
+        timestamp: UnixTimestamp,
+    ) -> Result<u64, ProgramError> {
+        let elapsed = timestamp.saturating_sub(self.last_accrued_at).max(0) as u128;
+        self.last_accrued_at = timestamp;
+
+        if total_amount == 0 || total_minted == 0 {
+            return Ok(0);
+        }
+
+        let total_amount = total_amount as u128;
+        let total_minted = total_minted as u128;
+
+        let share_price = share_price(total_amount, total_minted)?;
+
+        // Skip performance fee until the first price mark
+        let performance_fee_amount = if self.share_price_hwm == 0 {
+            0
+        } else {
+            (share_price as u128)
+                .saturating_sub(self.share_price_hwm as u128)
+                .checked_mul(total_minted)
+                .ok_or(EverlendError::MathOverflow)?
+                .checked_div(PRECISION_SCALER)
+                .ok_or(EverlendError::MathOverflow)?
+                .checked_mul(performance_fee as u128)
+                .ok_or(EverlendError::MathOverflow)?
+                .checked_div(MAX_FEE_BPS as u128)
+                .ok_or(EverlendError::MathOverflow)?
+        };
+
+        let management_fee_amount = total_amount
+            .checked_mul(management_fee as u128)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_mul(elapsed)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_div(MAX_FEE_BPS as u128 * SECONDS_PER_YEAR)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        let fee_amount = performance_fee_amount
+            .checked_add(management_fee_amount)
+            .ok_or(EverlendError::MathOverflow)?;
+        if fee_amount >= total_amount {
+            return Err(EverlendError::MathOverflow.into());
+        }
+
+        // Mint pool tokens worth the fee amount after dilution
+        let mint_amount = fee_amount
+            .checked_mul(total_minted)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_div(total_amount - fee_amount)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        let diluted_share_price = share_price(
+            total_amount,
+            total_minted
+                .checked_add(mint_amount)
+                .ok_or(EverlendError::MathOverflow)?,
+        )?;
+        self.share_price_hwm = self.share_price_hwm.max(diluted_share_price);
+
+        self.accrued_performance_fee = self
+            .accrued_performance_fee
+            .checked_add(performance_fee_amount as u64)
+            .ok_or(EverlendError::MathOverflow)?;
+        self.accrued_management_fee = self
+            .accrued_management_fee
+            .checked_add(management_fee_amount as u64)
+            .ok_or(EverlendError::MathOverflow)?;
+        self.pending_fee_tokens = self
+            .pending_fee_tokens
+            .checked_add(mint_amount as u64)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        Ok(mint_amount as u64)
+    }
+
+    /// Take pending fee tokens to mint to the treasury
+    pub fn take_pending_fee_tokens(&mut self) -> Result<u64, ProgramError> {
+        let amount = self.pending_fee_tokens;
+        self.pending_fee_tokens = 0;
+        self.minted_fee_tokens = self
+            .minted_fee_tokens
+            .checked_add(amount)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        Ok(amount)
+    }
+}
+
+fn share_price(total_amount: u128, total_minted: u128) -> Result<u64, ProgramError> {
+    Ok(total_amount
+        .checked_mul(PRECISION_SCALER)
+        .ok_or(EverlendError::MathOverflow)?
+        .checked_div(total_minted)
+        .ok_or(EverlendError::MathOverflow)? as u64)
+}
+
+impl Sealed for PoolFees {}
+impl Pack for PoolFees {
+    // 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8
+    const LEN: usize = 81;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Failed to deserialize");
+            msg!("Actual LEN: {}", std::mem::size_of::<PoolFees>());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for PoolFees {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::PoolFees
+    }
+}
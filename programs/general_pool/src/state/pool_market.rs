@@ -9,6 +9,8 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
+pub use deprecated::DeprecatedPoolMarket;
+
 /// Pool market
 #[repr(C)]
 #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
@@ -21,6 +23,8 @@ pub struct PoolMarket {
     pub manager: Pubkey,
     /// Registry
     pub registry: Pubkey,
+    /// Proposed manager, takes over after accepting
+    pub pending_manager: Pubkey,
 }
 
 impl PoolMarket {
@@ -33,6 +37,7 @@ impl PoolMarket {
             account_version: Self::ACTUAL_VERSION,
             manager: params.manager,
             registry: params.registry,
+            pending_manager: Pubkey::default(),
         }
     }
 }
@@ -47,8 +52,8 @@ pub struct InitPoolMarketParams {
 
 impl Sealed for PoolMarket {}
 impl Pack for PoolMarket {
-    // 1 + 1 + 32 + 32
-    const LEN: usize = 66;
+    // 1 + 1 + 32 + 32 + 32
+    const LEN: usize = 98;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -75,3 +80,57 @@ impl Uninitialized for PoolMarket {
         self.account_type == AccountType::default()
     }
 }
+
+mod deprecated {
+    use super::*;
+
+    /// Pool market without pending manager
+    #[repr(C)]
+    #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+    pub struct DeprecatedPoolMarket {
+        /// Account type - PoolMarket
+        pub account_type: AccountType,
+        /// Account version
+        pub account_version: AccountVersion,
+        /// Market manager
+        pub manager: Pubkey,
+        /// Registry
+        pub registry: Pubkey,
+    }
+
+    impl Sealed for DeprecatedPoolMarket {}
+    impl Pack for DeprecatedPoolMarket {
+        // 1 + 1 + 32 + 32
+        const LEN: usize = 66;
+
+        fn pack_into_slice(&self, dst: &mut [u8]) {
+            let mut slice = dst;
+            self.serialize(&mut slice).unwrap()
+        }
+
+        fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+            Self::try_from_slice(src).map_err(|_| {
+                msg!("Failed to deserialize");
+                ProgramError::InvalidAccountData
+            })
+        }
+    }
+
+    impl IsInitialized for DeprecatedPoolMarket {
+        fn is_initialized(&self) -> bool {
+            self.account_type == AccountType::PoolMarket
+        }
+    }
+
+    impl From<DeprecatedPoolMarket> for PoolMarket {
+        fn from(deprecated: DeprecatedPoolMarket) -> Self {
+            PoolMarket {
+                account_type: deprecated.account_type,
+                account_version: deprecated.account_version,
+                manager: deprecated.manager,
+                registry: deprecated.registry,
+                pending_manager: Pubkey::default(),
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::{AccountType, AccountVersion};
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+pub use deprecated::DeprecatedWithdrawalRequests;
 use everlend_utils::{EverlendError, Uninitialized};
 use solana_program::{
     clock::Slot,
@@ -9,10 +10,14 @@ use solana_program::{
     program_pack::{IsInitialized, Pack, Sealed},
     pubkey::Pubkey,
 };
+use std::convert::TryFrom;
 
-/// How long after the request, you can execute a withdraw
+/// How long after the request, you can execute a withdraw (unless set in the pool config)
 pub const WITHDRAW_DELAY: Slot = 200;
 
+/// Max withdraw delay the pool config can set, about a week of slots
+pub const MAX_WITHDRAW_DELAY: Slot = 1_512_000;
+
 /// Actual version of withdrawal requests struct
 pub const ACTUAL_VERSION: AccountVersion = AccountVersion::V0;
 
@@ -34,6 +39,15 @@ pub struct WithdrawalRequests {
 
     /// Total requests amount
     pub liquidity_supply: u64,
+
+    /// Index of the next queued request
+    pub next_index: u64,
+
+    /// Index of the first queued request not processed yet
+    pub head_index: u64,
+
+    /// Queued requests amount, the rest of the total is reserved for legacy requests
+    pub queued_liquidity_supply: u64,
 }
 
 /// Initialize a withdrawal requests params
@@ -53,6 +67,9 @@ impl WithdrawalRequests {
             pool: params.pool,
             mint: params.mint,
             liquidity_supply: 0,
+            next_index: 0,
+            head_index: 0,
+            queued_liquidity_supply: 0,
         }
     }
 
@@ -75,12 +92,86 @@ impl WithdrawalRequests {
 
         Ok(())
     }
+
+    /// Add new withdrawal request to the queue and return its index
+    pub fn enqueue(&mut self, liquidity_amount: u64) -> Result<u64, ProgramError> {
+        self.add(liquidity_amount)?;
+        self.queued_liquidity_supply = self
+            .queued_liquidity_supply
+            .checked_add(liquidity_amount)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        let index = self.next_index;
+        self.next_index = self
+            .next_index
+            .checked_add(1)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        Ok(index)
+    }
+
+    /// Remove liquidity of the queued request
+    pub fn process_queued(&mut self, liquidity_amount: u64) -> ProgramResult {
+        self.process(liquidity_amount)?;
+        self.queued_liquidity_supply = self
+            .queued_liquidity_supply
+            .checked_sub(liquidity_amount)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Move the queue head to the next request
+    pub fn dequeue(&mut self) -> ProgramResult {
+        if self.head_index >= self.next_index {
+            return Err(EverlendError::WithdrawRequestsQueueEmpty.into());
+        }
+
+        self.head_index += 1;
+
+        Ok(())
+    }
+
+    /// Liquidity of the pool token balance not reserved for queued and legacy requests
+    pub fn idle_liquidity(&self, balance: u64) -> u64 {
+        balance.saturating_sub(self.liquidity_supply)
+    }
+
+    /// Liquidity of the pool token balance left for the queue after legacy requests
+    pub fn queue_liquidity(&self, balance: u64) -> u64 {
+        let legacy_liquidity_supply = self
+            .liquidity_supply
+            .saturating_sub(self.queued_liquidity_supply);
+
+        balance.saturating_sub(legacy_liquidity_supply)
+    }
+
+    /// Unpack withdrawal requests of the actual or deprecated layout
+    pub fn unpack_any(src: &[u8]) -> Result<WithdrawalRequests, ProgramError> {
+        if src.len() == DeprecatedWithdrawalRequests::LEN {
+            return Ok(DeprecatedWithdrawalRequests::unpack(src)?.into());
+        }
+
+        WithdrawalRequests::unpack(src)
+    }
+
+    /// Pack withdrawal requests keeping the deprecated layout of accounts not reallocated yet
+    pub fn pack_any(withdrawal_requests: WithdrawalRequests, dst: &mut [u8]) -> ProgramResult {
+        if dst.len() == DeprecatedWithdrawalRequests::LEN {
+            return DeprecatedWithdrawalRequests::pack(
+                DeprecatedWithdrawalRequests::try_from(withdrawal_requests)?,
+                dst,
+            );
+        }
+
+        WithdrawalRequests::pack(withdrawal_requests, dst)
+    }
 }
 
 impl Sealed for WithdrawalRequests {}
 impl Pack for WithdrawalRequests {
-    // 1 + 1 + 32 + 32 + 8
-    const LEN: usize = 74;
+    // 1 + 1 + 32 + 32 + 8 + 8 + 8 + 8
+    const LEN: usize = 98;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -201,3 +292,88 @@ impl Uninitialized for WithdrawalRequest {
         self.account_type == AccountType::default()
     }
 }
+
+mod deprecated {
+    use super::*;
+
+    /// Withdrawal requests without the queue
+    #[repr(C)]
+    #[derive(Debug, Clone, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+    pub struct DeprecatedWithdrawalRequests {
+        /// Account type - WithdrawalRequests
+        pub account_type: AccountType,
+
+        /// Account version
+        pub account_version: AccountVersion,
+
+        /// Pool
+        pub pool: Pubkey,
+
+        /// Mint
+        pub mint: Pubkey,
+
+        /// Total requests amount
+        pub liquidity_supply: u64,
+    }
+
+    impl Sealed for DeprecatedWithdrawalRequests {}
+    impl Pack for DeprecatedWithdrawalRequests {
+        // 1 + 1 + 32 + 32 + 8
+        const LEN: usize = 74;
+
+        fn pack_into_slice(&self, dst: &mut [u8]) {
+            let mut slice = dst;
+            self.serialize(&mut slice).unwrap()
+        }
+
+        fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+            let mut src_mut = src;
+            Self::deserialize(&mut src_mut).map_err(|err| {
+                msg!("Failed to deserialize");
+                msg!(&err.to_string());
+                ProgramError::InvalidAccountData
+            })
+        }
+    }
+
+    impl IsInitialized for DeprecatedWithdrawalRequests {
+        fn is_initialized(&self) -> bool {
+            self.account_type == AccountType::WithdrawRequests
+                && self.account_version == ACTUAL_VERSION
+        }
+    }
+
+    impl From<DeprecatedWithdrawalRequests> for WithdrawalRequests {
+        fn from(deprecated: DeprecatedWithdrawalRequests) -> Self {
+            WithdrawalRequests {
+                account_type: deprecated.account_type,
+                account_version: deprecated.account_version,
+                pool: deprecated.pool,
+                mint: deprecated.mint,
+                liquidity_supply: deprecated.liquidity_supply,
+                next_index: 0,
+                head_index: 0,
+                queued_liquidity_supply: 0,
+            }
+        }
+    }
+
+    impl TryFrom<WithdrawalRequests> for DeprecatedWithdrawalRequests {
+        type Error = ProgramError;
+
+        fn try_from(withdrawal_requests: WithdrawalRequests) -> Result<Self, Self::Error> {
+            // Queue can't be stored in the deprecated layout
+            if withdrawal_requests.next_index != 0 {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            Ok(DeprecatedWithdrawalRequests {
+                account_type: withdrawal_requests.account_type,
+                account_version: withdrawal_requests.account_version,
+                pool: withdrawal_requests.pool,
+                mint: withdrawal_requests.mint,
+                liquidity_supply: withdrawal_requests.liquidity_supply,
+            })
+        }
+    }
+}
@@ -1,8 +1,17 @@
 //! Utils
 
-use everlend_utils::EverlendError;
-use solana_program::{account_info::AccountInfo, program_error::ProgramError, program_pack::Pack};
-use spl_token::state::Account;
+use crate::{
+    find_fee_schedule_program_address, find_pool_config_program_address,
+    find_pool_fees_program_address,
+    state::{FeeSchedule, PoolConfig, PoolFees, PoolMarket},
+};
+use everlend_registry::state::{PauseFlags, Role, RoleAccounts};
+use everlend_utils::{assert_account_key, EverlendError};
+use solana_program::{
+    account_info::AccountInfo, clock::UnixTimestamp, entrypoint::ProgramResult, msg,
+    program_error::ProgramError, program_pack::Pack, pubkey::Pubkey,
+};
+use spl_token::state::{Account, Mint};
 
 /// Get total pool amount
 pub fn total_pool_amount(
@@ -14,3 +23,115 @@ pub fn total_pool_amount(
         .checked_add(total_amount_borrowed)
         .ok_or(EverlendError::MathOverflow)?)
 }
+
+/// Get pause flags of the pool set in the registry of the pool market
+pub fn pause_flags(
+    pool_market: &AccountInfo,
+    pool: &AccountInfo,
+    global_pause: &AccountInfo,
+    pool_pause: &AccountInfo,
+) -> Result<PauseFlags, ProgramError> {
+    let pool_market = PoolMarket::unpack(&pool_market.data.borrow())?;
+
+    PauseFlags::load(&pool_market.registry, pool.key, global_pause, pool_pause)
+}
+
+/// Check the signer is the pool market manager or is granted the role in the pool market registry
+pub fn assert_manager_or_role(
+    pool_market: &PoolMarket,
+    signer: &AccountInfo,
+    role_accounts: Option<&RoleAccounts>,
+    role: Role,
+) -> ProgramResult {
+    if let Some(role_accounts) = role_accounts {
+        assert_account_key(role_accounts.registry, &pool_market.registry)?;
+    }
+
+    everlend_registry::state::assert_manager_or_role(
+        &pool_market.manager,
+        signer,
+        role_accounts,
+        role,
+    )
+}
+
+/// Get performance and management fees of the pool, pool config overrides market fees
+pub fn pool_fee_rates(
+    program_id: &Pubkey,
+    pool_market: &AccountInfo,
+    pool: &AccountInfo,
+    fee_schedule: &AccountInfo,
+    pool_config: &AccountInfo,
+) -> Result<(FeeSchedule, u16, u16), ProgramError> {
+    let (fee_schedule_pubkey, _) = find_fee_schedule_program_address(program_id, pool_market.key);
+    assert_account_key(fee_schedule, &fee_schedule_pubkey)?;
+    let fee_schedule = FeeSchedule::unpack(&fee_schedule.data.borrow())?;
+
+    let (pool_config_pubkey, _) = find_pool_config_program_address(program_id, pool.key);
+    assert_account_key(pool_config, &pool_config_pubkey)?;
+
+    if pool_config.owner.eq(&Pubkey::default()) {
+        let (performance_fee, management_fee) =
+            (fee_schedule.performance_fee, fee_schedule.management_fee);
+        return Ok((fee_schedule, performance_fee, management_fee));
+    }
+
+    let pool_config = PoolConfig::unpack_any(&pool_config.data.borrow())?;
+    let performance_fee = pool_config
+        .performance_fee
+        .unwrap_or(fee_schedule.performance_fee);
+    let management_fee = pool_config
+        .management_fee
+        .unwrap_or(fee_schedule.management_fee);
+
+    Ok((fee_schedule, performance_fee, management_fee))
+}
+
+/// Accrue pool fees before the pool token price is used and get the pool tokens supply
+/// including pending fee tokens. Fees accrual starts with the first `AccrueFees`.
+#[allow(clippy::too_many_arguments)]
+pub fn accrue_pool_fees(
+    program_id: &Pubkey,
+    pool_market: &AccountInfo,
+    pool: &AccountInfo,
+    fee_schedule: &AccountInfo,
+    pool_config: &AccountInfo,
+    pool_fees: &AccountInfo,
+    pool_mint: &AccountInfo,
+    total_amount: u64,
+    timestamp: UnixTimestamp,
+) -> Result<u64, ProgramError> {
+    let supply = Mint::unpack_unchecked(&pool_mint.data.borrow())?.supply;
+
+    let (pool_fees_pubkey, _) = find_pool_fees_program_address(program_id, pool.key);
+    assert_account_key(pool_fees, &pool_fees_pubkey)?;
+
+    if pool_fees.owner.eq(&Pubkey::default()) {
+        return Ok(supply);
+    }
+
+    let (_, performance_fee, management_fee) =
+        pool_fee_rates(program_id, pool_market, pool, fee_schedule, pool_config)?;
+
+    let mut fees = PoolFees::unpack(&pool_fees.data.borrow())?;
+    let total_minted = supply
+        .checked_add(fees.pending_fee_tokens)
+        .ok_or(EverlendError::MathOverflow)?;
+
+    let fee_tokens = fees.accrue(
+        total_amount,
+        total_minted,
+        performance_fee,
+        management_fee,
+        timestamp,
+    )?;
+    if fee_tokens > 0 {
+        msg!("Fee tokens accrued: {}", fee_tokens);
+    }
+
+    PoolFees::pack(fees, *pool_fees.data.borrow_mut())?;
+
+    Ok(total_minted
+        .checked_add(fee_tokens)
+        .ok_or(EverlendError::MathOverflow)?)
+}
@@ -1,7 +1,7 @@
 //! CPI
 
 use solana_program::{
-    account_info::AccountInfo, program::invoke_signed, program_error::ProgramError,
+    account_info::AccountInfo, program::invoke_signed, program_error::ProgramError, pubkey::Pubkey,
 };
 
 use crate::utils::IncomePoolAccounts;
@@ -37,3 +37,50 @@ pub fn deposit<'a, 'b>(
         signers_seeds,
     )
 }
+
+/// Income pools cover the loss from the safety fund
+#[allow(clippy::too_many_arguments)]
+pub fn cover_loss<'a>(
+    income_pool_market: AccountInfo<'a>,
+    income_pool_market_authority: AccountInfo<'a>,
+    safety_fund_ledger: AccountInfo<'a>,
+    safety_fund_token_account: AccountInfo<'a>,
+    general_pool: AccountInfo<'a>,
+    general_pool_borrow_authority: AccountInfo<'a>,
+    depositor: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    borrow_authority: AccountInfo<'a>,
+    clock: AccountInfo<'a>,
+    token_mint: &Pubkey,
+    amount: u64,
+    signers_seeds: &[&[&[u8]]],
+) -> Result<(), ProgramError> {
+    let ix = crate::instruction::cover_loss(
+        &crate::id(),
+        income_pool_market.key,
+        token_mint,
+        general_pool.key,
+        general_pool_borrow_authority.key,
+        depositor.key,
+        destination.key,
+        borrow_authority.key,
+        amount,
+    );
+
+    invoke_signed(
+        &ix,
+        &[
+            income_pool_market,
+            income_pool_market_authority,
+            safety_fund_ledger,
+            safety_fund_token_account,
+            general_pool,
+            general_pool_borrow_authority,
+            depositor,
+            destination,
+            borrow_authority,
+            clock,
+        ],
+        signers_seeds,
+    )
+}
@@ -1,7 +1,11 @@
 //! Instruction types
 
-use crate::{find_pool_program_address, find_safety_fund_token_account_address};
+use crate::{
+    find_pool_program_address, find_safety_fund_ledger_address,
+    find_safety_fund_token_account_address,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
+use everlend_registry::{find_pause_addresses, find_timelock_authority_program_address};
 use everlend_utils::find_program_address;
 use solana_program::{
     instruction::{AccountMeta, Instruction},
@@ -33,6 +37,9 @@ pub enum IncomePoolsInstruction {
     /// [R] Rent sysvar
     /// [R] System program
     /// [R] Token program id
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
     CreatePool,
 
     /// Deposit funds in the pool
@@ -57,7 +64,10 @@ pub enum IncomePoolsInstruction {
     /// [W] Token account
     /// [R] Income pool market authority
     /// [R] Safety fund token account
+    /// [R] General pool market
     /// [R] General pool
+    /// [R] Global pause
+    /// [R] Pool pause
     /// [W] General pool token account
     /// [R] Everlend ULP program id
     /// [R] Token program id
@@ -75,16 +85,107 @@ pub enum IncomePoolsInstruction {
     /// [R] Rent sysvar
     /// [R] System program
     /// [R] Token program id
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
     CreateSafetyPoolTokenAccount,
 
-    /// Update pool market manager
+    /// Propose a new pool market manager, takes effect after the new manager accepts it
+    ///
+    /// Accounts:
+    /// [W] Pool market
+    /// [RS] Manager
+    /// [R] New manager
+    ///
+    ProposeManager,
+
+    /// Accept the pool market manager proposal
     ///
     /// Accounts:
     /// [W] Pool market
-    /// [WS] Old manager
     /// [RS] New manager
+    AcceptManager,
+
+    /// Cancel the pool market manager proposal
+    ///
+    /// Accounts:
+    /// [W] Pool market
+    /// [RS] Manager
+    CancelManagerProposal,
+
+    /// Cover the loss of the depositor borrowing from the general pool with the safety fund,
+    /// recorded in the safety fund ledger. The ledger is created by SetSafetyFundLimit.
+    ///
+    /// Accounts:
+    /// [R] Income pool market
+    /// [R] Income pool market authority
+    /// [W] Safety fund ledger
+    /// [W] Safety fund token account
+    /// [R] General pool
+    /// [R] General pool borrow authority
+    /// [R] Depositor
+    /// [W] Depositor liquidity transit
+    /// [RS] Depositor authority
+    /// [R] Clock sysvar
+    /// [R] Token program id
+    CoverLoss {
+        /// Amount of the loss to cover
+        amount: u64,
+    },
+
+    /// Set the limits of the safety fund drawdowns, creates the safety fund ledger
     ///
-    UpdateManager,
+    /// Accounts:
+    /// [R] Income pool market
+    /// [W] Safety fund ledger
+    /// [R] Token mint
+    /// [WS] Market manager
+    /// [R] Rent sysvar
+    /// [R] System program
+    /// [R] Registry - optional, if signed by the role holder
+    /// [R] Registry role - optional, if signed by the role holder
+    /// [R] Registry timelock - optional, if signed by the role holder
+    SetSafetyFundLimit {
+        /// Maximum amount of a single drawdown
+        max_drawdown_amount: u64,
+        /// Maximum amount drawn within a drawdown period
+        max_period_drawdown_amount: u64,
+        /// Drawdown period in slots
+        drawdown_period: u64,
+    },
+
+    /// Transfer from the safety fund to compensate a loss, recorded in the safety fund ledger.
+    /// Signed by the registry timelock authority, so the drawdown is queued by the timelock
+    /// proposer and can be cancelled before the execution. Transfers to the general pool token
+    /// account or the transit of the depositor borrowing from the general pool.
+    ///
+    /// Accounts:
+    /// [R] Income pool market
+    /// [R] Income pool market authority
+    /// [W] Safety fund ledger
+    /// [W] Safety fund token account
+    /// [R] General pool
+    /// [W] Destination - general pool token account or depositor transit
+    /// [R] Registry
+    /// [RS] Registry timelock authority
+    /// [R] Clock sysvar
+    /// [R] Token program id
+    /// [R] Depositor - optional, if the destination is a depositor transit
+    /// [R] General pool borrow authority - optional, if the destination is a depositor transit
+    DrawdownSafetyFund {
+        /// Amount to transfer
+        amount: u64,
+    },
+
+    /// Migrate income pool market to the layout with pending manager and registry
+    ///
+    /// Accounts:
+    /// [W] Income pool market
+    /// [WS] Market manager
+    /// [R] General pool market
+    /// [R] Rent sysvar
+    /// [R] System program
+    MigratePoolMarket,
 }
 
 /// Creates 'InitPoolMarket' instruction.
@@ -171,12 +272,15 @@ pub fn withdraw(
     income_pool_market: &Pubkey,
     income_pool: &Pubkey,
     income_token_account: &Pubkey,
+    registry: &Pubkey,
+    general_pool_market: &Pubkey,
     general_pool: &Pubkey,
     general_pool_token_account: &Pubkey,
 ) -> Instruction {
     let (pool_market_authority, _) = find_program_address(program_id, income_pool_market);
     let (safety_fund_token_account, _) =
         find_safety_fund_token_account_address(program_id, income_pool_market, token_mint);
+    let (global_pause, pool_pause) = find_pause_addresses(registry, general_pool);
 
     let accounts = vec![
         AccountMeta::new_readonly(*income_pool_market, false),
@@ -184,7 +288,10 @@ pub fn withdraw(
         AccountMeta::new(*income_token_account, false),
         AccountMeta::new_readonly(pool_market_authority, false),
         AccountMeta::new(safety_fund_token_account, false),
+        AccountMeta::new_readonly(*general_pool_market, false),
         AccountMeta::new_readonly(*general_pool, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         AccountMeta::new(*general_pool_token_account, false),
         AccountMeta::new_readonly(everlend_general_pool::id(), false),
         AccountMeta::new_readonly(spl_token::id(), false),
@@ -225,9 +332,8 @@ pub fn create_safety_pool_token_account(
     )
 }
 
-/// Creates 'UpdateManager' instruction.
-#[allow(clippy::too_many_arguments)]
-pub fn update_manager(
+/// Creates 'ProposeManager' instruction.
+pub fn propose_manager(
     program_id: &Pubkey,
     pool_market: &Pubkey,
     manager: &Pubkey,
@@ -235,13 +341,192 @@ pub fn update_manager(
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new(*pool_market, false),
-        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(*new_manager, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &IncomePoolsInstruction::ProposeManager,
+        accounts,
+    )
+}
+
+/// Creates 'AcceptManager' instruction.
+pub fn accept_manager(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    new_manager: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*pool_market, false),
         AccountMeta::new_readonly(*new_manager, true),
     ];
 
     Instruction::new_with_borsh(
         *program_id,
-        &IncomePoolsInstruction::UpdateManager,
+        &IncomePoolsInstruction::AcceptManager,
+        accounts,
+    )
+}
+
+/// Creates 'CancelManagerProposal' instruction.
+pub fn cancel_manager_proposal(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    manager: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*pool_market, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &IncomePoolsInstruction::CancelManagerProposal,
+        accounts,
+    )
+}
+
+/// Creates 'CoverLoss' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn cover_loss(
+    program_id: &Pubkey,
+    income_pool_market: &Pubkey,
+    token_mint: &Pubkey,
+    general_pool: &Pubkey,
+    general_pool_borrow_authority: &Pubkey,
+    depositor: &Pubkey,
+    destination: &Pubkey,
+    borrow_authority: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (income_pool_market_authority, _) = find_program_address(program_id, income_pool_market);
+    let (safety_fund_ledger, _) =
+        find_safety_fund_ledger_address(program_id, income_pool_market, token_mint);
+    let (safety_fund_token_account, _) =
+        find_safety_fund_token_account_address(program_id, income_pool_market, token_mint);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*income_pool_market, false),
+        AccountMeta::new_readonly(income_pool_market_authority, false),
+        AccountMeta::new(safety_fund_ledger, false),
+        AccountMeta::new(safety_fund_token_account, false),
+        AccountMeta::new_readonly(*general_pool, false),
+        AccountMeta::new_readonly(*general_pool_borrow_authority, false),
+        AccountMeta::new_readonly(*depositor, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(*borrow_authority, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &IncomePoolsInstruction::CoverLoss { amount },
+        accounts,
+    )
+}
+
+/// Creates 'SetSafetyFundLimit' instruction.
+pub fn set_safety_fund_limit(
+    program_id: &Pubkey,
+    income_pool_market: &Pubkey,
+    token_mint: &Pubkey,
+    manager: &Pubkey,
+    max_drawdown_amount: u64,
+    max_period_drawdown_amount: u64,
+    drawdown_period: u64,
+) -> Instruction {
+    let (safety_fund_ledger, _) =
+        find_safety_fund_ledger_address(program_id, income_pool_market, token_mint);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*income_pool_market, false),
+        AccountMeta::new(safety_fund_ledger, false),
+        AccountMeta::new_readonly(*token_mint, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &IncomePoolsInstruction::SetSafetyFundLimit {
+            max_drawdown_amount,
+            max_period_drawdown_amount,
+            drawdown_period,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'DrawdownSafetyFund' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn drawdown_safety_fund(
+    program_id: &Pubkey,
+    income_pool_market: &Pubkey,
+    token_mint: &Pubkey,
+    general_pool: &Pubkey,
+    destination: &Pubkey,
+    depositor: Option<(&Pubkey, &Pubkey)>,
+    registry: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (income_pool_market_authority, _) = find_program_address(program_id, income_pool_market);
+    let (safety_fund_ledger, _) =
+        find_safety_fund_ledger_address(program_id, income_pool_market, token_mint);
+    let (safety_fund_token_account, _) =
+        find_safety_fund_token_account_address(program_id, income_pool_market, token_mint);
+    let (timelock_authority, _) =
+        find_timelock_authority_program_address(&everlend_registry::id(), registry);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*income_pool_market, false),
+        AccountMeta::new_readonly(income_pool_market_authority, false),
+        AccountMeta::new(safety_fund_ledger, false),
+        AccountMeta::new(safety_fund_token_account, false),
+        AccountMeta::new_readonly(*general_pool, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(timelock_authority, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    if let Some((depositor, general_pool_borrow_authority)) = depositor {
+        accounts.push(AccountMeta::new_readonly(*depositor, false));
+        accounts.push(AccountMeta::new_readonly(
+            *general_pool_borrow_authority,
+            false,
+        ));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &IncomePoolsInstruction::DrawdownSafetyFund { amount },
+        accounts,
+    )
+}
+
+/// Creates 'MigratePoolMarket' instruction.
+pub fn migrate_pool_market(
+    program_id: &Pubkey,
+    pool_market: &Pubkey,
+    manager: &Pubkey,
+    general_pool_market: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*pool_market, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(*general_pool_market, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &IncomePoolsInstruction::MigratePoolMarket,
         accounts,
     )
 }
@@ -17,6 +17,11 @@ use solana_program::pubkey::Pubkey;
 
 solana_program::declare_id!("incmgBdqLbD6qmxn3Ru7Dbbm1UiAMrvkhwgSdUz5EYX");
 
+/// Depositor program borrowing from the general pool, it depends on this crate
+pub mod depositor {
+    solana_program::declare_id!("DepSR26sqzN67TNf1aZ3VCjTPduzKKqTEY8QQkk3KwEz");
+}
+
 /// Generates pool address
 pub fn find_pool_program_address(
     program_id: &Pubkey,
@@ -48,6 +53,22 @@ pub fn find_safety_fund_token_account_address(
     )
 }
 
+/// Generates safety fund ledger address
+pub fn find_safety_fund_ledger_address(
+    program_id: &Pubkey,
+    pool_market_pubkey: &Pubkey,
+    token_mint_pubkey: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"safety_fund_ledger",
+            &pool_market_pubkey.to_bytes(),
+            &token_mint_pubkey.to_bytes(),
+        ],
+        program_id,
+    )
+}
+
 /// Generate safety fund token account seed
 pub fn safety_fund_token_account_seed() -> String {
     "safety_fund".to_owned()
@@ -1,20 +1,34 @@
 //! Program state processor
 
 use crate::{
-    find_pool_program_address, find_safety_fund_token_account_address,
+    depositor, find_pool_program_address, find_safety_fund_ledger_address,
+    find_safety_fund_token_account_address,
     instruction::IncomePoolsInstruction,
     safety_fund_token_account_seed,
-    state::{IncomePool, IncomePoolMarket, InitIncomePoolMarketParams, InitIncomePoolParams},
+    state::{
+        DeprecatedIncomePoolMarket, Drawdown, IncomePool, IncomePoolMarket,
+        InitIncomePoolMarketParams, InitIncomePoolParams, SafetyFundLedger,
+    },
+    utils::assert_manager_or_role,
 };
 use borsh::BorshDeserialize;
-use everlend_general_pool::state::Pool;
+use everlend_general_pool::{
+    state::{Pool, PoolBorrowAuthority, PoolMarket as GeneralPoolMarket},
+    utils::pause_flags,
+};
+use everlend_registry::{
+    find_timelock_authority_program_address,
+    state::{Role, RoleAccounts},
+};
 use everlend_utils::{
     assert_account_key, assert_non_zero_amount, assert_owned_by, assert_rent_exempt, assert_signer,
-    assert_uninitialized, cpi, find_program_address, math, EverlendError,
+    assert_uninitialized, cpi, cpi::system::realloc_with_rent, find_program_address, math,
+    EverlendError,
 };
 
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
@@ -48,6 +62,9 @@ impl Processor {
         // TODO: replace to getting id from config program
         assert_owned_by(general_pool_market_info, &everlend_general_pool::id())?;
 
+        let general_pool_market =
+            GeneralPoolMarket::unpack(&general_pool_market_info.data.borrow())?;
+
         // Get pool market state
         let mut pool_market = IncomePoolMarket::unpack_unchecked(&pool_market_info.data.borrow())?;
         assert_uninitialized(&pool_market)?;
@@ -55,6 +72,7 @@ impl Processor {
         pool_market.init(InitIncomePoolMarketParams {
             manager: *manager_info.key,
             general_pool_market: *general_pool_market_info.key,
+            registry: general_pool_market.registry,
         });
 
         IncomePoolMarket::pack(pool_market, *pool_market_info.data.borrow_mut())?;
@@ -75,6 +93,7 @@ impl Processor {
         let rent = &Rent::from_account_info(rent_info)?;
         let _system_program_info = next_account_info(account_info_iter)?;
         let _token_program_info = next_account_info(account_info_iter)?;
+        let role_accounts = RoleAccounts::next_optional(&mut account_info_iter.enumerate())?;
 
         assert_signer(manager_info)?;
 
@@ -84,8 +103,13 @@ impl Processor {
         // Get pool market state
         let pool_market = IncomePoolMarket::unpack(&pool_market_info.data.borrow())?;
 
-        // Check manager
-        assert_account_key(manager_info, &pool_market.manager)?;
+        // Check manager or role
+        assert_manager_or_role(
+            &pool_market,
+            manager_info,
+            role_accounts.as_ref(),
+            Role::Operator,
+        )?;
 
         // Check pool market authority pubkey
         {
@@ -179,7 +203,10 @@ impl Processor {
         let income_pool_token_account_info = next_account_info(account_info_iter)?;
         let income_pool_market_authority_info = next_account_info(account_info_iter)?;
         let safety_fund_token_account_info = next_account_info(account_info_iter)?;
+        let general_pool_market_info = next_account_info(account_info_iter)?;
         let general_pool_info = next_account_info(account_info_iter)?;
+        let global_pause_info = next_account_info(account_info_iter)?;
+        let pool_pause_info = next_account_info(account_info_iter)?;
         let general_pool_token_account_info = next_account_info(account_info_iter)?;
         let _everlend_general_pool_info = next_account_info(account_info_iter)?;
         let _token_program_info = next_account_info(account_info_iter)?;
@@ -187,9 +214,14 @@ impl Processor {
         // Check programs
         assert_owned_by(income_pool_market_info, program_id)?;
         assert_owned_by(income_pool_info, program_id)?;
+        assert_owned_by(general_pool_market_info, &everlend_general_pool::id())?;
         assert_owned_by(general_pool_info, &everlend_general_pool::id())?;
 
         let income_pool_market = IncomePoolMarket::unpack(&income_pool_market_info.data.borrow())?;
+        assert_account_key(
+            general_pool_market_info,
+            &income_pool_market.general_pool_market,
+        )?;
 
         let income_pool = IncomePool::unpack(&income_pool_info.data.borrow())?;
 
@@ -205,6 +237,16 @@ impl Processor {
         }
         assert_account_key(general_pool_token_account_info, &general_pool.token_account)?;
 
+        let flags = pause_flags(
+            general_pool_market_info,
+            general_pool_info,
+            global_pause_info,
+            pool_pause_info,
+        )?;
+        if flags.withdraw {
+            return Err(EverlendError::OperationPaused.into());
+        }
+
         let (safety_fund_token_account, _) = find_safety_fund_token_account_address(
             program_id,
             income_pool_market_info.key,
@@ -266,6 +308,7 @@ impl Processor {
         let rent = &Rent::from_account_info(rent_info)?;
         let _system_program_info = next_account_info(account_info_iter)?;
         let _token_program_info = next_account_info(account_info_iter)?;
+        let role_accounts = RoleAccounts::next_optional(&mut account_info_iter.enumerate())?;
 
         assert_signer(manager_info)?;
 
@@ -275,7 +318,12 @@ impl Processor {
         // Get pool market state
         let income_pool_market = IncomePoolMarket::unpack(&income_pool_market_info.data.borrow())?;
 
-        assert_account_key(manager_info, &income_pool_market.manager)?;
+        assert_manager_or_role(
+            &income_pool_market,
+            manager_info,
+            role_accounts.as_ref(),
+            Role::Operator,
+        )?;
 
         // Check pool account
         let (income_pool_pubkey, _) =
@@ -320,22 +368,416 @@ impl Processor {
         Ok(())
     }
 
-    /// Process UpdateManager instruction
-    pub fn update_manager(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    /// Process ProposeManager instruction
+    pub fn propose_manager(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let pool_market_info = next_account_info(account_info_iter)?;
         let manager_info = next_account_info(account_info_iter)?;
         let new_manager_info = next_account_info(account_info_iter)?;
 
         assert_signer(manager_info)?;
+
+        assert_owned_by(pool_market_info, program_id)?;
+
+        let mut pool_market = IncomePoolMarket::unpack(&pool_market_info.data.borrow())?;
+        assert_account_key(manager_info, &pool_market.manager)?;
+
+        pool_market.pending_manager = *new_manager_info.key;
+
+        IncomePoolMarket::pack(pool_market, *pool_market_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process AcceptManager instruction
+    pub fn accept_manager(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let pool_market_info = next_account_info(account_info_iter)?;
+        let new_manager_info = next_account_info(account_info_iter)?;
+
         assert_signer(new_manager_info)?;
 
         assert_owned_by(pool_market_info, program_id)?;
 
+        let mut pool_market = IncomePoolMarket::unpack(&pool_market_info.data.borrow())?;
+        assert_account_key(new_manager_info, &pool_market.pending_manager)?;
+
+        pool_market.manager = pool_market.pending_manager;
+        pool_market.pending_manager = Pubkey::default();
+
+        IncomePoolMarket::pack(pool_market, *pool_market_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process CancelManagerProposal instruction
+    pub fn cancel_manager_proposal(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let pool_market_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+
+        assert_signer(manager_info)?;
+
+        assert_owned_by(pool_market_info, program_id)?;
+
         let mut pool_market = IncomePoolMarket::unpack(&pool_market_info.data.borrow())?;
         assert_account_key(manager_info, &pool_market.manager)?;
 
-        pool_market.manager = *new_manager_info.key;
+        pool_market.pending_manager = Pubkey::default();
+
+        IncomePoolMarket::pack(pool_market, *pool_market_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process CoverLoss instruction
+    pub fn cover_loss(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let income_pool_market_info = next_account_info(account_info_iter)?;
+        let income_pool_market_authority_info = next_account_info(account_info_iter)?;
+        let safety_fund_ledger_info = next_account_info(account_info_iter)?;
+        let safety_fund_token_account_info = next_account_info(account_info_iter)?;
+        let general_pool_info = next_account_info(account_info_iter)?;
+        let general_pool_borrow_authority_info = next_account_info(account_info_iter)?;
+        let depositor_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let borrow_authority_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = Clock::from_account_info(clock_info)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+
+        assert_signer(borrow_authority_info)?;
+
+        // Check programs
+        assert_owned_by(income_pool_market_info, program_id)?;
+        assert_owned_by(safety_fund_ledger_info, program_id)?;
+        assert_owned_by(general_pool_info, &everlend_general_pool::id())?;
+        assert_owned_by(
+            general_pool_borrow_authority_info,
+            &everlend_general_pool::id(),
+        )?;
+        assert_owned_by(depositor_info, &depositor::id())?;
+
+        let income_pool_market = IncomePoolMarket::unpack(&income_pool_market_info.data.borrow())?;
+        let general_pool = Pool::unpack(&general_pool_info.data.borrow())?;
+
+        // Check general pool
+        if general_pool.pool_market != income_pool_market.general_pool_market {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Only the borrower of the general pool covers its loss
+        let general_pool_borrow_authority =
+            PoolBorrowAuthority::unpack_any(&general_pool_borrow_authority_info.data.borrow())?;
+        assert_account_key(general_pool_info, &general_pool_borrow_authority.pool)?;
+        assert_account_key(
+            borrow_authority_info,
+            &general_pool_borrow_authority.borrow_authority,
+        )?;
+
+        // Borrower is the depositor authority, the loss goes back to its liquidity transit
+        let (depositor_authority, _) = find_program_address(&depositor::id(), depositor_info.key);
+        assert_account_key(borrow_authority_info, &depositor_authority)?;
+        let (liquidity_transit, _) = Pubkey::find_program_address(
+            &[
+                b"",
+                &depositor_info.key.to_bytes(),
+                &general_pool.token_mint.to_bytes(),
+            ],
+            &depositor::id(),
+        );
+        assert_account_key(destination_info, &liquidity_transit)?;
+
+        if amount > general_pool_borrow_authority.amount_borrowed {
+            msg!("Loss is greater than the borrowed amount");
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut safety_fund_ledger =
+            SafetyFundLedger::unpack(&safety_fund_ledger_info.data.borrow())?;
+        assert_account_key(
+            income_pool_market_info,
+            &safety_fund_ledger.income_pool_market,
+        )?;
+        if safety_fund_ledger.token_mint != general_pool.token_mint {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let (safety_fund_token_account, _) = find_safety_fund_token_account_address(
+            program_id,
+            income_pool_market_info.key,
+            &general_pool.token_mint,
+        );
+        assert_account_key(safety_fund_token_account_info, &safety_fund_token_account)?;
+
+        assert_non_zero_amount(amount)?;
+
+        safety_fund_ledger.add_loss_cover(Drawdown {
+            amount,
+            destination: *destination_info.key,
+            authority: *borrow_authority_info.key,
+            slot: clock.slot,
+        })?;
+
+        let (_, bump_seed) = find_program_address(program_id, income_pool_market_info.key);
+        let signers_seeds = &[&income_pool_market_info.key.to_bytes()[..32], &[bump_seed]];
+
+        // Transfer from safety fund to destination
+        cpi::spl_token::transfer(
+            safety_fund_token_account_info.clone(),
+            destination_info.clone(),
+            income_pool_market_authority_info.clone(),
+            amount,
+            &[signers_seeds],
+        )?;
+
+        msg!("Loss covered by safety fund: {}", amount);
+
+        SafetyFundLedger::pack(
+            safety_fund_ledger,
+            *safety_fund_ledger_info.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Process SetSafetyFundLimit instruction
+    pub fn set_safety_fund_limit(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        max_drawdown_amount: u64,
+        max_period_drawdown_amount: u64,
+        drawdown_period: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let income_pool_market_info = next_account_info(account_info_iter)?;
+        let safety_fund_ledger_info = next_account_info(account_info_iter)?;
+        let token_mint_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+        let _system_program_info = next_account_info(account_info_iter)?;
+        let role_accounts = RoleAccounts::next_optional(&mut account_info_iter.enumerate())?;
+
+        assert_signer(manager_info)?;
+
+        assert_owned_by(income_pool_market_info, program_id)?;
+        assert_owned_by(token_mint_info, &spl_token::id())?;
+
+        if drawdown_period == 0 || max_period_drawdown_amount < max_drawdown_amount {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let income_pool_market = IncomePoolMarket::unpack(&income_pool_market_info.data.borrow())?;
+
+        assert_manager_or_role(
+            &income_pool_market,
+            manager_info,
+            role_accounts.as_ref(),
+            Role::Risk,
+        )?;
+
+        let (safety_fund_ledger_pubkey, bump_seed) = find_safety_fund_ledger_address(
+            program_id,
+            income_pool_market_info.key,
+            token_mint_info.key,
+        );
+        assert_account_key(safety_fund_ledger_info, &safety_fund_ledger_pubkey)?;
+
+        let mut safety_fund_ledger = match safety_fund_ledger_info.lamports() {
+            // Create safety fund ledger
+            0 => {
+                let signers_seeds = &[
+                    b"safety_fund_ledger".as_ref(),
+                    &income_pool_market_info.key.to_bytes()[..32],
+                    &token_mint_info.key.to_bytes()[..32],
+                    &[bump_seed],
+                ];
+
+                cpi::system::create_account::<SafetyFundLedger>(
+                    program_id,
+                    manager_info.clone(),
+                    safety_fund_ledger_info.clone(),
+                    &[signers_seeds],
+                    rent,
+                )?;
+
+                SafetyFundLedger::init(*income_pool_market_info.key, *token_mint_info.key)
+            }
+            _ => {
+                assert_owned_by(safety_fund_ledger_info, program_id)?;
+                SafetyFundLedger::unpack(&safety_fund_ledger_info.data.borrow())?
+            }
+        };
+
+        safety_fund_ledger.max_drawdown_amount = max_drawdown_amount;
+        safety_fund_ledger.max_period_drawdown_amount = max_period_drawdown_amount;
+        safety_fund_ledger.drawdown_period = drawdown_period;
+
+        SafetyFundLedger::pack(
+            safety_fund_ledger,
+            *safety_fund_ledger_info.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Process DrawdownSafetyFund instruction
+    pub fn drawdown_safety_fund(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let income_pool_market_info = next_account_info(account_info_iter)?;
+        let income_pool_market_authority_info = next_account_info(account_info_iter)?;
+        let safety_fund_ledger_info = next_account_info(account_info_iter)?;
+        let safety_fund_token_account_info = next_account_info(account_info_iter)?;
+        let general_pool_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let registry_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = Clock::from_account_info(clock_info)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+
+        assert_signer(authority_info)?;
+
+        // Check programs
+        assert_owned_by(income_pool_market_info, program_id)?;
+        assert_owned_by(safety_fund_ledger_info, program_id)?;
+        assert_owned_by(general_pool_info, &everlend_general_pool::id())?;
+        assert_owned_by(registry_info, &everlend_registry::id())?;
+
+        let income_pool_market = IncomePoolMarket::unpack(&income_pool_market_info.data.borrow())?;
+        let mut safety_fund_ledger =
+            SafetyFundLedger::unpack(&safety_fund_ledger_info.data.borrow())?;
+
+        // Check safety fund accounts
+        assert_account_key(
+            income_pool_market_info,
+            &safety_fund_ledger.income_pool_market,
+        )?;
+        let (safety_fund_token_account, _) = find_safety_fund_token_account_address(
+            program_id,
+            income_pool_market_info.key,
+            &safety_fund_ledger.token_mint,
+        );
+        assert_account_key(safety_fund_token_account_info, &safety_fund_token_account)?;
+
+        let general_pool = Pool::unpack(&general_pool_info.data.borrow())?;
+
+        // Check general pool
+        if general_pool.pool_market != income_pool_market.general_pool_market
+            || general_pool.token_mint != safety_fund_ledger.token_mint
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Drawdowns are executed by the registry timelock, so they can be cancelled while queued
+        assert_account_key(registry_info, &income_pool_market.registry)?;
+        let (timelock_authority, _) =
+            find_timelock_authority_program_address(&everlend_registry::id(), registry_info.key);
+        assert_account_key(authority_info, &timelock_authority)?;
+
+        // Transfer to the general pool or the transit of the general pool borrower
+        if destination_info.key != &general_pool.token_account {
+            let depositor_info = next_account_info(account_info_iter)?;
+            let general_pool_borrow_authority_info = next_account_info(account_info_iter)?;
+
+            assert_owned_by(depositor_info, &depositor::id())?;
+            assert_owned_by(
+                general_pool_borrow_authority_info,
+                &everlend_general_pool::id(),
+            )?;
+
+            let general_pool_borrow_authority =
+                PoolBorrowAuthority::unpack_any(&general_pool_borrow_authority_info.data.borrow())?;
+            assert_account_key(general_pool_info, &general_pool_borrow_authority.pool)?;
+
+            let (depositor_authority, _) =
+                find_program_address(&depositor::id(), depositor_info.key);
+            if general_pool_borrow_authority.borrow_authority != depositor_authority {
+                return Err(ProgramError::InvalidArgument);
+            }
+
+            let find_transit = |seed: &str| {
+                Pubkey::find_program_address(
+                    &[
+                        seed.as_bytes(),
+                        &depositor_info.key.to_bytes(),
+                        &general_pool.token_mint.to_bytes(),
+                    ],
+                    &depositor::id(),
+                )
+                .0
+            };
+            if destination_info.key != &find_transit("")
+                && destination_info.key != &find_transit("reserve")
+            {
+                return Err(ProgramError::InvalidArgument);
+            }
+        }
+
+        assert_non_zero_amount(amount)?;
+
+        safety_fund_ledger.add_drawdown(Drawdown {
+            amount,
+            destination: *destination_info.key,
+            authority: *authority_info.key,
+            slot: clock.slot,
+        })?;
+
+        let (_, bump_seed) = find_program_address(program_id, income_pool_market_info.key);
+        let signers_seeds = &[&income_pool_market_info.key.to_bytes()[..32], &[bump_seed]];
+
+        // Transfer from safety fund to destination
+        cpi::spl_token::transfer(
+            safety_fund_token_account_info.clone(),
+            destination_info.clone(),
+            income_pool_market_authority_info.clone(),
+            amount,
+            &[signers_seeds],
+        )?;
+
+        msg!(
+            "Safety fund drawdown: {} total: {}",
+            amount,
+            safety_fund_ledger.total_drawdown_amount
+        );
+
+        SafetyFundLedger::pack(
+            safety_fund_ledger,
+            *safety_fund_ledger_info.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Process MigratePoolMarket instruction
+    pub fn migrate_pool_market(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let pool_market_info = next_account_info(account_info_iter)?;
+        let manager_info = next_account_info(account_info_iter)?;
+        let general_pool_market_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+        let _system_program_info = next_account_info(account_info_iter)?;
+
+        assert_signer(manager_info)?;
+        assert_owned_by(pool_market_info, program_id)?;
+        assert_owned_by(general_pool_market_info, &everlend_general_pool::id())?;
+
+        let mut pool_market: IncomePoolMarket =
+            DeprecatedIncomePoolMarket::unpack(&pool_market_info.data.borrow())?.into();
+        assert_account_key(manager_info, &pool_market.manager)?;
+        assert_account_key(general_pool_market_info, &pool_market.general_pool_market)?;
+
+        let general_pool_market =
+            GeneralPoolMarket::unpack(&general_pool_market_info.data.borrow())?;
+        pool_market.registry = general_pool_market.registry;
+
+        realloc_with_rent(pool_market_info, manager_info, rent, IncomePoolMarket::LEN)?;
 
         IncomePoolMarket::pack(pool_market, *pool_market_info.data.borrow_mut())?;
 
@@ -376,9 +818,49 @@ impl Processor {
                 Self::create_safety_fund_token_account(program_id, accounts)
             }
 
-            IncomePoolsInstruction::UpdateManager => {
-                msg!("IncomePoolsInstruction: UpdateManager");
-                Self::update_manager(program_id, accounts)
+            IncomePoolsInstruction::ProposeManager => {
+                msg!("IncomePoolsInstruction: ProposeManager");
+                Self::propose_manager(program_id, accounts)
+            }
+
+            IncomePoolsInstruction::AcceptManager => {
+                msg!("IncomePoolsInstruction: AcceptManager");
+                Self::accept_manager(program_id, accounts)
+            }
+
+            IncomePoolsInstruction::CancelManagerProposal => {
+                msg!("IncomePoolsInstruction: CancelManagerProposal");
+                Self::cancel_manager_proposal(program_id, accounts)
+            }
+
+            IncomePoolsInstruction::CoverLoss { amount } => {
+                msg!("IncomePoolsInstruction: CoverLoss");
+                Self::cover_loss(program_id, accounts, amount)
+            }
+
+            IncomePoolsInstruction::SetSafetyFundLimit {
+                max_drawdown_amount,
+                max_period_drawdown_amount,
+                drawdown_period,
+            } => {
+                msg!("IncomePoolsInstruction: SetSafetyFundLimit");
+                Self::set_safety_fund_limit(
+                    program_id,
+                    accounts,
+                    max_drawdown_amount,
+                    max_period_drawdown_amount,
+                    drawdown_period,
+                )
+            }
+
+            IncomePoolsInstruction::DrawdownSafetyFund { amount } => {
+                msg!("IncomePoolsInstruction: DrawdownSafetyFund");
+                Self::drawdown_safety_fund(program_id, accounts, amount)
+            }
+
+            IncomePoolsInstruction::MigratePoolMarket => {
+                msg!("IncomePoolsInstruction: MigratePoolMarket");
+                Self::migrate_pool_market(program_id, accounts)
             }
         }
     }
@@ -9,6 +9,8 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
+pub use deprecated::DeprecatedIncomePoolMarket;
+
 /// Income pool market
 #[repr(C)]
 #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
@@ -19,6 +21,10 @@ pub struct IncomePoolMarket {
     pub manager: Pubkey,
     /// General pool market
     pub general_pool_market: Pubkey,
+    /// Proposed manager, takes over after accepting
+    pub pending_manager: Pubkey,
+    /// Registry granting roles of the market, the registry of the general pool market
+    pub registry: Pubkey,
 }
 
 impl IncomePoolMarket {
@@ -27,6 +33,7 @@ impl IncomePoolMarket {
         self.account_type = AccountType::IncomePoolMarket;
         self.manager = params.manager;
         self.general_pool_market = params.general_pool_market;
+        self.registry = params.registry;
     }
 }
 
@@ -36,12 +43,16 @@ pub struct InitIncomePoolMarketParams {
     pub manager: Pubkey,
     /// General pool market
     pub general_pool_market: Pubkey,
+    /// Registry
+    pub registry: Pubkey,
+    /// Registry
+    pub registry: Pubkey,
 }
 
 impl Sealed for IncomePoolMarket {}
 impl Pack for IncomePoolMarket {
-    // 1 + 32 + 32
-    const LEN: usize = 65;
+    // 1 + 32 + 32 + 32 + 32
+    const LEN: usize = 129;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -68,3 +79,55 @@ impl Uninitialized for IncomePoolMarket {
         self.account_type == AccountType::default()
     }
 }
+
+mod deprecated {
+    use super::*;
+
+    /// Income pool market without pending manager and registry
+    #[repr(C)]
+    #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+    pub struct DeprecatedIncomePoolMarket {
+        /// Account type - IncomePoolMarket
+        pub account_type: AccountType,
+        /// Market manager
+        pub manager: Pubkey,
+        /// General pool market
+        pub general_pool_market: Pubkey,
+    }
+
+    impl Sealed for DeprecatedIncomePoolMarket {}
+    impl Pack for DeprecatedIncomePoolMarket {
+        // 1 + 32 + 32
+        const LEN: usize = 65;
+
+        fn pack_into_slice(&self, dst: &mut [u8]) {
+            let mut slice = dst;
+            self.serialize(&mut slice).unwrap()
+        }
+
+        fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+            Self::try_from_slice(src).map_err(|_| {
+                msg!("Failed to deserialize");
+                ProgramError::InvalidAccountData
+            })
+        }
+    }
+
+    impl IsInitialized for DeprecatedIncomePoolMarket {
+        fn is_initialized(&self) -> bool {
+            self.account_type == AccountType::IncomePoolMarket
+        }
+    }
+
+    impl From<DeprecatedIncomePoolMarket> for IncomePoolMarket {
+        fn from(deprecated: DeprecatedIncomePoolMarket) -> Self {
+            IncomePoolMarket {
+                account_type: deprecated.account_type,
+                manager: deprecated.manager,
+                general_pool_market: deprecated.general_pool_market,
+                pending_manager: Pubkey::default(),
+                registry: Pubkey::default(),
+            }
+        }
+    }
+}
@@ -3,9 +3,11 @@ use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 
 mod income_pool;
 mod income_pool_market;
+mod safety_fund_ledger;
 
 pub use income_pool::*;
 pub use income_pool_market::*;
+pub use safety_fund_ledger::*;
 
 /// Enum representing the account type managed by the program
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -16,6 +18,8 @@ pub enum AccountType {
     IncomePoolMarket,
     /// Pool
     IncomePool,
+    /// Safety fund ledger
+    SafetyFundLedger,
 }
 
 impl Default for AccountType {
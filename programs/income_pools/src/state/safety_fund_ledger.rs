@@ -0,0 +1,265 @@
+//! Safety fund ledger state definitions
+
+use super::*;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_utils::EverlendError;
+use solana_program::{
+    clock::Slot,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Number of the latest drawdowns kept in the ledger
+pub const MAX_DRAWDOWNS: usize = 16;
+
+/// Safety fund drawdown
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default, Clone, Copy)]
+pub struct Drawdown {
+    /// Amount transferred from the safety fund
+    pub amount: u64,
+    /// Destination token account
+    pub destination: Pubkey,
+    /// Registry timelock authority or borrower signed the drawdown
+    pub authority: Pubkey,
+    /// Slot of the drawdown
+    pub slot: Slot,
+}
+
+impl Drawdown {
+    /// LEN
+    pub const LEN: usize = 8 + 32 + 32 + 8;
+}
+
+/// Ledger of the safety fund drawdowns
+#[repr(C)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct SafetyFundLedger {
+    /// Account type - SafetyFundLedger
+    pub account_type: AccountType,
+    /// Income pool market
+    pub income_pool_market: Pubkey,
+    /// Token mint
+    pub token_mint: Pubkey,
+    /// Maximum amount of a single drawdown
+    pub max_drawdown_amount: u64,
+    /// Maximum amount drawn within a drawdown period
+    pub max_period_drawdown_amount: u64,
+    /// Drawdown period in slots
+    pub drawdown_period: Slot,
+    /// Slot of the first drawdown in the current period
+    pub period_started_at: Slot,
+    /// Amount drawn within the current period
+    pub period_drawdown_amount: u64,
+    /// Total amount drawn from the safety fund
+    pub total_drawdown_amount: u64,
+    /// Number of drawdowns
+    pub drawdowns_count: u64,
+    /// Latest drawdowns, the oldest one is replaced when the ledger is full
+    pub drawdowns: Vec<Drawdown>,
+}
+
+impl SafetyFundLedger {
+    /// Initialize a safety fund ledger
+    pub fn init(income_pool_market: Pubkey, token_mint: Pubkey) -> SafetyFundLedger {
+        SafetyFundLedger {
+            account_type: AccountType::SafetyFundLedger,
+            income_pool_market,
+            token_mint,
+            ..Default::default()
+        }
+    }
+
+    /// Record the drawdown checking the single and the period limits
+    pub fn add_drawdown(&mut self, drawdown: Drawdown) -> Result<(), ProgramError> {
+        if drawdown.amount > self.max_drawdown_amount {
+            return Err(EverlendError::SafetyFundDrawdownLimitExceeded.into());
+        }
+
+        // Start a new period
+        let (period_started_at, period_drawdown_amount) =
+            if drawdown.slot >= self.period_started_at.saturating_add(self.drawdown_period) {
+                (drawdown.slot, 0)
+            } else {
+                (self.period_started_at, self.period_drawdown_amount)
+            };
+
+        let period_drawdown_amount = period_drawdown_amount
+            .checked_add(drawdown.amount)
+            .ok_or(EverlendError::MathOverflow)?;
+        if period_drawdown_amount > self.max_period_drawdown_amount {
+            return Err(EverlendError::SafetyFundDrawdownLimitExceeded.into());
+        }
+
+        self.period_started_at = period_started_at;
+        self.period_drawdown_amount = period_drawdown_amount;
+
+        self.record(drawdown)
+    }
+
+    /// Record the loss cover, it's bounded by the realized loss instead of the limits
+    pub fn add_loss_cover(&mut self, drawdown: Drawdown) -> Result<(), ProgramError> {
+        self.record(drawdown)
+    }
+
+    fn record(&mut self, drawdown: Drawdown) -> Result<(), ProgramError> {
+        self.total_drawdown_amount = self
+            .total_drawdown_amount
+            .checked_add(drawdown.amount)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        let index = (self.drawdowns_count % MAX_DRAWDOWNS as u64) as usize;
+        if index < self.drawdowns.len() {
+            self.drawdowns[index] = drawdown;
+        } else {
+            self.drawdowns.push(drawdown);
+        }
+
+        self.drawdowns_count = self
+            .drawdowns_count
+            .checked_add(1)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+impl Sealed for SafetyFundLedger {}
+impl Pack for SafetyFundLedger {
+    // 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + (4 + 16 * 80)
+    const LEN: usize = 1405;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for SafetyFundLedger {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::SafetyFundLedger
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packing() {
+        let pk = Pubkey::new_unique();
+        let mut ledger = SafetyFundLedger::init(pk, pk);
+        ledger.max_drawdown_amount = 100;
+        ledger.max_period_drawdown_amount = 100;
+        for _ in 0..MAX_DRAWDOWNS {
+            ledger
+                .add_drawdown(Drawdown {
+                    amount: 1,
+                    destination: pk,
+                    authority: pk,
+                    slot: 1,
+                })
+                .unwrap();
+        }
+
+        let mut expected = vec![0; SafetyFundLedger::LEN];
+        SafetyFundLedger::pack(ledger, &mut expected).unwrap();
+
+        let unpacked = SafetyFundLedger::unpack(&expected).unwrap();
+        assert_eq!(unpacked.drawdowns.len(), MAX_DRAWDOWNS);
+        assert_eq!(unpacked.total_drawdown_amount, MAX_DRAWDOWNS as u64);
+    }
+
+    #[test]
+    fn drawdowns() {
+        let pk = Pubkey::new_unique();
+        let mut ledger = SafetyFundLedger::init(pk, pk);
+        let drawdown = |amount: u64, slot: Slot| Drawdown {
+            amount,
+            destination: pk,
+            authority: pk,
+            slot,
+        };
+
+        // Drawdowns are disabled until the limit is set
+        assert_eq!(
+            ledger.add_drawdown(drawdown(1, 0)),
+            Err(EverlendError::SafetyFundDrawdownLimitExceeded.into())
+        );
+
+        ledger.max_drawdown_amount = 10;
+        ledger.max_period_drawdown_amount = 10;
+        ledger.drawdown_period = 1;
+        for slot in 0..(MAX_DRAWDOWNS as u64 + 2) {
+            ledger.add_drawdown(drawdown(10, slot)).unwrap();
+        }
+        assert_eq!(
+            ledger.add_drawdown(drawdown(11, 0)),
+            Err(EverlendError::SafetyFundDrawdownLimitExceeded.into())
+        );
+
+        assert_eq!(ledger.drawdowns_count, MAX_DRAWDOWNS as u64 + 2);
+        assert_eq!(
+            ledger.total_drawdown_amount,
+            (MAX_DRAWDOWNS as u64 + 2) * 10
+        );
+        assert_eq!(ledger.drawdowns.len(), MAX_DRAWDOWNS);
+        // The oldest drawdowns are replaced
+        assert_eq!(ledger.drawdowns[0].slot, MAX_DRAWDOWNS as u64);
+        assert_eq!(ledger.drawdowns[1].slot, MAX_DRAWDOWNS as u64 + 1);
+        assert_eq!(ledger.drawdowns[2].slot, 2);
+
+        // Loss cover is recorded over the limit
+        ledger.add_loss_cover(drawdown(11, 100)).unwrap();
+        assert_eq!(ledger.drawdowns[2].amount, 11);
+        assert_eq!(
+            ledger.total_drawdown_amount,
+            (MAX_DRAWDOWNS as u64 + 2) * 10 + 11
+        );
+        // Loss cover isn't counted in the period
+        assert_eq!(ledger.period_drawdown_amount, 10);
+    }
+
+    #[test]
+    fn period_drawdowns() {
+        let pk = Pubkey::new_unique();
+        let mut ledger = SafetyFundLedger::init(pk, pk);
+        ledger.max_drawdown_amount = 10;
+        ledger.max_period_drawdown_amount = 25;
+        ledger.drawdown_period = 100;
+        let drawdown = |amount: u64, slot: Slot| Drawdown {
+            amount,
+            destination: pk,
+            authority: pk,
+            slot,
+        };
+
+        ledger.add_drawdown(drawdown(10, 100)).unwrap();
+        ledger.add_drawdown(drawdown(10, 150)).unwrap();
+        assert_eq!(
+            ledger.add_drawdown(drawdown(10, 199)),
+            Err(EverlendError::SafetyFundDrawdownLimitExceeded.into())
+        );
+        assert_eq!(ledger.period_started_at, 100);
+        assert_eq!(ledger.period_drawdown_amount, 20);
+
+        ledger.add_drawdown(drawdown(5, 199)).unwrap();
+        assert_eq!(ledger.period_drawdown_amount, 25);
+
+        // The next period starts from the first drawdown after the previous one ends
+        ledger.add_drawdown(drawdown(10, 250)).unwrap();
+        assert_eq!(ledger.period_started_at, 250);
+        assert_eq!(ledger.period_drawdown_amount, 10);
+        assert_eq!(ledger.total_drawdown_amount, 35);
+    }
+}
@@ -1,5 +1,8 @@
 //! Utils
-use solana_program::account_info::AccountInfo;
+use crate::state::IncomePoolMarket;
+use everlend_registry::state::{Role, RoleAccounts};
+use everlend_utils::assert_account_key;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
 
 /// income pool accounts
 #[derive(Clone, Copy)]
@@ -11,3 +14,23 @@ pub struct IncomePoolAccounts<'a, 'b> {
     /// token account
     pub token_account: &'a AccountInfo<'b>,
 }
+
+/// Check the signer is the income pool market manager or is granted the role in the registry
+/// of the income pool market
+pub fn assert_manager_or_role(
+    pool_market: &IncomePoolMarket,
+    signer: &AccountInfo,
+    role_accounts: Option<&RoleAccounts>,
+    role: Role,
+) -> ProgramResult {
+    if let Some(role_accounts) = role_accounts {
+        assert_account_key(role_accounts.registry, &pool_market.registry)?;
+    }
+
+    everlend_registry::state::assert_manager_or_role(
+        &pool_market.manager,
+        signer,
+        role_accounts,
+        role,
+    )
+}
@@ -1,7 +1,12 @@
 //! Instruction states definitions.
-use crate::{find_token_oracle_program_address, state::DistributionArray};
+use crate::{
+    find_distribution_submissions_program_address, find_oracle_signers_program_address,
+    find_token_oracle_limits_program_address, find_token_oracle_program_address,
+    state::DistributionArray,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
+    clock::Slot,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     system_program, sysvar,
@@ -15,15 +20,16 @@ pub enum LiquidityOracleInstruction {
     /// Accounts:
     /// [W] Liquidity oracle - off-chain created account.
     /// [RS] Authority - liquidity oracle authority to update state.
+    /// [R] Registry - grants roles of the oracle.
     InitLiquidityOracle,
 
-    /// Updates liquidity oracle.
+    /// Proposes a new liquidity oracle authority, it takes effect after acceptance.
     ///
     /// Accounts:
     /// [W] Liquidity oracle - account.
-    /// [R] Update Authority
+    /// [R] New authority
     /// [RS] Authority - liquidity oracle authority to update state.
-    UpdateLiquidityOracleAuthority,
+    ProposeLiquidityOracleAuthority,
 
     /// Initializes a new token oracle account.
     ///
@@ -35,6 +41,9 @@ pub enum LiquidityOracleInstruction {
     /// [R]  Clock sysvar.
     /// [R]  Rent sysvar
     /// [R]  System program id
+    /// [R]  Registry - optional, if signed by the role holder
+    /// [R]  Registry role - optional, if signed by the role holder
+    /// [R]  Registry timelock - optional, if signed by the role holder
     CreateTokenOracle { value: DistributionArray },
 
     /// Updates token distribution account.
@@ -45,6 +54,8 @@ pub enum LiquidityOracleInstruction {
     /// [W] TokenOracle - to update state
     /// [RS] Authority - liquidity oracle authority.
     /// [R] Clock sysvar.
+    /// [R] Oracle signers - rejects update when quorum is enabled.
+    /// [R] Token oracle limits
     UpdateLiquidityDistribution { value: DistributionArray },
 
     /// Updates money market reserve rates
@@ -57,7 +68,7 @@ pub enum LiquidityOracleInstruction {
     /// [R] Clock sysvar.
     UpdateReserveRates { value: DistributionArray },
 
-    /// Migrate token distribution account.
+    /// Migrate token oracle account to the variable-length distribution layout.
     ///
     /// Accounts:
     /// [R] Liquidity oracle - off-chain created account.
@@ -67,6 +78,89 @@ pub enum LiquidityOracleInstruction {
     /// [R]  Rent sysvar
     /// [R]  System program id
     Migrate,
+
+    /// Sets oracle signers allowed to submit distributions and the quorum.
+    /// Once the quorum is enabled the change is delayed until 'ApplyOracleSigners'.
+    ///
+    /// Accounts:
+    /// [R]  Liquidity oracle - off-chain created account.
+    /// [W]  Oracle signers
+    /// [WS] Authority - liquidity oracle authority.
+    /// [R]  Rent sysvar
+    /// [R]  System program id
+    /// [R]  Clock sysvar
+    SetOracleSigners {
+        signers: Vec<Pubkey>,
+        quorum: u8,
+        window: Slot,
+    },
+
+    /// Submits signer distribution. Updates token oracle with the median
+    /// once the quorum of the window is reached.
+    ///
+    /// Accounts:
+    /// [R]  Liquidity oracle - off-chain created account.
+    /// [R]  Oracle signers
+    /// [R]  Token mint account
+    /// [W]  TokenOracle - to update state
+    /// [W]  Distribution submissions
+    /// [WS] Signer - oracle signer.
+    /// [R]  Clock sysvar.
+    /// [R]  Rent sysvar
+    /// [R]  System program id
+    /// [R]  Token oracle limits
+    SubmitLiquidityDistribution { value: DistributionArray },
+
+    /// Sets token oracle distribution update limits.
+    ///
+    /// Accounts:
+    /// [R]  Liquidity oracle - off-chain created account.
+    /// [R]  Token mint account
+    /// [R]  TokenOracle
+    /// [W]  Token oracle limits
+    /// [WS] Authority - liquidity oracle authority.
+    /// [R]  Rent sysvar
+    /// [R]  System program id
+    /// [R]  Registry - optional, if signed by the role holder
+    /// [R]  Registry role - optional, if signed by the role holder
+    /// [R]  Registry timelock - optional, if signed by the role holder
+    SetTokenOracleLimits {
+        max_distribution_change: u64,
+        min_update_interval: Slot,
+        frozen: bool,
+    },
+
+    /// Accepts the proposed liquidity oracle authority.
+    ///
+    /// Accounts:
+    /// [W] Liquidity oracle - account.
+    /// [RS] New authority - proposed liquidity oracle authority.
+    AcceptLiquidityOracleAuthority,
+
+    /// Cancels the proposed liquidity oracle authority.
+    ///
+    /// Accounts:
+    /// [W] Liquidity oracle - account.
+    /// [RS] Authority - liquidity oracle authority.
+    CancelLiquidityOracleAuthorityProposal,
+
+    /// Applies the delayed oracle signers update. Can be called by anyone.
+    ///
+    /// Accounts:
+    /// [R]  Liquidity oracle - off-chain created account.
+    /// [W]  Oracle signers
+    /// [R]  Clock sysvar
+    ApplyOracleSigners,
+
+    /// Migrate liquidity oracle account to the layout with pending authority and registry.
+    ///
+    /// Accounts:
+    /// [W]  Liquidity oracle - off-chain created account.
+    /// [WS] Authority - liquidity oracle authority.
+    /// [R]  Registry - grants roles of the oracle.
+    /// [R]  Rent sysvar
+    /// [R]  System program id
+    MigrateLiquidityOracle,
 }
 
 /// Creates 'InitLiquidityOracle' instruction.
@@ -74,10 +168,12 @@ pub fn init_liquidity_oracle(
     program_id: &Pubkey,
     liquidity_oracle: &Pubkey,
     authority: &Pubkey,
+    registry: &Pubkey,
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new(*liquidity_oracle, false),
         AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*registry, false),
     ];
 
     Instruction::new_with_borsh(
@@ -87,22 +183,58 @@ pub fn init_liquidity_oracle(
     )
 }
 
-/// Creates 'UpdateLiquidityOracleAuthority' instruction.
-pub fn update_liquidity_oracle_authority(
+/// Creates 'ProposeLiquidityOracleAuthority' instruction.
+pub fn propose_liquidity_oracle_authority(
     program_id: &Pubkey,
     liquidity_oracle: &Pubkey,
     authority: &Pubkey,
-    update_authority: &Pubkey,
+    new_authority: &Pubkey,
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new(*liquidity_oracle, false),
-        AccountMeta::new_readonly(*update_authority, false),
+        AccountMeta::new_readonly(*new_authority, false),
         AccountMeta::new_readonly(*authority, true),
     ];
 
     Instruction::new_with_borsh(
         *program_id,
-        &LiquidityOracleInstruction::UpdateLiquidityOracleAuthority,
+        &LiquidityOracleInstruction::ProposeLiquidityOracleAuthority,
+        accounts,
+    )
+}
+
+/// Creates 'AcceptLiquidityOracleAuthority' instruction.
+pub fn accept_liquidity_oracle_authority(
+    program_id: &Pubkey,
+    liquidity_oracle: &Pubkey,
+    new_authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*liquidity_oracle, false),
+        AccountMeta::new_readonly(*new_authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityOracleInstruction::AcceptLiquidityOracleAuthority,
+        accounts,
+    )
+}
+
+/// Creates 'CancelLiquidityOracleAuthorityProposal' instruction.
+pub fn cancel_liquidity_oracle_authority_proposal(
+    program_id: &Pubkey,
+    liquidity_oracle: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*liquidity_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityOracleInstruction::CancelLiquidityOracleAuthorityProposal,
         accounts,
     )
 }
@@ -147,12 +279,18 @@ pub fn update_liquidity_distribution(
     let (token_oracle, _) =
         find_token_oracle_program_address(program_id, liquidity_oracle, token_mint);
 
+    let (oracle_signers, _) = find_oracle_signers_program_address(program_id, liquidity_oracle);
+    let (token_oracle_limits, _) =
+        find_token_oracle_limits_program_address(program_id, liquidity_oracle, token_mint);
+
     let accounts = vec![
         AccountMeta::new_readonly(*liquidity_oracle, false),
         AccountMeta::new_readonly(*token_mint, false),
         AccountMeta::new(token_oracle, false),
         AccountMeta::new_readonly(*authority, true),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(oracle_signers, false),
+        AccountMeta::new_readonly(token_oracle_limits, false),
     ];
 
     Instruction::new_with_borsh(
@@ -204,7 +342,7 @@ pub fn migrate(
         AccountMeta::new_readonly(*liquidity_oracle, false),
         AccountMeta::new_readonly(*token_mint, false),
         AccountMeta::new(token_oracle, false),
-        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*authority, true),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
         AccountMeta::new_readonly(system_program::id(), false),
     ];
@@ -215,3 +353,143 @@ pub fn migrate(
         accounts,
     )
 }
+
+pub fn set_oracle_signers(
+    program_id: &Pubkey,
+    liquidity_oracle: &Pubkey,
+    authority: &Pubkey,
+    signers: Vec<Pubkey>,
+    quorum: u8,
+    window: Slot,
+) -> Instruction {
+    let (oracle_signers, _) = find_oracle_signers_program_address(program_id, liquidity_oracle);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*liquidity_oracle, false),
+        AccountMeta::new(oracle_signers, false),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityOracleInstruction::SetOracleSigners {
+            signers,
+            quorum,
+            window,
+        },
+        accounts,
+    )
+}
+
+pub fn apply_oracle_signers(program_id: &Pubkey, liquidity_oracle: &Pubkey) -> Instruction {
+    let (oracle_signers, _) = find_oracle_signers_program_address(program_id, liquidity_oracle);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*liquidity_oracle, false),
+        AccountMeta::new(oracle_signers, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityOracleInstruction::ApplyOracleSigners,
+        accounts,
+    )
+}
+
+pub fn submit_liquidity_distribution(
+    program_id: &Pubkey,
+    liquidity_oracle: &Pubkey,
+    signer: &Pubkey,
+    token_mint: &Pubkey,
+    distribution_array: DistributionArray,
+) -> Instruction {
+    let (oracle_signers, _) = find_oracle_signers_program_address(program_id, liquidity_oracle);
+    let (token_oracle, _) =
+        find_token_oracle_program_address(program_id, liquidity_oracle, token_mint);
+    let (distribution_submissions, _) =
+        find_distribution_submissions_program_address(program_id, liquidity_oracle, token_mint);
+    let (token_oracle_limits, _) =
+        find_token_oracle_limits_program_address(program_id, liquidity_oracle, token_mint);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*liquidity_oracle, false),
+        AccountMeta::new_readonly(oracle_signers, false),
+        AccountMeta::new_readonly(*token_mint, false),
+        AccountMeta::new(token_oracle, false),
+        AccountMeta::new(distribution_submissions, false),
+        AccountMeta::new(*signer, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(token_oracle_limits, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityOracleInstruction::SubmitLiquidityDistribution {
+            value: distribution_array,
+        },
+        accounts,
+    )
+}
+
+pub fn set_token_oracle_limits(
+    program_id: &Pubkey,
+    liquidity_oracle: &Pubkey,
+    authority: &Pubkey,
+    token_mint: &Pubkey,
+    max_distribution_change: u64,
+    min_update_interval: Slot,
+    frozen: bool,
+) -> Instruction {
+    let (token_oracle, _) =
+        find_token_oracle_program_address(program_id, liquidity_oracle, token_mint);
+    let (token_oracle_limits, _) =
+        find_token_oracle_limits_program_address(program_id, liquidity_oracle, token_mint);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*liquidity_oracle, false),
+        AccountMeta::new_readonly(*token_mint, false),
+        AccountMeta::new_readonly(token_oracle, false),
+        AccountMeta::new(token_oracle_limits, false),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityOracleInstruction::SetTokenOracleLimits {
+            max_distribution_change,
+            min_update_interval,
+            frozen,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'MigrateLiquidityOracle' instruction.
+pub fn migrate_liquidity_oracle(
+    program_id: &Pubkey,
+    liquidity_oracle: &Pubkey,
+    authority: &Pubkey,
+    registry: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*liquidity_oracle, false),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &LiquidityOracleInstruction::MigrateLiquidityOracle,
+        accounts,
+    )
+}
@@ -0,0 +1,56 @@
+use everlend_utils::{assert_account_key, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{clock, Sysvar},
+};
+
+use crate::{find_oracle_signers_program_address, state::OracleSigners};
+
+/// Instruction context
+pub struct ApplyOracleSignersContext<'a, 'b> {
+    liquidity_oracle: &'a AccountInfo<'b>,
+    oracle_signers: &'a AccountInfo<'b>,
+    clock: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> ApplyOracleSignersContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<ApplyOracleSignersContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let liquidity_oracle = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let oracle_signers = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let clock = AccountLoader::next_with_key(account_info_iter, &clock::id())?;
+
+        Ok(ApplyOracleSignersContext {
+            liquidity_oracle,
+            oracle_signers,
+            clock,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
+        let (oracle_signers_pubkey, _) =
+            find_oracle_signers_program_address(program_id, self.liquidity_oracle.key);
+        assert_account_key(self.oracle_signers, &oracle_signers_pubkey)?;
+
+        let mut oracle_signers = OracleSigners::unpack(&self.oracle_signers.data.borrow())?;
+        assert_account_key(self.liquidity_oracle, &oracle_signers.liquidity_oracle)?;
+
+        let clock = Clock::from_account_info(self.clock)?;
+        oracle_signers.apply_pending(clock.slot)?;
+
+        OracleSigners::pack(oracle_signers, *self.oracle_signers.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
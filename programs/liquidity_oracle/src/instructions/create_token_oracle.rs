@@ -1,3 +1,4 @@
+use everlend_registry::state::{Role, RoleAccounts};
 use everlend_utils::{assert_account_key, cpi::system::create_account, AccountLoader};
 use solana_program::{
     account_info::AccountInfo,
@@ -24,6 +25,7 @@ pub struct CreateTokenOracleContext<'a, 'b> {
     authority: &'a AccountInfo<'b>,
     clock: &'a AccountInfo<'b>,
     rent: &'a AccountInfo<'b>,
+    role_accounts: Option<RoleAccounts<'a, 'b>>,
 }
 
 impl<'a, 'b> CreateTokenOracleContext<'a, 'b> {
@@ -42,6 +44,8 @@ impl<'a, 'b> CreateTokenOracleContext<'a, 'b> {
         let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
         let _system = AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
 
+        let role_accounts = RoleAccounts::next_optional(account_info_iter)?;
+
         Ok(CreateTokenOracleContext {
             liquidity_oracle,
             token_mint,
@@ -49,15 +53,20 @@ impl<'a, 'b> CreateTokenOracleContext<'a, 'b> {
             authority,
             clock,
             rent,
+            role_accounts,
         })
     }
 
     /// Process instruction
     pub fn process(&self, program_id: &Pubkey, distribution: DistributionArray) -> ProgramResult {
         {
-            // Check authotiry
+            // Check authority or role
             let liquidity_oracle = LiquidityOracle::unpack(&self.liquidity_oracle.data.borrow())?;
-            assert_account_key(self.authority, &liquidity_oracle.authority)?;
+            liquidity_oracle.assert_authority_or_role(
+                self.authority,
+                self.role_accounts.as_ref(),
+                Role::Operator,
+            )?;
         }
 
         let bump_seed = {
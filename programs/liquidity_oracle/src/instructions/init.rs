@@ -10,6 +10,7 @@ use crate::state::LiquidityOracle;
 pub struct InitContext<'a, 'b> {
     liquidity_oracle: &'a AccountInfo<'b>,
     authority: &'a AccountInfo<'b>,
+    registry: &'a AccountInfo<'b>,
 }
 
 impl<'a, 'b> InitContext<'a, 'b> {
@@ -22,10 +23,12 @@ impl<'a, 'b> InitContext<'a, 'b> {
 
         let liquidity_oracle = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let authority = AccountLoader::next_signer(account_info_iter)?;
+        let registry = AccountLoader::next_with_owner(account_info_iter, &everlend_registry::id())?;
 
         Ok(InitContext {
             liquidity_oracle,
             authority,
+            registry,
         })
     }
 
@@ -36,7 +39,7 @@ impl<'a, 'b> InitContext<'a, 'b> {
         assert_uninitialized(&liquidity_oracle)?;
 
         // Initialize
-        let liquidity_oracle = LiquidityOracle::init(*self.authority.key);
+        let liquidity_oracle = LiquidityOracle::init(*self.authority.key, *self.registry.key);
         LiquidityOracle::pack(liquidity_oracle, *self.liquidity_oracle.data.borrow_mut())?;
 
         Ok(())
@@ -12,7 +12,7 @@ use solana_program::{
 
 use crate::{
     find_token_oracle_program_address,
-    state::{DeprecatedTokenDistribution, DistributionArray, LiquidityOracle, TokenOracle},
+    state::{DeprecatedTokenOracle, LiquidityOracle, TokenOracle},
 };
 
 /// Instruction context
@@ -66,7 +66,8 @@ impl<'a, 'b> MigrateContext<'a, 'b> {
             assert_account_key(self.token_oracle, &token_oracle_pubkey)?;
         }
 
-        let deprecated = DeprecatedTokenDistribution::unpack(&self.token_oracle.data.borrow())?;
+        let oracle: TokenOracle =
+            DeprecatedTokenOracle::unpack(&self.token_oracle.data.borrow())?.into();
 
         // Realloc account
         realloc_with_rent(
@@ -76,10 +77,6 @@ impl<'a, 'b> MigrateContext<'a, 'b> {
             TokenOracle::LEN,
         )?;
 
-        let mut oracle = TokenOracle::init();
-        oracle.update_liquidity_distribution(deprecated.updated_at, deprecated.distribution)?;
-        oracle.update_reserve_rates(deprecated.updated_at, DistributionArray::default())?;
-
         TokenOracle::pack(oracle, *self.token_oracle.data.borrow_mut())?;
 
         Ok(())
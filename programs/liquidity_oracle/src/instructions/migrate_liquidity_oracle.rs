@@ -0,0 +1,68 @@
+use everlend_utils::{assert_account_key, cpi::system::realloc_with_rent, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::state::{DeprecatedLiquidityOracle, LiquidityOracle};
+
+/// Instruction context
+pub struct MigrateLiquidityOracleContext<'a, 'b> {
+    liquidity_oracle: &'a AccountInfo<'b>,
+    authority: &'a AccountInfo<'b>,
+    registry: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> MigrateLiquidityOracleContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<MigrateLiquidityOracleContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let liquidity_oracle = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let authority = AccountLoader::next_signer(account_info_iter)?;
+        let registry = AccountLoader::next_with_owner(account_info_iter, &everlend_registry::id())?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        Ok(MigrateLiquidityOracleContext {
+            liquidity_oracle,
+            authority,
+            registry,
+            rent,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, _program_id: &Pubkey) -> ProgramResult {
+        let mut liquidity_oracle: LiquidityOracle =
+            DeprecatedLiquidityOracle::unpack(&self.liquidity_oracle.data.borrow())?.into();
+
+        // Check authority
+        assert_account_key(self.authority, &liquidity_oracle.authority)?;
+
+        liquidity_oracle.registry = *self.registry.key;
+
+        // Realloc account
+        realloc_with_rent(
+            self.liquidity_oracle,
+            self.authority,
+            &Rent::from_account_info(self.rent)?,
+            LiquidityOracle::LEN,
+        )?;
+
+        LiquidityOracle::pack(liquidity_oracle, *self.liquidity_oracle.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -1,14 +1,28 @@
 //! Program instructions
+mod accept_authority;
+mod apply_oracle_signers;
+mod cancel_authority_proposal;
 mod create_token_oracle;
 mod init;
-// mod migrate;
-mod update_authority;
+mod migrate;
+mod migrate_liquidity_oracle;
+mod propose_authority;
+mod set_oracle_signers;
+mod set_token_oracle_limits;
+mod submit_liquidity_distribution;
 mod update_liquidity_distribution;
 mod update_reserve_rates;
 
+pub use accept_authority::*;
+pub use apply_oracle_signers::*;
+pub use cancel_authority_proposal::*;
 pub use create_token_oracle::*;
 pub use init::*;
-// pub use migrate::*;
-pub use update_authority::*;
+pub use migrate::*;
+pub use migrate_liquidity_oracle::*;
+pub use propose_authority::*;
+pub use set_oracle_signers::*;
+pub use set_token_oracle_limits::*;
+pub use submit_liquidity_distribution::*;
 pub use update_liquidity_distribution::*;
 pub use update_reserve_rates::*;
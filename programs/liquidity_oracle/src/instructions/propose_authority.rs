@@ -7,25 +7,25 @@ use solana_program::{
 use crate::state::LiquidityOracle;
 
 /// Instruction context
-pub struct UpdateAuthorityContext<'a, 'b> {
+pub struct ProposeAuthorityContext<'a, 'b> {
     liquidity_oracle: &'a AccountInfo<'b>,
     new_authority: &'a AccountInfo<'b>,
     authority: &'a AccountInfo<'b>,
 }
 
-impl<'a, 'b> UpdateAuthorityContext<'a, 'b> {
+impl<'a, 'b> ProposeAuthorityContext<'a, 'b> {
     /// New instruction context
     pub fn new(
         program_id: &Pubkey,
         accounts: &'a [AccountInfo<'b>],
-    ) -> Result<UpdateAuthorityContext<'a, 'b>, ProgramError> {
+    ) -> Result<ProposeAuthorityContext<'a, 'b>, ProgramError> {
         let account_info_iter = &mut accounts.iter().enumerate();
 
         let liquidity_oracle = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let new_authority = AccountLoader::next_unchecked(account_info_iter)?; // can be any account
         let authority = AccountLoader::next_signer(account_info_iter)?;
 
-        Ok(UpdateAuthorityContext {
+        Ok(ProposeAuthorityContext {
             liquidity_oracle,
             new_authority,
             authority,
@@ -39,8 +39,8 @@ impl<'a, 'b> UpdateAuthorityContext<'a, 'b> {
         // Check current authority
         assert_account_key(self.authority, &liquidity_oracle.authority)?;
 
-        // Update to new authority
-        liquidity_oracle.update(*self.new_authority.key);
+        // New authority takes over after accepting
+        liquidity_oracle.propose_authority(*self.new_authority.key);
 
         LiquidityOracle::pack(liquidity_oracle, *self.liquidity_oracle.data.borrow_mut())?;
 
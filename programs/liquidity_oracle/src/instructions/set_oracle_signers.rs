@@ -0,0 +1,108 @@
+use everlend_utils::{assert_account_key, cpi::system::create_account, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::{Clock, Slot},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{clock, Sysvar, SysvarId},
+};
+
+use crate::{
+    find_oracle_signers_program_address,
+    state::{LiquidityOracle, OracleSigners},
+};
+
+/// Instruction context
+pub struct SetOracleSignersContext<'a, 'b> {
+    liquidity_oracle: &'a AccountInfo<'b>,
+    oracle_signers: &'a AccountInfo<'b>,
+    authority: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    clock: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> SetOracleSignersContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<SetOracleSignersContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let liquidity_oracle = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let oracle_signers = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let authority = AccountLoader::next_signer(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system = AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+        let clock = AccountLoader::next_with_key(account_info_iter, &clock::id())?;
+
+        Ok(SetOracleSignersContext {
+            liquidity_oracle,
+            oracle_signers,
+            authority,
+            rent,
+            clock,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(
+        &self,
+        program_id: &Pubkey,
+        signers: Vec<Pubkey>,
+        quorum: u8,
+        window: Slot,
+    ) -> ProgramResult {
+        {
+            // Check authotiry
+            let liquidity_oracle = LiquidityOracle::unpack(&self.liquidity_oracle.data.borrow())?;
+            assert_account_key(self.authority, &liquidity_oracle.authority)?;
+        }
+
+        let bump_seed = {
+            let (oracle_signers_pubkey, bump_seed) =
+                find_oracle_signers_program_address(program_id, self.liquidity_oracle.key);
+            assert_account_key(self.oracle_signers, &oracle_signers_pubkey)?;
+
+            bump_seed
+        };
+
+        let mut oracle_signers = match self.oracle_signers.lamports() {
+            // Create oracle signers account
+            0 => {
+                let signers_seeds: &[&[u8]] = &[
+                    br"signers",
+                    &self.liquidity_oracle.key.to_bytes()[..32],
+                    &[bump_seed],
+                ];
+
+                create_account::<OracleSigners>(
+                    program_id,
+                    self.authority.clone(),
+                    self.oracle_signers.clone(),
+                    &[signers_seeds],
+                    &Rent::from_account_info(self.rent)?,
+                )?;
+
+                OracleSigners::init(*self.liquidity_oracle.key)
+            }
+            _ => {
+                let oracle_signers = OracleSigners::unpack(&self.oracle_signers.data.borrow())?;
+                assert_account_key(self.liquidity_oracle, &oracle_signers.liquidity_oracle)?;
+
+                oracle_signers
+            }
+        };
+
+        let clock = Clock::from_account_info(self.clock)?;
+        oracle_signers.update(signers, quorum, window, clock.slot)?;
+
+        OracleSigners::pack(oracle_signers, *self.oracle_signers.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+use everlend_registry::state::{Role, RoleAccounts};
+use everlend_utils::{assert_account_key, cpi::system::create_account, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Slot,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::{
+    find_token_oracle_limits_program_address, find_token_oracle_program_address,
+    state::{LiquidityOracle, TokenOracleLimits},
+};
+
+/// Instruction context
+pub struct SetTokenOracleLimitsContext<'a, 'b> {
+    liquidity_oracle: &'a AccountInfo<'b>,
+    token_mint: &'a AccountInfo<'b>,
+    token_oracle: &'a AccountInfo<'b>,
+    token_oracle_limits: &'a AccountInfo<'b>,
+    authority: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    role_accounts: Option<RoleAccounts<'a, 'b>>,
+}
+
+impl<'a, 'b> SetTokenOracleLimitsContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<SetTokenOracleLimitsContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let liquidity_oracle = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let token_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let token_oracle = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let token_oracle_limits = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let authority = AccountLoader::next_signer(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system = AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        let role_accounts = RoleAccounts::next_optional(account_info_iter)?;
+
+        Ok(SetTokenOracleLimitsContext {
+            liquidity_oracle,
+            token_mint,
+            token_oracle,
+            token_oracle_limits,
+            authority,
+            rent,
+            role_accounts,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(
+        &self,
+        program_id: &Pubkey,
+        max_distribution_change: u64,
+        min_update_interval: Slot,
+        frozen: bool,
+    ) -> ProgramResult {
+        {
+            // Check authority or role
+            let liquidity_oracle = LiquidityOracle::unpack(&self.liquidity_oracle.data.borrow())?;
+            liquidity_oracle.assert_authority_or_role(
+                self.authority,
+                self.role_accounts.as_ref(),
+                Role::Risk,
+            )?;
+
+            // Check token distribution
+            let (token_oracle_pubkey, _) = find_token_oracle_program_address(
+                program_id,
+                self.liquidity_oracle.key,
+                self.token_mint.key,
+            );
+            assert_account_key(self.token_oracle, &token_oracle_pubkey)?;
+        }
+
+        let bump_seed = {
+            let (token_oracle_limits_pubkey, bump_seed) = find_token_oracle_limits_program_address(
+                program_id,
+                self.liquidity_oracle.key,
+                self.token_mint.key,
+            );
+            assert_account_key(self.token_oracle_limits, &token_oracle_limits_pubkey)?;
+
+            bump_seed
+        };
+
+        let mut token_oracle_limits = match self.token_oracle_limits.lamports() {
+            // Create token oracle limits account
+            0 => {
+                let signers_seeds: &[&[u8]] = &[
+                    br"limits",
+                    &self.liquidity_oracle.key.to_bytes()[..32],
+                    &self.token_mint.key.to_bytes()[..32],
+                    &[bump_seed],
+                ];
+
+                create_account::<TokenOracleLimits>(
+                    program_id,
+                    self.authority.clone(),
+                    self.token_oracle_limits.clone(),
+                    &[signers_seeds],
+                    &Rent::from_account_info(self.rent)?,
+                )?;
+
+                TokenOracleLimits::init(*self.token_oracle.key)
+            }
+            _ => {
+                let token_oracle_limits =
+                    TokenOracleLimits::unpack(&self.token_oracle_limits.data.borrow())?;
+                assert_account_key(self.token_oracle, &token_oracle_limits.token_oracle)?;
+
+                token_oracle_limits
+            }
+        };
+
+        token_oracle_limits.update(max_distribution_change, min_update_interval, frozen);
+
+        TokenOracleLimits::pack(
+            token_oracle_limits,
+            *self.token_oracle_limits.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+}
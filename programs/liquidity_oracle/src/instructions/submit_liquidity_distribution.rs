@@ -0,0 +1,177 @@
+use everlend_utils::{assert_account_key, cpi::system::create_account, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::{
+    find_distribution_submissions_program_address, find_oracle_signers_program_address,
+    find_token_oracle_limits_program_address, find_token_oracle_program_address,
+    state::{
+        DistributionArray, DistributionSubmissions, OracleSigners, TokenOracle, TokenOracleLimits,
+    },
+};
+
+/// Instruction context
+pub struct SubmitLiquidityDistributionContext<'a, 'b> {
+    liquidity_oracle: &'a AccountInfo<'b>,
+    oracle_signers: &'a AccountInfo<'b>,
+    token_mint: &'a AccountInfo<'b>,
+    token_oracle: &'a AccountInfo<'b>,
+    distribution_submissions: &'a AccountInfo<'b>,
+    signer: &'a AccountInfo<'b>,
+    clock: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+    token_oracle_limits: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> SubmitLiquidityDistributionContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<SubmitLiquidityDistributionContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let liquidity_oracle = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let oracle_signers = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let token_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+        let token_oracle = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let distribution_submissions = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let signer = AccountLoader::next_signer(account_info_iter)?;
+        let clock = AccountLoader::next_with_key(account_info_iter, &Clock::id())?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system = AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+        let token_oracle_limits = AccountLoader::next_optional(account_info_iter, program_id)?;
+
+        Ok(SubmitLiquidityDistributionContext {
+            liquidity_oracle,
+            oracle_signers,
+            token_mint,
+            token_oracle,
+            distribution_submissions,
+            signer,
+            clock,
+            rent,
+            token_oracle_limits,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey, distribution: DistributionArray) -> ProgramResult {
+        {
+            // Check oracle signers
+            let (oracle_signers_pubkey, _) =
+                find_oracle_signers_program_address(program_id, self.liquidity_oracle.key);
+            assert_account_key(self.oracle_signers, &oracle_signers_pubkey)?;
+
+            // Check token distribution
+            let (token_oracle_pubkey, _) = find_token_oracle_program_address(
+                program_id,
+                self.liquidity_oracle.key,
+                self.token_mint.key,
+            );
+            assert_account_key(self.token_oracle, &token_oracle_pubkey)?;
+
+            // Check token oracle limits
+            let (token_oracle_limits_pubkey, _) = find_token_oracle_limits_program_address(
+                program_id,
+                self.liquidity_oracle.key,
+                self.token_mint.key,
+            );
+            assert_account_key(self.token_oracle_limits, &token_oracle_limits_pubkey)?;
+        }
+
+        let bump_seed = {
+            let (distribution_submissions_pubkey, bump_seed) =
+                find_distribution_submissions_program_address(
+                    program_id,
+                    self.liquidity_oracle.key,
+                    self.token_mint.key,
+                );
+            assert_account_key(
+                self.distribution_submissions,
+                &distribution_submissions_pubkey,
+            )?;
+
+            bump_seed
+        };
+
+        let oracle_signers = OracleSigners::unpack(&self.oracle_signers.data.borrow())?;
+        if !oracle_signers.is_enabled() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let mut distribution_submissions = match self.distribution_submissions.lamports() {
+            // Create distribution submissions account
+            0 => {
+                let signers_seeds: &[&[u8]] = &[
+                    br"submissions",
+                    &self.liquidity_oracle.key.to_bytes()[..32],
+                    &self.token_mint.key.to_bytes()[..32],
+                    &[bump_seed],
+                ];
+
+                create_account::<DistributionSubmissions>(
+                    program_id,
+                    self.signer.clone(),
+                    self.distribution_submissions.clone(),
+                    &[signers_seeds],
+                    &Rent::from_account_info(self.rent)?,
+                )?;
+
+                DistributionSubmissions::init()
+            }
+            _ => DistributionSubmissions::unpack(&self.distribution_submissions.data.borrow())?,
+        };
+
+        let clock = Clock::from_account_info(self.clock)?;
+
+        distribution_submissions.submit(
+            &oracle_signers,
+            oracle_signers.window_of(clock.slot),
+            *self.signer.key,
+            distribution,
+        )?;
+
+        // Update token oracle with median of the window submissions
+        if distribution_submissions.has_quorum(&oracle_signers) {
+            let median = distribution_submissions.median()?;
+            msg!("Median distribution: {:?}", median);
+
+            let mut oracle = TokenOracle::unpack(&self.token_oracle.data.borrow())?;
+
+            // Submission is kept when the median violates the limits,
+            // the token oracle is updated by a later submission
+            let limits_check = if self.token_oracle_limits.owner.eq(program_id) {
+                TokenOracleLimits::unpack(&self.token_oracle_limits.data.borrow())?
+                    .check(&oracle, clock.slot, &median)
+            } else {
+                Ok(())
+            };
+
+            match limits_check {
+                Ok(()) => {
+                    oracle.update_liquidity_distribution(clock.slot, median)?;
+                    TokenOracle::pack(oracle, *self.token_oracle.data.borrow_mut())?;
+                }
+                Err(err) => msg!("Token oracle update skipped: {}", err),
+            }
+        }
+
+        DistributionSubmissions::pack(
+            distribution_submissions,
+            *self.distribution_submissions.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+}
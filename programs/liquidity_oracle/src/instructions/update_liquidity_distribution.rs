@@ -1,4 +1,4 @@
-use everlend_utils::{assert_account_key, AccountLoader};
+use everlend_utils::{assert_account_key, AccountLoader, EverlendError};
 use solana_program::{
     account_info::AccountInfo,
     clock::Clock,
@@ -10,8 +10,9 @@ use solana_program::{
 };
 
 use crate::{
+    find_oracle_signers_program_address, find_token_oracle_limits_program_address,
     find_token_oracle_program_address,
-    state::{DistributionArray, LiquidityOracle, TokenOracle},
+    state::{DistributionArray, LiquidityOracle, OracleSigners, TokenOracle, TokenOracleLimits},
 };
 
 /// Instruction context
@@ -21,6 +22,8 @@ pub struct UpdateLiquidityDistributionContext<'a, 'b> {
     token_oracle: &'a AccountInfo<'b>,
     authority: &'a AccountInfo<'b>,
     clock: &'a AccountInfo<'b>,
+    oracle_signers: &'a AccountInfo<'b>,
+    token_oracle_limits: &'a AccountInfo<'b>,
 }
 
 impl<'a, 'b> UpdateLiquidityDistributionContext<'a, 'b> {
@@ -36,6 +39,8 @@ impl<'a, 'b> UpdateLiquidityDistributionContext<'a, 'b> {
         let token_oracle = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let authority = AccountLoader::next_signer(account_info_iter)?;
         let clock = AccountLoader::next_with_key(account_info_iter, &Clock::id())?;
+        let oracle_signers = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let token_oracle_limits = AccountLoader::next_optional(account_info_iter, program_id)?;
 
         Ok(UpdateLiquidityDistributionContext {
             liquidity_oracle,
@@ -43,6 +48,8 @@ impl<'a, 'b> UpdateLiquidityDistributionContext<'a, 'b> {
             token_oracle,
             authority,
             clock,
+            oracle_signers,
+            token_oracle_limits,
         })
     }
 
@@ -61,11 +68,40 @@ impl<'a, 'b> UpdateLiquidityDistributionContext<'a, 'b> {
             );
 
             assert_account_key(self.token_oracle, &token_oracle_pubkey)?;
+
+            // Check oracle signers
+            let (oracle_signers_pubkey, _) =
+                find_oracle_signers_program_address(program_id, self.liquidity_oracle.key);
+            assert_account_key(self.oracle_signers, &oracle_signers_pubkey)?;
+
+            // Distribution is updated by signers when quorum is enabled
+            if self.oracle_signers.owner.eq(program_id)
+                && OracleSigners::unpack(&self.oracle_signers.data.borrow())?.is_enabled()
+            {
+                return Err(EverlendError::OracleQuorumEnabled.into());
+            }
+
+            // Check token oracle limits
+            let (token_oracle_limits_pubkey, _) = find_token_oracle_limits_program_address(
+                program_id,
+                self.liquidity_oracle.key,
+                self.token_mint.key,
+            );
+            assert_account_key(self.token_oracle_limits, &token_oracle_limits_pubkey)?;
         }
 
         let clock = Clock::from_account_info(self.clock)?;
 
         let mut oracle = TokenOracle::unpack(&self.token_oracle.data.borrow())?;
+
+        if self.token_oracle_limits.owner.eq(program_id) {
+            TokenOracleLimits::unpack(&self.token_oracle_limits.data.borrow())?.check(
+                &oracle,
+                clock.slot,
+                &distribution,
+            )?;
+        }
+
         oracle.update_liquidity_distribution(clock.slot, distribution)?;
 
         TokenOracle::pack(oracle, *self.token_oracle.data.borrow_mut())?;
@@ -23,3 +23,43 @@ pub fn find_token_oracle_program_address(
         program_id,
     )
 }
+
+/// Generates liquidity oracle signers address
+pub fn find_oracle_signers_program_address(
+    program_id: &Pubkey,
+    liquidity_oracle: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[br"signers", &liquidity_oracle.to_bytes()], program_id)
+}
+
+/// Generates token oracle distribution submissions address
+pub fn find_distribution_submissions_program_address(
+    program_id: &Pubkey,
+    liquidity_oracle: &Pubkey,
+    token_mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            br"submissions",
+            &liquidity_oracle.to_bytes(),
+            &token_mint.to_bytes(),
+        ],
+        program_id,
+    )
+}
+
+/// Generates token oracle limits address
+pub fn find_token_oracle_limits_program_address(
+    program_id: &Pubkey,
+    liquidity_oracle: &Pubkey,
+    token_mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            br"limits",
+            &liquidity_oracle.to_bytes(),
+            &token_mint.to_bytes(),
+        ],
+        program_id,
+    )
+}
@@ -1,11 +1,13 @@
 //! Program processor.
 use crate::instruction::LiquidityOracleInstruction;
 use crate::instructions::{
-    CreateTokenOracleContext, InitContext, UpdateAuthorityContext,
-    UpdateLiquidityDistributionContext, UpdateReserveRatesContext,
+    AcceptAuthorityContext, ApplyOracleSignersContext, CancelAuthorityProposalContext,
+    CreateTokenOracleContext, InitContext, MigrateContext, MigrateLiquidityOracleContext,
+    ProposeAuthorityContext, SetOracleSignersContext, SetTokenOracleLimitsContext,
+    SubmitLiquidityDistributionContext, UpdateLiquidityDistributionContext,
+    UpdateReserveRatesContext,
 };
 use borsh::BorshDeserialize;
-use everlend_utils::EverlendError;
 use solana_program::msg;
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 
@@ -23,9 +25,9 @@ pub fn process_instruction(
             InitContext::new(program_id, accounts)?.process(program_id)
         }
 
-        LiquidityOracleInstruction::UpdateLiquidityOracleAuthority => {
-            msg!("LiquidityOracleInstruction: UpdateLiquidityOracleAuthority");
-            UpdateAuthorityContext::new(program_id, accounts)?.process(program_id)
+        LiquidityOracleInstruction::ProposeLiquidityOracleAuthority => {
+            msg!("LiquidityOracleInstruction: ProposeLiquidityOracleAuthority");
+            ProposeAuthorityContext::new(program_id, accounts)?.process(program_id)
         }
 
         LiquidityOracleInstruction::CreateTokenOracle { value } => {
@@ -46,7 +48,57 @@ pub fn process_instruction(
 
         LiquidityOracleInstruction::Migrate => {
             msg!("LiquidityOracleInstruction: Migrate");
-            Err(EverlendError::TemporaryUnavailable.into())
+            MigrateContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        LiquidityOracleInstruction::SetOracleSigners {
+            signers,
+            quorum,
+            window,
+        } => {
+            msg!("LiquidityOracleInstruction: SetOracleSigners");
+            SetOracleSignersContext::new(program_id, accounts)?
+                .process(program_id, signers, quorum, window)
+        }
+
+        LiquidityOracleInstruction::SubmitLiquidityDistribution { value } => {
+            msg!("LiquidityOracleInstruction: SubmitLiquidityDistribution");
+            SubmitLiquidityDistributionContext::new(program_id, accounts)?
+                .process(program_id, value)
+        }
+
+        LiquidityOracleInstruction::SetTokenOracleLimits {
+            max_distribution_change,
+            min_update_interval,
+            frozen,
+        } => {
+            msg!("LiquidityOracleInstruction: SetTokenOracleLimits");
+            SetTokenOracleLimitsContext::new(program_id, accounts)?.process(
+                program_id,
+                max_distribution_change,
+                min_update_interval,
+                frozen,
+            )
+        }
+
+        LiquidityOracleInstruction::AcceptLiquidityOracleAuthority => {
+            msg!("LiquidityOracleInstruction: AcceptLiquidityOracleAuthority");
+            AcceptAuthorityContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        LiquidityOracleInstruction::CancelLiquidityOracleAuthorityProposal => {
+            msg!("LiquidityOracleInstruction: CancelLiquidityOracleAuthorityProposal");
+            CancelAuthorityProposalContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        LiquidityOracleInstruction::ApplyOracleSigners => {
+            msg!("LiquidityOracleInstruction: ApplyOracleSigners");
+            ApplyOracleSignersContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        LiquidityOracleInstruction::MigrateLiquidityOracle => {
+            msg!("LiquidityOracleInstruction: MigrateLiquidityOracle");
+            MigrateLiquidityOracleContext::new(program_id, accounts)?.process(program_id)
         }
     }
 }
@@ -0,0 +1,161 @@
+//! Distribution submissions state definitions.
+
+use super::{AccountType, DistributionArray, OracleSigners, MAX_ORACLE_SIGNERS};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_registry::state::MAX_MONEY_MARKETS;
+use everlend_utils::{EverlendError, PRECISION_SCALER};
+use solana_program::{
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Distribution submitted by an oracle signer.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct DistributionSubmission {
+    /// Oracle signer.
+    pub signer: Pubkey,
+    /// Submitted distribution.
+    pub values: DistributionArray,
+}
+
+impl DistributionSubmission {
+    pub const LEN: usize = 32 + (4 + 8 * MAX_MONEY_MARKETS);
+}
+
+/// Distribution submissions of the token oracle.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct DistributionSubmissions {
+    /// Account type.
+    pub account_type: AccountType,
+    /// Submission window.
+    pub window: u64,
+    /// Submissions of the current window.
+    pub submissions: Vec<DistributionSubmission>,
+}
+
+impl DistributionSubmissions {
+    /// Initialize a distribution submissions.
+    pub fn init() -> DistributionSubmissions {
+        DistributionSubmissions {
+            account_type: AccountType::DistributionSubmissions,
+            ..Default::default()
+        }
+    }
+
+    /// Add signer submission to the window. Submissions of previous windows are dropped.
+    pub fn submit(
+        &mut self,
+        oracle_signers: &OracleSigners,
+        window: u64,
+        signer: Pubkey,
+        values: DistributionArray,
+    ) -> Result<(), ProgramError> {
+        if values.len() > MAX_MONEY_MARKETS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if !oracle_signers.contains(&signer) {
+            return Err(EverlendError::OracleSignerNotAllowed.into());
+        }
+
+        if self.window != window {
+            self.window = window;
+            self.submissions = Vec::new();
+        }
+
+        // Drop submissions of removed signers
+        self.submissions
+            .retain(|submission| oracle_signers.contains(&submission.signer));
+
+        match self
+            .submissions
+            .iter_mut()
+            .find(|submission| submission.signer == signer)
+        {
+            Some(submission) => submission.values = values,
+            None => self
+                .submissions
+                .push(DistributionSubmission { signer, values }),
+        }
+
+        Ok(())
+    }
+
+    /// Check submissions reached the quorum.
+    pub fn has_quorum(&self, oracle_signers: &OracleSigners) -> bool {
+        oracle_signers.is_enabled() && self.submissions.len() >= oracle_signers.quorum as usize
+    }
+
+    /// Median of submitted distributions by each money market.
+    /// Scaled down if total distribution exceeds 100%.
+    pub fn median(&self) -> Result<DistributionArray, ProgramError> {
+        let len = self
+            .submissions
+            .iter()
+            .map(|submission| submission.values.len())
+            .max()
+            .unwrap_or_default();
+
+        let mut median: DistributionArray = (0..len)
+            .map(|index| {
+                let mut values: Vec<u64> = self
+                    .submissions
+                    .iter()
+                    .map(|submission| submission.values.get(index).copied().unwrap_or_default())
+                    .collect();
+                values.sort_unstable();
+
+                // Lower middle value for even count, so the median is always
+                // one of the submitted values instead of their average
+                values[(values.len() - 1) / 2]
+            })
+            .collect();
+
+        let total = median
+            .iter()
+            .try_fold(0u64, |acc, &x| acc.checked_add(x))
+            .ok_or(EverlendError::MathOverflow)?;
+        if total > PRECISION_SCALER as u64 {
+            for value in median.iter_mut() {
+                *value = (*value as u128)
+                    .checked_mul(PRECISION_SCALER)
+                    .ok_or(EverlendError::MathOverflow)?
+                    .checked_div(total as u128)
+                    .ok_or(EverlendError::MathOverflow)? as u64;
+            }
+        }
+
+        Ok(median)
+    }
+}
+
+impl Sealed for DistributionSubmissions {}
+
+impl Pack for DistributionSubmissions {
+    // 1 + 8 + (4 + 164 * 10)
+    const LEN: usize = 1 + 8 + (4 + DistributionSubmission::LEN * MAX_ORACLE_SIGNERS);
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for DistributionSubmissions {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::DistributionSubmissions
+    }
+}
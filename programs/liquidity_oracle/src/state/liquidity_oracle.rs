@@ -2,8 +2,11 @@
 
 use super::AccountType;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
-use everlend_utils::Uninitialized;
+use everlend_registry::state::{assert_manager_or_role, Role, RoleAccounts};
+use everlend_utils::{assert_account_key, Uninitialized};
 use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
     msg,
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
@@ -18,28 +21,54 @@ pub struct LiquidityOracle {
     pub account_type: AccountType,
     /// Authority.
     pub authority: Pubkey,
+    /// Proposed authority, takes over after accepting.
+    pub pending_authority: Pubkey,
+    /// Registry granting roles of the oracle.
+    pub registry: Pubkey,
 }
 
 impl LiquidityOracle {
     /// Initialize a liquidity oracle.
-    pub fn init(authority: Pubkey) -> LiquidityOracle {
+    pub fn init(authority: Pubkey, registry: Pubkey) -> LiquidityOracle {
         LiquidityOracle {
             account_type: AccountType::LiquidityOracle,
             authority,
+            pending_authority: Pubkey::default(),
+            registry,
         }
     }
 
-    /// Update liquidity oracle.
-    pub fn update(&mut self, authority: Pubkey) {
-        self.authority = authority;
+    /// Propose a new authority.
+    pub fn propose_authority(&mut self, authority: Pubkey) {
+        self.pending_authority = authority;
+    }
+
+    /// Replace authority with the proposed one.
+    pub fn accept_authority(&mut self) {
+        self.authority = self.pending_authority;
+        self.pending_authority = Pubkey::default();
+    }
+
+    /// Check the signer is the authority or is granted the role in the registry of the oracle.
+    pub fn assert_authority_or_role(
+        &self,
+        signer: &AccountInfo,
+        role_accounts: Option<&RoleAccounts>,
+        role: Role,
+    ) -> ProgramResult {
+        if let Some(role_accounts) = role_accounts {
+            assert_account_key(role_accounts.registry, &self.registry)?;
+        }
+
+        assert_manager_or_role(&self.authority, signer, role_accounts, role)
     }
 }
 
 impl Sealed for LiquidityOracle {}
 
 impl Pack for LiquidityOracle {
-    // 1 + 32
-    const LEN: usize = 33;
+    // 1 + 32 + 32 + 32
+    const LEN: usize = 97;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -1,33 +1,39 @@
-//! Token distribution state definitions.
+//! Deprecated liquidity oracle state definitions.
 
-use super::{AccountType, DistributionArray};
+use super::{AccountType, LiquidityOracle};
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
-use everlend_registry::state::TOTAL_DISTRIBUTIONS;
 use everlend_utils::Uninitialized;
 use solana_program::{
-    clock::Slot,
     msg,
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
 };
 
 #[repr(C)]
 #[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
-pub struct DeprecatedTokenDistribution {
-    // Account type.
+pub struct DeprecatedLiquidityOracle {
+    /// Account type.
     pub account_type: AccountType,
+    /// Authority.
+    pub authority: Pubkey,
+}
 
-    // Current distribution array
-    pub distribution: DistributionArray,
-
-    // Last update slot
-    pub updated_at: Slot,
+impl From<DeprecatedLiquidityOracle> for LiquidityOracle {
+    fn from(deprecated: DeprecatedLiquidityOracle) -> Self {
+        LiquidityOracle {
+            account_type: deprecated.account_type,
+            authority: deprecated.authority,
+            pending_authority: Pubkey::default(),
+            registry: Pubkey::default(),
+        }
+    }
 }
 
-impl Sealed for DeprecatedTokenDistribution {}
-impl Pack for DeprecatedTokenDistribution {
-    // 1 + (8 * 10) + 8 = 89
-    const LEN: usize = 1 + (8 * TOTAL_DISTRIBUTIONS) + 8;
+impl Sealed for DeprecatedLiquidityOracle {}
+impl Pack for DeprecatedLiquidityOracle {
+    // 1 + 32
+    const LEN: usize = 33;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -39,20 +45,20 @@ impl Pack for DeprecatedTokenDistribution {
             msg!("Failed to deserialize");
             msg!(
                 "Actual LEN: {}",
-                std::mem::size_of::<DeprecatedTokenDistribution>()
+                std::mem::size_of::<DeprecatedLiquidityOracle>()
             );
             ProgramError::InvalidAccountData
         })
     }
 }
 
-impl IsInitialized for DeprecatedTokenDistribution {
+impl IsInitialized for DeprecatedLiquidityOracle {
     fn is_initialized(&self) -> bool {
-        self.account_type == AccountType::TokenOracle
+        self.account_type == AccountType::LiquidityOracle
     }
 }
 
-impl Uninitialized for DeprecatedTokenDistribution {
+impl Uninitialized for DeprecatedLiquidityOracle {
     fn is_uninitialized(&self) -> bool {
         self.account_type == AccountType::default()
     }
@@ -1,13 +1,21 @@
 //! State types.
 
+mod distribution_submissions;
 mod liquidity_oracle;
-mod token_distribution_deprecated;
+mod liquidity_oracle_deprecated;
+mod oracle_signers;
 mod token_oracle;
+mod token_oracle_deprecated;
+mod token_oracle_limits;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+pub use distribution_submissions::*;
 pub use liquidity_oracle::*;
-pub use token_distribution_deprecated::*;
+pub use liquidity_oracle_deprecated::*;
+pub use oracle_signers::*;
 pub use token_oracle::*;
+pub use token_oracle_deprecated::*;
+pub use token_oracle_limits::*;
 
 /// Enum representing the account type managed by the program
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -18,6 +26,12 @@ pub enum AccountType {
     LiquidityOracle,
     /// Pool oracle
     TokenOracle,
+    /// Oracle signers
+    OracleSigners,
+    /// Token oracle distribution submissions
+    DistributionSubmissions,
+    /// Token oracle limits
+    TokenOracleLimits,
 }
 
 impl Default for AccountType {
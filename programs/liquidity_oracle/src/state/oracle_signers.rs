@@ -0,0 +1,177 @@
+//! Oracle signers state definitions.
+
+use super::AccountType;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_utils::EverlendError;
+use solana_program::{
+    clock::Slot,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Max number of oracle signers
+pub const MAX_ORACLE_SIGNERS: usize = 10;
+
+/// Delay of signers and quorum changes once the quorum is enabled, about 1 day
+pub const ORACLE_SIGNERS_UPDATE_DELAY: Slot = 216_000;
+
+/// Signers update waiting for the delay.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct PendingOracleSigners {
+    /// Signers allowed to submit distributions.
+    pub signers: Vec<Pubkey>,
+    /// Submissions required to update distribution.
+    pub quorum: u8,
+    /// Submission window length in slots.
+    pub window: Slot,
+    /// Slot after which the update can be applied.
+    pub effective_slot: Slot,
+}
+
+impl PendingOracleSigners {
+    pub const LEN: usize = (4 + 32 * MAX_ORACLE_SIGNERS) + 1 + 8 + 8;
+}
+
+/// Oracle signers.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct OracleSigners {
+    /// Account type.
+    pub account_type: AccountType,
+    /// Liquidity oracle.
+    pub liquidity_oracle: Pubkey,
+    /// Signers allowed to submit distributions.
+    pub signers: Vec<Pubkey>,
+    /// Submissions required to update distribution. Zero disables the quorum.
+    pub quorum: u8,
+    /// Submission window length in slots.
+    pub window: Slot,
+    /// Signers update waiting for the delay.
+    pub pending: Option<PendingOracleSigners>,
+}
+
+impl OracleSigners {
+    /// Initialize an oracle signers.
+    pub fn init(liquidity_oracle: Pubkey) -> OracleSigners {
+        OracleSigners {
+            account_type: AccountType::OracleSigners,
+            liquidity_oracle,
+            ..Default::default()
+        }
+    }
+
+    /// Update oracle signers. Once the quorum is enabled the update
+    /// is delayed so that signers can react to the authority changes.
+    pub fn update(
+        &mut self,
+        signers: Vec<Pubkey>,
+        quorum: u8,
+        window: Slot,
+        current_slot: Slot,
+    ) -> Result<(), ProgramError> {
+        if signers.len() > MAX_ORACLE_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Signers must be unique
+        if signers
+            .iter()
+            .enumerate()
+            .any(|(index, signer)| signers[..index].contains(signer))
+        {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Quorum must be a majority of signers
+        if quorum > 0 && (quorum as usize > signers.len() || quorum as usize * 2 <= signers.len()) {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if quorum > 0 && window == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if self.is_enabled() {
+            let effective_slot = current_slot
+                .checked_add(ORACLE_SIGNERS_UPDATE_DELAY)
+                .ok_or(EverlendError::MathOverflow)?;
+            msg!("Signers update is applied after slot {}", effective_slot);
+
+            self.pending = Some(PendingOracleSigners {
+                signers,
+                quorum,
+                window,
+                effective_slot,
+            });
+
+            return Ok(());
+        }
+
+        self.signers = signers;
+        self.quorum = quorum;
+        self.window = window;
+        self.pending = None;
+
+        Ok(())
+    }
+
+    /// Apply pending signers update after the delay.
+    pub fn apply_pending(&mut self, current_slot: Slot) -> Result<(), ProgramError> {
+        let pending = self.pending.take().ok_or(ProgramError::InvalidArgument)?;
+        if current_slot < pending.effective_slot {
+            return Err(EverlendError::OracleSignersUpdateDelayed.into());
+        }
+
+        self.signers = pending.signers;
+        self.quorum = pending.quorum;
+        self.window = pending.window;
+
+        Ok(())
+    }
+
+    /// Check quorum is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.quorum > 0
+    }
+
+    /// Check signer is allowed to submit distributions.
+    pub fn contains(&self, signer: &Pubkey) -> bool {
+        self.signers.contains(signer)
+    }
+
+    /// Submission window of the slot.
+    pub fn window_of(&self, slot: Slot) -> u64 {
+        slot / self.window
+    }
+}
+
+impl Sealed for OracleSigners {}
+
+impl Pack for OracleSigners {
+    // 1 + 32 + (4 + 32 * 10) + 1 + 8 + (1 + 341)
+    const LEN: usize =
+        1 + 32 + (4 + 32 * MAX_ORACLE_SIGNERS) + 1 + 8 + (1 + PendingOracleSigners::LEN);
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for OracleSigners {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::OracleSigners
+    }
+}
@@ -2,7 +2,7 @@
 
 use super::AccountType;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
-use everlend_registry::state::TOTAL_DISTRIBUTIONS;
+use everlend_registry::state::MAX_MONEY_MARKETS;
 use everlend_utils::{EverlendError, Uninitialized, PRECISION_SCALER};
 use solana_program::{
     clock::Slot,
@@ -11,7 +11,8 @@ use solana_program::{
     program_pack::{IsInitialized, Pack, Sealed},
 };
 
-pub type DistributionArray = [u64; TOTAL_DISTRIBUTIONS];
+/// Distribution values following the registry market list order
+pub type DistributionArray = Vec<u64>;
 
 #[repr(C)]
 #[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
@@ -50,6 +51,10 @@ impl TokenOracle {
         slot: Slot,
         distribution: DistributionArray,
     ) -> Result<(), ProgramError> {
+        if distribution.len() > MAX_MONEY_MARKETS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
         // Total distribution always should be < 1 * PRECISION_SCALER
         let total_distribution = distribution
             .iter()
@@ -73,6 +78,10 @@ impl TokenOracle {
         slot: Slot,
         rates: DistributionArray,
     ) -> Result<(), ProgramError> {
+        if rates.len() > MAX_MONEY_MARKETS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
         self.reserve_rates = Distribution {
             values: rates,
             updated_at: slot,
@@ -92,9 +101,10 @@ impl Pack for TokenOracle {
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        Self::try_from_slice(src).map_err(|_| {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
             msg!("Failed to deserialize");
-            msg!("Actual LEN: {}", std::mem::size_of::<TokenOracle>());
+            msg!(&err.to_string());
             ProgramError::InvalidAccountData
         })
     }
@@ -113,5 +123,10 @@ impl Uninitialized for TokenOracle {
 }
 
 impl Distribution {
-    pub const LEN: usize = (8 * TOTAL_DISTRIBUTIONS) + 8;
+    pub const LEN: usize = (4 + 8 * MAX_MONEY_MARKETS) + 8;
+
+    /// Get value by money market index, missing values are zero
+    pub fn get(&self, index: usize) -> u64 {
+        self.values.get(index).copied().unwrap_or_default()
+    }
 }
@@ -0,0 +1,94 @@
+//! Deprecated token oracle state definitions.
+
+use super::{AccountType, Distribution, TokenOracle};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_registry::state::TOTAL_DISTRIBUTIONS;
+use everlend_utils::Uninitialized;
+use solana_program::{
+    clock::Slot,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+};
+
+/// Fixed size distribution array
+pub type DeprecatedDistributionArray = [u64; TOTAL_DISTRIBUTIONS];
+
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct DeprecatedDistribution {
+    /// Current distribution array
+    pub values: DeprecatedDistributionArray,
+    /// Last update slot
+    pub updated_at: Slot,
+}
+
+impl DeprecatedDistribution {
+    pub const LEN: usize = (8 * TOTAL_DISTRIBUTIONS) + 8;
+}
+
+impl From<DeprecatedDistribution> for Distribution {
+    fn from(deprecated: DeprecatedDistribution) -> Self {
+        Distribution {
+            values: deprecated.values.to_vec(),
+            updated_at: deprecated.updated_at,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct DeprecatedTokenOracle {
+    /// Account type.
+    pub account_type: AccountType,
+
+    /// MM liquidity distribution
+    pub liquidity_distribution: DeprecatedDistribution,
+
+    /// Liquidity to collateral rates of reserves
+    pub reserve_rates: DeprecatedDistribution,
+}
+
+impl From<DeprecatedTokenOracle> for TokenOracle {
+    fn from(deprecated: DeprecatedTokenOracle) -> Self {
+        TokenOracle {
+            account_type: deprecated.account_type,
+            liquidity_distribution: deprecated.liquidity_distribution.into(),
+            reserve_rates: deprecated.reserve_rates.into(),
+        }
+    }
+}
+
+impl Sealed for DeprecatedTokenOracle {}
+impl Pack for DeprecatedTokenOracle {
+    // 1 + 88 + 88 = 177
+    const LEN: usize = 1 + DeprecatedDistribution::LEN + DeprecatedDistribution::LEN;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Failed to deserialize");
+            msg!(
+                "Actual LEN: {}",
+                std::mem::size_of::<DeprecatedTokenOracle>()
+            );
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for DeprecatedTokenOracle {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::TokenOracle
+    }
+}
+
+impl Uninitialized for DeprecatedTokenOracle {
+    fn is_uninitialized(&self) -> bool {
+        self.account_type == AccountType::default()
+    }
+}
@@ -0,0 +1,115 @@
+//! Token oracle limits state definitions.
+
+use super::{AccountType, DistributionArray, TokenOracle};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_utils::{abs_diff, EverlendError};
+use solana_program::{
+    clock::Slot,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Token oracle limits.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct TokenOracleLimits {
+    /// Account type.
+    pub account_type: AccountType,
+    /// Token oracle.
+    pub token_oracle: Pubkey,
+    /// Max absolute change of market distribution per update. Zero disables the limit.
+    pub max_distribution_change: u64,
+    /// Min slots between distribution updates. Zero disables the limit.
+    pub min_update_interval: Slot,
+    /// Distribution updates are disabled.
+    pub frozen: bool,
+}
+
+impl TokenOracleLimits {
+    /// Initialize a token oracle limits.
+    pub fn init(token_oracle: Pubkey) -> TokenOracleLimits {
+        TokenOracleLimits {
+            account_type: AccountType::TokenOracleLimits,
+            token_oracle,
+            ..Default::default()
+        }
+    }
+
+    /// Update token oracle limits.
+    pub fn update(
+        &mut self,
+        max_distribution_change: u64,
+        min_update_interval: Slot,
+        frozen: bool,
+    ) {
+        self.max_distribution_change = max_distribution_change;
+        self.min_update_interval = min_update_interval;
+        self.frozen = frozen;
+    }
+
+    /// Check new liquidity distribution of the token oracle.
+    pub fn check(
+        &self,
+        oracle: &TokenOracle,
+        slot: Slot,
+        distribution: &DistributionArray,
+    ) -> Result<(), ProgramError> {
+        if self.frozen {
+            return Err(EverlendError::TokenOracleFrozen.into());
+        }
+
+        if self.min_update_interval > 0
+            && slot
+                < oracle
+                    .liquidity_distribution
+                    .updated_at
+                    .saturating_add(self.min_update_interval)
+        {
+            return Err(EverlendError::LiquidityDistributionUpdateTooEarly.into());
+        }
+
+        if self.max_distribution_change > 0 {
+            let len = distribution
+                .len()
+                .max(oracle.liquidity_distribution.values.len());
+            for index in 0..len {
+                let value = distribution.get(index).copied().unwrap_or_default();
+                let change = abs_diff(value, oracle.liquidity_distribution.get(index))?;
+                if change > self.max_distribution_change {
+                    msg!("Market {} distribution change: {}", index, change);
+                    return Err(EverlendError::LiquidityDistributionChangeExceeded.into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Sealed for TokenOracleLimits {}
+
+impl Pack for TokenOracleLimits {
+    // 1 + 32 + 8 + 8 + 1
+    const LEN: usize = 50;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Failed to deserialize");
+            msg!("Actual LEN: {}", std::mem::size_of::<TokenOracleLimits>());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for TokenOracleLimits {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::TokenOracleLimits
+    }
+}
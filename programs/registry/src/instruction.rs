@@ -1,8 +1,15 @@
 //! Instruction types
 
-use crate::instructions::{UpdateRegistryData, UpdateRegistryMarketsData};
+use crate::instructions::{AddRegistryMarketData, UpdateRegistryData, UpdateRegistryMarketData};
+use crate::state::{ActionAccount, PauseFlags, Role};
+use crate::{
+    find_guardian_program_address, find_pause_program_address, find_role_program_address,
+    find_timelock_action_program_address, find_timelock_authority_program_address,
+    find_timelock_program_address,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
+    clock::Slot,
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     system_program, sysvar,
@@ -21,14 +28,14 @@ pub enum RegistryInstruction {
     /// [R] Rent sysvar
     Init,
 
-    /// Update manager
+    /// Propose a new manager, takes effect after the new manager accepts it
     ///
     /// Accounts:
     /// [W] Registry
-    /// [S] Old manager
-    /// [S] New manager
+    /// [S] Manager
+    /// [R] New manager
     ///
-    UpdateManager,
+    ProposeManager,
 
     /// Set a registry config
     ///
@@ -40,15 +47,163 @@ pub enum RegistryInstruction {
         data: UpdateRegistryData,
     },
 
-    /// Update registry markets
+    /// Add money market to the end of the registry market list
     ///
     /// Accounts:
     /// [W] Registry
     /// [S] Manager
-    UpdateRegistryMarkets {
-        /// MoneyMarkets data to update
-        data: UpdateRegistryMarketsData,
+    AddRegistryMarket {
+        /// Money market data to add
+        data: AddRegistryMarketData,
+    },
+
+    /// Update registry money market
+    ///
+    /// Accounts:
+    /// [W] Registry
+    /// [S] Manager
+    UpdateRegistryMarket {
+        /// Money market index
+        index: u8,
+        /// Money market data to update
+        data: UpdateRegistryMarketData,
+    },
+
+    /// Migrate registry markets from fixed slots to the market list
+    ///
+    /// Accounts:
+    /// [W] Registry
+    /// [WS] Manager
+    /// [R] Rent sysvar
+    /// [R] System program
+    MigrateRegistry,
+
+    /// Set registry guardian allowed to pause operations
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [W] Registry guardian
+    /// [WS] Manager
+    /// [R] Guardian
+    /// [R] Rent sysvar
+    /// [R] System program
+    SetGuardian,
+
+    /// Set paused operations of the pool or global ones
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [R] Registry guardian
+    /// [W] Pause
+    /// [WS] Guardian
+    /// [R] Rent sysvar
+    /// [R] System program
+    SetPause {
+        /// Pool, default pubkey for the global pause
+        pool: Pubkey,
+        /// Paused operations
+        flags: PauseFlags,
+    },
+
+    /// Accept the manager proposal
+    ///
+    /// Accounts:
+    /// [W] Registry
+    /// [S] New manager
+    AcceptManager,
+
+    /// Cancel the manager proposal
+    ///
+    /// Accounts:
+    /// [W] Registry
+    /// [S] Manager
+    CancelManagerProposal,
+
+    /// Grant the role to the holder
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [W] Registry role - uninitialized
+    /// [WS] Manager
+    /// [R] Holder
+    /// [R] Rent sysvar
+    /// [R] System program
+    GrantRole {
+        /// Granted role
+        role: Role,
+    },
+
+    /// Revoke the role from the holder and close the registry role account
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [W] Registry role
+    /// [WS] Manager
+    /// [R] Holder
+    RevokeRole {
+        /// Revoked role
+        role: Role,
+    },
+
+    /// Set the timelock proposer and delay
+    ///
+    /// Managers of the registry and the pool markets are handed over to the timelock
+    /// authority with ProposeManager, the proposal is accepted by AcceptManager queued
+    /// as the timelock action. Admin and Risk roles are rejected once the timelock is set.
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [W] Timelock
+    /// [WS] Manager
+    /// [R] Rent sysvar
+    /// [R] System program
+    SetTimelock {
+        /// The address allowed to queue and cancel actions
+        proposer: Pubkey,
+        /// Delay between queueing and execution of the action
+        delay: Slot,
+    },
+
+    /// Queue the action executed with the timelock authority after the delay
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [W] Timelock
+    /// [W] Timelock action - uninitialized
+    /// [WS] Proposer
+    /// [R] Clock sysvar
+    /// [R] Rent sysvar
+    /// [R] System program
+    QueueAction {
+        /// Program of the action instruction
+        program_id: Pubkey,
+        /// Accounts of the action instruction
+        accounts: Vec<ActionAccount>,
+        /// Data of the action instruction
+        data: Vec<u8>,
     },
+
+    /// Cancel the queued action and close the timelock action account
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [R] Timelock
+    /// [W] Timelock action
+    /// [WS] Proposer
+    CancelAction,
+
+    /// Execute the queued action after the delay, can be called by anyone
+    ///
+    /// Accounts:
+    /// [R] Registry
+    /// [R] Timelock
+    /// [R] Timelock authority
+    /// [W] Timelock action
+    /// [W] Proposer of the action
+    /// [R] Clock sysvar
+    /// [R] Action program
+    /// Action accounts
+    ExecuteAction,
 }
 
 /// Creates 'Init' instruction.
@@ -63,9 +218,8 @@ pub fn init(program_id: &Pubkey, registry: &Pubkey, manager: &Pubkey) -> Instruc
     Instruction::new_with_borsh(*program_id, &RegistryInstruction::Init, accounts)
 }
 
-/// Creates 'UpdateManager' instruction.
-#[allow(clippy::too_many_arguments)]
-pub fn update_manager(
+/// Creates 'ProposeManager' instruction.
+pub fn propose_manager(
     program_id: &Pubkey,
     registry: &Pubkey,
     manager: &Pubkey,
@@ -74,10 +228,38 @@ pub fn update_manager(
     let accounts = vec![
         AccountMeta::new(*registry, false),
         AccountMeta::new_readonly(*manager, true),
+        AccountMeta::new_readonly(*new_manager, false),
+    ];
+
+    Instruction::new_with_borsh(*program_id, &RegistryInstruction::ProposeManager, accounts)
+}
+
+/// Creates 'AcceptManager' instruction.
+pub fn accept_manager(program_id: &Pubkey, registry: &Pubkey, new_manager: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*registry, false),
         AccountMeta::new_readonly(*new_manager, true),
     ];
 
-    Instruction::new_with_borsh(*program_id, &RegistryInstruction::UpdateManager, accounts)
+    Instruction::new_with_borsh(*program_id, &RegistryInstruction::AcceptManager, accounts)
+}
+
+/// Creates 'CancelManagerProposal' instruction.
+pub fn cancel_manager_proposal(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    manager: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*registry, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RegistryInstruction::CancelManagerProposal,
+        accounts,
+    )
 }
 
 /// Creates 'UpdateRegistry' instruction.
@@ -99,12 +281,32 @@ pub fn update_registry(
     )
 }
 
-/// Creates 'UpdateRegistryMarkets' instruction.
-pub fn update_registry_markets(
+/// Creates 'AddRegistryMarket' instruction.
+pub fn add_registry_market(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    manager: &Pubkey,
+    data: AddRegistryMarketData,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*registry, false),
+        AccountMeta::new_readonly(*manager, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RegistryInstruction::AddRegistryMarket { data },
+        accounts,
+    )
+}
+
+/// Creates 'UpdateRegistryMarket' instruction.
+pub fn update_registry_market(
     program_id: &Pubkey,
     registry: &Pubkey,
     manager: &Pubkey,
-    data: UpdateRegistryMarketsData,
+    index: u8,
+    data: UpdateRegistryMarketData,
 ) -> Instruction {
     let accounts = vec![
         AccountMeta::new(*registry, false),
@@ -113,7 +315,244 @@ pub fn update_registry_markets(
 
     Instruction::new_with_borsh(
         *program_id,
-        &RegistryInstruction::UpdateRegistryMarkets { data },
+        &RegistryInstruction::UpdateRegistryMarket { index, data },
+        accounts,
+    )
+}
+
+/// Creates 'MigrateRegistry' instruction.
+pub fn migrate_registry(program_id: &Pubkey, registry: &Pubkey, manager: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*registry, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(*program_id, &RegistryInstruction::MigrateRegistry, accounts)
+}
+
+/// Creates 'SetGuardian' instruction.
+pub fn set_guardian(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    manager: &Pubkey,
+    guardian: &Pubkey,
+) -> Instruction {
+    let (registry_guardian, _) = find_guardian_program_address(program_id, registry);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new(registry_guardian, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(*guardian, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(*program_id, &RegistryInstruction::SetGuardian, accounts)
+}
+
+/// Creates 'SetPause' instruction.
+pub fn set_pause(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    guardian: &Pubkey,
+    pool: &Pubkey,
+    flags: PauseFlags,
+) -> Instruction {
+    let (registry_guardian, _) = find_guardian_program_address(program_id, registry);
+    let (pause, _) = find_pause_program_address(program_id, registry, pool);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(registry_guardian, false),
+        AccountMeta::new(pause, false),
+        AccountMeta::new(*guardian, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RegistryInstruction::SetPause { pool: *pool, flags },
+        accounts,
+    )
+}
+
+/// Creates 'GrantRole' instruction.
+pub fn grant_role(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    manager: &Pubkey,
+    holder: &Pubkey,
+    role: Role,
+) -> Instruction {
+    let (registry_role, _) = find_role_program_address(program_id, registry, holder, role);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new(registry_role, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(*holder, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RegistryInstruction::GrantRole { role },
+        accounts,
+    )
+}
+
+/// Creates 'RevokeRole' instruction.
+pub fn revoke_role(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    manager: &Pubkey,
+    holder: &Pubkey,
+    role: Role,
+) -> Instruction {
+    let (registry_role, _) = find_role_program_address(program_id, registry, holder, role);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new(registry_role, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(*holder, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RegistryInstruction::RevokeRole { role },
         accounts,
     )
 }
+
+/// Role accounts appended to the instruction signed by the role holder instead of the manager
+pub fn role_accounts(registry: &Pubkey, holder: &Pubkey, role: Role) -> Vec<AccountMeta> {
+    let (registry_role, _) = find_role_program_address(&crate::id(), registry, holder, role);
+    let (timelock, _) = find_timelock_program_address(&crate::id(), registry);
+
+    vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(registry_role, false),
+        AccountMeta::new_readonly(timelock, false),
+    ]
+}
+
+/// Creates 'SetTimelock' instruction.
+pub fn set_timelock(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    manager: &Pubkey,
+    proposer: &Pubkey,
+    delay: Slot,
+) -> Instruction {
+    let (timelock, _) = find_timelock_program_address(program_id, registry);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new(timelock, false),
+        AccountMeta::new(*manager, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RegistryInstruction::SetTimelock {
+            proposer: *proposer,
+            delay,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'QueueAction' instruction.
+pub fn queue_action(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    proposer: &Pubkey,
+    action_id: u64,
+    action: &Instruction,
+) -> Instruction {
+    let (timelock, _) = find_timelock_program_address(program_id, registry);
+    let (timelock_action, _) =
+        find_timelock_action_program_address(program_id, &timelock, action_id);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new(timelock, false),
+        AccountMeta::new(timelock_action, false),
+        AccountMeta::new(*proposer, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RegistryInstruction::QueueAction {
+            program_id: action.program_id,
+            accounts: action.accounts.iter().map(ActionAccount::from).collect(),
+            data: action.data.clone(),
+        },
+        accounts,
+    )
+}
+
+/// Creates 'CancelAction' instruction.
+pub fn cancel_action(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    proposer: &Pubkey,
+    action_id: u64,
+) -> Instruction {
+    let (timelock, _) = find_timelock_program_address(program_id, registry);
+    let (timelock_action, _) =
+        find_timelock_action_program_address(program_id, &timelock, action_id);
+
+    let accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(timelock, false),
+        AccountMeta::new(timelock_action, false),
+        AccountMeta::new(*proposer, true),
+    ];
+
+    Instruction::new_with_borsh(*program_id, &RegistryInstruction::CancelAction, accounts)
+}
+
+/// Creates 'ExecuteAction' instruction.
+pub fn execute_action(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    proposer: &Pubkey,
+    action_id: u64,
+    action: &Instruction,
+) -> Instruction {
+    let (timelock, _) = find_timelock_program_address(program_id, registry);
+    let (timelock_authority, _) = find_timelock_authority_program_address(program_id, registry);
+    let (timelock_action, _) =
+        find_timelock_action_program_address(program_id, &timelock, action_id);
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*registry, false),
+        AccountMeta::new_readonly(timelock, false),
+        AccountMeta::new_readonly(timelock_authority, false),
+        AccountMeta::new(timelock_action, false),
+        AccountMeta::new(*proposer, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(action.program_id, false),
+    ];
+
+    // The timelock authority signs the action on execution
+    accounts.extend(action.accounts.iter().map(|a| AccountMeta {
+        pubkey: a.pubkey,
+        is_signer: false,
+        is_writable: a.is_writable,
+    }));
+
+    Instruction::new_with_borsh(*program_id, &RegistryInstruction::ExecuteAction, accounts)
+}
@@ -0,0 +1,42 @@
+use everlend_utils::{assert_account_key, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+
+use crate::state::Registry;
+
+/// Instruction context
+pub struct AcceptManagerContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    new_manager: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> AcceptManagerContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<AcceptManagerContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+        let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let new_manager = AccountLoader::next_signer(account_info_iter)?;
+
+        Ok(AcceptManagerContext {
+            registry,
+            new_manager,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, _program_id: &Pubkey) -> ProgramResult {
+        let mut r = Registry::unpack(&self.registry.data.borrow())?;
+        assert_account_key(self.new_manager, &r.pending_manager)?;
+
+        r.manager = r.pending_manager;
+        r.pending_manager = Pubkey::default();
+        Registry::pack(r, *self.registry.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
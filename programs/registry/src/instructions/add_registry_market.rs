@@ -0,0 +1,65 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use everlend_utils::{assert_account_key, integrations::MoneyMarket, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+
+use crate::state::{MarketStatus, Registry, RegistryMarket, RegistryMarkets};
+
+/// Instruction data
+#[derive(BorshDeserialize, BorshSerialize, Clone, PartialEq, Debug)]
+pub struct AddRegistryMarketData {
+    /// Money market program id
+    pub money_market: Pubkey,
+    /// Collateral pool market
+    pub collateral_pool_market: Pubkey,
+    /// Money market adapter
+    pub adapter: MoneyMarket,
+}
+
+/// Instruction context
+pub struct AddRegistryMarketContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> AddRegistryMarketContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<AddRegistryMarketContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+        let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+
+        Ok(AddRegistryMarketContext { registry, manager })
+    }
+
+    /// Process instruction
+    pub fn process(&self, _program_id: &Pubkey, data: AddRegistryMarketData) -> ProgramResult {
+        {
+            let r = Registry::unpack(&self.registry.data.borrow())?;
+            assert_account_key(self.manager, &r.manager)?;
+        }
+
+        let mut markets = RegistryMarkets::unpack_from_slice(&self.registry.data.borrow())?;
+
+        // Money market program can be registered only once
+        if markets.contains_money_market(&data.money_market) {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        markets.add(RegistryMarket {
+            money_market: data.money_market,
+            collateral_pool_market: data.collateral_pool_market,
+            adapter: data.adapter,
+            status: MarketStatus::Active,
+        })?;
+
+        RegistryMarkets::pack_into_slice(&markets, *self.registry.data.borrow_mut());
+
+        Ok(())
+    }
+}
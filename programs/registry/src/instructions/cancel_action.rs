@@ -0,0 +1,66 @@
+use everlend_utils::{assert_account_key, AccountLoader, EverlendError};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+
+use crate::{
+    find_timelock_program_address,
+    state::{Timelock, TimelockAction},
+};
+
+/// Instruction context
+pub struct CancelActionContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    timelock: &'a AccountInfo<'b>,
+    action: &'a AccountInfo<'b>,
+    proposer: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> CancelActionContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<CancelActionContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+        let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let timelock = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let action = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let proposer = AccountLoader::next_signer(account_info_iter)?;
+
+        Ok(CancelActionContext {
+            registry,
+            timelock,
+            action,
+            proposer,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
+        let (timelock_pubkey, _) = find_timelock_program_address(program_id, self.registry.key);
+        assert_account_key(self.timelock, &timelock_pubkey)?;
+
+        let timelock = Timelock::unpack(&self.timelock.data.borrow())?;
+        assert_account_key(self.proposer, &timelock.proposer)?;
+
+        let action = TimelockAction::unpack(&self.action.data.borrow())?;
+        assert_account_key(self.timelock, &action.timelock)?;
+
+        // Close the action account, rent goes to the current proposer
+        let proposer_starting_lamports = self.proposer.lamports();
+        let action_lamports = self.action.lamports();
+
+        **self.action.lamports.borrow_mut() = 0;
+        **self.proposer.lamports.borrow_mut() = proposer_starting_lamports
+            .checked_add(action_lamports)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        TimelockAction::pack(Default::default(), *self.action.data.borrow_mut())?;
+
+        msg!("Timelock action {} cancelled", action.id);
+
+        Ok(())
+    }
+}
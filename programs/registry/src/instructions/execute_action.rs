@@ -0,0 +1,116 @@
+use everlend_utils::{assert_account_key, AccountLoader, EverlendError};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::{
+    find_timelock_authority_program_address, find_timelock_program_address,
+    state::{Timelock, TimelockAction},
+};
+
+/// Instruction context
+pub struct ExecuteActionContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    timelock: &'a AccountInfo<'b>,
+    timelock_authority: &'a AccountInfo<'b>,
+    action: &'a AccountInfo<'b>,
+    proposer: &'a AccountInfo<'b>,
+    clock: &'a AccountInfo<'b>,
+    action_accounts: &'a [AccountInfo<'b>],
+}
+
+impl<'a, 'b> ExecuteActionContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<ExecuteActionContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+        let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let timelock = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let timelock_authority = AccountLoader::next_unchecked(account_info_iter)?;
+        let action = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let proposer = AccountLoader::next_unchecked(account_info_iter)?;
+        let clock = AccountLoader::next_with_key(account_info_iter, &Clock::id())?;
+
+        // Action program and accounts
+        let action_accounts = &accounts[6..];
+
+        Ok(ExecuteActionContext {
+            registry,
+            timelock,
+            timelock_authority,
+            action,
+            proposer,
+            clock,
+            action_accounts,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
+        let (timelock_pubkey, _) = find_timelock_program_address(program_id, self.registry.key);
+        assert_account_key(self.timelock, &timelock_pubkey)?;
+
+        // Get timelock state to check initialized
+        Timelock::unpack(&self.timelock.data.borrow())?;
+
+        let (timelock_authority_pubkey, bump_seed) =
+            find_timelock_authority_program_address(program_id, self.registry.key);
+        assert_account_key(self.timelock_authority, &timelock_authority_pubkey)?;
+
+        let action = TimelockAction::unpack(&self.action.data.borrow())?;
+        assert_account_key(self.timelock, &action.timelock)?;
+        assert_account_key(self.proposer, &action.proposer)?;
+
+        let clock = Clock::from_account_info(self.clock)?;
+        if clock.slot < action.execution_slot {
+            msg!(
+                "Timelock action {} is executable after slot {}",
+                action.id,
+                action.execution_slot
+            );
+            return Err(EverlendError::TimelockActionNotReady.into());
+        }
+
+        let signers_seeds = &[
+            "timelock_authority".as_bytes(),
+            &self.registry.key.to_bytes(),
+            &[bump_seed],
+        ];
+
+        // Close the action account before the call, so it can't execute the action again.
+        // Rent goes back to the proposer
+        let proposer_starting_lamports = self.proposer.lamports();
+        let action_lamports = self.action.lamports();
+
+        **self.action.lamports.borrow_mut() = 0;
+        **self.proposer.lamports.borrow_mut() = proposer_starting_lamports
+            .checked_add(action_lamports)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        TimelockAction::pack(Default::default(), *self.action.data.borrow_mut())?;
+
+        invoke_signed(
+            &action.instruction(),
+            self.action_accounts,
+            &[signers_seeds],
+        )?;
+
+        msg!(
+            "Timelock action {} executed: program {}",
+            action.id,
+            action.program_id
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use everlend_utils::{assert_account_key, cpi, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::{
+    find_role_program_address,
+    state::{Registry, RegistryRole, Role},
+};
+
+/// Instruction context
+pub struct GrantRoleContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    registry_role: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+    holder: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> GrantRoleContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<GrantRoleContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+        let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let registry_role = AccountLoader::next_uninitialized(account_info_iter)?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+        let holder = AccountLoader::next_unchecked(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        Ok(GrantRoleContext {
+            registry,
+            registry_role,
+            manager,
+            holder,
+            rent,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey, role: Role) -> ProgramResult {
+        {
+            let r = Registry::unpack(&self.registry.data.borrow())?;
+            assert_account_key(self.manager, &r.manager)?;
+        }
+
+        let (registry_role_pubkey, bump_seed) =
+            find_role_program_address(program_id, self.registry.key, self.holder.key, role);
+        assert_account_key(self.registry_role, &registry_role_pubkey)?;
+
+        let signers_seeds = &[
+            "role".as_bytes(),
+            &self.registry.key.to_bytes(),
+            &self.holder.key.to_bytes(),
+            &[role as u8],
+            &[bump_seed],
+        ];
+
+        cpi::system::create_account::<RegistryRole>(
+            program_id,
+            self.manager.clone(),
+            self.registry_role.clone(),
+            &[signers_seeds],
+            &Rent::from_account_info(self.rent)?,
+        )?;
+
+        let registry_role = RegistryRole::init(*self.registry.key, *self.holder.key, role);
+        RegistryRole::pack(registry_role, *self.registry_role.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -10,7 +10,7 @@ use solana_program::{
     sysvar::{Sysvar, SysvarId},
 };
 
-use crate::state::Registry;
+use crate::state::{Registry, RegistryMarkets};
 
 /// Instruction context
 pub struct InitContext<'a, 'b> {
@@ -53,6 +53,10 @@ impl<'a, 'b> InitContext<'a, 'b> {
 
         let r = Registry::init(*self.manager.key);
         Registry::pack(r, *self.registry.data.borrow_mut())?;
+        RegistryMarkets::pack_into_slice(
+            &RegistryMarkets::init(),
+            *self.registry.data.borrow_mut(),
+        );
 
         Ok(())
     }
@@ -0,0 +1,62 @@
+use everlend_utils::{assert_account_key, cpi::system::realloc_with_rent, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::state::{DeprecatedRegistryMarkets, Registry, RegistryMarkets};
+
+/// Instruction context
+pub struct MigrateRegistryContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> MigrateRegistryContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<MigrateRegistryContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+        let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        Ok(MigrateRegistryContext {
+            registry,
+            manager,
+            rent,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, _program_id: &Pubkey) -> ProgramResult {
+        let registry = Registry::unpack_deprecated(&self.registry.data.borrow())?;
+        assert_account_key(self.manager, &registry.manager)?;
+
+        let markets = DeprecatedRegistryMarkets::unpack_from_slice(&self.registry.data.borrow())?
+            .to_markets();
+
+        realloc_with_rent(
+            self.registry,
+            self.manager,
+            &Rent::from_account_info(self.rent)?,
+            Registry::LEN,
+        )?;
+
+        Registry::pack(registry, *self.registry.data.borrow_mut())?;
+        RegistryMarkets::pack_into_slice(&markets, *self.registry.data.borrow_mut());
+
+        Ok(())
+    }
+}
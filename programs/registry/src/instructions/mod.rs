@@ -1,10 +1,34 @@
 //! Program instructions
+mod accept_manager;
+mod add_registry_market;
+mod cancel_action;
+mod cancel_manager_proposal;
+mod execute_action;
+mod grant_role;
 mod init;
-mod update_manager;
+mod migrate_registry;
+mod propose_manager;
+mod queue_action;
+mod revoke_role;
+mod set_guardian;
+mod set_pause;
+mod set_timelock;
 mod update_registry;
-mod update_registry_markets;
+mod update_registry_market;
 
+pub use accept_manager::*;
+pub use add_registry_market::*;
+pub use cancel_action::*;
+pub use cancel_manager_proposal::*;
+pub use execute_action::*;
+pub use grant_role::*;
 pub use init::*;
-pub use update_manager::*;
+pub use migrate_registry::*;
+pub use propose_manager::*;
+pub use queue_action::*;
+pub use revoke_role::*;
+pub use set_guardian::*;
+pub use set_pause::*;
+pub use set_timelock::*;
 pub use update_registry::*;
-pub use update_registry_markets::*;
+pub use update_registry_market::*;
@@ -7,24 +7,24 @@ use solana_program::{
 use crate::state::Registry;
 
 /// Instruction context
-pub struct UpdateManagerContext<'a, 'b> {
+pub struct ProposeManagerContext<'a, 'b> {
     registry: &'a AccountInfo<'b>,
     manager: &'a AccountInfo<'b>,
     new_manager: &'a AccountInfo<'b>,
 }
 
-impl<'a, 'b> UpdateManagerContext<'a, 'b> {
+impl<'a, 'b> ProposeManagerContext<'a, 'b> {
     /// New instruction context
     pub fn new(
         program_id: &Pubkey,
         accounts: &'a [AccountInfo<'b>],
-    ) -> Result<UpdateManagerContext<'a, 'b>, ProgramError> {
+    ) -> Result<ProposeManagerContext<'a, 'b>, ProgramError> {
         let account_info_iter = &mut accounts.iter().enumerate();
         let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let manager = AccountLoader::next_signer(account_info_iter)?;
-        let new_manager = AccountLoader::next_signer(account_info_iter)?;
+        let new_manager = AccountLoader::next_unchecked(account_info_iter)?;
 
-        Ok(UpdateManagerContext {
+        Ok(ProposeManagerContext {
             registry,
             manager,
             new_manager,
@@ -36,7 +36,7 @@ impl<'a, 'b> UpdateManagerContext<'a, 'b> {
         let mut r = Registry::unpack(&self.registry.data.borrow())?;
         assert_account_key(self.manager, &r.manager)?;
 
-        r.manager = *self.new_manager.key;
+        r.pending_manager = *self.new_manager.key;
         Registry::pack(r, *self.registry.data.borrow_mut())?;
 
         Ok(())
@@ -0,0 +1,130 @@
+use everlend_utils::{assert_account_key, cpi, AccountLoader, EverlendError};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::{
+    find_timelock_action_program_address, find_timelock_authority_program_address,
+    find_timelock_program_address,
+    state::{ActionAccount, InitTimelockActionParams, Timelock, TimelockAction},
+};
+
+/// Instruction context
+pub struct QueueActionContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    timelock: &'a AccountInfo<'b>,
+    action: &'a AccountInfo<'b>,
+    proposer: &'a AccountInfo<'b>,
+    clock: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> QueueActionContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<QueueActionContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+        let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let timelock = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let action = AccountLoader::next_uninitialized(account_info_iter)?;
+        let proposer = AccountLoader::next_signer(account_info_iter)?;
+        let clock = AccountLoader::next_with_key(account_info_iter, &Clock::id())?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        Ok(QueueActionContext {
+            registry,
+            timelock,
+            action,
+            proposer,
+            clock,
+            rent,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(
+        &self,
+        program_id: &Pubkey,
+        action_program_id: Pubkey,
+        accounts: Vec<ActionAccount>,
+        data: Vec<u8>,
+    ) -> ProgramResult {
+        let (timelock_pubkey, _) = find_timelock_program_address(program_id, self.registry.key);
+        assert_account_key(self.timelock, &timelock_pubkey)?;
+
+        let mut timelock = Timelock::unpack(&self.timelock.data.borrow())?;
+        assert_account_key(self.proposer, &timelock.proposer)?;
+
+        // Only the timelock authority can be signed on execution
+        let (timelock_authority, _) =
+            find_timelock_authority_program_address(program_id, self.registry.key);
+        if accounts
+            .iter()
+            .any(|a| a.is_signer && a.pubkey != timelock_authority)
+        {
+            msg!("Action signers other than the timelock authority are not allowed");
+            return Err(EverlendError::InvalidTimelockAction.into());
+        }
+
+        let action_id = timelock.next_action_id()?;
+        let (action_pubkey, bump_seed) =
+            find_timelock_action_program_address(program_id, self.timelock.key, action_id);
+        assert_account_key(self.action, &action_pubkey)?;
+
+        let clock = Clock::from_account_info(self.clock)?;
+        let execution_slot = clock
+            .slot
+            .checked_add(timelock.delay)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        let action = TimelockAction::init(InitTimelockActionParams {
+            timelock: *self.timelock.key,
+            id: action_id,
+            proposer: *self.proposer.key,
+            execution_slot,
+            program_id: action_program_id,
+            accounts,
+            data,
+        })?;
+
+        let signers_seeds = &[
+            "action".as_bytes(),
+            &self.timelock.key.to_bytes(),
+            &action_id.to_le_bytes(),
+            &[bump_seed],
+        ];
+
+        cpi::system::create_account::<TimelockAction>(
+            program_id,
+            self.proposer.clone(),
+            self.action.clone(),
+            &[signers_seeds],
+            &Rent::from_account_info(self.rent)?,
+        )?;
+
+        TimelockAction::pack(action, *self.action.data.borrow_mut())?;
+        Timelock::pack(timelock, *self.timelock.data.borrow_mut())?;
+
+        msg!(
+            "Timelock action {} queued: program {} execution slot {}",
+            action_id,
+            action_program_id,
+            execution_slot
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use everlend_utils::{assert_account_key, AccountLoader, EverlendError};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_pack::Pack, pubkey::Pubkey,
+};
+
+use crate::{
+    find_role_program_address,
+    state::{Registry, RegistryRole, Role},
+};
+
+/// Instruction context
+pub struct RevokeRoleContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    registry_role: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+    holder: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> RevokeRoleContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<RevokeRoleContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+        let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let registry_role = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+        let holder = AccountLoader::next_unchecked(account_info_iter)?;
+
+        Ok(RevokeRoleContext {
+            registry,
+            registry_role,
+            manager,
+            holder,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey, role: Role) -> ProgramResult {
+        {
+            let r = Registry::unpack(&self.registry.data.borrow())?;
+            assert_account_key(self.manager, &r.manager)?;
+        }
+
+        let (registry_role_pubkey, _) =
+            find_role_program_address(program_id, self.registry.key, self.holder.key, role);
+        assert_account_key(self.registry_role, &registry_role_pubkey)?;
+
+        // Get registry role state to check initialized
+        RegistryRole::unpack(&self.registry_role.data.borrow())?;
+
+        // Close the registry role account, rent goes back to the manager
+        let manager_starting_lamports = self.manager.lamports();
+        let registry_role_lamports = self.registry_role.lamports();
+
+        **self.registry_role.lamports.borrow_mut() = 0;
+        **self.manager.lamports.borrow_mut() = manager_starting_lamports
+            .checked_add(registry_role_lamports)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        RegistryRole::pack(Default::default(), *self.registry_role.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use everlend_utils::{assert_account_key, cpi, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::{
+    find_guardian_program_address,
+    state::{Registry, RegistryGuardian},
+};
+
+/// Instruction context
+pub struct SetGuardianContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    registry_guardian: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+    guardian: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> SetGuardianContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<SetGuardianContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+        let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let registry_guardian = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+        let guardian = AccountLoader::next_unchecked(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        Ok(SetGuardianContext {
+            registry,
+            registry_guardian,
+            manager,
+            guardian,
+            rent,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
+        {
+            let r = Registry::unpack(&self.registry.data.borrow())?;
+            assert_account_key(self.manager, &r.manager)?;
+        }
+
+        let (registry_guardian_pubkey, bump_seed) =
+            find_guardian_program_address(program_id, self.registry.key);
+        assert_account_key(self.registry_guardian, &registry_guardian_pubkey)?;
+
+        if self.registry_guardian.owner.eq(&Pubkey::default()) {
+            let signers_seeds = &[
+                "guardian".as_bytes(),
+                &self.registry.key.to_bytes(),
+                &[bump_seed],
+            ];
+
+            cpi::system::create_account::<RegistryGuardian>(
+                program_id,
+                self.manager.clone(),
+                self.registry_guardian.clone(),
+                &[signers_seeds],
+                &Rent::from_account_info(self.rent)?,
+            )?;
+        }
+
+        let registry_guardian = RegistryGuardian::init(*self.registry.key, *self.guardian.key);
+        RegistryGuardian::pack(registry_guardian, *self.registry_guardian.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
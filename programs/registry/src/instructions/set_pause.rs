@@ -0,0 +1,94 @@
+use everlend_utils::{assert_account_key, cpi, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::{
+    find_guardian_program_address, find_pause_program_address,
+    state::{Pause, PauseFlags, RegistryGuardian},
+};
+
+/// Instruction context
+pub struct SetPauseContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    registry_guardian: &'a AccountInfo<'b>,
+    pause: &'a AccountInfo<'b>,
+    guardian: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> SetPauseContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<SetPauseContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+        let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let registry_guardian = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let pause = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let guardian = AccountLoader::next_signer(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        Ok(SetPauseContext {
+            registry,
+            registry_guardian,
+            pause,
+            guardian,
+            rent,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey, pool: Pubkey, flags: PauseFlags) -> ProgramResult {
+        {
+            let (registry_guardian_pubkey, _) =
+                find_guardian_program_address(program_id, self.registry.key);
+            assert_account_key(self.registry_guardian, &registry_guardian_pubkey)?;
+
+            let registry_guardian =
+                RegistryGuardian::unpack(&self.registry_guardian.data.borrow())?;
+            assert_account_key(self.guardian, &registry_guardian.guardian)?;
+        }
+
+        let (pause_pubkey, bump_seed) =
+            find_pause_program_address(program_id, self.registry.key, &pool);
+        assert_account_key(self.pause, &pause_pubkey)?;
+
+        let mut pause = if self.pause.owner.eq(&Pubkey::default()) {
+            let signers_seeds = &[
+                "pause".as_bytes(),
+                &self.registry.key.to_bytes(),
+                &pool.to_bytes(),
+                &[bump_seed],
+            ];
+
+            cpi::system::create_account::<Pause>(
+                program_id,
+                self.guardian.clone(),
+                self.pause.clone(),
+                &[signers_seeds],
+                &Rent::from_account_info(self.rent)?,
+            )?;
+
+            Pause::init(*self.registry.key, pool)
+        } else {
+            Pause::unpack(&self.pause.data.borrow())?
+        };
+
+        pause.flags = flags;
+
+        Pause::pack(pause, *self.pause.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use everlend_utils::{assert_account_key, cpi, AccountLoader};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Slot,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::{Sysvar, SysvarId},
+};
+
+use crate::{
+    find_timelock_program_address,
+    state::{Registry, Timelock},
+};
+
+/// Instruction context
+pub struct SetTimelockContext<'a, 'b> {
+    registry: &'a AccountInfo<'b>,
+    timelock: &'a AccountInfo<'b>,
+    manager: &'a AccountInfo<'b>,
+    rent: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> SetTimelockContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<SetTimelockContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+        let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let timelock = AccountLoader::next_optional(account_info_iter, program_id)?;
+        let manager = AccountLoader::next_signer(account_info_iter)?;
+        let rent = AccountLoader::next_with_key(account_info_iter, &Rent::id())?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        Ok(SetTimelockContext {
+            registry,
+            timelock,
+            manager,
+            rent,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey, proposer: Pubkey, delay: Slot) -> ProgramResult {
+        {
+            let r = Registry::unpack(&self.registry.data.borrow())?;
+            assert_account_key(self.manager, &r.manager)?;
+        }
+
+        let (timelock_pubkey, bump_seed) =
+            find_timelock_program_address(program_id, self.registry.key);
+        assert_account_key(self.timelock, &timelock_pubkey)?;
+
+        // Keep the action counter of the existing timelock so action addresses are not reused
+        let mut timelock = if self.timelock.owner.eq(&Pubkey::default()) {
+            let signers_seeds = &[
+                "timelock".as_bytes(),
+                &self.registry.key.to_bytes(),
+                &[bump_seed],
+            ];
+
+            cpi::system::create_account::<Timelock>(
+                program_id,
+                self.manager.clone(),
+                self.timelock.clone(),
+                &[signers_seeds],
+                &Rent::from_account_info(self.rent)?,
+            )?;
+
+            Timelock::init(*self.registry.key)
+        } else {
+            Timelock::unpack(&self.timelock.data.borrow())?
+        };
+
+        timelock.proposer = proposer;
+        timelock.delay = delay;
+
+        Timelock::pack(timelock, *self.timelock.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -5,50 +5,60 @@ use solana_program::{
     program_pack::Pack, pubkey::Pubkey,
 };
 
-use crate::state::{DistributionPubkeys, Registry, RegistryMarkets};
+use crate::state::{MarketStatus, Registry, RegistryMarkets};
 
 /// Instruction data
 #[derive(BorshDeserialize, BorshSerialize, Clone, PartialEq, Debug)]
-
-pub struct UpdateRegistryMarketsData {
+pub struct UpdateRegistryMarketData {
     ///
-    pub money_markets: Option<DistributionPubkeys>,
+    pub collateral_pool_market: Option<Pubkey>,
     ///
-    pub collateral_pool_markets: Option<DistributionPubkeys>,
+    pub status: Option<MarketStatus>,
 }
+
 /// Instruction context
-pub struct UpdateRegistryMarketsContext<'a, 'b> {
+pub struct UpdateRegistryMarketContext<'a, 'b> {
     registry: &'a AccountInfo<'b>,
     manager: &'a AccountInfo<'b>,
 }
 
-impl<'a, 'b> UpdateRegistryMarketsContext<'a, 'b> {
+impl<'a, 'b> UpdateRegistryMarketContext<'a, 'b> {
     /// New instruction context
     pub fn new(
         program_id: &Pubkey,
         accounts: &'a [AccountInfo<'b>],
-    ) -> Result<UpdateRegistryMarketsContext<'a, 'b>, ProgramError> {
+    ) -> Result<UpdateRegistryMarketContext<'a, 'b>, ProgramError> {
         let account_info_iter = &mut accounts.iter().enumerate();
         let registry = AccountLoader::next_with_owner(account_info_iter, program_id)?;
         let manager = AccountLoader::next_signer(account_info_iter)?;
 
-        Ok(UpdateRegistryMarketsContext { registry, manager })
+        Ok(UpdateRegistryMarketContext { registry, manager })
     }
 
     /// Process instruction
-    pub fn process(&self, _program_id: &Pubkey, data: UpdateRegistryMarketsData) -> ProgramResult {
+    pub fn process(
+        &self,
+        _program_id: &Pubkey,
+        index: u8,
+        data: UpdateRegistryMarketData,
+    ) -> ProgramResult {
         {
             let r = Registry::unpack(&self.registry.data.borrow())?;
             assert_account_key(self.manager, &r.manager)?;
         }
 
         let mut markets = RegistryMarkets::unpack_from_slice(&self.registry.data.borrow())?;
-        if let Some(pubkeys) = data.money_markets {
-            markets.money_markets = pubkeys;
+        let market = markets
+            .markets
+            .get_mut(usize::from(index))
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        if let Some(collateral_pool_market) = data.collateral_pool_market {
+            market.collateral_pool_market = collateral_pool_market;
         }
 
-        if let Some(pubkeys) = data.collateral_pool_markets {
-            markets.collateral_pool_markets = pubkeys;
+        if let Some(status) = data.status {
+            market.status = status;
         }
 
         RegistryMarkets::pack_into_slice(&markets, *self.registry.data.borrow_mut());
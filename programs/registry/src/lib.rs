@@ -12,5 +12,75 @@ pub mod entrypoint;
 
 // Export current sdk types for downstream users building with a different sdk version
 pub use solana_program;
+use solana_program::pubkey::Pubkey;
+use state::Role;
 
 solana_program::declare_id!("REGQ6c5up9wmY3HRasUDaoJNHgqzSMr4edkuuBVT8Zk");
+
+/// Generates registry guardian address
+pub fn find_guardian_program_address(program_id: &Pubkey, registry: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"guardian", &registry.to_bytes()], program_id)
+}
+
+/// Generates pause address, default pool is used for the global pause
+pub fn find_pause_program_address(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    pool: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"pause", &registry.to_bytes(), &pool.to_bytes()],
+        program_id,
+    )
+}
+
+/// Generates global pause and pool pause addresses
+pub fn find_pause_addresses(registry: &Pubkey, pool: &Pubkey) -> (Pubkey, Pubkey) {
+    let (global_pause, _) = find_pause_program_address(&id(), registry, &Pubkey::default());
+    let (pool_pause, _) = find_pause_program_address(&id(), registry, pool);
+
+    (global_pause, pool_pause)
+}
+
+/// Generates registry role address of the holder
+pub fn find_role_program_address(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+    holder: &Pubkey,
+    role: Role,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"role",
+            &registry.to_bytes(),
+            &holder.to_bytes(),
+            &[role as u8],
+        ],
+        program_id,
+    )
+}
+
+/// Generates timelock address
+pub fn find_timelock_program_address(program_id: &Pubkey, registry: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"timelock", &registry.to_bytes()], program_id)
+}
+
+/// Generates timelock authority address, it signs executed actions
+pub fn find_timelock_authority_program_address(
+    program_id: &Pubkey,
+    registry: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"timelock_authority", &registry.to_bytes()], program_id)
+}
+
+/// Generates timelock action address
+pub fn find_timelock_action_program_address(
+    program_id: &Pubkey,
+    timelock: &Pubkey,
+    action_id: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"action", &timelock.to_bytes(), &action_id.to_le_bytes()],
+        program_id,
+    )
+}
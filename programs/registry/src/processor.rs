@@ -6,7 +6,11 @@ use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
 
 use crate::instruction::RegistryInstruction;
 use crate::instructions::{
-    InitContext, UpdateManagerContext, UpdateRegistryContext, UpdateRegistryMarketsContext,
+    AcceptManagerContext, AddRegistryMarketContext, CancelActionContext,
+    CancelManagerProposalContext, ExecuteActionContext, GrantRoleContext, InitContext,
+    MigrateRegistryContext, ProposeManagerContext, QueueActionContext, RevokeRoleContext,
+    SetGuardianContext, SetPauseContext, SetTimelockContext, UpdateRegistryContext,
+    UpdateRegistryMarketContext,
 };
 
 /// Instruction processing router
@@ -23,9 +27,9 @@ pub fn process_instruction(
             InitContext::new(program_id, accounts)?.process(program_id)
         }
 
-        RegistryInstruction::UpdateManager => {
-            msg!("RegistryInstruction: UpdateManager");
-            UpdateManagerContext::new(program_id, accounts)?.process(program_id)
+        RegistryInstruction::ProposeManager => {
+            msg!("RegistryInstruction: ProposeManager");
+            ProposeManagerContext::new(program_id, accounts)?.process(program_id)
         }
 
         RegistryInstruction::UpdateRegistry { data } => {
@@ -33,9 +37,78 @@ pub fn process_instruction(
             UpdateRegistryContext::new(program_id, accounts)?.process(program_id, data)
         }
 
-        RegistryInstruction::UpdateRegistryMarkets { data } => {
-            msg!("RegistryInstruction: UpdateRegistryMarkets");
-            UpdateRegistryMarketsContext::new(program_id, accounts)?.process(program_id, data)
+        RegistryInstruction::AddRegistryMarket { data } => {
+            msg!("RegistryInstruction: AddRegistryMarket");
+            AddRegistryMarketContext::new(program_id, accounts)?.process(program_id, data)
+        }
+
+        RegistryInstruction::UpdateRegistryMarket { index, data } => {
+            msg!("RegistryInstruction: UpdateRegistryMarket");
+            UpdateRegistryMarketContext::new(program_id, accounts)?.process(program_id, index, data)
+        }
+
+        RegistryInstruction::MigrateRegistry => {
+            msg!("RegistryInstruction: MigrateRegistry");
+            MigrateRegistryContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        RegistryInstruction::SetGuardian => {
+            msg!("RegistryInstruction: SetGuardian");
+            SetGuardianContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        RegistryInstruction::SetPause { pool, flags } => {
+            msg!("RegistryInstruction: SetPause");
+            SetPauseContext::new(program_id, accounts)?.process(program_id, pool, flags)
+        }
+
+        RegistryInstruction::AcceptManager => {
+            msg!("RegistryInstruction: AcceptManager");
+            AcceptManagerContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        RegistryInstruction::CancelManagerProposal => {
+            msg!("RegistryInstruction: CancelManagerProposal");
+            CancelManagerProposalContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        RegistryInstruction::GrantRole { role } => {
+            msg!("RegistryInstruction: GrantRole");
+            GrantRoleContext::new(program_id, accounts)?.process(program_id, role)
+        }
+
+        RegistryInstruction::RevokeRole { role } => {
+            msg!("RegistryInstruction: RevokeRole");
+            RevokeRoleContext::new(program_id, accounts)?.process(program_id, role)
+        }
+
+        RegistryInstruction::SetTimelock { proposer, delay } => {
+            msg!("RegistryInstruction: SetTimelock");
+            SetTimelockContext::new(program_id, accounts)?.process(program_id, proposer, delay)
+        }
+
+        RegistryInstruction::QueueAction {
+            program_id: action_program_id,
+            accounts: action_accounts,
+            data,
+        } => {
+            msg!("RegistryInstruction: QueueAction");
+            QueueActionContext::new(program_id, accounts)?.process(
+                program_id,
+                action_program_id,
+                action_accounts,
+                data,
+            )
+        }
+
+        RegistryInstruction::CancelAction => {
+            msg!("RegistryInstruction: CancelAction");
+            CancelActionContext::new(program_id, accounts)?.process(program_id)
+        }
+
+        RegistryInstruction::ExecuteAction => {
+            msg!("RegistryInstruction: ExecuteAction");
+            ExecuteActionContext::new(program_id, accounts)?.process(program_id)
         }
     }
 }
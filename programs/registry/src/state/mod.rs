@@ -1,9 +1,15 @@
 //! State types
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 
+mod pause;
 mod registry;
+mod role;
+mod timelock;
 
+pub use pause::*;
 pub use registry::*;
+pub use role::*;
+pub use timelock::*;
 
 /// Enum representing the account type managed by the program
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -12,6 +18,16 @@ pub enum AccountType {
     Uninitialized,
     /// Registry
     Registry,
+    /// Registry guardian
+    RegistryGuardian,
+    /// Pause
+    Pause,
+    /// Registry role
+    RegistryRole,
+    /// Timelock
+    Timelock,
+    /// Timelock action
+    TimelockAction,
 }
 
 impl Default for AccountType {
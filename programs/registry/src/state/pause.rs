@@ -0,0 +1,166 @@
+//! Pause state definitions
+
+use super::AccountType;
+use crate::find_pause_program_address;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_utils::assert_account_key;
+use solana_program::{
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Registry guardian
+#[repr(C)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct RegistryGuardian {
+    /// Account type - RegistryGuardian
+    pub account_type: AccountType,
+    /// Registry
+    pub registry: Pubkey,
+    /// The address allowed to pause and unpause operations
+    pub guardian: Pubkey,
+}
+
+impl RegistryGuardian {
+    /// Initialize a registry guardian
+    pub fn init(registry: Pubkey, guardian: Pubkey) -> RegistryGuardian {
+        RegistryGuardian {
+            account_type: AccountType::RegistryGuardian,
+            registry,
+            guardian,
+        }
+    }
+}
+
+impl Sealed for RegistryGuardian {}
+impl Pack for RegistryGuardian {
+    const LEN: usize = 1 + 32 + 32;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for RegistryGuardian {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::RegistryGuardian
+    }
+}
+
+/// Paused operations
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default, PartialEq, Copy, Clone)]
+pub struct PauseFlags {
+    /// Deposits are paused
+    pub deposit: bool,
+    /// Withdrawals are paused
+    pub withdraw: bool,
+    /// Borrows are paused
+    pub borrow: bool,
+    /// Rebalancing is paused
+    pub rebalancing: bool,
+}
+
+impl PauseFlags {
+    /// Operations paused by any of the flags
+    pub fn union(&self, other: &PauseFlags) -> PauseFlags {
+        PauseFlags {
+            deposit: self.deposit || other.deposit,
+            withdraw: self.withdraw || other.withdraw,
+            borrow: self.borrow || other.borrow,
+            rebalancing: self.rebalancing || other.rebalancing,
+        }
+    }
+
+    /// Load flags of the global pause and the pool pause
+    pub fn load(
+        registry: &Pubkey,
+        pool: &Pubkey,
+        global_pause: &AccountInfo,
+        pool_pause: &AccountInfo,
+    ) -> Result<PauseFlags, ProgramError> {
+        let global_flags = Pause::load_flags(registry, &Pubkey::default(), global_pause)?;
+        let pool_flags = Pause::load_flags(registry, pool, pool_pause)?;
+
+        Ok(global_flags.union(&pool_flags))
+    }
+}
+
+/// Pause of the pool operations
+#[repr(C)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct Pause {
+    /// Account type - Pause
+    pub account_type: AccountType,
+    /// Registry
+    pub registry: Pubkey,
+    /// Pool, default pubkey for the global pause
+    pub pool: Pubkey,
+    /// Paused operations
+    pub flags: PauseFlags,
+}
+
+impl Pause {
+    /// Initialize a pause
+    pub fn init(registry: Pubkey, pool: Pubkey) -> Pause {
+        Pause {
+            account_type: AccountType::Pause,
+            registry,
+            pool,
+            flags: PauseFlags::default(),
+        }
+    }
+
+    /// Load flags of the pause account, not created account pauses nothing
+    pub fn load_flags(
+        registry: &Pubkey,
+        pool: &Pubkey,
+        pause: &AccountInfo,
+    ) -> Result<PauseFlags, ProgramError> {
+        let (pause_pubkey, _) = find_pause_program_address(&crate::id(), registry, pool);
+        assert_account_key(pause, &pause_pubkey)?;
+
+        if !pause.owner.eq(&crate::id()) {
+            return Ok(PauseFlags::default());
+        }
+
+        Ok(Pause::unpack(&pause.data.borrow())?.flags)
+    }
+}
+
+impl Sealed for Pause {}
+impl Pack for Pause {
+    const LEN: usize = 1 + 32 + 32 + 4;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for Pause {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::Pause
+    }
+}
@@ -2,7 +2,7 @@
 
 use super::AccountType;
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
-use everlend_utils::Uninitialized;
+use everlend_utils::{integrations::MoneyMarket, AccountVersion, EverlendError, Uninitialized};
 use solana_program::{
     clock::Slot,
     msg,
@@ -11,14 +11,14 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
-/// Total number of money market distributions
-pub const TOTAL_DISTRIBUTIONS: usize = 10;
+use deprecated::DEPRECATED_REGISTRY_LEN;
+pub use deprecated::{DeprecatedRegistryMarkets, DistributionPubkeys, TOTAL_DISTRIBUTIONS};
 
-/// Distribution pubkeys
-pub type DistributionPubkeys = [Pubkey; TOTAL_DISTRIBUTIONS];
+/// Maximum number of money markets in the registry
+pub const MAX_MONEY_MARKETS: usize = 16;
 
-const REGISTRY_LEN: usize = 1 + (32 + 32 + 32 + 32 + 8);
-const REGISTRY_MARKETS_LEN: usize = (32 * TOTAL_DISTRIBUTIONS) + (32 * TOTAL_DISTRIBUTIONS);
+const REGISTRY_LEN: usize = 1 + (32 + 32 + 32 + 32 + 8 + 32);
+const REGISTRY_MARKETS_LEN: usize = 1 + (4 + MAX_MONEY_MARKETS * RegistryMarket::LEN);
 
 /// Registry
 #[repr(C)]
@@ -36,6 +36,8 @@ pub struct Registry {
     pub liquidity_oracle: Pubkey,
     /// Refresh income interval
     pub refresh_income_interval: Slot,
+    /// Proposed manager, takes over after accepting
+    pub pending_manager: Pubkey,
     // Program ids for money markets
     // pub money_market_program_ids: DistributionPubkeys,
     // Collateral pool markets
@@ -53,6 +55,26 @@ impl Registry {
     }
 }
 
+impl Registry {
+    /// Unpack registry from the account with deprecated markets layout
+    pub fn unpack_deprecated(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() != DeprecatedRegistryMarkets::ACCOUNT_LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Deprecated layout has no pending manager
+        let mut data = [0u8; REGISTRY_LEN];
+        data[0..DEPRECATED_REGISTRY_LEN].copy_from_slice(&src[0..DEPRECATED_REGISTRY_LEN]);
+
+        let mut src_mut = &data[..];
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
 impl Sealed for Registry {}
 impl Pack for Registry {
     const LEN: usize = REGISTRY_LEN + REGISTRY_MARKETS_LEN;
@@ -89,14 +111,103 @@ impl Uninitialized for Registry {
     }
 }
 
-/// Registry programs
+/// Money market status
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Copy, Clone)]
+pub enum MarketStatus {
+    /// Market accepts deposits and withdrawals
+    Active,
+    /// New deposits are suspended, distributed liquidity stays in the market
+    Paused,
+    /// Market is excluded from distribution, liquidity is withdrawn on next rebalancing
+    Retired,
+}
+
+impl Default for MarketStatus {
+    fn default() -> Self {
+        MarketStatus::Active
+    }
+}
+
+/// Registry money market.
+/// Liquidity caps are kept per token mint in the depositor `RebalancingLimits`
+/// keyed by the money market program id, since a registry market is shared
+/// by all tokens distributed into it.
+#[repr(C)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Copy, Clone)]
+pub struct RegistryMarket {
+    /// Money market program id
+    pub money_market: Pubkey,
+    /// Collateral pool market
+    pub collateral_pool_market: Pubkey,
+    /// Money market adapter
+    pub adapter: MoneyMarket,
+    /// Market status
+    pub status: MarketStatus,
+}
+
+impl RegistryMarket {
+    /// LEN
+    pub const LEN: usize = 32 + 32 + 1 + 1;
+
+    /// Check market is active
+    pub fn is_active(&self) -> bool {
+        self.status == MarketStatus::Active
+    }
+}
+
+/// Registry money markets
 #[repr(C)]
-#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default, PartialEq, Copy, Clone)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default, PartialEq, Clone)]
 pub struct RegistryMarkets {
-    /// Money market program ids
-    pub money_markets: DistributionPubkeys,
-    /// Collateral pool market program ids
-    pub collateral_pool_markets: DistributionPubkeys,
+    /// Account version
+    pub account_version: AccountVersion,
+    /// Money markets. Index in the list is the money market index used in distributions.
+    pub markets: Vec<RegistryMarket>,
+}
+
+impl RegistryMarkets {
+    /// Initialize a registry markets
+    pub fn init() -> RegistryMarkets {
+        RegistryMarkets {
+            account_version: AccountVersion::V1,
+            markets: Vec::new(),
+        }
+    }
+
+    /// Add money market to the end of the list
+    pub fn add(&mut self, market: RegistryMarket) -> Result<(), ProgramError> {
+        if self.markets.len() >= MAX_MONEY_MARKETS {
+            return Err(EverlendError::MoneyMarketsLimitExceeded.into());
+        }
+
+        self.markets.push(market);
+
+        Ok(())
+    }
+
+    /// Get money market by index
+    pub fn get(&self, index: usize) -> Result<&RegistryMarket, ProgramError> {
+        self.markets.get(index).ok_or(ProgramError::InvalidArgument)
+    }
+
+    /// Get money market by program id
+    pub fn find(&self, money_market: &Pubkey) -> Option<&RegistryMarket> {
+        self.markets
+            .iter()
+            .find(|market| market.money_market == *money_market)
+    }
+
+    /// Check that money market program is registered
+    pub fn contains_money_market(&self, money_market: &Pubkey) -> bool {
+        self.find(money_market).is_some()
+    }
+
+    /// Check that collateral pool market is registered
+    pub fn contains_collateral_pool_market(&self, collateral_pool_market: &Pubkey) -> bool {
+        self.markets
+            .iter()
+            .any(|market| market.collateral_pool_market == *collateral_pool_market)
+    }
 }
 
 impl Sealed for RegistryMarkets {}
@@ -107,7 +218,10 @@ impl Pack for RegistryMarkets {
         let mut slice = Vec::with_capacity(REGISTRY_MARKETS_LEN);
         self.serialize(&mut slice).unwrap();
 
-        dst[REGISTRY_LEN..REGISTRY_LEN + REGISTRY_MARKETS_LEN].copy_from_slice(&slice)
+        // Clear the tail left from a longer list
+        let dst = &mut dst[REGISTRY_LEN..REGISTRY_LEN + REGISTRY_MARKETS_LEN];
+        dst.fill(0);
+        dst[0..slice.len()].copy_from_slice(&slice)
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
@@ -125,12 +239,104 @@ impl Pack for RegistryMarkets {
     }
 }
 
+mod deprecated {
+    use super::*;
+
+    /// Total number of money market distributions
+    pub const TOTAL_DISTRIBUTIONS: usize = 10;
+
+    /// Distribution pubkeys
+    pub type DistributionPubkeys = [Pubkey; TOTAL_DISTRIBUTIONS];
+
+    /// Registry length without pending manager
+    pub const DEPRECATED_REGISTRY_LEN: usize = 1 + (32 + 32 + 32 + 32 + 8);
+
+    const DEPRECATED_REGISTRY_MARKETS_LEN: usize =
+        (32 * TOTAL_DISTRIBUTIONS) + (32 * TOTAL_DISTRIBUTIONS);
+
+    /// Registry programs
+    #[repr(C)]
+    #[derive(
+        Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default, PartialEq, Copy, Clone,
+    )]
+    pub struct DeprecatedRegistryMarkets {
+        /// Money market program ids
+        pub money_markets: DistributionPubkeys,
+        /// Collateral pool market program ids
+        pub collateral_pool_markets: DistributionPubkeys,
+    }
+
+    impl DeprecatedRegistryMarkets {
+        /// Registry account length with deprecated markets layout
+        pub const ACCOUNT_LEN: usize = DEPRECATED_REGISTRY_LEN + DEPRECATED_REGISTRY_MARKETS_LEN;
+
+        /// Convert fixed slots to the market list.
+        /// Slots are kept in place up to the last filled one, empty slots are retired.
+        pub fn to_markets(&self) -> RegistryMarkets {
+            let len = self
+                .money_markets
+                .iter()
+                .rposition(|money_market| *money_market != Pubkey::default())
+                .map_or(0, |index| index + 1);
+
+            let markets = (0..len)
+                .map(|index| RegistryMarket {
+                    money_market: self.money_markets[index],
+                    collateral_pool_market: self.collateral_pool_markets[index],
+                    adapter: MoneyMarket::from(index),
+                    status: if self.money_markets[index] == Pubkey::default() {
+                        MarketStatus::Retired
+                    } else {
+                        MarketStatus::Active
+                    },
+                })
+                .collect();
+
+            RegistryMarkets {
+                account_version: AccountVersion::V1,
+                markets,
+            }
+        }
+    }
+
+    impl Sealed for DeprecatedRegistryMarkets {}
+    impl Pack for DeprecatedRegistryMarkets {
+        const LEN: usize = DEPRECATED_REGISTRY_MARKETS_LEN;
+
+        fn pack_into_slice(&self, dst: &mut [u8]) {
+            let mut slice = Vec::with_capacity(DEPRECATED_REGISTRY_MARKETS_LEN);
+            self.serialize(&mut slice).unwrap();
+
+            dst[DEPRECATED_REGISTRY_LEN..Self::ACCOUNT_LEN].copy_from_slice(&slice)
+        }
+
+        fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+            if src.len() != Self::ACCOUNT_LEN {
+                return Err(ProgramError::InvalidAccountData);
+            }
+
+            let mut src_mut = &src[DEPRECATED_REGISTRY_LEN..Self::ACCOUNT_LEN];
+
+            Self::deserialize(&mut src_mut).map_err(|err| {
+                msg!("Failed to deserialize");
+                msg!(&err.to_string());
+                ProgramError::InvalidAccountData
+            })
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use crate::state::registry::{REGISTRY_LEN, REGISTRY_MARKETS_LEN};
-    use crate::state::{Registry, RegistryMarkets};
+    use crate::state::{
+        DeprecatedRegistryMarkets, MarketStatus, Registry, RegistryMarket, RegistryMarkets,
+    };
+    use borsh::BorshSerialize;
+    use everlend_utils::integrations::MoneyMarket;
     use solana_program::program_error::ProgramError;
     use solana_program::program_pack::Pack;
+    use solana_program::pubkey::Pubkey;
 
     #[test]
     fn unpack_registry() {
@@ -152,4 +358,64 @@ pub mod tests {
             ProgramError::InvalidAccountData,
         )
     }
+
+    #[test]
+    fn pack_registry_markets() {
+        let mut data = vec![0u8; REGISTRY_LEN + REGISTRY_MARKETS_LEN];
+        let market = RegistryMarket {
+            money_market: Pubkey::new_unique(),
+            collateral_pool_market: Pubkey::new_unique(),
+            adapter: MoneyMarket::Larix,
+            status: MarketStatus::Active,
+        };
+
+        let mut markets = RegistryMarkets::init();
+        markets.add(market).unwrap();
+        markets.add(market).unwrap();
+        RegistryMarkets::pack_into_slice(&markets, &mut data);
+        assert_eq!(RegistryMarkets::unpack_from_slice(&data).unwrap(), markets);
+
+        // Shrinked list clears the tail
+        markets.markets.pop();
+        RegistryMarkets::pack_into_slice(&markets, &mut data);
+        assert_eq!(RegistryMarkets::unpack_from_slice(&data).unwrap(), markets);
+        assert!(data[REGISTRY_LEN + 5 + RegistryMarket::LEN..]
+            .iter()
+            .all(|&b| b == 0));
+    }
+
+    #[test]
+    fn unpack_deprecated_registry() {
+        let manager = Pubkey::new_unique();
+        let mut registry = Registry::init(manager);
+        registry.refresh_income_interval = 100;
+
+        let mut data = vec![0u8; DeprecatedRegistryMarkets::ACCOUNT_LEN];
+        let mut slice = Vec::new();
+        registry.serialize(&mut slice).unwrap();
+        // Deprecated layout ends before pending manager
+        data[0..REGISTRY_LEN - 32].copy_from_slice(&slice[0..REGISTRY_LEN - 32]);
+
+        let registry = Registry::unpack_deprecated(&data).unwrap();
+        assert_eq!(registry.manager, manager);
+        assert_eq!(registry.refresh_income_interval, 100);
+        assert_eq!(registry.pending_manager, Pubkey::default());
+    }
+
+    #[test]
+    fn migrate_registry_markets() {
+        let mut deprecated = DeprecatedRegistryMarkets::default();
+        deprecated.money_markets[0] = Pubkey::new_unique();
+        deprecated.money_markets[2] = Pubkey::new_unique();
+        deprecated.collateral_pool_markets = [Pubkey::new_unique(); 10];
+
+        let markets = deprecated.to_markets();
+
+        assert_eq!(markets.markets.len(), 3);
+        assert_eq!(markets.markets[0].adapter, MoneyMarket::PortFinance);
+        assert_eq!(markets.markets[1].status, MarketStatus::Retired);
+        assert_eq!(markets.markets[2].adapter, MoneyMarket::Solend);
+        assert_eq!(markets.markets[2].money_market, deprecated.money_markets[2]);
+        assert!(markets.markets[2].is_active());
+    }
 }
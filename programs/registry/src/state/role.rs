@@ -0,0 +1,172 @@
+//! Role state definitions
+
+use super::{AccountType, Timelock};
+use crate::{find_role_program_address, find_timelock_program_address};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_utils::{assert_account_key, assert_owned_by, AccountLoader, EverlendError};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+use std::iter::Enumerate;
+
+/// Role granted by the registry manager, the manager itself is allowed to do everything.
+/// Pausing is granted separately with the registry guardian.
+/// Admin and Risk roles are rejected once the registry timelock is set, so changes of
+/// borrow authorities and risk parameters go through the timelock queue only.
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Copy, Clone)]
+pub enum Role {
+    /// Borrow authorities of the pools
+    Admin,
+    /// Pool configs, fee schedules, token oracle and rebalancing limits
+    Risk,
+    /// Pools, token metadata and token oracles creation
+    Operator,
+}
+
+impl Role {
+    /// Role changes are delayed by the registry timelock
+    pub fn is_timelocked(&self) -> bool {
+        matches!(self, Role::Admin | Role::Risk)
+    }
+}
+
+impl Default for Role {
+    fn default() -> Self {
+        Role::Admin
+    }
+}
+
+/// Registry role of the holder
+#[repr(C)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct RegistryRole {
+    /// Account type - RegistryRole
+    pub account_type: AccountType,
+    /// Registry
+    pub registry: Pubkey,
+    /// Holder of the role
+    pub holder: Pubkey,
+    /// Granted role
+    pub role: Role,
+}
+
+impl RegistryRole {
+    /// Initialize a registry role
+    pub fn init(registry: Pubkey, holder: Pubkey, role: Role) -> RegistryRole {
+        RegistryRole {
+            account_type: AccountType::RegistryRole,
+            registry,
+            holder,
+            role,
+        }
+    }
+}
+
+impl Sealed for RegistryRole {}
+impl Pack for RegistryRole {
+    const LEN: usize = 1 + 32 + 32 + 1;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for RegistryRole {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::RegistryRole
+    }
+}
+
+/// Optional accounts passed after the instruction accounts when
+/// the instruction is signed by the role holder instead of the manager
+pub struct RoleAccounts<'a, 'b> {
+    /// Registry
+    pub registry: &'a AccountInfo<'b>,
+    /// Registry role of the signer
+    pub registry_role: &'a AccountInfo<'b>,
+    /// Registry timelock, can be uninitialized
+    pub timelock: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> RoleAccounts<'a, 'b> {
+    /// Load role accounts if they are passed
+    pub fn next_optional<I: Iterator<Item = &'a AccountInfo<'b>>>(
+        iter: &mut Enumerate<I>,
+    ) -> Result<Option<RoleAccounts<'a, 'b>>, ProgramError> {
+        if !AccountLoader::has_more(iter) {
+            return Ok(None);
+        }
+
+        let registry = AccountLoader::next_with_owner(iter, &crate::id())?;
+        let registry_role = AccountLoader::next_unchecked(iter)?;
+        let timelock = AccountLoader::next_unchecked(iter)?;
+
+        Ok(Some(RoleAccounts {
+            registry,
+            registry_role,
+            timelock,
+        }))
+    }
+
+    /// Check the holder is granted the role in the registry
+    pub fn assert_role(&self, holder: &AccountInfo, role: Role) -> ProgramResult {
+        let (registry_role_pubkey, _) =
+            find_role_program_address(&crate::id(), self.registry.key, holder.key, role);
+        assert_account_key(self.registry_role, &registry_role_pubkey)?;
+        assert_owned_by(self.registry_role, &crate::id())?;
+
+        // Get registry role state to check initialized
+        RegistryRole::unpack(&self.registry_role.data.borrow())?;
+
+        if role.is_timelocked() {
+            let (timelock_pubkey, _) =
+                find_timelock_program_address(&crate::id(), self.registry.key);
+            assert_account_key(self.timelock, &timelock_pubkey)?;
+
+            if self.timelock.owner.eq(&crate::id())
+                && Timelock::unpack(&self.timelock.data.borrow()).is_ok()
+            {
+                msg!("Role is timelocked, queue the action with the registry timelock");
+                return Err(EverlendError::TimelockedRole.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Check the signer is the manager or is granted the role in the registry,
+/// the registry is checked by the caller
+pub fn assert_manager_or_role(
+    manager: &Pubkey,
+    signer: &AccountInfo,
+    role_accounts: Option<&RoleAccounts>,
+    role: Role,
+) -> ProgramResult {
+    if signer.key.eq(manager) {
+        return Ok(());
+    }
+
+    match role_accounts {
+        Some(role_accounts) => role_accounts.assert_role(signer, role),
+        None => {
+            msg!("Signer is not the manager and role accounts are missing");
+            Err(ProgramError::InvalidArgument)
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Timelock state definitions
+
+use super::AccountType;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use everlend_utils::EverlendError;
+use solana_program::{
+    clock::Slot,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+/// Maximum number of accounts of the queued action
+pub const MAX_ACTION_ACCOUNTS: usize = 16;
+/// Maximum instruction data length of the queued action
+pub const MAX_ACTION_DATA_LEN: usize = 256;
+
+const ACTION_ACCOUNTS_LEN: usize = 4 + MAX_ACTION_ACCOUNTS * (32 + 1 + 1);
+const ACTION_DATA_LEN: usize = 4 + MAX_ACTION_DATA_LEN;
+
+/// Timelock of the registry, its authority executes queued actions after the delay
+#[repr(C)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct Timelock {
+    /// Account type - Timelock
+    pub account_type: AccountType,
+    /// Registry
+    pub registry: Pubkey,
+    /// The address allowed to queue and cancel actions
+    pub proposer: Pubkey,
+    /// Delay between queueing and execution of the action
+    pub delay: Slot,
+    /// Number of queued actions, used as the next action id
+    pub action_count: u64,
+}
+
+impl Timelock {
+    /// Initialize a timelock
+    pub fn init(registry: Pubkey) -> Timelock {
+        Timelock {
+            account_type: AccountType::Timelock,
+            registry,
+            ..Default::default()
+        }
+    }
+
+    /// Take the next action id
+    pub fn next_action_id(&mut self) -> Result<u64, ProgramError> {
+        let action_id = self.action_count;
+        self.action_count = self
+            .action_count
+            .checked_add(1)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        Ok(action_id)
+    }
+}
+
+impl Sealed for Timelock {}
+impl Pack for Timelock {
+    const LEN: usize = 1 + 32 + 32 + 8 + 8;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for Timelock {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::Timelock
+    }
+}
+
+/// Account of the queued action instruction
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Clone)]
+pub struct ActionAccount {
+    /// Account pubkey
+    pub pubkey: Pubkey,
+    /// Account is signed by the timelock authority
+    pub is_signer: bool,
+    /// Account is writable
+    pub is_writable: bool,
+}
+
+impl From<&AccountMeta> for ActionAccount {
+    fn from(account_meta: &AccountMeta) -> Self {
+        ActionAccount {
+            pubkey: account_meta.pubkey,
+            is_signer: account_meta.is_signer,
+            is_writable: account_meta.is_writable,
+        }
+    }
+}
+
+impl From<&ActionAccount> for AccountMeta {
+    fn from(account: &ActionAccount) -> Self {
+        AccountMeta {
+            pubkey: account.pubkey,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        }
+    }
+}
+
+/// Parameters to queue the timelock action
+pub struct InitTimelockActionParams {
+    /// Timelock
+    pub timelock: Pubkey,
+    /// Action id
+    pub id: u64,
+    /// Proposer, receives the rent back
+    pub proposer: Pubkey,
+    /// Slot after which the action can be executed
+    pub execution_slot: Slot,
+    /// Program of the action instruction
+    pub program_id: Pubkey,
+    /// Accounts of the action instruction
+    pub accounts: Vec<ActionAccount>,
+    /// Data of the action instruction
+    pub data: Vec<u8>,
+}
+
+/// Queued timelock action
+#[repr(C)]
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct TimelockAction {
+    /// Account type - TimelockAction
+    pub account_type: AccountType,
+    /// Timelock
+    pub timelock: Pubkey,
+    /// Action id
+    pub id: u64,
+    /// Proposer, receives the rent back
+    pub proposer: Pubkey,
+    /// Slot after which the action can be executed
+    pub execution_slot: Slot,
+    /// Program of the action instruction
+    pub program_id: Pubkey,
+    /// Accounts of the action instruction
+    pub accounts: Vec<ActionAccount>,
+    /// Data of the action instruction
+    pub data: Vec<u8>,
+}
+
+impl TimelockAction {
+    /// Initialize a timelock action
+    pub fn init(params: InitTimelockActionParams) -> Result<TimelockAction, ProgramError> {
+        if params.accounts.len() > MAX_ACTION_ACCOUNTS || params.data.len() > MAX_ACTION_DATA_LEN {
+            return Err(EverlendError::InvalidTimelockAction.into());
+        }
+
+        Ok(TimelockAction {
+            account_type: AccountType::TimelockAction,
+            timelock: params.timelock,
+            id: params.id,
+            proposer: params.proposer,
+            execution_slot: params.execution_slot,
+            program_id: params.program_id,
+            accounts: params.accounts,
+            data: params.data,
+        })
+    }
+
+    /// Instruction to execute
+    pub fn instruction(&self) -> Instruction {
+        Instruction {
+            program_id: self.program_id,
+            accounts: self.accounts.iter().map(AccountMeta::from).collect(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl Sealed for TimelockAction {}
+impl Pack for TimelockAction {
+    const LEN: usize = 1 + 32 + 8 + 32 + 8 + 32 + ACTION_ACCOUNTS_LEN + ACTION_DATA_LEN;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!(&err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for TimelockAction {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::TimelockAction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_max_action() {
+        let action = TimelockAction::init(InitTimelockActionParams {
+            timelock: Pubkey::new_unique(),
+            id: 1,
+            proposer: Pubkey::new_unique(),
+            execution_slot: 100,
+            program_id: Pubkey::new_unique(),
+            accounts: vec![
+                ActionAccount {
+                    pubkey: Pubkey::new_unique(),
+                    is_signer: false,
+                    is_writable: true,
+                };
+                MAX_ACTION_ACCOUNTS
+            ],
+            data: vec![1; MAX_ACTION_DATA_LEN],
+        })
+        .unwrap();
+
+        let mut dst = vec![0; TimelockAction::LEN];
+        TimelockAction::pack(action, &mut dst).unwrap();
+
+        let action = TimelockAction::unpack(&dst).unwrap();
+        assert_eq!(action.accounts.len(), MAX_ACTION_ACCOUNTS);
+        assert_eq!(action.data.len(), MAX_ACTION_DATA_LEN);
+    }
+
+    #[test]
+    fn init_too_many_accounts() {
+        let result = TimelockAction::init(InitTimelockActionParams {
+            timelock: Pubkey::new_unique(),
+            id: 0,
+            proposer: Pubkey::new_unique(),
+            execution_slot: 0,
+            program_id: Pubkey::new_unique(),
+            accounts: vec![
+                ActionAccount {
+                    pubkey: Pubkey::new_unique(),
+                    is_signer: false,
+                    is_writable: false,
+                };
+                MAX_ACTION_ACCOUNTS + 1
+            ],
+            data: vec![],
+        });
+
+        assert_eq!(
+            result.unwrap_err(),
+            EverlendError::InvalidTimelockAction.into()
+        );
+    }
+}
@@ -121,3 +121,29 @@ pub fn fill_vault<'a>(
         signers_seeds,
     )
 }
+
+/// Rewards claim all
+pub fn claim_all<'a>(
+    program_id: &Pubkey,
+    reward_pool: AccountInfo<'a>,
+    mining: AccountInfo<'a>,
+    user: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    rewards: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let ix = crate::instruction::claim_all(
+        program_id,
+        reward_pool.key,
+        mining.key,
+        user.key,
+        &rewards
+            .chunks(3)
+            .map(|r| (*r[0].key, *r[1].key, *r[2].key))
+            .collect::<Vec<_>>(),
+    );
+
+    let mut account_infos = vec![reward_pool, mining, user, token_program];
+    account_infos.extend_from_slice(rewards);
+
+    invoke(&ix, &account_infos)
+}
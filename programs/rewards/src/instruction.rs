@@ -1,6 +1,7 @@
 //! Instruction types
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::clock::Slot;
 use solana_program::instruction::{AccountMeta, Instruction};
 use solana_program::pubkey::Pubkey;
 use solana_program::{system_program, sysvar};
@@ -87,7 +88,7 @@ pub enum RewardsInstruction {
     /// Claims amount of rewards
     ///
     /// Accounts:
-    /// [R] Reward pool account
+    /// [W] Reward pool account
     /// [R] Mint of rewards account
     /// [W] Vault for rewards account
     /// [W] Mining
@@ -115,6 +116,96 @@ pub enum RewardsInstruction {
     /// [R] System program
     /// [R] Rent sysvar
     MigratePool,
+
+    /// Sets fee and fee account of the reward vault
+    ///
+    /// Accounts:
+    /// [R] Root account (ex-Config program account)
+    /// [W] Reward pool account
+    /// [R] Reward mint account
+    /// [R] Fee account
+    /// [RS] Authority
+    SetVaultFee {
+        /// Fee in basis points
+        fee_bps: u16,
+    },
+
+    /// Sets emission duration of the reward vault, zero distributes fills instantly
+    ///
+    /// Accounts:
+    /// [R] Root account (ex-Config program account)
+    /// [W] Reward pool account
+    /// [R] Reward mint account
+    /// [RS] Authority
+    SetVaultEmission {
+        /// Slots to stream each fill over
+        emission_duration: Slot,
+    },
+
+    /// Locks the whole mining share for the duration to boost its weight.
+    /// The lock ends at the end of the lock epoch. An active lock can only be extended
+    ///
+    /// Accounts:
+    /// [W] Reward pool account
+    /// [W] Mining
+    /// [WS] User
+    /// [R] System program
+    LockMining {
+        /// Slots to lock the share for
+        duration: Slot,
+    },
+
+    /// Claims rewards of all vaults of the reward pool
+    ///
+    /// Accounts:
+    /// [W] Reward pool account
+    /// [W] Mining
+    /// [RS] User
+    /// [R] Token program
+    /// For each reward vault in the reward pool order:
+    /// [R] Mint of rewards account
+    /// [W] Vault for rewards account
+    /// [W] User reward token account
+    ClaimAll,
+
+    /// Proposes a new root authority, it takes effect after acceptance
+    ///
+    /// Accounts:
+    /// [W] Root account (ex-Config program account)
+    /// [RS] Authority
+    /// [R] New authority
+    ProposeRootAuthority,
+
+    /// Accepts the proposed root authority
+    ///
+    /// Accounts:
+    /// [W] Root account (ex-Config program account)
+    /// [RS] New authority
+    AcceptRootAuthority,
+
+    /// Cancels the proposed root authority
+    ///
+    /// Accounts:
+    /// [W] Root account (ex-Config program account)
+    /// [RS] Authority
+    CancelRootAuthorityProposal,
+
+    /// Releases the expired lock of the mining and its lock expiration in the reward pool.
+    /// The boost stops at the lock end without it. Can be called by anyone
+    ///
+    /// Accounts:
+    /// [W] Reward pool account
+    /// [W] Mining
+    ExpireLock,
+
+    /// Migrates rewards root to the layout with pending authority
+    ///
+    /// Accounts:
+    /// [W] Root account (ex-Config program account)
+    /// [WS] Authority
+    /// [R] System program
+    /// [R] Rent sysvar
+    MigrateRoot,
 }
 
 /// Creates 'InitializePool' instruction.
@@ -271,7 +362,7 @@ pub fn claim(
     user_reward_token: &Pubkey,
 ) -> Instruction {
     let accounts = vec![
-        AccountMeta::new_readonly(*reward_pool, false),
+        AccountMeta::new(*reward_pool, false),
         AccountMeta::new_readonly(*reward_mint, false),
         AccountMeta::new(*vault, false),
         AccountMeta::new(*mining, false),
@@ -318,3 +409,176 @@ pub fn migrate_pool(
 
     Instruction::new_with_borsh(*program_id, &RewardsInstruction::MigratePool, accounts)
 }
+
+/// Creates 'SetVaultFee' instruction.
+pub fn set_vault_fee(
+    program_id: &Pubkey,
+    rewards_root: &Pubkey,
+    reward_pool: &Pubkey,
+    reward_mint: &Pubkey,
+    fee_account: &Pubkey,
+    authority: &Pubkey,
+    fee_bps: u16,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*rewards_root, false),
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new_readonly(*reward_mint, false),
+        AccountMeta::new_readonly(*fee_account, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::SetVaultFee { fee_bps },
+        accounts,
+    )
+}
+
+/// Creates 'SetVaultEmission' instruction.
+pub fn set_vault_emission(
+    program_id: &Pubkey,
+    rewards_root: &Pubkey,
+    reward_pool: &Pubkey,
+    reward_mint: &Pubkey,
+    authority: &Pubkey,
+    emission_duration: Slot,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new_readonly(*rewards_root, false),
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new_readonly(*reward_mint, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::SetVaultEmission { emission_duration },
+        accounts,
+    )
+}
+
+/// Creates 'LockMining' instruction.
+pub fn lock_mining(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &Pubkey,
+    user: &Pubkey,
+    duration: Slot,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new(*mining, false),
+        AccountMeta::new(*user, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::LockMining { duration },
+        accounts,
+    )
+}
+
+/// Creates 'ClaimAll' instruction.
+/// Rewards are reward mint, vault and user reward token account for each vault of the pool.
+pub fn claim_all(
+    program_id: &Pubkey,
+    reward_pool: &Pubkey,
+    mining: &Pubkey,
+    user: &Pubkey,
+    rewards: &[(Pubkey, Pubkey, Pubkey)],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new(*mining, false),
+        AccountMeta::new_readonly(*user, true),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+
+    for (reward_mint, vault, user_reward_token) in rewards {
+        accounts.push(AccountMeta::new_readonly(*reward_mint, false));
+        accounts.push(AccountMeta::new(*vault, false));
+        accounts.push(AccountMeta::new(*user_reward_token, false));
+    }
+
+    Instruction::new_with_borsh(*program_id, &RewardsInstruction::ClaimAll, accounts)
+}
+
+/// Creates 'ProposeRootAuthority' instruction.
+pub fn propose_root_authority(
+    program_id: &Pubkey,
+    rewards_root: &Pubkey,
+    authority: &Pubkey,
+    new_authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*rewards_root, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(*new_authority, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::ProposeRootAuthority,
+        accounts,
+    )
+}
+
+/// Creates 'AcceptRootAuthority' instruction.
+pub fn accept_root_authority(
+    program_id: &Pubkey,
+    rewards_root: &Pubkey,
+    new_authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*rewards_root, false),
+        AccountMeta::new_readonly(*new_authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::AcceptRootAuthority,
+        accounts,
+    )
+}
+
+/// Creates 'CancelRootAuthorityProposal' instruction.
+pub fn cancel_root_authority_proposal(
+    program_id: &Pubkey,
+    rewards_root: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*rewards_root, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RewardsInstruction::CancelRootAuthorityProposal,
+        accounts,
+    )
+}
+
+/// Creates 'ExpireLock' instruction.
+pub fn expire_lock(program_id: &Pubkey, reward_pool: &Pubkey, mining: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*reward_pool, false),
+        AccountMeta::new(*mining, false),
+    ];
+
+    Instruction::new_with_borsh(*program_id, &RewardsInstruction::ExpireLock, accounts)
+}
+
+/// Creates 'MigrateRoot' instruction.
+pub fn migrate_root(program_id: &Pubkey, rewards_root: &Pubkey, authority: &Pubkey) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*rewards_root, false),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    Instruction::new_with_borsh(*program_id, &RewardsInstruction::MigrateRoot, accounts)
+}
@@ -0,0 +1,44 @@
+use crate::state::RewardsRoot;
+use everlend_utils::{assert_account_key, AccountLoader};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+
+/// Instruction context
+pub struct AcceptRootAuthorityContext<'a, 'b> {
+    rewards_root: &'a AccountInfo<'b>,
+    new_authority: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> AcceptRootAuthorityContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<AcceptRootAuthorityContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let rewards_root = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let new_authority = AccountLoader::next_signer(account_info_iter)?;
+
+        Ok(AcceptRootAuthorityContext {
+            rewards_root,
+            new_authority,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, _program_id: &Pubkey) -> ProgramResult {
+        let mut rewards_root = RewardsRoot::unpack(&self.rewards_root.data.borrow())?;
+        assert_account_key(self.new_authority, &rewards_root.pending_authority)?;
+
+        rewards_root.authority = rewards_root.pending_authority;
+        rewards_root.pending_authority = Pubkey::default();
+
+        RewardsRoot::pack(rewards_root, *self.rewards_root.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -10,7 +10,7 @@ use solana_program::system_program;
 use solana_program::sysvar::{Sysvar, SysvarId};
 use spl_token::state::Account;
 
-use crate::state::{RewardPool, RewardVault, RewardsRoot};
+use crate::state::{RewardPool, RewardVault, RewardsRoot, DEFAULT_FEE_BPS};
 
 /// Instruction context
 pub struct AddVaultContext<'a, 'b> {
@@ -96,6 +96,7 @@ impl<'a, 'b> AddVaultContext<'a, 'b> {
             bump,
             reward_mint: *self.reward_mint.key,
             fee_account: *self.fee_account.key,
+            fee_bps: DEFAULT_FEE_BPS,
             ..Default::default()
         })?;
 
@@ -1,10 +1,12 @@
 use crate::state::{Mining, RewardPool};
 use everlend_utils::{assert_account_key, AccountLoader};
 use solana_program::account_info::AccountInfo;
+use solana_program::clock::Clock;
 use solana_program::entrypoint::ProgramResult;
 use solana_program::program_error::ProgramError;
 use solana_program::program_pack::Pack;
 use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
 
 /// Instruction context
 pub struct ClaimContext<'a, 'b> {
@@ -45,8 +47,8 @@ impl<'a, 'b> ClaimContext<'a, 'b> {
 
     /// Process instruction
     pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
-        let reward_pool = RewardPool::unpack(&self.reward_pool.data.borrow())?;
-        let mut mining = Mining::unpack(&self.mining.data.borrow())?;
+        let mut reward_pool = RewardPool::unpack(&self.reward_pool.data.borrow())?;
+        let mut mining = Mining::unpack_any(&self.mining.data.borrow())?;
 
         let reward_pool_seeds = &[
             b"reward_pool".as_ref(),
@@ -81,7 +83,8 @@ impl<'a, 'b> ClaimContext<'a, 'b> {
             )?;
         }
 
-        mining.refresh_rewards(reward_pool.vaults.iter())?;
+        reward_pool.refresh_mining(&mut mining, Clock::get()?.slot)?;
+        RewardPool::pack(reward_pool, *self.reward_pool.data.borrow_mut())?;
 
         let mut reward_index = mining.reward_index_mut(*self.reward_mint.key);
         let amount = reward_index.rewards;
@@ -96,7 +99,7 @@ impl<'a, 'b> ClaimContext<'a, 'b> {
             &[reward_pool_seeds],
         )?;
 
-        Mining::pack(mining, *self.mining.data.borrow_mut())?;
+        Mining::pack_any(mining, *self.mining.data.borrow_mut())?;
 
         Ok(())
     }
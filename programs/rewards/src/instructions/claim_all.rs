@@ -0,0 +1,126 @@
+use crate::state::{Mining, RewardPool};
+use everlend_utils::{assert_account_key, AccountLoader};
+use solana_program::account_info::AccountInfo;
+use solana_program::clock::Clock;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
+
+/// Accounts to claim the reward of one vault
+struct RewardAccounts<'a, 'b> {
+    reward_mint: &'a AccountInfo<'b>,
+    vault: &'a AccountInfo<'b>,
+    user_reward_token_account: &'a AccountInfo<'b>,
+}
+
+/// Instruction context
+pub struct ClaimAllContext<'a, 'b> {
+    reward_pool: &'a AccountInfo<'b>,
+    mining: &'a AccountInfo<'b>,
+    user: &'a AccountInfo<'b>,
+    rewards: Vec<RewardAccounts<'a, 'b>>,
+}
+
+impl<'a, 'b> ClaimAllContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<ClaimAllContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let user = AccountLoader::next_signer(account_info_iter)?;
+        let _token_program = AccountLoader::next_with_key(account_info_iter, &spl_token::id())?;
+
+        // Reward mint, vault and user reward token account for each vault
+        let mut rewards = Vec::new();
+        for _ in 0..account_info_iter.len() / 3 {
+            let reward_mint = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+            let vault = AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+            let user_reward_token_account =
+                AccountLoader::next_with_owner(account_info_iter, &spl_token::id())?;
+
+            rewards.push(RewardAccounts {
+                reward_mint,
+                vault,
+                user_reward_token_account,
+            });
+        }
+
+        Ok(ClaimAllContext {
+            reward_pool,
+            mining,
+            user,
+            rewards,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
+        let mut reward_pool = RewardPool::unpack(&self.reward_pool.data.borrow())?;
+        let mut mining = Mining::unpack_any(&self.mining.data.borrow())?;
+
+        let reward_pool_seeds = &[
+            b"reward_pool".as_ref(),
+            &reward_pool.rewards_root.to_bytes()[..32],
+            &reward_pool.liquidity_mint.to_bytes()[..32],
+            &[reward_pool.bump],
+        ];
+
+        {
+            assert_account_key(self.user, &mining.owner)?;
+            assert_account_key(self.reward_pool, &mining.reward_pool)?;
+            assert_account_key(
+                self.reward_pool,
+                &Pubkey::create_program_address(reward_pool_seeds, program_id)?,
+            )?;
+
+            // Accounts for every vault in the pool order
+            if self.rewards.len() != reward_pool.vaults.len() {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+        }
+
+        reward_pool.refresh_mining(&mut mining, Clock::get()?.slot)?;
+
+        for (accounts, vault) in self.rewards.iter().zip(reward_pool.vaults.iter()) {
+            assert_account_key(accounts.reward_mint, &vault.reward_mint)?;
+
+            let vault_seeds = &[
+                b"vault".as_ref(),
+                &self.reward_pool.key.to_bytes()[..32],
+                &vault.reward_mint.to_bytes()[..32],
+                &[vault.bump],
+            ];
+            assert_account_key(
+                accounts.vault,
+                &Pubkey::create_program_address(vault_seeds, program_id)?,
+            )?;
+
+            let reward_index = mining.reward_index_mut(vault.reward_mint);
+            let amount = reward_index.rewards;
+            if amount == 0 {
+                continue;
+            }
+
+            reward_index.rewards = 0;
+
+            everlend_utils::cpi::spl_token::transfer(
+                accounts.vault.clone(),
+                accounts.user_reward_token_account.clone(),
+                self.reward_pool.clone(),
+                amount,
+                &[reward_pool_seeds],
+            )?;
+        }
+
+        RewardPool::pack(reward_pool, *self.reward_pool.data.borrow_mut())?;
+        Mining::pack_any(mining, *self.mining.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -1,12 +1,12 @@
 use crate::state::RewardPool;
 use everlend_utils::{assert_account_key, AccountLoader, EverlendError};
 use solana_program::account_info::AccountInfo;
+use solana_program::clock::Clock;
 use solana_program::entrypoint::ProgramResult;
 use solana_program::program_error::ProgramError;
 use solana_program::program_pack::Pack;
 use solana_program::pubkey::Pubkey;
-
-const FEE_PERCENTAGE: u64 = 2;
+use solana_program::sysvar::Sysvar;
 
 /// Instruction context
 pub struct FillVaultContext<'a, 'b> {
@@ -49,7 +49,7 @@ impl<'a, 'b> FillVaultContext<'a, 'b> {
     pub fn process(&self, program_id: &Pubkey, amount: u64) -> ProgramResult {
         let mut reward_pool = RewardPool::unpack(&self.reward_pool.data.borrow())?;
 
-        {
+        let fee_amount = {
             let vault = reward_pool
                 .vaults
                 .iter()
@@ -65,19 +65,16 @@ impl<'a, 'b> FillVaultContext<'a, 'b> {
             assert_account_key(
                 self.vault,
                 &Pubkey::create_program_address(vault_seeds, program_id)?,
-            )?
-        }
+            )?;
+
+            vault.fee_amount(amount)?
+        };
 
-        let fee_amount = amount
-            .checked_mul(FEE_PERCENTAGE)
-            .ok_or(EverlendError::MathOverflow)?
-            .checked_div(100)
-            .ok_or(EverlendError::MathOverflow)?;
         let reward_amount = amount
             .checked_sub(fee_amount)
             .ok_or(EverlendError::MathOverflow)?;
 
-        reward_pool.fill(*self.reward_mint.key, reward_amount)?;
+        reward_pool.fill(*self.reward_mint.key, reward_amount, Clock::get()?.slot)?;
 
         everlend_utils::cpi::spl_token::transfer(
             self.source_token_account.clone(),
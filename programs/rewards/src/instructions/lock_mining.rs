@@ -0,0 +1,62 @@
+use crate::state::{DeprecatedMining, Mining, RewardPool};
+use everlend_utils::cpi::system::realloc_with_rent;
+use everlend_utils::{assert_account_key, AccountLoader};
+use solana_program::account_info::AccountInfo;
+use solana_program::clock::{Clock, Slot};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_program;
+use solana_program::sysvar::Sysvar;
+
+/// Instruction context
+pub struct LockMiningContext<'a, 'b> {
+    reward_pool: &'a AccountInfo<'b>,
+    mining: &'a AccountInfo<'b>,
+    user: &'a AccountInfo<'b>,
+}
+
+impl<'a, 'b> LockMiningContext<'a, 'b> {
+    /// New instruction context
+    pub fn new(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<LockMiningContext<'a, 'b>, ProgramError> {
+        let account_info_iter = &mut accounts.iter().enumerate();
+
+        let reward_pool = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let mining = AccountLoader::next_with_owner(account_info_iter, program_id)?;
+        let user = AccountLoader::next_signer(account_info_iter)?;
+        let _system_program =
+            AccountLoader::next_with_key(account_info_iter, &system_program::id())?;
+
+        Ok(LockMiningContext {
+            reward_pool,
+            mining,
+            user,
+        })
+    }
+
+    /// Process instruction
+    pub fn process(&self, _program_id: &Pubkey, duration: Slot) -> ProgramResult {
+        let mut reward_pool = RewardPool::unpack(&self.reward_pool.data.borrow())?;
+        let mut mining = Mining::unpack_any(&self.mining.data.borrow())?;
+
+        assert_account_key(self.user, &mining.owner)?;
+        assert_account_key(self.reward_pool, &mining.reward_pool)?;
+
+        reward_pool.lock(&mut mining, duration, Clock::get()?.slot)?;
+
+        // Lock fields don't fit into the deprecated layout
+        if self.mining.data_len() == DeprecatedMining::LEN {
+            realloc_with_rent(self.mining, self.user, &Rent::get()?, Mining::LEN)?;
+        }
+
+        RewardPool::pack(reward_pool, *self.reward_pool.data.borrow_mut())?;
+        Mining::pack(mining, *self.mining.data.borrow_mut())?;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,5 @@
 use crate::find_reward_pool_program_address;
-use crate::state::{DeprecatedRewardPool, RewardPool, RewardsRoot};
+use crate::state::{DeprecatedRewardPool, DeprecatedRewardPoolV1, RewardPool, RewardsRoot};
 use everlend_utils::cpi::system::realloc_with_rent;
 use everlend_utils::{assert_account_key, AccountLoader};
 use solana_program::account_info::AccountInfo;
@@ -49,8 +49,17 @@ impl<'a, 'b> MigratePoolContext<'a, 'b> {
     pub fn process(&self, program_id: &Pubkey) -> ProgramResult {
         let rent = Rent::from_account_info(self.rent)?;
 
-        let deprecated_pool = DeprecatedRewardPool::unpack(&self.reward_pool.data.borrow())?;
-        let reward_pool = RewardPool::migrate(&deprecated_pool);
+        let reward_pool = match self.reward_pool.data_len() {
+            DeprecatedRewardPool::LEN => {
+                let deprecated_pool =
+                    DeprecatedRewardPool::unpack(&self.reward_pool.data.borrow())?;
+                RewardPool::migrate(&deprecated_pool)
+            }
+            DeprecatedRewardPoolV1::LEN => {
+                DeprecatedRewardPoolV1::unpack(&self.reward_pool.data.borrow())?.into()
+            }
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
 
         let (reward_pool_pubkey, _) = find_reward_pool_program_address(
             program_id,
@@ -61,8 +70,8 @@ impl<'a, 'b> MigratePoolContext<'a, 'b> {
             let rewards_root = RewardsRoot::unpack(&self.rewards_root.data.borrow())?;
             assert_account_key(self.payer, &rewards_root.authority)?;
             assert_account_key(self.reward_pool, &reward_pool_pubkey)?;
-            assert_account_key(self.rewards_root, &deprecated_pool.rewards_root)?;
-            assert_account_key(self.liquidity_mint, &deprecated_pool.liquidity_mint)?;
+            assert_account_key(self.rewards_root, &reward_pool.rewards_root)?;
+            assert_account_key(self.liquidity_mint, &reward_pool.liquidity_mint)?;
         }
 
         realloc_with_rent(self.reward_pool, self.payer, &rent, RewardPool::LEN)?;
@@ -1,21 +1,39 @@
 //! Program instructions
 
+mod accept_root_authority;
 mod add_vault;
+mod cancel_root_authority_proposal;
 mod claim;
+mod claim_all;
 mod deposit_mining;
+mod expire_lock;
 mod fill_vault;
 mod initialize_mining;
 mod initialize_pool;
 mod initialize_root;
+mod lock_mining;
 mod migrate_pool;
+mod migrate_root;
+mod propose_root_authority;
+mod set_vault_emission;
+mod set_vault_fee;
 mod withdraw_mining;
 
+pub use accept_root_authority::*;
 pub use add_vault::*;
+pub use cancel_root_authority_proposal::*;
 pub use claim::*;
+pub use claim_all::*;
 pub use deposit_mining::*;
+pub use expire_lock::*;
 pub use fill_vault::*;
 pub use initialize_mining::*;
 pub use initialize_pool::*;
 pub use initialize_root::*;
+pub use lock_mining::*;
 pub use migrate_pool::*;
+pub use migrate_root::*;
+pub use propose_root_authority::*;
+pub use set_vault_emission::*;
+pub use set_vault_fee::*;
 pub use withdraw_mining::*;
@@ -1,10 +1,12 @@
 use crate::state::{Mining, RewardPool};
 use everlend_utils::{assert_account_key, AccountLoader};
 use solana_program::account_info::AccountInfo;
+use solana_program::clock::Clock;
 use solana_program::entrypoint::ProgramResult;
 use solana_program::program_error::ProgramError;
 use solana_program::program_pack::Pack;
 use solana_program::pubkey::Pubkey;
+use solana_program::sysvar::Sysvar;
 
 /// Instruction context
 pub struct WithdrawMiningContext<'a, 'b> {
@@ -38,7 +40,7 @@ impl<'a, 'b> WithdrawMiningContext<'a, 'b> {
     /// Process instruction
     pub fn process(&self, program_id: &Pubkey, amount: u64) -> ProgramResult {
         let mut reward_pool = RewardPool::unpack(&self.reward_pool.data.borrow())?;
-        let mut mining = Mining::unpack(&self.mining.data.borrow())?;
+        let mut mining = Mining::unpack_any(&self.mining.data.borrow())?;
 
         {
             let mining_pubkey = Pubkey::create_program_address(
@@ -56,10 +58,10 @@ impl<'a, 'b> WithdrawMiningContext<'a, 'b> {
             assert_account_key(self.user, &mining.owner)?;
         }
 
-        reward_pool.withdraw(&mut mining, amount)?;
+        reward_pool.withdraw(&mut mining, amount, Clock::get()?.slot)?;
 
         RewardPool::pack(reward_pool, *self.reward_pool.data.borrow_mut())?;
-        Mining::pack(mining, *self.mining.data.borrow_mut())?;
+        Mining::pack_any(mining, *self.mining.data.borrow_mut())?;
 
         Ok(())
     }
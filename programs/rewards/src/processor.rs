@@ -53,5 +53,42 @@ pub fn process_instruction(
             msg!("RewardsInstruction: MigratePool");
             MigratePoolContext::new(program_id, accounts)?.process(program_id)
         }
+        RewardsInstruction::SetVaultFee { fee_bps } => {
+            msg!("RewardsInstruction: SetVaultFee");
+            SetVaultFeeContext::new(program_id, accounts)?.process(program_id, fee_bps)
+        }
+        RewardsInstruction::SetVaultEmission { emission_duration } => {
+            msg!("RewardsInstruction: SetVaultEmission");
+            SetVaultEmissionContext::new(program_id, accounts)?
+                .process(program_id, emission_duration)
+        }
+        RewardsInstruction::LockMining { duration } => {
+            msg!("RewardsInstruction: LockMining");
+            LockMiningContext::new(program_id, accounts)?.process(program_id, duration)
+        }
+        RewardsInstruction::ClaimAll => {
+            msg!("RewardsInstruction: ClaimAll");
+            ClaimAllContext::new(program_id, accounts)?.process(program_id)
+        }
+        RewardsInstruction::ProposeRootAuthority => {
+            msg!("RewardsInstruction: ProposeRootAuthority");
+            ProposeRootAuthorityContext::new(program_id, accounts)?.process(program_id)
+        }
+        RewardsInstruction::AcceptRootAuthority => {
+            msg!("RewardsInstruction: AcceptRootAuthority");
+            AcceptRootAuthorityContext::new(program_id, accounts)?.process(program_id)
+        }
+        RewardsInstruction::CancelRootAuthorityProposal => {
+            msg!("RewardsInstruction: CancelRootAuthorityProposal");
+            CancelRootAuthorityProposalContext::new(program_id, accounts)?.process(program_id)
+        }
+        RewardsInstruction::ExpireLock => {
+            msg!("RewardsInstruction: ExpireLock");
+            ExpireLockContext::new(program_id, accounts)?.process(program_id)
+        }
+        RewardsInstruction::MigrateRoot => {
+            msg!("RewardsInstruction: MigrateRoot");
+            MigrateRootContext::new(program_id, accounts)?.process(program_id)
+        }
     }
 }
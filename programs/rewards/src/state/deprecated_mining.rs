@@ -0,0 +1,83 @@
+use crate::state::{Mining, RewardIndex, MAX_REWARDS};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::msg;
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::{IsInitialized, Pack, Sealed};
+use solana_program::pubkey::Pubkey;
+use std::convert::TryFrom;
+
+/// Deprecated Mining without lock
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct DeprecatedMining {
+    /// Anchor id(For Anchor legacy contract compatibility)
+    pub anchor_id: [u8; 8],
+    /// Reward pool address
+    pub reward_pool: Pubkey,
+    /// Saved bump for mining account
+    pub bump: u8,
+    /// Share
+    pub share: u64,
+    /// Mining owner
+    pub owner: Pubkey,
+    /// Reward indexes
+    pub indexes: Vec<RewardIndex>,
+}
+
+impl From<DeprecatedMining> for Mining {
+    fn from(mining: DeprecatedMining) -> Self {
+        Mining {
+            anchor_id: mining.anchor_id,
+            reward_pool: mining.reward_pool,
+            bump: mining.bump,
+            share: mining.share,
+            owner: mining.owner,
+            indexes: mining.indexes,
+            ..Default::default()
+        }
+    }
+}
+
+impl TryFrom<Mining> for DeprecatedMining {
+    type Error = ProgramError;
+
+    fn try_from(mining: Mining) -> Result<Self, Self::Error> {
+        // Lock requires the account to be reallocated
+        if mining.locked_share != 0 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(DeprecatedMining {
+            anchor_id: mining.anchor_id,
+            reward_pool: mining.reward_pool,
+            bump: mining.bump,
+            share: mining.share,
+            owner: mining.owner,
+            indexes: mining.indexes,
+        })
+    }
+}
+
+impl Sealed for DeprecatedMining {}
+impl Pack for DeprecatedMining {
+    const LEN: usize = 8 + (32 + 1 + 8 + 32 + (4 + RewardIndex::LEN * MAX_REWARDS));
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!("{}", err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for DeprecatedMining {
+    fn is_initialized(&self) -> bool {
+        self.owner != Pubkey::default()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::state::{RewardVault, MAX_REWARDS};
+use crate::state::{AccountType, RewardPool, RewardVault, DEFAULT_FEE_BPS, MAX_REWARDS};
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::msg;
 use solana_program::program_error::ProgramError;
@@ -20,7 +20,7 @@ pub struct DeprecatedRewardPool {
     /// Reward total share
     pub total_share: u64,
     /// A set of all possible rewards that we can get for this pool
-    pub vaults: Vec<RewardVault>,
+    pub vaults: Vec<DeprecatedRewardVault>,
     /// The address responsible for the charge of rewards for users.
     /// It executes deposits on the rewards pools.
     pub deposit_authority: Pubkey,
@@ -28,7 +28,7 @@ pub struct DeprecatedRewardPool {
 
 impl Sealed for DeprecatedRewardPool {}
 impl Pack for DeprecatedRewardPool {
-    const LEN: usize = 8 + (32 + 1 + 32 + 8 + (4 + RewardVault::LEN * MAX_REWARDS) + 32);
+    const LEN: usize = 8 + (32 + 1 + 32 + 8 + (4 + DeprecatedRewardVault::LEN * MAX_REWARDS) + 32);
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -50,3 +50,94 @@ impl IsInitialized for DeprecatedRewardPool {
         self.rewards_root != Pubkey::default()
     }
 }
+
+/// Deprecated Reward pool without vault fees
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct DeprecatedRewardPoolV1 {
+    /// Account type - RewardPool
+    pub account_type: AccountType,
+    /// Rewards root account (ex-Config program account)
+    pub rewards_root: Pubkey,
+    /// Saved bump for reward pool account
+    pub bump: u8,
+    /// Liquidity mint
+    pub liquidity_mint: Pubkey,
+    /// Reward total share
+    pub total_share: u64,
+    /// A set of all possible rewards that we can get for this pool
+    pub vaults: Vec<DeprecatedRewardVault>,
+    /// The address responsible for the charge of rewards for users.
+    /// It executes deposits on the rewards pools.
+    pub deposit_authority: Pubkey,
+}
+
+impl Sealed for DeprecatedRewardPoolV1 {}
+impl Pack for DeprecatedRewardPoolV1 {
+    const LEN: usize = 1 + (32 + 1 + 32 + 8 + (4 + DeprecatedRewardVault::LEN * MAX_REWARDS) + 32);
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<DeprecatedRewardPoolV1, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|err| {
+            msg!("Failed to deserialize");
+            msg!("{}", err.to_string());
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for DeprecatedRewardPoolV1 {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::RewardPool
+    }
+}
+
+impl From<DeprecatedRewardPoolV1> for RewardPool {
+    fn from(deprecated: DeprecatedRewardPoolV1) -> Self {
+        RewardPool {
+            account_type: deprecated.account_type,
+            rewards_root: deprecated.rewards_root,
+            bump: deprecated.bump,
+            liquidity_mint: deprecated.liquidity_mint,
+            total_share: deprecated.total_share,
+            vaults: deprecated.vaults.into_iter().map(Into::into).collect(),
+            deposit_authority: deprecated.deposit_authority,
+            lock_expirations: vec![],
+        }
+    }
+}
+
+/// Deprecated Reward vault without fee
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default, Clone)]
+pub struct DeprecatedRewardVault {
+    /// Bump of vault account
+    pub bump: u8,
+    /// Reward mint address
+    pub reward_mint: Pubkey,
+    /// Index with precision
+    pub index_with_precision: u128,
+    /// Fee account address
+    pub fee_account: Pubkey,
+}
+
+impl DeprecatedRewardVault {
+    /// LEN
+    pub const LEN: usize = 1 + 32 + 16 + 32;
+}
+
+impl From<DeprecatedRewardVault> for RewardVault {
+    fn from(deprecated: DeprecatedRewardVault) -> Self {
+        RewardVault {
+            bump: deprecated.bump,
+            reward_mint: deprecated.reward_mint,
+            index_with_precision: deprecated.index_with_precision,
+            fee_account: deprecated.fee_account,
+            fee_bps: DEFAULT_FEE_BPS,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::state::{AccountType, RewardsRoot};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::msg;
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::{IsInitialized, Pack, Sealed};
+use solana_program::pubkey::Pubkey;
+
+/// Deprecated Rewards Root
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
+pub struct DeprecatedRewardsRoot {
+    /// Account type - RewardsRoot
+    pub account_type: AccountType,
+    /// Authority address
+    pub authority: Pubkey,
+}
+
+impl Sealed for DeprecatedRewardsRoot {}
+impl Pack for DeprecatedRewardsRoot {
+    const LEN: usize = 1 + 32;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let mut src_mut = src;
+        Self::deserialize(&mut src_mut).map_err(|_| {
+            msg!("Failed to deserialize");
+            msg!(
+                "Actual LEN: {}",
+                std::mem::size_of::<DeprecatedRewardsRoot>()
+            );
+            ProgramError::InvalidAccountData
+        })
+    }
+}
+
+impl IsInitialized for DeprecatedRewardsRoot {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::RewardsRoot
+    }
+}
+
+impl From<DeprecatedRewardsRoot> for RewardsRoot {
+    fn from(deprecated: DeprecatedRewardsRoot) -> Self {
+        RewardsRoot {
+            account_type: deprecated.account_type,
+            authority: deprecated.authority,
+            pending_authority: Pubkey::default(),
+        }
+    }
+}
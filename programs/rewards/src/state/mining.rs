@@ -1,13 +1,22 @@
-use crate::state::{RewardVault, MAX_REWARDS, PRECISION};
+use crate::state::{DeprecatedMining, RewardVault, MAX_REWARDS, PRECISION};
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use everlend_utils::EverlendError;
+use solana_program::clock::Slot;
 use solana_program::entrypoint::ProgramResult;
 use solana_program::msg;
 use solana_program::program_error::ProgramError;
 use solana_program::program_pack::{IsInitialized, Pack, Sealed};
 use solana_program::pubkey::Pubkey;
+use std::convert::TryFrom;
 use std::slice::Iter;
 
+/// Max lock duration in slots (~1 year)
+pub const MAX_LOCK_DURATION: Slot = 78_840_000;
+/// Boost of the share locked for max lock duration in basis points
+pub const MAX_LOCK_BOOST_BPS: u16 = 10_000;
+/// Locks end at the boundaries of lock epochs (~1 week)
+pub const LOCK_EPOCH_DURATION: Slot = 1_512_000;
+
 /// Mining
 #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
 pub struct Mining {
@@ -23,6 +32,12 @@ pub struct Mining {
     pub owner: Pubkey,
     /// Reward indexes
     pub indexes: Vec<RewardIndex>,
+    /// Share locked until the lock end slot
+    pub locked_share: u64,
+    /// Boost of the locked share in basis points
+    pub lock_boost_bps: u16,
+    /// Slot the lock expires at
+    pub lock_end_slot: Slot,
 }
 
 impl Mining {
@@ -35,9 +50,96 @@ impl Mining {
             share: 0,
             owner,
             indexes: vec![],
+            locked_share: 0,
+            lock_boost_bps: 0,
+            lock_end_slot: 0,
+        }
+    }
+
+    /// Unpack mining of the actual or deprecated layout
+    pub fn unpack_any(src: &[u8]) -> Result<Mining, ProgramError> {
+        if src.len() == DeprecatedMining::LEN {
+            return Ok(DeprecatedMining::unpack(src)?.into());
+        }
+
+        Mining::unpack(src)
+    }
+
+    /// Pack mining keeping the deprecated layout of accounts not reallocated yet
+    pub fn pack_any(mining: Mining, dst: &mut [u8]) -> ProgramResult {
+        if dst.len() == DeprecatedMining::LEN {
+            return DeprecatedMining::pack(DeprecatedMining::try_from(mining)?, dst);
+        }
+
+        Mining::pack(mining, dst)
+    }
+
+    /// Share locked at the slot
+    pub fn active_locked_share(&self, slot: Slot) -> u64 {
+        if slot < self.lock_end_slot {
+            self.locked_share
+        } else {
+            0
         }
     }
 
+    /// Extra share granted by the lock boost
+    pub fn lock_bonus(&self) -> Result<u64, ProgramError> {
+        Ok((self.locked_share as u128)
+            .checked_mul(self.lock_boost_bps as u128)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_div(10_000)
+            .ok_or(EverlendError::MathOverflow)? as u64)
+    }
+
+    /// Share weighted by the lock boost
+    pub fn weighted_share(&self) -> Result<u64, ProgramError> {
+        Ok(self
+            .share
+            .checked_add(self.lock_bonus()?)
+            .ok_or(EverlendError::MathOverflow)?)
+    }
+
+    /// Lock the whole share for the duration, rounded up to the lock epoch end.
+    /// An active lock can only be extended.
+    pub fn lock(&mut self, duration: Slot, slot: Slot) -> ProgramResult {
+        if duration == 0 || duration > MAX_LOCK_DURATION {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if self.share == 0 {
+            return Err(EverlendError::ZeroAmount.into());
+        }
+
+        let lock_end_slot = slot
+            .checked_add(duration)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_add(LOCK_EPOCH_DURATION - 1)
+            .ok_or(EverlendError::MathOverflow)?
+            / LOCK_EPOCH_DURATION
+            * LOCK_EPOCH_DURATION;
+        if lock_end_slot < self.lock_end_slot {
+            return Err(EverlendError::RewardsShareLocked.into());
+        }
+
+        self.locked_share = self.share;
+        self.lock_boost_bps = (MAX_LOCK_BOOST_BPS as u128)
+            .checked_mul(duration as u128)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_div(MAX_LOCK_DURATION as u128)
+            .ok_or(EverlendError::MathOverflow)? as u16;
+        self.lock_end_slot = lock_end_slot;
+
+        Ok(())
+    }
+
+    /// Release the lock
+    pub fn unlock(&mut self) {
+        self.locked_share = 0;
+        self.lock_boost_bps = 0;
+        self.lock_end_slot = 0;
+    }
+
     /// Returns reward index
     pub fn reward_index_mut(&mut self, reward_mint: Pubkey) -> &mut RewardIndex {
         match self
@@ -64,30 +166,52 @@ impl Mining {
 
     /// Refresh rewards
     pub fn refresh_rewards(&mut self, vaults: Iter<RewardVault>) -> ProgramResult {
-        let share = self.share;
+        let share = self.weighted_share()?;
 
         for vault in vaults {
-            let reward_index = self.reward_index_mut(vault.reward_mint);
-
-            if vault.index_with_precision > reward_index.index_with_precision {
-                let rewards = vault
-                    .index_with_precision
-                    .checked_sub(reward_index.index_with_precision)
-                    .ok_or(EverlendError::MathOverflow)?
-                    .checked_mul(share as u128)
-                    .ok_or(EverlendError::MathOverflow)?
-                    .checked_div(PRECISION)
-                    .ok_or(EverlendError::MathOverflow)?;
+            self.accrue(vault.reward_mint, vault.index_with_precision, share)?;
+        }
+
+        Ok(())
+    }
 
-                if rewards > 0 {
-                    reward_index.rewards = reward_index
-                        .rewards
-                        .checked_add(rewards as u64)
-                        .ok_or(EverlendError::MathOverflow)?;
-                }
+    /// Refresh rewards up to the vault indexes at the lock end and release the lock
+    pub fn release_lock(&mut self, vaults: Iter<RewardVault>, indexes: &[u128]) -> ProgramResult {
+        let share = self.weighted_share()?;
+
+        for (vault, index_with_precision) in vaults.zip(indexes) {
+            self.accrue(vault.reward_mint, *index_with_precision, share)?;
+        }
+        self.unlock();
+
+        Ok(())
+    }
+
+    fn accrue(
+        &mut self,
+        reward_mint: Pubkey,
+        index_with_precision: u128,
+        share: u64,
+    ) -> ProgramResult {
+        let reward_index = self.reward_index_mut(reward_mint);
+
+        if index_with_precision > reward_index.index_with_precision {
+            let rewards = index_with_precision
+                .checked_sub(reward_index.index_with_precision)
+                .ok_or(EverlendError::MathOverflow)?
+                .checked_mul(share as u128)
+                .ok_or(EverlendError::MathOverflow)?
+                .checked_div(PRECISION)
+                .ok_or(EverlendError::MathOverflow)?;
+
+            if rewards > 0 {
+                reward_index.rewards = reward_index
+                    .rewards
+                    .checked_add(rewards as u64)
+                    .ok_or(EverlendError::MathOverflow)?;
             }
 
-            reward_index.index_with_precision = vault.index_with_precision;
+            reward_index.index_with_precision = index_with_precision;
         }
 
         Ok(())
@@ -96,7 +220,7 @@ impl Mining {
 
 impl Sealed for Mining {}
 impl Pack for Mining {
-    const LEN: usize = 8 + (32 + 1 + 8 + 32 + (4 + RewardIndex::LEN * MAX_REWARDS));
+    const LEN: usize = 8 + (32 + 1 + 8 + 32 + (4 + RewardIndex::LEN * MAX_REWARDS) + 8 + 2 + 8);
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
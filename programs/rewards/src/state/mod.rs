@@ -1,12 +1,16 @@
 //! State types
 
+mod deprecated_mining;
 mod deprecated_reward_pool;
+mod deprecated_rewards_root;
 mod mining;
 mod reward_pool;
 mod rewards_root;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+pub use deprecated_mining::*;
 pub use deprecated_reward_pool::*;
+pub use deprecated_rewards_root::*;
 pub use mining::*;
 pub use reward_pool::*;
 pub use rewards_root::*;
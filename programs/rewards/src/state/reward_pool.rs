@@ -1,6 +1,7 @@
 use crate::state::{AccountType, DeprecatedRewardPool, Mining};
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use everlend_utils::EverlendError;
+use solana_program::clock::Slot;
 use solana_program::entrypoint::ProgramResult;
 use solana_program::msg;
 use solana_program::program_error::ProgramError;
@@ -11,6 +12,13 @@ use solana_program::pubkey::Pubkey;
 pub const PRECISION: u128 = 10_000_000_000_000_000;
 /// Max reward vaults
 pub const MAX_REWARDS: usize = 5;
+/// Default reward vault fee in basis points
+pub const DEFAULT_FEE_BPS: u16 = 200;
+/// Max reward vault fee in basis points
+pub const MAX_FEE_BPS: u16 = 1_000;
+/// Max lock expirations tracked by the reward pool.
+/// Expired ones are kept until all their locks are released
+pub const MAX_LOCK_EXPIRATIONS: usize = 64;
 
 /// Reward pool
 #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default)]
@@ -30,6 +38,8 @@ pub struct RewardPool {
     /// The address responsible for the charge of rewards for users.
     /// It executes deposits on the rewards pools.
     pub deposit_authority: Pubkey,
+    /// Lock bonuses grouped by the slot they expire at, ascending
+    pub lock_expirations: Vec<LockExpiration>,
 }
 
 impl RewardPool {
@@ -43,6 +53,7 @@ impl RewardPool {
             total_share: 0,
             vaults: vec![],
             deposit_authority: params.deposit_authority,
+            lock_expirations: vec![],
         }
     }
 
@@ -62,21 +73,28 @@ impl RewardPool {
     }
 
     /// Process fill
-    pub fn fill(&mut self, reward_mint: Pubkey, rewards: u64) -> ProgramResult {
-        if self.total_share == 0 {
-            return Err(EverlendError::RewardsNoDeposits.into());
-        }
+    pub fn fill(&mut self, reward_mint: Pubkey, rewards: u64, slot: Slot) -> ProgramResult {
+        self.update_vaults(slot)?;
 
+        let total_share = self.total_share;
         let vault = self
             .vaults
             .iter_mut()
             .find(|v| v.reward_mint == reward_mint)
             .ok_or(EverlendError::RewardsInvalidVault)?;
 
+        if vault.emission_duration > 0 {
+            return vault.stream(rewards, slot);
+        }
+
+        if total_share == 0 {
+            return Err(EverlendError::RewardsNoDeposits.into());
+        }
+
         let index = PRECISION
             .checked_mul(rewards as u128)
             .ok_or(EverlendError::MathOverflow)?
-            .checked_div(self.total_share as u128)
+            .checked_div(total_share as u128)
             .ok_or(EverlendError::MathOverflow)?;
 
         vault.index_with_precision = vault
@@ -87,9 +105,164 @@ impl RewardPool {
         Ok(())
     }
 
+    /// Accrue streamed rewards of all vaults up to the slot.
+    /// Lock bonuses leave the total share at their end slots.
+    pub fn update_vaults(&mut self, slot: Slot) -> ProgramResult {
+        for expiration in self.lock_expirations.iter_mut() {
+            if expiration.expired {
+                continue;
+            }
+            if expiration.end_slot > slot {
+                break;
+            }
+
+            for (vault, index_with_precision) in
+                self.vaults.iter_mut().zip(expiration.indexes.iter_mut())
+            {
+                vault.update_index(self.total_share, expiration.end_slot)?;
+                *index_with_precision = vault.index_with_precision;
+            }
+
+            self.total_share = self
+                .total_share
+                .checked_sub(expiration.bonus)
+                .ok_or(EverlendError::MathOverflow)?;
+            expiration.expired = true;
+        }
+
+        let total_share = self.total_share;
+        for vault in self.vaults.iter_mut() {
+            vault.update_index(total_share, slot)?;
+        }
+
+        Ok(())
+    }
+
+    /// Accrue rewards of the mining and release its expired lock
+    pub fn refresh_mining(&mut self, mining: &mut Mining, slot: Slot) -> ProgramResult {
+        self.update_vaults(slot)?;
+
+        if mining.locked_share > 0 && mining.lock_end_slot <= slot {
+            // The boosted share is accrued up to the lock end only
+            let position = self.lock_expiration_position(mining.lock_end_slot)?;
+            let expiration = &mut self.lock_expirations[position];
+            mining.release_lock(self.vaults.iter(), &expiration.indexes)?;
+
+            expiration.locks = expiration
+                .locks
+                .checked_sub(1)
+                .ok_or(EverlendError::MathOverflow)?;
+            if expiration.locks == 0 {
+                self.lock_expirations.remove(position);
+            }
+        }
+
+        mining.refresh_rewards(self.vaults.iter())
+    }
+
+    /// Process lock
+    pub fn lock(&mut self, mining: &mut Mining, duration: Slot, slot: Slot) -> ProgramResult {
+        self.refresh_mining(mining, slot)?;
+
+        let is_locked = mining.locked_share > 0;
+        let bonus = mining.lock_bonus()?;
+        let lock_end_slot = mining.lock_end_slot;
+        mining.lock(duration, slot)?;
+
+        if is_locked {
+            self.remove_lock(bonus, lock_end_slot)?;
+        }
+        self.add_lock(mining.lock_bonus()?, mining.lock_end_slot)
+    }
+
+    /// Add the lock bonus to its expiration
+    fn add_lock(&mut self, bonus: u64, end_slot: Slot) -> ProgramResult {
+        let position = match self
+            .lock_expirations
+            .binary_search_by_key(&end_slot, |e| e.end_slot)
+        {
+            Ok(position) => position,
+            Err(position) => {
+                if self.lock_expirations.len() >= MAX_LOCK_EXPIRATIONS {
+                    return Err(EverlendError::RewardsLockExpirationsLimit.into());
+                }
+
+                self.lock_expirations.insert(
+                    position,
+                    LockExpiration {
+                        end_slot,
+                        ..Default::default()
+                    },
+                );
+                position
+            }
+        };
+
+        let expiration = &mut self.lock_expirations[position];
+        expiration.bonus = expiration
+            .bonus
+            .checked_add(bonus)
+            .ok_or(EverlendError::MathOverflow)?;
+        expiration.locks = expiration
+            .locks
+            .checked_add(1)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        self.total_share = self
+            .total_share
+            .checked_add(bonus)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Remove the lock bonus from its pending expiration
+    fn remove_lock(&mut self, bonus: u64, end_slot: Slot) -> ProgramResult {
+        let position = self.lock_expiration_position(end_slot)?;
+
+        let expiration = &mut self.lock_expirations[position];
+        expiration.bonus = expiration
+            .bonus
+            .checked_sub(bonus)
+            .ok_or(EverlendError::MathOverflow)?;
+        expiration.locks = expiration
+            .locks
+            .checked_sub(1)
+            .ok_or(EverlendError::MathOverflow)?;
+        if expiration.locks == 0 {
+            self.lock_expirations.remove(position);
+        }
+
+        self.total_share = self
+            .total_share
+            .checked_sub(bonus)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    fn lock_expiration_position(&self, end_slot: Slot) -> Result<usize, ProgramError> {
+        self.lock_expirations
+            .binary_search_by_key(&end_slot, |e| e.end_slot)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    /// Process expire lock
+    pub fn expire_lock(&mut self, mining: &mut Mining, slot: Slot) -> ProgramResult {
+        if mining.locked_share == 0 {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        if mining.lock_end_slot > slot {
+            return Err(EverlendError::RewardsShareLocked.into());
+        }
+
+        self.refresh_mining(mining, slot)
+    }
+
     /// Process deposit
-    pub fn deposit(&mut self, mining: &mut Mining, amount: u64) -> ProgramResult {
-        mining.refresh_rewards(self.vaults.iter())?;
+    pub fn deposit(&mut self, mining: &mut Mining, amount: u64, slot: Slot) -> ProgramResult {
+        self.refresh_mining(mining, slot)?;
 
         self.total_share = self
             .total_share
@@ -104,17 +277,22 @@ impl RewardPool {
     }
 
     /// Process withdraw
-    pub fn withdraw(&mut self, mining: &mut Mining, amount: u64) -> ProgramResult {
-        mining.refresh_rewards(self.vaults.iter())?;
+    pub fn withdraw(&mut self, mining: &mut Mining, amount: u64, slot: Slot) -> ProgramResult {
+        self.refresh_mining(mining, slot)?;
 
-        self.total_share = self
-            .total_share
+        let share = mining
+            .share
             .checked_sub(amount)
             .ok_or(EverlendError::MathOverflow)?;
-        mining.share = mining
-            .share
+        if share < mining.locked_share {
+            return Err(EverlendError::RewardsShareLocked.into());
+        }
+
+        self.total_share = self
+            .total_share
             .checked_sub(amount)
             .ok_or(EverlendError::MathOverflow)?;
+        mining.share = share;
 
         Ok(())
     }
@@ -127,8 +305,14 @@ impl RewardPool {
             bump: deprecated_pool.bump,
             liquidity_mint: deprecated_pool.liquidity_mint,
             total_share: deprecated_pool.total_share,
-            vaults: deprecated_pool.vaults.clone(),
+            vaults: deprecated_pool
+                .vaults
+                .iter()
+                .cloned()
+                .map(Into::into)
+                .collect(),
             deposit_authority: deprecated_pool.deposit_authority,
+            lock_expirations: vec![],
         }
     }
 }
@@ -148,7 +332,14 @@ pub struct InitRewardPoolParams {
 
 impl Sealed for RewardPool {}
 impl Pack for RewardPool {
-    const LEN: usize = 1 + (32 + 1 + 32 + 8 + (4 + RewardVault::LEN * MAX_REWARDS) + 32);
+    const LEN: usize = 1
+        + (32
+            + 1
+            + 32
+            + 8
+            + (4 + RewardVault::LEN * MAX_REWARDS)
+            + 32
+            + (4 + LockExpiration::LEN * MAX_LOCK_EXPIRATIONS));
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -171,6 +362,26 @@ impl IsInitialized for RewardPool {
     }
 }
 
+/// Lock bonuses expiring at the same slot
+#[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default, Clone)]
+pub struct LockExpiration {
+    /// Slot the locks end at
+    pub end_slot: Slot,
+    /// Bonus share of the locks
+    pub bonus: u64,
+    /// Locks not released yet
+    pub locks: u32,
+    /// Bonus share left the total share
+    pub expired: bool,
+    /// Vault indexes at the end slot, in the order of vaults
+    pub indexes: [u128; MAX_REWARDS],
+}
+
+impl LockExpiration {
+    /// LEN
+    pub const LEN: usize = 8 + 8 + 4 + 1 + 16 * MAX_REWARDS;
+}
+
 /// Reward vault
 #[derive(Debug, BorshDeserialize, BorshSerialize, BorshSchema, Default, Clone)]
 pub struct RewardVault {
@@ -182,9 +393,107 @@ pub struct RewardVault {
     pub index_with_precision: u128,
     /// Fee account address
     pub fee_account: Pubkey,
+    /// Fee in basis points
+    pub fee_bps: u16,
+    /// Slots to stream each fill over, zero distributes fills instantly
+    pub emission_duration: Slot,
+    /// Streamed rewards per slot with precision
+    pub reward_rate_with_precision: u128,
+    /// Current stream start slot
+    pub emission_start_slot: Slot,
+    /// Current stream end slot
+    pub emission_end_slot: Slot,
+    /// Slot the index was accrued to
+    pub last_update_slot: Slot,
 }
 
 impl RewardVault {
     /// LEN
-    pub const LEN: usize = 1 + 32 + 16 + 32;
+    pub const LEN: usize = 1 + 32 + 16 + 32 + 2 + 8 + 16 + 8 + 8 + 8;
+
+    /// Accrue streamed rewards to the index.
+    /// The stream is postponed while there are no deposits, so its rewards stay for later.
+    pub fn update_index(&mut self, total_share: u64, slot: Slot) -> ProgramResult {
+        let to_slot = slot.min(self.emission_end_slot);
+        if self.reward_rate_with_precision == 0 || to_slot <= self.last_update_slot {
+            return Ok(());
+        }
+
+        if total_share == 0 {
+            // Shift the rest of the stream by the idle slots
+            self.emission_end_slot = self
+                .emission_end_slot
+                .checked_add(slot - self.last_update_slot)
+                .ok_or(EverlendError::MathOverflow)?;
+            self.last_update_slot = slot;
+
+            return Ok(());
+        }
+
+        let index = self
+            .reward_rate_with_precision
+            .checked_mul((to_slot - self.last_update_slot) as u128)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_div(total_share as u128)
+            .ok_or(EverlendError::MathOverflow)?;
+
+        self.index_with_precision = self
+            .index_with_precision
+            .checked_add(index)
+            .ok_or(EverlendError::MathOverflow)?;
+        self.last_update_slot = to_slot;
+
+        Ok(())
+    }
+
+    /// Top up the stream with rewards and extend it to at least emission duration from the slot.
+    /// The index must be accrued up to the slot.
+    pub fn stream(&mut self, rewards: u64, slot: Slot) -> ProgramResult {
+        let remaining = if slot < self.emission_end_slot {
+            self.reward_rate_with_precision
+                .checked_mul((self.emission_end_slot - slot) as u128)
+                .ok_or(EverlendError::MathOverflow)?
+        } else {
+            self.emission_start_slot = slot;
+            0
+        };
+
+        let end_slot = self.emission_end_slot.max(
+            slot.checked_add(self.emission_duration)
+                .ok_or(EverlendError::MathOverflow)?,
+        );
+
+        self.reward_rate_with_precision = PRECISION
+            .checked_mul(rewards as u128)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_add(remaining)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_div((end_slot - slot) as u128)
+            .ok_or(EverlendError::MathOverflow)?;
+        self.emission_end_slot = end_slot;
+        self.last_update_slot = slot;
+
+        Ok(())
+    }
+
+    /// Set fee and fee account
+    pub fn set_fee(&mut self, fee_account: Pubkey, fee_bps: u16) -> ProgramResult {
+        if fee_bps > MAX_FEE_BPS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        self.fee_account = fee_account;
+        self.fee_bps = fee_bps;
+
+        Ok(())
+    }
+
+    /// Calculate fee amount
+    pub fn fee_amount(&self, amount: u64) -> Result<u64, ProgramError> {
+        Ok((amount as u128)
+            .checked_mul(self.fee_bps as u128)
+            .ok_or(EverlendError::MathOverflow)?
+            .checked_div(10_000)
+            .ok_or(EverlendError::MathOverflow)? as u64)
+    }
 }
@@ -12,6 +12,8 @@ pub struct RewardsRoot {
     pub account_type: AccountType,
     /// Authority address
     pub authority: Pubkey,
+    /// Proposed authority address, takes over after accepting
+    pub pending_authority: Pubkey,
 }
 
 impl RewardsRoot {
@@ -20,13 +22,14 @@ impl RewardsRoot {
         RewardsRoot {
             account_type: AccountType::RewardsRoot,
             authority,
+            pending_authority: Pubkey::default(),
         }
     }
 }
 
 impl Sealed for RewardsRoot {}
 impl Pack for RewardsRoot {
-    const LEN: usize = 1 + (32);
+    const LEN: usize = 1 + (32 + 32);
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
@@ -12,6 +12,7 @@ use crate::utils::{
     presetup,
     TestPoolMarket,
     TestPool,
+    TestRegistry,
     TestPoolBorrowAuthority,
     get_token_balance,
     get_amount_allowed,
@@ -25,11 +26,20 @@ async fn setup() -> (
     TestPool,
     TestPoolBorrowAuthority,
     LiquidityProvider,
+    TestRegistry,
 ) {
-    let mut context = presetup().await.context;
+    let env = presetup().await;
+    let mut context = env.context;
 
     let test_pool_market = TestPoolMarket::new();
-    test_pool_market.init(&mut context).await.unwrap();
+    test_pool_market
+        .init(&mut context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+    env.registry
+        .add_collateral_pool_market(&mut context, &test_pool_market.keypair.pubkey())
+        .await
+        .unwrap();
     let test_pool = TestPool::new(&test_pool_market, None);
     test_pool
         .create(&mut context, &test_pool_market)
@@ -56,7 +66,7 @@ async fn setup() -> (
     .await;
 
     test_pool
-        .deposit(&mut context, &test_pool_market, &user, 100)
+        .deposit(&mut context, &test_pool_market, &env.registry.keypair.pubkey(), &user, 100)
         .await
         .unwrap();
 
@@ -66,12 +76,13 @@ async fn setup() -> (
         test_pool,
         test_pool_borrow_authority,
         user,
+        env.registry,
     )
 }
 
 #[tokio::test]
 async fn success() {
-    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user, registry) =
         setup().await;
     let amount_allowed = test_pool_borrow_authority
         .get_amount_allowed(&mut context)
@@ -81,6 +92,7 @@ async fn success() {
         .borrow(
             &mut context,
             &test_pool_market,
+            &registry.keypair.pubkey(),
             &test_pool_borrow_authority,
             None,
             &user.token_account,
@@ -101,7 +113,7 @@ async fn success() {
 
 #[tokio::test]
 async fn fail_wrong_borrow_authority() {
-    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user, registry) =
         setup().await;
     let amount_allowed = test_pool_borrow_authority
         .get_amount_allowed(&mut context)
@@ -112,6 +124,7 @@ async fn fail_wrong_borrow_authority() {
             .borrow(
                 &mut context,
                 &test_pool_market,
+                &registry.keypair.pubkey(),
                 &test_pool_borrow_authority,
                 Some(&Keypair::new()),
                 &user.token_account,
@@ -126,7 +139,7 @@ async fn fail_wrong_borrow_authority() {
 
 #[tokio::test]
 async fn fail_invalid_destination() {
-    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, _) =
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, _, registry) =
         setup().await;
     let amount_allowed = test_pool_borrow_authority
         .get_amount_allowed(&mut context)
@@ -137,6 +150,7 @@ async fn fail_invalid_destination() {
             .borrow(
                 &mut context,
                 &test_pool_market,
+                &registry.keypair.pubkey(),
                 &test_pool_borrow_authority,
                 None,
                 &Pubkey::new_unique(),
@@ -154,7 +168,7 @@ async fn fail_invalid_destination() {
 
 #[tokio::test]
 async fn fail_invalid_token_account() {
-    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user, registry) =
         setup().await;
     let amount_allowed =
         get_amount_allowed(&mut context, &test_pool, &test_pool_borrow_authority).await;
@@ -164,6 +178,7 @@ async fn fail_invalid_token_account() {
             &everlend_collateral_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry.keypair.pubkey(),
             &test_pool_borrow_authority.pool_borrow_authority_pubkey,
             &user.token_account,
             &Pubkey::new_unique(),
@@ -188,7 +203,7 @@ async fn fail_invalid_token_account() {
 
 #[tokio::test]
 async fn fail_invalid_pool_market() {
-    let (mut context, _test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, _test_pool_market, test_pool, test_pool_borrow_authority, user, registry) =
         setup().await;
     let amount_allowed =
         get_amount_allowed(&mut context, &test_pool, &test_pool_borrow_authority).await;
@@ -198,6 +213,7 @@ async fn fail_invalid_pool_market() {
             &everlend_collateral_pool::id(),
             &Pubkey::new_unique(),
             &test_pool.pool_pubkey,
+            &registry.keypair.pubkey(),
             &test_pool_borrow_authority.pool_borrow_authority_pubkey,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -225,7 +241,7 @@ async fn fail_invalid_pool_market() {
 
 #[tokio::test]
 async fn fail_invalid_pool() {
-    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user, registry) =
         setup().await;
     let amount_allowed =
         get_amount_allowed(&mut context, &test_pool, &test_pool_borrow_authority).await;
@@ -235,6 +251,7 @@ async fn fail_invalid_pool() {
             &everlend_collateral_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &Pubkey::new_unique(),
+            &registry.keypair.pubkey(),
             &test_pool_borrow_authority.pool_borrow_authority_pubkey,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -262,7 +279,7 @@ async fn fail_invalid_pool() {
 
 #[tokio::test]
 async fn fail_invalid_pool_borrow_authority() {
-    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user, registry) =
         setup().await;
     let amount_allowed =
         get_amount_allowed(&mut context, &test_pool, &test_pool_borrow_authority).await;
@@ -272,6 +289,7 @@ async fn fail_invalid_pool_borrow_authority() {
             &everlend_collateral_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry.keypair.pubkey(),
             &Pubkey::new_unique(),
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -296,3 +314,131 @@ async fn fail_invalid_pool_borrow_authority() {
         )
     );
 }
+
+#[tokio::test]
+async fn fail_with_amount_exceeding_share_allowed() {
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user, registry) =
+        setup().await;
+    let amount_allowed = test_pool_borrow_authority
+        .get_amount_allowed(&mut context)
+        .await;
+
+    assert_eq!(
+        test_pool
+            .borrow(
+                &mut context,
+                &test_pool_market,
+                &registry.keypair.pubkey(),
+                &test_pool_borrow_authority,
+                None,
+                &user.token_account,
+                amount_allowed + 1,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::BorrowShareAllowedExceeded as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_with_multiple_borrow_authorities() {
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user, registry) =
+        setup().await;
+
+    // 60% and 40% of the pool
+    test_pool_borrow_authority
+        .update(&mut context, &test_pool_market, &test_pool, 6_000)
+        .await
+        .unwrap();
+
+    let borrow_authority = Keypair::new();
+    let test_pool_borrow_authority2 =
+        TestPoolBorrowAuthority::new(&test_pool, borrow_authority.pubkey());
+    test_pool_borrow_authority2
+        .create(&mut context, &test_pool_market, &test_pool, 4_000)
+        .await
+        .unwrap();
+
+    test_pool
+        .borrow(
+            &mut context,
+            &test_pool_market,
+            &registry.keypair.pubkey(),
+            &test_pool_borrow_authority,
+            None,
+            &user.token_account,
+            60,
+        )
+        .await
+        .unwrap();
+
+    // Borrowed liquidity still counts to the total pool amount
+    assert_eq!(
+        test_pool_borrow_authority2
+            .get_amount_allowed(&mut context)
+            .await,
+        40
+    );
+
+    assert_eq!(
+        test_pool
+            .borrow(
+                &mut context,
+                &test_pool_market,
+                &registry.keypair.pubkey(),
+                &test_pool_borrow_authority2,
+                Some(&borrow_authority),
+                &user.token_account,
+                41,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::BorrowShareAllowedExceeded as u32)
+        )
+    );
+
+    test_pool
+        .borrow(
+            &mut context,
+            &test_pool_market,
+            &registry.keypair.pubkey(),
+            &test_pool_borrow_authority2,
+            Some(&borrow_authority),
+            &user.token_account,
+            40,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_pool
+            .borrow(
+                &mut context,
+                &test_pool_market,
+                &registry.keypair.pubkey(),
+                &test_pool_borrow_authority,
+                None,
+                &user.token_account,
+                1,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::BorrowShareAllowedExceeded as u32)
+        )
+    );
+
+    assert_eq!(
+        test_pool.get_data(&mut context).await.total_amount_borrowed,
+        100
+    );
+}
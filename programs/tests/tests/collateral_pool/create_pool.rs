@@ -10,10 +10,14 @@ use crate::utils::{
 };
 
 async fn setup() -> (ProgramTestContext, TestPoolMarket) {
-    let mut context = presetup().await.context;
+    let env = presetup().await;
+    let mut context = env.context;
 
     let test_pool_market = TestPoolMarket::new();
-    test_pool_market.init(&mut context).await.unwrap();
+    test_pool_market
+        .init(&mut context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     (context, test_pool_market)
 }
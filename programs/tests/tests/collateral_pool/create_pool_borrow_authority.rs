@@ -1,8 +1,10 @@
 #![cfg(feature = "test-bpf")]
 
 use everlend_collateral_pool::state::AccountType;
+use everlend_utils::EverlendError;
+use solana_program::instruction::InstructionError;
 use solana_program_test::*;
-use solana_sdk::{signer::Signer};
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
 use crate::utils::{
     presetup,
     TestPoolMarket,
@@ -12,10 +14,14 @@ use crate::utils::{
 };
 
 async fn setup() -> (ProgramTestContext, TestPoolMarket, TestPool) {
-    let mut context = presetup().await.context;
+    let env = presetup().await;
+    let mut context = env.context;
 
     let test_pool_market = TestPoolMarket::new();
-    test_pool_market.init(&mut context).await.unwrap();
+    test_pool_market
+        .init(&mut context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let test_pool = TestPool::new(&test_pool_market, None);
     test_pool
@@ -49,3 +55,35 @@ async fn success() {
         AccountType::PoolBorrowAuthority
     );
 }
+
+#[tokio::test]
+async fn fail_with_total_share_allowed_too_high() {
+    let (mut context, test_pool_market, test_pool) = setup().await;
+
+    let test_pool_borrow_authority =
+        TestPoolBorrowAuthority::new(&test_pool, context.payer.pubkey());
+    test_pool_borrow_authority
+        .create(&mut context, &test_pool_market, &test_pool, 6_000)
+        .await
+        .unwrap();
+
+    let test_pool_borrow_authority2 =
+        TestPoolBorrowAuthority::new(&test_pool, Keypair::new().pubkey());
+
+    assert_eq!(
+        test_pool_borrow_authority2
+            .create(&mut context, &test_pool_market, &test_pool, 4_001)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::TotalShareAllowedExceeded as u32)
+        )
+    );
+
+    test_pool_borrow_authority2
+        .create(&mut context, &test_pool_market, &test_pool, 4_000)
+        .await
+        .unwrap();
+}
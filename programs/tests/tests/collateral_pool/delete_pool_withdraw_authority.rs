@@ -14,10 +14,14 @@ use crate::utils::{
 };
 
 async fn setup() -> (ProgramTestContext, TestPoolMarket, TestPool) {
-    let mut context = presetup().await.context;
+    let env = presetup().await;
+    let mut context = env.context;
 
     let test_pool_market = TestPoolMarket::new();
-    test_pool_market.init(&mut context).await.unwrap();
+    test_pool_market
+        .init(&mut context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let test_pool = TestPool::new(&test_pool_market, None);
     test_pool
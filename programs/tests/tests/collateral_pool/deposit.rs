@@ -12,6 +12,7 @@ use crate::utils::{
     presetup,
     TestPoolMarket,
     TestPool,
+    TestRegistry,
     get_token_balance,
     EXP,
 };
@@ -25,11 +26,20 @@ async fn setup() -> (
     TestPoolMarket,
     TestPool,
     LiquidityProvider,
+    TestRegistry,
 ) {
-    let mut context = presetup().await.context;
+    let env = presetup().await;
+    let mut context = env.context;
 
     let test_pool_market = TestPoolMarket::new();
-    test_pool_market.init(&mut context).await.unwrap();
+    test_pool_market
+        .init(&mut context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+    env.registry
+        .add_collateral_pool_market(&mut context, &test_pool_market.keypair.pubkey())
+        .await
+        .unwrap();
 
     let test_pool = TestPool::new(&test_pool_market, None);
     test_pool
@@ -44,15 +54,15 @@ async fn setup() -> (
     )
     .await;
 
-    (context, test_pool_market, test_pool, user)
+    (context, test_pool_market, test_pool, user, env.registry)
 }
 
 #[tokio::test]
 async fn success() {
-    let (mut context, test_pool_market, test_pool, user) = setup().await;
+    let (mut context, test_pool_market, test_pool, user, registry) = setup().await;
 
     test_pool
-        .deposit(&mut context, &test_pool_market, &user, 100)
+        .deposit(&mut context, &test_pool_market, &registry.keypair.pubkey(), &user, 100)
         .await
         .unwrap();
 
@@ -62,15 +72,38 @@ async fn success() {
     );
 }
 
+#[tokio::test]
+async fn fail_with_other_registry() {
+    let (mut context, test_pool_market, test_pool, user, _) = setup().await;
+
+    // Registry listing the pool market but not bound to it
+    let other_registry = TestRegistry::new();
+    other_registry.init(&mut context).await.unwrap();
+    other_registry
+        .add_collateral_pool_market(&mut context, &test_pool_market.keypair.pubkey())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_pool
+            .deposit(&mut context, &test_pool_market, &other_registry.keypair.pubkey(), &user, 100)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
 #[tokio::test]
 async fn fail_with_invalid_token_account_pubkey_argument() {
-    let (mut context, test_pool_market, test_pool, user) = setup().await;
+    let (mut context, test_pool_market, test_pool, user, registry) = setup().await;
 
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
             &everlend_collateral_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry.keypair.pubkey(),
             &user.token_account,
             // Wrong pool token account pubkey
             &Pubkey::new_unique(),
@@ -95,13 +128,14 @@ async fn fail_with_invalid_token_account_pubkey_argument() {
 
 #[tokio::test]
 async fn fail_with_invalid_source_argument() {
-    let (mut context, test_pool_market, test_pool, user) = setup().await;
+    let (mut context, test_pool_market, test_pool, user, registry) = setup().await;
 
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
             &everlend_collateral_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry.keypair.pubkey(),
             // Wrong source
             &Pubkey::new_unique(),
             &test_pool.token_account.pubkey(),
@@ -129,7 +163,7 @@ async fn fail_with_invalid_source_argument() {
 
 #[tokio::test]
 async fn fail_with_invalid_pool_market_argument() {
-    let (mut context, _test_pool_market, test_pool, user) = setup().await;
+    let (mut context, _test_pool_market, test_pool, user, registry) = setup().await;
 
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
@@ -137,6 +171,7 @@ async fn fail_with_invalid_pool_market_argument() {
             // Wrong pool market
             &Pubkey::new_unique(),
             &test_pool.pool_pubkey,
+            &registry.keypair.pubkey(),
             &user.token_account,
             &test_pool.token_account.pubkey(),
             &user.owner.pubkey(),
@@ -163,7 +198,7 @@ async fn fail_with_invalid_pool_market_argument() {
 
 #[tokio::test]
 async fn fail_with_invalid_pool_argument() {
-    let (mut context, test_pool_market, test_pool, user) = setup().await;
+    let (mut context, test_pool_market, test_pool, user, registry) = setup().await;
 
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
@@ -171,6 +206,7 @@ async fn fail_with_invalid_pool_argument() {
             &test_pool_market.keypair.pubkey(),
             //Wrong pool
             &Pubkey::new_unique(),
+            &registry.keypair.pubkey(),
             &user.token_account,
             &test_pool.token_account.pubkey(),
             &user.owner.pubkey(),
@@ -197,11 +233,11 @@ async fn fail_with_invalid_pool_argument() {
 
 #[tokio::test]
 async fn fail_with_zero_amount() {
-    let (mut context, test_pool_market, test_pool, user) = setup().await;
+    let (mut context, test_pool_market, test_pool, user, registry) = setup().await;
 
     assert_eq!(
         test_pool
-            .deposit(&mut context, &test_pool_market, &user, 0)
+            .deposit(&mut context, &test_pool_market, &registry.keypair.pubkey(), &user, 0)
             .await
             .unwrap_err()
             .unwrap(),
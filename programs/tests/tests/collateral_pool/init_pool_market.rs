@@ -2,6 +2,7 @@
 
 use everlend_collateral_pool::state::AccountType;
 use solana_program_test::*;
+use solana_sdk::signer::Signer;
 use crate::utils::{
     presetup,
     TestPoolMarket,
@@ -9,12 +10,17 @@ use crate::utils::{
 
 #[tokio::test]
 async fn success() {
-    let mut context = presetup().await.context;
+    let env = presetup().await;
+    let mut context = env.context;
 
     let test_pool_market = TestPoolMarket::new();
-    test_pool_market.init(&mut context).await.unwrap();
+    test_pool_market
+        .init(&mut context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let pool_market = test_pool_market.get_data(&mut context).await;
 
     assert_eq!(pool_market.account_type, AccountType::PoolMarket);
+    assert_eq!(pool_market.registry, env.registry.keypair.pubkey());
 }
@@ -12,6 +12,7 @@ use crate::utils::{
     presetup,
     TestPoolMarket,
     TestPool,
+    TestRegistry,
     TestPoolBorrowAuthority,
     get_token_balance,
     COLLATERAL_POOL_SHARE_ALLOWED,
@@ -24,11 +25,20 @@ async fn setup() -> (
     TestPool,
     TestPoolBorrowAuthority,
     LiquidityProvider,
+    TestRegistry,
 ) {
-    let mut context = presetup().await.context;
+    let env = presetup().await;
+    let mut context = env.context;
 
     let test_pool_market = TestPoolMarket::new();
-    test_pool_market.init(&mut context).await.unwrap();
+    test_pool_market
+        .init(&mut context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+    env.registry
+        .add_collateral_pool_market(&mut context, &test_pool_market.keypair.pubkey())
+        .await
+        .unwrap();
     let test_pool = TestPool::new(&test_pool_market, None);
     test_pool
         .create(&mut context, &test_pool_market)
@@ -55,7 +65,7 @@ async fn setup() -> (
     .await;
 
     test_pool
-        .deposit(&mut context, &test_pool_market, &user, 100)
+        .deposit(&mut context, &test_pool_market, &env.registry.keypair.pubkey(), &user, 100)
         .await
         .unwrap();
 
@@ -65,12 +75,13 @@ async fn setup() -> (
         test_pool,
         test_pool_borrow_authority,
         user,
+        env.registry,
     )
 }
 
 #[tokio::test]
 async fn success() {
-    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user, registry) =
         setup().await;
     let amount_allowed = test_pool_borrow_authority
         .get_amount_allowed(&mut context)
@@ -80,6 +91,7 @@ async fn success() {
         .borrow(
             &mut context,
             &test_pool_market,
+            &registry.keypair.pubkey(),
             &test_pool_borrow_authority,
             None,
             &user.token_account,
@@ -112,7 +124,7 @@ async fn success() {
 
 #[tokio::test]
 async fn fail_with_invalid_pool_market_pubkey_argument() {
-    let (mut context, _test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, _test_pool_market, test_pool, test_pool_borrow_authority, user, _) =
         setup().await;
 
     let amount = 1;
@@ -152,7 +164,7 @@ async fn fail_with_invalid_pool_market_pubkey_argument() {
 
 #[tokio::test]
 async fn fail_with_invalid_pool_pubkey_argument() {
-    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user, _) =
         setup().await;
 
     let amount = 1;
@@ -193,7 +205,7 @@ async fn fail_with_invalid_pool_pubkey_argument() {
 
 #[tokio::test]
 async fn fail_with_invalid_pool_borrow_authority_argument() {
-    let (mut context, test_pool_market, test_pool, _test_pool_borrow_authority, user) =
+    let (mut context, test_pool_market, test_pool, _test_pool_borrow_authority, user, _) =
         setup().await;
 
     let amount = 1;
@@ -233,14 +245,17 @@ async fn fail_with_invalid_pool_borrow_authority_argument() {
 
 #[tokio::test]
 async fn fail_with_invalid_pool_market() {
-    let (mut context, _test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, _test_pool_market, test_pool, test_pool_borrow_authority, user, registry) =
         setup().await;
 
     let amount = 1;
     let interest_amount = 1;
 
     let test_pool_market = TestPoolMarket::new();
-    test_pool_market.init(&mut context).await.unwrap();
+    test_pool_market
+        .init(&mut context, &registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let tx = Transaction::new_signed_with_payer(
         &[instruction::repay(
@@ -272,7 +287,7 @@ async fn fail_with_invalid_pool_market() {
 
 #[tokio::test]
 async fn fail_with_invalid_pool_token_account() {
-    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user, _) =
         setup().await;
 
     let amount = 1;
@@ -308,7 +323,7 @@ async fn fail_with_invalid_pool_token_account() {
 
 #[tokio::test]
 async fn fail_with_invalid_repay_amount() {
-    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user) =
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user, _) =
         setup().await;
 
     let amount = 1;
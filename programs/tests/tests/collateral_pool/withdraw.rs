@@ -12,6 +12,7 @@ use crate::utils::{
     presetup,
     TestPoolMarket,
     TestPool,
+    TestRegistry,
     TestPoolWithdrawAuthority,
     get_token_balance,
     EXP,
@@ -30,11 +31,20 @@ async fn setup() -> (
     TestPool,
     TestPoolWithdrawAuthority,
     LiquidityProvider,
+    TestRegistry,
 ) {
-    let mut context = presetup().await.context;
+    let env = presetup().await;
+    let mut context = env.context;
 
     let test_pool_market = TestPoolMarket::new();
-    test_pool_market.init(&mut context).await.unwrap();
+    test_pool_market
+        .init(&mut context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+    env.registry
+        .add_collateral_pool_market(&mut context, &test_pool_market.keypair.pubkey())
+        .await
+        .unwrap();
     let test_pool = TestPool::new(&test_pool_market, None);
     test_pool
         .create(&mut context, &test_pool_market)
@@ -54,19 +64,19 @@ async fn setup() -> (
     )
     .await;
 
-    (context, test_pool_market, test_pool, withdraw_authority, user)
+    (context, test_pool_market, test_pool, withdraw_authority, user, env.registry)
 }
 
 #[tokio::test]
 async fn success() {
-    let (mut context, test_pool_market, test_pool, withdraw_authority, user) = setup().await;
+    let (mut context, test_pool_market, test_pool, withdraw_authority, user, registry) = setup().await;
 
     test_pool
-        .deposit(&mut context, &test_pool_market, &user, DEPOSIT_AMOUNT)
+        .deposit(&mut context, &test_pool_market, &registry.keypair.pubkey(), &user, DEPOSIT_AMOUNT)
         .await
         .unwrap();
     test_pool
-        .withdraw(&mut context, &test_pool_market, &withdraw_authority, None, &user, WITHDRAW_AMOUNT)
+        .withdraw(&mut context, &test_pool_market, &registry.keypair.pubkey(), &withdraw_authority, None, &user, WITHDRAW_AMOUNT)
         .await
         .unwrap();
 
@@ -82,13 +92,14 @@ async fn success() {
 
 #[tokio::test]
 async fn fail_with_invalid_token_account_pubkey_argument() {
-    let (mut context, test_pool_market, test_pool, withdraw_authority, user) = setup().await;
+    let (mut context, test_pool_market, test_pool, withdraw_authority, user, registry) = setup().await;
 
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_collateral_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry.keypair.pubkey(),
             &withdraw_authority.pool_withdraw_authority_pubkey,
             &user.token_account,
             // Wrong token account pubkey
@@ -114,11 +125,11 @@ async fn fail_with_invalid_token_account_pubkey_argument() {
 
 #[tokio::test]
 async fn fail_invalid_destination_argument() {
-    let (mut context, test_pool_market, test_pool, withdraw_authority, user) = setup().await;
+    let (mut context, test_pool_market, test_pool, withdraw_authority, user, registry) = setup().await;
 
     // 0. Deposit to 100
     test_pool
-        .deposit(&mut context, &test_pool_market, &user, 100)
+        .deposit(&mut context, &test_pool_market, &registry.keypair.pubkey(), &user, 100)
         .await
         .unwrap();
 
@@ -127,6 +138,7 @@ async fn fail_invalid_destination_argument() {
             &everlend_collateral_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry.keypair.pubkey(),
             &withdraw_authority.pool_withdraw_authority_pubkey,
             // wrong destination
             &Pubkey::new_unique(),
@@ -155,13 +167,14 @@ async fn fail_invalid_destination_argument() {
 
 #[tokio::test]
 async fn fail_withdraw_from_empty_pool_mint() {
-    let (mut context, test_pool_market, test_pool, withdraw_authority, user) = setup().await;
+    let (mut context, test_pool_market, test_pool, withdraw_authority, user, registry) = setup().await;
 
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_collateral_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry.keypair.pubkey(),
             &withdraw_authority.pool_withdraw_authority_pubkey,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -186,7 +199,7 @@ async fn fail_withdraw_from_empty_pool_mint() {
 
 #[tokio::test]
 async fn fail_with_invalid_pool_market_argument() {
-    let (mut context, _test_pool_market, test_pool, withdraw_authority, user) = setup().await;
+    let (mut context, _test_pool_market, test_pool, withdraw_authority, user, registry) = setup().await;
 
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
@@ -194,6 +207,7 @@ async fn fail_with_invalid_pool_market_argument() {
             // Wrong pool market
             &Pubkey::new_unique(),
             &test_pool.pool_pubkey,
+            &registry.keypair.pubkey(),
             &withdraw_authority.pool_withdraw_authority_pubkey,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -221,7 +235,7 @@ async fn fail_with_invalid_pool_market_argument() {
 
 #[tokio::test]
 async fn fail_with_invalid_pool_argument() {
-    let (mut context, test_pool_market, test_pool, withdraw_authority, user) = setup().await;
+    let (mut context, test_pool_market, test_pool, withdraw_authority, user, registry) = setup().await;
 
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
@@ -229,6 +243,7 @@ async fn fail_with_invalid_pool_argument() {
             &test_pool_market.keypair.pubkey(),
             //Wrong pool
             &Pubkey::new_unique(),
+            &registry.keypair.pubkey(),
             &withdraw_authority.pool_withdraw_authority_pubkey,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -256,16 +271,16 @@ async fn fail_with_invalid_pool_argument() {
 
 #[tokio::test]
 async fn fail_with_zero_amount() {
-    let (mut context, test_pool_market, test_pool, withdraw_authority, user) = setup().await;
+    let (mut context, test_pool_market, test_pool, withdraw_authority, user, registry) = setup().await;
 
     test_pool
-        .deposit(&mut context, &test_pool_market, &user, DEPOSIT_AMOUNT)
+        .deposit(&mut context, &test_pool_market, &registry.keypair.pubkey(), &user, DEPOSIT_AMOUNT)
         .await
         .unwrap();
 
     assert_eq!(
         test_pool
-            .withdraw(&mut context, &test_pool_market, &withdraw_authority, None, &user, 0)
+            .withdraw(&mut context, &test_pool_market, &registry.keypair.pubkey(), &withdraw_authority, None, &user, 0)
             .await
             .unwrap_err()
             .unwrap(),
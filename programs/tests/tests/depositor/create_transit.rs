@@ -11,7 +11,10 @@ async fn setup() -> (ProgramTestContext, TestDepositor) {
     let mut env = presetup().await;
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut env.context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let general_pool_market = TestGeneralPoolMarket::new();
     general_pool_market
@@ -1,4 +1,4 @@
-use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketsData};
+use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketData};
 use solana_program::instruction::InstructionError;
 use solana_program::{program_pack::Pack, pubkey::Pubkey};
 use solana_program_test::*;
@@ -7,7 +7,6 @@ use solana_sdk::signer::Signer;
 use solana_sdk::transaction::{Transaction, TransactionError};
 
 use everlend_liquidity_oracle::state::DistributionArray;
-use everlend_registry::state::DistributionPubkeys;
 use everlend_utils::{
     find_program_address,
     integrations::{self, MoneyMarketPubkeys},
@@ -106,7 +105,10 @@ async fn setup() -> (
     // 4. Prepare money market pool
 
     let mm_pool_market = TestPoolMarket::new();
-    mm_pool_market.init(&mut env.context).await.unwrap();
+    mm_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let mm_pool = TestPool::new(&mm_pool_market, Some(reserve.collateral.mint_pubkey));
     mm_pool
@@ -119,12 +121,16 @@ async fn setup() -> (
     // 5.1. Prepare liquidity oracle
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut env.context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
-    let mut distribution = DistributionArray::default();
+    let mut distribution: DistributionArray = vec![0; 2];
     distribution[0] = 500_000_000u64; // 50%
 
-    let test_token_oracle = TestTokenOracle::new(general_pool.token_mint_pubkey, distribution);
+    let test_token_oracle =
+        TestTokenOracle::new(general_pool.token_mint_pubkey, distribution.clone());
 
     test_token_oracle
         .init(&mut env.context, &test_liquidity_oracle, payer_pubkey)
@@ -136,7 +142,7 @@ async fn setup() -> (
             &mut env.context,
             &test_liquidity_oracle,
             payer_pubkey,
-            distribution,
+            distribution.clone(),
         )
         .await
         .unwrap();
@@ -176,9 +182,6 @@ async fn setup() -> (
         .await
         .unwrap();
 
-    let mut collateral_pool_markets = DistributionPubkeys::default();
-    collateral_pool_markets[0] = mm_pool_market.keypair.pubkey();
-
     env.registry
         .update_registry(
             &mut env.context,
@@ -193,11 +196,12 @@ async fn setup() -> (
         .unwrap();
 
     env.registry
-        .update_registry_markets(
+        .update_registry_market(
             &mut env.context,
-            UpdateRegistryMarketsData {
-                money_markets: None,
-                collateral_pool_markets: Some(collateral_pool_markets),
+            0,
+            UpdateRegistryMarketData {
+                collateral_pool_market: Some(mm_pool_market.keypair.pubkey()),
+                status: None,
             },
         )
         .await
@@ -441,12 +445,14 @@ async fn fail_with_invalid_registry() {
 
     let deposit_accounts =
         integrations::deposit_accounts(&spl_token_lending::id(), &money_market_pubkeys);
-    let deposit_collateral_storage_accounts = mm_pool.deposit_accounts(&mm_pool_market);
+    let deposit_collateral_storage_accounts =
+        mm_pool.deposit_accounts(&Pubkey::new_unique(), &mm_pool_market);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::deposit(
             &everlend_depositor::id(),
             &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
             &test_depositor.depositor.pubkey(),
             &get_liquidity_mint().1,
             &mm_pool.token_mint_pubkey,
@@ -481,7 +487,7 @@ async fn fail_with_invalid_depositor() {
         money_market,
         pyth_oracle,
         registry,
-        _,
+        general_pool_market,
         _,
         _,
         mm_pool_market,
@@ -511,11 +517,13 @@ async fn fail_with_invalid_depositor() {
     let deposit_accounts =
         integrations::deposit_accounts(&spl_token_lending::id(), &money_market_pubkeys);
 
-    let deposit_collateral_storage_accounts = mm_pool.deposit_accounts(&mm_pool_market);
+    let deposit_collateral_storage_accounts =
+        mm_pool.deposit_accounts(&registry.keypair.pubkey(), &mm_pool_market);
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::deposit(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &Pubkey::new_unique(),
             &get_liquidity_mint().1,
             &mm_pool.token_mint_pubkey,
@@ -550,7 +558,7 @@ async fn fail_with_invalid_mm_pool_market() {
         money_market,
         pyth_oracle,
         registry,
-        _,
+        general_pool_market,
         _,
         _,
         _,
@@ -580,15 +588,19 @@ async fn fail_with_invalid_mm_pool_market() {
     let deposit_accounts =
         integrations::deposit_accounts(&spl_token_lending::id(), &money_market_pubkeys);
 
-    let deposit_collateral_storage_accounts = mm_pool.deposit_accounts(&TestPoolMarket {
-        keypair: Keypair::new(),
-        manager: Keypair::new(),
-    });
+    let deposit_collateral_storage_accounts = mm_pool.deposit_accounts(
+        &registry.keypair.pubkey(),
+        &TestPoolMarket {
+            keypair: Keypair::new(),
+            manager: Keypair::new(),
+        },
+    );
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::deposit(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &get_liquidity_mint().1,
             &mm_pool.token_mint_pubkey,
@@ -623,7 +635,7 @@ async fn fail_with_invalid_mm_pool_token_account() {
         money_market,
         pyth_oracle,
         registry,
-        _,
+        general_pool_market,
         _,
         _,
         mm_pool_market,
@@ -658,12 +670,14 @@ async fn fail_with_invalid_mm_pool_token_account() {
         token_mint_pubkey: mm_pool.token_mint_pubkey,
         token_account: Keypair::new(),
     };
-    let deposit_collateral_storage_accounts = mock_mm_pool.deposit_accounts(&mm_pool_market);
+    let deposit_collateral_storage_accounts =
+        mock_mm_pool.deposit_accounts(&registry.keypair.pubkey(), &mm_pool_market);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::deposit(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &get_liquidity_mint().1,
             &mm_pool.token_mint_pubkey,
@@ -697,7 +711,7 @@ async fn fail_with_invalid_liquidity_mint() {
         money_market,
         pyth_oracle,
         registry,
-        _,
+        general_pool_market,
         _,
         _,
         mm_pool_market,
@@ -727,12 +741,14 @@ async fn fail_with_invalid_liquidity_mint() {
     let deposit_accounts =
         integrations::deposit_accounts(&spl_token_lending::id(), &money_market_pubkeys);
 
-    let deposit_collateral_storage_accounts = mm_pool.deposit_accounts(&mm_pool_market);
+    let deposit_collateral_storage_accounts =
+        mm_pool.deposit_accounts(&registry.keypair.pubkey(), &mm_pool_market);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::deposit(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &Pubkey::new_unique(),
             &mm_pool.token_mint_pubkey,
@@ -767,7 +783,7 @@ async fn fail_with_invalid_collateral_mint() {
         money_market,
         pyth_oracle,
         registry,
-        _,
+        general_pool_market,
         _,
         _,
         mm_pool_market,
@@ -804,12 +820,14 @@ async fn fail_with_invalid_collateral_mint() {
         token_mint_pubkey: Pubkey::new_unique(),
         token_account: mm_pool.token_account,
     };
-    let deposit_collateral_storage_accounts = mock_mm_pool.deposit_accounts(&mm_pool_market);
+    let deposit_collateral_storage_accounts =
+        mock_mm_pool.deposit_accounts(&registry.keypair.pubkey(), &mm_pool_market);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::deposit(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &get_liquidity_mint().1,
             &collateral_mint,
@@ -844,7 +862,7 @@ async fn fail_with_invalid_money_market_program_id() {
         money_market,
         pyth_oracle,
         registry,
-        _,
+        general_pool_market,
         _,
         _,
         mm_pool_market,
@@ -874,12 +892,14 @@ async fn fail_with_invalid_money_market_program_id() {
     let deposit_accounts =
         integrations::deposit_accounts(&spl_token_lending::id(), &money_market_pubkeys);
 
-    let deposit_collateral_storage_accounts = mm_pool.deposit_accounts(&mm_pool_market);
+    let deposit_collateral_storage_accounts =
+        mm_pool.deposit_accounts(&registry.keypair.pubkey(), &mm_pool_market);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::deposit(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &get_liquidity_mint().1,
             &mm_pool.token_mint_pubkey,
@@ -909,8 +929,23 @@ async fn fail_with_invalid_money_market_program_id() {
 
 #[tokio::test]
 async fn fail_with_invalid_money_market_accounts() {
-    let (mut context, _, pyth_oracle, registry, _, _, _, _, mm_pool, _, test_depositor, _, _, _, _) =
-        setup().await;
+    let (
+        mut context,
+        _,
+        pyth_oracle,
+        registry,
+        general_pool_market,
+        _,
+        _,
+        _,
+        mm_pool,
+        _,
+        test_depositor,
+        _,
+        _,
+        _,
+        _,
+    ) = setup().await;
 
     // Rates should be refreshed
     context.warp_to_slot(3).unwrap();
@@ -923,6 +958,7 @@ async fn fail_with_invalid_money_market_accounts() {
         &[everlend_depositor::instruction::deposit(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &get_liquidity_mint().1,
             &mm_pool.token_mint_pubkey,
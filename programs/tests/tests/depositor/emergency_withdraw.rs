@@ -0,0 +1,476 @@
+use everlend_depositor::state::RebalancingOperation;
+use everlend_liquidity_oracle::state::DistributionArray;
+use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketData};
+use everlend_utils::{
+    find_program_address,
+    integrations::{self, MoneyMarketPubkeys},
+    PDA,
+};
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+use crate::utils::*;
+
+async fn setup() -> (
+    TestEnvironment,
+    TestLiquidityOracle,
+    TestTokenOracle,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+    TestIncomePoolMarket,
+    TestIncomePool,
+    TestPoolMarket,
+    TestPool,
+    TestDepositor,
+) {
+    let mut env = presetup().await;
+
+    let payer_pubkey = env.context.payer.pubkey();
+
+    // 0. Prepare lending
+    let reserve = env
+        .spl_token_lending
+        .get_reserve_data(&mut env.context)
+        .await;
+
+    // 1. Prepare general pool
+    let general_pool_market = TestGeneralPoolMarket::new();
+    general_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let general_pool = TestGeneralPool::new(&general_pool_market, None);
+    general_pool
+        .create(&mut env.context, &general_pool_market)
+        .await
+        .unwrap();
+
+    // 1.1 Add liquidity to general pool
+    let liquidity_provider = add_liquidity_provider(
+        &mut env.context,
+        &general_pool.token_mint_pubkey,
+        &general_pool.pool_mint.pubkey(),
+        9999 * EXP,
+    )
+    .await
+    .unwrap();
+
+    let mining_acc = general_pool
+        .init_user_mining(&mut env.context, &general_pool_market, &liquidity_provider)
+        .await;
+
+    general_pool
+        .deposit(
+            &mut env.context,
+            &general_pool_market,
+            &liquidity_provider,
+            mining_acc,
+            100 * EXP,
+        )
+        .await
+        .unwrap();
+
+    // 2. Prepare income pool
+    let income_pool_market = TestIncomePoolMarket::new();
+    income_pool_market
+        .init(&mut env.context, &general_pool_market)
+        .await
+        .unwrap();
+
+    let income_pool = TestIncomePool::new(&income_pool_market, None);
+    income_pool
+        .create(&mut env.context, &income_pool_market)
+        .await
+        .unwrap();
+
+    // 3. Prepare money market pool
+    let mm_pool_market = TestPoolMarket::new();
+    mm_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let mm_pool = TestPool::new(&mm_pool_market, Some(reserve.collateral.mint_pubkey));
+    mm_pool
+        .create(&mut env.context, &mm_pool_market)
+        .await
+        .unwrap();
+
+    // 4. Prepare liquidity oracle
+    let test_liquidity_oracle = TestLiquidityOracle::new();
+    test_liquidity_oracle
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let mut distribution: DistributionArray = vec![0; 2];
+    distribution[0] = 500_000_000u64; // 50%
+
+    let test_token_oracle =
+        TestTokenOracle::new(general_pool.token_mint_pubkey, distribution.clone());
+
+    test_token_oracle
+        .init(&mut env.context, &test_liquidity_oracle, payer_pubkey)
+        .await
+        .unwrap();
+
+    test_token_oracle
+        .update(
+            &mut env.context,
+            &test_liquidity_oracle,
+            payer_pubkey,
+            distribution,
+        )
+        .await
+        .unwrap();
+
+    // 5. Prepare depositor
+    let test_depositor = TestDepositor::new();
+    test_depositor
+        .init(&mut env.context, &env.registry)
+        .await
+        .unwrap();
+
+    test_depositor
+        .create_transit(&mut env.context, &general_pool.token_mint_pubkey, None)
+        .await
+        .unwrap();
+
+    test_depositor
+        .create_transit(
+            &mut env.context,
+            &general_pool.token_mint_pubkey,
+            Some("reserve".to_string()),
+        )
+        .await
+        .unwrap();
+    let (reserve_transit_pubkey, _) = everlend_depositor::TransitPDA {
+        seed: "reserve",
+        depositor: test_depositor.depositor.pubkey(),
+        mint: general_pool.token_mint_pubkey,
+    }
+    .find_address(&everlend_depositor::id());
+    token_transfer(
+        &mut env.context,
+        &liquidity_provider.token_account,
+        &reserve_transit_pubkey,
+        &liquidity_provider.owner,
+        10000,
+    )
+    .await
+    .unwrap();
+
+    test_depositor
+        .create_transit(&mut env.context, &mm_pool.token_mint_pubkey, None)
+        .await
+        .unwrap();
+
+    // 6. Prepare borrow and withdraw authorities
+    let (depositor_authority, _) = find_program_address(
+        &everlend_depositor::id(),
+        &test_depositor.depositor.pubkey(),
+    );
+    let general_pool_borrow_authority =
+        TestGeneralPoolBorrowAuthority::new(&general_pool, depositor_authority);
+    general_pool_borrow_authority
+        .create(
+            &mut env.context,
+            &general_pool_market,
+            &general_pool,
+            COLLATERAL_POOL_SHARE_ALLOWED,
+        )
+        .await
+        .unwrap();
+
+    let withdraw_authority = TestPoolWithdrawAuthority::new(&mm_pool, &depositor_authority);
+    withdraw_authority
+        .create(
+            &mut env.context,
+            &mm_pool_market,
+            &mm_pool,
+            &depositor_authority,
+        )
+        .await
+        .unwrap();
+
+    env.registry
+        .update_registry(
+            &mut env.context,
+            UpdateRegistryData {
+                general_pool_market: Some(general_pool_market.keypair.pubkey()),
+                income_pool_market: Some(income_pool_market.keypair.pubkey()),
+                liquidity_oracle: Some(test_liquidity_oracle.keypair.pubkey()),
+                refresh_income_interval: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    env.registry
+        .update_registry_market(
+            &mut env.context,
+            0,
+            UpdateRegistryMarketData {
+                collateral_pool_market: Some(mm_pool_market.keypair.pubkey()),
+                status: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    // 7. Start rebalancing and deposit
+    test_depositor
+        .start_rebalancing(
+            &mut env.context,
+            &env.registry,
+            &general_pool_market,
+            &general_pool,
+            &test_liquidity_oracle,
+            false,
+            DistributionArray::default(),
+        )
+        .await
+        .unwrap();
+
+    env.context.warp_to_slot(3).unwrap();
+    env.pyth_oracle.update(&mut env.context, 3).await;
+
+    let money_market_pubkeys = money_market_pubkeys(&mut env).await;
+    test_depositor
+        .deposit(
+            &mut env.context,
+            &env.registry,
+            &mm_pool_market,
+            &mm_pool,
+            &spl_token_lending::id(),
+            &money_market_pubkeys,
+        )
+        .await
+        .unwrap();
+
+    (
+        env,
+        test_liquidity_oracle,
+        test_token_oracle,
+        general_pool_market,
+        general_pool,
+        income_pool_market,
+        income_pool,
+        mm_pool_market,
+        mm_pool,
+        test_depositor,
+    )
+}
+
+async fn money_market_pubkeys(env: &mut TestEnvironment) -> MoneyMarketPubkeys {
+    let reserve = env
+        .spl_token_lending
+        .get_reserve_data(&mut env.context)
+        .await;
+
+    MoneyMarketPubkeys::SPL(integrations::spl_token_lending::AccountPubkeys {
+        reserve: env.spl_token_lending.reserve_pubkey,
+        reserve_liquidity_supply: reserve.liquidity.supply_pubkey,
+        reserve_liquidity_oracle: reserve.liquidity.oracle_pubkey,
+        lending_market: env.spl_token_lending.market_pubkey,
+    })
+}
+
+#[tokio::test]
+async fn success() {
+    let (
+        mut env,
+        _,
+        _,
+        _,
+        general_pool,
+        income_pool_market,
+        income_pool,
+        mm_pool_market,
+        mm_pool,
+        test_depositor,
+    ) = setup().await;
+
+    env.context.warp_to_slot(5).unwrap();
+    env.pyth_oracle.update(&mut env.context, 5).await;
+
+    let money_market_pubkeys = money_market_pubkeys(&mut env).await;
+    test_depositor
+        .emergency_withdraw(
+            &mut env.context,
+            &env.registry,
+            &income_pool_market,
+            &income_pool,
+            &mm_pool_market,
+            &mm_pool,
+            &spl_token_lending::id(),
+            &money_market_pubkeys,
+            &env.registry.manager,
+            0,
+        )
+        .await
+        .unwrap();
+
+    let rebalancing = test_depositor
+        .get_rebalancing_data(&mut env.context, &general_pool.token_mint_pubkey)
+        .await;
+
+    assert!(rebalancing.is_completed());
+    assert!(rebalancing.is_disabled(0));
+    assert_eq!(rebalancing.steps.len(), 1);
+    assert_eq!(
+        rebalancing.steps[0].operation,
+        RebalancingOperation::Withdraw
+    );
+    assert_eq!(rebalancing.received_collateral[0], 0);
+    assert_eq!(rebalancing.distributed_liquidity[0], 0);
+    assert_eq!(
+        get_token_balance(&mut env.context, &mm_pool.token_account.pubkey()).await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn success_with_guardian_during_rebalancing() {
+    let (
+        mut env,
+        test_liquidity_oracle,
+        test_token_oracle,
+        general_pool_market,
+        general_pool,
+        income_pool_market,
+        income_pool,
+        mm_pool_market,
+        mm_pool,
+        test_depositor,
+    ) = setup().await;
+
+    let payer_pubkey = env.context.payer.pubkey();
+    let guardian = Keypair::new();
+    env.registry
+        .set_guardian(&mut env.context, &guardian.pubkey())
+        .await
+        .unwrap();
+
+    // Increase distribution, the deposit step stays pending
+    let mut distribution: DistributionArray = vec![0; 2];
+    distribution[0] = 800_000_000u64; // 80%
+    test_token_oracle
+        .update(
+            &mut env.context,
+            &test_liquidity_oracle,
+            payer_pubkey,
+            distribution,
+        )
+        .await
+        .unwrap();
+
+    test_depositor
+        .start_rebalancing(
+            &mut env.context,
+            &env.registry,
+            &general_pool_market,
+            &general_pool,
+            &test_liquidity_oracle,
+            false,
+            DistributionArray::default(),
+        )
+        .await
+        .unwrap();
+
+    let rebalancing = test_depositor
+        .get_rebalancing_data(&mut env.context, &general_pool.token_mint_pubkey)
+        .await;
+    assert!(!rebalancing.is_completed());
+    let collateral_amount = rebalancing.received_collateral[0];
+
+    env.context.warp_to_slot(5).unwrap();
+    env.pyth_oracle.update(&mut env.context, 5).await;
+
+    let money_market_pubkeys = money_market_pubkeys(&mut env).await;
+    test_depositor
+        .emergency_withdraw(
+            &mut env.context,
+            &env.registry,
+            &income_pool_market,
+            &income_pool,
+            &mm_pool_market,
+            &mm_pool,
+            &spl_token_lending::id(),
+            &money_market_pubkeys,
+            &guardian,
+            0,
+        )
+        .await
+        .unwrap();
+
+    let rebalancing = test_depositor
+        .get_rebalancing_data(&mut env.context, &general_pool.token_mint_pubkey)
+        .await;
+
+    // Pending deposit step is dropped
+    assert!(rebalancing.is_completed());
+    assert!(rebalancing.is_disabled(0));
+    assert_eq!(rebalancing.steps.len(), 1);
+    assert_eq!(
+        rebalancing.steps[0].operation,
+        RebalancingOperation::Withdraw
+    );
+    assert_eq!(
+        rebalancing.steps[0].collateral_amount,
+        Some(collateral_amount)
+    );
+    assert_eq!(rebalancing.received_collateral[0], 0);
+    assert_eq!(rebalancing.distributed_liquidity[0], 0);
+    assert_eq!(
+        get_token_balance(&mut env.context, &mm_pool.token_account.pubkey()).await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn fail_with_invalid_authority() {
+    let (
+        mut env,
+        _,
+        _,
+        _,
+        _,
+        income_pool_market,
+        income_pool,
+        mm_pool_market,
+        mm_pool,
+        test_depositor,
+    ) = setup().await;
+
+    env.registry
+        .set_guardian(&mut env.context, &Keypair::new().pubkey())
+        .await
+        .unwrap();
+
+    let money_market_pubkeys = money_market_pubkeys(&mut env).await;
+    assert_eq!(
+        test_depositor
+            .emergency_withdraw(
+                &mut env.context,
+                &env.registry,
+                &income_pool_market,
+                &income_pool,
+                &mm_pool_market,
+                &mm_pool,
+                &spl_token_lending::id(),
+                &money_market_pubkeys,
+                &Keypair::new(),
+                0,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
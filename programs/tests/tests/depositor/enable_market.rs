@@ -0,0 +1,103 @@
+use everlend_depositor::{
+    state::{InitRebalancingParams, Rebalancing},
+    RebalancingPDA,
+};
+use everlend_utils::PDA;
+use solana_program::{instruction::InstructionError, program_pack::Pack};
+use solana_program_test::*;
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+use crate::utils::*;
+
+async fn setup() -> (TestEnvironment, TestDepositor) {
+    let mut env = presetup().await;
+
+    let test_depositor = TestDepositor::new();
+    test_depositor
+        .init(&mut env.context, &env.registry)
+        .await
+        .unwrap();
+
+    // Rebalancing with the market disabled by emergency withdraw
+    let liquidity_mint = get_liquidity_mint().1;
+    let mut rebalancing = Rebalancing::default();
+    rebalancing.init(InitRebalancingParams {
+        depositor: test_depositor.depositor.pubkey(),
+        mint: liquidity_mint,
+    });
+    rebalancing.set_disabled(0, true);
+
+    let mut data = vec![0u8; Rebalancing::LEN];
+    Rebalancing::pack(rebalancing, &mut data).unwrap();
+
+    let (rebalancing_pubkey, _) = RebalancingPDA {
+        depositor: test_depositor.depositor.pubkey(),
+        mint: liquidity_mint,
+    }
+    .find_address(&everlend_depositor::id());
+    let rent = env.context.banks_client.get_rent().await.unwrap();
+    env.context.set_account(
+        &rebalancing_pubkey,
+        &AccountSharedData::from(Account {
+            lamports: rent.minimum_balance(Rebalancing::LEN),
+            data,
+            owner: everlend_depositor::id(),
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+
+    (env, test_depositor)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut env, test_depositor) = setup().await;
+
+    test_depositor
+        .enable_market(&mut env.context, &env.registry, &env.registry.manager, 0)
+        .await
+        .unwrap();
+
+    let rebalancing = test_depositor
+        .get_rebalancing_data(&mut env.context, &get_liquidity_mint().1)
+        .await;
+    assert!(!rebalancing.is_disabled(0));
+}
+
+#[tokio::test]
+async fn fail_with_invalid_manager() {
+    let (mut env, test_depositor) = setup().await;
+
+    assert_eq!(
+        test_depositor
+            .enable_market(&mut env.context, &env.registry, &Keypair::new(), 0)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_with_invalid_market_index() {
+    let (mut env, test_depositor) = setup().await;
+
+    assert_eq!(
+        test_depositor
+            .enable_market(
+                &mut env.context,
+                &env.registry,
+                &env.registry.manager,
+                u8::MAX
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
@@ -16,7 +16,10 @@ async fn success() {
     let mut env = presetup().await;
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut env.context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let general_pool_market = TestGeneralPoolMarket::new();
     general_pool_market
@@ -46,7 +49,10 @@ async fn fail_second_time_init() {
     let mut env = presetup().await;
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut env.context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let general_pool_market = TestGeneralPoolMarket::new();
     general_pool_market
@@ -98,7 +104,10 @@ async fn fail_with_invalid_registry() {
     let mut env = presetup().await;
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut env.context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let general_pool_market = TestGeneralPoolMarket::new();
     general_pool_market
@@ -156,7 +165,10 @@ async fn fail_with_invalid_uncreated_depositor_account() {
     let mut env = presetup().await;
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut env.context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let general_pool_market = TestGeneralPoolMarket::new();
     general_pool_market
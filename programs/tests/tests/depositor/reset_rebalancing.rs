@@ -1,5 +1,5 @@
 use everlend_liquidity_oracle::state::DistributionArray;
-use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketsData};
+use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketData};
 use everlend_utils::{find_program_address, PDA};
 use solana_program_test::*;
 use solana_sdk::signer::Signer;
@@ -77,7 +77,10 @@ async fn setup() -> (TestEnvironment, TestGeneralPool, TestDepositor) {
     // 3. Prepare money market pool
 
     let mm_pool_market = TestPoolMarket::new();
-    mm_pool_market.init(&mut env.context).await.unwrap();
+    mm_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let mm_pool = TestPool::new(&mm_pool_market, Some(reserve.collateral.mint_pubkey));
     mm_pool
@@ -90,12 +93,16 @@ async fn setup() -> (TestEnvironment, TestGeneralPool, TestDepositor) {
     // 4.1. Prepare liquidity oracle
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut env.context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
-    let mut distribution = DistributionArray::default();
+    let mut distribution: DistributionArray = vec![0; 2];
     distribution[0] = 500_000_000u64; // 50%
 
-    let test_token_oracle = TestTokenOracle::new(general_pool.token_mint_pubkey, distribution);
+    let test_token_oracle =
+        TestTokenOracle::new(general_pool.token_mint_pubkey, distribution.clone());
 
     test_token_oracle
         .init(&mut env.context, &test_liquidity_oracle, payer_pubkey)
@@ -172,10 +179,6 @@ async fn setup() -> (TestEnvironment, TestGeneralPool, TestDepositor) {
         .await
         .unwrap();
 
-    let ten = [1, 2, 3, 4, 5, 6, 7, 8, 9, 0];
-    let mut collateral_pool_markets = ten.map(|_| mm_pool_market.keypair.pubkey().clone());
-    collateral_pool_markets[0] = mm_pool_market.keypair.pubkey();
-
     env.registry
         .update_registry(
             &mut env.context,
@@ -190,11 +193,12 @@ async fn setup() -> (TestEnvironment, TestGeneralPool, TestDepositor) {
         .unwrap();
 
     env.registry
-        .update_registry_markets(
+        .update_registry_market(
             &mut env.context,
-            UpdateRegistryMarketsData {
-                money_markets: None,
-                collateral_pool_markets: Some(collateral_pool_markets),
+            0,
+            UpdateRegistryMarketData {
+                collateral_pool_market: Some(mm_pool_market.keypair.pubkey()),
+                status: None,
             },
         )
         .await
@@ -1,10 +1,12 @@
 use crate::utils::*;
-use everlend_depositor::state::{Rebalancing, RebalancingOperation};
+use everlend_depositor::state::{
+    MarketLimit, Rebalancing, RebalancingLimits, RebalancingOperation,
+};
 use everlend_depositor::utils::calculate_amount_to_distribute;
 use everlend_liquidity_oracle::state::{DistributionArray, TokenOracle};
-use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketsData};
-use everlend_registry::state::DistributionPubkeys;
-use everlend_utils::{abs_diff, percent_ratio, PDA};
+use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketData};
+use everlend_registry::state::MarketStatus;
+use everlend_utils::{abs_diff, percent_ratio, PDA, PRECISION_SCALER};
 use everlend_utils::{
     find_program_address,
     integrations::{self, MoneyMarketPubkeys},
@@ -13,10 +15,10 @@ use everlend_utils::{
 use solana_program::instruction::InstructionError;
 use solana_program::program_error::ProgramError;
 use solana_program::pubkey::Pubkey;
+use solana_program::system_instruction;
 use solana_program_test::*;
-use solana_sdk::signature::Keypair;
 use solana_sdk::transaction::Transaction;
-use solana_sdk::{signer::Signer, transaction::TransactionError};
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
 use std::vec;
 
 async fn setup(
@@ -109,7 +111,10 @@ async fn setup(
     // 3. Prepare money market pool
 
     let mm_pool_market = TestPoolMarket::new();
-    mm_pool_market.init(&mut env.context).await.unwrap();
+    mm_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let mm_pool = TestPool::new(&mm_pool_market, Some(reserve.collateral.mint_pubkey));
     mm_pool
@@ -122,12 +127,16 @@ async fn setup(
     // 4.1. Prepare liquidity oracle
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut env.context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
-    let mut distribution = DistributionArray::default();
+    let mut distribution: DistributionArray = vec![0; 2];
     distribution[0] = 500_000_000u64; // 50%
 
-    let test_token_oracle = TestTokenOracle::new(general_pool.token_mint_pubkey, distribution);
+    let test_token_oracle =
+        TestTokenOracle::new(general_pool.token_mint_pubkey, distribution.clone());
 
     test_token_oracle
         .init(&mut env.context, &test_liquidity_oracle, payer_pubkey)
@@ -139,7 +148,7 @@ async fn setup(
             &mut env.context,
             &test_liquidity_oracle,
             payer_pubkey,
-            distribution,
+            distribution.clone(),
         )
         .await
         .unwrap();
@@ -204,10 +213,6 @@ async fn setup(
         .await
         .unwrap();
 
-    let ten = [1, 2, 3, 4, 5, 6, 7, 8, 9, 0];
-    let mut collateral_pool_markets = ten.map(|_| mm_pool_market.keypair.pubkey().clone());
-    collateral_pool_markets[0] = mm_pool_market.keypair.pubkey();
-
     env.registry
         .update_registry(
             &mut env.context,
@@ -222,11 +227,12 @@ async fn setup(
         .unwrap();
 
     env.registry
-        .update_registry_markets(
+        .update_registry_market(
             &mut env.context,
-            UpdateRegistryMarketsData {
-                money_markets: None,
-                collateral_pool_markets: Some(collateral_pool_markets),
+            0,
+            UpdateRegistryMarketData {
+                collateral_pool_market: Some(mm_pool_market.keypair.pubkey()),
+                status: None,
             },
         )
         .await
@@ -310,6 +316,548 @@ async fn success() {
     assert_eq!(data.amount_to_distribute, deposit_amount);
 }
 
+#[tokio::test]
+async fn success_with_rebalancing_limits() {
+    let deposit_amount = 100 * EXP;
+    let (
+        mut context,
+        _,
+        _,
+        registry,
+        general_pool_market,
+        general_pool,
+        _,
+        _,
+        _,
+        _,
+        _,
+        _,
+        test_depositor,
+        test_liquidity_oracle,
+        _,
+        _,
+    ) = setup(deposit_amount).await;
+
+    test_depositor
+        .set_rebalancing_limits(
+            &mut context,
+            &registry,
+            &general_pool.token_mint_pubkey,
+            vec![MarketLimit {
+                money_market: spl_token_lending::id(),
+                max_liquidity: u64::MAX,
+                max_share: 200_000_000, // 20%
+            }],
+            0,
+            0,
+        )
+        .await
+        .unwrap();
+
+    test_depositor
+        .start_rebalancing(
+            &mut context,
+            &registry,
+            &general_pool_market,
+            &general_pool,
+            &test_liquidity_oracle,
+            false,
+            DistributionArray::default(),
+        )
+        .await
+        .unwrap();
+
+    let data = test_depositor
+        .get_rebalancing_data(&mut context, &general_pool.token_mint_pubkey)
+        .await;
+
+    // Excess stays on liquidity transit because other markets have zero distribution
+    assert_eq!(data.distributed_liquidity[0], deposit_amount / 5);
+    assert_eq!(
+        data.total_distributed_liquidity().unwrap(),
+        deposit_amount / 5
+    );
+    assert_eq!(data.amount_to_distribute, deposit_amount);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn start_rebalancing_by_keeper(
+    context: &ProgramTestContext,
+    registry: &TestRegistry,
+    general_pool_market: &TestGeneralPoolMarket,
+    general_pool: &TestGeneralPool,
+    test_depositor: &TestDepositor,
+    test_liquidity_oracle: &TestLiquidityOracle,
+    keeper: &Keypair,
+    refresh_income: bool,
+) -> Transaction {
+    Transaction::new_signed_with_payer(
+        &[
+            // Keeper pays for the rebalancing account
+            system_instruction::transfer(&context.payer.pubkey(), &keeper.pubkey(), EXP),
+            everlend_liquidity_oracle::instruction::update_reserve_rates(
+                &everlend_liquidity_oracle::id(),
+                &test_liquidity_oracle.keypair.pubkey(),
+                &context.payer.pubkey(),
+                &general_pool.token_mint_pubkey,
+                DistributionArray::default(),
+            ),
+            everlend_depositor::instruction::start_rebalancing(
+                &everlend_depositor::id(),
+                &registry.keypair.pubkey(),
+                &test_depositor.depositor.pubkey(),
+                &general_pool.token_mint_pubkey,
+                &general_pool_market.keypair.pubkey(),
+                &general_pool.token_account.pubkey(),
+                &test_liquidity_oracle.keypair.pubkey(),
+                &keeper.pubkey(),
+                refresh_income,
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, keeper],
+        context.last_blockhash,
+    )
+}
+
+#[tokio::test]
+async fn success_by_keeper() {
+    let deposit_amount = 100 * EXP;
+    let (
+        mut context,
+        _,
+        _,
+        registry,
+        general_pool_market,
+        general_pool,
+        _,
+        _,
+        _,
+        _,
+        _,
+        _,
+        test_depositor,
+        test_liquidity_oracle,
+        _,
+        _,
+    ) = setup(deposit_amount).await;
+
+    test_depositor
+        .set_keeper_config(
+            &mut context,
+            &registry,
+            &general_pool.token_mint_pubkey,
+            true,
+            10_000_000, // 1%
+            100,
+        )
+        .await
+        .unwrap();
+
+    let keeper = Keypair::new();
+    let tx = start_rebalancing_by_keeper(
+        &context,
+        &registry,
+        &general_pool_market,
+        &general_pool,
+        &test_depositor,
+        &test_liquidity_oracle,
+        &keeper,
+        false,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let data = test_depositor
+        .get_rebalancing_data(&mut context, &general_pool.token_mint_pubkey)
+        .await;
+
+    assert_eq!(
+        data.total_distributed_liquidity().unwrap(),
+        deposit_amount / 2
+    );
+    assert!(data.permissionless_crank);
+    assert_eq!(data.keeper_reward_share, 10_000_000);
+    assert_eq!(data.keeper_step_reward, 100);
+}
+
+#[tokio::test]
+async fn fail_by_keeper_without_permissionless_crank() {
+    let (
+        context,
+        _,
+        _,
+        registry,
+        general_pool_market,
+        general_pool,
+        _,
+        _,
+        _,
+        _,
+        _,
+        _,
+        test_depositor,
+        test_liquidity_oracle,
+        _,
+        _,
+    ) = setup(100 * EXP).await;
+
+    let keeper = Keypair::new();
+    let tx = start_rebalancing_by_keeper(
+        &context,
+        &registry,
+        &general_pool_market,
+        &general_pool,
+        &test_depositor,
+        &test_liquidity_oracle,
+        &keeper,
+        false,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(2, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_by_keeper_with_refresh_income() {
+    let (
+        mut context,
+        _,
+        _,
+        registry,
+        general_pool_market,
+        general_pool,
+        _,
+        _,
+        _,
+        _,
+        _,
+        _,
+        test_depositor,
+        test_liquidity_oracle,
+        _,
+        _,
+    ) = setup(100 * EXP).await;
+
+    test_depositor
+        .set_keeper_config(
+            &mut context,
+            &registry,
+            &general_pool.token_mint_pubkey,
+            true,
+            0,
+            0,
+        )
+        .await
+        .unwrap();
+
+    let keeper = Keypair::new();
+    let tx = start_rebalancing_by_keeper(
+        &context,
+        &registry,
+        &general_pool_market,
+        &general_pool,
+        &test_depositor,
+        &test_liquidity_oracle,
+        &keeper,
+        true,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(2, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn success_with_yield_optimization() {
+    let (
+        mut context,
+        money_market,
+        pyth_oracle,
+        registry,
+        general_pool_market,
+        general_pool,
+        _,
+        _,
+        _,
+        mm_pool_market,
+        mm_pool,
+        _,
+        test_depositor,
+        test_liquidity_oracle,
+        _,
+        _,
+    ) = setup(100 * EXP).await;
+    let reserve = money_market.get_reserve_data(&mut context).await;
+    let money_market_pubkeys =
+        MoneyMarketPubkeys::SPL(integrations::spl_token_lending::AccountPubkeys {
+            reserve: money_market.reserve_pubkey,
+            reserve_liquidity_supply: reserve.liquidity.supply_pubkey,
+            reserve_liquidity_oracle: reserve.liquidity.oracle_pubkey,
+            lending_market: money_market.market_pubkey,
+        });
+
+    // Rebalancing by the oracle distribution
+    test_depositor
+        .start_rebalancing(
+            &mut context,
+            &registry,
+            &general_pool_market,
+            &general_pool,
+            &test_liquidity_oracle,
+            false,
+            DistributionArray::default(),
+        )
+        .await
+        .unwrap();
+
+    context.warp_to_slot(REFRESH_INCOME_INTERVAL).unwrap();
+    pyth_oracle
+        .update(&mut context, REFRESH_INCOME_INTERVAL)
+        .await;
+
+    test_depositor
+        .deposit(
+            &mut context,
+            &registry,
+            &mm_pool_market,
+            &mm_pool,
+            &spl_token_lending::id(),
+            &money_market_pubkeys,
+        )
+        .await
+        .unwrap();
+
+    // Borrowers make the reserve profitable
+    let mut reserve = money_market.get_reserve_data(&mut context).await;
+    reserve.liquidity.borrowed_amount_wads = spl_token_lending::math::Decimal::from(1000 * EXP);
+    money_market.update_reserve(&mut context, &reserve).await;
+
+    // Only the market of the reserve takes part in the distribution
+    registry
+        .update_registry_market(
+            &mut context,
+            1,
+            UpdateRegistryMarketData {
+                collateral_pool_market: None,
+                status: Some(MarketStatus::Paused),
+            },
+        )
+        .await
+        .unwrap();
+
+    test_depositor
+        .update_yield_distribution(
+            &mut context,
+            &registry,
+            &general_pool_market,
+            &general_pool,
+            &[(money_market.reserve_pubkey, mm_pool.pool_pubkey)],
+        )
+        .await
+        .unwrap();
+
+    let yield_distribution = test_depositor
+        .get_yield_distribution_data(&mut context, &general_pool.token_mint_pubkey)
+        .await;
+    // The only market with income gets the whole liquidity
+    assert_eq!(
+        yield_distribution.liquidity_distribution.values[0],
+        PRECISION_SCALER as u64
+    );
+    assert!(yield_distribution.supply_rates[0] > 0);
+
+    test_depositor
+        .set_yield_optimization(
+            &mut context,
+            &registry,
+            &general_pool.token_mint_pubkey,
+            true,
+        )
+        .await
+        .unwrap();
+
+    let mut start_rebalancing = everlend_depositor::instruction::start_rebalancing(
+        &everlend_depositor::id(),
+        &registry.keypair.pubkey(),
+        &test_depositor.depositor.pubkey(),
+        &general_pool.token_mint_pubkey,
+        &general_pool_market.keypair.pubkey(),
+        &general_pool.token_account.pubkey(),
+        &test_liquidity_oracle.keypair.pubkey(),
+        &context.payer.pubkey(),
+        false,
+    );
+    start_rebalancing.accounts.extend(
+        everlend_depositor::instruction::yield_distribution_accounts(
+            &everlend_depositor::id(),
+            &test_depositor.depositor.pubkey(),
+            &general_pool.token_mint_pubkey,
+        ),
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            everlend_liquidity_oracle::instruction::update_reserve_rates(
+                &everlend_liquidity_oracle::id(),
+                &test_liquidity_oracle.keypair.pubkey(),
+                &context.payer.pubkey(),
+                &general_pool.token_mint_pubkey,
+                DistributionArray::default(),
+            ),
+            start_rebalancing,
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let rebalancing = test_depositor
+        .get_rebalancing_data(&mut context, &general_pool.token_mint_pubkey)
+        .await;
+
+    assert_eq!(
+        rebalancing.liquidity_distribution,
+        yield_distribution.liquidity_distribution
+    );
+    assert_eq!(
+        rebalancing.total_distributed_liquidity().unwrap(),
+        rebalancing.amount_to_distribute
+    );
+}
+
+#[tokio::test]
+async fn fail_with_yield_optimization_without_yield_distribution() {
+    let (
+        mut context,
+        _,
+        _,
+        registry,
+        general_pool_market,
+        general_pool,
+        _,
+        _,
+        _,
+        _,
+        _,
+        _,
+        test_depositor,
+        test_liquidity_oracle,
+        _,
+        _,
+    ) = setup(100 * EXP).await;
+
+    test_depositor
+        .set_yield_optimization(
+            &mut context,
+            &registry,
+            &general_pool.token_mint_pubkey,
+            true,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_depositor
+            .start_rebalancing(
+                &mut context,
+                &registry,
+                &general_pool_market,
+                &general_pool,
+                &test_liquidity_oracle,
+                false,
+                DistributionArray::default(),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(1, InstructionError::NotEnoughAccountKeys)
+    );
+}
+
+#[tokio::test]
+async fn fail_to_update_yield_distribution_with_unbound_reserve() {
+    let (
+        mut context,
+        money_market,
+        _,
+        registry,
+        general_pool_market,
+        general_pool,
+        _,
+        _,
+        _,
+        mm_pool_market,
+        _,
+        _,
+        test_depositor,
+        test_liquidity_oracle,
+        _,
+        _,
+    ) = setup(100 * EXP).await;
+
+    test_depositor
+        .start_rebalancing(
+            &mut context,
+            &registry,
+            &general_pool_market,
+            &general_pool,
+            &test_liquidity_oracle,
+            false,
+            DistributionArray::default(),
+        )
+        .await
+        .unwrap();
+
+    // Collateral storage isn't the collateral pool of the reserve
+    assert_eq!(
+        test_depositor
+            .update_yield_distribution(
+                &mut context,
+                &registry,
+                &general_pool_market,
+                &general_pool,
+                &[(money_market.reserve_pubkey, mm_pool_market.keypair.pubkey())],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::InvalidMoneyMarketReserve as u32)
+        )
+    );
+
+    // Registry market without a reserve
+    assert_eq!(
+        test_depositor
+            .update_yield_distribution(
+                &mut context,
+                &registry,
+                &general_pool_market,
+                &general_pool,
+                &[],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+}
+
 #[tokio::test]
 async fn success_with_reserve_rates() {
     let deposit_amount = 10;
@@ -332,7 +880,7 @@ async fn success_with_reserve_rates() {
         _,
     ) = setup(deposit_amount).await;
 
-    let mut reserve_rates = DistributionArray::default();
+    let mut reserve_rates: DistributionArray = vec![0; 2];
     reserve_rates[0] = 10_000_000; // 1% ratio - really low rate just for test
 
     test_depositor
@@ -1030,11 +1578,8 @@ async fn fail_with_invalid_liquidity_oracle() {
 
 #[tokio::test]
 async fn rebalancing_math_round() {
-    let mut d: DistributionArray = DistributionArray::default();
-    let mut p = DistributionPubkeys::default();
-    p[0] = Keypair::new().pubkey();
-    p[1] = Keypair::new().pubkey();
-    p[2] = Keypair::new().pubkey();
+    let mut d: DistributionArray = vec![0; 3];
+    let p = registry_markets(3);
 
     let distr_amount: u64 = 4610400063;
     let mut oracle = TokenOracle::default();
@@ -1057,18 +1602,30 @@ async fn rebalancing_math_round() {
         let current_slot = 1;
         oracle.reserve_rates.updated_at = current_slot;
         oracle
-            .update_liquidity_distribution(i as u64 + 1, d)
-            .unwrap();
-        r.compute(&p, oracle.clone(), distr_amount, current_slot)
+            .update_liquidity_distribution(i as u64 + 1, d.clone())
             .unwrap();
+        r.compute(
+            &p,
+            &RebalancingLimits::default(),
+            oracle.clone(),
+            distr_amount,
+            current_slot,
+        )
+        .unwrap();
         println!("{}", r.total_distributed_liquidity().unwrap());
         assert_eq!(
             distr_amount >= r.total_distributed_liquidity().unwrap(),
             true
         );
 
-        r.compute_with_refresh_income(&p, 0, i as u64 + 1, distr_amount)
-            .unwrap();
+        r.compute_with_refresh_income(
+            &p,
+            &RebalancingLimits::default(),
+            0,
+            i as u64 + 1,
+            distr_amount,
+        )
+        .unwrap();
         println!("{}", r.total_distributed_liquidity().unwrap());
         println!("{:?}", r.steps);
         assert_eq!(
@@ -1080,10 +1637,8 @@ async fn rebalancing_math_round() {
 
 #[tokio::test]
 async fn rebalancing_check_steps() {
-    let mut d: DistributionArray = DistributionArray::default();
-    let mut p = DistributionPubkeys::default();
-    p[0] = Keypair::new().pubkey();
-    p[1] = Keypair::new().pubkey();
+    let mut d: DistributionArray = vec![0; 3];
+    let p = registry_markets(2);
 
     let distr_amount: u64 = 10001;
     let mut oracle = TokenOracle::default();
@@ -1134,10 +1689,16 @@ async fn rebalancing_check_steps() {
         let current_slot = 1;
         oracle.reserve_rates.updated_at = current_slot;
         oracle
-            .update_liquidity_distribution(i as u64 + 1, d)
-            .unwrap();
-        r.compute(&p, oracle.clone(), distr_amount, current_slot)
+            .update_liquidity_distribution(i as u64 + 1, d.clone())
             .unwrap();
+        r.compute(
+            &p,
+            &RebalancingLimits::default(),
+            oracle.clone(),
+            distr_amount,
+            current_slot,
+        )
+        .unwrap();
 
         println!(
             "{} {}",
@@ -1164,23 +1725,20 @@ async fn rebalancing_check_steps() {
 
 #[tokio::test]
 async fn rebalancing_check_steps_math() {
-    let mut p = DistributionPubkeys::default();
-    p[0] = Keypair::new().pubkey();
-    p[1] = Keypair::new().pubkey();
-    p[2] = Keypair::new().pubkey();
+    let p = registry_markets(3);
 
-    let mut d: DistributionArray = DistributionArray::default();
+    let mut d: DistributionArray = vec![0; 3];
     d[0] = 500_000_000;
     d[1] = 500_000_000;
 
     let mut oracle = TokenOracle::default();
-    oracle.liquidity_distribution.values = d;
+    oracle.liquidity_distribution.values = d.clone();
 
-    let mut received_collateral = [0; 10];
+    let mut received_collateral = vec![0; 3];
     received_collateral[0] = 5218140718;
     received_collateral[1] = 12821948839;
 
-    let mut distributed_liquidity = [0; 10];
+    let mut distributed_liquidity = vec![0; 3];
     distributed_liquidity[0] = 12821948839;
     distributed_liquidity[1] = 12821948839;
 
@@ -1194,13 +1752,19 @@ async fn rebalancing_check_steps_math() {
     d[1] = 333_333_333;
     d[2] = 333_333_333;
 
-    oracle.update_liquidity_distribution(10, d).unwrap();
+    oracle.update_liquidity_distribution(10, d.clone()).unwrap();
 
     let amount_to_distribute = 25365814993;
     let current_slot = 1;
     oracle.reserve_rates.updated_at = current_slot;
-    r.compute(&p, oracle.clone(), amount_to_distribute, current_slot)
-        .unwrap();
+    r.compute(
+        &p,
+        &RebalancingLimits::default(),
+        oracle.clone(),
+        amount_to_distribute,
+        current_slot,
+    )
+    .unwrap();
 
     println!("{:?}", r.steps);
 
@@ -1215,7 +1779,7 @@ async fn rebalancing_check_steps_math() {
 #[tokio::test]
 async fn rebalancing_check_distribution_overflow() {
     let mut oracle = TokenOracle::default();
-    let mut d: DistributionArray = DistributionArray::default();
+    let mut d: DistributionArray = vec![0; 3];
 
     d[0] = 1;
     d[1] = u64::MAX;
@@ -1241,17 +1805,14 @@ async fn rebalancing_percent_ratio() {
 
 #[tokio::test]
 async fn collateral_leak_test() {
-    let mut p = DistributionPubkeys::default();
-    p[0] = Keypair::new().pubkey();
-    p[1] = Keypair::new().pubkey();
-    p[2] = Keypair::new().pubkey();
+    let p = registry_markets(3);
 
-    let mut d: DistributionArray = DistributionArray::default();
+    let mut d: DistributionArray = vec![0; 3];
     d[0] = 500_000_000;
     d[1] = 500_000_000;
 
     let mut oracle = TokenOracle::default();
-    oracle.liquidity_distribution.values = d;
+    oracle.liquidity_distribution.values = d.clone();
 
     let mut r = Rebalancing::default();
 
@@ -1260,12 +1821,18 @@ async fn collateral_leak_test() {
 
     oracle.liquidity_distribution.updated_at = current_slot;
     oracle.reserve_rates.updated_at = current_slot;
-    r.compute(&p, oracle.clone(), amount_to_distribute, current_slot)
-        .unwrap();
+    r.compute(
+        &p,
+        &RebalancingLimits::default(),
+        oracle.clone(),
+        amount_to_distribute,
+        current_slot,
+    )
+    .unwrap();
 
     assert_eq!(r.total_distributed_liquidity().unwrap(), 100_000_000);
 
-    let mut reserve_rates: DistributionArray = DistributionArray::default();
+    let mut reserve_rates: DistributionArray = vec![0; 3];
     reserve_rates[0] = 1_000_000;
 
     d[0] = 500_000_100;
@@ -1273,27 +1840,31 @@ async fn collateral_leak_test() {
 
     let current_slot = 2;
     oracle
-        .update_liquidity_distribution(current_slot, d)
+        .update_liquidity_distribution(current_slot, d.clone())
         .unwrap();
     oracle
         .update_reserve_rates(current_slot, reserve_rates)
         .unwrap();
 
-    r.compute(&p, oracle.clone(), amount_to_distribute, current_slot)
-        .unwrap();
+    r.compute(
+        &p,
+        &RebalancingLimits::default(),
+        oracle.clone(),
+        amount_to_distribute,
+        current_slot,
+    )
+    .unwrap();
 
     assert_eq!(r.total_distributed_liquidity().unwrap(), 999_999_90);
 }
 
 #[tokio::test]
 async fn collateral_leak_test2() {
-    let mut d: DistributionArray = DistributionArray::default();
-    let mut p = DistributionPubkeys::default();
-    p[0] = Keypair::new().pubkey();
-    p[1] = Keypair::new().pubkey();
+    let mut d: DistributionArray = vec![0; 3];
+    let p = registry_markets(2);
 
     let mut oracle = TokenOracle::default();
-    let mut rates = DistributionArray::default();
+    let mut rates: DistributionArray = vec![0; 3];
     let mut r = Rebalancing::default();
 
     struct TestCase {
@@ -1342,9 +1913,11 @@ async fn collateral_leak_test2() {
         rates[1] = elem.rates.1;
 
         let current_slot = 1;
-        oracle.update_reserve_rates(current_slot, rates).unwrap();
         oracle
-            .update_liquidity_distribution(i as u64 + 1, d)
+            .update_reserve_rates(current_slot, rates.clone())
+            .unwrap();
+        oracle
+            .update_liquidity_distribution(i as u64 + 1, d.clone())
             .unwrap();
 
         let (_, amount_to_distribute) = calculate_amount_to_distribute(
@@ -1355,8 +1928,14 @@ async fn collateral_leak_test2() {
         )
         .unwrap();
 
-        r.compute(&p, oracle.clone(), amount_to_distribute, current_slot)
-            .unwrap();
+        r.compute(
+            &p,
+            &RebalancingLimits::default(),
+            oracle.clone(),
+            amount_to_distribute,
+            current_slot,
+        )
+        .unwrap();
 
         println!(
             "amount_to_distribute: {} distributed_liquidity:{} \n\n",
@@ -1,4 +1,4 @@
-use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketsData};
+use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketData};
 use solana_program::{
     instruction::{AccountMeta, Instruction, InstructionError},
     program_pack::Pack,
@@ -33,6 +33,8 @@ async fn setup() -> (
     TestPool,
     LiquidityProvider,
     TestDepositor,
+    TestLiquidityOracle,
+    TestTokenOracle,
 ) {
     let mut env = presetup().await;
 
@@ -118,7 +120,10 @@ async fn setup() -> (
     // 3. Prepare money market pool
 
     let mm_pool_market = TestPoolMarket::new();
-    mm_pool_market.init(&mut env.context).await.unwrap();
+    mm_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let mm_pool = TestPool::new(&mm_pool_market, Some(reserve.collateral.mint_pubkey));
     mm_pool
@@ -131,12 +136,16 @@ async fn setup() -> (
     // 4.1. Prepare liquidity oracle
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut env.context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
 
-    let mut distribution = DistributionArray::default();
+    let mut distribution: DistributionArray = vec![0; 2];
     distribution[0] = 500_000_000u64; // 50%
 
-    let test_token_oracle = TestTokenOracle::new(general_pool.token_mint_pubkey, distribution);
+    let test_token_oracle =
+        TestTokenOracle::new(general_pool.token_mint_pubkey, distribution.clone());
 
     test_token_oracle
         .init(&mut env.context, &test_liquidity_oracle, payer_pubkey)
@@ -148,7 +157,7 @@ async fn setup() -> (
             &mut env.context,
             &test_liquidity_oracle,
             payer_pubkey,
-            distribution,
+            distribution.clone(),
         )
         .await
         .unwrap();
@@ -213,10 +222,6 @@ async fn setup() -> (
         .await
         .unwrap();
 
-    let ten = [1, 2, 3, 4, 5, 6, 7, 8, 9, 0];
-    let mut collateral_pool_markets = ten.map(|_| mm_pool_market.keypair.pubkey().clone());
-    collateral_pool_markets[0] = mm_pool_market.keypair.pubkey();
-
     env.registry
         .update_registry(
             &mut env.context,
@@ -231,11 +236,12 @@ async fn setup() -> (
         .unwrap();
 
     env.registry
-        .update_registry_markets(
+        .update_registry_market(
             &mut env.context,
-            UpdateRegistryMarketsData {
-                money_markets: None,
-                collateral_pool_markets: Some(collateral_pool_markets),
+            0,
+            UpdateRegistryMarketData {
+                collateral_pool_market: Some(mm_pool_market.keypair.pubkey()),
+                status: None,
             },
         )
         .await
@@ -334,6 +340,8 @@ async fn setup() -> (
         mm_pool,
         liquidity_provider,
         test_depositor,
+        test_liquidity_oracle,
+        test_token_oracle,
     )
 }
 
@@ -353,6 +361,8 @@ async fn success2() {
         mm_pool,
         _,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let reserve = money_market.get_reserve_data(&mut context).await;
@@ -416,6 +426,8 @@ async fn success_with_incomes() {
         mm_pool,
         liquidity_provider,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let mut reserve = money_market.get_reserve_data(&mut context).await;
@@ -479,6 +491,378 @@ async fn success_with_incomes() {
     );
 }
 
+#[tokio::test]
+async fn success_with_loss() {
+    let (
+        mut context,
+        money_market,
+        pyth_oracle,
+        registry,
+        general_pool_market,
+        general_pool,
+        _general_pool_borrow_authority,
+        income_pool_market,
+        income_pool,
+        mm_pool_market,
+        mm_pool,
+        _,
+        test_depositor,
+        test_liquidity_oracle,
+        test_token_oracle,
+    ) = setup().await;
+
+    let mut reserve = money_market.get_reserve_data(&mut context).await;
+    let money_market_pubkeys =
+        MoneyMarketPubkeys::SPL(integrations::spl_token_lending::AccountPubkeys {
+            reserve: money_market.reserve_pubkey,
+            reserve_liquidity_supply: reserve.liquidity.supply_pubkey,
+            reserve_liquidity_oracle: reserve.liquidity.oracle_pubkey,
+            lending_market: money_market.market_pubkey,
+        });
+
+    // Money market takes a haircut, collateral is redeemed at the lower rate
+    reserve.collateral.mint_total_supply += reserve.collateral.mint_total_supply / 10;
+    money_market.update_reserve(&mut context, &reserve).await;
+
+    let general_pool_borrowed = general_pool
+        .get_data(&mut context)
+        .await
+        .total_amount_borrowed;
+
+    context.warp_to_slot(5).unwrap();
+    pyth_oracle.update(&mut context, 5).await;
+
+    test_depositor
+        .withdraw(
+            &mut context,
+            &registry,
+            &income_pool_market,
+            &income_pool,
+            &mm_pool_market,
+            &mm_pool,
+            &spl_token_lending::id(),
+            &money_market_pubkeys,
+        )
+        .await
+        .unwrap();
+
+    let rebalancing = test_depositor
+        .get_rebalancing_data(&mut context, &general_pool.token_mint_pubkey)
+        .await;
+    assert!(rebalancing.is_completed());
+    assert!(rebalancing.unsettled_loss > 0);
+    let loss = rebalancing.unsettled_loss;
+
+    // Next rebalancing settles the loss and deposits the rest
+    let mut distribution: DistributionArray = vec![0; 2];
+    distribution[0] = 500_000_000u64; // 50%
+    test_token_oracle
+        .update(
+            &mut context,
+            &test_liquidity_oracle,
+            context.payer.pubkey(),
+            distribution,
+        )
+        .await
+        .unwrap();
+
+    test_depositor
+        .start_rebalancing(
+            &mut context,
+            &registry,
+            &general_pool_market,
+            &general_pool,
+            &test_liquidity_oracle,
+            false,
+            DistributionArray::default(),
+        )
+        .await
+        .unwrap();
+
+    let rebalancing = test_depositor
+        .get_rebalancing_data(&mut context, &general_pool.token_mint_pubkey)
+        .await;
+    assert_eq!(rebalancing.unsettled_loss, 0);
+    assert_eq!(rebalancing.total_loss, loss);
+    assert_eq!(
+        general_pool
+            .get_data(&mut context)
+            .await
+            .total_amount_borrowed,
+        general_pool_borrowed - loss
+    );
+
+    context.warp_to_slot(7).unwrap();
+    pyth_oracle.update(&mut context, 7).await;
+
+    test_depositor
+        .deposit(
+            &mut context,
+            &registry,
+            &mm_pool_market,
+            &mm_pool,
+            &spl_token_lending::id(),
+            &money_market_pubkeys,
+        )
+        .await
+        .unwrap();
+
+    let rebalancing = test_depositor
+        .get_rebalancing_data(&mut context, &general_pool.token_mint_pubkey)
+        .await;
+    assert!(rebalancing.is_completed());
+}
+
+#[tokio::test]
+async fn success_with_loss_covered_by_safety_fund() {
+    let (
+        mut context,
+        money_market,
+        pyth_oracle,
+        registry,
+        general_pool_market,
+        general_pool,
+        _general_pool_borrow_authority,
+        income_pool_market,
+        income_pool,
+        mm_pool_market,
+        mm_pool,
+        _,
+        test_depositor,
+        test_liquidity_oracle,
+        test_token_oracle,
+    ) = setup().await;
+
+    // Safety fund covers the whole loss
+    let safety_fund_amount = 10 * EXP;
+    income_pool
+        .create_safety_fund_token_account(&mut context, &income_pool_market, &general_pool)
+        .await
+        .unwrap();
+    mint_tokens(
+        &mut context,
+        &income_pool.token_mint_pubkey,
+        &income_pool.get_safety_fund_token_account(&income_pool_market),
+        safety_fund_amount,
+    )
+    .await
+    .unwrap();
+    income_pool
+        .set_safety_fund_limit(&mut context, &income_pool_market, 0, 0, 1)
+        .await
+        .unwrap();
+
+    let mut reserve = money_market.get_reserve_data(&mut context).await;
+    let money_market_pubkeys =
+        MoneyMarketPubkeys::SPL(integrations::spl_token_lending::AccountPubkeys {
+            reserve: money_market.reserve_pubkey,
+            reserve_liquidity_supply: reserve.liquidity.supply_pubkey,
+            reserve_liquidity_oracle: reserve.liquidity.oracle_pubkey,
+            lending_market: money_market.market_pubkey,
+        });
+
+    reserve.collateral.mint_total_supply += reserve.collateral.mint_total_supply / 10;
+    money_market.update_reserve(&mut context, &reserve).await;
+
+    let general_pool_borrowed = general_pool
+        .get_data(&mut context)
+        .await
+        .total_amount_borrowed;
+
+    context.warp_to_slot(5).unwrap();
+    pyth_oracle.update(&mut context, 5).await;
+
+    test_depositor
+        .withdraw(
+            &mut context,
+            &registry,
+            &income_pool_market,
+            &income_pool,
+            &mm_pool_market,
+            &mm_pool,
+            &spl_token_lending::id(),
+            &money_market_pubkeys,
+        )
+        .await
+        .unwrap();
+
+    let loss = test_depositor
+        .get_rebalancing_data(&mut context, &general_pool.token_mint_pubkey)
+        .await
+        .unsettled_loss;
+    assert!(loss > 0 && loss <= safety_fund_amount);
+
+    let mut distribution: DistributionArray = vec![0; 2];
+    distribution[0] = 500_000_000u64; // 50%
+    test_token_oracle
+        .update(
+            &mut context,
+            &test_liquidity_oracle,
+            context.payer.pubkey(),
+            distribution,
+        )
+        .await
+        .unwrap();
+
+    test_depositor
+        .start_rebalancing(
+            &mut context,
+            &registry,
+            &general_pool_market,
+            &general_pool,
+            &test_liquidity_oracle,
+            false,
+            DistributionArray::default(),
+        )
+        .await
+        .unwrap();
+
+    let rebalancing = test_depositor
+        .get_rebalancing_data(&mut context, &general_pool.token_mint_pubkey)
+        .await;
+    assert_eq!(rebalancing.unsettled_loss, 0);
+    assert_eq!(rebalancing.total_covered_loss, loss);
+    // Nothing is written off in the general pool
+    assert_eq!(
+        general_pool
+            .get_data(&mut context)
+            .await
+            .total_amount_borrowed,
+        general_pool_borrowed
+    );
+    assert_eq!(
+        get_token_balance(
+            &mut context,
+            &income_pool.get_safety_fund_token_account(&income_pool_market)
+        )
+        .await,
+        safety_fund_amount - loss
+    );
+}
+
+#[tokio::test]
+async fn success_with_loss_and_safety_fund_without_ledger() {
+    let (
+        mut context,
+        money_market,
+        pyth_oracle,
+        registry,
+        general_pool_market,
+        general_pool,
+        _general_pool_borrow_authority,
+        income_pool_market,
+        income_pool,
+        mm_pool_market,
+        mm_pool,
+        _,
+        test_depositor,
+        test_liquidity_oracle,
+        test_token_oracle,
+    ) = setup().await;
+
+    // Safety fund is funded, but its ledger isn't created yet
+    let safety_fund_amount = 10 * EXP;
+    income_pool
+        .create_safety_fund_token_account(&mut context, &income_pool_market, &general_pool)
+        .await
+        .unwrap();
+    mint_tokens(
+        &mut context,
+        &income_pool.token_mint_pubkey,
+        &income_pool.get_safety_fund_token_account(&income_pool_market),
+        safety_fund_amount,
+    )
+    .await
+    .unwrap();
+
+    let mut reserve = money_market.get_reserve_data(&mut context).await;
+    let money_market_pubkeys =
+        MoneyMarketPubkeys::SPL(integrations::spl_token_lending::AccountPubkeys {
+            reserve: money_market.reserve_pubkey,
+            reserve_liquidity_supply: reserve.liquidity.supply_pubkey,
+            reserve_liquidity_oracle: reserve.liquidity.oracle_pubkey,
+            lending_market: money_market.market_pubkey,
+        });
+
+    reserve.collateral.mint_total_supply += reserve.collateral.mint_total_supply / 10;
+    money_market.update_reserve(&mut context, &reserve).await;
+
+    let general_pool_borrowed = general_pool
+        .get_data(&mut context)
+        .await
+        .total_amount_borrowed;
+
+    context.warp_to_slot(5).unwrap();
+    pyth_oracle.update(&mut context, 5).await;
+
+    test_depositor
+        .withdraw(
+            &mut context,
+            &registry,
+            &income_pool_market,
+            &income_pool,
+            &mm_pool_market,
+            &mm_pool,
+            &spl_token_lending::id(),
+            &money_market_pubkeys,
+        )
+        .await
+        .unwrap();
+
+    let loss = test_depositor
+        .get_rebalancing_data(&mut context, &general_pool.token_mint_pubkey)
+        .await
+        .unsettled_loss;
+    assert!(loss > 0);
+
+    let mut distribution: DistributionArray = vec![0; 2];
+    distribution[0] = 500_000_000u64; // 50%
+    test_token_oracle
+        .update(
+            &mut context,
+            &test_liquidity_oracle,
+            context.payer.pubkey(),
+            distribution,
+        )
+        .await
+        .unwrap();
+
+    // Loss is written off without the ledger
+    test_depositor
+        .start_rebalancing(
+            &mut context,
+            &registry,
+            &general_pool_market,
+            &general_pool,
+            &test_liquidity_oracle,
+            false,
+            DistributionArray::default(),
+        )
+        .await
+        .unwrap();
+
+    let rebalancing = test_depositor
+        .get_rebalancing_data(&mut context, &general_pool.token_mint_pubkey)
+        .await;
+    assert_eq!(rebalancing.unsettled_loss, 0);
+    assert_eq!(rebalancing.total_covered_loss, 0);
+    assert_eq!(
+        general_pool
+            .get_data(&mut context)
+            .await
+            .total_amount_borrowed,
+        general_pool_borrowed - loss
+    );
+    assert_eq!(
+        get_token_balance(
+            &mut context,
+            &income_pool.get_safety_fund_token_account(&income_pool_market)
+        )
+        .await,
+        safety_fund_amount
+    );
+}
+
 #[tokio::test]
 async fn fail_with_invalid_registry() {
     let (
@@ -495,6 +879,8 @@ async fn fail_with_invalid_registry() {
         mm_pool,
         _,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let reserve = money_market.get_reserve_data(&mut context).await;
@@ -519,12 +905,13 @@ async fn fail_with_invalid_registry() {
         integrations::withdraw_accounts(money_market_program_id, &money_market_pubkeys);
 
     let collateral_pool_withdraw_accounts =
-        mm_pool.withdraw_accounts(&mm_pool_market, &test_depositor);
+        mm_pool.withdraw_accounts(&Pubkey::new_unique(), &mm_pool_market, &test_depositor);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::withdraw(
             &everlend_depositor::id(),
             &Pubkey::new_unique(),
+            &Pubkey::new_unique(),
             &test_depositor.depositor.pubkey(),
             &income_pool_market.keypair.pubkey(),
             &income_pool.token_account.pubkey(),
@@ -561,7 +948,7 @@ async fn fail_with_invalid_depositor() {
         money_market,
         pyth_oracle,
         registry,
-        _general_pool_market,
+        general_pool_market,
         _,
         _general_pool_borrow_authority,
         income_pool_market,
@@ -570,6 +957,8 @@ async fn fail_with_invalid_depositor() {
         mm_pool,
         _,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let reserve = money_market.get_reserve_data(&mut context).await;
@@ -594,12 +983,13 @@ async fn fail_with_invalid_depositor() {
         integrations::withdraw_accounts(money_market_program_id, &money_market_pubkeys);
 
     let collateral_pool_withdraw_accounts =
-        mm_pool.withdraw_accounts(&mm_pool_market, &test_depositor);
+        mm_pool.withdraw_accounts(&registry.keypair.pubkey(), &mm_pool_market, &test_depositor);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::withdraw(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &Pubkey::new_unique(),
             &income_pool_market.keypair.pubkey(),
             &income_pool.token_account.pubkey(),
@@ -636,7 +1026,7 @@ async fn fail_with_invalid_income_pool_market() {
         money_market,
         pyth_oracle,
         registry,
-        _general_pool_market,
+        general_pool_market,
         _,
         _general_pool_borrow_authority,
         _,
@@ -645,6 +1035,8 @@ async fn fail_with_invalid_income_pool_market() {
         mm_pool,
         liquidity_provider,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let mut reserve = money_market.get_reserve_data(&mut context).await;
@@ -683,12 +1075,13 @@ async fn fail_with_invalid_income_pool_market() {
         integrations::withdraw_accounts(money_market_program_id, &money_market_pubkeys);
 
     let collateral_pool_withdraw_accounts =
-        mm_pool.withdraw_accounts(&mm_pool_market, &test_depositor);
+        mm_pool.withdraw_accounts(&registry.keypair.pubkey(), &mm_pool_market, &test_depositor);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::withdraw(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &Pubkey::new_unique(),
             &income_pool.token_account.pubkey(),
@@ -725,7 +1118,7 @@ async fn fail_with_invalid_income_pool_token_account() {
         money_market,
         pyth_oracle,
         registry,
-        _general_pool_market,
+        general_pool_market,
         _,
         _general_pool_borrow_authority,
         income_pool_market,
@@ -734,6 +1127,8 @@ async fn fail_with_invalid_income_pool_token_account() {
         mm_pool,
         liquidity_provider,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let mut reserve = money_market.get_reserve_data(&mut context).await;
@@ -772,12 +1167,13 @@ async fn fail_with_invalid_income_pool_token_account() {
         integrations::withdraw_accounts(money_market_program_id, &money_market_pubkeys);
 
     let collateral_pool_withdraw_accounts =
-        mm_pool.withdraw_accounts(&mm_pool_market, &test_depositor);
+        mm_pool.withdraw_accounts(&registry.keypair.pubkey(), &mm_pool_market, &test_depositor);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::withdraw(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &income_pool_market.keypair.pubkey(),
             &Pubkey::new_unique(),
@@ -814,7 +1210,7 @@ async fn fail_with_invalid_mm_pool_market() {
         money_market,
         pyth_oracle,
         registry,
-        _general_pool_market,
+        general_pool_market,
         _,
         _general_pool_borrow_authority,
         income_pool_market,
@@ -823,6 +1219,8 @@ async fn fail_with_invalid_mm_pool_market() {
         mm_pool,
         _,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let reserve = money_market.get_reserve_data(&mut context).await;
@@ -847,6 +1245,7 @@ async fn fail_with_invalid_mm_pool_market() {
         integrations::withdraw_accounts(money_market_program_id, &money_market_pubkeys);
 
     let collateral_pool_withdraw_accounts = mm_pool.withdraw_accounts(
+        &registry.keypair.pubkey(),
         &TestPoolMarket {
             keypair: Keypair::new(),
             manager: Keypair::new(),
@@ -858,6 +1257,7 @@ async fn fail_with_invalid_mm_pool_market() {
         &[everlend_depositor::instruction::withdraw(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &income_pool_market.keypair.pubkey(),
             &income_pool.token_account.pubkey(),
@@ -894,7 +1294,7 @@ async fn fail_with_invalid_mm_pool_token_account() {
         money_market,
         pyth_oracle,
         registry,
-        _general_pool_market,
+        general_pool_market,
         _,
         _general_pool_borrow_authority,
         income_pool_market,
@@ -903,6 +1303,8 @@ async fn fail_with_invalid_mm_pool_token_account() {
         mm_pool,
         _,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let reserve = money_market.get_reserve_data(&mut context).await;
@@ -932,13 +1334,17 @@ async fn fail_with_invalid_mm_pool_token_account() {
         token_account: Keypair::new(),
     };
 
-    let collateral_pool_withdraw_accounts =
-        mock_mm_pool.withdraw_accounts(&mm_pool_market, &test_depositor);
+    let collateral_pool_withdraw_accounts = mock_mm_pool.withdraw_accounts(
+        &registry.keypair.pubkey(),
+        &mm_pool_market,
+        &test_depositor,
+    );
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::withdraw(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &income_pool_market.keypair.pubkey(),
             &income_pool.token_account.pubkey(),
@@ -975,7 +1381,7 @@ async fn fail_with_invalid_collateral_mint() {
         money_market,
         pyth_oracle,
         registry,
-        _general_pool_market,
+        general_pool_market,
         _,
         _general_pool_borrow_authority,
         income_pool_market,
@@ -984,6 +1390,8 @@ async fn fail_with_invalid_collateral_mint() {
         mm_pool,
         _,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let reserve = money_market.get_reserve_data(&mut context).await;
@@ -1013,13 +1421,17 @@ async fn fail_with_invalid_collateral_mint() {
         token_account: mm_pool.token_account,
     };
 
-    let collateral_pool_withdraw_accounts =
-        mock_mm_pool.withdraw_accounts(&mm_pool_market, &test_depositor);
+    let collateral_pool_withdraw_accounts = mock_mm_pool.withdraw_accounts(
+        &registry.keypair.pubkey(),
+        &mm_pool_market,
+        &test_depositor,
+    );
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::withdraw(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &income_pool_market.keypair.pubkey(),
             &income_pool.token_account.pubkey(),
@@ -1056,7 +1468,7 @@ async fn fail_with_invalid_liquidity_mint() {
         money_market,
         pyth_oracle,
         registry,
-        _general_pool_market,
+        general_pool_market,
         _,
         _general_pool_borrow_authority,
         income_pool_market,
@@ -1065,6 +1477,8 @@ async fn fail_with_invalid_liquidity_mint() {
         mm_pool,
         _,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let reserve = money_market.get_reserve_data(&mut context).await;
@@ -1089,12 +1503,13 @@ async fn fail_with_invalid_liquidity_mint() {
         integrations::withdraw_accounts(money_market_program_id, &money_market_pubkeys);
 
     let collateral_pool_withdraw_accounts =
-        mm_pool.withdraw_accounts(&mm_pool_market, &test_depositor);
+        mm_pool.withdraw_accounts(&registry.keypair.pubkey(), &mm_pool_market, &test_depositor);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::withdraw(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &income_pool_market.keypair.pubkey(),
             &income_pool.token_account.pubkey(),
@@ -1131,7 +1546,7 @@ async fn fail_with_invalid_mm_program_id() {
         money_market,
         pyth_oracle,
         registry,
-        _general_pool_market,
+        general_pool_market,
         _,
         _general_pool_borrow_authority,
         income_pool_market,
@@ -1140,6 +1555,8 @@ async fn fail_with_invalid_mm_program_id() {
         mm_pool,
         _,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let reserve = money_market.get_reserve_data(&mut context).await;
@@ -1164,12 +1581,13 @@ async fn fail_with_invalid_mm_program_id() {
         integrations::withdraw_accounts(money_market_program_id, &money_market_pubkeys);
 
     let collateral_pool_withdraw_accounts =
-        mm_pool.withdraw_accounts(&mm_pool_market, &test_depositor);
+        mm_pool.withdraw_accounts(&registry.keypair.pubkey(), &mm_pool_market, &test_depositor);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::withdraw(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &income_pool_market.keypair.pubkey(),
             &income_pool.token_account.pubkey(),
@@ -1206,7 +1624,7 @@ async fn fail_with_invalid_withdraw_accounts() {
         money_market,
         pyth_oracle,
         registry,
-        _general_pool_market,
+        general_pool_market,
         _,
         _general_pool_borrow_authority,
         income_pool_market,
@@ -1215,6 +1633,8 @@ async fn fail_with_invalid_withdraw_accounts() {
         mm_pool,
         _,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let reserve = money_market.get_reserve_data(&mut context).await;
@@ -1239,12 +1659,13 @@ async fn fail_with_invalid_withdraw_accounts() {
         integrations::withdraw_accounts(&Pubkey::new_unique(), &money_market_pubkeys);
 
     let collateral_pool_withdraw_accounts =
-        mm_pool.withdraw_accounts(&mm_pool_market, &test_depositor);
+        mm_pool.withdraw_accounts(&registry.keypair.pubkey(), &mm_pool_market, &test_depositor);
 
     let tx = Transaction::new_signed_with_payer(
         &[everlend_depositor::instruction::withdraw(
             &everlend_depositor::id(),
             &registry.keypair.pubkey(),
+            &general_pool_market.keypair.pubkey(),
             &test_depositor.depositor.pubkey(),
             &income_pool_market.keypair.pubkey(),
             &income_pool.token_account.pubkey(),
@@ -1278,7 +1699,7 @@ async fn fail_with_invalid_withdraw_authority() {
         money_market,
         pyth_oracle,
         registry,
-        _general_pool_market,
+        general_pool_market,
         _,
         _general_pool_borrow_authority,
         income_pool_market,
@@ -1287,6 +1708,8 @@ async fn fail_with_invalid_withdraw_authority() {
         mm_pool,
         _,
         test_depositor,
+        _,
+        _,
     ) = setup().await;
 
     let reserve = money_market.get_reserve_data(&mut context).await;
@@ -1346,7 +1769,16 @@ async fn fail_with_invalid_withdraw_authority() {
     }
     .find_address(&everlend_depositor::id());
 
+    let (general_pool, _) = everlend_general_pool::find_pool_program_address(
+        &everlend_general_pool::id(),
+        &general_pool_market.keypair.pubkey(),
+        &liquidity_mint,
+    );
+    let (global_pause, pool_pause) =
+        everlend_registry::find_pause_addresses(&registry.keypair.pubkey(), &general_pool);
+
     let collateral_pool_withdraw_accounts = mm_pool.withdraw_accounts(
+        &registry.keypair.pubkey(),
         &mm_pool_market,
         &TestDepositor {
             depositor: Keypair::new(),
@@ -1358,6 +1790,8 @@ async fn fail_with_invalid_withdraw_authority() {
         AccountMeta::new_readonly(test_depositor.depositor.pubkey(), false),
         AccountMeta::new_readonly(depositor_authority, false),
         AccountMeta::new(rebalancing, false),
+        AccountMeta::new_readonly(global_pause, false),
+        AccountMeta::new_readonly(pool_pause, false),
         AccountMeta::new_readonly(income_pool_market.keypair.pubkey(), false),
         AccountMeta::new_readonly(income_pool_address, false),
         AccountMeta::new(income_pool.token_account.pubkey(), false),
@@ -0,0 +1,278 @@
+use crate::utils::*;
+use everlend_general_pool::state::SetPoolConfigParams;
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+
+const PERFORMANCE_FEE: u16 = 2_000;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+    Keypair,
+    Pubkey,
+) {
+    let mut env = presetup().await;
+
+    let test_pool_market = TestGeneralPoolMarket::new();
+    test_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let test_pool = TestGeneralPool::new(&test_pool_market, None);
+    test_pool
+        .create(&mut env.context, &test_pool_market)
+        .await
+        .unwrap();
+
+    let user = add_liquidity_provider(
+        &mut env.context,
+        &test_pool.token_mint_pubkey,
+        &test_pool.pool_mint.pubkey(),
+        9999 * EXP,
+    )
+    .await
+    .unwrap();
+
+    let mining_acc = test_pool
+        .init_user_mining(&mut env.context, &test_pool_market, &user)
+        .await;
+
+    test_pool
+        .deposit(
+            &mut env.context,
+            &test_pool_market,
+            &user,
+            mining_acc,
+            100 * EXP,
+        )
+        .await
+        .unwrap();
+
+    let treasury = Keypair::new();
+    let treasury_account = Keypair::new();
+    create_token_account(
+        &mut env.context,
+        &treasury_account,
+        &test_pool.pool_mint.pubkey(),
+        &treasury.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    test_pool_market
+        .set_fee_schedule(&mut env.context, &treasury.pubkey(), PERFORMANCE_FEE, 0)
+        .await
+        .unwrap();
+
+    (
+        env.context,
+        test_pool_market,
+        test_pool,
+        treasury,
+        treasury_account.pubkey(),
+    )
+}
+
+async fn raise_income(context: &mut ProgramTestContext, test_pool: &TestGeneralPool, amount: u64) {
+    mint_tokens(
+        context,
+        &test_pool.token_mint_pubkey,
+        &test_pool.token_account.pubkey(),
+        amount,
+    )
+    .await
+    .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pool_market, test_pool, treasury, treasury_account) = setup().await;
+
+    let fee_schedule = test_pool_market.get_fee_schedule(&mut context).await;
+    assert_eq!(fee_schedule.treasury, treasury.pubkey());
+    assert_eq!(fee_schedule.performance_fee, PERFORMANCE_FEE);
+
+    // First accrual marks the pool token price
+    test_pool
+        .accrue_fees(&mut context, &test_pool_market, &treasury_account)
+        .await
+        .unwrap();
+
+    let pool_fees = test_pool.get_pool_fees(&mut context).await;
+    assert_eq!(pool_fees.share_price_hwm, EXP);
+    assert_eq!(get_token_balance(&mut context, &treasury_account).await, 0);
+
+    raise_income(&mut context, &test_pool, 50 * EXP).await;
+
+    test_pool
+        .accrue_fees(&mut context, &test_pool_market, &treasury_account)
+        .await
+        .unwrap();
+
+    // 20% of 50 income minted as pool tokens at the diluted price
+    let pool_fees = test_pool.get_pool_fees(&mut context).await;
+    assert_eq!(pool_fees.accrued_performance_fee, 10 * EXP);
+    assert_eq!(pool_fees.minted_fee_tokens, 7_142_857_142);
+    assert_eq!(pool_fees.share_price_hwm, 1_400_000_000);
+    assert_eq!(
+        get_token_balance(&mut context, &treasury_account).await,
+        7_142_857_142
+    );
+}
+
+#[tokio::test]
+async fn success_accrue_on_deposit() {
+    let (mut context, test_pool_market, test_pool, _, treasury_account) = setup().await;
+
+    test_pool
+        .accrue_fees(&mut context, &test_pool_market, &treasury_account)
+        .await
+        .unwrap();
+
+    raise_income(&mut context, &test_pool, 50 * EXP).await;
+
+    let user = add_liquidity_provider(
+        &mut context,
+        &test_pool.token_mint_pubkey,
+        &test_pool.pool_mint.pubkey(),
+        100 * EXP,
+    )
+    .await
+    .unwrap();
+    let mining_acc = test_pool
+        .init_user_mining(&mut context, &test_pool_market, &user)
+        .await;
+
+    test_pool
+        .deposit(
+            &mut context,
+            &test_pool_market,
+            &user,
+            mining_acc,
+            100 * EXP,
+        )
+        .await
+        .unwrap();
+
+    // Fee of the income before the deposit is accrued, the depositor doesn't pay it
+    let pool_fees = test_pool.get_pool_fees(&mut context).await;
+    assert_eq!(pool_fees.accrued_performance_fee, 10 * EXP);
+    assert_eq!(pool_fees.pending_fee_tokens, 7_142_857_142);
+    assert_eq!(
+        get_token_balance(&mut context, &user.pool_account).await,
+        71_428_571_428
+    );
+
+    test_pool
+        .accrue_fees(&mut context, &test_pool_market, &treasury_account)
+        .await
+        .unwrap();
+
+    let pool_fees = test_pool.get_pool_fees(&mut context).await;
+    assert_eq!(pool_fees.accrued_performance_fee, 10 * EXP);
+    assert_eq!(pool_fees.pending_fee_tokens, 0);
+    assert_eq!(pool_fees.minted_fee_tokens, 7_142_857_142);
+    assert_eq!(
+        get_token_balance(&mut context, &treasury_account).await,
+        7_142_857_142
+    );
+}
+
+#[tokio::test]
+async fn success_with_pool_config_override() {
+    let (mut context, test_pool_market, test_pool, _, treasury_account) = setup().await;
+
+    test_pool
+        .set_pool_config(
+            &mut context,
+            &test_pool_market,
+            SetPoolConfigParams {
+                deposit_minimum: None,
+                withdraw_minimum: None,
+                performance_fee: Some(Some(0)),
+                management_fee: None,
+                withdraw_delay: None,
+                exit_fee: None,
+                pool_deposit_cap: None,
+                user_deposit_cap: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let pool_config = test_pool.get_pool_config(&mut context).await;
+    assert_eq!(pool_config.performance_fee, Some(0));
+    assert_eq!(pool_config.management_fee, None);
+
+    test_pool
+        .accrue_fees(&mut context, &test_pool_market, &treasury_account)
+        .await
+        .unwrap();
+
+    raise_income(&mut context, &test_pool, 50 * EXP).await;
+
+    test_pool
+        .accrue_fees(&mut context, &test_pool_market, &treasury_account)
+        .await
+        .unwrap();
+
+    let pool_fees = test_pool.get_pool_fees(&mut context).await;
+    assert_eq!(pool_fees.accrued_performance_fee, 0);
+    assert_eq!(pool_fees.share_price_hwm, 1_500_000_000);
+    assert_eq!(get_token_balance(&mut context, &treasury_account).await, 0);
+}
+
+#[tokio::test]
+async fn fail_with_fee_too_high() {
+    let (mut context, test_pool_market, _, treasury, _) = setup().await;
+
+    assert_eq!(
+        test_pool_market
+            .set_fee_schedule(&mut context, &treasury.pubkey(), 2_001, 0)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_with_wrong_treasury() {
+    let (mut context, test_pool_market, test_pool, _, _) = setup().await;
+
+    let wrong_treasury_account = Keypair::new();
+    let wrong_treasury_pubkey = Keypair::new().pubkey();
+    create_token_account(
+        &mut context,
+        &wrong_treasury_account,
+        &test_pool.pool_mint.pubkey(),
+        &wrong_treasury_pubkey,
+        0,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        test_pool
+            .accrue_fees(
+                &mut context,
+                &test_pool_market,
+                &wrong_treasury_account.pubkey()
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
@@ -158,11 +158,14 @@ async fn fail_invalid_token_account() {
     let amount_allowed =
         get_amount_allowed_general(&mut context, &test_pool, &test_pool_borrow_authority).await;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::borrow(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &test_pool_borrow_authority.pool_borrow_authority_pubkey,
             &user.token_account,
             &Pubkey::new_unique(),
@@ -195,11 +198,14 @@ async fn fail_invalid_pool_market() {
     let amount_allowed =
         get_amount_allowed_general(&mut context, &test_pool, &test_pool_borrow_authority).await;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::borrow(
             &everlend_general_pool::id(),
             &Pubkey::new_unique(),
             &test_pool.pool_pubkey,
+            &registry,
             &test_pool_borrow_authority.pool_borrow_authority_pubkey,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -232,11 +238,14 @@ async fn fail_invalid_pool() {
     let amount_allowed =
         get_amount_allowed_general(&mut context, &test_pool, &test_pool_borrow_authority).await;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::borrow(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &Pubkey::new_unique(),
+            &registry,
             &test_pool_borrow_authority.pool_borrow_authority_pubkey,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -269,11 +278,14 @@ async fn fail_invalid_pool_borrow_authority() {
     let amount_allowed =
         get_amount_allowed_general(&mut context, &test_pool, &test_pool_borrow_authority).await;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::borrow(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &Pubkey::new_unique(),
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -298,3 +310,126 @@ async fn fail_invalid_pool_borrow_authority() {
         )
     );
 }
+
+#[tokio::test]
+async fn fail_with_amount_exceeding_share_allowed() {
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user) =
+        setup().await;
+    let amount_allowed = test_pool_borrow_authority
+        .get_amount_allowed(&mut context)
+        .await;
+
+    assert_eq!(
+        test_pool
+            .borrow(
+                &mut context,
+                &test_pool_market,
+                &test_pool_borrow_authority,
+                None,
+                &user.token_account,
+                amount_allowed + 1,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::BorrowShareAllowedExceeded as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_with_multiple_borrow_authorities() {
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, user) =
+        setup().await;
+
+    // 60% and 40% of the pool
+    test_pool_borrow_authority
+        .update(&mut context, &test_pool_market, &test_pool, 6_000)
+        .await
+        .unwrap();
+
+    let borrow_authority = Keypair::new();
+    let test_pool_borrow_authority2 =
+        TestGeneralPoolBorrowAuthority::new(&test_pool, borrow_authority.pubkey());
+    test_pool_borrow_authority2
+        .create(&mut context, &test_pool_market, &test_pool, 4_000)
+        .await
+        .unwrap();
+
+    test_pool
+        .borrow(
+            &mut context,
+            &test_pool_market,
+            &test_pool_borrow_authority,
+            None,
+            &user.token_account,
+            60,
+        )
+        .await
+        .unwrap();
+
+    // Borrowed liquidity still counts to the total pool amount
+    assert_eq!(
+        test_pool_borrow_authority2
+            .get_amount_allowed(&mut context)
+            .await,
+        40
+    );
+
+    assert_eq!(
+        test_pool
+            .borrow(
+                &mut context,
+                &test_pool_market,
+                &test_pool_borrow_authority2,
+                Some(&borrow_authority),
+                &user.token_account,
+                41,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::BorrowShareAllowedExceeded as u32)
+        )
+    );
+
+    test_pool
+        .borrow(
+            &mut context,
+            &test_pool_market,
+            &test_pool_borrow_authority2,
+            Some(&borrow_authority),
+            &user.token_account,
+            40,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_pool
+            .borrow(
+                &mut context,
+                &test_pool_market,
+                &test_pool_borrow_authority,
+                None,
+                &user.token_account,
+                1,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::BorrowShareAllowedExceeded as u32)
+        )
+    );
+
+    assert_eq!(
+        test_pool.get_data(&mut context).await.total_amount_borrowed,
+        100
+    );
+}
@@ -0,0 +1,176 @@
+use crate::utils::*;
+use everlend_rewards::find_vault_program_address;
+use everlend_rewards::state::Mining;
+use solana_program::program_pack::Pack;
+use solana_program_test::*;
+use solana_sdk::signature::Keypair;
+use solana_sdk::{pubkey::Pubkey, signer::Signer, transaction::Transaction};
+
+const REWARDS: u64 = 10 * EXP;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+    LiquidityProvider,
+    Pubkey,
+) {
+    let mut env = presetup().await;
+
+    let test_pool_market = TestGeneralPoolMarket::new();
+    test_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let test_pool = TestGeneralPool::new(&test_pool_market, None);
+    test_pool
+        .create(&mut env.context, &test_pool_market)
+        .await
+        .unwrap();
+
+    let user = add_liquidity_provider(
+        &mut env.context,
+        &test_pool.token_mint_pubkey,
+        &test_pool.pool_mint.pubkey(),
+        9999 * EXP,
+    )
+    .await
+    .unwrap();
+
+    let mining_acc = test_pool
+        .init_user_mining(&mut env.context, &test_pool_market, &user)
+        .await;
+
+    test_pool
+        .deposit(
+            &mut env.context,
+            &test_pool_market,
+            &user,
+            mining_acc,
+            100 * EXP,
+        )
+        .await
+        .unwrap();
+
+    fill_rewards(&mut env.context, &test_pool, REWARDS).await;
+
+    (env.context, test_pool_market, test_pool, user, mining_acc)
+}
+
+// Adds reward vault of the pool token mint and fills it
+async fn fill_rewards(context: &mut ProgramTestContext, test_pool: &TestGeneralPool, amount: u64) {
+    let payer = context.payer.pubkey();
+
+    let fee_account = Keypair::new();
+    create_token_account(
+        context,
+        &fee_account,
+        &test_pool.token_mint_pubkey,
+        &payer,
+        0,
+    )
+    .await
+    .unwrap();
+
+    let rewarder = Keypair::new();
+    create_token_account(context, &rewarder, &test_pool.token_mint_pubkey, &payer, 0)
+        .await
+        .unwrap();
+    mint_tokens(
+        context,
+        &test_pool.token_mint_pubkey,
+        &rewarder.pubkey(),
+        amount,
+    )
+    .await
+    .unwrap();
+
+    let (vault, _) = find_vault_program_address(
+        &everlend_rewards::id(),
+        &test_pool.mining_reward_pool,
+        &test_pool.token_mint_pubkey,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            everlend_rewards::instruction::add_vault(
+                &everlend_rewards::id(),
+                &test_pool.rewards_root.pubkey(),
+                &test_pool.mining_reward_pool,
+                &test_pool.token_mint_pubkey,
+                &vault,
+                &fee_account.pubkey(),
+                &payer,
+            ),
+            everlend_rewards::instruction::fill_vault(
+                &everlend_rewards::id(),
+                &test_pool.mining_reward_pool,
+                &test_pool.token_mint_pubkey,
+                &vault,
+                &fee_account.pubkey(),
+                &payer,
+                &rewarder.pubkey(),
+                amount,
+            ),
+        ],
+        Some(&payer),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pool_market, test_pool, user, mining_acc) = setup().await;
+
+    let token_balance = get_token_balance(&mut context, &user.token_account).await;
+
+    test_pool
+        .compound_rewards(&mut context, &test_pool_market, &user, mining_acc)
+        .await
+        .unwrap();
+
+    // Rewards without the vault fee are deposited back at 1:1 rate
+    let compounded = 9_800_000_000;
+    assert_eq!(
+        get_token_balance(&mut context, &user.token_account).await,
+        token_balance
+    );
+    assert_eq!(
+        get_token_balance(&mut context, &user.pool_account).await,
+        100 * EXP + compounded
+    );
+
+    let mining_account = get_account(&mut context, &mining_acc).await;
+    let mining = Mining::unpack(&mining_account.data).unwrap();
+    assert_eq!(mining.share, 100 * EXP + compounded);
+    assert_eq!(mining.indexes[0].rewards, 0);
+}
+
+#[tokio::test]
+async fn success_without_rewards() {
+    let (mut context, test_pool_market, test_pool, user, mining_acc) = setup().await;
+
+    test_pool
+        .compound_rewards(&mut context, &test_pool_market, &user, mining_acc)
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    // Nothing is claimed and deposited
+    let pool_balance = get_token_balance(&mut context, &user.pool_account).await;
+    test_pool
+        .compound_rewards(&mut context, &test_pool_market, &user, mining_acc)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &user.pool_account).await,
+        pool_balance
+    );
+}
@@ -1,7 +1,8 @@
 use crate::utils::*;
-use everlend_general_pool::instruction;
-use everlend_general_pool::state::AccountType;
+use everlend_general_pool::state::{AccountType, PoolBorrowShares};
+use everlend_general_pool::{find_pool_borrow_shares_program_address, instruction};
 use solana_program::instruction::InstructionError;
+use solana_program::program_pack::Pack;
 use solana_program_test::*;
 use solana_sdk::signer::Signer;
 use solana_sdk::transaction::{Transaction, TransactionError};
@@ -42,6 +43,17 @@ async fn success() {
         AccountType::WithdrawRequests
     );
     assert_eq!(withdrawal_requests.pool, test_pool.pool_pubkey,);
+
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(
+        &everlend_general_pool::id(),
+        &test_pool.pool_pubkey,
+    );
+    let pool_borrow_shares =
+        PoolBorrowShares::unpack(&get_account(&mut context, &pool_borrow_shares).await.data)
+            .unwrap();
+
+    assert_eq!(pool_borrow_shares.pool, test_pool.pool_pubkey);
+    assert_eq!(pool_borrow_shares.total_share_allowed, 0);
 }
 
 #[tokio::test]
@@ -151,3 +151,52 @@ async fn fail_with_wrong_pool_market() {
         )
     );
 }
+
+#[tokio::test]
+async fn fail_with_share_allowed_too_high() {
+    let (mut context, test_pool_market, test_pool) = setup().await;
+
+    let test_pool_borrow_authority =
+        TestGeneralPoolBorrowAuthority::new(&test_pool, context.payer.pubkey());
+
+    assert_eq!(
+        test_pool_borrow_authority
+            .create(&mut context, &test_pool_market, &test_pool, 10_001)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_with_total_share_allowed_too_high() {
+    let (mut context, test_pool_market, test_pool) = setup().await;
+
+    let test_pool_borrow_authority =
+        TestGeneralPoolBorrowAuthority::new(&test_pool, context.payer.pubkey());
+    test_pool_borrow_authority
+        .create(&mut context, &test_pool_market, &test_pool, 6_000)
+        .await
+        .unwrap();
+
+    let test_pool_borrow_authority2 =
+        TestGeneralPoolBorrowAuthority::new(&test_pool, Keypair::new().pubkey());
+
+    assert_eq!(
+        test_pool_borrow_authority2
+            .create(&mut context, &test_pool_market, &test_pool, 4_001)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::TotalShareAllowedExceeded as u32)
+        )
+    );
+
+    test_pool_borrow_authority2
+        .create(&mut context, &test_pool_market, &test_pool, 4_000)
+        .await
+        .unwrap();
+}
@@ -108,11 +108,14 @@ const AMOUNT: u64 = 100 * EXP;
 async fn fail_with_invalid_pool_mint_pubkey_argument() {
     let (mut context, test_pool_market, test_pool, user, mining_acc) = setup().await;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.token_account,
             &user.pool_account,
             &test_pool.token_account.pubkey(),
@@ -146,11 +149,14 @@ async fn fail_with_invalid_pool_mint_pubkey_argument() {
 async fn fail_with_invalid_token_account_pubkey_argument() {
     let (mut context, test_pool_market, test_pool, user, mining_acc) = setup().await;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.token_account,
             &user.pool_account,
             // Wrong token account pubkey
@@ -186,11 +192,14 @@ async fn fail_with_invalid_destination_argument() {
 
     // Create new pool
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.token_account,
             // Wrong destination
             &user.token_account,
@@ -224,11 +233,14 @@ async fn fail_with_invalid_destination_argument() {
 async fn fail_with_invalid_source_argument() {
     let (mut context, test_pool_market, test_pool, user, mining_acc) = setup().await;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             //Wrong source
             &user.pool_account,
             &user.pool_account,
@@ -264,11 +276,14 @@ async fn fail_with_invalid_user_transfer_authority() {
 
     let wrong_authority = Keypair::new();
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.token_account,
             &user.pool_account,
             &test_pool.token_account.pubkey(),
@@ -302,12 +317,15 @@ async fn fail_with_invalid_user_transfer_authority() {
 async fn fail_with_invalid_pool_market_argument() {
     let (mut context, _test_pool_market, test_pool, user, mining_acc) = setup().await;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
             &everlend_general_pool::id(),
             // Wrong pool market
             &Pubkey::new_unique(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.token_account,
             &user.pool_account,
             &test_pool.token_account.pubkey(),
@@ -340,12 +358,15 @@ async fn fail_with_invalid_pool_market_argument() {
 async fn fail_with_invalid_pool_argument() {
     let (mut context, test_pool_market, test_pool, user, mining_acc) = setup().await;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             //Wrong pool
             &Pubkey::new_unique(),
+            &registry,
             &user.token_account,
             &user.pool_account,
             &test_pool.token_account.pubkey(),
@@ -386,16 +407,25 @@ async fn fail_with_amount_too_small() {
             SetPoolConfigParams {
                 deposit_minimum: Some(1100),
                 withdraw_minimum: Some(1100),
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: None,
+                exit_fee: None,
+                pool_deposit_cap: None,
+                user_deposit_cap: None,
             },
         )
         .await
         .unwrap();
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::deposit(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.token_account,
             &user.pool_account,
             &test_pool.token_account.pubkey(),
@@ -439,4 +469,88 @@ async fn fail_with_zero_amount() {
             InstructionError::Custom(EverlendError::ZeroAmount as u32)
         ),
     );
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn fail_with_pool_deposit_cap() {
+    let (mut context, test_pool_market, test_pool, user, mining_acc) = setup().await;
+
+    test_pool
+        .set_pool_config(
+            &mut context,
+            &test_pool_market,
+            SetPoolConfigParams {
+                deposit_minimum: None,
+                withdraw_minimum: None,
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: None,
+                exit_fee: None,
+                pool_deposit_cap: Some(Some(150)),
+                user_deposit_cap: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    test_pool
+        .deposit(&mut context, &test_pool_market, &user, mining_acc, 100)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_pool
+            .deposit(&mut context, &test_pool_market, &user, mining_acc, 51)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::PoolDepositCapExceeded as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_with_user_deposit_cap() {
+    let (mut context, test_pool_market, test_pool, user, mining_acc) = setup().await;
+
+    test_pool
+        .set_pool_config(
+            &mut context,
+            &test_pool_market,
+            SetPoolConfigParams {
+                deposit_minimum: None,
+                withdraw_minimum: None,
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: None,
+                exit_fee: None,
+                pool_deposit_cap: None,
+                user_deposit_cap: Some(Some(150)),
+            },
+        )
+        .await
+        .unwrap();
+
+    test_pool
+        .deposit(&mut context, &test_pool_market, &user, mining_acc, 100)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_pool
+            .deposit(&mut context, &test_pool_market, &user, mining_acc, 51)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::UserDepositCapExceeded as u32)
+        )
+    );
+
+    test_pool
+        .deposit(&mut context, &test_pool_market, &user, mining_acc, 50)
+        .await
+        .unwrap();
+}
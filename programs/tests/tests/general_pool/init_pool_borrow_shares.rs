@@ -0,0 +1,228 @@
+use crate::utils::*;
+use everlend_general_pool::state::{
+    AccountType, DeprecatedPoolBorrowAuthority, PoolBorrowAuthority, PoolBorrowShares,
+};
+use everlend_general_pool::{
+    find_pool_borrow_authority_program_address, find_pool_borrow_shares_program_address,
+    instruction,
+};
+use solana_program::instruction::InstructionError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+    LiquidityProvider,
+    Keypair,
+) {
+    let mut env = presetup().await;
+
+    let test_pool_market = TestGeneralPoolMarket::new();
+    test_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let test_pool = TestGeneralPool::new(&test_pool_market, None);
+    test_pool
+        .create(&mut env.context, &test_pool_market)
+        .await
+        .unwrap();
+
+    let user = add_liquidity_provider(
+        &mut env.context,
+        &test_pool.token_mint_pubkey,
+        &test_pool.pool_mint.pubkey(),
+        100,
+    )
+    .await
+    .unwrap();
+
+    let mining_acc = test_pool
+        .init_user_mining(&mut env.context, &test_pool_market, &user)
+        .await;
+    test_pool
+        .deposit(&mut env.context, &test_pool_market, &user, mining_acc, 100)
+        .await
+        .unwrap();
+
+    // Pool created before the pool borrow shares
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(
+        &everlend_general_pool::id(),
+        &test_pool.pool_pubkey,
+    );
+    env.context
+        .set_account(&pool_borrow_shares, &AccountSharedData::default());
+
+    let left_out_authority = Keypair::new();
+    for borrow_authority in [env.context.payer.pubkey(), left_out_authority.pubkey()].iter() {
+        set_deprecated_pool_borrow_authority(&mut env.context, &test_pool, borrow_authority).await;
+    }
+
+    (
+        env.context,
+        test_pool_market,
+        test_pool,
+        user,
+        left_out_authority,
+    )
+}
+
+async fn set_deprecated_pool_borrow_authority(
+    context: &mut ProgramTestContext,
+    test_pool: &TestGeneralPool,
+    borrow_authority: &Pubkey,
+) {
+    let (pool_borrow_authority, _) = find_pool_borrow_authority_program_address(
+        &everlend_general_pool::id(),
+        &test_pool.pool_pubkey,
+        borrow_authority,
+    );
+
+    let mut data = vec![0u8; DeprecatedPoolBorrowAuthority::LEN];
+    DeprecatedPoolBorrowAuthority::pack(
+        DeprecatedPoolBorrowAuthority {
+            account_type: AccountType::PoolBorrowAuthority,
+            pool: test_pool.pool_pubkey,
+            borrow_authority: *borrow_authority,
+            amount_borrowed: 0,
+            share_allowed: 5_000,
+        },
+        &mut data,
+    )
+    .unwrap();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    context.set_account(
+        &pool_borrow_authority,
+        &AccountSharedData::from(Account {
+            lamports: rent.minimum_balance(DeprecatedPoolBorrowAuthority::LEN),
+            data,
+            owner: everlend_general_pool::id(),
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+}
+
+async fn init_pool_borrow_shares(
+    context: &mut ProgramTestContext,
+    test_pool_market: &TestGeneralPoolMarket,
+    test_pool: &TestGeneralPool,
+    borrow_authorities: &[Pubkey],
+) -> BanksClientResult<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::init_pool_borrow_shares(
+            &everlend_general_pool::id(),
+            &test_pool_market.keypair.pubkey(),
+            &test_pool.pool_pubkey,
+            &test_pool_market.manager.pubkey(),
+            borrow_authorities,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &test_pool_market.manager],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pool_market, test_pool, user, left_out_authority) = setup().await;
+    let counted_authority = context.payer.pubkey();
+
+    init_pool_borrow_shares(
+        &mut context,
+        &test_pool_market,
+        &test_pool,
+        &[counted_authority],
+    )
+    .await
+    .unwrap();
+
+    let (pool_borrow_shares, _) = find_pool_borrow_shares_program_address(
+        &everlend_general_pool::id(),
+        &test_pool.pool_pubkey,
+    );
+    let pool_borrow_shares =
+        PoolBorrowShares::unpack(&get_account(&mut context, &pool_borrow_shares).await.data)
+            .unwrap();
+    assert_eq!(pool_borrow_shares.total_share_allowed, 5_000);
+
+    let test_pool_borrow_authority =
+        TestGeneralPoolBorrowAuthority::new(&test_pool, counted_authority);
+    let (_, bump) = find_pool_borrow_authority_program_address(
+        &everlend_general_pool::id(),
+        &test_pool.pool_pubkey,
+        &counted_authority,
+    );
+    let pool_borrow_authority = PoolBorrowAuthority::unpack(
+        &get_account(
+            &mut context,
+            &test_pool_borrow_authority.pool_borrow_authority_pubkey,
+        )
+        .await
+        .data,
+    )
+    .unwrap();
+    assert_eq!(pool_borrow_authority.share_allowed, 5_000);
+    assert_eq!(pool_borrow_authority.bump, bump);
+
+    test_pool
+        .borrow(
+            &mut context,
+            &test_pool_market,
+            &test_pool_borrow_authority,
+            None,
+            &user.token_account,
+            50,
+        )
+        .await
+        .unwrap();
+
+    // Borrow authority left out of the total share allowed can't borrow
+    let left_out_pool_borrow_authority =
+        TestGeneralPoolBorrowAuthority::new(&test_pool, left_out_authority.pubkey());
+    assert_eq!(
+        test_pool
+            .borrow(
+                &mut context,
+                &test_pool_market,
+                &left_out_pool_borrow_authority,
+                Some(&left_out_authority),
+                &user.token_account,
+                10,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
+
+#[tokio::test]
+async fn fail_with_repeated_borrow_authority() {
+    let (mut context, test_pool_market, test_pool, _user, _left_out_authority) = setup().await;
+    let borrow_authority = context.payer.pubkey();
+
+    assert_eq!(
+        init_pool_borrow_shares(
+            &mut context,
+            &test_pool_market,
+            &test_pool,
+            &[borrow_authority, borrow_authority],
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
@@ -0,0 +1,175 @@
+use crate::utils::*;
+use everlend_general_pool::state::{SetPoolConfigParams, MAX_EXIT_FEE_BPS};
+use everlend_utils::EverlendError;
+use solana_program::instruction::InstructionError;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::TransactionError;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+    LiquidityProvider,
+    Pubkey,
+) {
+    let mut env = presetup().await;
+
+    let test_pool_market = TestGeneralPoolMarket::new();
+    test_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let test_pool = TestGeneralPool::new(&test_pool_market, None);
+    test_pool
+        .create(&mut env.context, &test_pool_market)
+        .await
+        .unwrap();
+
+    let user = add_liquidity_provider(
+        &mut env.context,
+        &test_pool.token_mint_pubkey,
+        &test_pool.pool_mint.pubkey(),
+        200,
+    )
+    .await
+    .unwrap();
+
+    let mining_acc = test_pool
+        .init_user_mining(&mut env.context, &test_pool_market, &user)
+        .await;
+
+    test_pool
+        .deposit(&mut env.context, &test_pool_market, &user, mining_acc, 100)
+        .await
+        .unwrap();
+
+    env.context.warp_to_slot(3).unwrap();
+
+    (env.context, test_pool_market, test_pool, user, mining_acc)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pool_market, test_pool, user, mining_acc) = setup().await;
+
+    test_pool
+        .instant_withdraw(&mut context, &test_pool_market, &user, mining_acc, 40)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &user.pool_account).await,
+        60
+    );
+    assert_eq!(
+        get_token_balance(&mut context, &user.token_account).await,
+        140
+    );
+    assert_eq!(
+        get_token_balance(&mut context, &test_pool.token_account.pubkey()).await,
+        60
+    );
+}
+
+#[tokio::test]
+async fn success_with_exit_fee() {
+    let (mut context, test_pool_market, test_pool, user, mining_acc) = setup().await;
+
+    test_pool
+        .set_pool_config(
+            &mut context,
+            &test_pool_market,
+            SetPoolConfigParams {
+                deposit_minimum: None,
+                withdraw_minimum: None,
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: None,
+                exit_fee: Some(MAX_EXIT_FEE_BPS),
+                pool_deposit_cap: None,
+                user_deposit_cap: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    test_pool
+        .instant_withdraw(&mut context, &test_pool_market, &user, mining_acc, 100)
+        .await
+        .unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &user.pool_account).await, 0);
+    assert_eq!(
+        get_token_balance(&mut context, &user.token_account).await,
+        197
+    );
+    // Fee stays in the pool
+    assert_eq!(
+        get_token_balance(&mut context, &test_pool.token_account.pubkey()).await,
+        3
+    );
+}
+
+#[tokio::test]
+async fn fail_with_reserved_liquidity() {
+    let (mut context, test_pool_market, test_pool, user, mining_acc) = setup().await;
+
+    let test_pool_borrow_authority =
+        TestGeneralPoolBorrowAuthority::new(&test_pool, context.payer.pubkey());
+    test_pool_borrow_authority
+        .create(
+            &mut context,
+            &test_pool_market,
+            &test_pool,
+            COLLATERAL_POOL_SHARE_ALLOWED,
+        )
+        .await
+        .unwrap();
+
+    test_pool
+        .borrow(
+            &mut context,
+            &test_pool_market,
+            &test_pool_borrow_authority,
+            None,
+            &user.token_account,
+            50,
+        )
+        .await
+        .unwrap();
+
+    test_pool
+        .withdraw_request(&mut context, &test_pool_market, &user, mining_acc, 20)
+        .await
+        .unwrap();
+    test_pool
+        .queue_withdraw_request(&mut context, &test_pool_market, &user, mining_acc, 10)
+        .await
+        .unwrap();
+
+    // Only 20 tokens are idle, 30 are reserved for the legacy and queued requests
+    assert_eq!(
+        test_pool
+            .instant_withdraw(&mut context, &test_pool_market, &user, mining_acc, 21)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::InstantWithdrawInsufficientLiquidity as u32)
+        )
+    );
+
+    test_pool
+        .instant_withdraw(&mut context, &test_pool_market, &user, mining_acc, 20)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &test_pool.token_account.pubkey()).await,
+        30
+    );
+}
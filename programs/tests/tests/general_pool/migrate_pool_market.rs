@@ -0,0 +1,114 @@
+use crate::utils::*;
+use everlend_general_pool::state::{AccountType, DeprecatedPoolMarket, PoolMarket};
+use everlend_utils::AccountVersion;
+use solana_program::instruction::InstructionError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program::system_instruction;
+use solana_program_test::*;
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+async fn setup() -> (ProgramTestContext, TestGeneralPoolMarket, Pubkey) {
+    let mut env = presetup().await;
+
+    let test_pool_market = TestGeneralPoolMarket::new();
+    let registry = env.registry.keypair.pubkey();
+
+    // Pool market created before the pending manager field
+    let mut data = vec![0u8; DeprecatedPoolMarket::LEN];
+    DeprecatedPoolMarket::pack(
+        DeprecatedPoolMarket {
+            account_type: AccountType::PoolMarket,
+            account_version: AccountVersion::V0,
+            manager: test_pool_market.manager.pubkey(),
+            registry,
+        },
+        &mut data,
+    )
+    .unwrap();
+
+    let rent = env.context.banks_client.get_rent().await.unwrap();
+    env.context.set_account(
+        &test_pool_market.keypair.pubkey(),
+        &AccountSharedData::from(Account {
+            lamports: rent.minimum_balance(DeprecatedPoolMarket::LEN),
+            data,
+            owner: everlend_general_pool::id(),
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &env.context.payer.pubkey(),
+            &test_pool_market.manager.pubkey(),
+            999999999,
+        )],
+        Some(&env.context.payer.pubkey()),
+        &[&env.context.payer],
+        env.context.last_blockhash,
+    );
+    env.context
+        .banks_client
+        .process_transaction(tx)
+        .await
+        .unwrap();
+
+    (env.context, test_pool_market, registry)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pool_market, registry) = setup().await;
+
+    test_pool_market.migrate(&mut context).await.unwrap();
+
+    let account = get_account(&mut context, &test_pool_market.keypair.pubkey()).await;
+    assert_eq!(account.data.len(), PoolMarket::LEN);
+
+    let pool_market = PoolMarket::unpack(&account.data).unwrap();
+    assert_eq!(pool_market.manager, test_pool_market.manager.pubkey());
+    assert_eq!(pool_market.registry, registry);
+    assert_eq!(pool_market.pending_manager, Pubkey::default());
+}
+
+#[tokio::test]
+async fn fail_with_wrong_manager() {
+    let (mut context, test_pool_market, _) = setup().await;
+
+    let wrong_pool_market = TestGeneralPoolMarket {
+        keypair: test_pool_market.keypair,
+        manager: Keypair::new(),
+    };
+
+    assert_eq!(
+        wrong_pool_market
+            .migrate(&mut context)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_second_time_migrate() {
+    let (mut context, test_pool_market, _) = setup().await;
+
+    test_pool_market.migrate(&mut context).await.unwrap();
+
+    context.warp_to_slot(3).unwrap();
+
+    assert_eq!(
+        test_pool_market
+            .migrate(&mut context)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
@@ -0,0 +1,451 @@
+use crate::utils::*;
+use everlend_general_pool::state::{SetPoolConfigParams, WITHDRAW_DELAY};
+use everlend_utils::EverlendError;
+use solana_program::instruction::InstructionError;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::TransactionError;
+
+const INITIAL_USER_BALANCE: u64 = 5000000;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+    TestGeneralPoolBorrowAuthority,
+    (LiquidityProvider, Pubkey),
+    (LiquidityProvider, Pubkey),
+) {
+    let mut env = presetup().await;
+
+    let test_pool_market = TestGeneralPoolMarket::new();
+    test_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let test_pool = TestGeneralPool::new(&test_pool_market, None);
+    test_pool
+        .create(&mut env.context, &test_pool_market)
+        .await
+        .unwrap();
+
+    let test_pool_borrow_authority =
+        TestGeneralPoolBorrowAuthority::new(&test_pool, env.context.payer.pubkey());
+    test_pool_borrow_authority
+        .create(
+            &mut env.context,
+            &test_pool_market,
+            &test_pool,
+            COLLATERAL_POOL_SHARE_ALLOWED,
+        )
+        .await
+        .unwrap();
+
+    let mut users = vec![];
+    for _ in 0..2 {
+        let user = add_liquidity_provider(
+            &mut env.context,
+            &test_pool.token_mint_pubkey,
+            &test_pool.pool_mint.pubkey(),
+            200,
+        )
+        .await
+        .unwrap();
+
+        // Fill user account by native token
+        transfer(&mut env.context, &user.owner.pubkey(), INITIAL_USER_BALANCE)
+            .await
+            .unwrap();
+
+        let mining_acc = test_pool
+            .init_user_mining(&mut env.context, &test_pool_market, &user)
+            .await;
+
+        test_pool
+            .deposit(&mut env.context, &test_pool_market, &user, mining_acc, 100)
+            .await
+            .unwrap();
+
+        users.push((user, mining_acc));
+    }
+
+    env.context.warp_to_slot(3).unwrap();
+
+    let user2 = users.pop().unwrap();
+    let user1 = users.pop().unwrap();
+
+    (
+        env.context,
+        test_pool_market,
+        test_pool,
+        test_pool_borrow_authority,
+        user1,
+        user2,
+    )
+}
+
+async fn refresh_blockhash(context: &mut ProgramTestContext, slot: u64) {
+    context.warp_to_slot(slot).unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+}
+
+#[tokio::test]
+async fn success() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        _test_pool_borrow_authority,
+        (user1, mining_acc1),
+        (user2, mining_acc2),
+    ) = setup().await;
+
+    // Multiple requests of the same user
+    test_pool
+        .queue_withdraw_request(&mut context, &test_pool_market, &user1, mining_acc1, 30)
+        .await
+        .unwrap();
+    test_pool
+        .queue_withdraw_request(&mut context, &test_pool_market, &user2, mining_acc2, 50)
+        .await
+        .unwrap();
+    test_pool
+        .queue_withdraw_request(&mut context, &test_pool_market, &user1, mining_acc1, 20)
+        .await
+        .unwrap();
+
+    let (withdrawal_requests_pubkey, withdrawal_requests) = test_pool
+        .get_withdrawal_requests(&mut context, &test_pool_market)
+        .await;
+    assert_eq!(withdrawal_requests.liquidity_supply, 100);
+    assert_eq!(withdrawal_requests.next_index, 3);
+    assert_eq!(withdrawal_requests.head_index, 0);
+
+    let withdrawal_request = test_pool
+        .get_queued_withdrawal_request(&mut context, &withdrawal_requests_pubkey, 1)
+        .await;
+    assert_eq!(withdrawal_request.from, user2.pubkey());
+    assert_eq!(withdrawal_request.liquidity_amount, 50);
+    assert_eq!(
+        get_token_balance(&mut context, &user1.pool_account).await,
+        50
+    );
+
+    refresh_blockhash(&mut context, 3 + WITHDRAW_DELAY).await;
+
+    // Requests are processed in order
+    for (user, balance) in [(&user1, 130), (&user2, 150), (&user1, 150)] {
+        test_pool
+            .process_withdrawal_request(&mut context, &test_pool_market, user)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_token_balance(&mut context, &user.token_account).await,
+            balance
+        );
+    }
+
+    let (_, withdrawal_requests) = test_pool
+        .get_withdrawal_requests(&mut context, &test_pool_market)
+        .await;
+    assert_eq!(withdrawal_requests.liquidity_supply, 0);
+    assert_eq!(withdrawal_requests.head_index, 3);
+
+    assert_eq!(
+        test_pool
+            .process_withdrawal_request(&mut context, &test_pool_market, &user1)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::WithdrawRequestsQueueEmpty as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_with_partial_fulfilment() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        test_pool_borrow_authority,
+        (user1, mining_acc1),
+        (user2, _),
+    ) = setup().await;
+
+    test_pool
+        .queue_withdraw_request(&mut context, &test_pool_market, &user1, mining_acc1, 100)
+        .await
+        .unwrap();
+
+    // Pool is short of liquidity
+    test_pool
+        .borrow(
+            &mut context,
+            &test_pool_market,
+            &test_pool_borrow_authority,
+            None,
+            &user2.token_account,
+            150,
+        )
+        .await
+        .unwrap();
+
+    refresh_blockhash(&mut context, 3 + WITHDRAW_DELAY).await;
+
+    test_pool
+        .process_withdrawal_request(&mut context, &test_pool_market, &user1)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &user1.token_account).await,
+        150
+    );
+
+    let (withdrawal_requests_pubkey, withdrawal_requests) = test_pool
+        .get_withdrawal_requests(&mut context, &test_pool_market)
+        .await;
+    assert_eq!(withdrawal_requests.liquidity_supply, 50);
+    assert_eq!(withdrawal_requests.head_index, 0);
+
+    let withdrawal_request = test_pool
+        .get_queued_withdrawal_request(&mut context, &withdrawal_requests_pubkey, 0)
+        .await;
+    assert_eq!(withdrawal_request.liquidity_amount, 50);
+    assert_eq!(withdrawal_request.collateral_amount, 50);
+
+    refresh_blockhash(&mut context, 4 + WITHDRAW_DELAY).await;
+
+    assert_eq!(
+        test_pool
+            .process_withdrawal_request(&mut context, &test_pool_market, &user1)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::WithdrawRequestsInsufficientLiquidity as u32)
+        )
+    );
+
+    test_pool
+        .repay(
+            &mut context,
+            &test_pool_market,
+            &test_pool_borrow_authority,
+            &user2,
+            150,
+            0,
+        )
+        .await
+        .unwrap();
+
+    refresh_blockhash(&mut context, 5 + WITHDRAW_DELAY).await;
+
+    test_pool
+        .process_withdrawal_request(&mut context, &test_pool_market, &user1)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &user1.token_account).await,
+        200
+    );
+
+    let (_, withdrawal_requests) = test_pool
+        .get_withdrawal_requests(&mut context, &test_pool_market)
+        .await;
+    assert_eq!(withdrawal_requests.liquidity_supply, 0);
+    assert_eq!(withdrawal_requests.head_index, 1);
+}
+
+#[tokio::test]
+async fn success_with_legacy_request() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        test_pool_borrow_authority,
+        (user1, mining_acc1),
+        (user2, mining_acc2),
+    ) = setup().await;
+
+    test_pool
+        .withdraw_request(&mut context, &test_pool_market, &user2, mining_acc2, 40)
+        .await
+        .unwrap();
+    test_pool
+        .queue_withdraw_request(&mut context, &test_pool_market, &user1, mining_acc1, 100)
+        .await
+        .unwrap();
+
+    test_pool
+        .borrow(
+            &mut context,
+            &test_pool_market,
+            &test_pool_borrow_authority,
+            None,
+            &user2.token_account,
+            100,
+        )
+        .await
+        .unwrap();
+
+    refresh_blockhash(&mut context, 3 + WITHDRAW_DELAY).await;
+
+    // Liquidity of the legacy request isn't used by the queue
+    test_pool
+        .process_withdrawal_request(&mut context, &test_pool_market, &user1)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &user1.token_account).await,
+        160
+    );
+
+    let (_, withdrawal_requests) = test_pool
+        .get_withdrawal_requests(&mut context, &test_pool_market)
+        .await;
+    assert_eq!(withdrawal_requests.liquidity_supply, 80);
+    assert_eq!(withdrawal_requests.queued_liquidity_supply, 40);
+
+    test_pool
+        .withdraw(&mut context, &test_pool_market, &user2)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &user2.token_account).await,
+        240
+    );
+
+    let (_, withdrawal_requests) = test_pool
+        .get_withdrawal_requests(&mut context, &test_pool_market)
+        .await;
+    assert_eq!(withdrawal_requests.liquidity_supply, 40);
+    assert_eq!(withdrawal_requests.queued_liquidity_supply, 40);
+}
+
+#[tokio::test]
+async fn success_with_cancelled_request() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        _test_pool_borrow_authority,
+        (user1, mining_acc1),
+        (user2, mining_acc2),
+    ) = setup().await;
+
+    test_pool
+        .queue_withdraw_request(&mut context, &test_pool_market, &user1, mining_acc1, 30)
+        .await
+        .unwrap();
+    test_pool
+        .queue_withdraw_request(&mut context, &test_pool_market, &user2, mining_acc2, 50)
+        .await
+        .unwrap();
+
+    test_pool
+        .cancel_queued_withdraw_request(&mut context, &test_pool_market, &user1, 0)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &user1.pool_account).await,
+        100
+    );
+
+    refresh_blockhash(&mut context, 3 + WITHDRAW_DELAY).await;
+
+    // Cancelled request is skipped
+    test_pool
+        .process_withdrawal_request(&mut context, &test_pool_market, &user1)
+        .await
+        .unwrap();
+
+    let (_, withdrawal_requests) = test_pool
+        .get_withdrawal_requests(&mut context, &test_pool_market)
+        .await;
+    assert_eq!(withdrawal_requests.liquidity_supply, 50);
+    assert_eq!(withdrawal_requests.queued_liquidity_supply, 50);
+    assert_eq!(withdrawal_requests.head_index, 1);
+
+    test_pool
+        .process_withdrawal_request(&mut context, &test_pool_market, &user2)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &user2.token_account).await,
+        150
+    );
+}
+
+#[tokio::test]
+async fn fail_with_pool_withdraw_delay() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        _test_pool_borrow_authority,
+        (user1, mining_acc1),
+        _,
+    ) = setup().await;
+
+    test_pool
+        .set_pool_config(
+            &mut context,
+            &test_pool_market,
+            SetPoolConfigParams {
+                deposit_minimum: None,
+                withdraw_minimum: None,
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: Some(Some(2 * WITHDRAW_DELAY)),
+                exit_fee: None,
+                pool_deposit_cap: None,
+                user_deposit_cap: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    test_pool
+        .queue_withdraw_request(&mut context, &test_pool_market, &user1, mining_acc1, 30)
+        .await
+        .unwrap();
+
+    refresh_blockhash(&mut context, 3 + WITHDRAW_DELAY).await;
+
+    assert_eq!(
+        test_pool
+            .process_withdrawal_request(&mut context, &test_pool_market, &user1)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::WithdrawRequestsInvalidTicket as u32)
+        )
+    );
+
+    refresh_blockhash(&mut context, 3 + 2 * WITHDRAW_DELAY).await;
+
+    test_pool
+        .process_withdrawal_request(&mut context, &test_pool_market, &user1)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &user1.token_account).await,
+        130
+    );
+}
@@ -0,0 +1,329 @@
+use crate::utils::*;
+use everlend_general_pool::instruction;
+use everlend_rewards::state::{Mining, MAX_LOCK_DURATION};
+use everlend_utils::EverlendError;
+use solana_program::instruction::InstructionError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+    LiquidityProvider,
+    LiquidityProvider,
+    Pubkey,
+    Pubkey,
+) {
+    let mut env = presetup().await;
+
+    let test_pool_market = TestGeneralPoolMarket::new();
+    test_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let test_pool = TestGeneralPool::new(&test_pool_market, None);
+    test_pool
+        .create(&mut env.context, &test_pool_market)
+        .await
+        .unwrap();
+
+    let user = add_liquidity_provider(
+        &mut env.context,
+        &test_pool.token_mint_pubkey,
+        &test_pool.pool_mint.pubkey(),
+        200,
+    )
+    .await
+    .unwrap();
+
+    let destination_user = add_liquidity_provider(
+        &mut env.context,
+        &test_pool.token_mint_pubkey,
+        &test_pool.pool_mint.pubkey(),
+        200,
+    )
+    .await
+    .unwrap();
+
+    let mining_acc = test_pool
+        .init_user_mining(&mut env.context, &test_pool_market, &user)
+        .await;
+    let destination_mining_acc = test_pool
+        .init_user_mining(&mut env.context, &test_pool_market, &destination_user)
+        .await;
+
+    test_pool
+        .deposit(&mut env.context, &test_pool_market, &user, mining_acc, 100)
+        .await
+        .unwrap();
+
+    // Raw SPL transfer doesn't move the mining share
+    token_transfer(
+        &mut env.context,
+        &user.pool_account,
+        &destination_user.pool_account,
+        &user.owner,
+        40,
+    )
+    .await
+    .unwrap();
+
+    (
+        env.context,
+        test_pool_market,
+        test_pool,
+        user,
+        destination_user,
+        mining_acc,
+        destination_mining_acc,
+    )
+}
+
+async fn get_share(context: &mut ProgramTestContext, mining: &Pubkey) -> u64 {
+    let mining_account = get_account(context, mining).await;
+    Mining::unpack(&mining_account.data).unwrap().share
+}
+
+async fn resync_canonical_account(
+    context: &mut ProgramTestContext,
+    test_pool_market: &TestGeneralPoolMarket,
+    test_pool: &TestGeneralPool,
+    user: &Pubkey,
+    mining_account: &Pubkey,
+    authority: &Keypair,
+) -> BanksClientResult<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::resync_mining(
+            &everlend_general_pool::id(),
+            &test_pool_market.keypair.pubkey(),
+            &test_pool.pool_pubkey,
+            &get_associated_token_address(user, &test_pool.pool_mint.pubkey()),
+            user,
+            &authority.pubkey(),
+            &test_pool.mining_reward_pool,
+            mining_account,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success_decrease() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        user,
+        _destination_user,
+        mining_acc,
+        _destination_mining_acc,
+    ) = setup().await;
+
+    assert_eq!(get_share(&mut context, &mining_acc).await, 100);
+
+    test_pool
+        .resync_mining(
+            &mut context,
+            &test_pool_market,
+            &user,
+            mining_acc,
+            &user.owner,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(get_share(&mut context, &mining_acc).await, 60);
+}
+
+#[tokio::test]
+async fn fail_decrease_by_anyone() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        user,
+        destination_user,
+        mining_acc,
+        _destination_mining_acc,
+    ) = setup().await;
+
+    // An empty or partial account of the user can't be used to cut the share
+    assert_eq!(
+        test_pool
+            .resync_mining(
+                &mut context,
+                &test_pool_market,
+                &user,
+                mining_acc,
+                &destination_user.owner,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn success_with_canonical_account() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        user,
+        destination_user,
+        mining_acc,
+        _destination_mining_acc,
+    ) = setup().await;
+
+    let canonical_account =
+        get_associated_token_address(&user.owner.pubkey(), &test_pool.pool_mint.pubkey());
+    let tx = Transaction::new_signed_with_payer(
+        &[create_associated_token_account(
+            &context.payer.pubkey(),
+            &user.owner.pubkey(),
+            &test_pool.pool_mint.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    token_transfer(
+        &mut context,
+        &user.pool_account,
+        &canonical_account,
+        &user.owner,
+        50,
+    )
+    .await
+    .unwrap();
+
+    // Anyone can decrease the share down to the canonical account balance
+    resync_canonical_account(
+        &mut context,
+        &test_pool_market,
+        &test_pool,
+        &user.owner.pubkey(),
+        &mining_acc,
+        &destination_user.owner,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(get_share(&mut context, &mining_acc).await, 50);
+}
+
+#[tokio::test]
+async fn fail_increase_by_manager() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        _user,
+        destination_user,
+        _mining_acc,
+        destination_mining_acc,
+    ) = setup().await;
+
+    assert_eq!(
+        test_pool
+            .resync_mining(
+                &mut context,
+                &test_pool_market,
+                &destination_user,
+                destination_mining_acc,
+                &test_pool_market.manager,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_increase_by_user() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        _user,
+        destination_user,
+        _mining_acc,
+        destination_mining_acc,
+    ) = setup().await;
+
+    assert_eq!(
+        test_pool
+            .resync_mining(
+                &mut context,
+                &test_pool_market,
+                &destination_user,
+                destination_mining_acc,
+                &destination_user.owner,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_decrease_locked_share() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        user,
+        _destination_user,
+        mining_acc,
+        _destination_mining_acc,
+    ) = setup().await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[everlend_rewards::instruction::lock_mining(
+            &everlend_rewards::id(),
+            &test_pool.mining_reward_pool,
+            &mining_acc,
+            &user.owner.pubkey(),
+            MAX_LOCK_DURATION,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &user.owner],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // The locked share stays after the pool tokens are moved out
+    assert_eq!(
+        test_pool
+            .resync_mining(
+                &mut context,
+                &test_pool_market,
+                &user,
+                mining_acc,
+                &user.owner,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::RewardsShareLocked as u32)
+        )
+    );
+}
@@ -6,6 +6,8 @@ use solana_program_test::*;
 use solana_sdk::signer::Signer;
 use solana_sdk::transaction::TransactionError;
 use everlend_utils::EverlendError;
+use everlend_rewards::state::Mining;
+use solana_program::program_pack::Pack;
 
 async fn setup() -> (
     ProgramTestContext,
@@ -137,6 +139,103 @@ async fn success() {
     assert_eq!(get_token_balance(&mut context, &destination_user.pool_account).await, 100);
 }
 
+#[tokio::test]
+async fn success_with_amount() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        _pool_borrow_authority,
+        user,
+        destination_user,
+        mining_acc,
+        destination_mining_acc,
+    ) = setup().await;
+
+    test_pool
+        .deposit(
+            &mut context,
+            &test_pool_market,
+            &user,
+            mining_acc,
+            100,
+        )
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+
+    test_pool
+        .transfer_deposit_amount(
+            &mut context,
+            &user,
+            &destination_user,
+            mining_acc,
+            destination_mining_acc,
+            40,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &user.pool_account).await,
+        60
+    );
+    assert_eq!(get_token_balance(&mut context, &destination_user.pool_account).await, 40);
+
+    let mining = Mining::unpack(&get_account(&mut context, &mining_acc).await.data).unwrap();
+    assert_eq!(mining.share, 60);
+    let destination_mining =
+        Mining::unpack(&get_account(&mut context, &destination_mining_acc).await.data).unwrap();
+    assert_eq!(destination_mining.share, 40);
+}
+
+#[tokio::test]
+async fn fail_with_amount_exceeding_share() {
+    let (
+        mut context,
+        test_pool_market,
+        test_pool,
+        _pool_borrow_authority,
+        user,
+        destination_user,
+        mining_acc,
+        destination_mining_acc,
+    ) = setup().await;
+
+    test_pool
+        .deposit(
+            &mut context,
+            &test_pool_market,
+            &user,
+            mining_acc,
+            100,
+        )
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+
+    assert_eq!(
+        test_pool
+            .transfer_deposit_amount(
+                &mut context,
+                &user,
+                &destination_user,
+                mining_acc,
+                destination_mining_acc,
+                101,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::RewardAndCollateralMismatch as u32)
+        )
+    )
+}
+
 #[tokio::test]
 async fn failed_after_spl_transfer() {
     let (
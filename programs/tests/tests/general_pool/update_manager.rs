@@ -3,6 +3,7 @@ use everlend_general_pool::instruction;
 use everlend_general_pool::state::PoolMarket;
 use solana_program::instruction::InstructionError;
 use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
 use solana_program_test::*;
 use solana_sdk::signature::Keypair;
 use solana_sdk::signer::Signer;
@@ -41,14 +42,33 @@ async fn success() {
     let new_manager = Keypair::new();
 
     let tx = Transaction::new_signed_with_payer(
-        &[instruction::update_manager(
+        &[instruction::propose_manager(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool_market.manager.pubkey(),
             &new_manager.pubkey(),
         )],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &test_pool_market.manager, &new_manager],
+        &[&context.payer, &test_pool_market.manager],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Manager is not changed until accepted
+    let pool_market_acc = get_account(&mut context, &pool.pool_market).await;
+    let pool_market = PoolMarket::unpack_unchecked(&pool_market_acc.data).unwrap();
+    assert_eq!(pool_market.manager, test_pool_market.manager.pubkey());
+    assert_eq!(pool_market.pending_manager, new_manager.pubkey());
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::accept_manager(
+            &everlend_general_pool::id(),
+            &test_pool_market.keypair.pubkey(),
+            &new_manager.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &new_manager],
         context.last_blockhash,
     );
 
@@ -58,19 +78,117 @@ async fn success() {
     let pool_market_acc = get_account(&mut context, &pool.pool_market).await;
     let pool_market = PoolMarket::unpack_unchecked(&pool_market_acc.data).unwrap();
     assert_eq!(pool_market.manager, new_manager.pubkey());
+    assert_eq!(pool_market.pending_manager, Pubkey::default());
 
     let new_manager = Keypair::new();
 
     // Try to change back without proper signature
     let tx = Transaction::new_signed_with_payer(
-        &[instruction::update_manager(
+        &[instruction::propose_manager(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool_market.manager.pubkey(),
             &new_manager.pubkey(),
         )],
         Some(&context.payer.pubkey()),
-        &[&context.payer, &test_pool_market.manager, &new_manager],
+        &[&context.payer, &test_pool_market.manager],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn success_cancel_proposal() {
+    let (mut context, test_pool_market) = setup().await;
+
+    let new_manager = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::propose_manager(
+                &everlend_general_pool::id(),
+                &test_pool_market.keypair.pubkey(),
+                &test_pool_market.manager.pubkey(),
+                &new_manager.pubkey(),
+            ),
+            instruction::cancel_manager_proposal(
+                &everlend_general_pool::id(),
+                &test_pool_market.keypair.pubkey(),
+                &test_pool_market.manager.pubkey(),
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &test_pool_market.manager],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let pool_market_acc = get_account(&mut context, &test_pool_market.keypair.pubkey()).await;
+    let pool_market = PoolMarket::unpack_unchecked(&pool_market_acc.data).unwrap();
+    assert_eq!(pool_market.pending_manager, Pubkey::default());
+
+    context.warp_to_slot(3).unwrap();
+
+    // Canceled proposal can't be accepted
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::accept_manager(
+            &everlend_general_pool::id(),
+            &test_pool_market.keypair.pubkey(),
+            &new_manager.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &new_manager],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_accept_by_not_proposed_manager() {
+    let (mut context, test_pool_market) = setup().await;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::propose_manager(
+            &everlend_general_pool::id(),
+            &test_pool_market.keypair.pubkey(),
+            &test_pool_market.manager.pubkey(),
+            &Keypair::new().pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &test_pool_market.manager],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let wrong_manager = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::accept_manager(
+            &everlend_general_pool::id(),
+            &test_pool_market.keypair.pubkey(),
+            &wrong_manager.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &wrong_manager],
         context.last_blockhash,
     );
 
@@ -1,5 +1,7 @@
 use crate::utils::*;
-use everlend_general_pool::state::{AccountType, SetPoolConfigParams};
+use everlend_general_pool::state::{
+    AccountType, SetPoolConfigParams, MAX_EXIT_FEE_BPS, MAX_WITHDRAW_DELAY,
+};
 use solana_program::instruction::InstructionError;
 use solana_program_test::*;
 use solana_sdk::signature::Keypair;
@@ -39,6 +41,12 @@ async fn success() {
             SetPoolConfigParams {
                 deposit_minimum: Some(100),
                 withdraw_minimum: Some(150),
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: None,
+                exit_fee: None,
+                pool_deposit_cap: None,
+                user_deposit_cap: None,
             },
         )
         .await
@@ -57,6 +65,12 @@ async fn success() {
             SetPoolConfigParams {
                 deposit_minimum: Some(500),
                 withdraw_minimum: None,
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: None,
+                exit_fee: None,
+                pool_deposit_cap: None,
+                user_deposit_cap: None,
             },
         )
         .await
@@ -84,6 +98,68 @@ async fn fail_with_wrong_manager() {
             SetPoolConfigParams {
                 deposit_minimum: Some(100),
                 withdraw_minimum: Some(150),
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: None,
+                exit_fee: None,
+                pool_deposit_cap: None,
+                user_deposit_cap: None,
+            },
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_with_withdraw_delay_too_high() {
+    let (mut context, test_pool_market, test_pool) = setup().await;
+
+    let err = test_pool
+        .set_pool_config(
+            &mut context,
+            &test_pool_market,
+            SetPoolConfigParams {
+                deposit_minimum: None,
+                withdraw_minimum: None,
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: Some(Some(MAX_WITHDRAW_DELAY + 1)),
+                exit_fee: None,
+                pool_deposit_cap: None,
+                user_deposit_cap: None,
+            },
+        )
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_with_exit_fee_too_high() {
+    let (mut context, test_pool_market, test_pool) = setup().await;
+
+    let err = test_pool
+        .set_pool_config(
+            &mut context,
+            &test_pool_market,
+            SetPoolConfigParams {
+                deposit_minimum: None,
+                withdraw_minimum: None,
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: None,
+                exit_fee: Some(MAX_EXIT_FEE_BPS + 1),
+                pool_deposit_cap: None,
+                user_deposit_cap: None,
             },
         )
         .await
@@ -248,11 +248,14 @@ async fn fail_with_invalid_pool_market() {
     );
     assert_eq!(get_token_balance(&mut context, &transit_account).await, 45);
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_general_pool::id(),
             &Pubkey::new_unique(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.token_account,
             &test_pool.token_account.pubkey(),
             &test_pool.token_mint_pubkey,
@@ -305,11 +308,14 @@ async fn fail_with_invalid_pool() {
     );
     assert_eq!(get_token_balance(&mut context, &transit_account).await, 45);
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &Pubkey::new_unique(),
+            &registry,
             &user.token_account,
             &test_pool.token_account.pubkey(),
             &test_pool.token_mint_pubkey,
@@ -362,11 +368,14 @@ async fn fail_with_invalid_destination() {
     );
     assert_eq!(get_token_balance(&mut context, &transit_account).await, 45);
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &Pubkey::new_unique(),
             &test_pool.token_account.pubkey(),
             &test_pool.token_mint_pubkey,
@@ -416,11 +425,14 @@ async fn fail_with_invalid_token_account() {
     );
     assert_eq!(get_token_balance(&mut context, &transit_account).await, 45);
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.token_account,
             &Pubkey::new_unique(),
             &test_pool.token_mint_pubkey,
@@ -473,11 +485,14 @@ async fn fail_with_invalid_token_mint() {
     );
     assert_eq!(get_token_balance(&mut context, &transit_account).await, 45);
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.token_account,
             &test_pool.token_account.pubkey(),
             &Pubkey::new_unique(),
@@ -530,11 +545,14 @@ async fn fail_with_invalid_pool_mint() {
     );
     assert_eq!(get_token_balance(&mut context, &transit_account).await, 45);
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.token_account,
             &test_pool.token_account.pubkey(),
             &test_pool.token_mint_pubkey,
@@ -599,11 +617,14 @@ async fn success_with_random_tx_signer() {
     );
     assert_eq!(get_token_balance(&mut context, &transit_account).await, 45);
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.token_account,
             &test_pool.token_account.pubkey(),
             &test_pool.token_mint_pubkey,
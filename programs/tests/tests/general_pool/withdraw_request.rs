@@ -130,11 +130,14 @@ async fn fail_with_invalid_pool_market() {
 
     let withdraw_amount = 50;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw_request(
             &everlend_general_pool::id(),
             &Pubkey::new_unique(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.pool_account,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -178,11 +181,14 @@ async fn fail_with_invalid_pool() {
 
     let withdraw_amount = 50;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw_request(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &Pubkey::new_unique(),
+            &registry,
             &user.pool_account,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -226,11 +232,14 @@ async fn fail_with_invalid_destination() {
 
     let withdraw_amount = 50;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw_request(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.pool_account,
             &Pubkey::new_unique(),
             &test_pool.token_account.pubkey(),
@@ -271,11 +280,14 @@ async fn fail_with_invalid_token_account() {
 
     let withdraw_amount = 50;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw_request(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.pool_account,
             &user.token_account,
             &Pubkey::new_unique(),
@@ -319,11 +331,14 @@ async fn fail_with_invalid_token_mint() {
 
     let withdraw_amount = 50;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw_request(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.pool_account,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -367,11 +382,14 @@ async fn fail_with_invalid_pool_mint() {
 
     let withdraw_amount = 50;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw_request(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.pool_account,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -417,11 +435,14 @@ async fn fail_with_wrong_user_transfer_authority() {
 
     let wrong_user_authority = Keypair::new();
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw_request(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.pool_account,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -465,11 +486,14 @@ async fn fail_with_invalid_withdraw_amount() {
 
     let withdraw_amount = u64::MAX;
 
+    let registry = test_pool_market.get_data(&mut context).await.registry;
+
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw_request(
             &everlend_general_pool::id(),
             &test_pool_market.keypair.pubkey(),
             &test_pool.pool_pubkey,
+            &registry,
             &user.pool_account,
             &user.token_account,
             &test_pool.token_account.pubkey(),
@@ -511,6 +535,12 @@ async fn fail_with_amount_too_small() {
             SetPoolConfigParams {
                 deposit_minimum: Some(90),
                 withdraw_minimum: Some(90),
+                performance_fee: None,
+                management_fee: None,
+                withdraw_delay: None,
+                exit_fee: None,
+                pool_deposit_cap: None,
+                user_deposit_cap: None,
             },
         )
         .await
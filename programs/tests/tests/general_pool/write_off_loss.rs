@@ -0,0 +1,163 @@
+use crate::utils::*;
+use everlend_general_pool::instruction;
+use everlend_utils::EverlendError;
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::signature::Keypair;
+use solana_sdk::{signer::Signer, transaction::Transaction, transaction::TransactionError};
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+    TestGeneralPoolBorrowAuthority,
+    LiquidityProvider,
+) {
+    let mut env = presetup().await;
+
+    let test_pool_market = TestGeneralPoolMarket::new();
+    test_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let test_pool = TestGeneralPool::new(&test_pool_market, None);
+    test_pool
+        .create(&mut env.context, &test_pool_market)
+        .await
+        .unwrap();
+
+    let test_pool_borrow_authority =
+        TestGeneralPoolBorrowAuthority::new(&test_pool, env.context.payer.pubkey());
+    test_pool_borrow_authority
+        .create(
+            &mut env.context,
+            &test_pool_market,
+            &test_pool,
+            COLLATERAL_POOL_SHARE_ALLOWED,
+        )
+        .await
+        .unwrap();
+
+    let user = add_liquidity_provider(
+        &mut env.context,
+        &test_pool.token_mint_pubkey,
+        &test_pool.pool_mint.pubkey(),
+        101,
+    )
+    .await
+    .unwrap();
+
+    let mining_acc = test_pool
+        .init_user_mining(&mut env.context, &test_pool_market, &user)
+        .await;
+    test_pool
+        .deposit(&mut env.context, &test_pool_market, &user, mining_acc, 100)
+        .await
+        .unwrap();
+
+    let amount_allowed = test_pool_borrow_authority
+        .get_amount_allowed(&mut env.context)
+        .await;
+    test_pool
+        .borrow(
+            &mut env.context,
+            &test_pool_market,
+            &test_pool_borrow_authority,
+            None,
+            &user.token_account,
+            amount_allowed,
+        )
+        .await
+        .unwrap();
+
+    (
+        env.context,
+        test_pool_market,
+        test_pool,
+        test_pool_borrow_authority,
+        user,
+    )
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, _) = setup().await;
+    let amount_borrowed = test_pool.get_data(&mut context).await.total_amount_borrowed;
+
+    test_pool
+        .write_off_loss(
+            &mut context,
+            &test_pool_market,
+            &test_pool_borrow_authority,
+            10,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_pool.get_data(&mut context).await.total_amount_borrowed,
+        amount_borrowed - 10
+    );
+    assert_eq!(
+        test_pool_borrow_authority
+            .get_data(&mut context)
+            .await
+            .amount_borrowed,
+        amount_borrowed - 10
+    );
+}
+
+#[tokio::test]
+async fn fail_with_amount_exceeding_borrowed() {
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, _) = setup().await;
+    let amount_borrowed = test_pool.get_data(&mut context).await.total_amount_borrowed;
+
+    assert_eq!(
+        test_pool
+            .write_off_loss(
+                &mut context,
+                &test_pool_market,
+                &test_pool_borrow_authority,
+                amount_borrowed + 1,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::RepayAmountCheckFailed as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_with_invalid_borrow_authority() {
+    let (mut context, test_pool_market, test_pool, test_pool_borrow_authority, _) = setup().await;
+    let borrow_authority = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::write_off_loss(
+            &everlend_general_pool::id(),
+            &test_pool_market.keypair.pubkey(),
+            &test_pool.pool_pubkey,
+            &test_pool_borrow_authority.pool_borrow_authority_pubkey,
+            // Wrong borrow authority
+            &borrow_authority.pubkey(),
+            10,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &borrow_authority],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
@@ -0,0 +1,548 @@
+use solana_program::instruction::{AccountMeta, InstructionError};
+use solana_program_test::*;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+use everlend_depositor::TransitPDA;
+use everlend_income_pools::instruction;
+use everlend_registry::{
+    find_timelock_authority_program_address, instructions::UpdateRegistryData,
+};
+use everlend_utils::{find_program_address, EverlendError, PDA};
+
+use crate::utils::*;
+
+const SAFETY_FUND_AMOUNT: u64 = 100 * EXP;
+const MAX_DRAWDOWN_AMOUNT: u64 = 10 * EXP;
+const MAX_PERIOD_DRAWDOWN_AMOUNT: u64 = 15 * EXP;
+const DRAWDOWN_PERIOD: u64 = 100;
+const DELAY: u64 = 10;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRegistry,
+    TestIncomePoolMarket,
+    TestIncomePool,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+    Keypair,
+) {
+    let mut env = presetup().await;
+
+    let general_pool_market = TestGeneralPoolMarket::new();
+    general_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let test_income_pool_market = TestIncomePoolMarket::new();
+    test_income_pool_market
+        .init(&mut env.context, &general_pool_market)
+        .await
+        .unwrap();
+
+    env.registry
+        .update_registry(
+            &mut env.context,
+            UpdateRegistryData {
+                general_pool_market: Some(general_pool_market.keypair.pubkey()),
+                income_pool_market: Some(test_income_pool_market.keypair.pubkey()),
+                liquidity_oracle: None,
+                refresh_income_interval: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let proposer = Keypair::new();
+    transfer(&mut env.context, &proposer.pubkey(), 100_000_000)
+        .await
+        .unwrap();
+    env.registry
+        .set_timelock(&mut env.context, &proposer.pubkey(), DELAY)
+        .await
+        .unwrap();
+
+    let test_general_pool = TestGeneralPool::new(&general_pool_market, None);
+    test_general_pool
+        .create(&mut env.context, &general_pool_market)
+        .await
+        .unwrap();
+
+    let test_income_pool = TestIncomePool::new(&test_income_pool_market, None);
+    test_income_pool
+        .create(&mut env.context, &test_income_pool_market)
+        .await
+        .unwrap();
+
+    test_income_pool
+        .create_safety_fund_token_account(
+            &mut env.context,
+            &test_income_pool_market,
+            &test_general_pool,
+        )
+        .await
+        .unwrap();
+
+    mint_tokens(
+        &mut env.context,
+        &test_income_pool.token_mint_pubkey,
+        &test_income_pool.get_safety_fund_token_account(&test_income_pool_market),
+        SAFETY_FUND_AMOUNT,
+    )
+    .await
+    .unwrap();
+
+    test_income_pool
+        .set_safety_fund_limit(
+            &mut env.context,
+            &test_income_pool_market,
+            MAX_DRAWDOWN_AMOUNT,
+            MAX_PERIOD_DRAWDOWN_AMOUNT,
+            DRAWDOWN_PERIOD,
+        )
+        .await
+        .unwrap();
+
+    (
+        env.context,
+        env.registry,
+        test_income_pool_market,
+        test_income_pool,
+        general_pool_market,
+        test_general_pool,
+        proposer,
+    )
+}
+
+async fn setup_depositor(
+    context: &mut ProgramTestContext,
+    test_registry: &TestRegistry,
+    general_pool_market: &TestGeneralPoolMarket,
+    test_general_pool: &TestGeneralPool,
+) -> (TestDepositor, TestGeneralPoolBorrowAuthority) {
+    let test_depositor = TestDepositor::new();
+    test_depositor.init(context, test_registry).await.unwrap();
+
+    test_depositor
+        .create_transit(
+            context,
+            &test_general_pool.token_mint_pubkey,
+            Some("reserve".to_string()),
+        )
+        .await
+        .unwrap();
+
+    let (depositor_authority, _) = find_program_address(
+        &everlend_depositor::id(),
+        &test_depositor.depositor.pubkey(),
+    );
+    let general_pool_borrow_authority =
+        TestGeneralPoolBorrowAuthority::new(test_general_pool, depositor_authority);
+    general_pool_borrow_authority
+        .create(
+            context,
+            general_pool_market,
+            test_general_pool,
+            GENERAL_POOL_SHARE_ALLOWED,
+        )
+        .await
+        .unwrap();
+
+    (test_depositor, general_pool_borrow_authority)
+}
+
+#[tokio::test]
+async fn success() {
+    let (
+        mut context,
+        test_registry,
+        test_income_pool_market,
+        test_income_pool,
+        _,
+        test_general_pool,
+        proposer,
+    ) = setup().await;
+
+    test_income_pool
+        .drawdown_safety_fund(
+            &mut context,
+            &test_income_pool_market,
+            &test_general_pool,
+            &test_general_pool.token_account.pubkey(),
+            None,
+            &test_registry,
+            &proposer,
+            MAX_DRAWDOWN_AMOUNT,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &test_general_pool.token_account.pubkey()).await,
+        MAX_DRAWDOWN_AMOUNT
+    );
+    assert_eq!(
+        get_token_balance(
+            &mut context,
+            &test_income_pool.get_safety_fund_token_account(&test_income_pool_market)
+        )
+        .await,
+        SAFETY_FUND_AMOUNT - MAX_DRAWDOWN_AMOUNT
+    );
+
+    let ledger = test_income_pool
+        .get_safety_fund_ledger(&mut context, &test_income_pool_market)
+        .await;
+    assert_eq!(ledger.max_drawdown_amount, MAX_DRAWDOWN_AMOUNT);
+    assert_eq!(
+        ledger.max_period_drawdown_amount,
+        MAX_PERIOD_DRAWDOWN_AMOUNT
+    );
+    assert_eq!(ledger.drawdown_period, DRAWDOWN_PERIOD);
+    assert_eq!(ledger.period_drawdown_amount, MAX_DRAWDOWN_AMOUNT);
+    assert_eq!(ledger.total_drawdown_amount, MAX_DRAWDOWN_AMOUNT);
+    assert_eq!(ledger.drawdowns_count, 1);
+    assert_eq!(ledger.drawdowns[0].amount, MAX_DRAWDOWN_AMOUNT);
+    assert_eq!(
+        ledger.drawdowns[0].destination,
+        test_general_pool.token_account.pubkey()
+    );
+    assert_eq!(
+        ledger.drawdowns[0].authority,
+        find_timelock_authority_program_address(
+            &everlend_registry::id(),
+            &test_registry.keypair.pubkey()
+        )
+        .0
+    );
+}
+
+#[tokio::test]
+async fn success_to_depositor_transit() {
+    let (
+        mut context,
+        test_registry,
+        test_income_pool_market,
+        test_income_pool,
+        general_pool_market,
+        test_general_pool,
+        proposer,
+    ) = setup().await;
+
+    let (test_depositor, general_pool_borrow_authority) = setup_depositor(
+        &mut context,
+        &test_registry,
+        &general_pool_market,
+        &test_general_pool,
+    )
+    .await;
+    let (reserve_transit, _) = TransitPDA {
+        seed: "reserve",
+        depositor: test_depositor.depositor.pubkey(),
+        mint: test_general_pool.token_mint_pubkey,
+    }
+    .find_address(&everlend_depositor::id());
+
+    test_income_pool
+        .drawdown_safety_fund(
+            &mut context,
+            &test_income_pool_market,
+            &test_general_pool,
+            &reserve_transit,
+            Some((
+                &test_depositor.depositor.pubkey(),
+                &general_pool_borrow_authority.pool_borrow_authority_pubkey,
+            )),
+            &test_registry,
+            &proposer,
+            MAX_DRAWDOWN_AMOUNT,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &reserve_transit).await,
+        MAX_DRAWDOWN_AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn fail_with_invalid_destination() {
+    let (
+        mut context,
+        test_registry,
+        test_income_pool_market,
+        test_income_pool,
+        general_pool_market,
+        test_general_pool,
+        proposer,
+    ) = setup().await;
+
+    let (test_depositor, general_pool_borrow_authority) = setup_depositor(
+        &mut context,
+        &test_registry,
+        &general_pool_market,
+        &test_general_pool,
+    )
+    .await;
+
+    let destination = Keypair::new();
+    create_token_account(
+        &mut context,
+        &destination,
+        &test_income_pool.token_mint_pubkey,
+        &test_income_pool_market.manager.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        test_income_pool
+            .drawdown_safety_fund(
+                &mut context,
+                &test_income_pool_market,
+                &test_general_pool,
+                &destination.pubkey(),
+                Some((
+                    &test_depositor.depositor.pubkey(),
+                    &general_pool_borrow_authority.pool_borrow_authority_pubkey,
+                )),
+                &test_registry,
+                &proposer,
+                MAX_DRAWDOWN_AMOUNT,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_with_exceeded_period_limit() {
+    let (
+        mut context,
+        test_registry,
+        test_income_pool_market,
+        test_income_pool,
+        _,
+        test_general_pool,
+        proposer,
+    ) = setup().await;
+
+    let drawdown_amount = MAX_PERIOD_DRAWDOWN_AMOUNT - MAX_DRAWDOWN_AMOUNT;
+
+    test_income_pool
+        .drawdown_safety_fund(
+            &mut context,
+            &test_income_pool_market,
+            &test_general_pool,
+            &test_general_pool.token_account.pubkey(),
+            None,
+            &test_registry,
+            &proposer,
+            MAX_DRAWDOWN_AMOUNT,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_income_pool
+            .drawdown_safety_fund(
+                &mut context,
+                &test_income_pool_market,
+                &test_general_pool,
+                &test_general_pool.token_account.pubkey(),
+                None,
+                &test_registry,
+                &proposer,
+                drawdown_amount + 1,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::SafetyFundDrawdownLimitExceeded as u32)
+        )
+    );
+
+    test_income_pool
+        .drawdown_safety_fund(
+            &mut context,
+            &test_income_pool_market,
+            &test_general_pool,
+            &test_general_pool.token_account.pubkey(),
+            None,
+            &test_registry,
+            &proposer,
+            drawdown_amount,
+        )
+        .await
+        .unwrap();
+
+    // Limit is restored in the next period
+    let ledger = test_income_pool
+        .get_safety_fund_ledger(&mut context, &test_income_pool_market)
+        .await;
+    context
+        .warp_to_slot(ledger.period_started_at + DRAWDOWN_PERIOD)
+        .unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    test_income_pool
+        .drawdown_safety_fund(
+            &mut context,
+            &test_income_pool_market,
+            &test_general_pool,
+            &test_general_pool.token_account.pubkey(),
+            None,
+            &test_registry,
+            &proposer,
+            MAX_DRAWDOWN_AMOUNT,
+        )
+        .await
+        .unwrap();
+
+    let ledger = test_income_pool
+        .get_safety_fund_ledger(&mut context, &test_income_pool_market)
+        .await;
+    assert_eq!(ledger.period_drawdown_amount, MAX_DRAWDOWN_AMOUNT);
+    assert_eq!(
+        ledger.total_drawdown_amount,
+        MAX_PERIOD_DRAWDOWN_AMOUNT + MAX_DRAWDOWN_AMOUNT
+    );
+}
+
+#[tokio::test]
+async fn fail_with_exceeded_limit() {
+    let (
+        mut context,
+        test_registry,
+        test_income_pool_market,
+        test_income_pool,
+        _,
+        test_general_pool,
+        proposer,
+    ) = setup().await;
+
+    assert_eq!(
+        test_income_pool
+            .drawdown_safety_fund(
+                &mut context,
+                &test_income_pool_market,
+                &test_general_pool,
+                &test_general_pool.token_account.pubkey(),
+                None,
+                &test_registry,
+                &proposer,
+                MAX_DRAWDOWN_AMOUNT + 1,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::SafetyFundDrawdownLimitExceeded as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_without_timelock() {
+    let (
+        mut context,
+        test_registry,
+        test_income_pool_market,
+        test_income_pool,
+        _,
+        test_general_pool,
+        _,
+    ) = setup().await;
+
+    // Market manager can't sign instead of the timelock authority
+    let mut drawdown = instruction::drawdown_safety_fund(
+        &everlend_income_pools::id(),
+        &test_income_pool_market.keypair.pubkey(),
+        &test_income_pool.token_mint_pubkey,
+        &test_general_pool.pool_pubkey,
+        &test_general_pool.token_account.pubkey(),
+        None,
+        &test_registry.keypair.pubkey(),
+        MAX_DRAWDOWN_AMOUNT,
+    );
+    drawdown.accounts[7] =
+        AccountMeta::new_readonly(test_income_pool_market.manager.pubkey(), true);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[drawdown],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &test_income_pool_market.manager],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_with_invalid_depositor() {
+    let (
+        mut context,
+        test_registry,
+        test_income_pool_market,
+        test_income_pool,
+        general_pool_market,
+        test_general_pool,
+        proposer,
+    ) = setup().await;
+
+    let (test_depositor, general_pool_borrow_authority) = setup_depositor(
+        &mut context,
+        &test_registry,
+        &general_pool_market,
+        &test_general_pool,
+    )
+    .await;
+    let (reserve_transit, _) = TransitPDA {
+        seed: "reserve",
+        depositor: test_depositor.depositor.pubkey(),
+        mint: test_general_pool.token_mint_pubkey,
+    }
+    .find_address(&everlend_depositor::id());
+
+    // Depositor of another program
+    assert_eq!(
+        test_income_pool
+            .drawdown_safety_fund(
+                &mut context,
+                &test_income_pool_market,
+                &test_general_pool,
+                &reserve_transit,
+                Some((
+                    &general_pool_market.keypair.pubkey(),
+                    &general_pool_borrow_authority.pool_borrow_authority_pubkey,
+                )),
+                &test_registry,
+                &proposer,
+                MAX_DRAWDOWN_AMOUNT,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::InvalidAccountOwner as u32)
+        )
+    );
+}
@@ -27,6 +27,7 @@ async fn success() {
     let pool_market = test_income_pool_market.get_data(&mut env.context).await;
 
     assert_eq!(pool_market.account_type, AccountType::IncomePoolMarket);
+    assert_eq!(pool_market.registry, env.registry.keypair.pubkey());
 }
 
 #[tokio::test]
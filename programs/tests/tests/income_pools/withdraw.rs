@@ -16,6 +16,7 @@ async fn setup() -> (
     ProgramTestContext,
     TestIncomePoolMarket,
     TestIncomePool,
+    TestGeneralPoolMarket,
     TestGeneralPool,
 ) {
     let mut env = presetup().await;
@@ -63,13 +64,20 @@ async fn setup() -> (
         env.context,
         test_income_pool_market,
         test_income_pool,
+        general_pool_market,
         test_general_pool,
     )
 }
 
 #[tokio::test]
 async fn success() {
-    let (mut context, test_income_pool_market, test_income_pool, test_general_pool) = setup().await;
+    let (
+        mut context,
+        test_income_pool_market,
+        test_income_pool,
+        general_pool_market,
+        test_general_pool,
+    ) = setup().await;
 
     assert_eq!(
         get_token_balance(&mut context, &test_income_pool.token_account.pubkey()).await,
@@ -77,7 +85,12 @@ async fn success() {
     );
 
     test_income_pool
-        .withdraw(&mut context, &test_income_pool_market, &test_general_pool)
+        .withdraw(
+            &mut context,
+            &test_income_pool_market,
+            &general_pool_market,
+            &test_general_pool,
+        )
         .await
         .unwrap();
 
@@ -98,7 +111,13 @@ async fn success() {
 
 #[tokio::test]
 async fn fail_with_zero_balance() {
-    let (mut context, test_income_pool_market, test_income_pool, test_general_pool) = setup().await;
+    let (
+        mut context,
+        test_income_pool_market,
+        test_income_pool,
+        general_pool_market,
+        test_general_pool,
+    ) = setup().await;
 
     assert_eq!(
         get_token_balance(&mut context, &test_income_pool.token_account.pubkey()).await,
@@ -106,7 +125,12 @@ async fn fail_with_zero_balance() {
     );
 
     test_income_pool
-        .withdraw(&mut context, &test_income_pool_market, &test_general_pool)
+        .withdraw(
+            &mut context,
+            &test_income_pool_market,
+            &general_pool_market,
+            &test_general_pool,
+        )
         .await
         .unwrap();
 
@@ -119,7 +143,12 @@ async fn fail_with_zero_balance() {
 
     assert_eq!(
         test_income_pool
-            .withdraw(&mut context, &test_income_pool_market, &test_general_pool)
+            .withdraw(
+                &mut context,
+                &test_income_pool_market,
+                &general_pool_market,
+                &test_general_pool,
+            )
             .await
             .unwrap_err()
             .unwrap(),
@@ -132,8 +161,9 @@ async fn fail_with_zero_balance() {
 
 #[tokio::test]
 async fn fail_with_invalid_income_pool_market() {
-    let (mut context, _, test_income_pool, test_general_pool) = setup().await;
+    let (mut context, _, test_income_pool, general_pool_market, test_general_pool) = setup().await;
 
+    let registry = general_pool_market.get_data(&mut context).await.registry;
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_income_pools::id(),
@@ -141,6 +171,8 @@ async fn fail_with_invalid_income_pool_market() {
             &Pubkey::new_unique(),
             &test_income_pool.pool_pubkey,
             &test_income_pool.token_account.pubkey(),
+            &registry,
+            &general_pool_market.keypair.pubkey(),
             &test_general_pool.pool_pubkey,
             &test_general_pool.token_account.pubkey(),
         )],
@@ -165,8 +197,15 @@ async fn fail_with_invalid_income_pool_market() {
 
 #[tokio::test]
 async fn fail_with_invalid_income_pool() {
-    let (mut context, test_income_pool_market, test_income_pool, test_general_pool) = setup().await;
+    let (
+        mut context,
+        test_income_pool_market,
+        test_income_pool,
+        general_pool_market,
+        test_general_pool,
+    ) = setup().await;
 
+    let registry = general_pool_market.get_data(&mut context).await.registry;
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_income_pools::id(),
@@ -174,6 +213,8 @@ async fn fail_with_invalid_income_pool() {
             &test_income_pool_market.keypair.pubkey(),
             &Pubkey::new_unique(),
             &test_income_pool.token_account.pubkey(),
+            &registry,
+            &general_pool_market.keypair.pubkey(),
             &test_general_pool.pool_pubkey,
             &test_general_pool.token_account.pubkey(),
         )],
@@ -198,8 +239,15 @@ async fn fail_with_invalid_income_pool() {
 
 #[tokio::test]
 async fn fail_with_invalid_income_token_account() {
-    let (mut context, test_income_pool_market, test_income_pool, test_general_pool) = setup().await;
+    let (
+        mut context,
+        test_income_pool_market,
+        test_income_pool,
+        general_pool_market,
+        test_general_pool,
+    ) = setup().await;
 
+    let registry = general_pool_market.get_data(&mut context).await.registry;
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_income_pools::id(),
@@ -207,6 +255,8 @@ async fn fail_with_invalid_income_token_account() {
             &test_income_pool_market.keypair.pubkey(),
             &test_income_pool.pool_pubkey,
             &Pubkey::new_unique(),
+            &registry,
+            &general_pool_market.keypair.pubkey(),
             &test_general_pool.pool_pubkey,
             &test_general_pool.token_account.pubkey(),
         )],
@@ -228,8 +278,15 @@ async fn fail_with_invalid_income_token_account() {
 
 #[tokio::test]
 async fn fail_with_invalid_general_pool() {
-    let (mut context, test_income_pool_market, test_income_pool, test_general_pool) = setup().await;
+    let (
+        mut context,
+        test_income_pool_market,
+        test_income_pool,
+        general_pool_market,
+        test_general_pool,
+    ) = setup().await;
 
+    let registry = general_pool_market.get_data(&mut context).await.registry;
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_income_pools::id(),
@@ -237,6 +294,8 @@ async fn fail_with_invalid_general_pool() {
             &test_income_pool_market.keypair.pubkey(),
             &test_income_pool.pool_pubkey,
             &test_income_pool.token_account.pubkey(),
+            &registry,
+            &general_pool_market.keypair.pubkey(),
             &Pubkey::new_unique(),
             &test_general_pool.token_account.pubkey(),
         )],
@@ -261,8 +320,15 @@ async fn fail_with_invalid_general_pool() {
 
 #[tokio::test]
 async fn fail_with_invalid_general_pool_token_account() {
-    let (mut context, test_income_pool_market, test_income_pool, test_general_pool) = setup().await;
+    let (
+        mut context,
+        test_income_pool_market,
+        test_income_pool,
+        general_pool_market,
+        test_general_pool,
+    ) = setup().await;
 
+    let registry = general_pool_market.get_data(&mut context).await.registry;
     let tx = Transaction::new_signed_with_payer(
         &[instruction::withdraw(
             &everlend_income_pools::id(),
@@ -270,6 +336,8 @@ async fn fail_with_invalid_general_pool_token_account() {
             &test_income_pool_market.keypair.pubkey(),
             &test_income_pool.pool_pubkey,
             &test_income_pool.token_account.pubkey(),
+            &registry,
+            &general_pool_market.keypair.pubkey(),
             &test_general_pool.pool_pubkey,
             &Pubkey::new_unique(),
         )],
@@ -17,14 +17,19 @@ async fn success() {
         .unwrap();
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut context).await.unwrap();
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut context, &test_registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     context.warp_to_slot(WARP_SLOT).unwrap();
 
-    let mut distribution = DistributionArray::default();
+    let mut distribution: DistributionArray = vec![0; 2];
     distribution[0] = 100u64;
 
-    let test_token_oracle = TestTokenOracle::new(token_mint.pubkey(), distribution);
+    let test_token_oracle = TestTokenOracle::new(token_mint.pubkey(), distribution.clone());
     let authority = context.payer.pubkey();
 
     test_token_oracle
@@ -60,11 +65,16 @@ async fn fail_second_time_init() {
         .unwrap();
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut context).await.unwrap();
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut context, &test_registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     context.warp_to_slot(WARP_SLOT).unwrap();
 
-    let mut distribution = DistributionArray::default();
+    let mut distribution: DistributionArray = vec![0; 2];
     distribution[0] = 100u64;
 
     let test_token_oracle = TestTokenOracle::new(token_mint.pubkey(), distribution);
@@ -98,11 +108,16 @@ async fn fail_incorrect_max_distribution() {
         .unwrap();
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut context).await.unwrap();
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut context, &test_registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     context.warp_to_slot(WARP_SLOT).unwrap();
 
-    let mut distribution = DistributionArray::default();
+    let mut distribution: DistributionArray = vec![0; 2];
     distribution[0] = 1000000001u64;
 
     let test_token_oracle = TestTokenOracle::new(token_mint.pubkey(), distribution);
@@ -8,17 +8,28 @@ use solana_sdk::{signer::Signer, transaction::Transaction, transaction::Transact
 async fn success() {
     let mut context = program_test().start_with_context().await;
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut context).await.unwrap();
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut context, &test_registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     let liquidity_oracle = test_liquidity_oracle.get_data(&mut context).await;
     assert_eq!(liquidity_oracle.authority, context.payer.pubkey());
+    assert_eq!(liquidity_oracle.registry, test_registry.keypair.pubkey());
 }
 
 #[tokio::test]
 async fn fail_second_time_init() {
     let mut context = program_test().start_with_context().await;
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut context).await.unwrap();
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut context, &test_registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     context.warp_to_slot(3).unwrap();
 
@@ -27,6 +38,7 @@ async fn fail_second_time_init() {
             &everlend_liquidity_oracle::id(),
             &test_liquidity_oracle.keypair.pubkey(),
             &context.payer.pubkey(),
+            &test_registry.keypair.pubkey(),
         )],
         Some(&context.payer.pubkey()),
         &[&context.payer],
@@ -0,0 +1,99 @@
+use crate::utils::*;
+use everlend_liquidity_oracle::state::{AccountType, DeprecatedLiquidityOracle, LiquidityOracle};
+use solana_program::instruction::InstructionError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::TransactionError;
+
+async fn setup(
+    authority: Option<Pubkey>,
+) -> (ProgramTestContext, TestLiquidityOracle, TestRegistry) {
+    let mut context = program_test().start_with_context().await;
+    let test_liquidity_oracle = TestLiquidityOracle::new();
+
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+    let authority = authority.unwrap_or_else(|| context.payer.pubkey());
+
+    // Liquidity oracle created before the pending authority field
+    let mut data = vec![0u8; DeprecatedLiquidityOracle::LEN];
+    DeprecatedLiquidityOracle::pack(
+        DeprecatedLiquidityOracle {
+            account_type: AccountType::LiquidityOracle,
+            authority,
+        },
+        &mut data,
+    )
+    .unwrap();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    context.set_account(
+        &test_liquidity_oracle.keypair.pubkey(),
+        &AccountSharedData::from(Account {
+            lamports: rent.minimum_balance(DeprecatedLiquidityOracle::LEN),
+            data,
+            owner: everlend_liquidity_oracle::id(),
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+
+    (context, test_liquidity_oracle, test_registry)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_liquidity_oracle, test_registry) = setup(None).await;
+
+    test_liquidity_oracle
+        .migrate(&mut context, &test_registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let account = get_account(&mut context, &test_liquidity_oracle.keypair.pubkey()).await;
+    assert_eq!(account.data.len(), LiquidityOracle::LEN);
+
+    let liquidity_oracle = LiquidityOracle::unpack(&account.data).unwrap();
+    assert_eq!(liquidity_oracle.authority, context.payer.pubkey());
+    assert_eq!(liquidity_oracle.pending_authority, Pubkey::default());
+    assert_eq!(liquidity_oracle.registry, test_registry.keypair.pubkey());
+}
+
+#[tokio::test]
+async fn fail_with_wrong_authority() {
+    let (mut context, test_liquidity_oracle, test_registry) =
+        setup(Some(Pubkey::new_unique())).await;
+
+    assert_eq!(
+        test_liquidity_oracle
+            .migrate(&mut context, &test_registry.keypair.pubkey())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_second_time_migrate() {
+    let (mut context, test_liquidity_oracle, test_registry) = setup(None).await;
+
+    test_liquidity_oracle
+        .migrate(&mut context, &test_registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+
+    assert_eq!(
+        test_liquidity_oracle
+            .migrate(&mut context, &test_registry.keypair.pubkey())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidAccountData)
+    );
+}
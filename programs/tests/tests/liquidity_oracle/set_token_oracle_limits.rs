@@ -0,0 +1,295 @@
+use crate::utils::*;
+use everlend_liquidity_oracle::{instruction, state::DistributionArray};
+use everlend_registry::{instructions::UpdateRegistryData, state::Role};
+use everlend_utils::EverlendError;
+use solana_program::{clock::Slot, instruction::InstructionError};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+
+const WARP_SLOT: Slot = 3;
+const MAX_DISTRIBUTION_CHANGE: u64 = 200_000_000;
+const MIN_UPDATE_INTERVAL: Slot = 10;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestLiquidityOracle,
+    TestTokenOracle,
+    TestRegistry,
+) {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = Keypair::new();
+    let payer_pubkey = context.payer.pubkey();
+
+    create_mint(&mut context, &token_mint, &payer_pubkey)
+        .await
+        .unwrap();
+
+    let test_liquidity_oracle = TestLiquidityOracle::new();
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut context, &test_registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(WARP_SLOT).unwrap();
+
+    let distribution: DistributionArray = vec![500_000_000, 500_000_000];
+    let test_token_oracle = TestTokenOracle::new(token_mint.pubkey(), distribution);
+    test_token_oracle
+        .init(&mut context, &test_liquidity_oracle, payer_pubkey)
+        .await
+        .unwrap();
+
+    test_token_oracle
+        .set_limits(
+            &mut context,
+            &test_liquidity_oracle,
+            MAX_DISTRIBUTION_CHANGE,
+            MIN_UPDATE_INTERVAL,
+            false,
+        )
+        .await
+        .unwrap();
+
+    (
+        context,
+        test_liquidity_oracle,
+        test_token_oracle,
+        test_registry,
+    )
+}
+
+async fn set_limits_by_role(
+    context: &mut ProgramTestContext,
+    test_liquidity_oracle: &TestLiquidityOracle,
+    test_token_oracle: &TestTokenOracle,
+    test_registry: &TestRegistry,
+    holder: &Keypair,
+) -> BanksClientResult<()> {
+    let mut ix = instruction::set_token_oracle_limits(
+        &everlend_liquidity_oracle::id(),
+        &test_liquidity_oracle.keypair.pubkey(),
+        &holder.pubkey(),
+        &test_token_oracle.token_mint,
+        MAX_DISTRIBUTION_CHANGE,
+        MIN_UPDATE_INTERVAL,
+        true,
+    );
+    ix.accounts
+        .extend(everlend_registry::instruction::role_accounts(
+            &test_registry.keypair.pubkey(),
+            &holder.pubkey(),
+            Role::Risk,
+        ));
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, holder],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_liquidity_oracle, test_token_oracle, _) = setup().await;
+
+    let limits = test_token_oracle
+        .get_limits(&mut context, &test_liquidity_oracle)
+        .await;
+    assert_eq!(limits.max_distribution_change, MAX_DISTRIBUTION_CHANGE);
+    assert_eq!(limits.min_update_interval, MIN_UPDATE_INTERVAL);
+    assert!(!limits.frozen);
+
+    context
+        .warp_to_slot(WARP_SLOT + MIN_UPDATE_INTERVAL)
+        .unwrap();
+
+    let authority = context.payer.pubkey();
+    test_token_oracle
+        .update(
+            &mut context,
+            &test_liquidity_oracle,
+            authority,
+            vec![700_000_000, 300_000_000],
+        )
+        .await
+        .unwrap();
+
+    let token_oracle = test_token_oracle
+        .get_data(
+            &mut context,
+            &everlend_liquidity_oracle::id(),
+            &test_liquidity_oracle,
+        )
+        .await;
+    assert_eq!(
+        token_oracle.liquidity_distribution.values,
+        vec![700_000_000, 300_000_000]
+    );
+}
+
+#[tokio::test]
+async fn fail_update_too_early() {
+    let (mut context, test_liquidity_oracle, test_token_oracle, _) = setup().await;
+
+    context.warp_to_slot(WARP_SLOT + 2).unwrap();
+
+    let authority = context.payer.pubkey();
+    assert_eq!(
+        test_token_oracle
+            .update(
+                &mut context,
+                &test_liquidity_oracle,
+                authority,
+                vec![600_000_000, 400_000_000],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::LiquidityDistributionUpdateTooEarly as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_distribution_change_exceeded() {
+    let (mut context, test_liquidity_oracle, test_token_oracle, _) = setup().await;
+
+    context
+        .warp_to_slot(WARP_SLOT + MIN_UPDATE_INTERVAL)
+        .unwrap();
+
+    let authority = context.payer.pubkey();
+    assert_eq!(
+        test_token_oracle
+            .update(
+                &mut context,
+                &test_liquidity_oracle,
+                authority,
+                vec![1_000_000_000, 0],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::LiquidityDistributionChangeExceeded as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_frozen() {
+    let (mut context, test_liquidity_oracle, test_token_oracle, _) = setup().await;
+
+    context.warp_to_slot(WARP_SLOT + 2).unwrap();
+
+    test_token_oracle
+        .set_limits(
+            &mut context,
+            &test_liquidity_oracle,
+            MAX_DISTRIBUTION_CHANGE,
+            MIN_UPDATE_INTERVAL,
+            true,
+        )
+        .await
+        .unwrap();
+
+    context
+        .warp_to_slot(WARP_SLOT + MIN_UPDATE_INTERVAL)
+        .unwrap();
+
+    let authority = context.payer.pubkey();
+    assert_eq!(
+        test_token_oracle
+            .update(
+                &mut context,
+                &test_liquidity_oracle,
+                authority,
+                vec![600_000_000, 400_000_000],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::TokenOracleFrozen as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn success_with_role() {
+    let (mut context, test_liquidity_oracle, test_token_oracle, test_registry) = setup().await;
+    let risk = Keypair::new();
+
+    test_registry
+        .grant_role(&mut context, &risk.pubkey(), Role::Risk)
+        .await
+        .unwrap();
+
+    set_limits_by_role(
+        &mut context,
+        &test_liquidity_oracle,
+        &test_token_oracle,
+        &test_registry,
+        &risk,
+    )
+    .await
+    .unwrap();
+
+    let limits = test_token_oracle
+        .get_limits(&mut context, &test_liquidity_oracle)
+        .await;
+    assert!(limits.frozen);
+}
+
+#[tokio::test]
+async fn fail_with_role_of_other_registry() {
+    let (mut context, test_liquidity_oracle, test_token_oracle, _) = setup().await;
+    let risk = Keypair::new();
+
+    // Registry listing the oracle but not bound to it
+    let other_registry = TestRegistry::new();
+    other_registry.init(&mut context).await.unwrap();
+    other_registry
+        .update_registry(
+            &mut context,
+            UpdateRegistryData {
+                general_pool_market: None,
+                income_pool_market: None,
+                liquidity_oracle: Some(test_liquidity_oracle.keypair.pubkey()),
+                refresh_income_interval: None,
+            },
+        )
+        .await
+        .unwrap();
+    other_registry
+        .grant_role(&mut context, &risk.pubkey(), Role::Risk)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        set_limits_by_role(
+            &mut context,
+            &test_liquidity_oracle,
+            &test_token_oracle,
+            &other_registry,
+            &risk,
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
@@ -0,0 +1,342 @@
+use crate::utils::*;
+use everlend_liquidity_oracle::state::{DistributionArray, ORACLE_SIGNERS_UPDATE_DELAY};
+use everlend_utils::EverlendError;
+use solana_program::{clock::Slot, instruction::InstructionError};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
+
+const WARP_SLOT: Slot = 3;
+const WINDOW: Slot = 100;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestLiquidityOracle,
+    TestTokenOracle,
+    Vec<Keypair>,
+) {
+    let mut context = program_test().start_with_context().await;
+    let token_mint = Keypair::new();
+    let payer_pubkey = context.payer.pubkey();
+
+    create_mint(&mut context, &token_mint, &payer_pubkey)
+        .await
+        .unwrap();
+
+    let test_liquidity_oracle = TestLiquidityOracle::new();
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut context, &test_registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(WARP_SLOT).unwrap();
+
+    let distribution: DistributionArray = vec![100, 0];
+    let test_token_oracle = TestTokenOracle::new(token_mint.pubkey(), distribution);
+    test_token_oracle
+        .init(&mut context, &test_liquidity_oracle, payer_pubkey)
+        .await
+        .unwrap();
+
+    let signers = vec![Keypair::new(), Keypair::new(), Keypair::new()];
+    for signer in signers.iter() {
+        transfer(&mut context, &signer.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+    }
+
+    test_liquidity_oracle
+        .set_oracle_signers(
+            &mut context,
+            signers.iter().map(|signer| signer.pubkey()).collect(),
+            2,
+            WINDOW,
+        )
+        .await
+        .unwrap();
+
+    (context, test_liquidity_oracle, test_token_oracle, signers)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_liquidity_oracle, test_token_oracle, signers) = setup().await;
+
+    let oracle_signers = test_liquidity_oracle.get_oracle_signers(&mut context).await;
+    assert_eq!(oracle_signers.signers.len(), signers.len());
+    assert_eq!(oracle_signers.quorum, 2);
+
+    test_token_oracle
+        .submit(
+            &mut context,
+            &test_liquidity_oracle,
+            &signers[0],
+            vec![600_000_000, 400_000_000],
+        )
+        .await
+        .unwrap();
+
+    // Quorum is not reached
+    let token_oracle = test_token_oracle
+        .get_data(
+            &mut context,
+            &everlend_liquidity_oracle::id(),
+            &test_liquidity_oracle,
+        )
+        .await;
+    assert_eq!(token_oracle.liquidity_distribution.values, vec![100, 0]);
+
+    test_token_oracle
+        .submit(
+            &mut context,
+            &test_liquidity_oracle,
+            &signers[1],
+            vec![800_000_000, 200_000_000],
+        )
+        .await
+        .unwrap();
+
+    let token_oracle = test_token_oracle
+        .get_data(
+            &mut context,
+            &everlend_liquidity_oracle::id(),
+            &test_liquidity_oracle,
+        )
+        .await;
+    // Lower middle value of each market
+    assert_eq!(
+        token_oracle.liquidity_distribution.values,
+        vec![600_000_000, 200_000_000]
+    );
+
+    test_token_oracle
+        .submit(
+            &mut context,
+            &test_liquidity_oracle,
+            &signers[2],
+            vec![1_000_000_000, 0],
+        )
+        .await
+        .unwrap();
+
+    let token_oracle = test_token_oracle
+        .get_data(
+            &mut context,
+            &everlend_liquidity_oracle::id(),
+            &test_liquidity_oracle,
+        )
+        .await;
+    assert_eq!(
+        token_oracle.liquidity_distribution.values,
+        vec![800_000_000, 200_000_000]
+    );
+}
+
+#[tokio::test]
+async fn success_new_window() {
+    let (mut context, test_liquidity_oracle, test_token_oracle, signers) = setup().await;
+
+    test_token_oracle
+        .submit(
+            &mut context,
+            &test_liquidity_oracle,
+            &signers[0],
+            vec![600_000_000, 400_000_000],
+        )
+        .await
+        .unwrap();
+
+    context.warp_to_slot(WARP_SLOT + WINDOW).unwrap();
+
+    // Submission of the previous window is dropped
+    test_token_oracle
+        .submit(
+            &mut context,
+            &test_liquidity_oracle,
+            &signers[1],
+            vec![800_000_000, 200_000_000],
+        )
+        .await
+        .unwrap();
+
+    let token_oracle = test_token_oracle
+        .get_data(
+            &mut context,
+            &everlend_liquidity_oracle::id(),
+            &test_liquidity_oracle,
+        )
+        .await;
+    assert_eq!(token_oracle.liquidity_distribution.values, vec![100, 0]);
+}
+
+#[tokio::test]
+async fn fail_not_oracle_signer() {
+    let (mut context, test_liquidity_oracle, test_token_oracle, _) = setup().await;
+
+    let signer = Keypair::new();
+    transfer(&mut context, &signer.pubkey(), 1_000_000_000)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_token_oracle
+            .submit(
+                &mut context,
+                &test_liquidity_oracle,
+                &signer,
+                vec![600_000_000, 400_000_000],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::OracleSignerNotAllowed as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_authority_update_with_quorum() {
+    let (mut context, test_liquidity_oracle, test_token_oracle, _) = setup().await;
+
+    let authority = context.payer.pubkey();
+    assert_eq!(
+        test_token_oracle
+            .update(
+                &mut context,
+                &test_liquidity_oracle,
+                authority,
+                vec![1_000_000_000, 0],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::OracleQuorumEnabled as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_minority_quorum() {
+    let (mut context, test_liquidity_oracle, _, signers) = setup().await;
+
+    context.warp_to_slot(WARP_SLOT + 2).unwrap();
+
+    assert_eq!(
+        test_liquidity_oracle
+            .set_oracle_signers(
+                &mut context,
+                signers.iter().map(|signer| signer.pubkey()).collect(),
+                1,
+                WINDOW,
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn success_delayed_signers_update() {
+    let (mut context, test_liquidity_oracle, _, signers) = setup().await;
+
+    // Authority can't take over the quorum at once
+    let authority = context.payer.pubkey();
+    test_liquidity_oracle
+        .set_oracle_signers(&mut context, vec![authority], 1, WINDOW)
+        .await
+        .unwrap();
+
+    let oracle_signers = test_liquidity_oracle.get_oracle_signers(&mut context).await;
+    assert_eq!(oracle_signers.signers.len(), signers.len());
+    assert_eq!(oracle_signers.quorum, 2);
+    assert!(oracle_signers.pending.is_some());
+
+    assert_eq!(
+        test_liquidity_oracle
+            .apply_oracle_signers(&mut context)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::OracleSignersUpdateDelayed as u32)
+        )
+    );
+
+    context
+        .warp_to_slot(WARP_SLOT + ORACLE_SIGNERS_UPDATE_DELAY + 1)
+        .unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    test_liquidity_oracle
+        .apply_oracle_signers(&mut context)
+        .await
+        .unwrap();
+
+    let oracle_signers = test_liquidity_oracle.get_oracle_signers(&mut context).await;
+    assert_eq!(oracle_signers.signers, vec![authority]);
+    assert_eq!(oracle_signers.quorum, 1);
+    assert_eq!(oracle_signers.pending, None);
+}
+
+#[tokio::test]
+async fn success_with_limits_violation() {
+    let (mut context, test_liquidity_oracle, test_token_oracle, signers) = setup().await;
+
+    test_token_oracle
+        .set_limits(&mut context, &test_liquidity_oracle, 100_000_000, 0, false)
+        .await
+        .unwrap();
+
+    // Median exceeds the change limit, submissions are kept without the oracle update
+    for signer in signers.iter() {
+        test_token_oracle
+            .submit(
+                &mut context,
+                &test_liquidity_oracle,
+                signer,
+                vec![600_000_000, 400_000_000],
+            )
+            .await
+            .unwrap();
+    }
+
+    let token_oracle = test_token_oracle
+        .get_data(
+            &mut context,
+            &everlend_liquidity_oracle::id(),
+            &test_liquidity_oracle,
+        )
+        .await;
+    assert_eq!(token_oracle.liquidity_distribution.values, vec![100, 0]);
+
+    for signer in signers[..2].iter() {
+        test_token_oracle
+            .submit(
+                &mut context,
+                &test_liquidity_oracle,
+                signer,
+                vec![100_000_000, 0],
+            )
+            .await
+            .unwrap();
+    }
+
+    let token_oracle = test_token_oracle
+        .get_data(
+            &mut context,
+            &everlend_liquidity_oracle::id(),
+            &test_liquidity_oracle,
+        )
+        .await;
+    assert_eq!(
+        token_oracle.liquidity_distribution.values,
+        vec![100_000_000, 0]
+    );
+}
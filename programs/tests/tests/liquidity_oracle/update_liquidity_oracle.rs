@@ -1,13 +1,18 @@
 use crate::utils::*;
 use solana_program::{instruction::InstructionError, pubkey::Pubkey};
 use solana_program_test::*;
-use solana_sdk::{signer::Signer, transaction::TransactionError};
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::TransactionError};
 
 async fn setup() -> (ProgramTestContext, TestLiquidityOracle) {
     let mut context = program_test().start_with_context().await;
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut context).await.unwrap();
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut context, &test_registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     (context, test_liquidity_oracle)
 }
@@ -17,14 +22,55 @@ async fn success() {
     let (mut context, test_liquidity_oracle) = setup().await;
     context.warp_to_slot(3).unwrap();
 
-    let p_k = Pubkey::new_unique();
+    let new_authority = Keypair::new();
+    test_liquidity_oracle
+        .propose_authority(&mut context, &new_authority.pubkey())
+        .await
+        .unwrap();
+
+    let liquidity_oracle = test_liquidity_oracle.get_data(&mut context).await;
+    assert_eq!(liquidity_oracle.authority, context.payer.pubkey());
+    assert_eq!(liquidity_oracle.pending_authority, new_authority.pubkey());
+
     test_liquidity_oracle
-        .update(&mut context, &p_k)
+        .accept_authority(&mut context, &new_authority)
         .await
         .unwrap();
 
     let liquidity_oracle = test_liquidity_oracle.get_data(&mut context).await;
-    assert_eq!(liquidity_oracle.authority, p_k);
+    assert_eq!(liquidity_oracle.authority, new_authority.pubkey());
+    assert_eq!(liquidity_oracle.pending_authority, Pubkey::default());
+}
+
+#[tokio::test]
+async fn success_cancel_proposal() {
+    let (mut context, test_liquidity_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    let new_authority = Keypair::new();
+    test_liquidity_oracle
+        .propose_authority(&mut context, &new_authority.pubkey())
+        .await
+        .unwrap();
+
+    test_liquidity_oracle
+        .cancel_authority_proposal(&mut context)
+        .await
+        .unwrap();
+
+    let liquidity_oracle = test_liquidity_oracle.get_data(&mut context).await;
+    assert_eq!(liquidity_oracle.pending_authority, Pubkey::default());
+
+    context.warp_to_slot(5).unwrap();
+
+    assert_eq!(
+        test_liquidity_oracle
+            .accept_authority(&mut context, &new_authority)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
 }
 
 #[tokio::test]
@@ -32,9 +78,13 @@ async fn fail_wrong_liquidity_oracle_authority() {
     let (mut context, test_liquidity_oracle) = setup().await;
     context.warp_to_slot(3).unwrap();
 
-    let pb_k = Pubkey::new_unique();
+    let new_authority = Keypair::new();
     test_liquidity_oracle
-        .update(&mut context, &pb_k)
+        .propose_authority(&mut context, &new_authority.pubkey())
+        .await
+        .unwrap();
+    test_liquidity_oracle
+        .accept_authority(&mut context, &new_authority)
         .await
         .unwrap();
 
@@ -43,7 +93,27 @@ async fn fail_wrong_liquidity_oracle_authority() {
     let pb_k = context.payer.pubkey();
     assert_eq!(
         test_liquidity_oracle
-            .update(&mut context, &pb_k)
+            .propose_authority(&mut context, &pb_k)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_accept_by_not_proposed_authority() {
+    let (mut context, test_liquidity_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    test_liquidity_oracle
+        .propose_authority(&mut context, &Pubkey::new_unique())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_liquidity_oracle
+            .accept_authority(&mut context, &Keypair::new())
             .await
             .unwrap_err()
             .unwrap(),
@@ -18,14 +18,19 @@ async fn success() {
         .unwrap();
 
     let test_liquidity_oracle = TestLiquidityOracle::new();
-    test_liquidity_oracle.init(&mut context).await.unwrap();
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+    test_liquidity_oracle
+        .init(&mut context, &test_registry.keypair.pubkey())
+        .await
+        .unwrap();
 
     context.warp_to_slot(WARP_SLOT).unwrap();
 
-    let mut distribution = DistributionArray::default();
+    let mut distribution: DistributionArray = vec![0; 2];
     distribution[0] = 100u64;
 
-    let test_token_oracle = TestTokenOracle::new(token_mint.pubkey(), distribution);
+    let test_token_oracle = TestTokenOracle::new(token_mint.pubkey(), distribution.clone());
     let authority = context.payer.pubkey();
 
     test_token_oracle
@@ -43,7 +48,7 @@ async fn success() {
             &mut context,
             &test_liquidity_oracle,
             authority,
-            distribution,
+            distribution.clone(),
         )
         .await
         .unwrap();
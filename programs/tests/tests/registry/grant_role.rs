@@ -0,0 +1,255 @@
+use everlend_general_pool::{instruction, state::SetPoolConfigParams};
+use everlend_registry::state::{AccountType, Role};
+use everlend_utils::EverlendError;
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+use crate::utils::*;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRegistry,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+) {
+    let mut env = presetup().await;
+
+    let test_pool_market = TestGeneralPoolMarket::new();
+    test_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let test_pool = TestGeneralPool::new(&test_pool_market, None);
+    test_pool
+        .create(&mut env.context, &test_pool_market)
+        .await
+        .unwrap();
+
+    (env.context, env.registry, test_pool_market, test_pool)
+}
+
+async fn set_deposit_minimum(
+    context: &mut ProgramTestContext,
+    test_registry: &TestRegistry,
+    test_pool_market: &TestGeneralPoolMarket,
+    test_pool: &TestGeneralPool,
+    holder: &Keypair,
+    deposit_minimum: u64,
+) -> BanksClientResult<()> {
+    let mut ix = instruction::set_pool_config(
+        &everlend_general_pool::id(),
+        &test_pool_market.keypair.pubkey(),
+        &test_pool.pool_pubkey,
+        &holder.pubkey(),
+        SetPoolConfigParams {
+            deposit_minimum: Some(deposit_minimum),
+            withdraw_minimum: None,
+            performance_fee: None,
+            management_fee: None,
+            withdraw_delay: None,
+            exit_fee: None,
+            pool_deposit_cap: None,
+            user_deposit_cap: None,
+        },
+    );
+    ix.accounts
+        .extend(everlend_registry::instruction::role_accounts(
+            &test_registry.keypair.pubkey(),
+            &holder.pubkey(),
+            Role::Risk,
+        ));
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, holder],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_registry, test_pool_market, test_pool) = setup().await;
+    let risk = Keypair::new();
+
+    test_registry
+        .grant_role(&mut context, &risk.pubkey(), Role::Risk)
+        .await
+        .unwrap();
+
+    let registry_role = test_registry
+        .get_role(&mut context, &risk.pubkey(), Role::Risk)
+        .await;
+    assert_eq!(registry_role.account_type, AccountType::RegistryRole);
+    assert_eq!(registry_role.holder, risk.pubkey());
+    assert_eq!(registry_role.role, Role::Risk);
+
+    set_deposit_minimum(
+        &mut context,
+        &test_registry,
+        &test_pool_market,
+        &test_pool,
+        &risk,
+        100,
+    )
+    .await
+    .unwrap();
+
+    let pool_config = test_pool.get_pool_config(&mut context).await;
+    assert_eq!(pool_config.deposit_minimum, 100);
+}
+
+#[tokio::test]
+async fn fail_without_role() {
+    let (mut context, test_registry, test_pool_market, test_pool) = setup().await;
+    let risk = Keypair::new();
+
+    assert_eq!(
+        set_deposit_minimum(
+            &mut context,
+            &test_registry,
+            &test_pool_market,
+            &test_pool,
+            &risk,
+            100,
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::InvalidAccountOwner as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_with_other_role() {
+    let (mut context, test_registry, test_pool_market, test_pool) = setup().await;
+    let operator = Keypair::new();
+
+    test_registry
+        .grant_role(&mut context, &operator.pubkey(), Role::Operator)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        set_deposit_minimum(
+            &mut context,
+            &test_registry,
+            &test_pool_market,
+            &test_pool,
+            &operator,
+            100,
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::InvalidAccountOwner as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_with_timelock() {
+    let (mut context, test_registry, test_pool_market, test_pool) = setup().await;
+    let risk = Keypair::new();
+
+    test_registry
+        .grant_role(&mut context, &risk.pubkey(), Role::Risk)
+        .await
+        .unwrap();
+    test_registry
+        .set_timelock(&mut context, &Keypair::new().pubkey(), 100)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        set_deposit_minimum(
+            &mut context,
+            &test_registry,
+            &test_pool_market,
+            &test_pool,
+            &risk,
+            100,
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::TimelockedRole as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_grant_by_not_manager() {
+    let (mut context, test_registry, _, _) = setup().await;
+    let risk = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[everlend_registry::instruction::grant_role(
+            &everlend_registry::id(),
+            &test_registry.keypair.pubkey(),
+            &risk.pubkey(),
+            &risk.pubkey(),
+            Role::Admin,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &risk],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn success_revoke_role() {
+    let (mut context, test_registry, test_pool_market, test_pool) = setup().await;
+    let risk = Keypair::new();
+
+    test_registry
+        .grant_role(&mut context, &risk.pubkey(), Role::Risk)
+        .await
+        .unwrap();
+    test_registry
+        .revoke_role(&mut context, &risk.pubkey(), Role::Risk)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        set_deposit_minimum(
+            &mut context,
+            &test_registry,
+            &test_pool_market,
+            &test_pool,
+            &risk,
+            100,
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::InvalidAccountOwner as u32)
+        )
+    );
+}
@@ -0,0 +1,330 @@
+use everlend_general_pool::{instruction, state::SetPoolConfigParams};
+use everlend_registry::{
+    find_timelock_action_program_address, find_timelock_authority_program_address,
+    find_timelock_program_address,
+};
+use everlend_utils::EverlendError;
+use solana_program::{instruction::InstructionError, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+use crate::utils::*;
+
+const DELAY: u64 = 10;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRegistry,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+    Keypair,
+) {
+    let mut env = presetup().await;
+
+    let test_pool_market = TestGeneralPoolMarket::new();
+    test_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let test_pool = TestGeneralPool::new(&test_pool_market, None);
+    test_pool
+        .create(&mut env.context, &test_pool_market)
+        .await
+        .unwrap();
+
+    let proposer = Keypair::new();
+    transfer(&mut env.context, &proposer.pubkey(), 100_000_000)
+        .await
+        .unwrap();
+
+    env.registry
+        .set_timelock(&mut env.context, &proposer.pubkey(), DELAY)
+        .await
+        .unwrap();
+
+    (
+        env.context,
+        env.registry,
+        test_pool_market,
+        test_pool,
+        proposer,
+    )
+}
+
+async fn refresh_blockhash(context: &mut ProgramTestContext, slot: u64) {
+    context.warp_to_slot(slot).unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+}
+
+fn timelock_authority(test_registry: &TestRegistry) -> Pubkey {
+    find_timelock_authority_program_address(
+        &everlend_registry::id(),
+        &test_registry.keypair.pubkey(),
+    )
+    .0
+}
+
+async fn propose_timelock_manager(
+    context: &mut ProgramTestContext,
+    test_registry: &TestRegistry,
+    test_pool_market: &TestGeneralPoolMarket,
+) {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::propose_manager(
+            &everlend_general_pool::id(),
+            &test_pool_market.keypair.pubkey(),
+            &test_pool_market.manager.pubkey(),
+            &timelock_authority(test_registry),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &test_pool_market.manager],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await.unwrap();
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_registry, test_pool_market, _, proposer) = setup().await;
+    propose_timelock_manager(&mut context, &test_registry, &test_pool_market).await;
+
+    let action = instruction::accept_manager(
+        &everlend_general_pool::id(),
+        &test_pool_market.keypair.pubkey(),
+        &timelock_authority(&test_registry),
+    );
+
+    test_registry
+        .queue_action(&mut context, &proposer, 0, &action)
+        .await
+        .unwrap();
+
+    let timelock = test_registry.get_timelock(&mut context).await;
+    assert_eq!(timelock.action_count, 1);
+
+    let timelock_action = test_registry.get_timelock_action(&mut context, 0).await;
+    assert_eq!(timelock_action.proposer, proposer.pubkey());
+    assert_eq!(timelock_action.program_id, everlend_general_pool::id());
+    assert_eq!(timelock_action.instruction(), action);
+
+    assert_eq!(
+        test_registry
+            .execute_action(&mut context, &proposer.pubkey(), 0, &action)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::TimelockActionNotReady as u32)
+        )
+    );
+
+    refresh_blockhash(&mut context, timelock_action.execution_slot + 1).await;
+
+    test_registry
+        .execute_action(&mut context, &proposer.pubkey(), 0, &action)
+        .await
+        .unwrap();
+
+    let pool_market = test_pool_market.get_data(&mut context).await;
+    assert_eq!(pool_market.manager, timelock_authority(&test_registry));
+
+    // Executed action is closed
+    let (timelock, _) =
+        find_timelock_program_address(&everlend_registry::id(), &test_registry.keypair.pubkey());
+    let (timelock_action, _) =
+        find_timelock_action_program_address(&everlend_registry::id(), &timelock, 0);
+    assert!(context
+        .banks_client
+        .get_account(timelock_action)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn success_set_pool_config() {
+    let (mut context, test_registry, test_pool_market, test_pool, proposer) = setup().await;
+    propose_timelock_manager(&mut context, &test_registry, &test_pool_market).await;
+
+    let accept_manager = instruction::accept_manager(
+        &everlend_general_pool::id(),
+        &test_pool_market.keypair.pubkey(),
+        &timelock_authority(&test_registry),
+    );
+    test_registry
+        .queue_action(&mut context, &proposer, 0, &accept_manager)
+        .await
+        .unwrap();
+
+    let set_pool_config = instruction::set_pool_config(
+        &everlend_general_pool::id(),
+        &test_pool_market.keypair.pubkey(),
+        &test_pool.pool_pubkey,
+        &timelock_authority(&test_registry),
+        SetPoolConfigParams {
+            deposit_minimum: Some(100),
+            withdraw_minimum: None,
+            performance_fee: None,
+            management_fee: None,
+            withdraw_delay: None,
+            exit_fee: None,
+            pool_deposit_cap: None,
+            user_deposit_cap: None,
+        },
+    );
+    test_registry
+        .queue_action(&mut context, &proposer, 1, &set_pool_config)
+        .await
+        .unwrap();
+
+    let timelock_action = test_registry.get_timelock_action(&mut context, 1).await;
+    refresh_blockhash(&mut context, timelock_action.execution_slot + 1).await;
+
+    test_registry
+        .execute_action(&mut context, &proposer.pubkey(), 0, &accept_manager)
+        .await
+        .unwrap();
+    test_registry
+        .execute_action(&mut context, &proposer.pubkey(), 1, &set_pool_config)
+        .await
+        .unwrap();
+
+    let pool_config = test_pool.get_pool_config(&mut context).await;
+    assert_eq!(pool_config.deposit_minimum, 100);
+}
+
+#[tokio::test]
+async fn fail_execute_action_again() {
+    let (mut context, test_registry, test_pool_market, _, proposer) = setup().await;
+    propose_timelock_manager(&mut context, &test_registry, &test_pool_market).await;
+
+    let accept_manager = instruction::accept_manager(
+        &everlend_general_pool::id(),
+        &test_pool_market.keypair.pubkey(),
+        &timelock_authority(&test_registry),
+    );
+
+    // Action executing itself by the registry call
+    let action = everlend_registry::instruction::execute_action(
+        &everlend_registry::id(),
+        &test_registry.keypair.pubkey(),
+        &proposer.pubkey(),
+        0,
+        &accept_manager,
+    );
+    test_registry
+        .queue_action(&mut context, &proposer, 0, &action)
+        .await
+        .unwrap();
+
+    let timelock_action = test_registry.get_timelock_action(&mut context, 0).await;
+    refresh_blockhash(&mut context, timelock_action.execution_slot + 1).await;
+
+    // Action is closed before the call
+    assert_eq!(
+        test_registry
+            .execute_action(&mut context, &proposer.pubkey(), 0, &action)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::UninitializedAccount)
+    );
+
+    let pool_market = test_pool_market.get_data(&mut context).await;
+    assert_eq!(pool_market.manager, test_pool_market.manager.pubkey());
+}
+
+#[tokio::test]
+async fn success_cancel_action() {
+    let (mut context, test_registry, test_pool_market, _, proposer) = setup().await;
+    propose_timelock_manager(&mut context, &test_registry, &test_pool_market).await;
+
+    let action = instruction::accept_manager(
+        &everlend_general_pool::id(),
+        &test_pool_market.keypair.pubkey(),
+        &timelock_authority(&test_registry),
+    );
+
+    test_registry
+        .queue_action(&mut context, &proposer, 0, &action)
+        .await
+        .unwrap();
+    test_registry
+        .cancel_action(&mut context, &proposer, 0)
+        .await
+        .unwrap();
+
+    refresh_blockhash(&mut context, DELAY * 10).await;
+
+    // Cancelled action can't be executed
+    assert_eq!(
+        test_registry
+            .execute_action(&mut context, &proposer.pubkey(), 0, &action)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::InvalidAccountOwner as u32)
+        )
+    );
+
+    let pool_market = test_pool_market.get_data(&mut context).await;
+    assert_eq!(pool_market.manager, test_pool_market.manager.pubkey());
+}
+
+#[tokio::test]
+async fn fail_queue_by_not_proposer() {
+    let (mut context, test_registry, test_pool_market, _, _) = setup().await;
+
+    let action = instruction::accept_manager(
+        &everlend_general_pool::id(),
+        &test_pool_market.keypair.pubkey(),
+        &timelock_authority(&test_registry),
+    );
+
+    let not_proposer = Keypair::new();
+    transfer(&mut context, &not_proposer.pubkey(), 100_000_000)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_registry
+            .queue_action(&mut context, &not_proposer, 0, &action)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_queue_with_other_signer() {
+    let (mut context, test_registry, test_pool_market, _, proposer) = setup().await;
+
+    // Only the timelock authority can sign the action
+    let action = instruction::propose_manager(
+        &everlend_general_pool::id(),
+        &test_pool_market.keypair.pubkey(),
+        &test_pool_market.manager.pubkey(),
+        &proposer.pubkey(),
+    );
+
+    assert_eq!(
+        test_registry
+            .queue_action(&mut context, &proposer, 0, &action)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::InvalidTimelockAction as u32)
+        )
+    );
+}
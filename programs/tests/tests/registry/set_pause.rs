@@ -0,0 +1,216 @@
+use everlend_registry::state::{AccountType, PauseFlags};
+use everlend_utils::EverlendError;
+use solana_program::instruction::InstructionError;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+use crate::utils::*;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRegistry,
+    TestGeneralPoolMarket,
+    TestGeneralPool,
+    LiquidityProvider,
+    Pubkey,
+) {
+    let mut env = presetup().await;
+
+    let test_pool_market = TestGeneralPoolMarket::new();
+    test_pool_market
+        .init(&mut env.context, &env.registry.keypair.pubkey())
+        .await
+        .unwrap();
+
+    let test_pool = TestGeneralPool::new(&test_pool_market, None);
+    test_pool
+        .create(&mut env.context, &test_pool_market)
+        .await
+        .unwrap();
+
+    let user = add_liquidity_provider(
+        &mut env.context,
+        &test_pool.token_mint_pubkey,
+        &test_pool.pool_mint.pubkey(),
+        9999 * EXP,
+    )
+    .await
+    .unwrap();
+
+    let mining_acc = test_pool
+        .init_user_mining(&mut env.context, &test_pool_market, &user)
+        .await;
+
+    env.registry
+        .set_guardian(&mut env.context, &env.registry.manager.pubkey())
+        .await
+        .unwrap();
+
+    (
+        env.context,
+        env.registry,
+        test_pool_market,
+        test_pool,
+        user,
+        mining_acc,
+    )
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_registry, _, test_pool, _, _) = setup().await;
+
+    let flags = PauseFlags {
+        deposit: true,
+        borrow: true,
+        ..Default::default()
+    };
+
+    test_registry
+        .set_pause(
+            &mut context,
+            &test_registry.manager,
+            &test_pool.pool_pubkey,
+            flags,
+        )
+        .await
+        .unwrap();
+
+    let pause = test_registry
+        .get_pause(&mut context, &test_pool.pool_pubkey)
+        .await;
+
+    assert_eq!(pause.account_type, AccountType::Pause);
+    assert_eq!(pause.registry, test_registry.keypair.pubkey());
+    assert_eq!(pause.pool, test_pool.pool_pubkey);
+    assert_eq!(pause.flags, flags);
+}
+
+#[tokio::test]
+async fn success_unpause() {
+    let (mut context, test_registry, test_pool_market, test_pool, user, mining_acc) = setup().await;
+
+    test_registry
+        .set_pause(
+            &mut context,
+            &test_registry.manager,
+            &test_pool.pool_pubkey,
+            PauseFlags {
+                deposit: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+
+    test_registry
+        .set_pause(
+            &mut context,
+            &test_registry.manager,
+            &test_pool.pool_pubkey,
+            PauseFlags::default(),
+        )
+        .await
+        .unwrap();
+
+    test_pool
+        .deposit(&mut context, &test_pool_market, &user, mining_acc, 100)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        get_token_balance(&mut context, &user.pool_account).await,
+        100,
+    );
+}
+
+#[tokio::test]
+async fn fail_deposit_with_pool_pause() {
+    let (mut context, test_registry, test_pool_market, test_pool, user, mining_acc) = setup().await;
+
+    test_registry
+        .set_pause(
+            &mut context,
+            &test_registry.manager,
+            &test_pool.pool_pubkey,
+            PauseFlags {
+                deposit: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_pool
+            .deposit(&mut context, &test_pool_market, &user, mining_acc, 100)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::OperationPaused as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_deposit_with_global_pause() {
+    let (mut context, test_registry, test_pool_market, test_pool, user, mining_acc) = setup().await;
+
+    test_registry
+        .set_pause(
+            &mut context,
+            &test_registry.manager,
+            &Pubkey::default(),
+            PauseFlags {
+                deposit: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_pool
+            .deposit(&mut context, &test_pool_market, &user, mining_acc, 100)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::OperationPaused as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_with_invalid_guardian() {
+    let (mut context, test_registry, _, test_pool, _, _) = setup().await;
+
+    let guardian = Keypair::new();
+    transfer(&mut context, &guardian.pubkey(), 999999999)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_registry
+            .set_pause(
+                &mut context,
+                &guardian,
+                &test_pool.pool_pubkey,
+                PauseFlags {
+                    deposit: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
@@ -1,9 +1,12 @@
 use everlend_registry::{
-    instructions::{UpdateRegistryData, UpdateRegistryMarketsData},
-    state::{AccountType, DistributionPubkeys},
+    instructions::{AddRegistryMarketData, UpdateRegistryData, UpdateRegistryMarketData},
+    state::{AccountType, MarketStatus, RegistryMarket},
 };
+use everlend_utils::integrations::MoneyMarket;
 use solana_program::example_mocks::solana_sdk::signature::Keypair;
+use solana_program::instruction::InstructionError;
 use solana_program_test::*;
+use solana_sdk::transaction::TransactionError;
 
 use crate::utils::*;
 
@@ -14,14 +17,6 @@ async fn success() {
     let test_registry = TestRegistry::new();
     test_registry.init(&mut context).await.unwrap();
 
-    let mut mm_program_ids = DistributionPubkeys::default();
-    mm_program_ids[0] = Keypair::new().pubkey();
-    mm_program_ids[1] = Keypair::new().pubkey();
-
-    let mut collateral_program_ids = DistributionPubkeys::default();
-    collateral_program_ids[0] = Keypair::new().pubkey();
-    collateral_program_ids[1] = Keypair::new().pubkey();
-
     let data = UpdateRegistryData {
         general_pool_market: Some(Keypair::new().pubkey()),
         income_pool_market: Some(Keypair::new().pubkey()),
@@ -34,15 +29,25 @@ async fn success() {
         .await
         .unwrap();
 
-    let market_data = UpdateRegistryMarketsData {
-        money_markets: Some(mm_program_ids),
-        collateral_pool_markets: Some(collateral_program_ids),
-    };
+    let market_data = vec![
+        AddRegistryMarketData {
+            money_market: Keypair::new().pubkey(),
+            collateral_pool_market: Keypair::new().pubkey(),
+            adapter: MoneyMarket::PortFinance,
+        },
+        AddRegistryMarketData {
+            money_market: Keypair::new().pubkey(),
+            collateral_pool_market: Keypair::new().pubkey(),
+            adapter: MoneyMarket::Larix,
+        },
+    ];
 
-    test_registry
-        .update_registry_markets(&mut context, market_data.clone())
-        .await
-        .unwrap();
+    for data in market_data.iter() {
+        test_registry
+            .add_registry_market(&mut context, data.clone())
+            .await
+            .unwrap();
+    }
 
     let r = test_registry.get_data(&mut context).await;
     let rm = test_registry.get_registry_markets(&mut context).await;
@@ -59,9 +64,111 @@ async fn success() {
         data.refresh_income_interval.unwrap()
     );
 
-    assert_eq!(rm.money_markets, market_data.money_markets.unwrap());
+    assert_eq!(rm.markets.len(), market_data.len());
+    for (market, data) in rm.markets.iter().zip(market_data.iter()) {
+        assert_eq!(
+            *market,
+            RegistryMarket {
+                money_market: data.money_market,
+                collateral_pool_market: data.collateral_pool_market,
+                adapter: data.adapter,
+                status: MarketStatus::Active,
+            }
+        );
+    }
+}
+
+#[tokio::test]
+async fn success_update_registry_market() {
+    let mut context = program_test().start_with_context().await;
+
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+
+    let market_data = AddRegistryMarketData {
+        money_market: Keypair::new().pubkey(),
+        collateral_pool_market: Default::default(),
+        adapter: MoneyMarket::Tulip,
+    };
+
+    test_registry
+        .add_registry_market(&mut context, market_data.clone())
+        .await
+        .unwrap();
+
+    let collateral_pool_market = Keypair::new().pubkey();
+    test_registry
+        .update_registry_market(
+            &mut context,
+            0,
+            UpdateRegistryMarketData {
+                collateral_pool_market: Some(collateral_pool_market),
+                status: Some(MarketStatus::Paused),
+            },
+        )
+        .await
+        .unwrap();
+
+    let rm = test_registry.get_registry_markets(&mut context).await;
+    let market = rm.get(0).unwrap();
+
+    assert_eq!(market.money_market, market_data.money_market);
+    assert_eq!(market.collateral_pool_market, collateral_pool_market);
+    assert_eq!(market.adapter, market_data.adapter);
+    assert_eq!(market.status, MarketStatus::Paused);
+    assert!(!market.is_active());
+}
+
+#[tokio::test]
+async fn fail_add_duplicate_registry_market() {
+    let mut context = program_test().start_with_context().await;
+
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+
+    let market_data = AddRegistryMarketData {
+        money_market: Keypair::new().pubkey(),
+        collateral_pool_market: Default::default(),
+        adapter: MoneyMarket::PortFinance,
+    };
+
+    test_registry
+        .add_registry_market(&mut context, market_data.clone())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+
+    assert_eq!(
+        test_registry
+            .add_registry_market(&mut context, market_data)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_update_missing_registry_market() {
+    let mut context = program_test().start_with_context().await;
+
+    let test_registry = TestRegistry::new();
+    test_registry.init(&mut context).await.unwrap();
+
     assert_eq!(
-        rm.collateral_pool_markets,
-        market_data.collateral_pool_markets.unwrap()
+        test_registry
+            .update_registry_market(
+                &mut context,
+                0,
+                UpdateRegistryMarketData {
+                    collateral_pool_market: None,
+                    status: Some(MarketStatus::Retired),
+                },
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
     );
 }
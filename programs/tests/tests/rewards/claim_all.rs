@@ -0,0 +1,126 @@
+use crate::utils::*;
+use solana_program::instruction::InstructionError;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::TransactionError;
+
+async fn setup() -> (ProgramTestContext, TestRewards, Keypair, Pubkey, Pubkey) {
+    let mut env = presetup().await;
+    let owner = &env.context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut env.context, &mint, &owner).await.unwrap();
+
+    let test_reward_pool = TestRewards::new(Some(mint.pubkey()));
+    test_reward_pool
+        .initialize_pool(&mut env.context)
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let user_mining = test_reward_pool
+        .initialize_mining(&mut env.context, &user.pubkey())
+        .await;
+    test_reward_pool
+        .deposit_mining(&mut env.context, &user.pubkey(), &user_mining, 100)
+        .await
+        .unwrap();
+
+    let rewarder = Keypair::new();
+    create_token_account(&mut env.context, &rewarder, &mint.pubkey(), owner, 0)
+        .await
+        .unwrap();
+    mint_tokens(
+        &mut env.context,
+        &mint.pubkey(),
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    let fee_keypair = Keypair::new();
+    create_token_account(&mut env.context, &fee_keypair, &mint.pubkey(), owner, 0)
+        .await
+        .unwrap();
+
+    test_reward_pool
+        .add_vault(&mut env.context, &fee_keypair.pubkey())
+        .await;
+    test_reward_pool
+        .fill_vault(
+            &mut env.context,
+            &fee_keypair.pubkey(),
+            &rewarder.pubkey(),
+            1_000_000,
+        )
+        .await
+        .unwrap();
+
+    let user_reward = Keypair::new();
+    create_token_account(
+        &mut env.context,
+        &user_reward,
+        &mint.pubkey(),
+        &user.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    (
+        env.context,
+        test_reward_pool,
+        user,
+        user_mining,
+        user_reward.pubkey(),
+    )
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_rewards, user, user_mining, user_reward) = setup().await;
+
+    test_rewards
+        .claim_all(
+            &mut context,
+            &user,
+            &user_mining,
+            &[(test_rewards.token_mint_pubkey, user_reward)],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &user_reward).await, 980_000);
+
+    // Nothing left to claim
+    context.warp_to_slot(3).unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    test_rewards
+        .claim_all(
+            &mut context,
+            &user,
+            &user_mining,
+            &[(test_rewards.token_mint_pubkey, user_reward)],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &user_reward).await, 980_000);
+}
+
+#[tokio::test]
+async fn fail_with_missing_vault_accounts() {
+    let (mut context, test_rewards, user, user_mining, _) = setup().await;
+
+    assert_eq!(
+        test_rewards
+            .claim_all(&mut context, &user, &user_mining, &[])
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys)
+    );
+}
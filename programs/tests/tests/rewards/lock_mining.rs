@@ -0,0 +1,336 @@
+use crate::utils::*;
+use everlend_rewards::state::{Mining, LOCK_EPOCH_DURATION, MAX_LOCK_DURATION};
+use everlend_utils::EverlendError;
+use solana_program::clock::Slot;
+use solana_program::instruction::InstructionError;
+use solana_program::program_pack::Pack;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::TransactionError;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRewards,
+    Keypair,
+    Pubkey,
+    Keypair,
+    Pubkey,
+    Pubkey,
+) {
+    let mut env = presetup().await;
+    let owner = &env.context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut env.context, &mint, &owner).await.unwrap();
+
+    let test_reward_pool = TestRewards::new(Some(mint.pubkey()));
+    test_reward_pool
+        .initialize_pool(&mut env.context)
+        .await
+        .unwrap();
+
+    let user1 = Keypair::new();
+    let user_mining1 = test_reward_pool
+        .initialize_mining(&mut env.context, &user1.pubkey())
+        .await;
+    test_reward_pool
+        .deposit_mining(&mut env.context, &user1.pubkey(), &user_mining1, 100)
+        .await
+        .unwrap();
+
+    let user2 = Keypair::new();
+    let user_mining2 = test_reward_pool
+        .initialize_mining(&mut env.context, &user2.pubkey())
+        .await;
+    test_reward_pool
+        .deposit_mining(&mut env.context, &user2.pubkey(), &user_mining2, 100)
+        .await
+        .unwrap();
+
+    let rewarder = Keypair::new();
+    create_token_account(&mut env.context, &rewarder, &mint.pubkey(), owner, 0)
+        .await
+        .unwrap();
+    mint_tokens(
+        &mut env.context,
+        &mint.pubkey(),
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    let fee_keypair = Keypair::new();
+    create_token_account(
+        &mut env.context,
+        &fee_keypair,
+        &test_reward_pool.token_mint_pubkey,
+        owner,
+        0,
+    )
+    .await
+    .unwrap();
+
+    test_reward_pool
+        .add_vault(&mut env.context, &fee_keypair.pubkey())
+        .await;
+
+    (
+        env.context,
+        test_reward_pool,
+        user1,
+        user_mining1,
+        user2,
+        user_mining2,
+        rewarder.pubkey(),
+    )
+}
+
+async fn get_mining(context: &mut ProgramTestContext, mining: &Pubkey) -> Mining {
+    let mining_account = get_account(context, mining).await;
+    Mining::unpack(&mining_account.data).unwrap()
+}
+
+async fn warp(context: &mut ProgramTestContext, slot: Slot) {
+    context.warp_to_slot(slot).unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+}
+
+async fn claim_to_new_account(
+    context: &mut ProgramTestContext,
+    test_rewards: &TestRewards,
+    user: &Keypair,
+    mining: &Pubkey,
+) -> u64 {
+    let user_reward = Keypair::new();
+    create_token_account(
+        context,
+        &user_reward,
+        &test_rewards.token_mint_pubkey,
+        &user.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    test_rewards
+        .claim(context, user, mining, &user_reward.pubkey())
+        .await
+        .unwrap();
+
+    get_token_balance(context, &user_reward.pubkey()).await
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_rewards, user1, mining1, user2, mining2, rewarder) = setup().await;
+
+    test_rewards
+        .lock_mining(&mut context, &user1, &mining1, MAX_LOCK_DURATION / 2)
+        .await
+        .unwrap();
+
+    let mining = get_mining(&mut context, &mining1).await;
+    assert_eq!(mining.share, 100);
+    assert_eq!(mining.locked_share, 100);
+    assert_eq!(mining.lock_boost_bps, 5_000);
+
+    let reward_pool = test_rewards.get_reward_pool(&mut context).await;
+    assert_eq!(reward_pool.total_share, 250);
+
+    let fee = reward_pool.vaults[0].fee_account;
+    test_rewards
+        .fill_vault(&mut context, &fee, &rewarder, 1_000_000)
+        .await
+        .unwrap();
+
+    // 980_000 distributed by 150 to 100 weight
+    assert_eq!(
+        claim_to_new_account(&mut context, &test_rewards, &user1, &mining1).await,
+        588_000
+    );
+    assert_eq!(
+        claim_to_new_account(&mut context, &test_rewards, &user2, &mining2).await,
+        392_000
+    );
+}
+
+#[tokio::test]
+async fn success_after_lock_expired() {
+    let (mut context, test_rewards, user1, mining1, user2, mining2, rewarder) = setup().await;
+    let duration: Slot = 100;
+
+    test_rewards
+        .lock_mining(&mut context, &user1, &mining1, duration)
+        .await
+        .unwrap();
+
+    warp(&mut context, LOCK_EPOCH_DURATION + 10).await;
+
+    // Anyone releases the expired lock
+    test_rewards
+        .expire_lock(&mut context, &mining1)
+        .await
+        .unwrap();
+
+    let mining = get_mining(&mut context, &mining1).await;
+    assert_eq!(mining.share, 100);
+    assert_eq!(mining.locked_share, 0);
+    assert_eq!(mining.lock_end_slot, 0);
+
+    let reward_pool = test_rewards.get_reward_pool(&mut context).await;
+    assert_eq!(reward_pool.total_share, 200);
+
+    let fee = reward_pool.vaults[0].fee_account;
+    test_rewards
+        .fill_vault(&mut context, &fee, &rewarder, 1_000_000)
+        .await
+        .unwrap();
+
+    // 980_000 distributed equally without the boost
+    assert_eq!(
+        claim_to_new_account(&mut context, &test_rewards, &user1, &mining1).await,
+        490_000
+    );
+    assert_eq!(
+        claim_to_new_account(&mut context, &test_rewards, &user2, &mining2).await,
+        490_000
+    );
+
+    test_rewards
+        .withdraw_mining(&mut context, &user1.pubkey(), &mining1, 100)
+        .await
+        .unwrap();
+
+    let mining = get_mining(&mut context, &mining1).await;
+    assert_eq!(mining.share, 0);
+
+    let reward_pool = test_rewards.get_reward_pool(&mut context).await;
+    assert_eq!(reward_pool.total_share, 100);
+}
+
+#[tokio::test]
+async fn success_without_expire_lock() {
+    let (mut context, test_rewards, user1, mining1, user2, mining2, rewarder) = setup().await;
+    let lock_slot: Slot = LOCK_EPOCH_DURATION * 27 - 40_000_000;
+
+    test_rewards
+        .set_vault_emission(&mut context, &test_rewards.root_authority, 80_000_000)
+        .await
+        .unwrap();
+
+    warp(&mut context, lock_slot).await;
+    test_rewards
+        .lock_mining(&mut context, &user1, &mining1, MAX_LOCK_DURATION / 2)
+        .await
+        .unwrap();
+
+    let mining = get_mining(&mut context, &mining1).await;
+    assert_eq!(mining.lock_end_slot, lock_slot + 40_000_000);
+
+    let reward_pool = test_rewards.get_reward_pool(&mut context).await;
+    let fee = reward_pool.vaults[0].fee_account;
+    test_rewards
+        .fill_vault(&mut context, &fee, &rewarder, 1_000_000)
+        .await
+        .unwrap();
+
+    // The lock ends in the middle of the stream and nobody releases it
+    warp(&mut context, lock_slot + 80_000_010).await;
+
+    // 490_000 distributed by 150 to 100 weight and 490_000 equally
+    assert_eq!(
+        claim_to_new_account(&mut context, &test_rewards, &user2, &mining2).await,
+        441_000
+    );
+    assert_eq!(
+        claim_to_new_account(&mut context, &test_rewards, &user1, &mining1).await,
+        539_000
+    );
+
+    let reward_pool = test_rewards.get_reward_pool(&mut context).await;
+    assert_eq!(reward_pool.total_share, 200);
+    assert!(reward_pool.lock_expirations.is_empty());
+}
+
+#[tokio::test]
+async fn fail_expire_active_lock() {
+    let (mut context, test_rewards, user1, mining1, _, _, _) = setup().await;
+
+    test_rewards
+        .lock_mining(&mut context, &user1, &mining1, MAX_LOCK_DURATION)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_rewards
+            .expire_lock(&mut context, &mining1)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::RewardsShareLocked as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_with_locked_share() {
+    let (mut context, test_rewards, user1, mining1, _, _, _) = setup().await;
+
+    test_rewards
+        .lock_mining(&mut context, &user1, &mining1, MAX_LOCK_DURATION)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_rewards
+            .withdraw_mining(&mut context, &user1.pubkey(), &mining1, 1)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::RewardsShareLocked as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_with_shorter_lock() {
+    let (mut context, test_rewards, user1, mining1, _, _, _) = setup().await;
+
+    test_rewards
+        .lock_mining(&mut context, &user1, &mining1, LOCK_EPOCH_DURATION * 2)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_rewards
+            .lock_mining(&mut context, &user1, &mining1, 100)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(EverlendError::RewardsShareLocked as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_with_too_long_lock() {
+    let (mut context, test_rewards, user1, mining1, _, _, _) = setup().await;
+
+    assert_eq!(
+        test_rewards
+            .lock_mining(&mut context, &user1, &mining1, MAX_LOCK_DURATION + 1)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
@@ -0,0 +1,211 @@
+use crate::utils::*;
+use solana_program::clock::Slot;
+use solana_program::instruction::InstructionError;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::TransactionError;
+
+const EMISSION_DURATION: Slot = 100;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRewards,
+    Keypair,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+    Pubkey,
+) {
+    let mut env = presetup().await;
+    let owner = &env.context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut env.context, &mint, &owner).await.unwrap();
+
+    let test_reward_pool = TestRewards::new(Some(mint.pubkey()));
+    test_reward_pool
+        .initialize_pool(&mut env.context)
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let user_mining = test_reward_pool
+        .initialize_mining(&mut env.context, &user.pubkey())
+        .await;
+    test_reward_pool
+        .deposit_mining(&mut env.context, &user.pubkey(), &user_mining, 100)
+        .await
+        .unwrap();
+
+    let rewarder = Keypair::new();
+    create_token_account(&mut env.context, &rewarder, &mint.pubkey(), owner, 0)
+        .await
+        .unwrap();
+    mint_tokens(
+        &mut env.context,
+        &mint.pubkey(),
+        &rewarder.pubkey(),
+        2_000_000,
+    )
+    .await
+    .unwrap();
+
+    let fee_keypair = Keypair::new();
+    create_token_account(
+        &mut env.context,
+        &fee_keypair,
+        &test_reward_pool.token_mint_pubkey,
+        &user.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    test_reward_pool
+        .add_vault(&mut env.context, &fee_keypair.pubkey())
+        .await;
+
+    let user_reward = Keypair::new();
+    create_token_account(
+        &mut env.context,
+        &user_reward,
+        &test_reward_pool.token_mint_pubkey,
+        &user.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    (
+        env.context,
+        test_reward_pool,
+        user,
+        user_mining,
+        fee_keypair.pubkey(),
+        rewarder.pubkey(),
+        user_reward.pubkey(),
+    )
+}
+
+async fn warp(context: &mut ProgramTestContext, slot: Slot) {
+    context.warp_to_slot(slot).unwrap();
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_rewards, user, user_mining, fee, rewarder, user_reward) = setup().await;
+
+    test_rewards
+        .set_vault_emission(
+            &mut context,
+            &test_rewards.root_authority,
+            EMISSION_DURATION,
+        )
+        .await
+        .unwrap();
+
+    warp(&mut context, 10).await;
+    test_rewards
+        .fill_vault(&mut context, &fee, &rewarder, 1_000_000)
+        .await
+        .unwrap();
+
+    let reward_pool = test_rewards.get_reward_pool(&mut context).await;
+    assert_eq!(reward_pool.vaults[0].emission_start_slot, 10);
+    assert_eq!(
+        reward_pool.vaults[0].emission_end_slot,
+        10 + EMISSION_DURATION
+    );
+
+    // Half of the stream is emitted
+    warp(&mut context, 60).await;
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .unwrap();
+    assert_eq!(get_token_balance(&mut context, &user_reward).await, 490_000);
+
+    // Top up extends the stream
+    test_rewards
+        .fill_vault(&mut context, &fee, &rewarder, 1_000_000)
+        .await
+        .unwrap();
+
+    let reward_pool = test_rewards.get_reward_pool(&mut context).await;
+    assert_eq!(reward_pool.vaults[0].emission_start_slot, 10);
+    assert_eq!(
+        reward_pool.vaults[0].emission_end_slot,
+        60 + EMISSION_DURATION
+    );
+
+    warp(&mut context, 200).await;
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .unwrap();
+    assert_eq!(
+        get_token_balance(&mut context, &user_reward).await,
+        1_960_000
+    );
+}
+
+#[tokio::test]
+async fn success_with_idle_stream() {
+    let (mut context, test_rewards, user, user_mining, fee, rewarder, user_reward) = setup().await;
+
+    test_rewards
+        .set_vault_emission(
+            &mut context,
+            &test_rewards.root_authority,
+            EMISSION_DURATION,
+        )
+        .await
+        .unwrap();
+    test_rewards
+        .withdraw_mining(&mut context, &user.pubkey(), &user_mining, 100)
+        .await
+        .unwrap();
+
+    warp(&mut context, 10).await;
+    test_rewards
+        .fill_vault(&mut context, &fee, &rewarder, 1_000_000)
+        .await
+        .unwrap();
+
+    // Nothing is streamed without deposits
+    warp(&mut context, 60).await;
+    test_rewards
+        .deposit_mining(&mut context, &user.pubkey(), &user_mining, 100)
+        .await
+        .unwrap();
+
+    let reward_pool = test_rewards.get_reward_pool(&mut context).await;
+    assert_eq!(
+        reward_pool.vaults[0].emission_end_slot,
+        60 + EMISSION_DURATION
+    );
+
+    warp(&mut context, 200).await;
+    test_rewards
+        .claim(&mut context, &user, &user_mining, &user_reward)
+        .await
+        .unwrap();
+    assert_eq!(get_token_balance(&mut context, &user_reward).await, 980_000);
+}
+
+#[tokio::test]
+async fn fail_with_wrong_authority() {
+    let (mut context, test_rewards, _, _, _, _, _) = setup().await;
+
+    assert_eq!(
+        test_rewards
+            .set_vault_emission(&mut context, &Keypair::new(), EMISSION_DURATION)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
@@ -0,0 +1,129 @@
+use crate::utils::*;
+use everlend_rewards::state::DEFAULT_FEE_BPS;
+use solana_program::instruction::InstructionError;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::TransactionError;
+
+async fn setup() -> (ProgramTestContext, TestRewards, Pubkey, Pubkey) {
+    let mut env = presetup().await;
+    let owner = &env.context.payer.pubkey();
+
+    let mint = Keypair::new();
+    create_mint(&mut env.context, &mint, &owner).await.unwrap();
+
+    let test_reward_pool = TestRewards::new(Some(mint.pubkey()));
+    test_reward_pool
+        .initialize_pool(&mut env.context)
+        .await
+        .unwrap();
+
+    let user = Keypair::new();
+    let user_mining = test_reward_pool
+        .initialize_mining(&mut env.context, &user.pubkey())
+        .await;
+    test_reward_pool
+        .deposit_mining(&mut env.context, &user.pubkey(), &user_mining, 100)
+        .await
+        .unwrap();
+
+    let rewarder = Keypair::new();
+    create_token_account(&mut env.context, &rewarder, &mint.pubkey(), owner, 0)
+        .await
+        .unwrap();
+    mint_tokens(
+        &mut env.context,
+        &mint.pubkey(),
+        &rewarder.pubkey(),
+        1_000_000,
+    )
+    .await
+    .unwrap();
+
+    let fee_keypair = Keypair::new();
+    create_token_account(
+        &mut env.context,
+        &fee_keypair,
+        &test_reward_pool.token_mint_pubkey,
+        &user.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    test_reward_pool
+        .add_vault(&mut env.context, &fee_keypair.pubkey())
+        .await;
+
+    let new_fee_keypair = Keypair::new();
+    create_token_account(
+        &mut env.context,
+        &new_fee_keypair,
+        &test_reward_pool.token_mint_pubkey,
+        &user.pubkey(),
+        0,
+    )
+    .await
+    .unwrap();
+
+    (
+        env.context,
+        test_reward_pool,
+        new_fee_keypair.pubkey(),
+        rewarder.pubkey(),
+    )
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_rewards, new_fee, rewarder) = setup().await;
+
+    let reward_pool = test_rewards.get_reward_pool(&mut context).await;
+    assert_eq!(reward_pool.vaults[0].fee_bps, DEFAULT_FEE_BPS);
+
+    test_rewards
+        .set_vault_fee(&mut context, &new_fee, &test_rewards.root_authority, 500)
+        .await
+        .unwrap();
+
+    let reward_pool = test_rewards.get_reward_pool(&mut context).await;
+    assert_eq!(reward_pool.vaults[0].fee_bps, 500);
+    assert_eq!(reward_pool.vaults[0].fee_account, new_fee);
+
+    test_rewards
+        .fill_vault(&mut context, &new_fee, &rewarder, 1_000_000)
+        .await
+        .unwrap();
+
+    assert_eq!(get_token_balance(&mut context, &new_fee).await, 50_000);
+}
+
+#[tokio::test]
+async fn fail_with_fee_too_high() {
+    let (mut context, test_rewards, new_fee, _) = setup().await;
+
+    assert_eq!(
+        test_rewards
+            .set_vault_fee(&mut context, &new_fee, &test_rewards.root_authority, 1_001)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
+
+#[tokio::test]
+async fn fail_with_wrong_authority() {
+    let (mut context, test_rewards, new_fee, _) = setup().await;
+
+    assert_eq!(
+        test_rewards
+            .set_vault_fee(&mut context, &new_fee, &Keypair::new(), 500)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
@@ -3,6 +3,8 @@ mod utils;
 mod depositor {
     mod create_transit;
     mod deposit;
+    mod emergency_withdraw;
+    mod enable_market;
     mod init;
     mod reset_rebalancing;
     mod start_rebalancing;
@@ -22,25 +24,36 @@ mod collateral_pool {
 }
 
 mod general_pool {
+    mod accrue_fees;
     mod borrow;
     mod cancel_withdraw_request;
+    mod compound_rewards;
     mod create_pool;
     mod create_pool_borrow_authority;
     mod delete_pool_borrow_authority;
     mod deposit;
+    mod init_pool_borrow_shares;
     mod init_pool_market;
+    mod instant_withdraw;
+    mod migrate_pool_market;
+    mod process_withdrawal_request;
     mod repay;
+    mod resync_mining;
     mod transfer_deposit;
     mod update_manager;
     mod update_pool_borrow_authority;
     mod update_pool_config;
     mod withdraw;
     mod withdraw_request;
+    mod write_off_loss;
 }
 
 mod liquidity_oracle {
     mod create_token_oracle;
     mod init_liquidity_oracle;
+    mod migrate_liquidity_oracle;
+    mod set_token_oracle_limits;
+    mod submit_liquidity_distribution;
     mod update_liquidity_oracle;
     mod update_token_oracle;
 }
@@ -48,21 +61,29 @@ mod liquidity_oracle {
 mod income_pools {
     mod create_pool;
     mod deposit;
+    mod drawdown_safety_fund;
     mod init_pool_market;
     mod withdraw;
 }
 
 mod registry {
+    mod grant_role;
     mod init;
+    mod queue_action;
+    mod set_pause;
     mod update_registry;
 }
 
 mod rewards {
     mod add_vault;
     mod claim;
+    mod claim_all;
     mod deposit_mining;
     mod fill_vault;
     mod initialize_mining;
     mod initialize_pool;
+    mod lock_mining;
+    mod set_vault_emission;
+    mod set_vault_fee;
     mod withdraw_mining;
 }
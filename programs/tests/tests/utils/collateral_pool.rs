@@ -2,6 +2,7 @@ use super::{
     get_account, get_liquidity_mint, collateral_pool_borrow_authority::TestPoolBorrowAuthority,
     BanksClientResult, TestPoolMarket, TestPoolWithdrawAuthority,
 };
+use everlend_registry::find_pause_addresses;
 use everlend_utils::find_program_address;
 use everlend_collateral_pool::{find_pool_program_address, find_pool_withdraw_authority_program_address, instruction, state::Pool};
 use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
@@ -65,6 +66,13 @@ impl TestPool {
                     &self.token_account.pubkey(),
                     &test_pool_market.manager.pubkey(),
                 ),
+                instruction::init_pool_borrow_shares(
+                    &everlend_collateral_pool::id(),
+                    &test_pool_market.keypair.pubkey(),
+                    &self.pool_pubkey,
+                    &test_pool_market.manager.pubkey(),
+                    &[],
+                ),
             ],
             Some(&context.payer.pubkey()),
             &[
@@ -80,6 +88,7 @@ impl TestPool {
 
     pub fn deposit_accounts(
         &self,
+        registry: &Pubkey,
         test_pool_market: &TestPoolMarket) -> Vec<AccountMeta> {
 
         let (collateral_pool_market_authority, _) =
@@ -89,12 +98,15 @@ impl TestPool {
             &test_pool_market.keypair.pubkey(),
             &self.token_mint_pubkey,
         );
+        let (global_pause, pool_pause) = find_pause_addresses(registry, &collateral_pool);
 
         vec![
             AccountMeta::new_readonly(test_pool_market.keypair.pubkey(), false),
             AccountMeta::new_readonly(collateral_pool_market_authority, false),
             AccountMeta::new_readonly(collateral_pool, false),
             AccountMeta::new(self.token_account.pubkey(), false),
+            AccountMeta::new_readonly(global_pause, false),
+            AccountMeta::new_readonly(pool_pause, false),
             AccountMeta::new_readonly(everlend_collateral_pool::id(), false),
         ]
     }
@@ -103,6 +115,7 @@ impl TestPool {
         &self,
         context: &mut ProgramTestContext,
         test_pool_market: &TestPoolMarket,
+        registry: &Pubkey,
         user: &LiquidityProvider,
         amount: u64,
     ) -> BanksClientResult<()> {
@@ -111,6 +124,7 @@ impl TestPool {
                 &everlend_collateral_pool::id(),
                 &test_pool_market.keypair.pubkey(),
                 &self.pool_pubkey,
+                registry,
                 &user.token_account,
                 &self.token_account.pubkey(),
                 &user.owner.pubkey(),
@@ -126,6 +140,7 @@ impl TestPool {
 
     pub fn withdraw_accounts(
         &self,
+        registry: &Pubkey,
         test_pool_market: &TestPoolMarket,
         depositor: &TestDepositor,
     ) -> Vec<AccountMeta> {
@@ -145,12 +160,15 @@ impl TestPool {
             &collateral_pool,
             &depositor_authority,
         );
+        let (global_pause, pool_pause) = find_pause_addresses(registry, &collateral_pool);
 
             vec![
                 AccountMeta::new_readonly(test_pool_market.keypair.pubkey(), false),
                 AccountMeta::new_readonly(collateral_pool_market_authority, false),
                 AccountMeta::new_readonly(collateral_pool, false),
                 AccountMeta::new(self.token_account.pubkey(), false),
+                AccountMeta::new_readonly(global_pause, false),
+                AccountMeta::new_readonly(pool_pause, false),
                 AccountMeta::new_readonly(everlend_collateral_pool::id(), false),
                 AccountMeta::new_readonly(collateral_pool_withdraw_authority, false),
             ]
@@ -160,6 +178,7 @@ impl TestPool {
         &self,
         context: &mut ProgramTestContext,
         test_pool_market: &TestPoolMarket,
+        registry: &Pubkey,
         pool_withdraw_authority: &TestPoolWithdrawAuthority,
         withdraw_authority: Option<&Keypair>,
         user: &LiquidityProvider,
@@ -171,6 +190,7 @@ impl TestPool {
                 &everlend_collateral_pool::id(),
                 &test_pool_market.keypair.pubkey(),
                 &self.pool_pubkey,
+                registry,
                 &pool_withdraw_authority.pool_withdraw_authority_pubkey,
                 &user.token_account,
                 &self.token_account.pubkey(),
@@ -189,6 +209,7 @@ impl TestPool {
         &self,
         context: &mut ProgramTestContext,
         test_pool_market: &TestPoolMarket,
+        registry: &Pubkey,
         test_pool_borrow_authority: &TestPoolBorrowAuthority,
         borrow_authority: Option<&Keypair>,
         destination: &Pubkey,
@@ -201,6 +222,7 @@ impl TestPool {
                 &everlend_collateral_pool::id(),
                 &test_pool_market.keypair.pubkey(),
                 &self.pool_pubkey,
+                registry,
                 &test_pool_borrow_authority.pool_borrow_authority_pubkey,
                 destination,
                 &self.token_account.pubkey(),
@@ -229,6 +251,7 @@ impl TestPool {
                 &everlend_collateral_pool::id(),
                 &test_pool_market.keypair.pubkey(),
                 &self.pool_pubkey,
+                registry,
                 &test_pool_borrow_authority.pool_borrow_authority_pubkey,
                 &user.token_account,
                 &self.token_account.pubkey(),
@@ -1,6 +1,6 @@
 use super::{get_account, BanksClientResult};
 use everlend_collateral_pool::{instruction, state::PoolMarket};
-use solana_program::{program_pack::Pack, system_instruction};
+use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
 use solana_program_test::ProgramTestContext;
 use solana_sdk::{
     signature::{Keypair, Signer},
@@ -26,7 +26,11 @@ impl TestPoolMarket {
         PoolMarket::unpack_unchecked(&account.data).unwrap()
     }
 
-    pub async fn init(&self, context: &mut ProgramTestContext) -> BanksClientResult<()> {
+    pub async fn init(
+        &self,
+        context: &mut ProgramTestContext,
+        registry: &Pubkey,
+    ) -> BanksClientResult<()> {
         let rent = context.banks_client.get_rent().await.unwrap();
         let tx = Transaction::new_signed_with_payer(
             &[
@@ -47,6 +51,7 @@ impl TestPoolMarket {
                     &everlend_collateral_pool::id(),
                     &self.keypair.pubkey(),
                     &self.manager.pubkey(),
+                    registry,
                 ),
             ],
             Some(&context.payer.pubkey()),
@@ -4,13 +4,13 @@ use super::{
     TestRegistry,
 };
 use everlend_depositor::{
-    state::{Depositor, Rebalancing},
-    RebalancingPDA,
+    state::{Depositor, MarketLimit, Rebalancing, YieldDistribution},
+    RebalancingPDA, YieldDistributionPDA,
 };
 use everlend_liquidity_oracle::state::DistributionArray;
 use everlend_utils::integrations::{self, MoneyMarketPubkeys};
 use everlend_utils::PDA;
-use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
+use solana_program::{clock::Slot, program_pack::Pack, pubkey::Pubkey, system_instruction};
 use solana_program_test::ProgramTestContext;
 use solana_sdk::{
     compute_budget::ComputeBudgetInstruction,
@@ -48,6 +48,20 @@ impl TestDepositor {
         Rebalancing::unpack_unchecked(&account.data).unwrap()
     }
 
+    pub async fn get_yield_distribution_data(
+        &self,
+        context: &mut ProgramTestContext,
+        mint: &Pubkey,
+    ) -> YieldDistribution {
+        let (yield_distribution, _) = YieldDistributionPDA {
+            depositor: self.depositor.pubkey(),
+            mint: *mint,
+        }
+        .find_address(&everlend_depositor::id());
+        let account = get_account(context, &yield_distribution).await;
+        YieldDistribution::unpack_unchecked(&account.data).unwrap()
+    }
+
     pub async fn init(
         &self,
         context: &mut ProgramTestContext,
@@ -110,6 +124,41 @@ impl TestDepositor {
         refresh_income: bool,
         reserve_rates: DistributionArray,
     ) -> BanksClientResult<()> {
+        let mut start_rebalancing = everlend_depositor::instruction::start_rebalancing(
+            &everlend_depositor::id(),
+            &registry.keypair.pubkey(),
+            &self.depositor.pubkey(),
+            &general_pool.token_mint_pubkey,
+            &general_pool_market.keypair.pubkey(),
+            &general_pool.token_account.pubkey(),
+            &liquidity_oracle.keypair.pubkey(),
+            &context.payer.pubkey(),
+            refresh_income,
+        );
+
+        // Cover unsettled loss from the safety fund
+        let (rebalancing, _) = RebalancingPDA {
+            depositor: self.depositor.pubkey(),
+            mint: general_pool.token_mint_pubkey,
+        }
+        .find_address(&everlend_depositor::id());
+        let unsettled_loss = context
+            .banks_client
+            .get_account(rebalancing)
+            .await
+            .unwrap()
+            .and_then(|account| Rebalancing::unpack(&account.data).ok())
+            .map_or(0, |rebalancing| rebalancing.unsettled_loss);
+        if unsettled_loss > 0 {
+            let income_pool_market = registry.get_data(context).await.income_pool_market;
+            start_rebalancing.accounts.extend(
+                everlend_depositor::instruction::safety_fund_accounts(
+                    &income_pool_market,
+                    &general_pool.token_mint_pubkey,
+                ),
+            );
+        }
+
         let tx = Transaction::new_signed_with_payer(
             &[
                 everlend_liquidity_oracle::instruction::update_reserve_rates(
@@ -119,17 +168,7 @@ impl TestDepositor {
                     &general_pool.token_mint_pubkey,
                     reserve_rates,
                 ),
-                everlend_depositor::instruction::start_rebalancing(
-                    &everlend_depositor::id(),
-                    &registry.keypair.pubkey(),
-                    &self.depositor.pubkey(),
-                    &general_pool.token_mint_pubkey,
-                    &general_pool_market.keypair.pubkey(),
-                    &general_pool.token_account.pubkey(),
-                    &liquidity_oracle.keypair.pubkey(),
-                    &context.payer.pubkey(),
-                    refresh_income,
-                ),
+                start_rebalancing,
             ],
             Some(&context.payer.pubkey()),
             &[&context.payer],
@@ -167,6 +206,113 @@ impl TestDepositor {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn set_rebalancing_limits(
+        &self,
+        context: &mut ProgramTestContext,
+        registry: &TestRegistry,
+        liquidity_mint: &Pubkey,
+        limits: Vec<MarketLimit>,
+        reserve_rates_window: Slot,
+        max_reserve_rate_deviation: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_depositor::instruction::set_rebalancing_limits(
+                &everlend_depositor::id(),
+                &registry.keypair.pubkey(),
+                &self.depositor.pubkey(),
+                liquidity_mint,
+                &registry.manager.pubkey(),
+                limits,
+                reserve_rates_window,
+                max_reserve_rate_deviation,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &registry.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_keeper_config(
+        &self,
+        context: &mut ProgramTestContext,
+        registry: &TestRegistry,
+        liquidity_mint: &Pubkey,
+        permissionless_crank: bool,
+        keeper_reward_share: u64,
+        keeper_step_reward: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_depositor::instruction::set_keeper_config(
+                &everlend_depositor::id(),
+                &registry.keypair.pubkey(),
+                &self.depositor.pubkey(),
+                liquidity_mint,
+                &registry.manager.pubkey(),
+                permissionless_crank,
+                keeper_reward_share,
+                keeper_step_reward,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &registry.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_yield_optimization(
+        &self,
+        context: &mut ProgramTestContext,
+        registry: &TestRegistry,
+        liquidity_mint: &Pubkey,
+        yield_optimization: bool,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_depositor::instruction::set_yield_optimization(
+                &everlend_depositor::id(),
+                &registry.keypair.pubkey(),
+                &self.depositor.pubkey(),
+                liquidity_mint,
+                &registry.manager.pubkey(),
+                yield_optimization,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &registry.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn update_yield_distribution(
+        &self,
+        context: &mut ProgramTestContext,
+        registry: &TestRegistry,
+        general_pool_market: &TestGeneralPoolMarket,
+        general_pool: &TestGeneralPool,
+        reserves: &[(Pubkey, Pubkey)],
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_depositor::instruction::update_yield_distribution(
+                &everlend_depositor::id(),
+                &registry.keypair.pubkey(),
+                &self.depositor.pubkey(),
+                &general_pool.token_mint_pubkey,
+                &general_pool_market.keypair.pubkey(),
+                &general_pool.token_account.pubkey(),
+                &context.payer.pubkey(),
+                reserves,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn deposit(
         &self,
@@ -179,15 +325,18 @@ impl TestDepositor {
     ) -> BanksClientResult<()> {
         let liquidity_mint = get_liquidity_mint().1;
         let collateral_mint = mm_pool.token_mint_pubkey;
+        let general_pool_market = registry.get_data(context).await.general_pool_market;
 
         let deposit_accounts =
             integrations::deposit_accounts(money_market_program_id, money_market_pubkeys);
-        let deposit_collateral_storage_accounts = mm_pool.deposit_accounts(&mm_pool_market);
+        let deposit_collateral_storage_accounts =
+            mm_pool.deposit_accounts(&registry.keypair.pubkey(), &mm_pool_market);
 
         let tx = Transaction::new_signed_with_payer(
             &[everlend_depositor::instruction::deposit(
                 &everlend_depositor::id(),
                 &registry.keypair.pubkey(),
+                &general_pool_market,
                 &self.depositor.pubkey(),
                 &liquidity_mint,
                 &collateral_mint,
@@ -218,15 +367,18 @@ impl TestDepositor {
     ) -> BanksClientResult<()> {
         let collateral_mint = mm_pool.token_mint_pubkey;
         let liquidity_mint = get_liquidity_mint().1;
+        let general_pool_market = registry.get_data(context).await.general_pool_market;
 
         let withdraw_accounts =
             integrations::withdraw_accounts(money_market_program_id, money_market_pubkeys);
-        let collateral_storage_withdraw_accounts = mm_pool.withdraw_accounts(mm_pool_market, self);
+        let collateral_storage_withdraw_accounts =
+            mm_pool.withdraw_accounts(&registry.keypair.pubkey(), mm_pool_market, self);
 
         let tx = Transaction::new_signed_with_payer(
             &[everlend_depositor::instruction::withdraw(
                 &everlend_depositor::id(),
                 &registry.keypair.pubkey(),
+                &general_pool_market,
                 &self.depositor.pubkey(),
                 &income_pool_market.keypair.pubkey(),
                 &income_pool.token_account.pubkey(),
@@ -245,6 +397,77 @@ impl TestDepositor {
         context.banks_client.process_transaction(tx).await
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub async fn emergency_withdraw(
+        &self,
+        context: &mut ProgramTestContext,
+        registry: &TestRegistry,
+        income_pool_market: &TestIncomePoolMarket,
+        income_pool: &TestIncomePool,
+        mm_pool_market: &TestPoolMarket,
+        mm_pool: &TestPool,
+        money_market_program_id: &Pubkey,
+        money_market_pubkeys: &MoneyMarketPubkeys,
+        authority: &Keypair,
+        money_market_index: u8,
+    ) -> BanksClientResult<()> {
+        let collateral_mint = mm_pool.token_mint_pubkey;
+        let liquidity_mint = get_liquidity_mint().1;
+
+        let withdraw_accounts =
+            integrations::withdraw_accounts(money_market_program_id, money_market_pubkeys);
+        let collateral_storage_withdraw_accounts =
+            mm_pool.withdraw_accounts(&registry.keypair.pubkey(), mm_pool_market, self);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_depositor::instruction::emergency_withdraw(
+                &everlend_depositor::id(),
+                &registry.keypair.pubkey(),
+                &self.depositor.pubkey(),
+                &income_pool_market.keypair.pubkey(),
+                &income_pool.token_account.pubkey(),
+                &collateral_mint,
+                &liquidity_mint,
+                &authority.pubkey(),
+                money_market_program_id,
+                money_market_index,
+                withdraw_accounts,
+                collateral_storage_withdraw_accounts,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn enable_market(
+        &self,
+        context: &mut ProgramTestContext,
+        registry: &TestRegistry,
+        manager: &Keypair,
+        money_market_index: u8,
+    ) -> BanksClientResult<()> {
+        let liquidity_mint = get_liquidity_mint().1;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_depositor::instruction::enable_market(
+                &everlend_depositor::id(),
+                &registry.keypair.pubkey(),
+                &self.depositor.pubkey(),
+                &liquidity_mint,
+                &manager.pubkey(),
+                money_market_index,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn refresh_mm_incomes(
         &self,
@@ -262,7 +485,8 @@ impl TestDepositor {
 
         let withdraw_accounts =
             integrations::withdraw_accounts(money_market_program_id, money_market_pubkeys);
-        let collateral_storage_withdraw_accounts = mm_pool.withdraw_accounts(mm_pool_market, self);
+        let collateral_storage_withdraw_accounts =
+            mm_pool.withdraw_accounts(&registry.keypair.pubkey(), mm_pool_market, self);
 
         let bump_budget = ComputeBudgetInstruction::request_units(400_000u32, 0);
 
@@ -3,14 +3,14 @@ use super::{
     general_pool_borrow_authority::TestGeneralPoolBorrowAuthority, get_account, get_liquidity_mint,
     LiquidityProvider, TestGeneralPoolMarket, User,
 };
-use everlend_general_pool::find_pool_config_program_address;
 use everlend_general_pool::state::{
-    PoolConfig, SetPoolConfigParams, WithdrawalRequest, WithdrawalRequests,
+    PoolConfig, PoolFees, SetPoolConfigParams, WithdrawalRequest, WithdrawalRequests,
 };
+use everlend_general_pool::{find_pool_config_program_address, find_pool_fees_program_address};
 use everlend_general_pool::{
-    find_pool_program_address, find_transit_sol_unwrap_address,
-    find_withdrawal_request_program_address, find_withdrawal_requests_program_address, instruction,
-    state::Pool,
+    find_pool_program_address, find_queued_withdrawal_request_program_address,
+    find_transit_sol_unwrap_address, find_withdrawal_request_program_address,
+    find_withdrawal_requests_program_address, instruction, state::Pool,
 };
 use solana_program::{
     instruction::AccountMeta, program_pack::Pack, pubkey::Pubkey, system_instruction,
@@ -189,11 +189,14 @@ impl TestGeneralPool {
         mining_account: Pubkey,
         amount: u64,
     ) -> BanksClientResult<()> {
+        let registry = test_pool_market.get_data(context).await.registry;
+
         let tx = Transaction::new_signed_with_payer(
             &[instruction::deposit(
                 &everlend_general_pool::id(),
                 &test_pool_market.keypair.pubkey(),
                 &self.pool_pubkey,
+                &registry,
                 &user.token_account,
                 &user.pool_account,
                 &self.token_account.pubkey(),
@@ -239,6 +242,63 @@ impl TestGeneralPool {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn transfer_deposit_amount(
+        &self,
+        context: &mut ProgramTestContext,
+        user: &LiquidityProvider,
+        destination_user: &LiquidityProvider,
+        mining_account: Pubkey,
+        destination_mining_account: Pubkey,
+        amount: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::transfer_deposit_amount(
+                &everlend_general_pool::id(),
+                &self.pool_pubkey,
+                &user.pool_account,
+                &destination_user.pool_account,
+                &user.owner.pubkey(),
+                &destination_user.owner.pubkey(),
+                &self.mining_reward_pool,
+                &mining_account,
+                &destination_mining_account,
+                amount,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user.owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn resync_mining(
+        &self,
+        context: &mut ProgramTestContext,
+        test_pool_market: &TestGeneralPoolMarket,
+        user: &LiquidityProvider,
+        mining_account: Pubkey,
+        authority: &Keypair,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::resync_mining(
+                &everlend_general_pool::id(),
+                &test_pool_market.keypair.pubkey(),
+                &self.pool_pubkey,
+                &user.pool_account,
+                &user.owner.pubkey(),
+                &authority.pubkey(),
+                &self.mining_reward_pool,
+                &mining_account,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn withdraw(
         &self,
         context: &mut ProgramTestContext,
@@ -273,11 +333,14 @@ impl TestGeneralPool {
             destination = user.owner.pubkey();
         }
 
+        let registry = test_pool_market.get_data(context).await.registry;
+
         let tx = Transaction::new_signed_with_payer(
             &[instruction::withdraw(
                 &everlend_general_pool::id(),
                 &test_pool_market.keypair.pubkey(),
                 &self.pool_pubkey,
+                &registry,
                 &destination,
                 &self.token_account.pubkey(),
                 &self.token_mint_pubkey,
@@ -293,6 +356,44 @@ impl TestGeneralPool {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn compound_rewards(
+        &self,
+        context: &mut ProgramTestContext,
+        test_pool_market: &TestGeneralPoolMarket,
+        user: &LiquidityProvider,
+        mining_account: Pubkey,
+    ) -> BanksClientResult<()> {
+        let (vault, _) = everlend_rewards::find_vault_program_address(
+            &everlend_rewards::id(),
+            &self.mining_reward_pool,
+            &self.token_mint_pubkey,
+        );
+
+        let registry = test_pool_market.get_data(context).await.registry;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::compound_rewards(
+                &everlend_general_pool::id(),
+                &test_pool_market.keypair.pubkey(),
+                &self.pool_pubkey,
+                &registry,
+                &user.token_account,
+                &user.pool_account,
+                &self.token_account.pubkey(),
+                &self.pool_mint.pubkey(),
+                &user.pubkey(),
+                &self.mining_reward_pool,
+                &mining_account,
+                &[(self.token_mint_pubkey, vault, user.token_account)],
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user.owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn withdraw_request(
         &self,
         context: &mut ProgramTestContext,
@@ -306,11 +407,14 @@ impl TestGeneralPool {
             destination = user.owner.pubkey();
         };
 
+        let registry = test_pool_market.get_data(context).await.registry;
+
         let tx = Transaction::new_signed_with_payer(
             &[instruction::withdraw_request(
                 &everlend_general_pool::id(),
                 &test_pool_market.keypair.pubkey(),
                 &self.pool_pubkey,
+                &registry,
                 &user.pool_account,
                 &destination,
                 &self.token_account.pubkey(),
@@ -329,6 +433,40 @@ impl TestGeneralPool {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn instant_withdraw(
+        &self,
+        context: &mut ProgramTestContext,
+        test_pool_market: &TestGeneralPoolMarket,
+        user: &LiquidityProvider,
+        mining_acc: Pubkey,
+        collateral_amount: u64,
+    ) -> BanksClientResult<()> {
+        let registry = test_pool_market.get_data(context).await.registry;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::instant_withdraw(
+                &everlend_general_pool::id(),
+                &test_pool_market.keypair.pubkey(),
+                &self.pool_pubkey,
+                &registry,
+                &user.pool_account,
+                &user.token_account,
+                &self.token_account.pubkey(),
+                &self.token_mint_pubkey,
+                &self.pool_mint.pubkey(),
+                &user.pubkey(),
+                &self.mining_reward_pool,
+                &mining_acc,
+                collateral_amount,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user.owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn cancel_withdraw_request(
         &self,
         context: &mut ProgramTestContext,
@@ -354,6 +492,124 @@ impl TestGeneralPool {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn queue_withdraw_request(
+        &self,
+        context: &mut ProgramTestContext,
+        test_pool_market: &TestGeneralPoolMarket,
+        user: &LiquidityProvider,
+        mining_acc: Pubkey,
+        collateral_amount: u64,
+    ) -> BanksClientResult<()> {
+        let (_, withdrawal_requests) = self
+            .get_withdrawal_requests(context, test_pool_market)
+            .await;
+
+        let registry = test_pool_market.get_data(context).await.registry;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::queue_withdraw_request(
+                &everlend_general_pool::id(),
+                &test_pool_market.keypair.pubkey(),
+                &self.pool_pubkey,
+                &registry,
+                &user.pool_account,
+                &user.token_account,
+                &self.token_account.pubkey(),
+                &self.token_mint_pubkey,
+                &self.pool_mint.pubkey(),
+                &user.pubkey(),
+                &self.mining_reward_pool,
+                &mining_acc,
+                withdrawal_requests.next_index,
+                collateral_amount,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &user.owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn process_withdrawal_request(
+        &self,
+        context: &mut ProgramTestContext,
+        test_pool_market: &TestGeneralPoolMarket,
+        user: &LiquidityProvider,
+    ) -> BanksClientResult<()> {
+        let (_, withdrawal_requests) = self
+            .get_withdrawal_requests(context, test_pool_market)
+            .await;
+
+        let registry = test_pool_market.get_data(context).await.registry;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::process_withdrawal_request(
+                &everlend_general_pool::id(),
+                &test_pool_market.keypair.pubkey(),
+                &self.pool_pubkey,
+                &registry,
+                &user.token_account,
+                &self.token_account.pubkey(),
+                &self.token_mint_pubkey,
+                &self.pool_mint.pubkey(),
+                &user.pubkey(),
+                withdrawal_requests.head_index,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn cancel_queued_withdraw_request(
+        &self,
+        context: &mut ProgramTestContext,
+        test_pool_market: &TestGeneralPoolMarket,
+        user: &LiquidityProvider,
+        index: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::cancel_queued_withdraw_request(
+                &everlend_general_pool::id(),
+                &test_pool_market.keypair.pubkey(),
+                &self.pool_pubkey,
+                &user.pool_account,
+                &self.token_mint_pubkey,
+                &self.pool_mint.pubkey(),
+                &test_pool_market.manager.pubkey(),
+                &user.owner.pubkey(),
+                index,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &test_pool_market.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn get_queued_withdrawal_request(
+        &self,
+        context: &mut ProgramTestContext,
+        withdrawal_requests: &Pubkey,
+        index: u64,
+    ) -> WithdrawalRequest {
+        let (withdrawal_request, _) = find_queued_withdrawal_request_program_address(
+            &everlend_general_pool::id(),
+            withdrawal_requests,
+            index,
+        );
+
+        context
+            .banks_client
+            .get_account_data_with_borsh::<WithdrawalRequest>(withdrawal_request)
+            .await
+            .unwrap()
+    }
+
     pub async fn borrow(
         &self,
         context: &mut ProgramTestContext,
@@ -365,11 +621,14 @@ impl TestGeneralPool {
     ) -> BanksClientResult<()> {
         let borrow_authority = borrow_authority.unwrap_or(&context.payer);
 
+        let registry = test_pool_market.get_data(context).await.registry;
+
         let tx = Transaction::new_signed_with_payer(
             &[instruction::borrow(
                 &everlend_general_pool::id(),
                 &test_pool_market.keypair.pubkey(),
                 &self.pool_pubkey,
+                &registry,
                 &test_pool_borrow_authority.pool_borrow_authority_pubkey,
                 destination,
                 &self.token_account.pubkey(),
@@ -413,6 +672,30 @@ impl TestGeneralPool {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn write_off_loss(
+        &self,
+        context: &mut ProgramTestContext,
+        test_pool_market: &TestGeneralPoolMarket,
+        test_pool_borrow_authority: &TestGeneralPoolBorrowAuthority,
+        amount: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::write_off_loss(
+                &everlend_general_pool::id(),
+                &test_pool_market.keypair.pubkey(),
+                &self.pool_pubkey,
+                &test_pool_borrow_authority.pool_borrow_authority_pubkey,
+                &context.payer.pubkey(),
+                amount,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn init_user_mining(
         &self,
         context: &mut ProgramTestContext,
@@ -445,47 +728,61 @@ impl TestGeneralPool {
         mining_account
     }
 
-    pub async fn migrate_withdraw_requests_account(
+    pub async fn set_pool_config(
         &self,
         context: &mut ProgramTestContext,
-        test_pool_market: &TestGeneralPoolMarket,
+        pool_market: &TestGeneralPoolMarket,
+        params: SetPoolConfigParams,
     ) -> BanksClientResult<()> {
         let tx = Transaction::new_signed_with_payer(
-            &[instruction::migrate_instruction(
+            &[instruction::set_pool_config(
                 &everlend_general_pool::id(),
+                &pool_market.keypair.pubkey(),
+                &self.pool_pubkey,
+                &pool_market.manager.pubkey(),
+                params,
             )],
             Some(&context.payer.pubkey()),
-            &[&context.payer, &test_pool_market.manager],
+            &[&context.payer, &pool_market.manager],
             context.last_blockhash,
         );
 
         context.banks_client.process_transaction(tx).await
     }
 
-    pub async fn set_pool_config(
+    pub async fn get_pool_config(&self, context: &mut ProgramTestContext) -> PoolConfig {
+        let account = get_account(context, &self.pool_config_pubkey).await;
+        PoolConfig::unpack_unchecked(&account.data).unwrap()
+    }
+
+    pub async fn get_pool_fees(&self, context: &mut ProgramTestContext) -> PoolFees {
+        let (pool_fees, _) =
+            find_pool_fees_program_address(&everlend_general_pool::id(), &self.pool_pubkey);
+        let account = get_account(context, &pool_fees).await;
+        PoolFees::unpack_unchecked(&account.data).unwrap()
+    }
+
+    pub async fn accrue_fees(
         &self,
         context: &mut ProgramTestContext,
         pool_market: &TestGeneralPoolMarket,
-        params: SetPoolConfigParams,
+        treasury_account: &Pubkey,
     ) -> BanksClientResult<()> {
         let tx = Transaction::new_signed_with_payer(
-            &[instruction::set_pool_config(
+            &[instruction::accrue_fees(
                 &everlend_general_pool::id(),
                 &pool_market.keypair.pubkey(),
                 &self.pool_pubkey,
-                &pool_market.manager.pubkey(),
-                params,
+                &self.pool_mint.pubkey(),
+                &self.token_account.pubkey(),
+                treasury_account,
+                &context.payer.pubkey(),
             )],
             Some(&context.payer.pubkey()),
-            &[&context.payer, &pool_market.manager],
+            &[&context.payer],
             context.last_blockhash,
         );
 
         context.banks_client.process_transaction(tx).await
     }
-
-    pub async fn get_pool_config(&self, context: &mut ProgramTestContext) -> PoolConfig {
-        let account = get_account(context, &self.pool_config_pubkey).await;
-        PoolConfig::unpack_unchecked(&account.data).unwrap()
-    }
 }
@@ -1,5 +1,8 @@
 use super::{get_account, BanksClientResult};
-use everlend_general_pool::{instruction, state::PoolMarket};
+use everlend_general_pool::{
+    find_fee_schedule_program_address, instruction,
+    state::{FeeSchedule, PoolMarket},
+};
 use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
 use solana_program_test::ProgramTestContext;
 use solana_sdk::{
@@ -61,4 +64,50 @@ impl TestGeneralPoolMarket {
 
         context.banks_client.process_transaction(tx).await
     }
+
+    pub async fn migrate(&self, context: &mut ProgramTestContext) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::migrate_instruction(
+                &everlend_general_pool::id(),
+                &self.keypair.pubkey(),
+                &self.manager.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn get_fee_schedule(&self, context: &mut ProgramTestContext) -> FeeSchedule {
+        let (fee_schedule, _) =
+            find_fee_schedule_program_address(&everlend_general_pool::id(), &self.keypair.pubkey());
+        let account = get_account(context, &fee_schedule).await;
+        FeeSchedule::unpack_unchecked(&account.data).unwrap()
+    }
+
+    pub async fn set_fee_schedule(
+        &self,
+        context: &mut ProgramTestContext,
+        treasury: &Pubkey,
+        performance_fee: u16,
+        management_fee: u16,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_fee_schedule(
+                &everlend_general_pool::id(),
+                &self.keypair.pubkey(),
+                &self.manager.pubkey(),
+                treasury,
+                performance_fee,
+                management_fee,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
 }
@@ -1,10 +1,11 @@
 use super::{
-    get_account, get_liquidity_mint, BanksClientResult, TestGeneralPool, TestIncomePoolMarket,
-    TokenHolder, User,
+    get_account, get_liquidity_mint, BanksClientResult, TestGeneralPool, TestGeneralPoolMarket,
+    TestIncomePoolMarket, TestRegistry, TokenHolder, User,
 };
 use everlend_income_pools::{
-    find_pool_program_address, find_safety_fund_token_account_address, instruction,
-    state::IncomePool,
+    find_pool_program_address, find_safety_fund_ledger_address,
+    find_safety_fund_token_account_address, instruction,
+    state::{IncomePool, SafetyFundLedger},
 };
 use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
 use solana_program_test::ProgramTestContext;
@@ -121,8 +122,10 @@ impl TestIncomePool {
         &self,
         context: &mut ProgramTestContext,
         test_income_pool_market: &TestIncomePoolMarket,
+        general_pool_market: &TestGeneralPoolMarket,
         general_pool: &TestGeneralPool,
     ) -> BanksClientResult<()> {
+        let registry = general_pool_market.get_data(context).await.registry;
         let tx = Transaction::new_signed_with_payer(
             &[instruction::withdraw(
                 &everlend_income_pools::id(),
@@ -130,6 +133,8 @@ impl TestIncomePool {
                 &test_income_pool_market.keypair.pubkey(),
                 &self.pool_pubkey,
                 &self.token_account.pubkey(),
+                &registry,
+                &general_pool_market.keypair.pubkey(),
                 &general_pool.pool_pubkey,
                 &general_pool.token_account.pubkey(),
             )],
@@ -162,4 +167,84 @@ impl TestIncomePool {
 
         context.banks_client.process_transaction(tx).await
     }
+
+    pub async fn get_safety_fund_ledger(
+        &self,
+        context: &mut ProgramTestContext,
+        test_income_pool_market: &TestIncomePoolMarket,
+    ) -> SafetyFundLedger {
+        let (safety_fund_ledger, _) = find_safety_fund_ledger_address(
+            &everlend_income_pools::id(),
+            &test_income_pool_market.keypair.pubkey(),
+            &self.token_mint_pubkey,
+        );
+        let account = get_account(context, &safety_fund_ledger).await;
+        SafetyFundLedger::unpack(&account.data).unwrap()
+    }
+
+    pub async fn set_safety_fund_limit(
+        &self,
+        context: &mut ProgramTestContext,
+        test_income_pool_market: &TestIncomePoolMarket,
+        max_drawdown_amount: u64,
+        max_period_drawdown_amount: u64,
+        drawdown_period: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_safety_fund_limit(
+                &everlend_income_pools::id(),
+                &test_income_pool_market.keypair.pubkey(),
+                &self.token_mint_pubkey,
+                &test_income_pool_market.manager.pubkey(),
+                max_drawdown_amount,
+                max_period_drawdown_amount,
+                drawdown_period,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &test_income_pool_market.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn drawdown_safety_fund(
+        &self,
+        context: &mut ProgramTestContext,
+        test_income_pool_market: &TestIncomePoolMarket,
+        general_pool: &TestGeneralPool,
+        destination: &Pubkey,
+        depositor: Option<(&Pubkey, &Pubkey)>,
+        test_registry: &TestRegistry,
+        proposer: &Keypair,
+        amount: u64,
+    ) -> BanksClientResult<()> {
+        let action = instruction::drawdown_safety_fund(
+            &everlend_income_pools::id(),
+            &test_income_pool_market.keypair.pubkey(),
+            &self.token_mint_pubkey,
+            &general_pool.pool_pubkey,
+            destination,
+            depositor,
+            &test_registry.keypair.pubkey(),
+            amount,
+        );
+
+        let action_id = test_registry.get_timelock(context).await.action_count;
+        test_registry
+            .queue_action(context, proposer, action_id, &action)
+            .await?;
+
+        let execution_slot = test_registry
+            .get_timelock_action(context, action_id)
+            .await
+            .execution_slot;
+        context.warp_to_slot(execution_slot + 1).unwrap();
+        context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+        test_registry
+            .execute_action(context, &proposer.pubkey(), action_id, &action)
+            .await
+    }
 }
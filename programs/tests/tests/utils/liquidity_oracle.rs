@@ -1,9 +1,11 @@
 use super::{get_account, BanksClientResult};
 
 use everlend_liquidity_oracle::{
+    find_oracle_signers_program_address, find_token_oracle_limits_program_address,
     find_token_oracle_program_address, instruction, state::DistributionArray,
-    state::LiquidityOracle, state::TokenOracle,
+    state::LiquidityOracle, state::OracleSigners, state::TokenOracle, state::TokenOracleLimits,
 };
+use solana_program::clock::Slot;
 use solana_program_test::*;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::{
@@ -22,7 +24,11 @@ impl TestLiquidityOracle {
         }
     }
 
-    pub async fn init(&self, context: &mut ProgramTestContext) -> BanksClientResult<()> {
+    pub async fn init(
+        &self,
+        context: &mut ProgramTestContext,
+        registry: &Pubkey,
+    ) -> BanksClientResult<()> {
         let rent = context.banks_client.get_rent().await.unwrap();
         let tx = Transaction::new_signed_with_payer(
             &[
@@ -37,6 +43,7 @@ impl TestLiquidityOracle {
                     &everlend_liquidity_oracle::id(),
                     &self.keypair.pubkey(),
                     &context.payer.pubkey(),
+                    registry,
                 ),
             ],
             Some(&context.payer.pubkey()),
@@ -47,13 +54,13 @@ impl TestLiquidityOracle {
         context.banks_client.process_transaction(tx).await
     }
 
-    pub async fn update(
+    pub async fn propose_authority(
         &self,
         context: &mut ProgramTestContext,
         authority: &Pubkey,
     ) -> BanksClientResult<()> {
         let tx = Transaction::new_signed_with_payer(
-            &[instruction::update_liquidity_oracle_authority(
+            &[instruction::propose_liquidity_oracle_authority(
                 &everlend_liquidity_oracle::id(),
                 &self.keypair.pubkey(),
                 &context.payer.pubkey(),
@@ -67,10 +74,118 @@ impl TestLiquidityOracle {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn accept_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::accept_liquidity_oracle_authority(
+                &everlend_liquidity_oracle::id(),
+                &self.keypair.pubkey(),
+                &authority.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn cancel_authority_proposal(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::cancel_liquidity_oracle_authority_proposal(
+                &everlend_liquidity_oracle::id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_oracle_signers(
+        &self,
+        context: &mut ProgramTestContext,
+        signers: Vec<Pubkey>,
+        quorum: u8,
+        window: Slot,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_oracle_signers(
+                &everlend_liquidity_oracle::id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                signers,
+                quorum,
+                window,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn apply_oracle_signers(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::apply_oracle_signers(
+                &everlend_liquidity_oracle::id(),
+                &self.keypair.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn migrate(
+        &self,
+        context: &mut ProgramTestContext,
+        registry: &Pubkey,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::migrate_liquidity_oracle(
+                &everlend_liquidity_oracle::id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                registry,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn get_data(&self, context: &mut ProgramTestContext) -> LiquidityOracle {
         let account = get_account(context, &self.keypair.pubkey()).await;
         LiquidityOracle::unpack_unchecked(&account.data).unwrap()
     }
+
+    pub async fn get_oracle_signers(&self, context: &mut ProgramTestContext) -> OracleSigners {
+        let (oracle_signers, _) = find_oracle_signers_program_address(
+            &everlend_liquidity_oracle::id(),
+            &self.keypair.pubkey(),
+        );
+
+        let account = get_account(context, &oracle_signers).await;
+        OracleSigners::unpack_unchecked(&account.data).unwrap()
+    }
 }
 
 pub struct TestTokenOracle {
@@ -100,7 +215,7 @@ impl TestTokenOracle {
                 &liquidity_oracle.keypair.pubkey(),
                 &authority,
                 &self.token_mint,
-                self.distribution,
+                self.distribution.clone(),
             )],
             Some(&context.payer.pubkey()),
             &[&context.payer, &context.payer],
@@ -133,6 +248,29 @@ impl TestTokenOracle {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn submit(
+        &self,
+        context: &mut ProgramTestContext,
+        liquidity_oracle: &TestLiquidityOracle,
+        signer: &Keypair,
+        distribution: DistributionArray,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::submit_liquidity_distribution(
+                &everlend_liquidity_oracle::id(),
+                &liquidity_oracle.keypair.pubkey(),
+                &signer.pubkey(),
+                &self.token_mint,
+                distribution,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, signer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn update_reserve_rates(
         &self,
         context: &mut ProgramTestContext,
@@ -156,6 +294,47 @@ impl TestTokenOracle {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn set_limits(
+        &self,
+        context: &mut ProgramTestContext,
+        liquidity_oracle: &TestLiquidityOracle,
+        max_distribution_change: u64,
+        min_update_interval: Slot,
+        frozen: bool,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_token_oracle_limits(
+                &everlend_liquidity_oracle::id(),
+                &liquidity_oracle.keypair.pubkey(),
+                &context.payer.pubkey(),
+                &self.token_mint,
+                max_distribution_change,
+                min_update_interval,
+                frozen,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn get_limits(
+        &self,
+        context: &mut ProgramTestContext,
+        liquidity_oracle: &TestLiquidityOracle,
+    ) -> TokenOracleLimits {
+        let (token_oracle_limits, _) = find_token_oracle_limits_program_address(
+            &everlend_liquidity_oracle::id(),
+            &liquidity_oracle.keypair.pubkey(),
+            &self.token_mint,
+        );
+
+        let account = get_account(context, &token_oracle_limits).await;
+        TokenOracleLimits::unpack_unchecked(&account.data).unwrap()
+    }
+
     pub async fn get_data(
         &self,
         context: &mut ProgramTestContext,
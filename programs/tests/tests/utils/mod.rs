@@ -1,7 +1,7 @@
 #![allow(dead_code)]
 
-use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketsData};
-use everlend_registry::state::DistributionPubkeys;
+use everlend_registry::instructions::{AddRegistryMarketData, UpdateRegistryData};
+use everlend_utils::integrations::MoneyMarket;
 use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
 use solana_program_test::*;
 use solana_program_test::{ProgramTest, ProgramTestContext};
@@ -160,10 +160,6 @@ pub async fn presetup() -> TestEnvironment {
     let registry = TestRegistry::new();
     registry.init(&mut context).await.unwrap();
 
-    let mut mm_program_ids = DistributionPubkeys::default();
-    mm_program_ids[0] = spl_token_lending::id();
-    mm_program_ids[1] = larix_lending::id();
-
     registry
         .update_registry(
             &mut context,
@@ -177,16 +173,22 @@ pub async fn presetup() -> TestEnvironment {
         .await
         .unwrap();
 
-    registry
-        .update_registry_markets(
-            &mut context,
-            UpdateRegistryMarketsData {
-                money_markets: Some(mm_program_ids),
-                collateral_pool_markets: None,
-            },
-        )
-        .await
-        .unwrap();
+    for (money_market, adapter) in [
+        (spl_token_lending::id(), MoneyMarket::PortFinance),
+        (larix_lending::id(), MoneyMarket::Larix),
+    ] {
+        registry
+            .add_registry_market(
+                &mut context,
+                AddRegistryMarketData {
+                    money_market,
+                    collateral_pool_market: Pubkey::default(),
+                    adapter,
+                },
+            )
+            .await
+            .unwrap();
+    }
 
     TestEnvironment {
         context,
@@ -1,9 +1,17 @@
 use super::{get_account, BanksClientResult};
 use everlend_registry::{
-    instructions::{UpdateRegistryData, UpdateRegistryMarketsData},
-    state::{Registry, RegistryMarkets},
+    find_pause_program_address, find_role_program_address, find_timelock_action_program_address,
+    find_timelock_program_address,
+    instructions::{AddRegistryMarketData, UpdateRegistryData, UpdateRegistryMarketData},
+    state::{
+        MarketStatus, Pause, PauseFlags, Registry, RegistryMarket, RegistryMarkets, RegistryRole,
+        Role, Timelock, TimelockAction,
+    },
+};
+use everlend_utils::integrations::MoneyMarket;
+use solana_program::{
+    clock::Slot, instruction::Instruction, program_pack::Pack, pubkey::Pubkey, system_instruction,
 };
-use solana_program::{program_pack::Pack, system_instruction};
 use solana_program_test::ProgramTestContext;
 use solana_sdk::{
     signature::{Keypair, Signer},
@@ -34,6 +42,49 @@ impl TestRegistry {
         RegistryMarkets::unpack_from_slice(&account.data).unwrap()
     }
 
+    pub async fn get_pause(&self, context: &mut ProgramTestContext, pool: &Pubkey) -> Pause {
+        let (pause, _) =
+            find_pause_program_address(&everlend_registry::id(), &self.keypair.pubkey(), pool);
+        let account = get_account(context, &pause).await;
+        Pause::unpack_unchecked(&account.data).unwrap()
+    }
+
+    pub async fn get_role(
+        &self,
+        context: &mut ProgramTestContext,
+        holder: &Pubkey,
+        role: Role,
+    ) -> RegistryRole {
+        let (registry_role, _) = find_role_program_address(
+            &everlend_registry::id(),
+            &self.keypair.pubkey(),
+            holder,
+            role,
+        );
+        let account = get_account(context, &registry_role).await;
+        RegistryRole::unpack_unchecked(&account.data).unwrap()
+    }
+
+    pub async fn get_timelock(&self, context: &mut ProgramTestContext) -> Timelock {
+        let (timelock, _) =
+            find_timelock_program_address(&everlend_registry::id(), &self.keypair.pubkey());
+        let account = get_account(context, &timelock).await;
+        Timelock::unpack_unchecked(&account.data).unwrap()
+    }
+
+    pub async fn get_timelock_action(
+        &self,
+        context: &mut ProgramTestContext,
+        action_id: u64,
+    ) -> TimelockAction {
+        let (timelock, _) =
+            find_timelock_program_address(&everlend_registry::id(), &self.keypair.pubkey());
+        let (timelock_action, _) =
+            find_timelock_action_program_address(&everlend_registry::id(), &timelock, action_id);
+        let account = get_account(context, &timelock_action).await;
+        TimelockAction::unpack_unchecked(&account.data).unwrap()
+    }
+
     pub async fn init(&self, context: &mut ProgramTestContext) -> BanksClientResult<()> {
         let tx = Transaction::new_signed_with_payer(
             &[
@@ -76,16 +127,54 @@ impl TestRegistry {
         context.banks_client.process_transaction(tx).await
     }
 
-    pub async fn update_registry_markets(
+    pub async fn add_registry_market(
+        &self,
+        context: &mut ProgramTestContext,
+        data: AddRegistryMarketData,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_registry::instruction::add_registry_market(
+                &everlend_registry::id(),
+                &self.keypair.pubkey(),
+                &self.manager.pubkey(),
+                data,
+            )],
+            Some(&self.manager.pubkey()),
+            &[&self.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn add_collateral_pool_market(
+        &self,
+        context: &mut ProgramTestContext,
+        collateral_pool_market: &Pubkey,
+    ) -> BanksClientResult<()> {
+        self.add_registry_market(
+            context,
+            AddRegistryMarketData {
+                money_market: Pubkey::new_unique(),
+                collateral_pool_market: *collateral_pool_market,
+                adapter: MoneyMarket::PortFinance,
+            },
+        )
+        .await
+    }
+
+    pub async fn update_registry_market(
         &self,
         context: &mut ProgramTestContext,
-        data: UpdateRegistryMarketsData,
+        index: u8,
+        data: UpdateRegistryMarketData,
     ) -> BanksClientResult<()> {
         let tx = Transaction::new_signed_with_payer(
-            &[everlend_registry::instruction::update_registry_markets(
+            &[everlend_registry::instruction::update_registry_market(
                 &everlend_registry::id(),
                 &self.keypair.pubkey(),
                 &self.manager.pubkey(),
+                index,
                 data,
             )],
             Some(&self.manager.pubkey()),
@@ -95,4 +184,197 @@ impl TestRegistry {
 
         context.banks_client.process_transaction(tx).await
     }
+
+    pub async fn set_guardian(
+        &self,
+        context: &mut ProgramTestContext,
+        guardian: &Pubkey,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_registry::instruction::set_guardian(
+                &everlend_registry::id(),
+                &self.keypair.pubkey(),
+                &self.manager.pubkey(),
+                guardian,
+            )],
+            Some(&self.manager.pubkey()),
+            &[&self.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_pause(
+        &self,
+        context: &mut ProgramTestContext,
+        guardian: &Keypair,
+        pool: &Pubkey,
+        flags: PauseFlags,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_registry::instruction::set_pause(
+                &everlend_registry::id(),
+                &self.keypair.pubkey(),
+                &guardian.pubkey(),
+                pool,
+                flags,
+            )],
+            Some(&guardian.pubkey()),
+            &[guardian],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn grant_role(
+        &self,
+        context: &mut ProgramTestContext,
+        holder: &Pubkey,
+        role: Role,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_registry::instruction::grant_role(
+                &everlend_registry::id(),
+                &self.keypair.pubkey(),
+                &self.manager.pubkey(),
+                holder,
+                role,
+            )],
+            Some(&self.manager.pubkey()),
+            &[&self.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn revoke_role(
+        &self,
+        context: &mut ProgramTestContext,
+        holder: &Pubkey,
+        role: Role,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_registry::instruction::revoke_role(
+                &everlend_registry::id(),
+                &self.keypair.pubkey(),
+                &self.manager.pubkey(),
+                holder,
+                role,
+            )],
+            Some(&self.manager.pubkey()),
+            &[&self.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_timelock(
+        &self,
+        context: &mut ProgramTestContext,
+        proposer: &Pubkey,
+        delay: Slot,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_registry::instruction::set_timelock(
+                &everlend_registry::id(),
+                &self.keypair.pubkey(),
+                &self.manager.pubkey(),
+                proposer,
+                delay,
+            )],
+            Some(&self.manager.pubkey()),
+            &[&self.manager],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn queue_action(
+        &self,
+        context: &mut ProgramTestContext,
+        proposer: &Keypair,
+        action_id: u64,
+        action: &Instruction,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_registry::instruction::queue_action(
+                &everlend_registry::id(),
+                &self.keypair.pubkey(),
+                &proposer.pubkey(),
+                action_id,
+                action,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, proposer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn cancel_action(
+        &self,
+        context: &mut ProgramTestContext,
+        proposer: &Keypair,
+        action_id: u64,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_registry::instruction::cancel_action(
+                &everlend_registry::id(),
+                &self.keypair.pubkey(),
+                &proposer.pubkey(),
+                action_id,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, proposer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn execute_action(
+        &self,
+        context: &mut ProgramTestContext,
+        proposer: &Pubkey,
+        action_id: u64,
+        action: &Instruction,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_registry::instruction::execute_action(
+                &everlend_registry::id(),
+                &self.keypair.pubkey(),
+                proposer,
+                action_id,
+                action,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+}
+
+/// Registry market list with active markets of unique program ids
+pub fn registry_markets(count: usize) -> RegistryMarkets {
+    let mut registry_markets = RegistryMarkets::init();
+    for _ in 0..count {
+        registry_markets
+            .add(RegistryMarket {
+                money_market: Pubkey::new_unique(),
+                collateral_pool_market: Pubkey::default(),
+                adapter: MoneyMarket::PortFinance,
+                status: MarketStatus::Active,
+            })
+            .unwrap();
+    }
+
+    registry_markets
 }
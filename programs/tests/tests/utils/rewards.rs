@@ -1,4 +1,8 @@
-use crate::utils::{get_liquidity_mint, transfer, BanksClientResult};
+use crate::utils::{get_account, get_liquidity_mint, transfer, BanksClientResult};
+use everlend_rewards::find_vault_program_address;
+use everlend_rewards::state::RewardPool;
+use solana_program::clock::Slot;
+use solana_program::program_pack::Pack;
 use solana_program::pubkey::Pubkey;
 use solana_program_test::ProgramTestContext;
 use solana_sdk::signature::{Keypair, Signer};
@@ -39,6 +43,11 @@ impl TestRewards {
         }
     }
 
+    pub async fn get_reward_pool(&self, context: &mut ProgramTestContext) -> RewardPool {
+        let account = get_account(context, &self.mining_reward_pool).await;
+        RewardPool::unpack_unchecked(&account.data).unwrap()
+    }
+
     pub async fn initialize_pool(&self, context: &mut ProgramTestContext) -> BanksClientResult<()> {
         transfer(context, &self.root_authority.pubkey(), 10000000)
             .await
@@ -250,4 +259,129 @@ impl TestRewards {
 
         context.banks_client.process_transaction(tx).await
     }
+
+    pub async fn set_vault_fee(
+        &self,
+        context: &mut ProgramTestContext,
+        fee_account: &Pubkey,
+        authority: &Keypair,
+        fee_bps: u16,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_rewards::instruction::set_vault_fee(
+                &everlend_rewards::id(),
+                &self.rewards_root.pubkey(),
+                &self.mining_reward_pool,
+                &self.token_mint_pubkey,
+                fee_account,
+                &authority.pubkey(),
+                fee_bps,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_vault_emission(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+        emission_duration: Slot,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_rewards::instruction::set_vault_emission(
+                &everlend_rewards::id(),
+                &self.rewards_root.pubkey(),
+                &self.mining_reward_pool,
+                &self.token_mint_pubkey,
+                &authority.pubkey(),
+                emission_duration,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn lock_mining(
+        &self,
+        context: &mut ProgramTestContext,
+        user: &Keypair,
+        mining_account: &Pubkey,
+        duration: Slot,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_rewards::instruction::lock_mining(
+                &everlend_rewards::id(),
+                &self.mining_reward_pool,
+                mining_account,
+                &user.pubkey(),
+                duration,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, user],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn expire_lock(
+        &self,
+        context: &mut ProgramTestContext,
+        mining_account: &Pubkey,
+    ) -> BanksClientResult<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_rewards::instruction::expire_lock(
+                &everlend_rewards::id(),
+                &self.mining_reward_pool,
+                mining_account,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn claim_all(
+        &self,
+        context: &mut ProgramTestContext,
+        user: &Keypair,
+        mining_account: &Pubkey,
+        rewards: &[(Pubkey, Pubkey)],
+    ) -> BanksClientResult<()> {
+        let rewards: Vec<(Pubkey, Pubkey, Pubkey)> = rewards
+            .iter()
+            .map(|(reward_mint, user_reward_token)| {
+                let (vault, _) = find_vault_program_address(
+                    &everlend_rewards::id(),
+                    &self.mining_reward_pool,
+                    reward_mint,
+                );
+                (*reward_mint, vault, *user_reward_token)
+            })
+            .collect();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[everlend_rewards::instruction::claim_all(
+                &everlend_rewards::id(),
+                &self.mining_reward_pool,
+                mining_account,
+                &user.pubkey(),
+                &rewards,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, user],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
 }
@@ -5,7 +5,7 @@ use crate::helpers::{
     create_general_pool_market, create_income_pool, create_income_pool_market,
     create_pool_borrow_authority, create_pool_withdraw_authority, create_token_oracle,
     create_transit, init_depositor, init_liquidity_oracle, init_registry, init_rewards_root,
-    update_registry, update_registry_markets, PoolPubkeys,
+    set_registry_markets, update_registry, PoolPubkeys,
 };
 use crate::utils::{
     arg_multiple, arg_pubkey, get_asset_maps, spl_create_associated_token_account,
@@ -14,8 +14,8 @@ use crate::utils::{
 use crate::{arg_keypair, Config, InitializedAccounts, ToolkitCommand, ARG_ACCOUNTS};
 use clap::{Arg, ArgMatches};
 use everlend_liquidity_oracle::state::DistributionArray;
-use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketsData};
-use everlend_registry::state::DistributionPubkeys;
+use everlend_registry::instructions::{AddRegistryMarketData, UpdateRegistryData};
+use everlend_utils::integrations::MoneyMarket;
 use solana_clap_utils::input_parsers::{keypair_of, pubkey_of};
 use solana_client::client_error::ClientError;
 use solana_program::pubkey::Pubkey;
@@ -74,37 +74,40 @@ impl<'a> ToolkitCommand<'a> for CreateAccountsCommand {
             create_income_pool_market(config, None, &general_pool_market_pubkey)?;
 
         println!("Liquidity oracle");
-        let liquidity_oracle_pubkey = init_liquidity_oracle(config, None)?;
-        let mut distribution = DistributionArray::default();
-        distribution[0] = 0;
-        distribution[1] = 0;
-        distribution[2] = 0;
-        distribution[3] = 0;
-        distribution[4] = 0;
-        distribution[5] = 0;
-        distribution[6] = 0;
+        let liquidity_oracle_pubkey = init_liquidity_oracle(config, None, &registry_pubkey)?;
+        let distribution: DistributionArray = vec![0; 7];
 
         println!("Registry");
-        let mut money_market_program_ids = DistributionPubkeys::default();
-        money_market_program_ids[0] = default_accounts.port_finance.program_id;
-        money_market_program_ids[1] = default_accounts.larix.program_id;
-        money_market_program_ids[2] = default_accounts.solend.program_id;
-        money_market_program_ids[3] = default_accounts.tulip.program_id;
-        money_market_program_ids[4] = default_accounts.francium.program_id;
-        money_market_program_ids[5] = default_accounts.jet.program_id;
-        money_market_program_ids[6] = default_accounts.frakt.program_id;
-
         let mm_collateral_pool_markets = vec![
-            create_collateral_market(config, None)?,
-            create_collateral_market(config, None)?,
-            create_collateral_market(config, None)?,
-            create_collateral_market(config, None)?,
-            create_collateral_market(config, None)?,
+            create_collateral_market(config, None, &registry_pubkey)?,
+            create_collateral_market(config, None, &registry_pubkey)?,
+            create_collateral_market(config, None, &registry_pubkey)?,
+            create_collateral_market(config, None, &registry_pubkey)?,
+            create_collateral_market(config, None, &registry_pubkey)?,
         ];
 
-        let mut collateral_pool_markets = DistributionPubkeys::default();
-        collateral_pool_markets[..mm_collateral_pool_markets.len()]
-            .copy_from_slice(&mm_collateral_pool_markets);
+        let markets = vec![
+            (
+                default_accounts.port_finance.program_id,
+                MoneyMarket::PortFinance,
+            ),
+            (default_accounts.larix.program_id, MoneyMarket::Larix),
+            (default_accounts.solend.program_id, MoneyMarket::Solend),
+            (default_accounts.tulip.program_id, MoneyMarket::Tulip),
+            (default_accounts.francium.program_id, MoneyMarket::Francium),
+            (default_accounts.jet.program_id, MoneyMarket::Jet),
+            (default_accounts.frakt.program_id, MoneyMarket::Frakt),
+        ]
+        .into_iter()
+        .map(|(money_market, adapter)| AddRegistryMarketData {
+            money_market,
+            collateral_pool_market: mm_collateral_pool_markets
+                .get(adapter as usize)
+                .copied()
+                .unwrap_or_default(),
+            adapter,
+        })
+        .collect();
 
         update_registry(
             config,
@@ -117,14 +120,7 @@ impl<'a> ToolkitCommand<'a> for CreateAccountsCommand {
             },
         )?;
 
-        update_registry_markets(
-            config,
-            &registry_pubkey,
-            UpdateRegistryMarketsData {
-                money_markets: Some(money_market_program_ids),
-                collateral_pool_markets: Some(collateral_pool_markets),
-            },
-        )?;
+        set_registry_markets(config, &registry_pubkey, markets)?;
 
         println!("Depositor");
         let depositor_pubkey = init_depositor(config, &registry_pubkey, None, rebalance_executor)?;
@@ -42,10 +42,7 @@ impl<'a> ToolkitCommand<'a> for CreateTokenAccountsCommand {
 
         let (mint_map, collateral_mint_map) = get_asset_maps(default_accounts);
 
-        let mut distribution = DistributionArray::default();
-        distribution[0] = 0;
-        distribution[1] = 0;
-        distribution[2] = 0;
+        let distribution: DistributionArray = vec![0; 3];
 
         println!("Prepare borrow authority");
         let (depositor_authority, _) = &everlend_utils::find_program_address(
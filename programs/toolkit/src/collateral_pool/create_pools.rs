@@ -5,7 +5,6 @@ use crate::helpers::{
 use crate::utils::get_asset_maps;
 use crate::{Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
-use everlend_registry::state::TOTAL_DISTRIBUTIONS;
 use solana_client::client_error::ClientError;
 use solana_program::pubkey::Pubkey;
 use std::str::FromStr;
@@ -31,24 +30,21 @@ impl<'a> ToolkitCommand<'a> for CreatePoolsCommand {
     }
 
     fn handle(&self, config: &Config, _arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let mut initialized_accounts = config.get_initialized_accounts();
+        let registry = initialized_accounts.registry;
         let collateral_pool_markets = vec![
-            create_collateral_market(config, None)?,
-            create_collateral_market(config, None)?,
-            create_collateral_market(config, None)?,
-            create_collateral_market(config, None)?,
-            create_collateral_market(config, None)?,
+            create_collateral_market(config, None, &registry)?,
+            create_collateral_market(config, None, &registry)?,
+            create_collateral_market(config, None, &registry)?,
+            create_collateral_market(config, None, &registry)?,
+            create_collateral_market(config, None, &registry)?,
         ];
-        let mut initialized_accounts = config.get_initialized_accounts();
         initialized_accounts.collateral_pool_markets = collateral_pool_markets;
 
         let default_accounts = config.get_default_accounts();
 
         let (_, collateral_mint_map) = get_asset_maps(default_accounts);
 
-        let mut collateral_pool_markets: [Pubkey; TOTAL_DISTRIBUTIONS] = Default::default();
-        collateral_pool_markets[..initialized_accounts.collateral_pool_markets.len()]
-            .copy_from_slice(&initialized_accounts.collateral_pool_markets);
-
         let token_accounts = initialized_accounts.token_accounts.iter_mut();
         let depositor_pubkey = &initialized_accounts.depositor;
         for pair in token_accounts {
@@ -40,7 +40,8 @@ impl<'a> ToolkitCommand<'a> for InitPoolMarketCommand {
 
         let mut initialiazed_accounts = config.get_initialized_accounts();
 
-        let mm_pool_market_pubkey = create_collateral_market(config, keypair)?;
+        let mm_pool_market_pubkey =
+            create_collateral_market(config, keypair, &initialiazed_accounts.registry)?;
         initialiazed_accounts.collateral_pool_markets[money_market as usize] =
             mm_pool_market_pubkey;
 
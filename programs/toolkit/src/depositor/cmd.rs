@@ -1,6 +1,8 @@
 use super::{
     CreateDepositorCommand, CreateDepositorTransitAccountCommand, DumpAccountsCommand,
     GetRebalancingAccountCommand, ResetRebalancingCommand, InitMiningCommand,
+    SetKeeperConfigCommand, SetRebalancingLimitsCommand, SetYieldOptimizationCommand,
+    SimulateRebalancingCommand, UpdateYieldDistributionCommand,
 };
 use crate::{print_commands, utils::Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
@@ -26,7 +28,12 @@ impl<'a> ToolkitCommand<'a> for DepositorCommand {
             Box::new(CreateDepositorCommand),
             Box::new(CreateDepositorTransitAccountCommand),
             Box::new(ResetRebalancingCommand),
+            Box::new(SetRebalancingLimitsCommand),
+            Box::new(SetKeeperConfigCommand),
+            Box::new(SetYieldOptimizationCommand),
+            Box::new(UpdateYieldDistributionCommand),
             Box::new(GetRebalancingAccountCommand),
+            Box::new(SimulateRebalancingCommand),
             Box::new(InitMiningCommand),
             Box::new(DumpAccountsCommand),
         ]
@@ -4,6 +4,11 @@ mod create_transit_account;
 mod dump_accounts;
 mod get_account;
 mod reset_rebalancing;
+mod set_keeper_config;
+mod set_rebalancing_limits;
+mod set_yield_optimization;
+mod simulate_rebalancing;
+mod update_yield_distribution;
 mod init_mining;
 
 pub use cmd::*;
@@ -13,3 +18,8 @@ pub use init_mining::*;
 pub use dump_accounts::*;
 pub use get_account::*;
 pub use reset_rebalancing::*;
+pub use set_keeper_config::*;
+pub use set_rebalancing_limits::*;
+pub use set_yield_optimization::*;
+pub use simulate_rebalancing::*;
+pub use update_yield_distribution::*;
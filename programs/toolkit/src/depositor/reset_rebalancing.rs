@@ -26,12 +26,13 @@ impl<'a> ToolkitCommand<'a> for ResetRebalancingCommand {
         vec![
             arg_pubkey(ARG_TOKEN_MINT, true).help("Token mint pubkey"),
             arg_amount(ARG_AMOUNT, true).help("Amount to distribute"),
-            arg_multiple(ARG_DISTRIBUTED_LIQUIDITY, true).help("Distributed liduidity")
-                .number_of_values(10),
+            arg_multiple(ARG_DISTRIBUTED_LIQUIDITY, true)
+                .help("Distributed liduidity")
+                .min_values(1),
             arg_multiple(ARG_DISTRIBUTION, true)
                 .value_name("DISTRIBUTION")
                 .short("d")
-                .number_of_values(10),
+                .min_values(1),
         ]
     }
 
@@ -47,11 +48,8 @@ impl<'a> ToolkitCommand<'a> for ResetRebalancingCommand {
         let distribution: Vec<u64> = values_of::<u64>(arg_matches, ARG_DISTRIBUTION).unwrap();
         let initialiazed_accounts = config.get_initialized_accounts();
 
-        let mut distributed_liquidity = DistributionArray::default();
-        distributed_liquidity.copy_from_slice(liquidity.as_slice());
-
-        let mut distribution_array = DistributionArray::default();
-        distribution_array.copy_from_slice(distribution.as_slice());
+        let distributed_liquidity: DistributionArray = liquidity;
+        let distribution_array: DistributionArray = distribution;
 
         println!("distribution_array {:?}", distribution_array);
 
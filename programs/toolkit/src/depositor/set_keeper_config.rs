@@ -0,0 +1,80 @@
+use crate::helpers::{create_transit, set_keeper_config};
+use crate::utils::{arg_amount, arg_pubkey};
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use solana_clap_utils::input_parsers::{pubkey_of, value_of};
+
+const ARG_TOKEN_MINT: &str = "token-mint";
+const ARG_PERMISSIONLESS_CRANK: &str = "permissionless-crank";
+const ARG_KEEPER_REWARD_SHARE: &str = "keeper-reward-share";
+const ARG_KEEPER_STEP_REWARD: &str = "keeper-step-reward";
+
+#[derive(Clone, Copy)]
+pub struct SetKeeperConfigCommand;
+
+impl<'a> ToolkitCommand<'a> for SetKeeperConfigCommand {
+    fn get_name(&self) -> &'a str {
+        "set-keeper-config"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Set permissionless rebalancing crank and keeper reward"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_TOKEN_MINT, true).help("Token mint pubkey"),
+            Arg::with_name(ARG_PERMISSIONLESS_CRANK)
+                .long(ARG_PERMISSIONLESS_CRANK)
+                .takes_value(false)
+                .help("Allow any keeper to crank the rebalancing"),
+            arg_amount(ARG_KEEPER_REWARD_SHARE, false).help(
+                "Share of the withdrawal income paid to the keeper reward transit [default: 0]",
+            ),
+            arg_amount(ARG_KEEPER_STEP_REWARD, false)
+                .help("Reward paid to the keeper for each executed step [default: 0]"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let token_mint = pubkey_of(arg_matches, ARG_TOKEN_MINT).unwrap();
+        let permissionless_crank = arg_matches.is_present(ARG_PERMISSIONLESS_CRANK);
+        let keeper_reward_share =
+            value_of::<u64>(arg_matches, ARG_KEEPER_REWARD_SHARE).unwrap_or_default();
+        let keeper_step_reward =
+            value_of::<u64>(arg_matches, ARG_KEEPER_STEP_REWARD).unwrap_or_default();
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        println!(
+            "permissionless crank {} keeper reward share {} keeper step reward {}",
+            permissionless_crank, keeper_reward_share, keeper_step_reward
+        );
+
+        // Keeper reward is paid from the keeper reward transit
+        if keeper_reward_share > 0 || keeper_step_reward > 0 {
+            create_transit(
+                config,
+                &initialiazed_accounts.depositor,
+                &token_mint,
+                Some("keeper".to_string()),
+            )?;
+        }
+
+        set_keeper_config(
+            config,
+            &initialiazed_accounts.registry,
+            &initialiazed_accounts.depositor,
+            &token_mint,
+            permissionless_crank,
+            keeper_reward_share,
+            keeper_step_reward,
+        )?;
+
+        Ok(())
+    }
+}
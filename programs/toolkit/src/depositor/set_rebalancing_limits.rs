@@ -0,0 +1,90 @@
+use crate::helpers::set_rebalancing_limits;
+use crate::utils::{arg_amount, arg_multiple, arg_pubkey};
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use everlend_depositor::state::MarketLimit;
+use solana_clap_utils::input_parsers::{pubkey_of, pubkeys_of, value_of, values_of};
+
+const ARG_TOKEN_MINT: &str = "token-mint";
+const ARG_MONEY_MARKETS: &str = "money-markets";
+const ARG_MAX_LIQUIDITY: &str = "max-liquidity";
+const ARG_MAX_SHARE: &str = "max-share";
+const ARG_RESERVE_RATES_WINDOW: &str = "reserve-rates-window";
+const ARG_MAX_RESERVE_RATE_DEVIATION: &str = "max-reserve-rate-deviation";
+
+#[derive(Clone, Copy)]
+pub struct SetRebalancingLimitsCommand;
+
+impl<'a> ToolkitCommand<'a> for SetRebalancingLimitsCommand {
+    fn get_name(&self) -> &'a str {
+        "set-rebalancing-limits"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Set per market rebalancing limits"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_TOKEN_MINT, true).help("Token mint pubkey"),
+            arg_multiple(ARG_MONEY_MARKETS, true).help("Money market program ids"),
+            arg_multiple(ARG_MAX_LIQUIDITY, true).help("Max liquidity for each market"),
+            arg_multiple(ARG_MAX_SHARE, true).help("Max share for each market"),
+            arg_amount(ARG_RESERVE_RATES_WINDOW, false)
+                .help("Max age of reserve rates in slots [default: 0]"),
+            arg_amount(ARG_MAX_RESERVE_RATE_DEVIATION, false)
+                .help("Max deviation of reserve rates from money market rates [default: disabled]"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let token_mint = pubkey_of(arg_matches, ARG_TOKEN_MINT).unwrap();
+        let money_markets = pubkeys_of(arg_matches, ARG_MONEY_MARKETS).unwrap();
+        let max_liquidity: Vec<u64> = values_of::<u64>(arg_matches, ARG_MAX_LIQUIDITY).unwrap();
+        let max_share: Vec<u64> = values_of::<u64>(arg_matches, ARG_MAX_SHARE).unwrap();
+        let reserve_rates_window =
+            value_of::<u64>(arg_matches, ARG_RESERVE_RATES_WINDOW).unwrap_or_default();
+        let max_reserve_rate_deviation =
+            value_of::<u64>(arg_matches, ARG_MAX_RESERVE_RATE_DEVIATION).unwrap_or_default();
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        if max_liquidity.len() != money_markets.len() || max_share.len() != money_markets.len() {
+            return Err(anyhow::anyhow!(
+                "Max liquidity and max share must be set for each market"
+            ));
+        }
+
+        let limits: Vec<MarketLimit> = money_markets
+            .into_iter()
+            .zip(max_liquidity.into_iter())
+            .zip(max_share.into_iter())
+            .map(|((money_market, max_liquidity), max_share)| MarketLimit {
+                money_market,
+                max_liquidity,
+                max_share,
+            })
+            .collect();
+
+        println!(
+            "limits {:?} reserve rates window {} max reserve rate deviation {}",
+            limits, reserve_rates_window, max_reserve_rate_deviation
+        );
+
+        set_rebalancing_limits(
+            config,
+            &initialiazed_accounts.registry,
+            &initialiazed_accounts.depositor,
+            &token_mint,
+            limits,
+            reserve_rates_window,
+            max_reserve_rate_deviation,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::helpers::set_yield_optimization;
+use crate::utils::arg_pubkey;
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use solana_clap_utils::input_parsers::pubkey_of;
+
+const ARG_TOKEN_MINT: &str = "token-mint";
+const ARG_DISABLE: &str = "disable";
+
+#[derive(Clone, Copy)]
+pub struct SetYieldOptimizationCommand;
+
+impl<'a> ToolkitCommand<'a> for SetYieldOptimizationCommand {
+    fn get_name(&self) -> &'a str {
+        "set-yield-optimization"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Switch rebalancing to the yield distribution computed on-chain"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_TOKEN_MINT, true).help("Token mint pubkey"),
+            Arg::with_name(ARG_DISABLE)
+                .long(ARG_DISABLE)
+                .takes_value(false)
+                .help("Return to the liquidity oracle distribution"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let token_mint = pubkey_of(arg_matches, ARG_TOKEN_MINT).unwrap();
+        let yield_optimization = !arg_matches.is_present(ARG_DISABLE);
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        println!("yield optimization {}", yield_optimization);
+
+        set_yield_optimization(
+            config,
+            &initialiazed_accounts.registry,
+            &initialiazed_accounts.depositor,
+            &token_mint,
+            yield_optimization,
+        )?;
+
+        Ok(())
+    }
+}
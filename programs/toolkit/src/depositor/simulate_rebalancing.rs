@@ -0,0 +1,246 @@
+use crate::utils::arg_pubkey;
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use everlend_depositor::state::{
+    InitRebalancingParams, Rebalancing, RebalancingLimits, RebalancingOperation, YieldDistribution,
+};
+use everlend_depositor::utils::calculate_amount_to_distribute;
+use everlend_depositor::{RebalancingLimitsPDA, RebalancingPDA, TransitPDA, YieldDistributionPDA};
+use everlend_general_pool::state::{Pool, WithdrawalRequests};
+use everlend_general_pool::{find_pool_program_address, find_withdrawal_requests_program_address};
+use everlend_liquidity_oracle::{find_token_oracle_program_address, state::TokenOracle};
+use everlend_registry::state::{Registry, RegistryMarkets};
+use everlend_utils::PDA;
+use solana_clap_utils::input_parsers::pubkey_of;
+use solana_program::program_pack::Pack;
+use spl_token::state::Account;
+use std::{cmp::min, collections::HashSet};
+
+const ARG_TOKEN_MINT: &str = "token-mint";
+const ARG_REFRESH_INCOME: &str = "refresh-income";
+
+#[derive(Clone, Copy)]
+pub struct SimulateRebalancingCommand;
+
+impl<'a> ToolkitCommand<'a> for SimulateRebalancingCommand {
+    fn get_name(&self) -> &'a str {
+        "simulate-rebalancing"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Compute rebalancing against current accounts without sending a transaction"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_TOKEN_MINT, true).help("Token mint pubkey"),
+            Arg::with_name(ARG_REFRESH_INCOME)
+                .long(ARG_REFRESH_INCOME)
+                .takes_value(false)
+                .help("Simulate refresh income rebalancing"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let mint = pubkey_of(arg_matches, ARG_TOKEN_MINT).unwrap();
+        let refresh_income = arg_matches.is_present(ARG_REFRESH_INCOME);
+        let acc = config.get_initialized_accounts();
+
+        let registry: Registry = config.get_account_unpack(&acc.registry)?;
+        let registry_markets = RegistryMarkets::unpack_from_slice(
+            &config.rpc_client.get_account(&acc.registry)?.data,
+        )?;
+
+        let (rebalancing_pubkey, _) = RebalancingPDA {
+            depositor: acc.depositor,
+            mint,
+        }
+        .find_address(&everlend_depositor::id());
+        let (rebalancing_limits_pubkey, _) = RebalancingLimitsPDA {
+            depositor: acc.depositor,
+            mint,
+        }
+        .find_address(&everlend_depositor::id());
+        let (pool_pubkey, _) = find_pool_program_address(
+            &everlend_general_pool::id(),
+            &acc.general_pool_market,
+            &mint,
+        );
+        let (withdrawal_requests_pubkey, _) = find_withdrawal_requests_program_address(
+            &everlend_general_pool::id(),
+            &acc.general_pool_market,
+            &mint,
+        );
+        let (liquidity_transit_pubkey, _) = TransitPDA {
+            seed: "",
+            depositor: acc.depositor,
+            mint,
+        }
+        .find_address(&everlend_depositor::id());
+        let (token_oracle_pubkey, _) = find_token_oracle_program_address(
+            &everlend_liquidity_oracle::id(),
+            &acc.liquidity_oracle,
+            &mint,
+        );
+
+        // Rebalancing account is created by the first rebalancing
+        let before = match config.rpc_client.get_account(&rebalancing_pubkey) {
+            Ok(account) => Rebalancing::unpack(&account.data)?,
+            Err(_) => {
+                let mut rebalancing = Rebalancing::default();
+                rebalancing.init(InitRebalancingParams {
+                    depositor: acc.depositor,
+                    mint,
+                });
+                rebalancing
+            }
+        };
+        if !before.is_completed() {
+            return Err(anyhow::anyhow!("Rebalancing is not completed"));
+        }
+
+        // Markets are unlimited until limits are set
+        let rebalancing_limits = config
+            .rpc_client
+            .get_account(&rebalancing_limits_pubkey)
+            .ok()
+            .map(|account| RebalancingLimits::unpack(&account.data))
+            .transpose()?
+            .unwrap_or_default();
+
+        let pool: Pool = config.get_account_unpack(&pool_pubkey)?;
+        let pool_token_account: Account = config.get_account_unpack(&pool.token_account)?;
+        let withdrawal_requests = WithdrawalRequests::unpack_any(
+            &config
+                .rpc_client
+                .get_account(&withdrawal_requests_pubkey)?
+                .data,
+        )?;
+        let liquidity_transit: Account = config.get_account_unpack(&liquidity_transit_pubkey)?;
+
+        if before.unsettled_loss > 0 {
+            println!(
+                "Unsettled loss {} is settled before computing, amounts may differ",
+                before.unsettled_loss
+            );
+        }
+
+        let (available_liquidity, amount_to_distribute) = calculate_amount_to_distribute(
+            before.total_distributed_liquidity()?,
+            liquidity_transit.amount,
+            pool_token_account.amount,
+            withdrawal_requests.liquidity_supply,
+        )?;
+
+        println!(
+            "available_liquidity: {} amount_to_distribute: {}",
+            available_liquidity, amount_to_distribute
+        );
+        // StartRebalancing is rejected with RebalanceLiquidityCheckFailed
+        if available_liquidity != pool.total_amount_borrowed {
+            return Err(anyhow::anyhow!(
+                "Liquidity check fails: total_amount_borrowed {}",
+                pool.total_amount_borrowed
+            ));
+        }
+
+        if amount_to_distribute > available_liquidity {
+            println!(
+                "Borrow from general pool: {}",
+                amount_to_distribute - available_liquidity
+            );
+        } else if withdrawal_requests.liquidity_supply > 0 {
+            let repay_amount = min(
+                withdrawal_requests
+                    .liquidity_supply
+                    .saturating_sub(pool_token_account.amount),
+                liquidity_transit.amount,
+            );
+            println!("Repay to general pool: {}", repay_amount);
+        }
+
+        let current_slot = config.rpc_client.get_slot()?;
+
+        let mut after = before.clone();
+        if refresh_income {
+            after.compute_with_refresh_income(
+                &registry_markets,
+                &rebalancing_limits,
+                registry.refresh_income_interval,
+                current_slot,
+                amount_to_distribute,
+            )?;
+        } else {
+            let mut token_oracle: TokenOracle = config.get_account_unpack(&token_oracle_pubkey)?;
+            if rebalancing_limits.yield_optimization {
+                let (yield_distribution_pubkey, _) = YieldDistributionPDA {
+                    depositor: acc.depositor,
+                    mint,
+                }
+                .find_address(&everlend_depositor::id());
+                let yield_distribution: YieldDistribution =
+                    config.get_account_unpack(&yield_distribution_pubkey)?;
+                token_oracle.liquidity_distribution = yield_distribution.liquidity_distribution;
+            }
+
+            after.compute(
+                &registry_markets,
+                &rebalancing_limits,
+                token_oracle,
+                amount_to_distribute,
+                current_slot,
+            )?;
+        }
+
+        println!("Steps:");
+        for step in after.steps.iter() {
+            println!(
+                "  market {}: {:?} liquidity {} collateral {:?}",
+                step.money_market_index,
+                step.operation,
+                step.liquidity_amount,
+                step.collateral_amount
+            );
+        }
+
+        // Deposit collateral is known after the money market deposit only
+        let mut unknown_collateral = HashSet::new();
+        for step in after.steps.clone() {
+            if step.operation == RebalancingOperation::Deposit && step.collateral_amount.is_none() {
+                unknown_collateral.insert(step.money_market_index as usize);
+            }
+
+            after.execute_step(
+                step.operation,
+                Some(step.collateral_amount.unwrap_or_default()),
+                current_slot,
+            )?;
+        }
+
+        println!("Distributed liquidity / received collateral:");
+        for (index, market) in registry_markets.markets.iter().enumerate() {
+            let value = |array: &Vec<u64>| array.get(index).copied().unwrap_or_default();
+            let received_collateral = if unknown_collateral.contains(&index) {
+                "unknown".to_string()
+            } else {
+                value(&after.received_collateral).to_string()
+            };
+            println!(
+                "  market {} ({:?}): {} -> {} / {} -> {}",
+                index,
+                market.adapter,
+                value(&before.distributed_liquidity),
+                value(&after.distributed_liquidity),
+                value(&before.received_collateral),
+                received_collateral,
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::helpers::update_yield_distribution;
+use crate::utils::{arg_multiple, arg_pubkey};
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use everlend_depositor::state::YieldDistribution;
+use everlend_depositor::YieldDistributionPDA;
+use everlend_general_pool::{find_pool_program_address, state::Pool};
+use everlend_utils::PDA;
+use solana_clap_utils::input_parsers::{pubkey_of, pubkeys_of};
+
+const ARG_TOKEN_MINT: &str = "token-mint";
+const ARG_RESERVES: &str = "reserves";
+const ARG_COLLATERAL_STORAGES: &str = "collateral-storages";
+
+#[derive(Clone, Copy)]
+pub struct UpdateYieldDistributionCommand;
+
+impl<'a> ToolkitCommand<'a> for UpdateYieldDistributionCommand {
+    fn get_name(&self) -> &'a str {
+        "update-yield-distribution"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Compute yield distribution from money market reserves"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_TOKEN_MINT, true).help("Token mint pubkey"),
+            arg_multiple(ARG_RESERVES, true)
+                .help("Money market reserves in the registry market order"),
+            arg_multiple(ARG_COLLATERAL_STORAGES, true)
+                .help("Collateral pools or internal minings of the reserves"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let token_mint = pubkey_of(arg_matches, ARG_TOKEN_MINT).unwrap();
+        let reserves = pubkeys_of(arg_matches, ARG_RESERVES).unwrap();
+        let collateral_storages = pubkeys_of(arg_matches, ARG_COLLATERAL_STORAGES).unwrap();
+        if reserves.len() != collateral_storages.len() {
+            return Err(anyhow::anyhow!(
+                "Each reserve requires a collateral storage"
+            ));
+        }
+        let reserves: Vec<_> = reserves.into_iter().zip(collateral_storages).collect();
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        let (general_pool_pubkey, _) = find_pool_program_address(
+            &everlend_general_pool::id(),
+            &initialiazed_accounts.general_pool_market,
+            &token_mint,
+        );
+        let general_pool: Pool = config.get_account_unpack(&general_pool_pubkey)?;
+
+        update_yield_distribution(
+            config,
+            &initialiazed_accounts.registry,
+            &initialiazed_accounts.depositor,
+            &token_mint,
+            &initialiazed_accounts.general_pool_market,
+            &general_pool.token_account,
+            &reserves,
+        )?;
+
+        let (yield_distribution_pubkey, _) = YieldDistributionPDA {
+            depositor: initialiazed_accounts.depositor,
+            mint: token_mint,
+        }
+        .find_address(&everlend_depositor::id());
+        let yield_distribution: YieldDistribution =
+            config.get_account_unpack(&yield_distribution_pubkey)?;
+
+        println!("{:#?}", yield_distribution);
+
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use super::{
-    CancelWithdrawRequestCommand, InitPoolMarketCommand, SetPoolConfigCommand,
+    CancelWithdrawRequestCommand, InitPoolMarketCommand, SetFeeScheduleCommand,
+    SetPoolConfigCommand,
 };
 use crate::{print_commands, utils::Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
@@ -25,6 +26,7 @@ impl<'a> ToolkitCommand<'a> for GeneralPoolCommand {
             Box::new(CancelWithdrawRequestCommand),
             Box::new(SetPoolConfigCommand),
             Box::new(InitPoolMarketCommand),
+            Box::new(SetFeeScheduleCommand),
         ]
     }
 
@@ -1,9 +1,11 @@
 mod cancel_withdraw_request;
 mod cmd;
 mod init_pool_market;
+mod set_fee_schedule;
 mod set_pool_config;
 
 pub use cancel_withdraw_request::*;
 pub use cmd::*;
 pub use init_pool_market::*;
+pub use set_fee_schedule::*;
 pub use set_pool_config::*;
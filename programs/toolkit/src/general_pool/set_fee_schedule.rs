@@ -0,0 +1,62 @@
+use crate::helpers::set_fee_schedule;
+use crate::utils::{arg, arg_pubkey};
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use solana_clap_utils::input_parsers::{pubkey_of, value_of};
+
+const ARG_TREASURY: &str = "treasury";
+const ARG_PERFORMANCE_FEE: &str = "performance-fee";
+const ARG_MANAGEMENT_FEE: &str = "management-fee";
+
+#[derive(Clone, Copy)]
+pub struct SetFeeScheduleCommand;
+
+impl<'a> ToolkitCommand<'a> for SetFeeScheduleCommand {
+    fn get_name(&self) -> &'a str {
+        "set-fee-schedule"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Create or update pool market fee schedule"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_TREASURY, true).help("Owner of the fee pool token accounts"),
+            arg(ARG_PERFORMANCE_FEE, false)
+                .value_name("BPS")
+                .help("Performance fee in basis points [default: 0]"),
+            arg(ARG_MANAGEMENT_FEE, false)
+                .value_name("BPS")
+                .help("Annual management fee in basis points [default: 0]"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let initialized_accounts = config.get_initialized_accounts();
+
+        let treasury = pubkey_of(arg_matches, ARG_TREASURY).unwrap();
+        let performance_fee = value_of::<u16>(arg_matches, ARG_PERFORMANCE_FEE).unwrap_or_default();
+        let management_fee = value_of::<u16>(arg_matches, ARG_MANAGEMENT_FEE).unwrap_or_default();
+
+        println!(
+            "Treasury: {} performance fee: {} management fee: {}",
+            treasury, performance_fee, management_fee
+        );
+
+        set_fee_schedule(
+            config,
+            &initialized_accounts.general_pool_market,
+            &treasury,
+            performance_fee,
+            management_fee,
+        )?;
+
+        Ok(())
+    }
+}
@@ -10,6 +10,10 @@ use solana_program::program_pack::Pack;
 const ARG_MINT: &str = "mint";
 const ARG_MIN_DEPOSIT: &str = "min-deposit";
 const ARG_MIN_WITHDRAW: &str = "min-withdraw";
+const ARG_WITHDRAW_DELAY: &str = "withdraw-delay";
+const ARG_EXIT_FEE: &str = "exit-fee";
+const ARG_POOL_DEPOSIT_CAP: &str = "pool-deposit-cap";
+const ARG_USER_DEPOSIT_CAP: &str = "user-deposit-cap";
 
 #[derive(Clone, Copy)]
 pub struct SetPoolConfigCommand;
@@ -32,6 +36,18 @@ impl<'a> ToolkitCommand<'a> for SetPoolConfigCommand {
             arg(ARG_MIN_WITHDRAW, false)
                 .value_name("DECIMAL")
                 .help("Minimum amount for withdraw (e.g. 0.01 or 1)"),
+            arg(ARG_WITHDRAW_DELAY, false)
+                .value_name("SLOTS")
+                .help("Withdraw delay in slots"),
+            arg(ARG_EXIT_FEE, false)
+                .value_name("BPS")
+                .help("Instant withdraw fee in basis points"),
+            arg(ARG_POOL_DEPOSIT_CAP, false)
+                .value_name("DECIMAL")
+                .help("Maximum total liquidity of the pool (e.g. 1000 or 0 to remove the cap)"),
+            arg(ARG_USER_DEPOSIT_CAP, false)
+                .value_name("DECIMAL")
+                .help("Maximum liquidity deposited by a user (e.g. 100 or 0 to remove the cap)"),
         ]
     }
 
@@ -46,6 +62,10 @@ impl<'a> ToolkitCommand<'a> for SetPoolConfigCommand {
         let arg_mint = pubkey_of(arg_matches, ARG_MINT).unwrap();
         let arg_deposit_minimum: Option<f64> = value_of(arg_matches, ARG_MIN_DEPOSIT);
         let arg_withdraw_minimum: Option<f64> = value_of(arg_matches, ARG_MIN_WITHDRAW);
+        let arg_withdraw_delay: Option<u64> = value_of(arg_matches, ARG_WITHDRAW_DELAY);
+        let arg_exit_fee: Option<u16> = value_of(arg_matches, ARG_EXIT_FEE);
+        let arg_pool_deposit_cap: Option<f64> = value_of(arg_matches, ARG_POOL_DEPOSIT_CAP);
+        let arg_user_deposit_cap: Option<f64> = value_of(arg_matches, ARG_USER_DEPOSIT_CAP);
 
         let account = config.rpc_client.get_account(&arg_mint)?;
         let mint_account = spl_token::state::Mint::unpack(&account.data).unwrap();
@@ -59,6 +79,12 @@ impl<'a> ToolkitCommand<'a> for SetPoolConfigCommand {
         let mut params = SetPoolConfigParams {
             deposit_minimum: None,
             withdraw_minimum: None,
+            performance_fee: None,
+            management_fee: None,
+            withdraw_delay: arg_withdraw_delay.map(Some),
+            exit_fee: arg_exit_fee,
+            pool_deposit_cap: None,
+            user_deposit_cap: None,
         };
 
         if let Some(min) = arg_deposit_minimum {
@@ -69,12 +95,24 @@ impl<'a> ToolkitCommand<'a> for SetPoolConfigCommand {
             params.withdraw_minimum =
                 Some((min * (10_u32.pow(mint_account.decimals as u32) as f64)) as u64);
         }
+        if let Some(cap) = arg_pool_deposit_cap {
+            let cap = (cap * (10_u64.pow(mint_account.decimals as u32) as f64)) as u64;
+            params.pool_deposit_cap = Some(if cap == 0 { None } else { Some(cap) });
+        }
+        if let Some(cap) = arg_user_deposit_cap {
+            let cap = (cap * (10_u64.pow(mint_account.decimals as u32) as f64)) as u64;
+            params.user_deposit_cap = Some(if cap == 0 { None } else { Some(cap) });
+        }
 
         println!(
-            "Pool: {} deposit-min: {}, withdraw-min: {}",
+            "Pool: {} deposit-min: {}, withdraw-min: {}, withdraw-delay: {:?}, exit-fee: {:?}, pool-deposit-cap: {:?}, user-deposit-cap: {:?}",
             pool,
             params.deposit_minimum.unwrap_or_default(),
-            params.withdraw_minimum.unwrap_or_default()
+            params.withdraw_minimum.unwrap_or_default(),
+            arg_withdraw_delay,
+            arg_exit_fee,
+            params.pool_deposit_cap,
+            params.user_deposit_cap
         );
 
         set_pool_config(
@@ -26,6 +26,7 @@ pub struct PoolPubkeys {
 pub fn create_collateral_market(
     config: &Config,
     pool_market_keypair: Option<Keypair>,
+    registry: &Pubkey,
 ) -> Result<Pubkey, ClientError> {
     let pool_market_keypair = pool_market_keypair.unwrap_or_else(Keypair::new);
 
@@ -50,6 +51,7 @@ pub fn create_collateral_market(
                 &everlend_collateral_pool::id(),
                 &pool_market_keypair.pubkey(),
                 &config.fee_payer.pubkey(),
+                registry,
             ),
         ],
         Some(&config.fee_payer.pubkey()),
@@ -116,6 +118,13 @@ pub fn create_collateral_pool(
                 &token_account.pubkey(),
                 &config.fee_payer.pubkey(),
             ),
+            everlend_collateral_pool::instruction::init_pool_borrow_shares(
+                &everlend_collateral_pool::id(),
+                pool_market_pubkey,
+                &pool_pubkey,
+                &config.fee_payer.pubkey(),
+                &[],
+            ),
         ],
         Some(&config.fee_payer.pubkey()),
     );
@@ -254,12 +263,19 @@ pub fn collateral_pool_update_manager(
     new_manager: &Keypair,
 ) -> Result<(), ClientError> {
     let tx = Transaction::new_with_payer(
-        &[instruction::update_manager(
-            &everlend_collateral_pool::id(),
-            pool_market,
-            &manager.pubkey(),
-            &new_manager.pubkey(),
-        )],
+        &[
+            instruction::propose_manager(
+                &everlend_collateral_pool::id(),
+                pool_market,
+                &manager.pubkey(),
+                &new_manager.pubkey(),
+            ),
+            instruction::accept_manager(
+                &everlend_collateral_pool::id(),
+                pool_market,
+                &new_manager.pubkey(),
+            ),
+        ],
         Some(&config.fee_payer.pubkey()),
     );
 
@@ -270,3 +286,42 @@ pub fn collateral_pool_update_manager(
 
     Ok(())
 }
+
+pub fn collateral_pool_cancel_manager_proposal(
+    config: &Config,
+    pool_market: &Pubkey,
+    manager: &Keypair,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::cancel_manager_proposal(
+            &everlend_collateral_pool::id(),
+            pool_market,
+            &manager.pubkey(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref(), manager])?;
+
+    Ok(())
+}
+
+pub fn migrate_collateral_pool_market(
+    config: &Config,
+    pool_market: &Pubkey,
+    registry: &Pubkey,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::migrate_pool_market(
+            &everlend_collateral_pool::id(),
+            pool_market,
+            &config.fee_payer.pubkey(),
+            registry,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
@@ -1,11 +1,12 @@
-use everlend_depositor::state::{Depositor, Rebalancing};
-use everlend_depositor::{RebalancingPDA, TransitPDA};
+use everlend_depositor::state::{Depositor, MarketLimit, Rebalancing, RebalancingLimits};
+use everlend_depositor::{RebalancingLimitsPDA, RebalancingPDA, TransitPDA};
 use everlend_liquidity_oracle::state::DistributionArray;
+use everlend_registry::state::Registry;
 use everlend_utils::PDA;
 use solana_client::client_error::ClientError;
 use solana_program::instruction::Instruction;
 use solana_program::{
-    instruction::AccountMeta, program_pack::Pack, pubkey::Pubkey, system_instruction,
+    clock::Slot, instruction::AccountMeta, program_pack::Pack, pubkey::Pubkey, system_instruction,
 };
 use solana_sdk::{
     signature::{write_keypair_file, Keypair},
@@ -110,29 +111,72 @@ pub fn start_rebalancing(
     liquidity_oracle_pubkey: &Pubkey,
     refresh_income: bool,
 ) -> Result<(Pubkey, Rebalancing), ClientError> {
-    let tx = Transaction::new_with_payer(
-        &[everlend_depositor::instruction::start_rebalancing(
-            &everlend_depositor::id(),
-            registry_pubkey,
-            depositor_pubkey,
-            token_mint,
-            general_pool_market_pubkey,
-            general_pool_token_account,
-            liquidity_oracle_pubkey,
-            &config.fee_payer.pubkey(),
-            refresh_income,
-        )],
-        Some(&config.fee_payer.pubkey()),
-    );
-
-    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
-
     let (rebalancing_pubkey, _) = RebalancingPDA {
         depositor: depositor_pubkey.clone(),
         mint: token_mint.clone(),
     }
     .find_address(&everlend_depositor::id());
 
+    let mut start_rebalancing_instruction = everlend_depositor::instruction::start_rebalancing(
+        &everlend_depositor::id(),
+        registry_pubkey,
+        depositor_pubkey,
+        token_mint,
+        general_pool_market_pubkey,
+        general_pool_token_account,
+        liquidity_oracle_pubkey,
+        &config.fee_payer.pubkey(),
+        refresh_income,
+    );
+
+    // Follow the yield distribution computed on-chain
+    let (rebalancing_limits_pubkey, _) = RebalancingLimitsPDA {
+        depositor: *depositor_pubkey,
+        mint: *token_mint,
+    }
+    .find_address(&everlend_depositor::id());
+    let yield_optimization = config
+        .rpc_client
+        .get_account(&rebalancing_limits_pubkey)
+        .ok()
+        .and_then(|account| RebalancingLimits::unpack(&account.data).ok())
+        .map_or(false, |rebalancing_limits| {
+            rebalancing_limits.yield_optimization
+        });
+    if yield_optimization && !refresh_income {
+        start_rebalancing_instruction.accounts.extend(
+            everlend_depositor::instruction::yield_distribution_accounts(
+                &everlend_depositor::id(),
+                depositor_pubkey,
+                token_mint,
+            ),
+        );
+    }
+
+    // Cover unsettled loss from the safety fund
+    let unsettled_loss = config
+        .rpc_client
+        .get_account(&rebalancing_pubkey)
+        .ok()
+        .and_then(|account| Rebalancing::unpack(&account.data).ok())
+        .map_or(0, |rebalancing| rebalancing.unsettled_loss);
+    if unsettled_loss > 0 {
+        let registry: Registry = config.get_account_unpack(registry_pubkey)?;
+        start_rebalancing_instruction.accounts.extend(
+            everlend_depositor::instruction::safety_fund_accounts(
+                &registry.income_pool_market,
+                token_mint,
+            ),
+        );
+    }
+
+    let tx = Transaction::new_with_payer(
+        &[start_rebalancing_instruction],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
     let rebalancing_account = config.rpc_client.get_account(&rebalancing_pubkey)?;
     let rebalancing = Rebalancing::unpack(&rebalancing_account.data).unwrap();
 
@@ -176,10 +220,119 @@ pub fn reset_rebalancing(
     Ok((rebalancing_pubkey, rebalancing))
 }
 
+pub fn set_rebalancing_limits(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    depositor_pubkey: &Pubkey,
+    token_mint: &Pubkey,
+    limits: Vec<MarketLimit>,
+    reserve_rates_window: Slot,
+    max_reserve_rate_deviation: u64,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_depositor::instruction::set_rebalancing_limits(
+            &everlend_depositor::id(),
+            registry_pubkey,
+            depositor_pubkey,
+            token_mint,
+            &config.fee_payer.pubkey(),
+            limits,
+            reserve_rates_window,
+            max_reserve_rate_deviation,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn set_keeper_config(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    depositor_pubkey: &Pubkey,
+    token_mint: &Pubkey,
+    permissionless_crank: bool,
+    keeper_reward_share: u64,
+    keeper_step_reward: u64,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_depositor::instruction::set_keeper_config(
+            &everlend_depositor::id(),
+            registry_pubkey,
+            depositor_pubkey,
+            token_mint,
+            &config.fee_payer.pubkey(),
+            permissionless_crank,
+            keeper_reward_share,
+            keeper_step_reward,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn set_yield_optimization(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    depositor_pubkey: &Pubkey,
+    token_mint: &Pubkey,
+    yield_optimization: bool,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_depositor::instruction::set_yield_optimization(
+            &everlend_depositor::id(),
+            registry_pubkey,
+            depositor_pubkey,
+            token_mint,
+            &config.fee_payer.pubkey(),
+            yield_optimization,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn update_yield_distribution(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    depositor_pubkey: &Pubkey,
+    token_mint: &Pubkey,
+    general_pool_market_pubkey: &Pubkey,
+    general_pool_token_account: &Pubkey,
+    reserves: &[(Pubkey, Pubkey)],
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_depositor::instruction::update_yield_distribution(
+            &everlend_depositor::id(),
+            registry_pubkey,
+            depositor_pubkey,
+            token_mint,
+            general_pool_market_pubkey,
+            general_pool_token_account,
+            &config.fee_payer.pubkey(),
+            reserves,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn depositor_deposit(
     config: &Config,
     registry_pubkey: &Pubkey,
+    general_pool_market_pubkey: &Pubkey,
     depositor_pubkey: &Pubkey,
     liquidity_mint: &Pubkey,
     collateral_mint: &Pubkey,
@@ -191,6 +344,7 @@ pub fn depositor_deposit(
         &[everlend_depositor::instruction::deposit(
             &everlend_depositor::id(),
             registry_pubkey,
+            general_pool_market_pubkey,
             depositor_pubkey,
             liquidity_mint,
             collateral_mint,
@@ -211,6 +365,7 @@ pub fn depositor_deposit(
 pub fn depositor_withdraw(
     config: &Config,
     registry_pubkey: &Pubkey,
+    general_pool_market_pubkey: &Pubkey,
     depositor_pubkey: &Pubkey,
     income_pool_market_pubkey: &Pubkey,
     income_pool_token_account: &Pubkey,
@@ -224,6 +379,7 @@ pub fn depositor_withdraw(
         &[everlend_depositor::instruction::withdraw(
             &everlend_depositor::id(),
             registry_pubkey,
+            general_pool_market_pubkey,
             depositor_pubkey,
             income_pool_market_pubkey,
             income_pool_token_account,
@@ -214,6 +214,7 @@ pub fn deposit(
     config: &Config,
     pool_market_pubkey: &Pubkey,
     pool_pubkey: &Pubkey,
+    registry_pubkey: &Pubkey,
     source: &Pubkey,
     destination: &Pubkey,
     pool_token_account: &Pubkey,
@@ -227,6 +228,7 @@ pub fn deposit(
             &everlend_general_pool::id(),
             pool_market_pubkey,
             pool_pubkey,
+            registry_pubkey,
             source,
             destination,
             pool_token_account,
@@ -249,6 +251,7 @@ pub fn withdraw_request(
     config: &Config,
     pool_market_pubkey: &Pubkey,
     pool_pubkey: &Pubkey,
+    registry_pubkey: &Pubkey,
     source: &Pubkey,
     destination: &Pubkey,
     pool_token_account: &Pubkey,
@@ -270,6 +273,7 @@ pub fn withdraw_request(
             &everlend_general_pool::id(),
             pool_market_pubkey,
             pool_pubkey,
+            registry_pubkey,
             source,
             destination,
             pool_token_account,
@@ -322,6 +326,7 @@ pub fn withdraw(
     config: &Config,
     pool_market_pubkey: &Pubkey,
     pool_pubkey: &Pubkey,
+    registry_pubkey: &Pubkey,
     destination: &Pubkey,
     pool_token_account: &Pubkey,
     token_mint: &Pubkey,
@@ -352,6 +357,7 @@ pub fn withdraw(
             &everlend_general_pool::id(),
             pool_market_pubkey,
             pool_pubkey,
+            registry_pubkey,
             destination,
             pool_token_account,
             token_mint,
@@ -367,10 +373,15 @@ pub fn withdraw(
     Ok(())
 }
 
-pub fn migrate_general_pool_account(config: &Config) -> Result<(), ClientError> {
+pub fn migrate_general_pool_account(
+    config: &Config,
+    pool_market: &Pubkey,
+) -> Result<(), ClientError> {
     let tx = Transaction::new_with_payer(
         &[instruction::migrate_instruction(
             &everlend_general_pool::id(),
+            pool_market,
+            &config.fee_payer.pubkey(),
         )],
         Some(&config.fee_payer.pubkey()),
     );
@@ -416,7 +427,7 @@ pub fn get_withdrawal_requests(
 
     let withdrawal_requests_account = config.rpc_client.get_account(&withdrawal_requests_pubkey)?;
     let withdrawal_requests =
-        WithdrawalRequests::unpack(&withdrawal_requests_account.data).unwrap();
+        WithdrawalRequests::unpack_any(&withdrawal_requests_account.data).unwrap();
 
     Ok((withdrawal_requests_pubkey, withdrawal_requests))
 }
@@ -452,12 +463,19 @@ pub fn general_pool_update_manager(
     new_manager: &Keypair,
 ) -> Result<(), ClientError> {
     let tx = Transaction::new_with_payer(
-        &[instruction::update_manager(
-            &everlend_general_pool::id(),
-            pool_market,
-            &manager.pubkey(),
-            &new_manager.pubkey(),
-        )],
+        &[
+            instruction::propose_manager(
+                &everlend_general_pool::id(),
+                pool_market,
+                &manager.pubkey(),
+                &new_manager.pubkey(),
+            ),
+            instruction::accept_manager(
+                &everlend_general_pool::id(),
+                pool_market,
+                &new_manager.pubkey(),
+            ),
+        ],
         Some(&config.fee_payer.pubkey()),
     );
 
@@ -469,6 +487,25 @@ pub fn general_pool_update_manager(
     Ok(())
 }
 
+pub fn general_pool_cancel_manager_proposal(
+    config: &Config,
+    pool_market: &Pubkey,
+    manager: &Keypair,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::cancel_manager_proposal(
+            &everlend_general_pool::id(),
+            pool_market,
+            &manager.pubkey(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref(), manager])?;
+
+    Ok(())
+}
+
 pub fn set_pool_config(
     config: &Config,
     pool_market: &Pubkey,
@@ -490,3 +527,27 @@ pub fn set_pool_config(
 
     Ok(())
 }
+
+pub fn set_fee_schedule(
+    config: &Config,
+    pool_market: &Pubkey,
+    treasury: &Pubkey,
+    performance_fee: u16,
+    management_fee: u16,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::set_fee_schedule(
+            &everlend_general_pool::id(),
+            pool_market,
+            &config.fee_payer.pubkey(),
+            treasury,
+            performance_fee,
+            management_fee,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
@@ -161,12 +161,19 @@ pub fn income_pools_update_manager(
     new_manager: &Keypair,
 ) -> Result<(), ClientError> {
     let tx = Transaction::new_with_payer(
-        &[instruction::update_manager(
-            &everlend_income_pools::id(),
-            pool_market,
-            &manager.pubkey(),
-            &new_manager.pubkey(),
-        )],
+        &[
+            instruction::propose_manager(
+                &everlend_income_pools::id(),
+                pool_market,
+                &manager.pubkey(),
+                &new_manager.pubkey(),
+            ),
+            instruction::accept_manager(
+                &everlend_income_pools::id(),
+                pool_market,
+                &new_manager.pubkey(),
+            ),
+        ],
         Some(&config.fee_payer.pubkey()),
     );
 
@@ -177,3 +184,68 @@ pub fn income_pools_update_manager(
 
     Ok(())
 }
+
+pub fn income_pools_cancel_manager_proposal(
+    config: &Config,
+    pool_market: &Pubkey,
+    manager: &Keypair,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::cancel_manager_proposal(
+            &everlend_income_pools::id(),
+            pool_market,
+            &manager.pubkey(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref(), manager])?;
+
+    Ok(())
+}
+
+pub fn migrate_income_pool_market(
+    config: &Config,
+    pool_market: &Pubkey,
+    general_pool_market: &Pubkey,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::migrate_pool_market(
+            &everlend_income_pools::id(),
+            pool_market,
+            &config.fee_payer.pubkey(),
+            general_pool_market,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn set_safety_fund_limit(
+    config: &Config,
+    income_pool_market_pubkey: &Pubkey,
+    token_mint: &Pubkey,
+    max_drawdown_amount: u64,
+    max_period_drawdown_amount: u64,
+    drawdown_period: u64,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::set_safety_fund_limit(
+            &everlend_income_pools::id(),
+            income_pool_market_pubkey,
+            token_mint,
+            &config.fee_payer.pubkey(),
+            max_drawdown_amount,
+            max_period_drawdown_amount,
+            drawdown_period,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use everlend_liquidity_oracle::{
     state::{DistributionArray, LiquidityOracle},
 };
 use solana_client::client_error::ClientError;
-use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
+use solana_program::{clock::Slot, program_pack::Pack, pubkey::Pubkey, system_instruction};
 use solana_sdk::{
     signature::{write_keypair_file, Keypair},
     signer::Signer,
@@ -14,6 +14,7 @@ use solana_sdk::{
 pub fn init_liquidity_oracle(
     config: &Config,
     oracle_keypair: Option<Keypair>,
+    registry: &Pubkey,
 ) -> Result<Pubkey, ClientError> {
     let oracle_keypair = oracle_keypair.unwrap_or_else(Keypair::new);
 
@@ -36,6 +37,7 @@ pub fn init_liquidity_oracle(
                 &everlend_liquidity_oracle::id(),
                 &oracle_keypair.pubkey(),
                 &config.fee_payer.pubkey(),
+                registry,
             ),
         ],
         Some(&config.fee_payer.pubkey()),
@@ -62,11 +64,40 @@ pub fn update_oracle_authority(
     new_authority: Keypair,
 ) -> Result<(), ClientError> {
     let tx = Transaction::new_with_payer(
-        &[instruction::update_liquidity_oracle_authority(
+        &[
+            instruction::propose_liquidity_oracle_authority(
+                &everlend_liquidity_oracle::id(),
+                &oracle,
+                &authority.pubkey(),
+                &new_authority.pubkey(),
+            ),
+            instruction::accept_liquidity_oracle_authority(
+                &everlend_liquidity_oracle::id(),
+                &oracle,
+                &new_authority.pubkey(),
+            ),
+        ],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(
+        tx,
+        vec![config.fee_payer.as_ref(), &authority, &new_authority],
+    )?;
+
+    Ok(())
+}
+
+pub fn cancel_oracle_authority_proposal(
+    config: &Config,
+    oracle: Pubkey,
+    authority: Keypair,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::cancel_liquidity_oracle_authority_proposal(
             &everlend_liquidity_oracle::id(),
             &oracle,
             &authority.pubkey(),
-            &new_authority.pubkey(),
         )],
         Some(&config.fee_payer.pubkey()),
     );
@@ -77,6 +108,90 @@ pub fn update_oracle_authority(
     Ok(())
 }
 
+pub fn migrate_liquidity_oracle(
+    config: &Config,
+    oracle: &Pubkey,
+    registry: &Pubkey,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::migrate_liquidity_oracle(
+            &everlend_liquidity_oracle::id(),
+            oracle,
+            &config.fee_payer.pubkey(),
+            registry,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn set_oracle_signers(
+    config: &Config,
+    oracle: &Pubkey,
+    signers: Vec<Pubkey>,
+    quorum: u8,
+    window: Slot,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::set_oracle_signers(
+            &everlend_liquidity_oracle::id(),
+            oracle,
+            &config.fee_payer.pubkey(),
+            signers,
+            quorum,
+            window,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn apply_oracle_signers(config: &Config, oracle: &Pubkey) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::apply_oracle_signers(
+            &everlend_liquidity_oracle::id(),
+            oracle,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn set_token_oracle_limits(
+    config: &Config,
+    oracle: &Pubkey,
+    token_mint: &Pubkey,
+    max_distribution_change: u64,
+    min_update_interval: Slot,
+    frozen: bool,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[instruction::set_token_oracle_limits(
+            &everlend_liquidity_oracle::id(),
+            oracle,
+            &config.fee_payer.pubkey(),
+            token_mint,
+            max_distribution_change,
+            min_update_interval,
+            frozen,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
 pub fn create_token_oracle(
     config: &Config,
     oracle_pubkey: &Pubkey,
@@ -103,7 +218,7 @@ pub fn create_token_oracle(
             oracle_pubkey,
             &config.fee_payer.pubkey(),
             token_mint,
-            *distribution,
+            distribution.clone(),
         )],
         Some(&config.fee_payer.pubkey()),
     );
@@ -125,7 +240,7 @@ pub fn update_liquidity_distribution(
             oracle_pubkey,
             &config.fee_payer.pubkey(),
             token_mint,
-            *distribution,
+            distribution.clone(),
         )],
         Some(&config.fee_payer.pubkey()),
     );
@@ -1,12 +1,17 @@
 use solana_client::client_error::ClientError;
-use solana_program::pubkey::Pubkey;
+use solana_program::{clock::Slot, pubkey::Pubkey};
 use solana_sdk::{
     signature::{write_keypair_file, Keypair},
     signer::Signer,
     transaction::Transaction,
 };
 
-use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketsData};
+use everlend_registry::{
+    find_timelock_action_program_address, find_timelock_program_address,
+    instructions::{AddRegistryMarketData, UpdateRegistryData, UpdateRegistryMarketData},
+    state::{PauseFlags, RegistryMarkets, Role, Timelock, TimelockAction},
+};
+use solana_program::program_pack::Pack;
 
 use crate::utils::*;
 
@@ -61,16 +66,38 @@ pub fn update_registry(
     Ok(())
 }
 
-pub fn update_registry_markets(
+pub fn add_registry_market(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    data: AddRegistryMarketData,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_registry::instruction::add_registry_market(
+            &everlend_registry::id(),
+            registry_pubkey,
+            &config.fee_payer.pubkey(),
+            data,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn update_registry_market(
     config: &Config,
     registry_pubkey: &Pubkey,
-    data: UpdateRegistryMarketsData,
+    index: u8,
+    data: UpdateRegistryMarketData,
 ) -> Result<(), ClientError> {
     let tx = Transaction::new_with_payer(
-        &[everlend_registry::instruction::update_registry_markets(
+        &[everlend_registry::instruction::update_registry_market(
             &everlend_registry::id(),
             registry_pubkey,
             &config.fee_payer.pubkey(),
+            index,
             data,
         )],
         Some(&config.fee_payer.pubkey()),
@@ -81,6 +108,61 @@ pub fn update_registry_markets(
     Ok(())
 }
 
+/// Adds missing money markets and updates collateral pool markets of registered ones
+pub fn set_registry_markets(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    markets: Vec<AddRegistryMarketData>,
+) -> Result<(), ClientError> {
+    let registry_account = config.rpc_client.get_account(registry_pubkey)?;
+    let registry_markets = RegistryMarkets::unpack_from_slice(&registry_account.data).unwrap();
+
+    for data in markets {
+        let index = registry_markets
+            .markets
+            .iter()
+            .position(|market| market.money_market == data.money_market);
+
+        match index {
+            Some(index) => {
+                if registry_markets.markets[index].collateral_pool_market
+                    == data.collateral_pool_market
+                {
+                    continue;
+                }
+
+                update_registry_market(
+                    config,
+                    registry_pubkey,
+                    index as u8,
+                    UpdateRegistryMarketData {
+                        collateral_pool_market: Some(data.collateral_pool_market),
+                        status: None,
+                    },
+                )?;
+            }
+            None => add_registry_market(config, registry_pubkey, data)?,
+        }
+    }
+
+    Ok(())
+}
+
+pub fn migrate_registry(config: &Config, registry_pubkey: &Pubkey) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_registry::instruction::migrate_registry(
+            &everlend_registry::id(),
+            registry_pubkey,
+            &config.fee_payer.pubkey(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
 pub fn registry_update_manager(
     config: &Config,
     registry: &Pubkey,
@@ -88,12 +170,19 @@ pub fn registry_update_manager(
     new_manager: &Keypair,
 ) -> Result<(), ClientError> {
     let tx = Transaction::new_with_payer(
-        &[everlend_registry::instruction::update_manager(
-            &everlend_registry::id(),
-            registry,
-            &manager.pubkey(),
-            &new_manager.pubkey(),
-        )],
+        &[
+            everlend_registry::instruction::propose_manager(
+                &everlend_registry::id(),
+                registry,
+                &manager.pubkey(),
+                &new_manager.pubkey(),
+            ),
+            everlend_registry::instruction::accept_manager(
+                &everlend_registry::id(),
+                registry,
+                &new_manager.pubkey(),
+            ),
+        ],
         Some(&config.fee_payer.pubkey()),
     );
 
@@ -104,3 +193,162 @@ pub fn registry_update_manager(
 
     Ok(())
 }
+
+pub fn registry_cancel_manager_proposal(
+    config: &Config,
+    registry: &Pubkey,
+    manager: &Keypair,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_registry::instruction::cancel_manager_proposal(
+            &everlend_registry::id(),
+            registry,
+            &manager.pubkey(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref(), manager])?;
+
+    Ok(())
+}
+
+pub fn set_guardian(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    guardian: &Pubkey,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_registry::instruction::set_guardian(
+            &everlend_registry::id(),
+            registry_pubkey,
+            &config.fee_payer.pubkey(),
+            guardian,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn grant_role(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    holder: &Pubkey,
+    role: Role,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_registry::instruction::grant_role(
+            &everlend_registry::id(),
+            registry_pubkey,
+            &config.fee_payer.pubkey(),
+            holder,
+            role,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn revoke_role(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    holder: &Pubkey,
+    role: Role,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_registry::instruction::revoke_role(
+            &everlend_registry::id(),
+            registry_pubkey,
+            &config.fee_payer.pubkey(),
+            holder,
+            role,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn set_pause(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    pool: &Pubkey,
+    flags: PauseFlags,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_registry::instruction::set_pause(
+            &everlend_registry::id(),
+            registry_pubkey,
+            &config.fee_payer.pubkey(),
+            pool,
+            flags,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn set_timelock(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    proposer: &Pubkey,
+    delay: Slot,
+) -> Result<(), ClientError> {
+    let tx = Transaction::new_with_payer(
+        &[everlend_registry::instruction::set_timelock(
+            &everlend_registry::id(),
+            registry_pubkey,
+            &config.fee_payer.pubkey(),
+            proposer,
+            delay,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
+
+pub fn get_timelock(config: &Config, registry_pubkey: &Pubkey) -> Result<Timelock, ClientError> {
+    let (timelock, _) = find_timelock_program_address(&everlend_registry::id(), registry_pubkey);
+
+    config.get_account_unpack::<Timelock>(&timelock)
+}
+
+pub fn execute_timelock_action(
+    config: &Config,
+    registry_pubkey: &Pubkey,
+    action_id: u64,
+) -> Result<(), ClientError> {
+    let (timelock, _) = find_timelock_program_address(&everlend_registry::id(), registry_pubkey);
+    let (timelock_action, _) =
+        find_timelock_action_program_address(&everlend_registry::id(), &timelock, action_id);
+    let action = config.get_account_unpack::<TimelockAction>(&timelock_action)?;
+
+    let tx = Transaction::new_with_payer(
+        &[everlend_registry::instruction::execute_action(
+            &everlend_registry::id(),
+            registry_pubkey,
+            &action.proposer,
+            action_id,
+            &action.instruction(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    config.sign_and_send_and_confirm_transaction(tx, vec![config.fee_payer.as_ref()])?;
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
-use super::{CreateSafetyFundTokenAccountCommand, InitPoolMarketCommand};
+use super::{
+    CreateSafetyFundTokenAccountCommand, InitPoolMarketCommand, SetSafetyFundLimitCommand,
+};
 use crate::{print_commands, utils::Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
 
@@ -22,6 +24,7 @@ impl<'a> ToolkitCommand<'a> for IncomePoolCommand {
         vec![
             Box::new(CreateSafetyFundTokenAccountCommand),
             Box::new(InitPoolMarketCommand),
+            Box::new(SetSafetyFundLimitCommand),
         ]
     }
 
@@ -1,7 +1,9 @@
 mod cmd;
 mod create_safety_fund_token_account;
 mod init_pool_market;
+mod set_safety_fund_limit;
 
 pub use cmd::*;
 pub use create_safety_fund_token_account::*;
 pub use init_pool_market::*;
+pub use set_safety_fund_limit::*;
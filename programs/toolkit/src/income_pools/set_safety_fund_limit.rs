@@ -0,0 +1,76 @@
+use crate::helpers::set_safety_fund_limit;
+use crate::utils::{arg, arg_pubkey};
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use solana_clap_utils::input_parsers::{pubkey_of, value_of};
+use solana_program::program_pack::Pack;
+
+const ARG_MINT: &str = "mint";
+const ARG_MAX_DRAWDOWN: &str = "max-drawdown";
+const ARG_MAX_PERIOD_DRAWDOWN: &str = "max-period-drawdown";
+const ARG_DRAWDOWN_PERIOD: &str = "drawdown-period";
+
+#[derive(Clone, Copy)]
+pub struct SetSafetyFundLimitCommand;
+
+impl<'a> ToolkitCommand<'a> for SetSafetyFundLimitCommand {
+    fn get_name(&self) -> &'a str {
+        "set-safety-fund-limit"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Set the limits of the safety fund drawdowns"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_MINT, true),
+            arg(ARG_MAX_DRAWDOWN, true)
+                .value_name("DECIMAL")
+                .help("Maximum amount of a single drawdown (e.g. 1000 or 0 to disable drawdowns)"),
+            arg(ARG_MAX_PERIOD_DRAWDOWN, true)
+                .value_name("DECIMAL")
+                .help("Maximum amount drawn within a drawdown period (e.g. 5000)"),
+            arg(ARG_DRAWDOWN_PERIOD, true)
+                .value_name("NUMBER")
+                .help("Drawdown period in slots"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let initialized_accounts = config.get_initialized_accounts();
+
+        let arg_mint = pubkey_of(arg_matches, ARG_MINT).unwrap();
+        let arg_max_drawdown: f64 = value_of(arg_matches, ARG_MAX_DRAWDOWN).unwrap();
+        let arg_max_period_drawdown: f64 = value_of(arg_matches, ARG_MAX_PERIOD_DRAWDOWN).unwrap();
+        let drawdown_period: u64 = value_of(arg_matches, ARG_DRAWDOWN_PERIOD).unwrap();
+
+        let account = config.rpc_client.get_account(&arg_mint)?;
+        let mint_account = spl_token::state::Mint::unpack(&account.data).unwrap();
+        let max_drawdown_amount =
+            (arg_max_drawdown * (10_u64.pow(mint_account.decimals as u32) as f64)) as u64;
+        let max_period_drawdown_amount =
+            (arg_max_period_drawdown * (10_u64.pow(mint_account.decimals as u32) as f64)) as u64;
+
+        println!(
+            "Mint: {} max-drawdown: {} max-period-drawdown: {} drawdown-period: {}",
+            arg_mint, max_drawdown_amount, max_period_drawdown_amount, drawdown_period
+        );
+
+        set_safety_fund_limit(
+            config,
+            &initialized_accounts.income_pool_market,
+            &arg_mint,
+            max_drawdown_amount,
+            max_period_drawdown_amount,
+            drawdown_period,
+        )?;
+
+        Ok(())
+    }
+}
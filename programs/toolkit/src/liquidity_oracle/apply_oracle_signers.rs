@@ -0,0 +1,32 @@
+use crate::helpers::apply_oracle_signers;
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+
+#[derive(Clone, Copy)]
+pub struct ApplyOracleSignersCommand;
+
+impl<'a> ToolkitCommand<'a> for ApplyOracleSignersCommand {
+    fn get_name(&self) -> &'a str {
+        "apply-oracle-signers"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Apply delayed liquidity oracle signers update"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, _arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        apply_oracle_signers(config, &initialiazed_accounts.liquidity_oracle)?;
+
+        Ok(())
+    }
+}
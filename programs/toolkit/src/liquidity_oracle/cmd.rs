@@ -1,4 +1,7 @@
-use super::{CreateLiquidityOracleCommand, GetTokenOracleAccountCommand, UpdateAuthorityCommand};
+use super::{
+    ApplyOracleSignersCommand, CreateLiquidityOracleCommand, GetTokenOracleAccountCommand,
+    SetOracleSignersCommand, SetTokenOracleLimitsCommand, UpdateAuthorityCommand,
+};
 use crate::{print_commands, utils::Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
 
@@ -23,6 +26,9 @@ impl<'a> ToolkitCommand<'a> for LiquidityOracleCommand {
             Box::new(CreateLiquidityOracleCommand),
             Box::new(UpdateAuthorityCommand),
             Box::new(GetTokenOracleAccountCommand),
+            Box::new(SetOracleSignersCommand),
+            Box::new(ApplyOracleSignersCommand),
+            Box::new(SetTokenOracleLimitsCommand),
         ]
     }
 
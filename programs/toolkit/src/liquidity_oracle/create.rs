@@ -31,7 +31,8 @@ impl<'a> ToolkitCommand<'a> for CreateLiquidityOracleCommand {
 
         let mut initialiazed_accounts = config.get_initialized_accounts();
 
-        let liquidity_oracle_pubkey = init_liquidity_oracle(config, keypair)?;
+        let liquidity_oracle_pubkey =
+            init_liquidity_oracle(config, keypair, &initialiazed_accounts.registry)?;
 
         initialiazed_accounts.liquidity_oracle = liquidity_oracle_pubkey;
 
@@ -1,8 +1,10 @@
 use crate::utils::arg_pubkey;
 use crate::{Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
-use everlend_liquidity_oracle::find_token_oracle_program_address;
-use everlend_liquidity_oracle::state::TokenOracle;
+use everlend_liquidity_oracle::state::{TokenOracle, TokenOracleLimits};
+use everlend_liquidity_oracle::{
+    find_token_oracle_limits_program_address, find_token_oracle_program_address,
+};
 use solana_clap_utils::input_parsers::pubkey_of;
 
 const ARG_MINT: &str = "mint";
@@ -41,6 +43,28 @@ impl<'a> ToolkitCommand<'a> for GetTokenOracleAccountCommand {
         let oracle: TokenOracle = config.get_account_unpack(&token_oracle_pubkey)?;
         println!("{:#?}", oracle);
 
+        let (token_oracle_limits_pubkey, _) = find_token_oracle_limits_program_address(
+            &everlend_liquidity_oracle::id(),
+            &acc.liquidity_oracle,
+            &mint,
+        );
+
+        let limits_account = config
+            .rpc_client
+            .get_account_with_commitment(
+                &token_oracle_limits_pubkey,
+                config.rpc_client.commitment(),
+            )?
+            .value;
+        match limits_account {
+            Some(_) => {
+                let limits: TokenOracleLimits =
+                    config.get_account_unpack(&token_oracle_limits_pubkey)?;
+                println!("{:#?}", limits);
+            }
+            None => println!("Token oracle limits are not set"),
+        }
+
         Ok(())
     }
 }
@@ -1,9 +1,15 @@
+mod apply_oracle_signers;
 mod cmd;
 mod create;
 mod get_account;
+mod set_oracle_signers;
+mod set_token_oracle_limits;
 mod update_authority;
 
+pub use apply_oracle_signers::*;
 pub use cmd::*;
 pub use create::*;
 pub use get_account::*;
+pub use set_oracle_signers::*;
+pub use set_token_oracle_limits::*;
 pub use update_authority::*;
@@ -0,0 +1,58 @@
+use crate::helpers::set_oracle_signers;
+use crate::utils::{arg_amount, arg_multiple};
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use solana_clap_utils::input_parsers::{pubkeys_of, value_of};
+
+const ARG_SIGNERS: &str = "signers";
+const ARG_QUORUM: &str = "quorum";
+const ARG_WINDOW: &str = "window";
+
+#[derive(Clone, Copy)]
+pub struct SetOracleSignersCommand;
+
+impl<'a> ToolkitCommand<'a> for SetOracleSignersCommand {
+    fn get_name(&self) -> &'a str {
+        "set-oracle-signers"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Set liquidity oracle signers and quorum"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_multiple(ARG_SIGNERS, true).help("Oracle signer pubkeys"),
+            arg_amount(ARG_QUORUM, true).help("Submissions required to update distribution"),
+            arg_amount(ARG_WINDOW, true).help("Submission window in slots"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let signers = pubkeys_of(arg_matches, ARG_SIGNERS).unwrap();
+        let quorum = value_of::<u8>(arg_matches, ARG_QUORUM).unwrap();
+        let window = value_of::<u64>(arg_matches, ARG_WINDOW).unwrap();
+
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        println!(
+            "oracle {} signers {:?} quorum {} window {}",
+            initialiazed_accounts.liquidity_oracle, signers, quorum, window
+        );
+
+        set_oracle_signers(
+            config,
+            &initialiazed_accounts.liquidity_oracle,
+            signers,
+            quorum,
+            window,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::helpers::set_token_oracle_limits;
+use crate::utils::{arg_amount, arg_pubkey};
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use solana_clap_utils::input_parsers::{pubkey_of, value_of};
+
+const ARG_MINT: &str = "mint";
+const ARG_MAX_DISTRIBUTION_CHANGE: &str = "max-distribution-change";
+const ARG_MIN_UPDATE_INTERVAL: &str = "min-update-interval";
+const ARG_FROZEN: &str = "frozen";
+
+#[derive(Clone, Copy)]
+pub struct SetTokenOracleLimitsCommand;
+
+impl<'a> ToolkitCommand<'a> for SetTokenOracleLimitsCommand {
+    fn get_name(&self) -> &'a str {
+        "set-token-oracle-limits"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Set token oracle distribution update limits"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_MINT, true).help("Token mint"),
+            arg_amount(ARG_MAX_DISTRIBUTION_CHANGE, false)
+                .help("Max market distribution change per update [default: unlimited]"),
+            arg_amount(ARG_MIN_UPDATE_INTERVAL, false)
+                .help("Min slots between updates [default: unlimited]"),
+            Arg::with_name(ARG_FROZEN)
+                .long(ARG_FROZEN)
+                .takes_value(false)
+                .help("Freeze distribution updates"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let mint = pubkey_of(arg_matches, ARG_MINT).unwrap();
+        let max_distribution_change =
+            value_of::<u64>(arg_matches, ARG_MAX_DISTRIBUTION_CHANGE).unwrap_or_default();
+        let min_update_interval =
+            value_of::<u64>(arg_matches, ARG_MIN_UPDATE_INTERVAL).unwrap_or_default();
+        let frozen = arg_matches.is_present(ARG_FROZEN);
+
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        println!(
+            "mint {} max distribution change {} min update interval {} frozen {}",
+            mint, max_distribution_change, min_update_interval, frozen
+        );
+
+        set_token_oracle_limits(
+            config,
+            &initialiazed_accounts.liquidity_oracle,
+            &mint,
+            max_distribution_change,
+            min_update_interval,
+            frozen,
+        )?;
+
+        Ok(())
+    }
+}
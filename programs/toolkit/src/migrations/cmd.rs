@@ -4,7 +4,8 @@ use clap::{Arg, ArgMatches};
 
 use super::{
     MigrateDepositorCommand, MigrateGeneralPoolCommand, MigrateLiquidityOracleCommand,
-    MigratePoolMarketCommand, MigrateRewardsPoolCommand, MigrateRewardsRootCommand,
+    MigratePoolMarketCommand, MigrateRegistryCommand, MigrateRewardsPoolCommand,
+    MigrateRewardsRootCommand,
 };
 
 #[derive(Clone, Copy)]
@@ -25,6 +26,7 @@ impl<'a> ToolkitCommand<'a> for MigrationsCommand {
 
     fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
         vec![
+            Box::new(MigrateRegistryCommand),
             Box::new(MigrateGeneralPoolCommand),
             Box::new(MigrateDepositorCommand),
             Box::new(MigratePoolMarketCommand),
@@ -1,12 +1,17 @@
-use crate::helpers::{bulk_migrate_pool_borrow_authority, bulk_migrate_pool_withdraw_authority};
+use crate::helpers::{
+    bulk_migrate_pool_borrow_authority, bulk_migrate_pool_withdraw_authority,
+    migrate_collateral_pool_market,
+};
 use crate::utils::get_asset_maps;
 use crate::{utils::Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
 use everlend_collateral_pool::find_pool_program_address;
-use everlend_collateral_pool::state::{PoolBorrowAuthority, PoolWithdrawAuthority};
+use everlend_collateral_pool::state::{
+    DeprecatedPoolMarket, PoolBorrowAuthority, PoolWithdrawAuthority,
+};
 use everlend_utils::find_program_address;
 use solana_client::client_error::ClientError;
-use solana_program::pubkey::Pubkey;
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
 
 pub struct MigrateCollateralPoolCommand;
 
@@ -28,9 +33,22 @@ impl<'a> ToolkitCommand<'a> for MigrateCollateralPoolCommand {
     }
 
     fn handle(&self, config: &Config, _arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
-        println!("Started Collateral pool withdraw authorities migration");
         let default_accounts = config.get_default_accounts();
         let initialiazed_accounts = config.get_initialized_accounts();
+
+        println!("Started Collateral pool markets migration");
+        for pool_market in initialiazed_accounts.collateral_pool_markets.iter() {
+            let data = config.rpc_client.get_account_data(pool_market)?;
+            if data.len() == DeprecatedPoolMarket::LEN {
+                migrate_collateral_pool_market(
+                    config,
+                    pool_market,
+                    &initialiazed_accounts.registry,
+                )?;
+            }
+        }
+
+        println!("Started Collateral pool withdraw authorities migration");
         let (_, collateral_mint_map) = get_asset_maps(default_accounts);
 
         let mut withdraw_authorities = vec![];
@@ -1,6 +1,9 @@
-use crate::helpers::migrate_general_pool_account;
+use crate::helpers::{migrate_general_pool_account, migrate_income_pool_market};
 use crate::{utils::Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
+use everlend_general_pool::state::DeprecatedPoolMarket;
+use everlend_income_pools::state::DeprecatedIncomePoolMarket;
+use solana_program::program_pack::Pack;
 
 pub struct MigrateGeneralPoolCommand;
 
@@ -22,8 +25,23 @@ impl<'a> ToolkitCommand<'a> for MigrateGeneralPoolCommand {
     }
 
     fn handle(&self, config: &Config, _arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
-        println!("Migrate withdraw requests");
-        migrate_general_pool_account(config)?;
+        let acc = config.get_initialized_accounts();
+
+        let data = config
+            .rpc_client
+            .get_account_data(&acc.general_pool_market)?;
+        if data.len() == DeprecatedPoolMarket::LEN {
+            println!("Migrate pool market");
+            migrate_general_pool_account(config, &acc.general_pool_market)?;
+        }
+
+        let data = config
+            .rpc_client
+            .get_account_data(&acc.income_pool_market)?;
+        if data.len() == DeprecatedIncomePoolMarket::LEN {
+            println!("Migrate income pool market");
+            migrate_income_pool_market(config, &acc.income_pool_market, &acc.general_pool_market)?;
+        }
         println!("Finished!");
 
         Ok(())
@@ -1,6 +1,8 @@
+use crate::helpers::migrate_liquidity_oracle;
 use crate::{utils::Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
-use everlend_liquidity_oracle::instruction;
+use everlend_liquidity_oracle::{instruction, state::DeprecatedLiquidityOracle};
+use solana_program::program_pack::Pack;
 use solana_sdk::transaction::Transaction;
 pub struct MigrateLiquidityOracleCommand;
 
@@ -25,6 +27,11 @@ impl<'a> ToolkitCommand<'a> for MigrateLiquidityOracleCommand {
         println!("Started LiquidityOracle migration");
         let acc = config.get_initialized_accounts();
 
+        let data = config.rpc_client.get_account_data(&acc.liquidity_oracle)?;
+        if data.len() == DeprecatedLiquidityOracle::LEN {
+            migrate_liquidity_oracle(config, &acc.liquidity_oracle, &acc.registry)?;
+        }
+
         for token in acc.token_accounts {
             let tx = Transaction::new_with_payer(
                 &[instruction::migrate(
@@ -4,6 +4,7 @@ mod depositor;
 mod general_pool;
 mod liquidity_oracle;
 mod pool_market;
+mod registry;
 mod rewards_pool;
 mod rewards_root;
 
@@ -13,5 +14,6 @@ pub use depositor::*;
 pub use general_pool::*;
 pub use liquidity_oracle::*;
 pub use pool_market::*;
+pub use registry::*;
 pub use rewards_pool::*;
 pub use rewards_root::*;
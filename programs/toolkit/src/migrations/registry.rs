@@ -0,0 +1,32 @@
+use crate::helpers::migrate_registry;
+use crate::{utils::Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+
+pub struct MigrateRegistryCommand;
+
+impl<'a> ToolkitCommand<'a> for MigrateRegistryCommand {
+    fn get_name(&self) -> &'a str {
+        "registry"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Migrate Registry markets to the market list"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, _arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        println!("Started Registry migration");
+        let acc = config.get_initialized_accounts();
+        migrate_registry(config, &acc.registry)?;
+        println!("Migration of Registry finished");
+
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
+use crate::helpers::migrate_rewards_root;
 use crate::{utils::Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
-use everlend_rewards::state::RewardsRoot;
-use solana_sdk::transaction::Transaction;
+use everlend_rewards::state::{DeprecatedRewardsRoot, RewardsRoot};
+use solana_program::program_pack::Pack;
 
 pub struct MigrateRewardsRootCommand;
 
@@ -25,6 +26,11 @@ impl<'a> ToolkitCommand<'a> for MigrateRewardsRootCommand {
     fn handle(&self, config: &Config, _arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
         let acc = config.get_initialized_accounts();
 
+        let data = config.rpc_client.get_account_data(&acc.rewards_root)?;
+        if data.len() == DeprecatedRewardsRoot::LEN {
+            migrate_rewards_root(config, &acc.rewards_root)?;
+        }
+
         let r: RewardsRoot = config.get_account_unpack(&acc.rewards_root)?;
         println!("Migration of rewards root: \n{:?}", &r);
 
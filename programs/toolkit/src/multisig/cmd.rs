@@ -1,5 +1,6 @@
 use super::{
-    ApproveCommand, CreateMultisigCommand, ExecuteCommand, InfoCommand, ProposeUpgradeCommand,
+    ApproveCommand, CreateMultisigCommand, ExecuteCommand, InfoCommand,
+    ProposeTimelockActionCommand, ProposeUpgradeCommand,
 };
 use crate::{print_commands, Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
@@ -24,6 +25,7 @@ impl<'a> ToolkitCommand<'a> for MultisigCommand {
         vec![
             Box::new(CreateMultisigCommand),
             Box::new(ProposeUpgradeCommand),
+            Box::new(ProposeTimelockActionCommand),
             Box::new(ApproveCommand),
             Box::new(ExecuteCommand),
             Box::new(InfoCommand),
@@ -3,6 +3,7 @@ mod cmd;
 mod create;
 mod execute;
 mod info;
+mod propose_timelock_action;
 mod propose_upgrade;
 
 pub use approve::*;
@@ -10,4 +11,5 @@ pub use cmd::*;
 pub use create::*;
 pub use execute::*;
 pub use info::*;
+pub use propose_timelock_action::*;
 pub use propose_upgrade::*;
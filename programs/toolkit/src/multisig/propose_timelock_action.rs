@@ -0,0 +1,214 @@
+use crate::helpers::{create_transaction, get_multisig_program_address, get_timelock};
+use crate::utils::{arg, arg_pubkey};
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use everlend_general_pool::state::{Pool, SetPoolConfigParams};
+use everlend_general_pool::{
+    find_pool_borrow_authority_program_address, find_pool_program_address,
+};
+use everlend_registry::find_timelock_authority_program_address;
+use everlend_utils::find_program_address;
+use solana_clap_utils::input_parsers::{pubkey_of, value_of};
+use solana_program::program_pack::Pack;
+
+const ARG_MULTISIG: &str = "multisig";
+const ARG_ACTION: &str = "action";
+const ARG_CANCEL: &str = "cancel";
+const ARG_MINT: &str = "mint";
+const ARG_MIN_DEPOSIT: &str = "min-deposit";
+const ARG_MIN_WITHDRAW: &str = "min-withdraw";
+const ARG_WITHDRAW_DELAY: &str = "withdraw-delay";
+const ARG_EXIT_FEE: &str = "exit-fee";
+const ARG_AMOUNT: &str = "amount";
+const ARG_DESTINATION: &str = "destination";
+
+#[derive(Clone, Copy)]
+pub struct ProposeTimelockActionCommand;
+
+impl<'a> ToolkitCommand<'a> for ProposeTimelockActionCommand {
+    fn get_name(&self) -> &'a str {
+        "propose-timelock-action"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Propose to queue or cancel timelock action"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_MULTISIG, true).help("Multisig pubkey"),
+            arg(ARG_ACTION, false)
+                .possible_values(&["accept-manager", "set-pool-config", "drawdown-safety-fund"])
+                .required_unless(ARG_CANCEL)
+                .help("Action executed by the timelock authority"),
+            arg(ARG_CANCEL, false)
+                .value_name("NUMBER")
+                .help("Cancel the queued action with the id"),
+            arg_pubkey(ARG_MINT, false)
+                .help("Pool mint for set-pool-config and drawdown-safety-fund"),
+            arg(ARG_MIN_DEPOSIT, false)
+                .value_name("DECIMAL")
+                .help("Minimum amount for deposit (e.g. 0.01 or 1)"),
+            arg(ARG_MIN_WITHDRAW, false)
+                .value_name("DECIMAL")
+                .help("Minimum amount for withdraw (e.g. 0.01 or 1)"),
+            arg(ARG_WITHDRAW_DELAY, false)
+                .value_name("SLOTS")
+                .help("Withdraw delay in slots"),
+            arg(ARG_EXIT_FEE, false)
+                .value_name("BPS")
+                .help("Instant withdraw fee in basis points"),
+            arg(ARG_AMOUNT, false)
+                .value_name("DECIMAL")
+                .help("Safety fund drawdown amount (e.g. 0.01 or 1)"),
+            arg_pubkey(ARG_DESTINATION, false).help(
+                "Depositor transit of the safety fund drawdown, the general pool token account \
+                 by default",
+            ),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let multisig_pubkey = pubkey_of(arg_matches, ARG_MULTISIG).unwrap();
+
+        let default_accounts = config.get_default_accounts();
+        let initialized_accounts = config.get_initialized_accounts();
+
+        // Multisig signer is the timelock proposer and pays for the action account
+        let (pda, _) =
+            get_multisig_program_address(&default_accounts.multisig_program_id, &multisig_pubkey);
+
+        if let Some(action_id) = value_of::<u64>(arg_matches, ARG_CANCEL) {
+            let cancel_instruction = everlend_registry::instruction::cancel_action(
+                &everlend_registry::id(),
+                &initialized_accounts.registry,
+                &pda,
+                action_id,
+            );
+
+            let transaction_pubkey =
+                create_transaction(config, &multisig_pubkey, cancel_instruction)?;
+
+            println!("transaction_pubkey = {:?}", transaction_pubkey);
+
+            return Ok(());
+        }
+
+        let (timelock_authority, _) = find_timelock_authority_program_address(
+            &everlend_registry::id(),
+            &initialized_accounts.registry,
+        );
+
+        let action_instruction = match arg_matches.value_of(ARG_ACTION).unwrap() {
+            "accept-manager" => everlend_general_pool::instruction::accept_manager(
+                &everlend_general_pool::id(),
+                &initialized_accounts.general_pool_market,
+                &timelock_authority,
+            ),
+            "set-pool-config" => {
+                let mint = pubkey_of(arg_matches, ARG_MINT)
+                    .ok_or_else(|| anyhow::anyhow!("mint is required"))?;
+                let deposit_minimum: Option<f64> = value_of(arg_matches, ARG_MIN_DEPOSIT);
+                let withdraw_minimum: Option<f64> = value_of(arg_matches, ARG_MIN_WITHDRAW);
+                let withdraw_delay: Option<u64> = value_of(arg_matches, ARG_WITHDRAW_DELAY);
+                let exit_fee: Option<u16> = value_of(arg_matches, ARG_EXIT_FEE);
+
+                let account = config.rpc_client.get_account(&mint)?;
+                let mint_account = spl_token::state::Mint::unpack(&account.data).unwrap();
+                let multiplier = 10_u64.pow(mint_account.decimals as u32) as f64;
+
+                let (pool, _) = find_pool_program_address(
+                    &everlend_general_pool::id(),
+                    &initialized_accounts.general_pool_market,
+                    &mint,
+                );
+
+                everlend_general_pool::instruction::set_pool_config(
+                    &everlend_general_pool::id(),
+                    &initialized_accounts.general_pool_market,
+                    &pool,
+                    &timelock_authority,
+                    SetPoolConfigParams {
+                        deposit_minimum: deposit_minimum.map(|min| (min * multiplier) as u64),
+                        withdraw_minimum: withdraw_minimum.map(|min| (min * multiplier) as u64),
+                        performance_fee: None,
+                        management_fee: None,
+                        withdraw_delay: withdraw_delay.map(Some),
+                        exit_fee,
+                        pool_deposit_cap: None,
+                        user_deposit_cap: None,
+                    },
+                )
+            }
+            "drawdown-safety-fund" => {
+                let mint = pubkey_of(arg_matches, ARG_MINT)
+                    .ok_or_else(|| anyhow::anyhow!("mint is required"))?;
+                let amount: f64 = value_of(arg_matches, ARG_AMOUNT)
+                    .ok_or_else(|| anyhow::anyhow!("amount is required"))?;
+                let arg_destination = pubkey_of(arg_matches, ARG_DESTINATION);
+
+                let account = config.rpc_client.get_account(&mint)?;
+                let mint_account = spl_token::state::Mint::unpack(&account.data).unwrap();
+                let amount = (amount * (10_u64.pow(mint_account.decimals as u32) as f64)) as u64;
+
+                let (pool, _) = find_pool_program_address(
+                    &everlend_general_pool::id(),
+                    &initialized_accounts.general_pool_market,
+                    &mint,
+                );
+                let destination = match arg_destination {
+                    Some(destination) => destination,
+                    None => config.get_account_unpack::<Pool>(&pool)?.token_account,
+                };
+
+                // Depositor transit is checked against the general pool borrow authority
+                let (depositor_authority, _) = find_program_address(
+                    &everlend_depositor::id(),
+                    &initialized_accounts.depositor,
+                );
+                let (pool_borrow_authority, _) = find_pool_borrow_authority_program_address(
+                    &everlend_general_pool::id(),
+                    &pool,
+                    &depositor_authority,
+                );
+                let depositor = arg_destination
+                    .map(|_| (&initialized_accounts.depositor, &pool_borrow_authority));
+
+                everlend_income_pools::instruction::drawdown_safety_fund(
+                    &everlend_income_pools::id(),
+                    &initialized_accounts.income_pool_market,
+                    &mint,
+                    &pool,
+                    &destination,
+                    depositor,
+                    &initialized_accounts.registry,
+                    amount,
+                )
+            }
+            _ => return Err(anyhow::anyhow!("wrong action")),
+        };
+
+        // The action id is taken at execution of the multisig transaction,
+        // so it's valid only if no other action is queued before
+        let timelock = get_timelock(config, &initialized_accounts.registry)?;
+        let queue_instruction = everlend_registry::instruction::queue_action(
+            &everlend_registry::id(),
+            &initialized_accounts.registry,
+            &pda,
+            timelock.action_count,
+            &action_instruction,
+        );
+
+        let transaction_pubkey = create_transaction(config, &multisig_pubkey, queue_instruction)?;
+
+        println!("action_id = {}", timelock.action_count);
+        println!("transaction_pubkey = {:?}", transaction_pubkey);
+
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{print_commands, utils::Config, ToolkitCommand};
 use clap::{Arg, ArgMatches};
 
-use super::{InitRegistryCommand, SetRegistryCommand};
+use super::{
+    ExecuteActionCommand, InitRegistryCommand, SetGuardianCommand, SetPauseCommand,
+    SetRegistryCommand, SetRoleCommand, SetTimelockCommand,
+};
 
 #[derive(Clone, Copy)]
 pub struct RegistryCommand;
@@ -20,7 +23,15 @@ impl<'a> ToolkitCommand<'a> for RegistryCommand {
     }
 
     fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
-        vec![Box::new(InitRegistryCommand), Box::new(SetRegistryCommand)]
+        vec![
+            Box::new(InitRegistryCommand),
+            Box::new(SetRegistryCommand),
+            Box::new(SetGuardianCommand),
+            Box::new(SetPauseCommand),
+            Box::new(SetRoleCommand),
+            Box::new(SetTimelockCommand),
+            Box::new(ExecuteActionCommand),
+        ]
     }
 
     fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
@@ -0,0 +1,43 @@
+use crate::helpers::execute_timelock_action;
+use crate::utils::arg;
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use solana_clap_utils::input_parsers::value_of;
+
+const ARG_ACTION_ID: &str = "action-id";
+
+#[derive(Clone, Copy)]
+pub struct ExecuteActionCommand;
+
+impl<'a> ToolkitCommand<'a> for ExecuteActionCommand {
+    fn get_name(&self) -> &'a str {
+        "execute-action"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Execute queued timelock action"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![arg(ARG_ACTION_ID, true)
+            .value_name("NUMBER")
+            .help("Timelock action id")]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let action_id: u64 = value_of(arg_matches, ARG_ACTION_ID).unwrap();
+
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        println!("action {}", action_id);
+
+        execute_timelock_action(config, &initialiazed_accounts.registry, action_id)?;
+
+        Ok(())
+    }
+}
@@ -1,12 +1,12 @@
-use crate::helpers::{init_registry, update_registry, update_registry_markets};
+use crate::helpers::{init_registry, set_registry_markets, update_registry};
 use crate::utils::REFRESH_INCOME_INTERVAL;
 use crate::{
     utils::{arg_keypair, Config},
     ToolkitCommand,
 };
 use clap::{Arg, ArgMatches};
-use everlend_registry::instructions::{UpdateRegistryData, UpdateRegistryMarketsData};
-use everlend_registry::state::DistributionPubkeys;
+use everlend_registry::instructions::{AddRegistryMarketData, UpdateRegistryData};
+use everlend_utils::integrations::MoneyMarket;
 use solana_clap_utils::input_parsers::keypair_of;
 
 const ARG_REGISTRY: &str = "registry";
@@ -41,19 +41,29 @@ impl<'a> ToolkitCommand<'a> for InitRegistryCommand {
         let default_accounts = config.get_default_accounts();
         let initialized_accounts = config.get_initialized_accounts();
 
-        let mut money_market_program_ids = DistributionPubkeys::default();
-        money_market_program_ids[0] = default_accounts.port_finance.program_id;
-        money_market_program_ids[1] = default_accounts.larix.program_id;
-        money_market_program_ids[2] = default_accounts.solend.program_id;
-        money_market_program_ids[3] = default_accounts.tulip.program_id;
-        money_market_program_ids[4] = default_accounts.francium.program_id;
-        money_market_program_ids[5] = default_accounts.jet.program_id;
-        money_market_program_ids[6] = default_accounts.frakt.program_id;
-
-        let mut collateral_pool_markets = DistributionPubkeys::default();
-        let initialized_collateral_pool_markets = &initialized_accounts.collateral_pool_markets;
-        collateral_pool_markets[..initialized_collateral_pool_markets.len()]
-            .copy_from_slice(initialized_collateral_pool_markets);
+        let markets = vec![
+            (
+                default_accounts.port_finance.program_id,
+                MoneyMarket::PortFinance,
+            ),
+            (default_accounts.larix.program_id, MoneyMarket::Larix),
+            (default_accounts.solend.program_id, MoneyMarket::Solend),
+            (default_accounts.tulip.program_id, MoneyMarket::Tulip),
+            (default_accounts.francium.program_id, MoneyMarket::Francium),
+            (default_accounts.jet.program_id, MoneyMarket::Jet),
+            (default_accounts.frakt.program_id, MoneyMarket::Frakt),
+        ]
+        .into_iter()
+        .map(|(money_market, adapter)| AddRegistryMarketData {
+            money_market,
+            collateral_pool_market: initialized_accounts
+                .collateral_pool_markets
+                .get(adapter as usize)
+                .copied()
+                .unwrap_or_default(),
+            adapter,
+        })
+        .collect();
 
         update_registry(
             config,
@@ -66,14 +76,7 @@ impl<'a> ToolkitCommand<'a> for InitRegistryCommand {
             },
         )?;
 
-        update_registry_markets(
-            config,
-            &registry_pubkey,
-            UpdateRegistryMarketsData {
-                money_markets: Some(money_market_program_ids),
-                collateral_pool_markets: Some(collateral_pool_markets),
-            },
-        )?;
+        set_registry_markets(config, &registry_pubkey, markets)?;
 
         let mut initialized_accounts = config.get_initialized_accounts();
         initialized_accounts.payer = payer_pubkey;
@@ -1,7 +1,17 @@
 mod cmd;
+mod execute_action;
 mod init;
 mod set;
+mod set_guardian;
+mod set_pause;
+mod set_role;
+mod set_timelock;
 
 pub use cmd::*;
+pub use execute_action::*;
 pub use init::*;
 pub use set::*;
+pub use set_guardian::*;
+pub use set_pause::*;
+pub use set_role::*;
+pub use set_timelock::*;
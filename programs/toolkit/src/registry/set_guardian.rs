@@ -0,0 +1,41 @@
+use crate::helpers::set_guardian;
+use crate::utils::arg_pubkey;
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use solana_clap_utils::input_parsers::pubkey_of;
+
+const ARG_GUARDIAN: &str = "guardian";
+
+#[derive(Clone, Copy)]
+pub struct SetGuardianCommand;
+
+impl<'a> ToolkitCommand<'a> for SetGuardianCommand {
+    fn get_name(&self) -> &'a str {
+        "set-guardian"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Set registry guardian"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![arg_pubkey(ARG_GUARDIAN, true).help("Guardian")]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let guardian = pubkey_of(arg_matches, ARG_GUARDIAN).unwrap();
+
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        println!("guardian {}", guardian);
+
+        set_guardian(config, &initialiazed_accounts.registry, &guardian)?;
+
+        Ok(())
+    }
+}
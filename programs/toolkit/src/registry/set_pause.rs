@@ -0,0 +1,70 @@
+use crate::helpers::set_pause;
+use crate::utils::arg_pubkey;
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use everlend_registry::state::PauseFlags;
+use solana_clap_utils::input_parsers::pubkey_of;
+
+const ARG_POOL: &str = "pool";
+const ARG_DEPOSIT: &str = "deposit";
+const ARG_WITHDRAW: &str = "withdraw";
+const ARG_BORROW: &str = "borrow";
+const ARG_REBALANCING: &str = "rebalancing";
+
+#[derive(Clone, Copy)]
+pub struct SetPauseCommand;
+
+impl<'a> ToolkitCommand<'a> for SetPauseCommand {
+    fn get_name(&self) -> &'a str {
+        "set-pause"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Set paused operations of the pool, omitted flags are unpaused"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_POOL, false).help("Pool [default: global pause]"),
+            Arg::with_name(ARG_DEPOSIT)
+                .long(ARG_DEPOSIT)
+                .takes_value(false)
+                .help("Pause deposits"),
+            Arg::with_name(ARG_WITHDRAW)
+                .long(ARG_WITHDRAW)
+                .takes_value(false)
+                .help("Pause withdrawals"),
+            Arg::with_name(ARG_BORROW)
+                .long(ARG_BORROW)
+                .takes_value(false)
+                .help("Pause borrows"),
+            Arg::with_name(ARG_REBALANCING)
+                .long(ARG_REBALANCING)
+                .takes_value(false)
+                .help("Pause rebalancing"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let pool = pubkey_of(arg_matches, ARG_POOL).unwrap_or_default();
+        let flags = PauseFlags {
+            deposit: arg_matches.is_present(ARG_DEPOSIT),
+            withdraw: arg_matches.is_present(ARG_WITHDRAW),
+            borrow: arg_matches.is_present(ARG_BORROW),
+            rebalancing: arg_matches.is_present(ARG_REBALANCING),
+        };
+
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        println!("pool {} flags {:?}", pool, flags);
+
+        set_pause(config, &initialiazed_accounts.registry, &pool, flags)?;
+
+        Ok(())
+    }
+}
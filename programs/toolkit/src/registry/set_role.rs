@@ -0,0 +1,63 @@
+use crate::helpers::{grant_role, revoke_role};
+use crate::utils::{arg, arg_pubkey};
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use everlend_registry::state::Role;
+use solana_clap_utils::input_parsers::pubkey_of;
+
+const ARG_HOLDER: &str = "holder";
+const ARG_ROLE: &str = "role";
+const ARG_REVOKE: &str = "revoke";
+
+#[derive(Clone, Copy)]
+pub struct SetRoleCommand;
+
+impl<'a> ToolkitCommand<'a> for SetRoleCommand {
+    fn get_name(&self) -> &'a str {
+        "set-role"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Grant or revoke registry role"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_HOLDER, true).help("Role holder"),
+            arg(ARG_ROLE, true)
+                .possible_values(&["admin", "risk", "operator"])
+                .help("Role"),
+            Arg::with_name(ARG_REVOKE)
+                .long(ARG_REVOKE)
+                .takes_value(false)
+                .help("Revoke the role"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let holder = pubkey_of(arg_matches, ARG_HOLDER).unwrap();
+        let role = match arg_matches.value_of(ARG_ROLE).unwrap() {
+            "admin" => Role::Admin,
+            "risk" => Role::Risk,
+            "operator" => Role::Operator,
+            _ => return Err(anyhow::anyhow!("wrong role")),
+        };
+
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        println!("holder {} role {:?}", holder, role);
+
+        if arg_matches.is_present(ARG_REVOKE) {
+            revoke_role(config, &initialiazed_accounts.registry, &holder, role)?;
+        } else {
+            grant_role(config, &initialiazed_accounts.registry, &holder, role)?;
+        }
+
+        Ok(())
+    }
+}
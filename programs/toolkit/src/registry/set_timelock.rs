@@ -0,0 +1,48 @@
+use crate::helpers::set_timelock;
+use crate::utils::{arg, arg_pubkey};
+use crate::{Config, ToolkitCommand};
+use clap::{Arg, ArgMatches};
+use solana_clap_utils::input_parsers::{pubkey_of, value_of};
+
+const ARG_PROPOSER: &str = "proposer";
+const ARG_DELAY: &str = "delay";
+
+#[derive(Clone, Copy)]
+pub struct SetTimelockCommand;
+
+impl<'a> ToolkitCommand<'a> for SetTimelockCommand {
+    fn get_name(&self) -> &'a str {
+        "set-timelock"
+    }
+
+    fn get_description(&self) -> &'a str {
+        "Set timelock proposer and delay"
+    }
+
+    fn get_args(&self) -> Vec<Arg<'a, 'a>> {
+        vec![
+            arg_pubkey(ARG_PROPOSER, true).help("Proposer allowed to queue and cancel actions"),
+            arg(ARG_DELAY, true)
+                .value_name("SLOTS")
+                .help("Delay between queueing and execution in slots"),
+        ]
+    }
+
+    fn get_subcommands(&self) -> Vec<Box<dyn ToolkitCommand<'a>>> {
+        vec![]
+    }
+
+    fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
+        let arg_matches = arg_matches.unwrap();
+        let proposer = pubkey_of(arg_matches, ARG_PROPOSER).unwrap();
+        let delay: u64 = value_of(arg_matches, ARG_DELAY).unwrap();
+
+        let initialiazed_accounts = config.get_initialized_accounts();
+
+        println!("proposer {} delay {}", proposer, delay);
+
+        set_timelock(config, &initialiazed_accounts.registry, &proposer, delay)?;
+
+        Ok(())
+    }
+}
@@ -174,12 +174,14 @@ impl<'a> ToolkitCommand<'a> for TestCommand {
             depositor_deposit(
                 config,
                 &registry,
+                &general_pool_market,
                 &depositor,
                 &sol.mint,
                 &sol.collateral_pools[i].token_mint,
-                &registry_markets.money_markets[i],
-                integrations::deposit_accounts(&registry_markets.money_markets[i], &pubkeys),
+                &registry_markets.markets[i].money_market,
+                integrations::deposit_accounts(&registry_markets.markets[i].money_market, &pubkeys),
                 everlend_depositor::utils::collateral_pool_deposit_accounts(
+                    &registry,
                     &collateral_pool_markets[i],
                     &sol.collateral_pools[i].token_mint,
                     &sol.collateral_pools[i].pool_token_account,
@@ -200,14 +202,19 @@ impl<'a> ToolkitCommand<'a> for TestCommand {
             depositor_withdraw(
                 config,
                 &registry,
+                &general_pool_market,
                 &depositor,
                 &income_pool_market,
                 &sol.income_pool_token_account,
                 &sol.collateral_pools[i].token_mint,
                 &sol.mint,
-                &registry_markets.money_markets[i],
-                integrations::withdraw_accounts(&registry_markets.money_markets[i], &pubkeys),
+                &registry_markets.markets[i].money_market,
+                integrations::withdraw_accounts(
+                    &registry_markets.markets[i].money_market,
+                    &pubkeys,
+                ),
                 everlend_depositor::utils::collateral_pool_withdraw_accounts(
+                    &registry,
                     &collateral_pool_markets[i],
                     &sol.collateral_pools[i].token_mint,
                     &sol.collateral_pools[i].pool_token_account,
@@ -269,6 +276,7 @@ impl<'a> ToolkitCommand<'a> for TestCommand {
                 config,
                 &general_pool_market,
                 &sol.general_pool,
+                &registry,
                 &sol.liquidity_token_account,
                 &sol.collateral_token_account,
                 &sol.general_pool_token_account,
@@ -286,6 +294,7 @@ impl<'a> ToolkitCommand<'a> for TestCommand {
                 config,
                 &general_pool_market,
                 &sol.general_pool,
+                &registry,
                 &sol.collateral_token_account,
                 &sol.liquidity_token_account,
                 &sol.general_pool_token_account,
@@ -304,6 +313,7 @@ impl<'a> ToolkitCommand<'a> for TestCommand {
                 config,
                 &general_pool_market,
                 &sol.general_pool,
+                &registry,
                 &sol.liquidity_token_account,
                 &sol.general_pool_token_account,
                 &sol.mint,
@@ -1,6 +1,9 @@
 use crate::helpers::{
-    collateral_pool_update_manager, general_pool_update_manager, income_pools_update_manager,
-    registry_update_manager,
+    cancel_oracle_authority_proposal, collateral_pool_cancel_manager_proposal,
+    collateral_pool_update_manager, general_pool_cancel_manager_proposal,
+    general_pool_update_manager, income_pools_cancel_manager_proposal, income_pools_update_manager,
+    registry_cancel_manager_proposal, registry_update_manager,
+    rewards_root_cancel_authority_proposal, rewards_root_update_authority, update_oracle_authority,
 };
 use crate::{
     utils::{arg, arg_keypair, Config},
@@ -12,6 +15,7 @@ use solana_clap_utils::input_parsers::keypair_of;
 const ARG_SOURCE: &str = "source";
 const ARG_TARGET: &str = "target";
 const ARG_PROGRAM: &str = "program";
+const ARG_CANCEL: &str = "cancel";
 
 #[derive(Clone, Copy)]
 pub struct UpdateManagerCommand;
@@ -22,16 +26,22 @@ impl<'a> ToolkitCommand<'a> for UpdateManagerCommand {
     }
 
     fn get_description(&self) -> &'a str {
-        "Update pool manager account"
+        "Propose and accept new manager account or cancel the pending proposal"
     }
 
     fn get_args(&self) -> Vec<Arg<'a, 'a>> {
         vec![
             arg_keypair(ARG_SOURCE, true).help("Old manager keypair"),
-            arg_keypair(ARG_TARGET, true).help("New manager keypair"),
+            arg_keypair(ARG_TARGET, false)
+                .required_unless(ARG_CANCEL)
+                .help("New manager keypair"),
             arg(ARG_PROGRAM, true).help(
-                "Program to update manager: collateral-pool|general-pool|income-pools|registry|ulp",
+                "Program to update manager: collateral-pool|general-pool|income-pools|registry|liquidity-oracle|rewards",
             ),
+            Arg::with_name(ARG_CANCEL)
+                .long(ARG_CANCEL)
+                .takes_value(false)
+                .help("Cancel the pending manager proposal"),
         ]
     }
 
@@ -42,10 +52,56 @@ impl<'a> ToolkitCommand<'a> for UpdateManagerCommand {
     fn handle(&self, config: &Config, arg_matches: Option<&ArgMatches>) -> anyhow::Result<()> {
         let arg_matches = arg_matches.unwrap();
         let source = keypair_of(arg_matches, ARG_SOURCE).unwrap();
-        let target = keypair_of(arg_matches, ARG_TARGET).unwrap();
         let program = arg_matches.value_of(ARG_PROGRAM).unwrap();
         let initialized_accounts = config.get_initialized_accounts();
 
+        if arg_matches.is_present(ARG_CANCEL) {
+            match program {
+                "collateral-pool" => {
+                    for p in initialized_accounts.collateral_pool_markets.iter() {
+                        println!(
+                            "Cancel collateral pool manager proposal: Pool market: {}",
+                            p
+                        );
+                        collateral_pool_cancel_manager_proposal(config, p, &source)?;
+                    }
+                }
+                "general-pool" => general_pool_cancel_manager_proposal(
+                    config,
+                    &initialized_accounts.general_pool_market,
+                    &source,
+                )?,
+                "income-pools" => income_pools_cancel_manager_proposal(
+                    config,
+                    &initialized_accounts.income_pool_market,
+                    &source,
+                )?,
+                "registry" => registry_cancel_manager_proposal(
+                    config,
+                    &initialized_accounts.registry,
+                    &source,
+                )?,
+                "liquidity-oracle" => cancel_oracle_authority_proposal(
+                    config,
+                    initialized_accounts.liquidity_oracle,
+                    source,
+                )?,
+                "rewards" => rewards_root_cancel_authority_proposal(
+                    config,
+                    &initialized_accounts.rewards_root,
+                    &source,
+                )?,
+                _ => {
+                    return Err(anyhow::anyhow!("wrong program"));
+                }
+            }
+            println!("Program {:?}", program);
+
+            return Ok(());
+        }
+
+        let target = keypair_of(arg_matches, ARG_TARGET).unwrap();
+
         match program {
             "collateral-pool" => {
                 for p in initialized_accounts.collateral_pool_markets.iter() {
@@ -84,6 +140,30 @@ impl<'a> ToolkitCommand<'a> for UpdateManagerCommand {
                 );
                 registry_update_manager(config, &initialized_accounts.registry, &source, &target)?;
             }
+            "liquidity-oracle" => {
+                println!(
+                    "Updating liquidity oracle authority: Oracle {}",
+                    initialized_accounts.liquidity_oracle
+                );
+                update_oracle_authority(
+                    config,
+                    initialized_accounts.liquidity_oracle,
+                    source,
+                    target,
+                )?;
+            }
+            "rewards" => {
+                println!(
+                    "Updating rewards root authority: Root {}",
+                    initialized_accounts.rewards_root
+                );
+                rewards_root_update_authority(
+                    config,
+                    &initialized_accounts.rewards_root,
+                    &source,
+                    &target,
+                )?;
+            }
             _ => {
                 return Err(anyhow::anyhow!("wrong program"));
             }
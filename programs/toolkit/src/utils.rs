@@ -26,12 +26,11 @@ use crate::accounts_config::{DefaultAccounts, InitializedAccounts};
 
 pub const REFRESH_INCOME_INTERVAL: u64 = 300;
 
-/// Generates fixed distribution from slice
+/// Generates distribution from slice
 #[macro_export]
 macro_rules! distribution {
     ($distribuition:expr) => {{
-        let mut new_distribuition = DistributionArray::default();
-        new_distribuition[..$distribuition.len()].copy_from_slice(&$distribuition);
+        let new_distribuition: DistributionArray = $distribuition.to_vec();
         new_distribuition
     }};
 }
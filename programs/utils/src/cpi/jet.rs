@@ -1,3 +1,4 @@
+use crate::integrations::ReserveRates;
 use borsh::{BorshDeserialize, BorshSerialize};
 use jet_proto_math::Number;
 use solana_program::account_info::AccountInfo;
@@ -212,3 +213,30 @@ pub fn get_real_liquidity_amount(
 
     Ok(mp.convert_amount(collateral_amount))
 }
+
+pub fn get_reserve_rates(margin_pool: AccountInfo) -> Result<ReserveRates, ProgramError> {
+    let mp = MarginPool::try_from_slice(*margin_pool.data.borrow())?;
+
+    Ok(ReserveRates {
+        liquidity_mint: mp.token_mint,
+        collateral_mint: mp.deposit_note_mint,
+        available_amount: mp.deposit_tokens,
+        borrowed_amount: Number::from_bits(mp.borrowed_tokens).as_u64(0),
+        borrow_rate_curve: vec![
+            (0, ReserveRates::from_bps(mp.config.borrow_rate_0)),
+            (
+                ReserveRates::from_bps(mp.config.utilization_rate_1),
+                ReserveRates::from_bps(mp.config.borrow_rate_1),
+            ),
+            (
+                ReserveRates::from_bps(mp.config.utilization_rate_2),
+                ReserveRates::from_bps(mp.config.borrow_rate_2),
+            ),
+            (
+                ReserveRates::from_bps(10_000),
+                ReserveRates::from_bps(mp.config.borrow_rate_3),
+            ),
+        ],
+        fee_rate: ReserveRates::from_bps(mp.config.management_fee_rate),
+    })
+}
@@ -1,3 +1,4 @@
+use crate::integrations::ReserveRates;
 use solana_program::program_pack::Pack;
 use solana_program::{
     account_info::AccountInfo,
@@ -127,3 +128,29 @@ pub fn get_real_liquidity_amount(
 
     reserve.redeem_collateral(collateral_amount)
 }
+
+pub fn get_reserve_rates(reserve: AccountInfo) -> Result<ReserveRates, ProgramError> {
+    let reserve = solend_program::state::Reserve::unpack(&reserve.data.borrow())?;
+
+    Ok(ReserveRates {
+        liquidity_mint: reserve.liquidity.mint_pubkey,
+        collateral_mint: reserve.collateral.mint_pubkey,
+        available_amount: reserve.liquidity.available_amount,
+        borrowed_amount: reserve.liquidity.borrowed_amount_wads.try_round_u64()?,
+        borrow_rate_curve: vec![
+            (
+                0,
+                ReserveRates::from_percent(reserve.config.min_borrow_rate),
+            ),
+            (
+                ReserveRates::from_percent(reserve.config.optimal_utilization_rate),
+                ReserveRates::from_percent(reserve.config.optimal_borrow_rate),
+            ),
+            (
+                ReserveRates::from_percent(100),
+                ReserveRates::from_percent(reserve.config.max_borrow_rate),
+            ),
+        ],
+        fee_rate: ReserveRates::from_percent(reserve.config.protocol_take_rate),
+    })
+}
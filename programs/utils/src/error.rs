@@ -24,7 +24,7 @@ pub enum EverlendError {
     #[error("Data type mismatch")]
     DataTypeMismatch,
 
-    /// Amount allowed of interest on the borrowing is exceeded
+    /// Deprecated, borrow limit breaches fail with `BorrowShareAllowedExceeded`
     #[error("Amount allowed of interest on the borrowing is exceeded")]
     AmountAllowedCheckFailed,
 
@@ -107,7 +107,7 @@ pub enum EverlendError {
     ReserveThreshold,
 
     /// Reserve rates not updated
-    #[error("Reserve rates have not been updated within this slot")]
+    #[error("Reserve rates have not been updated within the staleness window")]
     ReserveRatesStale,
 
     /// Collateral leak
@@ -131,6 +131,110 @@ pub enum EverlendError {
     /// Check for liquidity amount in rebalance
     #[error("Rebalancing: liquidity check failed")]
     RebalanceLiquidityCheckFailed,
+
+    /// Registry
+
+    /// Money market is not active
+    #[error("Registry: money market is not active")]
+    MoneyMarketNotActive,
+
+    /// Money market list is full
+    #[error("Registry: money market list is full")]
+    MoneyMarketsLimitExceeded,
+
+    /// Liquidity distribution exceeds market limits
+    #[error("Rebalancing: liquidity distribution clipped by market limits")]
+    LiquidityDistributionClipped,
+
+    /// Liquidity oracle
+
+    /// Oracle signer is not allowed
+    #[error("Liquidity oracle: signer is not allowed")]
+    OracleSignerNotAllowed,
+
+    /// Distribution is updated by oracle signers quorum
+    #[error("Liquidity oracle: distribution is updated by signers quorum")]
+    OracleQuorumEnabled,
+
+    /// Token oracle is frozen
+    #[error("Liquidity oracle: token oracle is frozen")]
+    TokenOracleFrozen,
+
+    /// Distribution update interval is not passed
+    #[error("Liquidity oracle: distribution updated too early")]
+    LiquidityDistributionUpdateTooEarly,
+
+    /// Distribution change exceeds the limit
+    #[error("Liquidity oracle: distribution change exceeds the limit")]
+    LiquidityDistributionChangeExceeded,
+
+    /// Oracle reserve rate deviates from money market exchange rate
+    #[error("Rebalancing: reserve rate deviates from money market exchange rate")]
+    ReserveRateDeviationExceeded,
+
+    /// Mining share is locked
+    #[error("Rewards: Share is locked")]
+    RewardsShareLocked,
+
+    /// Amount borrowed exceeds share allowed of the pool
+    #[error("Borrow: amount borrowed exceeds share allowed of the pool liquidity")]
+    BorrowShareAllowedExceeded,
+
+    /// Withdraw requests queue has no requests to process
+    #[error("Withdraw requests: Queue is empty")]
+    WithdrawRequestsQueueEmpty,
+
+    /// Pool has no liquidity to fulfil withdraw request
+    #[error("Withdraw requests: Insufficient liquidity")]
+    WithdrawRequestsInsufficientLiquidity,
+
+    /// Pool idle liquidity doesn't cover instant withdraw
+    #[error("Instant withdraw: Insufficient idle liquidity")]
+    InstantWithdrawInsufficientLiquidity,
+
+    /// Deposit exceeds the pool deposit cap
+    #[error("Deposit: pool deposit cap exceeded")]
+    PoolDepositCapExceeded,
+
+    /// Deposit exceeds the user deposit cap
+    #[error("Deposit: user deposit cap exceeded")]
+    UserDepositCapExceeded,
+
+    /// Operation is paused by the registry guardian
+    #[error("Pause: operation is paused")]
+    OperationPaused,
+
+    /// Timelock action exceeds the size limits or has a disallowed signer
+    #[error("Timelock: invalid action")]
+    InvalidTimelockAction,
+
+    /// Timelock action execution slot is not reached yet
+    #[error("Timelock: action is not ready")]
+    TimelockActionNotReady,
+
+    /// Safety fund drawdown exceeds the limit of a single drawdown
+    #[error("Safety fund: drawdown limit exceeded")]
+    SafetyFundDrawdownLimitExceeded,
+
+    /// Money market reserve doesn't match the market or the liquidity mint
+    #[error("Yield distribution: invalid money market reserve")]
+    InvalidMoneyMarketReserve,
+
+    /// Oracle signers update delay is not passed
+    #[error("Liquidity oracle: signers update is delayed")]
+    OracleSignersUpdateDelayed,
+
+    /// Total share allowed of the pool borrow authorities exceeds the whole pool
+    #[error("Borrow: total share allowed of the pool borrow authorities exceeds the pool")]
+    TotalShareAllowedExceeded,
+
+    /// Role is rejected while the registry timelock is set
+    #[error("Timelock: role actions go through the timelock")]
+    TimelockedRole,
+
+    /// Reward pool can't track more lock expirations
+    #[error("Rewards: Lock expirations limit exceeded")]
+    RewardsLockExpirationsLimit,
 }
 
 impl PrintProgramError for EverlendError {
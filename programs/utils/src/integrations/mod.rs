@@ -1,3 +1,4 @@
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use num_enum::{FromPrimitive, IntoPrimitive};
 use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
 
@@ -5,16 +6,29 @@ pub mod frakt;
 pub mod francium;
 pub mod jet;
 pub mod larix;
+mod reserve_rates;
 pub mod solend;
 pub mod spl_token_lending;
 pub mod tulip;
 
+pub use reserve_rates::*;
+
 // Program IDs
 pub const SPL_TOKEN_LENDING_PROGRAM_ID: &str = "Bp1MJ1qr4g8t9AQJjm5H6zDB2NmRrkJL8H8zuvb1g7oV";
 // pub const PORT_FINANCE_PROGRAM_ID: &str = "pdQ2rQQU5zH2rDgZ7xH2azMBJegUzUyunJ5Jd637hC4";
 // pub const LARIX_PROGRAM_ID: &str = "BDBsJpBPWtMfTgxejekYCWUAJu1mvQshiwrKuTjdEeT3";
 
-#[derive(Debug, IntoPrimitive, FromPrimitive, Clone, Copy)]
+#[derive(
+    Debug,
+    IntoPrimitive,
+    FromPrimitive,
+    Clone,
+    Copy,
+    PartialEq,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+)]
 #[repr(usize)]
 pub enum MoneyMarket {
     #[num_enum(default)]
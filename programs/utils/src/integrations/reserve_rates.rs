@@ -0,0 +1,106 @@
+use crate::{EverlendError, PRECISION_SCALER};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+/// Liquidity and borrow rate curve of a money market reserve
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReserveRates {
+    /// Reserve liquidity mint
+    pub liquidity_mint: Pubkey,
+    /// Reserve collateral mint
+    pub collateral_mint: Pubkey,
+    /// Liquidity available for borrowing
+    pub available_amount: u64,
+    /// Borrowed liquidity
+    pub borrowed_amount: u64,
+    /// Points (utilization rate, borrow rate) of the piecewise linear borrow rate curve
+    /// ascending by utilization rate (PRECISION_SCALER based)
+    pub borrow_rate_curve: Vec<(u64, u64)>,
+    /// Share of the interest kept by the money market (PRECISION_SCALER based)
+    pub fee_rate: u64,
+}
+
+impl ReserveRates {
+    /// Convert percent rate to PRECISION_SCALER based rate
+    pub fn from_percent(rate: u8) -> u64 {
+        (rate as u128 * PRECISION_SCALER / 100) as u64
+    }
+
+    /// Convert basis points rate to PRECISION_SCALER based rate
+    pub fn from_bps(rate: u16) -> u64 {
+        (rate as u128 * PRECISION_SCALER / 10_000) as u64
+    }
+
+    /// Exclude liquidity we have already supplied to the reserve
+    pub fn exclude_supplied(&mut self, supplied_amount: u64) {
+        self.available_amount = self.available_amount.saturating_sub(supplied_amount);
+    }
+
+    /// Utilization rate after supplying the amount
+    pub fn utilization_rate(&self, supply_amount: u64) -> Result<u64, ProgramError> {
+        let total_amount = (self.available_amount as u128)
+            .checked_add(self.borrowed_amount as u128)
+            .and_then(|amount| amount.checked_add(supply_amount as u128))
+            .ok_or(EverlendError::MathOverflow)?;
+        if total_amount == 0 {
+            return Ok(0);
+        }
+
+        let utilization_rate = (self.borrowed_amount as u128)
+            .checked_mul(PRECISION_SCALER)
+            .ok_or(EverlendError::MathOverflow)?
+            / total_amount;
+
+        Ok(utilization_rate as u64)
+    }
+
+    /// Borrow rate interpolated by the curve
+    pub fn borrow_rate(&self, utilization_rate: u64) -> u64 {
+        let mut prev = match self.borrow_rate_curve.first() {
+            Some(point) => *point,
+            None => return 0,
+        };
+
+        for &(point_utilization, point_rate) in self.borrow_rate_curve.iter().skip(1) {
+            if utilization_rate <= point_utilization {
+                let (prev_utilization, prev_rate) = prev;
+                if point_utilization <= prev_utilization {
+                    return point_rate;
+                }
+
+                // Linear interpolation between the points
+                let progress = (utilization_rate.saturating_sub(prev_utilization) as i128)
+                    * (point_rate as i128 - prev_rate as i128)
+                    / (point_utilization - prev_utilization) as i128;
+
+                return (prev_rate as i128 + progress) as u64;
+            }
+            prev = (point_utilization, point_rate);
+        }
+
+        prev.1
+    }
+
+    /// Supply rate after supplying the amount
+    pub fn supply_rate(&self, supply_amount: u64) -> Result<u64, ProgramError> {
+        let utilization_rate = self.utilization_rate(supply_amount)?;
+        let borrow_rate = self.borrow_rate(utilization_rate);
+
+        let supply_rate = (borrow_rate as u128)
+            .checked_mul(utilization_rate as u128)
+            .and_then(|rate| {
+                rate.checked_mul(PRECISION_SCALER.saturating_sub(self.fee_rate as u128))
+            })
+            .ok_or(EverlendError::MathOverflow)?
+            / PRECISION_SCALER
+            / PRECISION_SCALER;
+
+        Ok(supply_rate as u64)
+    }
+
+    /// Interest earned per period by the supplied amount (PRECISION_SCALER based)
+    pub fn supply_interest(&self, supply_amount: u64) -> Result<u128, ProgramError> {
+        Ok((supply_amount as u128)
+            .checked_mul(self.supply_rate(supply_amount)? as u128)
+            .ok_or(EverlendError::MathOverflow)?)
+    }
+}